@@ -4,15 +4,23 @@
  * SPDX-License-Identifier: MIT
  */
 
+use serde::{Deserialize, Serialize};
 use web_sys::CanvasRenderingContext2d;
 
 use crate::consts::BRICK_HEIGHT;
 
-#[derive(Clone)]
+/// Color that is used for the bricks and platform position of a stride
+pub(crate) fn stride_color(stride: usize) -> String {
+    format!("hsl({}, 70%, 30%)", 240 - (stride as i32 * 37 % 240))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub(crate) struct Brick {
     x: f64,
     y: f64,
     width: f64,
+    #[serde(default)]
+    lintel: bool,
     build_stride: Option<usize>,
 }
 
@@ -22,6 +30,17 @@ impl Brick {
             x,
             y,
             width,
+            lintel: false,
+            build_stride: None,
+        }
+    }
+
+    pub(crate) const fn new_lintel(x: f64, y: f64, width: f64) -> Self {
+        Brick {
+            x,
+            y,
+            width,
+            lintel: true,
             build_stride: None,
         }
     }
@@ -38,6 +57,10 @@ impl Brick {
         self.width
     }
 
+    pub(crate) const fn is_lintel(&self) -> bool {
+        self.lintel
+    }
+
     pub(crate) const fn is_build(&self) -> bool {
         self.build_stride.is_some()
     }
@@ -46,15 +69,25 @@ impl Brick {
         self.build_stride = Some(stride);
     }
 
+    pub(crate) const fn unbuild(&mut self) {
+        self.build_stride = None;
+    }
+
     pub(crate) fn draw(&self, context: &CanvasRenderingContext2d) {
         // Draw  brick
         if let Some(stride) = self.build_stride {
-            let color = format!("hsl({}, 70%, 30%)", 240 - (stride as i32 * 37 % 240));
-            context.set_fill_style_str(&color);
+            context.set_fill_style_str(&stride_color(stride));
+        } else if self.lintel {
+            context.set_fill_style_str("#ccc");
         } else {
             context.set_fill_style_str("#faa");
         }
         context.fill_rect(self.x, self.y, self.width, BRICK_HEIGHT);
+        if self.lintel {
+            context.set_stroke_style_str("#333");
+            context.set_line_width(4.0);
+            context.stroke_rect(self.x, self.y, self.width, BRICK_HEIGHT);
+        }
 
         // Draw build stride number
         if let Some(stride) = self.build_stride {
@@ -65,11 +98,13 @@ impl Brick {
             context.set_text_align("center");
             context.set_text_baseline("middle");
             context.set_fill_style_str("#fff");
+            // Squeeze the number so it stays inside narrow cut bricks at the wall edges
             context
-                .fill_text(
+                .fill_text_with_max_width(
                     &stride.to_string(),
                     self.x + self.width / 2.0,
                     self.y + BRICK_HEIGHT / 2.0,
+                    self.width * 0.9,
                 )
                 .unwrap();
         }
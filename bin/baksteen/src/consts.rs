@@ -11,6 +11,9 @@ pub(crate) const BRICK_HALF_WIDTH: f64 = 100.0;
 pub(crate) const BRICK_WIDTH: f64 = BRICK_HALF_WIDTH * 2.0 + BRICK_BED_JOINT;
 pub(crate) const BRICK_TWO_THIRDS_WIDTH: f64 = BRICK_HALF_WIDTH * 1.5 + BRICK_BED_JOINT / 2.0;
 pub(crate) const BRICK_HEIGHT: f64 = 50.0;
+pub(crate) const BRICK_MIN_CUT_WIDTH: f64 = 25.0; // Smaller cut pieces are filled with mortar
+
+pub(crate) const LINTEL_BEARING: f64 = 100.0; // Lintel length on both sides of an opening
 
 pub(crate) const STRIDE_WIDTH: f64 = 800.0; // Robot's horizontal reach
 pub(crate) const STRIDE_HEIGHT: f64 = 1300.0; // Robot's vertical reach
@@ -0,0 +1,196 @@
+/*
+ * Copyright (c) 2025 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::fmt::Write;
+
+use crate::consts::{BRICK_HALF_WIDTH, BRICK_HEIGHT, BRICK_WIDTH};
+use crate::wall::Wall;
+
+const MARGIN: f64 = 250.0;
+const TABLE_WIDTH: f64 = 700.0;
+const FONT_SIZE: f64 = 28.0;
+const LINE_HEIGHT: f64 = FONT_SIZE * 1.5;
+
+/// Draw a dimension line with end ticks and a centered label
+fn dimension(svg: &mut String, x1: f64, y1: f64, x2: f64, y2: f64, label: &str) {
+    let vertical = x1 == x2;
+    let (tick_x, tick_y) = if vertical { (15.0, 0.0) } else { (0.0, 15.0) };
+    _ = writeln!(
+        svg,
+        r#"<path d="M{x1} {y1}L{x2} {y2}M{} {}L{} {}M{} {}L{} {}" stroke="black" stroke-width="2"/>"#,
+        x1 - tick_x,
+        y1 - tick_y,
+        x1 + tick_x,
+        y1 + tick_y,
+        x2 - tick_x,
+        y2 - tick_y,
+        x2 + tick_x,
+        y2 + tick_y
+    );
+    let (x, y) = ((x1 + x2) / 2.0, (y1 + y2) / 2.0);
+    let transform = if vertical {
+        format!(r#" transform="rotate(-90 {x} {y})""#)
+    } else {
+        String::new()
+    };
+    _ = writeln!(
+        svg,
+        r#"<text x="{x}" y="{}" text-anchor="middle"{transform}>{label}</text>"#,
+        y - 10.0
+    );
+}
+
+/// Export the wall as a printable SVG drawing with dimensions, course heights, brick counts per
+/// course and a materials list
+pub(crate) fn wall_svg(wall: &Wall) -> String {
+    let (width, height) = (wall.width(), wall.height());
+
+    // Group the bricks per course from the bottom up
+    let mut courses: Vec<(f64, usize)> = Vec::new();
+    for brick in wall.bricks() {
+        match courses.iter_mut().find(|(y, _)| *y == brick.y()) {
+            Some((_, count)) => *count += 1,
+            None => courses.push((brick.y(), 1)),
+        }
+    }
+    courses.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    // Count the materials
+    let lintels = wall.bricks().iter().filter(|brick| brick.is_lintel());
+    let lintel_count = lintels.clone().count();
+    let lintel_length = lintels.map(|brick| brick.width()).sum::<f64>();
+    let stretchers = wall
+        .bricks()
+        .iter()
+        .filter(|brick| !brick.is_lintel() && brick.width() == BRICK_WIDTH)
+        .count();
+    let headers = wall
+        .bricks()
+        .iter()
+        .filter(|brick| !brick.is_lintel() && brick.width() == BRICK_HALF_WIDTH)
+        .count();
+    let cut = wall.bricks().len() - lintel_count - stretchers - headers;
+    let opening_area = wall
+        .openings()
+        .iter()
+        .map(|opening| opening.width() * opening.height())
+        .sum::<f64>();
+    let materials = [
+        format!("Wall: {width} x {height} mm, {} bond", wall.bond().as_str()),
+        format!(
+            "Brickwork area: {:.2} m²",
+            (width * height - opening_area) / 1_000_000.0
+        ),
+        format!("Courses: {}", courses.len()),
+        format!("Full bricks (stretchers): {stretchers}"),
+        format!("Full bricks (headers): {headers}"),
+        format!("Cut bricks: {cut}"),
+        format!("Lintels: {lintel_count} ({lintel_length} mm total)"),
+        format!("Total bricks: {}", wall.bricks().len() - lintel_count),
+    ];
+
+    let view_width = MARGIN + width + MARGIN / 2.0 + TABLE_WIDTH;
+    let view_height = MARGIN / 2.0 + height + MARGIN + (materials.len() + 1) as f64 * LINE_HEIGHT;
+    let mut svg = String::new();
+    _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {view_width} {view_height}" font-family="sans-serif" font-size="{FONT_SIZE}">"#,
+        -MARGIN,
+        -MARGIN / 2.0
+    );
+    _ = writeln!(
+        svg,
+        r#"<rect x="{}" y="{}" width="{view_width}" height="{view_height}" fill="white"/>"#,
+        -MARGIN,
+        -MARGIN / 2.0
+    );
+
+    // Bricks and openings
+    for brick in wall.bricks() {
+        _ = writeln!(
+            svg,
+            r##"<rect x="{}" y="{}" width="{}" height="{BRICK_HEIGHT}" fill="{}" stroke="#333" stroke-width="1"/>"##,
+            brick.x(),
+            brick.y(),
+            brick.width(),
+            if brick.is_lintel() { "#ccc" } else { "#e8b4a0" }
+        );
+    }
+    for opening in wall.openings() {
+        let top = opening.top(height);
+        _ = writeln!(
+            svg,
+            r##"<rect x="{}" y="{top}" width="{}" height="{}" fill="none" stroke="#888" stroke-width="2" stroke-dasharray="20 10"/>"##,
+            opening.x(),
+            opening.width(),
+            opening.height()
+        );
+        dimension(
+            &mut svg,
+            opening.x(),
+            top + opening.height() / 2.0,
+            opening.x() + opening.width(),
+            top + opening.height() / 2.0,
+            &format!("{}", opening.width()),
+        );
+        dimension(
+            &mut svg,
+            opening.x() + opening.width() / 2.0,
+            top + opening.height(),
+            opening.x() + opening.width() / 2.0,
+            top,
+            &format!("{}", opening.height()),
+        );
+    }
+    _ = writeln!(
+        svg,
+        r#"<rect x="0" y="0" width="{width}" height="{height}" fill="none" stroke="black" stroke-width="4"/>"#
+    );
+
+    // Overall dimensions
+    dimension(
+        &mut svg,
+        0.0,
+        height + MARGIN / 3.0,
+        width,
+        height + MARGIN / 3.0,
+        &format!("{width}"),
+    );
+    dimension(
+        &mut svg,
+        -MARGIN / 3.0,
+        height,
+        -MARGIN / 3.0,
+        0.0,
+        &format!("{height}"),
+    );
+
+    // Course table with the height of each course from the ground
+    let table_x = width + MARGIN / 2.0;
+    for (index, (y, count)) in courses.iter().enumerate() {
+        _ = writeln!(
+            svg,
+            r#"<text x="{table_x}" y="{}" font-size="{}" dominant-baseline="middle">Course {}: {} mm, {count} bricks</text>"#,
+            y + BRICK_HEIGHT / 2.0,
+            (BRICK_HEIGHT * 0.7).min(FONT_SIZE),
+            index + 1,
+            height - y - BRICK_HEIGHT
+        );
+    }
+
+    // Materials list
+    let mut y = height + MARGIN;
+    _ = writeln!(
+        svg,
+        r#"<text x="0" y="{y}" font-weight="bold">Materials</text>"#
+    );
+    for line in &materials {
+        y += LINE_HEIGHT;
+        _ = writeln!(svg, r#"<text x="0" y="{y}">{line}</text>"#);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
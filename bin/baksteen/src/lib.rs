@@ -10,163 +10,438 @@
 use std::sync::RwLock;
 
 use wasm_bindgen::prelude::*;
-use web_sys::{CanvasRenderingContext2d, Event, HtmlCanvasElement, HtmlFormElement, KeyboardEvent};
+use web_sys::{
+    Blob, BlobPropertyBag, CanvasRenderingContext2d, Event, FileReader, HtmlAnchorElement,
+    HtmlButtonElement, HtmlCanvasElement, HtmlElement, HtmlFormElement, HtmlInputElement,
+    HtmlSelectElement, KeyboardEvent, MouseEvent, Url, WheelEvent,
+};
 
-use crate::wall::{BondType, Wall};
+use crate::consts::{STRIDE_HEIGHT, STRIDE_WIDTH};
+use crate::view::View;
+use crate::wall::{BondType, BuildMode, Opening, Wall};
 
 mod brick;
 mod consts;
+mod drawing;
+mod planner;
+mod view;
 mod wall;
 
-// Global wall state
+// Global wall and view state
 static WALL: RwLock<Option<Wall>> = RwLock::new(None);
+static VIEW: RwLock<View> = RwLock::new(View::new());
 
-fn wall_init(
-    context: &CanvasRenderingContext2d,
-    wall_width_input: &web_sys::HtmlInputElement,
-    wall_height_input: &web_sys::HtmlInputElement,
-    wall_bond_select: &web_sys::HtmlSelectElement,
-) {
+const WALL_STORAGE_KEY: &str = "baksteen-wall";
+
+#[derive(Clone)]
+struct Elements {
+    context: CanvasRenderingContext2d,
+    wall_width_input: HtmlInputElement,
+    wall_height_input: HtmlInputElement,
+    wall_bond_select: HtmlSelectElement,
+    wall_custom_bond: HtmlElement,
+    wall_custom_bond_input: HtmlInputElement,
+    wall_openings_input: HtmlInputElement,
+    robot_reach_width_input: HtmlInputElement,
+    robot_reach_height_input: HtmlInputElement,
+    build_mode_select: HtmlSelectElement,
+    stats: HtmlElement,
+    undo_button: HtmlButtonElement,
+    redo_button: HtmlButtonElement,
+}
+
+fn get_element<T: JsCast>(id: &str) -> Result<T, JsValue> {
+    Ok(web_sys::window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .get_element_by_id(id)
+        .unwrap()
+        .dyn_into::<T>()?)
+}
+
+fn wall_update(elements: &Elements, wall: &Wall) {
+    wall.draw(&elements.context, &VIEW.read().unwrap());
+    elements.undo_button.set_disabled(!wall.can_undo());
+    elements.redo_button.set_disabled(!wall.can_redo());
+
+    let stats = wall.stats();
+    elements.stats.set_text_content(Some(&format!(
+        "Current stride: {}, platform moves: {} optimized vs {} naive",
+        wall.current_stride(),
+        stats.optimized_moves,
+        stats.naive_moves
+    )));
+
+    // Save the wall after every change so the session can be resumed after a reload
+    if let Ok(Some(storage)) = web_sys::window().unwrap().local_storage() {
+        _ = storage.set_item(WALL_STORAGE_KEY, &wall.to_json());
+    }
+}
+
+fn wall_set(elements: &Elements, wall: Wall) {
+    elements
+        .wall_width_input
+        .set_value(&wall.width().to_string());
+    elements
+        .wall_height_input
+        .set_value(&wall.height().to_string());
+    elements.wall_bond_select.set_value(wall.bond().as_str());
+    elements
+        .wall_custom_bond_input
+        .set_value(&wall.bond().custom_pattern());
+    wall_custom_bond_update(elements);
+    elements
+        .wall_openings_input
+        .set_value(&Opening::format_list(wall.openings()));
+    elements
+        .robot_reach_width_input
+        .set_value(&wall.reach_width().to_string());
+    elements
+        .robot_reach_height_input
+        .set_value(&wall.reach_height().to_string());
+    elements.build_mode_select.set_value(wall.mode().as_str());
+    wall_update(elements, &wall);
+    *WALL.write().unwrap() = Some(wall);
+}
+
+fn wall_custom_bond_update(elements: &Elements) {
+    elements
+        .wall_custom_bond
+        .set_hidden(elements.wall_bond_select.value() != "custom");
+}
+
+fn wall_init(elements: &Elements) {
+    let bond = match elements.wall_bond_select.value().as_str() {
+        "custom" => BondType::parse_custom(&elements.wall_custom_bond_input.value()),
+        bond => bond.parse::<BondType>(),
+    };
+    let Ok(bond) = bond else {
+        _ = web_sys::window()
+            .unwrap()
+            .alert_with_message("Invalid custom bond, use course offsets in mm like: 0, 105");
+        return;
+    };
+    let Ok(openings) = Opening::parse_list(&elements.wall_openings_input.value()) else {
+        _ = web_sys::window().unwrap().alert_with_message(
+            "Invalid openings, use x, bottom, width and height in mm like: 400 0 900 2100; 1600 900 600 600",
+        );
+        return;
+    };
     let wall = Wall::new(
-        wall_width_input.value().parse().unwrap(),
-        wall_height_input.value().parse().unwrap(),
-        wall_bond_select.value().parse::<BondType>().unwrap(),
+        elements.wall_width_input.value().parse().unwrap(),
+        elements.wall_height_input.value().parse().unwrap(),
+        bond,
+        openings,
+        elements
+            .robot_reach_width_input
+            .value()
+            .parse()
+            .unwrap_or(STRIDE_WIDTH),
+        elements
+            .robot_reach_height_input
+            .value()
+            .parse()
+            .unwrap_or(STRIDE_HEIGHT),
+        elements
+            .build_mode_select
+            .value()
+            .parse()
+            .unwrap_or(BuildMode::Naive),
     );
-    wall.draw(context);
-    *WALL.write().unwrap() = Some(wall);
+    wall_set(elements, wall);
 }
 
-fn wall_next_brick(context: &CanvasRenderingContext2d) {
-    if let Some(ref mut wall) = *WALL.write().unwrap() {
-        wall.next_brick();
-        wall.draw(context);
+fn wall_action(elements: &Elements, action: impl FnOnce(&mut Wall) -> bool) {
+    if let Some(ref mut wall) = *WALL.write().unwrap()
+        && action(wall)
+    {
+        wall_update(elements, wall);
     }
 }
 
+fn wall_redraw(elements: &Elements) {
+    if let Some(ref wall) = *WALL.read().unwrap() {
+        wall.draw(&elements.context, &VIEW.read().unwrap());
+    }
+}
+
+/// Download a file via a temporary object URL
+fn download(file_name: &str, mime_type: &str, contents: &str) -> Result<(), JsValue> {
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_str_sequence_and_options(
+        &js_sys::Array::of1(&JsValue::from_str(contents)),
+        &options,
+    )?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+    let link = web_sys::window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .create_element("a")?
+        .dyn_into::<HtmlAnchorElement>()?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+    Url::revoke_object_url(&url)?;
+    Ok(())
+}
+
+fn wall_export() -> Result<(), JsValue> {
+    let Some(ref wall) = *WALL.read().unwrap() else {
+        return Ok(());
+    };
+    download("wall.json", "application/json", &wall.to_json())
+}
+
+fn wall_export_drawing() -> Result<(), JsValue> {
+    let Some(ref wall) = *WALL.read().unwrap() else {
+        return Ok(());
+    };
+    download("wall.svg", "image/svg+xml", &drawing::wall_svg(wall))
+}
+
+fn wall_import(elements: &Elements, input: &HtmlInputElement) -> Result<(), JsValue> {
+    let Some(file) = input.files().and_then(|files| files.get(0)) else {
+        return Ok(());
+    };
+    input.set_value("");
+
+    let reader = FileReader::new()?;
+    let elements = elements.clone();
+    let reader_clone = reader.clone();
+    let closure = Closure::once(Box::new(move |_: Event| {
+        let json = reader_clone
+            .result()
+            .unwrap()
+            .as_string()
+            .unwrap_or_default();
+        match Wall::from_json(&json) {
+            Ok(wall) => wall_set(&elements, wall),
+            Err(err) => {
+                _ = web_sys::window()
+                    .unwrap()
+                    .alert_with_message(&format!("Can't import wall: {err}"));
+            }
+        }
+    }) as Box<dyn FnOnce(_)>);
+    reader.set_onload(Some(closure.as_ref().unchecked_ref()));
+    closure.forget();
+    reader.read_as_text(&file)
+}
+
 /// Main
 #[wasm_bindgen]
 pub fn main() -> Result<(), JsValue> {
     // MARK: Elements
     let window = web_sys::window().unwrap();
-    let document = window.document().unwrap();
 
-    // Canvas context
-    let canvas = document
-        .get_element_by_id("canvas")
-        .unwrap()
-        .dyn_into::<HtmlCanvasElement>()?;
+    // Canvas context, the canvas resolution follows the device pixel ratio for crisp drawings
+    let canvas = get_element::<HtmlCanvasElement>("canvas")?;
+    let pixel_ratio = window.device_pixel_ratio();
+    canvas.set_width((canvas.client_width() as f64 * pixel_ratio) as u32);
+    canvas.set_height((canvas.client_height() as f64 * pixel_ratio) as u32);
     let context = canvas
         .get_context("2d")?
         .unwrap()
         .dyn_into::<CanvasRenderingContext2d>()?;
 
     // Wall form
-    let wall_form = document
-        .get_element_by_id("wall-form")
-        .unwrap()
-        .dyn_into::<HtmlFormElement>()?;
-    let wall_width_input = document
-        .get_element_by_id("wall-width")
-        .unwrap()
-        .dyn_into::<web_sys::HtmlInputElement>()?;
-    let wall_height_input = document
-        .get_element_by_id("wall-height")
-        .unwrap()
-        .dyn_into::<web_sys::HtmlInputElement>()?;
-    let wall_bond_select = document
-        .get_element_by_id("wall-bond")
-        .unwrap()
-        .dyn_into::<web_sys::HtmlSelectElement>()?;
+    let wall_form = get_element::<HtmlFormElement>("wall-form")?;
+    let elements = Elements {
+        context,
+        wall_width_input: get_element("wall-width")?,
+        wall_height_input: get_element("wall-height")?,
+        wall_bond_select: get_element("wall-bond")?,
+        wall_custom_bond: get_element("wall-custom-bond")?,
+        wall_custom_bond_input: get_element("wall-custom-bond-input")?,
+        wall_openings_input: get_element("wall-openings")?,
+        robot_reach_width_input: get_element("robot-reach-width")?,
+        robot_reach_height_input: get_element("robot-reach-height")?,
+        build_mode_select: get_element("build-mode")?,
+        stats: get_element("stats")?,
+        undo_button: get_element("undo-button")?,
+        redo_button: get_element("redo-button")?,
+    };
 
     // Actions
-    let next_brick_button = document
-        .get_element_by_id("next-brick-button")
-        .unwrap()
-        .dyn_into::<web_sys::HtmlButtonElement>()?;
-    let fill_bricks_button = document
-        .get_element_by_id("fill-bricks-button")
-        .unwrap()
-        .dyn_into::<web_sys::HtmlButtonElement>()?;
+    let next_brick_button = get_element::<HtmlButtonElement>("next-brick-button")?;
+    let fill_bricks_button = get_element::<HtmlButtonElement>("fill-bricks-button")?;
+    let reset_view_button = get_element::<HtmlButtonElement>("reset-view-button")?;
+    let export_button = get_element::<HtmlButtonElement>("export-button")?;
+    let export_drawing_button = get_element::<HtmlButtonElement>("export-drawing-button")?;
+    let import_input = get_element::<HtmlInputElement>("import-input")?;
 
     // MARK: Init wall
     {
-        let wall_width_input = wall_width_input.clone();
-        let wall_height_input = wall_height_input.clone();
-        let wall_bond_select = wall_bond_select.clone();
-        let context = context.clone();
+        let elements = elements.clone();
         let closure = Closure::wrap(Box::new(move |event: Event| {
             event.prevent_default();
-            wall_init(
-                &context,
-                &wall_width_input,
-                &wall_height_input,
-                &wall_bond_select,
-            );
+            wall_init(&elements);
         }) as Box<dyn FnMut(_)>);
         wall_form.add_event_listener_with_callback("submit", closure.as_ref().unchecked_ref())?;
         closure.forget();
     }
     {
-        let wall_width_input = wall_width_input.clone();
-        let wall_height_input = wall_height_input.clone();
-        let wall_bond_select = wall_bond_select.clone();
-        let context = context.clone();
-        let closure = Closure::wrap(Box::new(move |event: Event| {
-            event.prevent_default();
-            wall_init(
-                &context,
-                &wall_width_input,
-                &wall_height_input,
-                &wall_bond_select,
-            );
+        let wall_bond_select = elements.wall_bond_select.clone();
+        let elements = elements.clone();
+        let closure = Closure::wrap(Box::new(move |_: Event| {
+            wall_custom_bond_update(&elements);
         }) as Box<dyn FnMut(_)>);
-        wall_form.add_event_listener_with_callback("submit", closure.as_ref().unchecked_ref())?;
+        wall_bond_select
+            .add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())?;
         closure.forget();
     }
-    wall_init(
-        &context,
-        &wall_width_input,
-        &wall_height_input,
-        &wall_bond_select,
-    );
+    let saved_wall = window
+        .local_storage()?
+        .and_then(|storage| storage.get_item(WALL_STORAGE_KEY).ok().flatten())
+        .and_then(|json| Wall::from_json(&json).ok());
+    match saved_wall {
+        Some(wall) => wall_set(&elements, wall),
+        None => wall_init(&elements),
+    }
 
     // MARK: Next brick
     {
-        let context = context.clone();
+        let elements = elements.clone();
         let closure = Closure::wrap(Box::new(move |event: Event| {
             event.prevent_default();
-            wall_next_brick(&context);
+            wall_action(&elements, Wall::next_brick);
         }) as Box<dyn FnMut(_)>);
         next_brick_button
             .add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
         closure.forget();
     }
+
+    // MARK: Fill bricks
     {
-        let context = context.clone();
+        let elements = elements.clone();
+        let closure = Closure::wrap(Box::new(move |event: Event| {
+            event.prevent_default();
+            wall_action(&elements, Wall::fill_bricks);
+        }) as Box<dyn FnMut(_)>);
+        fill_bricks_button
+            .add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    // MARK: Undo and redo
+    {
+        let undo_button = elements.undo_button.clone();
+        let elements = elements.clone();
+        let closure = Closure::wrap(Box::new(move |event: Event| {
+            event.prevent_default();
+            wall_action(&elements, Wall::undo);
+        }) as Box<dyn FnMut(_)>);
+        undo_button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+    {
+        let redo_button = elements.redo_button.clone();
+        let elements = elements.clone();
+        let closure = Closure::wrap(Box::new(move |event: Event| {
+            event.prevent_default();
+            wall_action(&elements, Wall::redo);
+        }) as Box<dyn FnMut(_)>);
+        redo_button.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    // MARK: Keyboard shortcuts
+    {
+        let elements = elements.clone();
         let closure = Closure::wrap(Box::new(move |event: KeyboardEvent| {
-            if event.key() == " " || event.key() == "Enter" {
+            let key = event.key().to_lowercase();
+            if event.ctrl_key() || event.meta_key() {
+                if key == "z" && !event.shift_key() {
+                    event.prevent_default();
+                    wall_action(&elements, Wall::undo);
+                } else if key == "y" || (key == "z" && event.shift_key()) {
+                    event.prevent_default();
+                    wall_action(&elements, Wall::redo);
+                }
+            } else if key == " " || key == "enter" {
                 event.prevent_default();
-                wall_next_brick(&context);
+                wall_action(&elements, Wall::next_brick);
             }
         }) as Box<dyn FnMut(_)>);
         window.add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())?;
         closure.forget();
     }
 
-    // MARK: Fill bricks
+    // MARK: Zoom and pan
     {
-        let context = context.clone();
-        let closure = Closure::wrap(Box::new(move |event: Event| {
+        let elements = elements.clone();
+        let closure = Closure::wrap(Box::new(move |event: WheelEvent| {
             event.prevent_default();
-            if let Some(ref mut wall) = *WALL.write().unwrap() {
-                wall.fill_bricks();
-                wall.draw(&context);
+            let factor = (-event.delta_y() * 0.001).exp();
+            VIEW.write().unwrap().zoom_at(
+                factor,
+                event.offset_x() as f64 * pixel_ratio,
+                event.offset_y() as f64 * pixel_ratio,
+            );
+            wall_redraw(&elements);
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback("wheel", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+    {
+        let elements = elements.clone();
+        let closure = Closure::wrap(Box::new(move |event: MouseEvent| {
+            // Pan while the primary mouse button is held down
+            if event.buttons() & 1 != 0 {
+                VIEW.write().unwrap().pan(
+                    event.movement_x() as f64 * pixel_ratio,
+                    event.movement_y() as f64 * pixel_ratio,
+                );
+                wall_redraw(&elements);
             }
         }) as Box<dyn FnMut(_)>);
-        fill_bricks_button
+        canvas.add_event_listener_with_callback("mousemove", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+    {
+        let elements = elements.clone();
+        let closure = Closure::wrap(Box::new(move |event: Event| {
+            event.prevent_default();
+            VIEW.write().unwrap().reset();
+            wall_redraw(&elements);
+        }) as Box<dyn FnMut(_)>);
+        reset_view_button
+            .add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    // MARK: Export and import
+    {
+        let closure = Closure::wrap(Box::new(move |event: Event| {
+            event.prevent_default();
+            wall_export().unwrap();
+        }) as Box<dyn FnMut(_)>);
+        export_button
             .add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
         closure.forget();
     }
+    {
+        let closure = Closure::wrap(Box::new(move |event: Event| {
+            event.prevent_default();
+            wall_export_drawing().unwrap();
+        }) as Box<dyn FnMut(_)>);
+        export_drawing_button
+            .add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+    {
+        let elements = elements.clone();
+        let import_input_clone = import_input.clone();
+        let closure = Closure::wrap(Box::new(move |_: Event| {
+            wall_import(&elements, &import_input_clone).unwrap();
+        }) as Box<dyn FnMut(_)>);
+        import_input
+            .add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
 
     Ok(())
 }
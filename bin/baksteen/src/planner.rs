@@ -0,0 +1,201 @@
+/*
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::brick::Brick;
+use crate::consts::{BRICK_HALF_WIDTH, BRICK_HEIGHT};
+
+const CANDIDATE_STEP: f64 = BRICK_HALF_WIDTH / 2.0;
+
+/// A position of the robot platform, the robot can reach all bricks within its reach from here
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Platform {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+}
+
+/// An optimized build order, the bricks are stored as brick index with their stride
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct Plan {
+    pub(crate) order: Vec<(usize, usize)>,
+    pub(crate) platforms: Vec<Platform>,
+}
+
+struct Planner<'a> {
+    bricks: &'a [Brick],
+    supports: Vec<Vec<usize>>,
+    built: Vec<bool>,
+    reach_width: f64,
+    reach_height: f64,
+    min_x: f64,
+    max_x: f64,
+    min_y: f64,
+    max_y: f64,
+}
+
+impl Planner<'_> {
+    fn is_buildable(&self, index: usize) -> bool {
+        !self.built[index] && self.supports[index].iter().all(|&below| self.built[below])
+    }
+
+    fn in_reach(&self, platform: Platform, brick: &Brick) -> bool {
+        brick.x() >= platform.x
+            && brick.x() + brick.width() <= platform.x + self.reach_width
+            && brick.y() >= platform.y
+            && brick.y() + BRICK_HEIGHT <= platform.y + self.reach_height
+    }
+
+    /// Collect the bricks that can be built from a platform position, bottom course first and
+    /// left to right, a built brick can support other bricks within reach
+    fn reachable_bricks(&self, platform: Platform) -> Vec<usize> {
+        let mut in_reach = (0..self.bricks.len())
+            .filter(|&index| !self.built[index] && self.in_reach(platform, &self.bricks[index]))
+            .collect::<Vec<_>>();
+        in_reach.sort_by(|&a, &b| {
+            let (a, b) = (&self.bricks[a], &self.bricks[b]);
+            b.y().total_cmp(&a.y()).then(a.x().total_cmp(&b.x()))
+        });
+
+        let mut built = self.built.clone();
+        let mut order = Vec::new();
+        loop {
+            let before = order.len();
+            for &index in &in_reach {
+                if !built[index] && self.supports[index].iter().all(|&below| built[below]) {
+                    built[index] = true;
+                    order.push(index);
+                }
+            }
+            if order.len() == before {
+                return order;
+            }
+        }
+    }
+
+    /// Platform positions worth trying, all keep the lowest buildable brick within reach so
+    /// the wall grows evenly and no unreachable gaps are left behind
+    fn candidates(&self) -> Vec<Platform> {
+        let Some(brick) = (0..self.bricks.len())
+            .filter(|&index| self.is_buildable(index))
+            .map(|index| &self.bricks[index])
+            .max_by(|a, b| a.y().total_cmp(&b.y()).then(b.x().total_cmp(&a.x())))
+        else {
+            return Vec::new();
+        };
+
+        let y = (brick.y() + BRICK_HEIGHT - self.reach_height).clamp(self.min_y, self.max_y);
+        let mut candidates = Vec::new();
+        let mut x = brick.x() + brick.width() - self.reach_width;
+        loop {
+            candidates.push(Platform {
+                x: x.clamp(self.min_x, self.max_x),
+                y,
+            });
+            if x >= brick.x() {
+                return candidates;
+            }
+            x = (x + CANDIDATE_STEP).min(brick.x());
+        }
+    }
+}
+
+/// Plan a build order that needs as few platform moves as possible, every stride the platform is
+/// moved to the position where the most bricks can be built
+pub(crate) fn plan(
+    bricks: &[Brick],
+    width: f64,
+    height: f64,
+    reach_width: f64,
+    reach_height: f64,
+) -> Plan {
+    // Precompute which bricks in the course below support each brick
+    let supports = bricks
+        .iter()
+        .map(|brick| {
+            bricks
+                .iter()
+                .enumerate()
+                .filter(|(_, below)| {
+                    below.y() > brick.y()
+                        && below.y() - brick.y() < BRICK_HEIGHT * 2.0
+                        && below.x() < brick.x() + brick.width()
+                        && below.x() + below.width() > brick.x()
+                })
+                .map(|(index, _)| index)
+                .collect()
+        })
+        .collect();
+    let top = bricks.iter().map(Brick::y).fold(0.0, f64::min);
+    let mut planner = Planner {
+        bricks,
+        supports,
+        built: vec![false; bricks.len()],
+        reach_width,
+        reach_height,
+        min_x: 0.0,
+        // The reach can be larger than the wall, keep the ranges non-empty so clamping is valid
+        max_x: (width - reach_width).max(0.0),
+        min_y: top,
+        max_y: (height - reach_height).max(top),
+    };
+
+    let mut plan = Plan::default();
+    while planner.built.iter().any(|built| !built) {
+        // Pick the candidate that builds the most bricks, prefer lower and then left positions
+        let mut best: Option<(Platform, Vec<usize>)> = None;
+        for platform in planner.candidates() {
+            let order = planner.reachable_bricks(platform);
+            let is_better = best.as_ref().is_none_or(|(best_platform, best_order)| {
+                order.len() > best_order.len()
+                    || (order.len() == best_order.len()
+                        && (platform.y > best_platform.y
+                            || (platform.y == best_platform.y && platform.x < best_platform.x)))
+            });
+            if is_better {
+                best = Some((platform, order));
+            }
+        }
+        let Some((platform, mut order)) = best else {
+            break;
+        };
+
+        // When the reach is too small for any brick, build the first buildable brick anyway
+        if order.is_empty() {
+            order.extend((0..bricks.len()).find(|&index| planner.is_buildable(index)));
+        }
+
+        plan.platforms.push(platform);
+        let stride = plan.platforms.len();
+        for index in order {
+            planner.built[index] = true;
+            plan.order.push((index, stride));
+        }
+    }
+    plan
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::consts::BRICK_WIDTH;
+
+    #[test]
+    fn test_plan_reach_larger_than_wall() {
+        // Two courses of two bricks on a wall smaller than the robot reach
+        let bricks = [
+            Brick::new(0.0, -BRICK_HEIGHT, BRICK_WIDTH),
+            Brick::new(BRICK_WIDTH, -BRICK_HEIGHT, BRICK_WIDTH),
+            Brick::new(0.0, -BRICK_HEIGHT * 2.0, BRICK_WIDTH),
+            Brick::new(BRICK_WIDTH, -BRICK_HEIGHT * 2.0, BRICK_WIDTH),
+        ];
+        let plan = plan(&bricks, BRICK_WIDTH * 2.0, 0.0, 10_000.0, 10_000.0);
+        assert_eq!(plan.platforms.len(), 1);
+        assert_eq!(plan.order.len(), bricks.len());
+        assert!(plan.order.iter().all(|&(_, stride)| stride == 1));
+    }
+}
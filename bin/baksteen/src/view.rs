@@ -0,0 +1,56 @@
+/*
+ * Copyright (c) 2025 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use web_sys::CanvasRenderingContext2d;
+
+const ZOOM_MIN: f64 = 0.25;
+const ZOOM_MAX: f64 = 20.0;
+
+/// Zoom and pan of the canvas, the pan is in canvas pixels
+pub(crate) struct View {
+    zoom: f64,
+    pan_x: f64,
+    pan_y: f64,
+}
+
+impl View {
+    pub(crate) const fn new() -> Self {
+        View {
+            zoom: 1.0,
+            pan_x: 0.0,
+            pan_y: 0.0,
+        }
+    }
+
+    pub(crate) const fn reset(&mut self) {
+        *self = View::new();
+    }
+
+    /// Zoom by a factor while keeping the canvas point under the cursor in place
+    pub(crate) fn zoom_at(&mut self, factor: f64, x: f64, y: f64) {
+        let zoom = (self.zoom * factor).clamp(ZOOM_MIN, ZOOM_MAX);
+        let factor = zoom / self.zoom;
+        self.pan_x = x - (x - self.pan_x) * factor;
+        self.pan_y = y - (y - self.pan_y) * factor;
+        self.zoom = zoom;
+    }
+
+    pub(crate) fn pan(&mut self, dx: f64, dy: f64) {
+        self.pan_x += dx;
+        self.pan_y += dy;
+    }
+
+    /// Clear the canvas and transform it so the wall fits the width at zoom level 1
+    pub(crate) fn apply(&self, context: &CanvasRenderingContext2d, wall_width: f64) {
+        let canvas = context.canvas().unwrap();
+        context.reset();
+        context.clear_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
+
+        let scale = canvas.width() as f64 / (wall_width * 1.2) * self.zoom;
+        context.translate(self.pan_x, self.pan_y).unwrap();
+        context.scale(scale, scale).unwrap();
+    }
+}
@@ -1,22 +1,31 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
+use std::iter;
 use std::str::FromStr;
 
+use serde::{Deserialize, Serialize};
 use web_sys::CanvasRenderingContext2d;
 
-use crate::brick::Brick;
+use crate::brick::{Brick, stride_color};
 use crate::consts::*;
+use crate::planner::{self, Plan, Platform};
+use crate::view::View;
 
 // MARK: Bond type
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub(crate) enum BondType {
     Stretcher,
     Header,
     English,
     Flemish,
+    Stack,
+    /// Stretcher courses with a custom offset in mm per course, the pattern repeats upwards
+    Custom(Vec<f64>),
 }
 
 impl FromStr for BondType {
@@ -27,13 +36,254 @@ impl FromStr for BondType {
             "header" => Ok(BondType::Header),
             "english" => Ok(BondType::English),
             "flemish" => Ok(BondType::Flemish),
+            "stack" => Ok(BondType::Stack),
             _ => Err(()),
         }
     }
 }
 
+impl BondType {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            BondType::Stretcher => "stretcher",
+            BondType::Header => "header",
+            BondType::English => "english",
+            BondType::Flemish => "flemish",
+            BondType::Stack => "stack",
+            BondType::Custom(_) => "custom",
+        }
+    }
+
+    /// Parse a custom offset pattern like `0, 105, 52.5`
+    pub(crate) fn parse_custom(pattern: &str) -> Result<Self, ()> {
+        let offsets = pattern
+            .split([',', ' '])
+            .filter(|part| !part.is_empty())
+            .map(|part| part.parse::<f64>().map_err(|_| ()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if offsets.is_empty() || offsets.iter().any(|offset| !offset.is_finite()) {
+            return Err(());
+        }
+        Ok(BondType::Custom(offsets))
+    }
+
+    pub(crate) fn custom_pattern(&self) -> String {
+        match self {
+            BondType::Custom(offsets) => offsets
+                .iter()
+                .map(|offset| offset.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => String::new(),
+        }
+    }
+
+    /// Brick widths of a course from left to right, the course is cut off at the wall edge
+    fn course_widths(&self, course: usize) -> Box<dyn Iterator<Item = f64>> {
+        let is_even = course.is_multiple_of(2);
+        match self {
+            // MARK: Stretcher bond
+            BondType::Stretcher if is_even => {
+                Box::new(iter::once(BRICK_HALF_WIDTH).chain(iter::repeat(BRICK_WIDTH)))
+            }
+            BondType::Stretcher => Box::new(iter::repeat(BRICK_WIDTH)),
+
+            // MARK: Header bond
+            BondType::Header if is_even => Box::new(iter::repeat(BRICK_HALF_WIDTH)),
+            BondType::Header => {
+                Box::new(iter::once(BRICK_TWO_THIRDS_WIDTH).chain(iter::repeat(BRICK_HALF_WIDTH)))
+            }
+
+            // MARK: English bond
+            BondType::English if is_even => Box::new(iter::repeat(BRICK_WIDTH)),
+            BondType::English => {
+                Box::new(iter::once(BRICK_TWO_THIRDS_WIDTH).chain(iter::repeat(BRICK_HALF_WIDTH)))
+            }
+
+            // MARK: Flemish bond
+            BondType::Flemish if is_even => {
+                Box::new([BRICK_WIDTH, BRICK_HALF_WIDTH].into_iter().cycle())
+            }
+            BondType::Flemish => Box::new(
+                iter::once(BRICK_TWO_THIRDS_WIDTH)
+                    .chain([BRICK_WIDTH, BRICK_HALF_WIDTH].into_iter().cycle()),
+            ),
+
+            // MARK: Stack bond
+            BondType::Stack => Box::new(iter::repeat(BRICK_WIDTH)),
+
+            // MARK: Custom bond
+            BondType::Custom(offsets) => {
+                // The offset is the start of the first full brick, the part before it is a cut brick
+                let offset =
+                    offsets[course % offsets.len()].rem_euclid(BRICK_WIDTH + BRICK_BED_JOINT);
+                let first = offset - BRICK_BED_JOINT;
+                Box::new(
+                    iter::once(first)
+                        .filter(|width| *width > 0.0)
+                        .chain(iter::repeat(BRICK_WIDTH)),
+                )
+            }
+        }
+    }
+}
+
+// MARK: Opening
+/// A rectangular opening for a door or window, the bottom is measured from the ground
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct Opening {
+    x: f64,
+    bottom: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Opening {
+    /// Parse openings like `400 0 900 2100; 1600 900 600 600` as x, bottom, width and height
+    pub(crate) fn parse_list(list: &str) -> Result<Vec<Self>, ()> {
+        list.split(';')
+            .filter(|opening| !opening.trim().is_empty())
+            .map(|opening| {
+                let values = opening
+                    .split([',', ' '])
+                    .filter(|part| !part.is_empty())
+                    .map(|part| part.parse::<f64>().map_err(|_| ()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match values[..] {
+                    [x, bottom, width, height]
+                        if values.iter().all(|value| value.is_finite())
+                            && width > 0.0
+                            && height > 0.0 =>
+                    {
+                        Ok(Opening {
+                            x,
+                            bottom,
+                            width,
+                            height,
+                        })
+                    }
+                    _ => Err(()),
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn format_list(openings: &[Self]) -> String {
+        openings
+            .iter()
+            .map(|opening| {
+                format!(
+                    "{} {} {} {}",
+                    opening.x, opening.bottom, opening.width, opening.height
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+
+    pub(crate) const fn x(&self) -> f64 {
+        self.x
+    }
+
+    pub(crate) const fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub(crate) const fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// Top of the opening in canvas coordinates
+    pub(crate) fn top(&self, wall_height: f64) -> f64 {
+        wall_height - self.bottom - self.height
+    }
+
+    fn overlaps_course(&self, wall_height: f64, y: f64) -> bool {
+        self.top(wall_height) < y + BRICK_HEIGHT && wall_height - self.bottom > y
+    }
+
+    /// The lintel is laid in the first course that is completely above the opening
+    fn has_lintel_in_course(&self, wall_height: f64, y: f64) -> bool {
+        let top = self.top(wall_height);
+        y + BRICK_HEIGHT <= top && y + BRICK_HEIGHT * 2.0 + BRICK_HEAD_JOINT > top
+    }
+}
+
+/// Lay a course of bricks from left to right, the last brick is cut to fit the wall width and
+/// bricks are cut around the gaps, cut pieces that are too small are left out
+fn lay_course(
+    bricks: &mut Vec<Brick>,
+    y: f64,
+    width: f64,
+    widths: impl Iterator<Item = f64>,
+    gaps: &[(f64, f64)],
+) {
+    let mut x = 0.0;
+    for brick_width in widths {
+        if x >= width {
+            break;
+        }
+        let brick_width = brick_width.min(width - x);
+
+        let mut pieces = vec![(x, x + brick_width)];
+        for (gap_left, gap_right) in gaps {
+            pieces = pieces
+                .into_iter()
+                .flat_map(|(left, right)| {
+                    [(left, right.min(*gap_left)), (left.max(*gap_right), right)]
+                })
+                .filter(|(left, right)| right - left > 0.0)
+                .collect();
+        }
+        for (left, right) in pieces {
+            if right - left >= BRICK_MIN_CUT_WIDTH {
+                bricks.push(Brick::new(left, y, right - left));
+            }
+        }
+        x += brick_width + BRICK_BED_JOINT;
+    }
+}
+
+// MARK: Build mode
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum BuildMode {
+    /// Build the next buildable brick and move the robot when it is out of reach
+    Naive,
+    /// Follow a precomputed plan that minimizes the platform moves
+    Optimized,
+}
+
+impl FromStr for BuildMode {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "naive" => Ok(BuildMode::Naive),
+            "optimized" => Ok(BuildMode::Optimized),
+            _ => Err(()),
+        }
+    }
+}
+
+impl BuildMode {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            BuildMode::Naive => "naive",
+            BuildMode::Optimized => "optimized",
+        }
+    }
+}
+
+/// Number of platform moves needed to build the whole wall with both build modes
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct BuildStats {
+    pub(crate) naive_moves: usize,
+    pub(crate) optimized_moves: usize,
+}
+
 // MARK: Wall
 
+#[derive(Clone, Serialize, Deserialize)]
 struct Robot {
     x: f64,
     y: f64,
@@ -42,168 +292,258 @@ struct Robot {
     current_stride: usize,
 }
 
+/// A next brick or fill operation, stores the built brick indexes with their stride so it
+/// can be undone and redone
+#[derive(Serialize, Deserialize)]
+struct Action {
+    bricks: Vec<(usize, usize)>,
+    robot_before: Robot,
+    robot_after: Robot,
+}
+
+#[derive(Serialize, Deserialize)]
 pub(crate) struct Wall {
     width: f64,
     height: f64,
+    bond: BondType,
+    openings: Vec<Opening>,
     bricks: Vec<Brick>,
     robot: Robot,
+    mode: BuildMode,
+    plan: Plan,
+    stats: BuildStats,
+    undo_stack: Vec<Action>,
+    redo_stack: Vec<Action>,
 }
 
 impl Wall {
-    pub(crate) fn new(width: f64, height: f64, bond: BondType) -> Self {
+    pub(crate) fn new(
+        width: f64,
+        height: f64,
+        bond: BondType,
+        openings: Vec<Opening>,
+        reach_width: f64,
+        reach_height: f64,
+        mode: BuildMode,
+    ) -> Self {
         let mut bricks = Vec::new();
         let mut y = height - BRICK_HEIGHT;
         let rows = (height / (BRICK_HEIGHT + BRICK_HEAD_JOINT)).ceil() as usize;
         for j in 0..rows {
-            match bond {
-                // MARK: Stretcher bond
-                BondType::Stretcher => {
-                    let mut x = 0.0;
-                    if j % 2 == 0 {
-                        // Start half brick
-                        bricks.push(Brick::new(x, y, BRICK_HALF_WIDTH));
-                        x += BRICK_HALF_WIDTH + BRICK_BED_JOINT;
-
-                        // Normal bricks
-                        while x + BRICK_WIDTH <= width {
-                            bricks.push(Brick::new(x, y, BRICK_WIDTH));
-                            x += BRICK_WIDTH + BRICK_BED_JOINT;
-                        }
-                    } else {
-                        // Normal bricks
-                        while x + BRICK_WIDTH <= width - BRICK_HALF_WIDTH {
-                            bricks.push(Brick::new(x, y, BRICK_WIDTH));
-                            x += BRICK_WIDTH + BRICK_BED_JOINT;
-                        }
-
-                        // End half brick
-                        bricks.push(Brick::new(x, y, BRICK_HALF_WIDTH));
-                    }
-                }
-
-                // MARK: Header bond
-                BondType::Header => {
-                    let mut x = 0.0;
-                    if j % 2 == 0 {
-                        // Half bricks
-                        while x + BRICK_HALF_WIDTH <= width {
-                            bricks.push(Brick::new(x, y, BRICK_HALF_WIDTH));
-                            x += BRICK_HALF_WIDTH + BRICK_BED_JOINT;
-                        }
-                    } else {
-                        // Start two thirds brick
-                        bricks.push(Brick::new(x, y, BRICK_TWO_THIRDS_WIDTH));
-                        x += BRICK_TWO_THIRDS_WIDTH + BRICK_BED_JOINT;
-
-                        // Half bricks
-                        while x + BRICK_HALF_WIDTH <= width - BRICK_TWO_THIRDS_WIDTH {
-                            bricks.push(Brick::new(x, y, BRICK_HALF_WIDTH));
-                            x += BRICK_HALF_WIDTH + BRICK_BED_JOINT;
-                        }
-
-                        // End two thirds brick
-                        bricks.push(Brick::new(x, y, BRICK_TWO_THIRDS_WIDTH));
-                    }
-                }
-
-                // MARK: English bond
-                BondType::English => {
-                    let mut x = 0.0;
-                    if j % 2 == 0 {
-                        // Normal bricks
-                        while x + BRICK_WIDTH <= width {
-                            bricks.push(Brick::new(x, y, BRICK_WIDTH));
-                            x += BRICK_WIDTH + BRICK_BED_JOINT;
-                        }
-
-                        // Half brick at the end if space allows
-                        if x + BRICK_HALF_WIDTH <= width {
-                            bricks.push(Brick::new(x, y, BRICK_HALF_WIDTH));
-                        }
-                    } else {
-                        // Start two thirds brick
-                        bricks.push(Brick::new(x, y, BRICK_TWO_THIRDS_WIDTH));
-                        x += BRICK_TWO_THIRDS_WIDTH + BRICK_BED_JOINT;
-
-                        // Half bricks
-                        while x + BRICK_HALF_WIDTH <= width - BRICK_TWO_THIRDS_WIDTH {
-                            bricks.push(Brick::new(x, y, BRICK_HALF_WIDTH));
-                            x += BRICK_HALF_WIDTH + BRICK_BED_JOINT;
-                        }
-
-                        // End two thirds brick
-                        bricks.push(Brick::new(x, y, BRICK_TWO_THIRDS_WIDTH));
-                    }
-                }
-
-                // MARK: Flemish bond
-                BondType::Flemish => {
-                    let mut x = 0.0;
-                    if j % 2 == 0 {
-                        // Alternate normal and half bricks
-                        let mut is_normal = true;
-                        while x < width {
-                            let brick_width = if is_normal {
-                                BRICK_WIDTH
-                            } else {
-                                BRICK_HALF_WIDTH
-                            };
-                            if x + brick_width > width {
-                                break;
-                            }
-                            bricks.push(Brick::new(x, y, brick_width));
-                            x += brick_width + BRICK_BED_JOINT;
-                            is_normal = !is_normal;
-                        }
-                    } else {
-                        // Start with two thirds brick
-                        bricks.push(Brick::new(x, y, BRICK_TWO_THIRDS_WIDTH));
-                        x += BRICK_TWO_THIRDS_WIDTH + BRICK_BED_JOINT;
-
-                        // Alternate normal and half bricks
-                        let mut is_normal = true;
-                        while x + BRICK_TWO_THIRDS_WIDTH < width {
-                            let brick_width = if is_normal {
-                                BRICK_WIDTH
-                            } else {
-                                BRICK_HALF_WIDTH
-                            };
-                            if x + brick_width > width - BRICK_TWO_THIRDS_WIDTH {
-                                break;
-                            }
-                            bricks.push(Brick::new(x, y, brick_width));
-                            x += brick_width + BRICK_BED_JOINT;
-                            is_normal = !is_normal;
-                        }
-
-                        // End with two thirds brick
-                        bricks.push(Brick::new(x, y, BRICK_TWO_THIRDS_WIDTH));
-                    }
+            // Leave the openings free and replace the bricks above them with a lintel
+            let mut gaps = Vec::new();
+            let mut lintels = Vec::new();
+            for opening in &openings {
+                if opening.overlaps_course(height, y) {
+                    gaps.push((opening.x, opening.x + opening.width));
+                } else if opening.has_lintel_in_course(height, y) {
+                    let left = (opening.x - LINTEL_BEARING).max(0.0);
+                    let right = (opening.x + opening.width + LINTEL_BEARING).min(width);
+                    gaps.push((left - BRICK_BED_JOINT, right + BRICK_BED_JOINT));
+                    lintels.push(Brick::new_lintel(left, y, right - left));
                 }
             }
 
+            let mut course = Vec::new();
+            lay_course(&mut course, y, width, bond.course_widths(j), &gaps);
+            course.extend(lintels);
+            course.sort_by(|a, b| a.x().total_cmp(&b.x()));
+            bricks.extend(course);
             y -= BRICK_HEIGHT + BRICK_HEAD_JOINT;
         }
 
-        let robot_height = STRIDE_HEIGHT.min(height);
+        // The robot must at least be able to reach a single full brick
+        let robot_width = reach_width.max(BRICK_WIDTH).min(width);
+        let robot_height = reach_height.max(BRICK_HEIGHT).min(height);
         let robot = Robot {
             x: 0.0,
             y: height - robot_height,
-            width: STRIDE_WIDTH.min(width),
+            width: robot_width,
             height: robot_height,
             current_stride: 1,
         };
+        let plan = planner::plan(&bricks, width, height, robot_width, robot_height);
 
-        Wall {
+        let mut wall = Wall {
             width,
             height,
+            bond,
+            openings,
             bricks,
-            robot,
+            robot: robot.clone(),
+            mode: BuildMode::Naive,
+            plan,
+            stats: BuildStats {
+                naive_moves: 0,
+                optimized_moves: 0,
+            },
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        };
+
+        // Build the wall once with the naive order to compare it with the plan
+        while wall.build_next_brick().is_some() {}
+        wall.stats = BuildStats {
+            naive_moves: wall.robot.current_stride - 1,
+            optimized_moves: wall.plan.platforms.len().saturating_sub(1),
+        };
+        for brick in &mut wall.bricks {
+            brick.unbuild();
         }
+
+        wall.mode = mode;
+        wall.robot = robot;
+        if mode == BuildMode::Optimized
+            && let Some(platform) = wall.plan.platforms.first()
+        {
+            wall.robot.x = platform.x;
+            wall.robot.y = platform.y;
+        }
+        wall
+    }
+
+    pub(crate) const fn width(&self) -> f64 {
+        self.width
+    }
+
+    pub(crate) const fn height(&self) -> f64 {
+        self.height
+    }
+
+    pub(crate) const fn bond(&self) -> &BondType {
+        &self.bond
+    }
+
+    pub(crate) fn bricks(&self) -> &[Brick] {
+        &self.bricks
+    }
+
+    pub(crate) fn openings(&self) -> &[Opening] {
+        &self.openings
+    }
+
+    pub(crate) const fn reach_width(&self) -> f64 {
+        self.robot.width
+    }
+
+    pub(crate) const fn reach_height(&self) -> f64 {
+        self.robot.height
+    }
+
+    pub(crate) const fn mode(&self) -> BuildMode {
+        self.mode
+    }
+
+    pub(crate) const fn stats(&self) -> BuildStats {
+        self.stats
+    }
+
+    pub(crate) const fn current_stride(&self) -> usize {
+        self.robot.current_stride
+    }
+
+    // MARK: Save and load
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Can't serialize wall")
+    }
+
+    pub(crate) fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let wall: Self = serde_json::from_str(json)?;
+
+        // Reject files whose brick indexes would panic when undoing, redoing or building
+        let in_range = |index: &usize| *index < wall.bricks.len();
+        let actions_in_range = wall
+            .undo_stack
+            .iter()
+            .chain(&wall.redo_stack)
+            .all(|action| action.bricks.iter().map(|(index, _)| index).all(in_range));
+        if !actions_in_range || !wall.plan.order.iter().map(|(index, _)| index).all(in_range) {
+            return Err(serde::de::Error::custom("brick index out of range"));
+        }
+        Ok(wall)
+    }
+
+    // MARK: Undo and redo
+    pub(crate) const fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub(crate) const fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub(crate) fn undo(&mut self) -> bool {
+        let Some(action) = self.undo_stack.pop() else {
+            return false;
+        };
+        for (index, _) in &action.bricks {
+            self.bricks[*index].unbuild();
+        }
+        self.robot = action.robot_before.clone();
+        self.redo_stack.push(action);
+        true
+    }
+
+    pub(crate) fn redo(&mut self) -> bool {
+        let Some(action) = self.redo_stack.pop() else {
+            return false;
+        };
+        for (index, stride) in &action.bricks {
+            self.bricks[*index].build(*stride);
+        }
+        self.robot = action.robot_after.clone();
+        self.undo_stack.push(action);
+        true
+    }
+
+    /// Run an operation that builds bricks and record it as one undoable action
+    fn record_action(&mut self, operation: impl FnOnce(&mut Self) -> Vec<(usize, usize)>) -> bool {
+        let robot_before = self.robot.clone();
+        let bricks = operation(self);
+        if bricks.is_empty() {
+            return false;
+        }
+        self.undo_stack.push(Action {
+            bricks,
+            robot_before,
+            robot_after: self.robot.clone(),
+        });
+        self.redo_stack.clear();
+        true
     }
 
-    // MARK: Next brick algorithm
     pub(crate) fn next_brick(&mut self) -> bool {
+        self.record_action(|wall| wall.build_next_brick().into_iter().collect())
+    }
+
+    pub(crate) fn fill_bricks(&mut self) -> bool {
+        self.record_action(|wall| iter::from_fn(|| wall.build_next_brick()).collect())
+    }
+
+    // MARK: Next brick algorithm
+    /// Build the next brick, returns the index of the built brick and its stride
+    fn build_next_brick(&mut self) -> Option<(usize, usize)> {
+        match self.mode {
+            BuildMode::Naive => self.build_next_brick_naive(),
+            BuildMode::Optimized => self.build_next_brick_optimized(),
+        }
+    }
+
+    fn build_next_brick_optimized(&mut self) -> Option<(usize, usize)> {
+        let (index, stride) = *self
+            .plan
+            .order
+            .iter()
+            .find(|(index, _)| !self.bricks[*index].is_build())?;
+        let Platform { x, y } = self.plan.platforms[stride - 1];
+        self.robot.x = x;
+        self.robot.y = y;
+        self.robot.current_stride = stride;
+        self.bricks[index].build(stride);
+        Some((index, stride))
+    }
+
+    fn build_next_brick_naive(&mut self) -> Option<(usize, usize)> {
         // Clone bricks to avoid borrowing issues
         let bricks = self.bricks.clone();
 
@@ -211,11 +551,12 @@ impl Wall {
         let mut bricks_iter = self
             .bricks
             .iter_mut()
-            .filter(|b| !b.is_build())
+            .enumerate()
+            .filter(|(_, b)| !b.is_build())
             .collect::<Vec<_>>();
 
         // Sort bricks so those within robot's reach come first
-        bricks_iter.sort_by_key(|brick| {
+        bricks_iter.sort_by_key(|(_, brick)| {
             let in_reach = brick.x() >= self.robot.x
                 && brick.x() + brick.width() <= self.robot.x + self.robot.width
                 && brick.y() >= self.robot.y
@@ -224,30 +565,26 @@ impl Wall {
         });
 
         // Filter out all bricks that can't be build
-        bricks_iter.retain(|brick| {
+        bricks_iter.retain(|(_, brick)| {
             // If brick is on the bottom row, it can always be built
             if brick.y() == self.height - BRICK_HEIGHT {
                 return true;
             }
 
-            // Check if all bricks below this brick are built
+            // Check if all bricks below this brick are built, a lintel only rests on the
+            // bricks next to its opening
             let below_y = brick.y() + BRICK_HEIGHT + BRICK_HEAD_JOINT;
-            let mut covered = 0.0;
-            for b in &bricks {
-                if b.y() == below_y
-                    && b.is_build()
-                    && b.x() < brick.x() + brick.width()
-                    && b.x() + b.width() > brick.x()
-                {
-                    let left = brick.x().max(b.x());
-                    let right = (brick.x() + brick.width()).min(b.x() + b.width());
-                    covered += right - left + BRICK_HEAD_JOINT;
-                }
-            }
-            covered >= brick.width()
+            bricks
+                .iter()
+                .filter(|b| {
+                    b.y() == below_y
+                        && b.x() < brick.x() + brick.width()
+                        && b.x() + b.width() > brick.x()
+                })
+                .all(Brick::is_build)
         });
 
-        if let Some(brick) = bricks_iter.first_mut() {
+        if let Some((index, brick)) = bricks_iter.first_mut() {
             // Check if brick is within robot's reach
             let in_reach = brick.x() >= self.robot.x
                 && brick.x() + brick.width() <= self.robot.x + self.robot.width
@@ -265,38 +602,35 @@ impl Wall {
 
             // Build the brick
             brick.build(self.robot.current_stride);
-            true
+            Some((*index, self.robot.current_stride))
         } else {
-            false
+            None
         }
     }
 
-    pub(crate) fn fill_bricks(&mut self) {
-        while self.next_brick() {}
-    }
-
     // MARK: Draw wall
-    pub(crate) fn draw(&self, context: &CanvasRenderingContext2d) {
-        let canvas = context.canvas().unwrap();
-
-        // Scale canvas
-        let scale = canvas.width() as f64 / (self.width * 1.2);
-        context.reset();
-        context.scale(scale, scale).unwrap();
-
-        // Clear canvas
-        context.clear_rect(
-            0.0,
-            0.0,
-            canvas.width() as f64 * scale,
-            canvas.height() as f64 * scale,
-        );
+    pub(crate) fn draw(&self, context: &CanvasRenderingContext2d, view: &View) {
+        view.apply(context, self.width);
 
         // Draw bricks
         for brick in &self.bricks {
             brick.draw(context);
         }
 
+        // Draw openings
+        context.set_stroke_style_str("#888");
+        context.set_line_width(2.0);
+        for opening in &self.openings {
+            let top = opening.top(self.height);
+            context.stroke_rect(opening.x, top, opening.width, opening.height);
+            context.begin_path();
+            context.move_to(opening.x, top);
+            context.line_to(opening.x + opening.width, top + opening.height);
+            context.move_to(opening.x + opening.width, top);
+            context.line_to(opening.x, top + opening.height);
+            context.stroke();
+        }
+
         // Draw wall outline and label
         context.set_stroke_style_str("green");
         context.set_line_width(5.0);
@@ -311,6 +645,20 @@ impl Wall {
         context.set_text_baseline("middle");
         context.fill_text("Wall", 80.0 / 2.0, 30.0 / 2.0).unwrap();
 
+        // Draw planned platform positions
+        if self.mode == BuildMode::Optimized {
+            context.set_line_width(2.0);
+            context
+                .set_line_dash(&js_sys::Array::of2(&20.0.into(), &10.0.into()))
+                .unwrap();
+            for (index, platform) in self.plan.platforms.iter().enumerate() {
+                context.set_stroke_style_str(&stride_color(index + 1));
+                context.stroke_rect(platform.x, platform.y, self.robot.width, self.robot.height);
+            }
+            context.set_line_dash(&js_sys::Array::new()).unwrap();
+            context.set_line_width(5.0);
+        }
+
         // Draw robot's reach and label
         context.set_stroke_style_str("blue");
         context.stroke_rect(
@@ -336,3 +684,39 @@ impl Wall {
             .unwrap();
     }
 }
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_json_rejects_out_of_range_actions() {
+        let wall = Wall::new(
+            1000.0,
+            500.0,
+            BondType::Stretcher,
+            Vec::new(),
+            1000.0,
+            500.0,
+            BuildMode::Naive,
+        );
+        let json = wall.to_json();
+        assert!(Wall::from_json(&json).is_ok());
+
+        let mut value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        let robot = value["robot"].clone();
+        for stack in ["undo_stack", "redo_stack"] {
+            let mut value = value.clone();
+            value[stack] = serde_json::json!([{
+                "bricks": [[wall.bricks.len(), 1]],
+                "robot_before": robot,
+                "robot_after": robot,
+            }]);
+            assert!(Wall::from_json(&value.to_string()).is_err());
+        }
+
+        value["plan"]["order"] = serde_json::json!([[usize::MAX, 1]]);
+        assert!(Wall::from_json(&value.to_string()).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample, StreamConfig};
+use log::{error, info, warn};
+
+use crate::dmx::beat_sensitivity;
+use crate::ipc::{IpcMessage, broadcast_all};
+
+// Constants
+const WINDOW_DURATION: Duration = Duration::from_millis(20);
+const HISTORY_DURATION: Duration = Duration::from_secs(1);
+const MIN_BEAT_INTERVAL: Duration = Duration::from_millis(250);
+const MIN_ENERGY: f32 = 0.0001;
+
+// MARK: Beat
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Beat {
+    pub strength: f32,
+}
+
+static BEAT_SUBSCRIBERS: Mutex<Vec<Sender<Beat>>> = Mutex::new(Vec::new());
+
+/// Subscribe to beats detected in the audio input
+pub(crate) fn subscribe() -> Receiver<Beat> {
+    let (tx, rx) = mpsc::channel();
+    BEAT_SUBSCRIBERS
+        .lock()
+        .expect("Failed to lock beat subscribers")
+        .push(tx);
+    rx
+}
+
+fn publish(beat: Beat) {
+    BEAT_SUBSCRIBERS
+        .lock()
+        .expect("Failed to lock beat subscribers")
+        .retain(|tx| tx.send(beat).is_ok());
+    broadcast_all(IpcMessage::Beat {
+        strength: beat.strength,
+    });
+}
+
+// MARK: BeatDetector
+/// Energy based onset detector, a beat is a window that is louder than the recent average
+struct BeatDetector {
+    window_size: usize,
+    window_energy: f32,
+    window_len: usize,
+    history: VecDeque<f32>,
+    history_size: usize,
+    last_beat: Option<Instant>,
+}
+
+impl BeatDetector {
+    fn new(sample_rate: u32) -> Self {
+        let window_size = (sample_rate as f32 * WINDOW_DURATION.as_secs_f32()).max(1.0) as usize;
+        let history_size = (HISTORY_DURATION.as_millis() / WINDOW_DURATION.as_millis()) as usize;
+        Self {
+            window_size,
+            window_energy: 0.0,
+            window_len: 0,
+            history: VecDeque::with_capacity(history_size),
+            history_size,
+            last_beat: None,
+        }
+    }
+
+    /// Feed a mono sample, returns a beat when a window completes with an onset
+    fn process(&mut self, sample: f32, sensitivity: f32) -> Option<Beat> {
+        self.window_energy += sample * sample;
+        self.window_len += 1;
+        if self.window_len < self.window_size {
+            return None;
+        }
+
+        let energy = self.window_energy / self.window_len as f32;
+        self.window_energy = 0.0;
+        self.window_len = 0;
+
+        let average = if self.history.is_empty() {
+            energy
+        } else {
+            self.history.iter().sum::<f32>() / self.history.len() as f32
+        };
+        if self.history.len() == self.history_size {
+            self.history.pop_front();
+        }
+        self.history.push_back(energy);
+
+        // Higher sensitivity lowers the threshold above the average energy
+        let threshold = 2.0 - sensitivity.clamp(0.0, 1.0);
+        if self.history.len() < self.history_size / 2
+            || energy < MIN_ENERGY
+            || energy < average * threshold
+            || self
+                .last_beat
+                .is_some_and(|last_beat| last_beat.elapsed() < MIN_BEAT_INTERVAL)
+        {
+            return None;
+        }
+        self.last_beat = Some(Instant::now());
+        Some(Beat {
+            strength: energy / average.max(MIN_ENERGY),
+        })
+    }
+}
+
+// MARK: Audio Thread
+pub(crate) fn audio_thread() {
+    let host = cpal::default_host();
+    let Some(device) = host.default_input_device() else {
+        warn!("No audio input device found, beat detection disabled");
+        return;
+    };
+    let supported_config = match device.default_input_config() {
+        Ok(config) => config,
+        Err(err) => {
+            error!("Can't get audio input config: {err}");
+            return;
+        }
+    };
+    let config = supported_config.config();
+    let (tx, rx) = mpsc::channel();
+    let stream = match supported_config.sample_format() {
+        SampleFormat::F32 => build_stream::<f32>(&device, &config, tx),
+        SampleFormat::I16 => build_stream::<i16>(&device, &config, tx),
+        SampleFormat::U16 => build_stream::<u16>(&device, &config, tx),
+        SampleFormat::I32 => build_stream::<i32>(&device, &config, tx),
+        sample_format => {
+            error!("Unsupported audio input sample format: {sample_format}");
+            return;
+        }
+    };
+    let stream = match stream {
+        Ok(stream) => stream,
+        Err(err) => {
+            error!("Can't build audio input stream: {err}");
+            return;
+        }
+    };
+    if let Err(err) = stream.play() {
+        error!("Can't start audio input stream: {err}");
+        return;
+    }
+    info!(
+        "Audio input started: {} Hz, {} channels",
+        config.sample_rate.0, config.channels
+    );
+
+    // Publish beats outside the realtime audio callback, the stream stops when dropped
+    for beat in rx {
+        publish(beat);
+    }
+    drop(stream);
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    tx: Sender<Beat>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = config.channels.max(1) as usize;
+    let mut detector = BeatDetector::new(config.sample_rate.0);
+    device.build_input_stream(
+        config,
+        move |data: &[T], _| {
+            let sensitivity = beat_sensitivity();
+            for frame in data.chunks(channels) {
+                let sample = frame
+                    .iter()
+                    .map(|sample| sample.to_sample::<f32>())
+                    .sum::<f32>()
+                    / frame.len() as f32;
+                if let Some(beat) = detector.process(sample, sensitivity) {
+                    _ = tx.send(beat);
+                }
+            }
+        },
+        |err| error!("Audio input stream error: {err}"),
+        None,
+    )
+}
@@ -5,6 +5,7 @@
  * SPDX-License-Identifier: MIT
  */
 
+use std::fmt::{self, Display};
 use std::fs::File;
 use std::path::PathBuf;
 
@@ -39,6 +40,17 @@ impl FixtureType {
             FixtureType::ShowtecMultidimMKII => DMX_SWITCHES_LENGTH,
         }
     }
+
+    /// Channels that control the brightness of the fixture, scaled by the intensity buses
+    pub(crate) const fn dimmer_channels(&self) -> &'static [usize] {
+        match self {
+            FixtureType::AmericanDJP56Led => &[0, 1, 2],
+            FixtureType::AmericanDJMegaTripar => &[6],
+            FixtureType::AyraCompar10 => &[0],
+            FixtureType::AyraCompar20 => &[0],
+            FixtureType::ShowtecMultidimMKII => &[0, 1, 2, 3],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +59,8 @@ pub(crate) struct Fixture {
     #[serde(rename = "type")]
     pub r#type: FixtureType,
     pub addr: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub switches: Option<Vec<String>>,
 }
@@ -58,6 +72,62 @@ pub(crate) struct Config {
     pub dmx_fps: u64,
 }
 
+// MARK: Patch
+/// Problem found in the fixture patch of a config
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum PatchIssue {
+    /// Two fixtures use the same DMX channels
+    Overlap {
+        fixtures: [String; 2],
+        start: usize,
+        end: usize,
+    },
+    /// A fixture uses channels outside the DMX universe
+    OutOfRange {
+        fixture: String,
+        start: usize,
+        end: usize,
+    },
+    /// Multiple fixtures have the same name
+    DuplicateName { name: String },
+}
+
+impl Display for PatchIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchIssue::Overlap {
+                fixtures,
+                start,
+                end,
+            } => write!(
+                f,
+                "Fixtures {} and {} overlap on channels {start}-{end}",
+                fixtures[0], fixtures[1]
+            ),
+            PatchIssue::OutOfRange {
+                fixture,
+                start,
+                end,
+            } => write!(
+                f,
+                "Fixture {fixture} uses channels {start}-{end} outside the DMX universe"
+            ),
+            PatchIssue::DuplicateName { name } => write!(f, "Fixture name {name} is used twice"),
+        }
+    }
+}
+
+impl Fixture {
+    /// First and last DMX channel used by the fixture, starting at 1
+    pub(crate) const fn channels(&self) -> (usize, usize) {
+        (
+            self.addr,
+            (self.addr + self.r#type.channel_count()).saturating_sub(1),
+        )
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Config {
@@ -69,6 +139,55 @@ impl Default for Config {
 }
 
 impl Config {
+    /// Unique fixture group names in config order
+    pub(crate) fn groups(&self) -> Vec<String> {
+        let mut groups = Vec::new();
+        for group in self.fixtures.iter().filter_map(|f| f.group.as_ref()) {
+            if !groups.contains(group) {
+                groups.push(group.clone());
+            }
+        }
+        groups
+    }
+
+    /// Check the fixture patch for overlapping channels, channels outside the 512 channel
+    /// universe and duplicate names
+    pub(crate) fn validate(&self) -> Vec<PatchIssue> {
+        let mut issues = Vec::new();
+        for (index, fixture) in self.fixtures.iter().enumerate() {
+            let (start, end) = fixture.channels();
+            if start < 1 || end > DMX_LENGTH {
+                issues.push(PatchIssue::OutOfRange {
+                    fixture: fixture.name.clone(),
+                    start,
+                    end,
+                });
+            }
+            for other in &self.fixtures[..index] {
+                let (other_start, other_end) = other.channels();
+                if start <= other_end && other_start <= end {
+                    issues.push(PatchIssue::Overlap {
+                        fixtures: [other.name.clone(), fixture.name.clone()],
+                        start: start.max(other_start),
+                        end: end.min(other_end),
+                    });
+                }
+            }
+            if self.fixtures[..index]
+                .iter()
+                .any(|other| other.name == fixture.name)
+                && !issues.iter().any(|issue| {
+                    matches!(issue, PatchIssue::DuplicateName { name } if *name == fixture.name)
+                })
+            {
+                issues.push(PatchIssue::DuplicateName {
+                    name: fixture.name.clone(),
+                });
+            }
+        }
+        issues
+    }
+
     fn default_path() -> PathBuf {
         // FIXME: Don't use debug_assertions to determine the config path
         if !cfg!(debug_assertions) {
@@ -95,3 +214,82 @@ impl Config {
         }
     }
 }
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fixture(name: &str, r#type: FixtureType, addr: usize) -> Fixture {
+        Fixture {
+            name: name.to_string(),
+            r#type,
+            addr,
+            group: None,
+            switches: None,
+        }
+    }
+
+    fn config(fixtures: Vec<Fixture>) -> Config {
+        Config {
+            fixtures,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_ok() {
+        let config = config(vec![
+            fixture("Left", FixtureType::AmericanDJP56Led, 1),
+            fixture("Right", FixtureType::AmericanDJP56Led, 7),
+            fixture("Last", FixtureType::ShowtecMultidimMKII, 509),
+        ]);
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_overlap() {
+        let config = config(vec![
+            fixture("Left", FixtureType::AmericanDJP56Led, 1),
+            fixture("Right", FixtureType::AmericanDJP56Led, 5),
+        ]);
+        let issues = config.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].to_string(),
+            "Fixtures Left and Right overlap on channels 5-6"
+        );
+    }
+
+    #[test]
+    fn test_validate_out_of_range() {
+        // The universe has 512 channels, also when fewer channels are sent
+        let mut config = config(vec![
+            fixture("Zero", FixtureType::AyraCompar20, 0),
+            fixture("Last", FixtureType::AyraCompar20, 508),
+        ]);
+        config.dmx_length = 16;
+        let issues = config.validate();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0].to_string(),
+            "Fixture Zero uses channels 0-5 outside the DMX universe"
+        );
+        assert_eq!(
+            issues[1].to_string(),
+            "Fixture Last uses channels 508-513 outside the DMX universe"
+        );
+    }
+
+    #[test]
+    fn test_validate_duplicate_names() {
+        let config = config(vec![
+            fixture("Par", FixtureType::AyraCompar20, 1),
+            fixture("Par", FixtureType::AyraCompar20, 7),
+            fixture("Par", FixtureType::AyraCompar20, 13),
+        ]);
+        let issues = config.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].to_string(), "Fixture name Par is used twice");
+    }
+}
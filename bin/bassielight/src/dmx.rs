@@ -6,14 +6,17 @@
 
 use std::fmt::{self, Display};
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::thread::sleep;
 use std::time::{Duration, SystemTime};
 
-use log::{info, trace, warn};
+use log::{error, info, trace, warn};
 use rusb::{Context, DeviceHandle};
 use serde::{Deserialize, Serialize};
 
 use crate::config::{Config, DMX_SWITCHES_LENGTH, FixtureType};
+use crate::ipc::{IpcMessage, broadcast_all};
+use crate::recorder::{Player, Recorder};
 use crate::usb;
 
 // MARK: Color
@@ -105,6 +108,14 @@ pub(crate) struct DmxState {
     pub strobe_speed: Option<Duration>,
     pub switches_toggle: [bool; DMX_SWITCHES_LENGTH],
     pub switches_press: [bool; DMX_SWITCHES_LENGTH],
+    pub is_recording: bool,
+    pub playback: Option<String>,
+    pub playback_loop: bool,
+    pub playback_speed: f32,
+    pub master: f32,
+    pub group_masters: Vec<f32>,
+    pub blackout: bool,
+    pub flash: bool,
 }
 
 pub(crate) static DMX_STATE: Mutex<DmxState> = Mutex::new(DmxState {
@@ -118,10 +129,55 @@ pub(crate) static DMX_STATE: Mutex<DmxState> = Mutex::new(DmxState {
     strobe_speed: None,
     switches_toggle: [false; DMX_SWITCHES_LENGTH],
     switches_press: [false; DMX_SWITCHES_LENGTH],
+    is_recording: false,
+    playback: None,
+    playback_loop: false,
+    playback_speed: 1.0,
+    master: 1.0,
+    group_masters: Vec::new(),
+    blackout: false,
+    flash: false,
 });
 
+// Beat settings are atomics, so the realtime audio callback never waits on the DMX state lock
+pub(crate) static BEAT_SYNC: AtomicBool = AtomicBool::new(false);
+static BEAT_SENSITIVITY: AtomicU32 = AtomicU32::new(0.5f32.to_bits());
+
+pub(crate) fn beat_sensitivity() -> f32 {
+    f32::from_bits(BEAT_SENSITIVITY.load(Ordering::Relaxed))
+}
+
+pub(crate) fn set_beat_sensitivity(beat_sensitivity: f32) {
+    BEAT_SENSITIVITY.store(beat_sensitivity.to_bits(), Ordering::Relaxed);
+}
+
+fn stop_recording() {
+    DMX_STATE
+        .lock()
+        .expect("Failed to lock DMX state")
+        .is_recording = false;
+    broadcast_all(IpcMessage::SetIsRecording {
+        is_recording: false,
+    });
+}
+
+fn stop_playback() {
+    DMX_STATE.lock().expect("Failed to lock DMX state").playback = None;
+    broadcast_all(IpcMessage::SetPlayback { playback: None });
+}
+
 // MARK: DMX Thread
-pub(crate) fn dmx_thread(config: Config) {
+pub(crate) fn dmx_thread(mut config: Config) {
+    // Group masters are indexed by all groups in the config, so collect them before filtering
+    let groups = config.groups();
+
+    // Skip fixtures outside the universe, they are reported by the patch validation
+    let dmx_length = config.dmx_length;
+    config.fixtures.retain(|fixture| {
+        let (start, end) = fixture.channels();
+        start >= 1 && end <= dmx_length
+    });
+
     let mut handle: Option<DeviceHandle<Context>> = usb::open_udmx_handle();
     if handle.is_some() {
         info!("uDMX device opened");
@@ -143,9 +199,50 @@ pub(crate) fn dmx_thread(config: Config) {
     let mut strobe_time = SystemTime::now();
     let mut is_strobe = false;
     let mut consecutive_errors: u32 = 0;
+    let mut recorder: Option<Recorder> = None;
+    let mut player: Option<Player> = None;
+    #[cfg(feature = "audio")]
+    let beats = crate::audio::subscribe();
 
     loop {
         let dmx_state = DMX_STATE.lock().expect("Failed to lock DMX state").clone();
+
+        // Start or stop recording and playback
+        if dmx_state.is_recording != recorder.is_some() {
+            if let Some(recorder) = recorder.take() {
+                info!("Recording {} stopped", recorder.name);
+                if let Err(err) = recorder.finish() {
+                    error!("Can't write recording: {err}");
+                }
+            } else {
+                match Recorder::create() {
+                    Ok(new_recorder) => {
+                        info!("Recording {} started", new_recorder.name);
+                        recorder = Some(new_recorder);
+                    }
+                    Err(err) => {
+                        error!("Can't create recording: {err}");
+                        stop_recording();
+                    }
+                }
+            }
+        }
+        if dmx_state.playback.as_deref() != player.as_ref().map(|player| player.name.as_str()) {
+            player = None;
+            if let Some(name) = &dmx_state.playback {
+                match Player::open(name) {
+                    Ok(new_player) => {
+                        info!("Playback of {name} started");
+                        player = Some(new_player);
+                    }
+                    Err(err) => {
+                        error!("Can't open recording {name}: {err}");
+                        stop_playback();
+                    }
+                }
+            }
+        }
+
         if !dmx_state.is_running {
             // FIXME: Create async framework don't do micro sleeps
             sleep(Duration::from_millis(100));
@@ -158,23 +255,48 @@ pub(crate) fn dmx_thread(config: Config) {
             toggle_color_time = SystemTime::now();
             is_toggle_color = false;
         }
-        if let Some(toggle_speed) = dmx_state.toggle_speed
-            && SystemTime::now()
-                .duration_since(toggle_color_time)
-                .expect("Time went backwards")
-                > toggle_speed
-        {
-            is_toggle_color = !is_toggle_color;
-            toggle_color_time = SystemTime::now();
-        }
-        if let Some(strobe_speed) = dmx_state.strobe_speed
-            && SystemTime::now()
-                .duration_since(strobe_time)
-                .expect("Time went backwards")
-                > strobe_speed
-        {
-            is_strobe = !is_strobe;
-            strobe_time = SystemTime::now();
+        #[cfg(feature = "audio")]
+        let is_beat = beats.try_iter().count() > 0;
+        #[cfg(not(feature = "audio"))]
+        let is_beat = false;
+        if BEAT_SYNC.load(Ordering::Relaxed) {
+            // Toggle color on every beat and flash the strobe for the strobe speed duration
+            if is_beat && dmx_state.toggle_speed.is_some() {
+                is_toggle_color = !is_toggle_color;
+                toggle_color_time = SystemTime::now();
+            }
+            if is_beat && dmx_state.strobe_speed.is_some() {
+                is_strobe = true;
+                strobe_time = SystemTime::now();
+            } else if is_strobe
+                && dmx_state.strobe_speed.is_none_or(|strobe_speed| {
+                    SystemTime::now()
+                        .duration_since(strobe_time)
+                        .expect("Time went backwards")
+                        > strobe_speed
+                })
+            {
+                is_strobe = false;
+            }
+        } else {
+            if let Some(toggle_speed) = dmx_state.toggle_speed
+                && SystemTime::now()
+                    .duration_since(toggle_color_time)
+                    .expect("Time went backwards")
+                    > toggle_speed
+            {
+                is_toggle_color = !is_toggle_color;
+                toggle_color_time = SystemTime::now();
+            }
+            if let Some(strobe_speed) = dmx_state.strobe_speed
+                && SystemTime::now()
+                    .duration_since(strobe_time)
+                    .expect("Time went backwards")
+                    > strobe_speed
+            {
+                is_strobe = !is_strobe;
+                strobe_time = SystemTime::now();
+            }
         }
 
         // Update DMX data
@@ -316,6 +438,54 @@ pub(crate) fn dmx_thread(config: Config) {
             }
         }
 
+        // Replace the rendered frame with the recorded frame during playback
+        if let Some(current_player) = &mut player {
+            if let Some(frame) =
+                current_player.next_frame(dmx_state.playback_speed, dmx_state.playback_loop)
+            {
+                let length = frame.len().min(dmx.len());
+                dmx.fill(0);
+                dmx[..length].copy_from_slice(&frame[..length]);
+            } else {
+                info!("Playback of {} finished", current_player.name);
+                player = None;
+                stop_playback();
+            }
+        }
+
+        // Record the frame before the buses, so they also ride the brightness during playback
+        if let Some(current_recorder) = &mut recorder
+            && let Err(err) = current_recorder.write_frame(&dmx[..send_length])
+        {
+            error!("Can't write recording: {err}");
+            recorder = None;
+            stop_recording();
+        }
+
+        // Apply intensity buses to the final frame, blackout wins over flash
+        for fixture in &config.fixtures {
+            let base_addr = fixture.addr - 1;
+            if dmx_state.blackout {
+                dmx[base_addr..base_addr + fixture.r#type.channel_count()].fill(0);
+                continue;
+            }
+            let group_master = fixture
+                .group
+                .as_ref()
+                .and_then(|group| groups.iter().position(|g| g == group))
+                .and_then(|index| dmx_state.group_masters.get(index).copied())
+                .unwrap_or(1.0);
+            let factor = (dmx_state.master * group_master).clamp(0.0, 1.0);
+            for channel in fixture.r#type.dimmer_channels() {
+                let value = &mut dmx[base_addr + channel];
+                *value = if dmx_state.flash {
+                    255
+                } else {
+                    (*value as f32 * factor) as u8
+                };
+            }
+        }
+
         // Send DMX data, use RECIPIENT_INTERFACE as required by the uDMX firmware
         let write_err = if let Some(h) = &handle {
             h.write_control(
@@ -1,20 +1,26 @@
 /*
- * Copyright (c) 2023-2025 Bastiaan van der Plaat
+ * Copyright (c) 2023-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use bwebview::EventLoopProxy;
-use log::debug;
+use log::{debug, error, warn};
 use serde::{Deserialize, Serialize};
 use small_websocket::{Message, WebSocket};
 
 use crate::CONFIG;
-use crate::config::FixtureType;
-use crate::dmx::{Color, DMX_STATE, Mode, ToggleTween};
+use crate::config::{FixtureType, PatchIssue};
+use crate::dmx::{
+    BEAT_SYNC, Color, DMX_STATE, DmxState, Mode, ToggleTween, beat_sensitivity,
+    set_beat_sensitivity,
+};
+use crate::recorder::list_recordings;
+use crate::shows::{LIVE_SHOW, Show, delete_show, list_shows, save_show};
 
 // MARK: IpcMessage
 #[derive(Debug, Deserialize, Serialize)]
@@ -60,11 +66,84 @@ pub(crate) enum IpcMessage {
     SetMode {
         mode: Mode,
     },
+    SetBeatSync {
+        #[serde(rename = "beatSync")]
+        beat_sync: bool,
+    },
+    SetBeatSensitivity {
+        #[serde(rename = "beatSensitivity")]
+        beat_sensitivity: f32,
+    },
+    Beat {
+        strength: f32,
+    },
+
+    // Buses
+    SetMaster {
+        master: f32,
+    },
+    SetGroupMasters {
+        #[serde(rename = "groupMasters")]
+        group_masters: Vec<f32>,
+    },
+    SetBlackout {
+        blackout: bool,
+    },
+    SetFlash {
+        flash: bool,
+    },
+
+    // Recorder
+    GetRecordings,
+    GetRecordingsResponse {
+        recordings: Vec<String>,
+    },
+    SetIsRecording {
+        #[serde(rename = "isRecording")]
+        is_recording: bool,
+    },
+    SetPlayback {
+        playback: Option<String>,
+    },
+    SetPlaybackLoop {
+        #[serde(rename = "playbackLoop")]
+        playback_loop: bool,
+    },
+    SetPlaybackSpeed {
+        #[serde(rename = "playbackSpeed")]
+        playback_speed: f32,
+    },
+
+    // Patch
+    GetPatch,
+    GetPatchResponse {
+        patch: Patch,
+    },
+
+    // Shows
+    GetShows,
+    GetShowsResponse {
+        shows: Vec<String>,
+    },
+    SaveShow {
+        name: String,
+    },
+    LoadShow {
+        name: String,
+    },
+    DeleteShow {
+        name: String,
+    },
+    UndoShow,
+    ShowLoaded {
+        state: State,
+    },
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct State {
+    pub is_running: bool,
     pub color: Color,
     pub toggle_color: Color,
     pub intensity: f32,
@@ -75,6 +154,81 @@ pub(crate) struct State {
     pub switches_labels: Option<Vec<String>>,
     pub switches_toggle: Vec<bool>,
     pub switches_press: Vec<bool>,
+    pub audio_available: bool,
+    pub beat_sync: bool,
+    pub beat_sensitivity: f32,
+    pub groups: Vec<String>,
+    pub master: f32,
+    pub group_masters: Vec<f32>,
+    pub blackout: bool,
+    pub flash: bool,
+    pub is_recording: bool,
+    pub playback: Option<String>,
+    pub playback_loop: bool,
+    pub playback_speed: f32,
+    pub show: Option<String>,
+    pub can_undo_show: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Patch {
+    pub dmx_length: usize,
+    pub fixtures: Vec<PatchFixture>,
+    #[serde(skip_deserializing)]
+    pub issues: Vec<PatchIssue>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PatchFixture {
+    pub name: String,
+    pub r#type: FixtureType,
+    pub group: Option<String>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl State {
+    fn new(dmx_state: &DmxState) -> Self {
+        let config = CONFIG.lock().expect("Failed to lock config");
+        let live_show = LIVE_SHOW.lock().expect("Failed to lock live show");
+        let groups = config.as_ref().map(|c| c.groups()).unwrap_or_default();
+        State {
+            is_running: dmx_state.is_running,
+            color: dmx_state.color,
+            toggle_color: dmx_state.toggle_color,
+            intensity: dmx_state.intensity,
+            toggle_tween: dmx_state.toggle_tween,
+            toggle_speed: dmx_state.toggle_speed.map(|d| d.as_millis() as u64),
+            strobe_speed: dmx_state.strobe_speed.map(|d| d.as_millis() as u64),
+            mode: dmx_state.mode,
+            switches_labels: config.as_ref().and_then(|c| {
+                c.fixtures
+                    .iter()
+                    .find(|f| f.r#type == FixtureType::ShowtecMultidimMKII)
+                    .and_then(|f| f.switches.clone())
+            }),
+            switches_toggle: dmx_state.switches_toggle.to_vec(),
+            switches_press: dmx_state.switches_press.to_vec(),
+            audio_available: cfg!(feature = "audio"),
+            beat_sync: BEAT_SYNC.load(Ordering::Relaxed),
+            beat_sensitivity: beat_sensitivity(),
+            groups: groups.clone(),
+            master: dmx_state.master,
+            group_masters: (0..groups.len())
+                .map(|index| dmx_state.group_masters.get(index).copied().unwrap_or(1.0))
+                .collect(),
+            blackout: dmx_state.blackout,
+            flash: dmx_state.flash,
+            is_recording: dmx_state.is_recording,
+            playback: dmx_state.playback.clone(),
+            playback_loop: dmx_state.playback_loop,
+            playback_speed: dmx_state.playback_speed,
+            show: live_show.name.clone(),
+            can_undo_show: live_show.can_undo(),
+        }
+    }
 }
 
 // MARK: IpcConnection
@@ -100,9 +254,12 @@ impl IpcConnection {
     pub(crate) fn send(&mut self, message: String) {
         match self {
             Self::WebviewIpc(event_loop_proxy) => event_loop_proxy.send_user_event(message),
-            Self::WebSocket(ws) => ws
-                .send(Message::Text(message))
-                .expect("Failed to send IPC message"),
+            Self::WebSocket(ws) => {
+                // Remote clients can disappear at any moment, their recv loop cleans them up
+                if let Err(err) = ws.send(Message::Text(message)) {
+                    warn!("Failed to send IPC message to WebSocket: {err}");
+                }
+            }
         }
     }
 
@@ -120,6 +277,25 @@ impl IpcConnection {
     }
 }
 
+/// Send message to all connections
+pub(crate) fn broadcast_all(message: IpcMessage) {
+    let message = serde_json::to_string(&message).expect("Failed to serialize IPC message");
+    for connection in IPC_CONNECTIONS
+        .lock()
+        .expect("Failed to lock IPC connections")
+        .iter_mut()
+    {
+        connection.send(message.clone());
+    }
+}
+
+/// Send the new live state to all connections after a show is saved, loaded or undone
+fn broadcast_show_loaded(dmx_state: &DmxState) {
+    broadcast_all(IpcMessage::ShowLoaded {
+        state: State::new(dmx_state),
+    });
+}
+
 // MARK: IPC Message Handler
 pub(crate) fn ipc_message_handler(mut connection: IpcConnection, message: &str) {
     let mut dmx_state = DMX_STATE.lock().expect("Failed to lock DMX state");
@@ -140,27 +316,11 @@ pub(crate) fn ipc_message_handler(mut connection: IpcConnection, message: &str)
             );
         }
         IpcMessage::GetState => {
-            let config = CONFIG.lock().expect("Failed to lock config");
-            let state = State {
-                color: dmx_state.color,
-                toggle_color: dmx_state.toggle_color,
-                intensity: dmx_state.intensity,
-                toggle_tween: dmx_state.toggle_tween,
-                toggle_speed: dmx_state.toggle_speed.map(|d| d.as_millis() as u64),
-                strobe_speed: dmx_state.strobe_speed.map(|d| d.as_millis() as u64),
-                mode: dmx_state.mode,
-                switches_labels: config.as_ref().and_then(|c| {
-                    c.fixtures
-                        .iter()
-                        .find(|f| f.r#type == FixtureType::ShowtecMultidimMKII)
-                        .and_then(|f| f.switches.clone())
-                }),
-                switches_toggle: dmx_state.switches_toggle.to_vec(),
-                switches_press: dmx_state.switches_press.to_vec(),
-            };
             connection.send(
-                serde_json::to_string(&IpcMessage::GetStateResponse { state })
-                    .expect("Failed to serialize IPC response"),
+                serde_json::to_string(&IpcMessage::GetStateResponse {
+                    state: State::new(&dmx_state),
+                })
+                .expect("Failed to serialize IPC response"),
             );
         }
 
@@ -227,6 +387,187 @@ pub(crate) fn ipc_message_handler(mut connection: IpcConnection, message: &str)
                     .expect("Failed to serialize IPC message"),
             );
         }
+        IpcMessage::SetBeatSync { beat_sync } => {
+            BEAT_SYNC.store(beat_sync, Ordering::Relaxed);
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetBeatSync { beat_sync })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+        IpcMessage::SetBeatSensitivity { beat_sensitivity } => {
+            if beat_sensitivity.is_nan() {
+                return;
+            }
+            set_beat_sensitivity(beat_sensitivity);
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetBeatSensitivity { beat_sensitivity })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+
+        // Buses
+        IpcMessage::SetMaster { master } => {
+            dmx_state.master = master;
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetMaster { master })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+        IpcMessage::SetGroupMasters { group_masters } => {
+            dmx_state.group_masters = group_masters.clone();
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetGroupMasters { group_masters })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+        IpcMessage::SetBlackout { blackout } => {
+            dmx_state.blackout = blackout;
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetBlackout { blackout })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+        IpcMessage::SetFlash { flash } => {
+            dmx_state.flash = flash;
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetFlash { flash })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+
+        // Recorder
+        IpcMessage::GetRecordings => {
+            connection.send(
+                serde_json::to_string(&IpcMessage::GetRecordingsResponse {
+                    recordings: list_recordings(),
+                })
+                .expect("Failed to serialize IPC response"),
+            );
+        }
+        IpcMessage::SetIsRecording { is_recording } => {
+            dmx_state.is_recording = is_recording;
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetIsRecording { is_recording })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+        IpcMessage::SetPlayback { playback } => {
+            dmx_state.playback = playback.clone();
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetPlayback { playback })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+        IpcMessage::SetPlaybackLoop { playback_loop } => {
+            dmx_state.playback_loop = playback_loop;
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetPlaybackLoop { playback_loop })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+        IpcMessage::SetPlaybackSpeed { playback_speed } => {
+            // Keep the speed in a range the playback clock can handle
+            let playback_speed = if playback_speed.is_nan() {
+                1.0
+            } else {
+                playback_speed.clamp(0.0, 16.0)
+            };
+            dmx_state.playback_speed = playback_speed;
+            connection.broadcast(
+                serde_json::to_string(&IpcMessage::SetPlaybackSpeed { playback_speed })
+                    .expect("Failed to serialize IPC message"),
+            );
+        }
+
+        // Patch
+        IpcMessage::GetPatch => {
+            let config = CONFIG.lock().expect("Failed to lock config");
+            let patch = config.as_ref().map_or_else(
+                || Patch {
+                    dmx_length: 0,
+                    fixtures: Vec::new(),
+                    issues: Vec::new(),
+                },
+                |config| Patch {
+                    dmx_length: config.dmx_length,
+                    fixtures: config
+                        .fixtures
+                        .iter()
+                        .map(|fixture| {
+                            let (start, end) = fixture.channels();
+                            PatchFixture {
+                                name: fixture.name.clone(),
+                                r#type: fixture.r#type.clone(),
+                                group: fixture.group.clone(),
+                                start,
+                                end,
+                            }
+                        })
+                        .collect(),
+                    issues: config.validate(),
+                },
+            );
+            connection.send(
+                serde_json::to_string(&IpcMessage::GetPatchResponse { patch })
+                    .expect("Failed to serialize IPC response"),
+            );
+        }
+
+        // Shows
+        IpcMessage::GetShows => {
+            connection.send(
+                serde_json::to_string(&IpcMessage::GetShowsResponse {
+                    shows: list_shows(),
+                })
+                .expect("Failed to serialize IPC response"),
+            );
+        }
+        IpcMessage::SaveShow { name } => {
+            if let Err(err) = save_show(&name, &Show::from_state(&dmx_state)) {
+                error!("Can't save show {name}: {err}");
+                return;
+            }
+            LIVE_SHOW.lock().expect("Failed to lock live show").name = Some(name);
+            broadcast_all(IpcMessage::GetShowsResponse {
+                shows: list_shows(),
+            });
+            broadcast_show_loaded(&dmx_state);
+        }
+        IpcMessage::LoadShow { name } => {
+            let result = LIVE_SHOW
+                .lock()
+                .expect("Failed to lock live show")
+                .load(&name, &mut dmx_state);
+            if let Err(err) = result {
+                error!("Can't load show {name}: {err}");
+                return;
+            }
+            broadcast_show_loaded(&dmx_state);
+        }
+        IpcMessage::DeleteShow { name } => {
+            if let Err(err) = delete_show(&name) {
+                error!("Can't delete show {name}: {err}");
+                return;
+            }
+            let mut live_show = LIVE_SHOW.lock().expect("Failed to lock live show");
+            if live_show.name.as_deref() == Some(name.as_str()) {
+                live_show.name = None;
+            }
+            drop(live_show);
+            broadcast_all(IpcMessage::GetShowsResponse {
+                shows: list_shows(),
+            });
+            broadcast_show_loaded(&dmx_state);
+        }
+        IpcMessage::UndoShow => {
+            if LIVE_SHOW
+                .lock()
+                .expect("Failed to lock live show")
+                .undo(&mut dmx_state)
+            {
+                broadcast_show_loaded(&dmx_state);
+            }
+        }
 
         _ => unimplemented!(),
     }
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2023-2025 Bastiaan van der Plaat
+ * Copyright (c) 2023-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -14,7 +14,7 @@ use std::time::Duration;
 use bwebview::{
     Event, EventLoopBuilder, LogicalSize, Theme, WebviewBuilder, WebviewEvent, WindowBuilder,
 };
-use log::info;
+use log::{info, warn};
 use rust_embed::Embed;
 use small_http::Response;
 use small_websocket::Message;
@@ -22,9 +22,13 @@ use small_websocket::Message;
 use crate::config::Config;
 use crate::ipc::{IPC_CONNECTIONS, IpcConnection, ipc_message_handler};
 
+#[cfg(feature = "audio")]
+mod audio;
 mod config;
 mod dmx;
 mod ipc;
+mod recorder;
+mod shows;
 mod usb;
 
 // MARK: Internal HTTP server
@@ -53,12 +57,26 @@ fn main() {
     // Load config
     let config = Config::load();
     info!("Config: {config:?}");
+    for issue in config.validate() {
+        warn!("Patch: {issue}");
+    }
     let cloned_config = config.clone();
     *CONFIG.lock().expect("Failed to lock config") = Some(config);
 
+    // Restore last show
+    if let Some(name) =
+        shows::restore_last_show(&mut dmx::DMX_STATE.lock().expect("Failed to lock DMX state"))
+    {
+        info!("Restored show {name}");
+    }
+
     // Start DMX thread
     thread::spawn(move || dmx::dmx_thread(cloned_config));
 
+    // Start audio beat detection thread
+    #[cfg(feature = "audio")]
+    thread::spawn(audio::audio_thread);
+
     // Try to get local IP address, fallback to localhost if it fails
     let listener = std::net::TcpListener::bind((std::net::Ipv4Addr::UNSPECIFIED, PORT))
         .unwrap_or_else(|_| panic!("Can't start local http server"));
@@ -71,7 +89,7 @@ fn main() {
         format!("http://127.0.0.1:{}", local_addr.port())
     };
 
-    // Start internal http server thread
+    // Start internal http server thread, also serves the remote page at /remote
     info!("Starting internal HTTP server at {url}");
     thread::spawn(move || {
         small_http::serve_single_threaded(listener, move |req| {
@@ -92,7 +110,10 @@ fn main() {
                             Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
                                 continue;
                             }
-                            Err(err) => panic!("WebSocket recv error: {err}"),
+                            Err(err) => {
+                                warn!("WebSocket recv error: {err}");
+                                break;
+                            }
                         };
                         match message {
                             Some(Message::Close(_, _)) => break,
@@ -164,7 +185,7 @@ fn main() {
         }
 
         // IPC events
-        Event::Webview(WebviewEvent::PageLoadStart) => {
+        Event::Webview(WebviewEvent::PageLoadStart(_)) => {
             IPC_CONNECTIONS
                 .lock()
                 .expect("Failed to lock IPC connections")
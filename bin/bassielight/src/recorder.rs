@@ -0,0 +1,164 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+// Constants
+const MAGIC: &[u8] = b"BLDMX1";
+const EXTENSION: &str = "bldmx";
+const FRAME_HEADER_LENGTH: usize = 6;
+
+// MARK: Recordings
+fn recordings_dir() -> PathBuf {
+    // FIXME: Don't use debug_assertions to determine the recordings path
+    if !cfg!(debug_assertions) {
+        let project_dirs =
+            directories::ProjectDirs::from("nl", "bplaat", "BassieLight").expect("Can't get dirs");
+        project_dirs.config_dir().join("recordings")
+    } else {
+        PathBuf::from("recordings")
+    }
+}
+
+/// List recording file names, newest first
+pub(crate) fn list_recordings() -> Vec<String> {
+    let mut recordings = fs::read_dir(recordings_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+                .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    recordings.sort_unstable_by(|a, b| b.cmp(a));
+    recordings
+}
+
+fn recording_path(name: &str) -> io::Result<PathBuf> {
+    // Only allow plain recording file names, they can be chosen by remote clients
+    if Path::new(name).file_name().and_then(|name| name.to_str()) != Some(name)
+        || !name.ends_with(&format!(".{EXTENSION}"))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Invalid recording name",
+        ));
+    }
+    Ok(recordings_dir().join(name))
+}
+
+// MARK: Recorder
+/// Writes outgoing DMX frames to a timestamped recording file
+///
+/// The file starts with the `BLDMX1` magic followed by frames of a little endian `u32`
+/// timestamp in milliseconds since the start of the recording, a `u16` length and the channel data.
+pub(crate) struct Recorder {
+    pub name: String,
+    writer: BufWriter<File>,
+    start: Instant,
+}
+
+impl Recorder {
+    pub(crate) fn create() -> io::Result<Self> {
+        let dir = recordings_dir();
+        fs::create_dir_all(&dir)?;
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+        let name = format!("recording-{timestamp}.{EXTENSION}");
+        let mut writer = BufWriter::new(File::create(dir.join(&name))?);
+        writer.write_all(MAGIC)?;
+        Ok(Self {
+            name,
+            writer,
+            start: Instant::now(),
+        })
+    }
+
+    pub(crate) fn write_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        let time = self.start.elapsed().as_millis() as u32;
+        self.writer.write_all(&time.to_le_bytes())?;
+        self.writer.write_all(&(frame.len() as u16).to_le_bytes())?;
+        self.writer.write_all(frame)
+    }
+
+    pub(crate) fn finish(mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+// MARK: Player
+/// Replays the frames of a recording file
+pub(crate) struct Player {
+    pub name: String,
+    frames: Vec<(Duration, Vec<u8>)>,
+    index: usize,
+    position: Duration,
+    last_update: Instant,
+}
+
+impl Player {
+    pub(crate) fn open(name: &str) -> io::Result<Self> {
+        let data = fs::read(recording_path(name)?)?;
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "Invalid recording file");
+        let mut data = data.strip_prefix(MAGIC).ok_or_else(invalid)?;
+
+        let mut frames = Vec::new();
+        while !data.is_empty() {
+            if data.len() < FRAME_HEADER_LENGTH {
+                return Err(invalid());
+            }
+            let time = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+            let length = u16::from_le_bytes([data[4], data[5]]) as usize;
+            let frame = data
+                .get(FRAME_HEADER_LENGTH..FRAME_HEADER_LENGTH + length)
+                .ok_or_else(invalid)?;
+            frames.push((Duration::from_millis(time as u64), frame.to_vec()));
+            data = &data[FRAME_HEADER_LENGTH + length..];
+        }
+        if frames.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            frames,
+            index: 0,
+            position: Duration::ZERO,
+            last_update: Instant::now(),
+        })
+    }
+
+    /// Advance the playback position, returns the current frame or `None` when finished
+    pub(crate) fn next_frame(&mut self, speed: f32, looping: bool) -> Option<&[u8]> {
+        let now = Instant::now();
+        self.position += now.duration_since(self.last_update).mul_f32(speed.max(0.0));
+        self.last_update = now;
+
+        let duration = self.frames.last()?.0;
+        if self.position > duration {
+            if !looping {
+                return None;
+            }
+            self.position = if duration.is_zero() {
+                Duration::ZERO
+            } else {
+                Duration::from_nanos((self.position.as_nanos() % duration.as_nanos()) as u64)
+            };
+            self.index = 0;
+        }
+        while self.index + 1 < self.frames.len() && self.frames[self.index + 1].0 <= self.position {
+            self.index += 1;
+        }
+        Some(&self.frames[self.index].1)
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::DMX_SWITCHES_LENGTH;
+use crate::dmx::{
+    BEAT_SYNC, Color, DmxState, Mode, ToggleTween, beat_sensitivity, set_beat_sensitivity,
+};
+
+// Constants
+const EXTENSION: &str = "json";
+const LAST_SHOW_FILE: &str = ".last-show";
+const MAX_NAME_LENGTH: usize = 64;
+
+// MARK: Show
+/// Snapshot of the live state: fixture values, active scenes and bus levels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Show {
+    pub mode: Mode,
+    pub color: Color,
+    pub toggle_color: Color,
+    pub intensity: f32,
+    pub toggle_tween: ToggleTween,
+    pub toggle_speed: Option<u64>,
+    pub strobe_speed: Option<u64>,
+    pub switches_toggle: [bool; DMX_SWITCHES_LENGTH],
+    pub beat_sync: bool,
+    pub beat_sensitivity: f32,
+    pub master: f32,
+    pub group_masters: Vec<f32>,
+    pub blackout: bool,
+}
+
+impl Show {
+    pub(crate) fn from_state(state: &DmxState) -> Self {
+        Self {
+            mode: state.mode,
+            color: state.color,
+            toggle_color: state.toggle_color,
+            intensity: state.intensity,
+            toggle_tween: state.toggle_tween,
+            toggle_speed: state.toggle_speed.map(|d| d.as_millis() as u64),
+            strobe_speed: state.strobe_speed.map(|d| d.as_millis() as u64),
+            switches_toggle: state.switches_toggle,
+            beat_sync: BEAT_SYNC.load(Ordering::Relaxed),
+            beat_sensitivity: beat_sensitivity(),
+            master: state.master,
+            group_masters: state.group_masters.clone(),
+            blackout: state.blackout,
+        }
+    }
+
+    pub(crate) fn apply_to(&self, state: &mut DmxState) {
+        state.mode = self.mode;
+        state.color = self.color;
+        state.toggle_color = self.toggle_color;
+        state.intensity = self.intensity;
+        state.toggle_tween = self.toggle_tween;
+        state.toggle_speed = self.toggle_speed.map(Duration::from_millis);
+        state.strobe_speed = self.strobe_speed.map(Duration::from_millis);
+        state.switches_toggle = self.switches_toggle;
+        BEAT_SYNC.store(self.beat_sync, Ordering::Relaxed);
+        set_beat_sensitivity(self.beat_sensitivity);
+        state.master = self.master;
+        state.group_masters = self.group_masters.clone();
+        state.blackout = self.blackout;
+    }
+}
+
+// MARK: Show files
+fn shows_dir() -> PathBuf {
+    // FIXME: Don't use debug_assertions to determine the shows path
+    if !cfg!(debug_assertions) {
+        let project_dirs =
+            directories::ProjectDirs::from("nl", "bplaat", "BassieLight").expect("Can't get dirs");
+        project_dirs.config_dir().join("shows")
+    } else {
+        PathBuf::from("shows")
+    }
+}
+
+fn show_path(name: &str) -> io::Result<PathBuf> {
+    // Only allow plain show names, they can be chosen by remote clients
+    if name.is_empty()
+        || name.len() > MAX_NAME_LENGTH
+        || name.starts_with('.')
+        || Path::new(name).file_name().and_then(|name| name.to_str()) != Some(name)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Invalid show name",
+        ));
+    }
+    Ok(shows_dir().join(format!("{name}.{EXTENSION}")))
+}
+
+/// List show names sorted alphabetically
+pub(crate) fn list_shows() -> Vec<String> {
+    let mut shows = fs::read_dir(shows_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+                .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    shows.sort_unstable_by_key(|name| name.to_lowercase());
+    shows
+}
+
+pub(crate) fn save_show(name: &str, show: &Show) -> io::Result<()> {
+    let path = show_path(name)?;
+    fs::create_dir_all(shows_dir())?;
+    serde_json::to_writer_pretty(File::create(path)?, show)?;
+    set_last_show(Some(name))
+}
+
+pub(crate) fn read_show(name: &str) -> io::Result<Show> {
+    Ok(serde_json::from_reader(File::open(show_path(name)?)?)?)
+}
+
+pub(crate) fn delete_show(name: &str) -> io::Result<()> {
+    fs::remove_file(show_path(name)?)?;
+    if last_show().as_deref() == Some(name) {
+        set_last_show(None)?;
+    }
+    Ok(())
+}
+
+/// Name of the last saved or loaded show, restored at startup
+pub(crate) fn last_show() -> Option<String> {
+    fs::read_to_string(shows_dir().join(LAST_SHOW_FILE))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+fn set_last_show(name: Option<&str>) -> io::Result<()> {
+    let path = shows_dir().join(LAST_SHOW_FILE);
+    match name {
+        Some(name) => {
+            fs::create_dir_all(shows_dir())?;
+            fs::write(path, name)
+        }
+        None => fs::remove_file(path),
+    }
+}
+
+// MARK: Live show
+/// The loaded show and the state before it was loaded, so loading can be undone
+pub(crate) struct LiveShow {
+    pub name: Option<String>,
+    undo: Option<(Option<String>, Show)>,
+}
+
+pub(crate) static LIVE_SHOW: Mutex<LiveShow> = Mutex::new(LiveShow {
+    name: None,
+    undo: None,
+});
+
+impl LiveShow {
+    pub(crate) const fn can_undo(&self) -> bool {
+        self.undo.is_some()
+    }
+
+    /// Load a show into the DMX state, the replaced state is kept for undo
+    pub(crate) fn load(&mut self, name: &str, state: &mut DmxState) -> io::Result<()> {
+        let show = read_show(name)?;
+        self.undo = Some((self.name.take(), Show::from_state(state)));
+        show.apply_to(state);
+        self.name = Some(name.to_string());
+        set_last_show(Some(name))
+    }
+
+    /// Restore the state from before the last load, undoing again redoes the load
+    pub(crate) fn undo(&mut self, state: &mut DmxState) -> bool {
+        let Some((name, show)) = self.undo.take() else {
+            return false;
+        };
+        self.undo = Some((self.name.take(), Show::from_state(state)));
+        show.apply_to(state);
+        self.name = name;
+        _ = set_last_show(self.name.as_deref());
+        true
+    }
+}
+
+/// Load the last show at startup
+pub(crate) fn restore_last_show(state: &mut DmxState) -> Option<String> {
+    let name = last_show()?;
+    let show = read_show(&name).ok()?;
+    show.apply_to(state);
+    LIVE_SHOW.lock().expect("Failed to lock live show").name = Some(name.clone());
+    Some(name)
+}
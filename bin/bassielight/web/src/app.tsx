@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -10,6 +10,8 @@ import { Menubar } from './components/menubar.tsx';
 import { Ipc } from './ipc.ts';
 import { EditorPage } from './pages/editor.tsx';
 import { NotFoundPage } from './pages/notfound.tsx';
+import { PatchPage } from './pages/patch.tsx';
+import { RemotePage } from './pages/remote.tsx';
 import { SettingsPage } from './pages/settings.tsx';
 import { StagePage } from './pages/stage.tsx';
 
@@ -23,7 +25,9 @@ export function App() {
             <Switch>
                 <Route path="/" component={StagePage} />
                 <Route path="/editor" component={EditorPage} />
+                <Route path="/patch" component={PatchPage} />
                 <Route path="/settings" component={SettingsPage} />
+                <Route path="/remote" component={RemotePage} />
                 <Route component={NotFoundPage} />
             </Switch>
         </IpcContext.Provider>
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  * Copyright (c) 2025 Leonard van der Plaat
  *
  * SPDX-License-Identifier: MIT
@@ -9,6 +9,7 @@ import {
     mdiAccount,
     mdiClose,
     mdiCog,
+    mdiGrid,
     mdiLightbulb,
     mdiLightbulbOff,
     mdiMotionPlayOutline,
@@ -29,6 +30,7 @@ function Icon({ path }: { path: string }) {
 export const AccountIcon = () => <Icon path={mdiAccount} />;
 export const CloseIcon = () => <Icon path={mdiClose} />;
 export const CogIcon = () => <Icon path={mdiCog} />;
+export const GridIcon = () => <Icon path={mdiGrid} />;
 export const LightbulbIcon = () => <Icon path={mdiLightbulb} />;
 export const LightbulbOffIcon = () => <Icon path={mdiLightbulbOff} />;
 export const MusicIcon = () => <Icon path={mdiMusic} />;
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -8,7 +8,7 @@ import { signal } from '@preact/signals';
 import { useContext, useEffect, useState } from 'preact/hooks';
 import { Link, useRoute } from 'wouter-preact';
 import { IpcContext } from '../app.tsx';
-import { CogIcon, MotionPlayOutlineIcon, QrcodeIcon, SquareEditOutlineIcon } from './icons.tsx';
+import { CogIcon, GridIcon, MotionPlayOutlineIcon, QrcodeIcon, SquareEditOutlineIcon } from './icons.tsx';
 import { QrModal } from './qrmodal.tsx';
 import './menubar.css';
 
@@ -25,6 +25,7 @@ function NavLink({ href, children }: { href: string; children: any }) {
 
 export function Menubar() {
     const ipc = useContext(IpcContext)!;
+    const [isRemote] = useRoute('/remote');
     const [showQrCode, setShowQrCode] = useState(false);
 
     useEffect(() => {
@@ -35,6 +36,9 @@ export function Menubar() {
         return () => listeners.forEach((l) => l.remove());
     }, []);
 
+    // The remote page is a standalone mobile page without menubar
+    if (isRemote) return null;
+
     return (
         <>
             <div id="menubar" class="menubar">
@@ -48,6 +52,10 @@ export function Menubar() {
                     <SquareEditOutlineIcon />
                     Editor
                 </NavLink>
+                <NavLink href="/patch">
+                    <GridIcon />
+                    Patch
+                </NavLink>
                 <NavLink href="/settings">
                     <CogIcon />
                     Settings
@@ -66,7 +74,7 @@ export function Menubar() {
                 </button>
             </div>
             {showQrCode && (
-                <QrModal contents={`http://${window.location.host}/`} onClose={() => setShowQrCode(false)} />
+                <QrModal contents={`http://${window.location.host}/remote`} onClose={() => setShowQrCode(false)} />
             )}
         </>
     );
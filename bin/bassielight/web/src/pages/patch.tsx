@@ -0,0 +1,124 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+import { useContext, useEffect, useState } from 'preact/hooks';
+import { IpcContext } from '../app.tsx';
+import './patch.css';
+
+type PatchFixture = {
+    name: string;
+    type: string;
+    group: string | null;
+    start: number;
+    end: number;
+};
+
+type PatchIssue =
+    | { type: 'overlap'; fixtures: [string, string]; start: number; end: number }
+    | { type: 'outOfRange'; fixture: string; start: number; end: number }
+    | { type: 'duplicateName'; name: string };
+
+type Patch = {
+    dmxLength: number;
+    fixtures: PatchFixture[];
+    issues: PatchIssue[];
+};
+
+const FIXTURE_COLORS = ['#3b82f6', '#22c55e', '#eab308', '#a855f7', '#06b6d4', '#f97316', '#ec4899', '#84cc16'];
+
+function formatIssue(issue: PatchIssue) {
+    if (issue.type === 'overlap') {
+        return `Fixtures ${issue.fixtures[0]} and ${issue.fixtures[1]} overlap on channels ${issue.start}-${issue.end}`;
+    }
+    if (issue.type === 'outOfRange') {
+        return `Fixture ${issue.fixture} uses channels ${issue.start}-${issue.end} outside the DMX universe`;
+    }
+    return `Fixture name ${issue.name} is used twice`;
+}
+
+export function PatchPage() {
+    const ipc = useContext(IpcContext)!;
+    const [patch, setPatch] = useState<Patch | null>(null);
+
+    useEffect(() => {
+        document.title = 'BassieLight - Patch';
+        (async () => {
+            const { patch } = (await ipc.request('getPatch')) as { patch: Patch };
+            setPatch(patch);
+        })();
+    }, []);
+
+    if (patch === null) return null;
+
+    // Find the fixtures that use each channel of the universe
+    const channels: number[][] = Array.from({ length: patch.dmxLength }, () => []);
+    patch.fixtures.forEach((fixture, index) => {
+        for (let channel = Math.max(fixture.start, 1); channel <= Math.min(fixture.end, patch.dmxLength); channel++) {
+            channels[channel - 1].push(index);
+        }
+    });
+
+    return (
+        <>
+            <div class="section">
+                <h2 class="title">Patch</h2>
+                <div class="patch-grid">
+                    {channels.map((fixtures, index) => {
+                        const fixture = fixtures.length > 0 ? patch.fixtures[fixtures[0]] : null;
+                        const isStart = fixture !== null && fixture.start === index + 1;
+                        return (
+                            <div
+                                key={index}
+                                class={`patch-channel ${fixtures.length > 1 ? 'is-conflict' : ''}`}
+                                style={
+                                    fixtures.length === 1
+                                        ? { backgroundColor: FIXTURE_COLORS[fixtures[0] % FIXTURE_COLORS.length] }
+                                        : undefined
+                                }
+                                title={
+                                    `${index + 1}` +
+                                    (fixtures.length > 0
+                                        ? `: ${fixtures.map((fixture) => patch.fixtures[fixture].name).join(', ')}`
+                                        : '')
+                                }
+                            >
+                                <span class="patch-channel-number">{index + 1}</span>
+                                {isStart && <span class="patch-channel-name">{fixture.name}</span>}
+                            </div>
+                        );
+                    })}
+                </div>
+            </div>
+
+            <div class="sidebar">
+                <h2 class="title">Issues</h2>
+                {patch.issues.length === 0 ? (
+                    <p class="block">No issues found</p>
+                ) : (
+                    patch.issues.map((issue, index) => (
+                        <p key={index} class="patch-issue">
+                            {formatIssue(issue)}
+                        </p>
+                    ))
+                )}
+
+                <h2 class="title">Fixtures</h2>
+                {patch.fixtures.map((fixture, index) => (
+                    <div key={index} class="patch-fixture">
+                        <span
+                            class="patch-fixture-color"
+                            style={{ backgroundColor: FIXTURE_COLORS[index % FIXTURE_COLORS.length] }}
+                        />
+                        <span class="patch-fixture-name">{fixture.name}</span>
+                        <span>
+                            {fixture.start}-{fixture.end}
+                        </span>
+                    </div>
+                ))}
+            </div>
+        </>
+    );
+}
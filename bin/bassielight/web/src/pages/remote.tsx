@@ -0,0 +1,201 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+import { useContext, useEffect, useState } from 'preact/hooks';
+import { IpcContext } from '../app.tsx';
+import { $dmxLive } from '../components/menubar.tsx';
+import { capitalize } from '../utils.ts';
+import { COLORS, MODES, useIpcState } from './stage.tsx';
+import './stage.css';
+import './remote.css';
+
+export function RemotePage() {
+    const ipc = useContext(IpcContext)!;
+
+    const [selectedMode, setSelectedMode] = useIpcState('mode');
+    const [selectedColor, setSelectedColor] = useIpcState('color');
+    const [selectedToggleColor, setSelectedToggleColor] = useIpcState('toggleColor');
+    const [intensity, setIntensity] = useIpcState('intensity');
+    const [master, setMaster] = useIpcState('master');
+    const [groups, setGroups] = useState<string[]>([]);
+    const [groupMasters, setGroupMasters] = useIpcState('groupMasters');
+    const [blackout, setBlackout] = useIpcState('blackout');
+    const [flash, setFlash] = useIpcState('flash');
+    const [switchesLabels, setSwitchesLabels] = useState<string[] | null>(null);
+    const [switchesToggle, setSwitchesToggle] = useIpcState('switchesToggle');
+
+    useEffect(() => {
+        document.title = 'BassieLight - Remote';
+
+        // Mirror the DMX status, the remote doesn't start or stop DMX itself
+        const listeners = [
+            ipc.on('start', () => ($dmxLive.value = true)),
+            ipc.on('stop', () => ($dmxLive.value = false)),
+        ];
+
+        // Load initial state
+        (async () => {
+            const { state } = (await ipc.request('getState')) as {
+                state: {
+                    isRunning: boolean;
+                    mode: string;
+                    color: number;
+                    toggleColor: number;
+                    intensity: number;
+                    groups: string[];
+                    master: number;
+                    groupMasters: number[];
+                    blackout: boolean;
+                    flash: boolean;
+                    switchesLabels: string[] | null;
+                    switchesToggle: boolean[];
+                };
+            };
+            $dmxLive.value = state.isRunning;
+            setSelectedMode(state.mode, false);
+            setSelectedColor(state.color, false);
+            setSelectedToggleColor(state.toggleColor, false);
+            setIntensity(state.intensity, false);
+            setGroups(state.groups);
+            setMaster(state.master, false);
+            setGroupMasters(state.groupMasters, false);
+            setBlackout(state.blackout, false);
+            setFlash(state.flash, false);
+            setSwitchesLabels(state.switchesLabels);
+            setSwitchesToggle(state.switchesToggle, false);
+        })();
+
+        return () => listeners.forEach((l) => l.remove());
+    }, []);
+
+    return (
+        <div class="remote">
+            <div class="remote-header">
+                <h1 class="remote-title">BassieLight</h1>
+                <div class="menubar-status">
+                    <span class={`menubar-dot ${$dmxLive.value ? 'is-success' : 'is-danger'}`} />
+                    {$dmxLive.value ? 'DMX is live' : 'DMX is off'}
+                </div>
+            </div>
+
+            <h2 class="title">Master</h2>
+            <div class="buttons">
+                <button
+                    class={`button is-pill ${blackout ? 'is-selected' : ''}`}
+                    onClick={() => setBlackout(!blackout)}
+                >
+                    Blackout
+                </button>
+                <button
+                    class={`button is-pill ${flash ? 'is-selected' : ''}`}
+                    onPointerDown={() => setFlash(true)}
+                    onPointerUp={() => setFlash(false)}
+                    onPointerLeave={() => flash && setFlash(false)}
+                >
+                    Flash
+                </button>
+            </div>
+            <p class="block">Grand Master</p>
+            <input
+                class="slider"
+                type="range"
+                min="0"
+                max="1"
+                step="0.01"
+                value={master ?? 0}
+                onInput={(e) => setMaster(parseFloat((e.target as HTMLInputElement).value))}
+            />
+            {groups.map((group, index) => (
+                <div key={group}>
+                    <p class="block">{group}</p>
+                    <input
+                        class="slider"
+                        type="range"
+                        min="0"
+                        max="1"
+                        step="0.01"
+                        value={groupMasters?.[index] ?? 0}
+                        onInput={(e) => {
+                            const newGroupMasters = [...groupMasters];
+                            newGroupMasters[index] = parseFloat((e.target as HTMLInputElement).value);
+                            setGroupMasters(newGroupMasters);
+                        }}
+                    />
+                </div>
+            ))}
+
+            <h2 class="title">Mode</h2>
+            <div class="buttons">
+                {MODES.map((mode) => (
+                    <button
+                        key={mode.type}
+                        class={`button is-expanded ${mode.type === selectedMode ? 'is-selected' : ''}`}
+                        onClick={() => setSelectedMode(mode.type)}
+                    >
+                        <mode.icon />
+                        {capitalize(mode.type)}
+                    </button>
+                ))}
+            </div>
+
+            <h2 class="title">Color</h2>
+            <div class="buttons">
+                {COLORS.map((color) => (
+                    <button
+                        key={color}
+                        class={`swatch ${color === selectedColor ? 'is-selected' : ''}`}
+                        style={{ backgroundColor: `#${color.toString(16).padStart(6, '0')}` }}
+                        onClick={() => setSelectedColor(color)}
+                    />
+                ))}
+            </div>
+
+            <h2 class="title">Toggle Color</h2>
+            <div class="buttons">
+                {COLORS.map((color) => (
+                    <button
+                        key={color}
+                        class={`swatch ${color === selectedToggleColor ? 'is-selected' : ''}`}
+                        style={{ backgroundColor: `#${color.toString(16).padStart(6, '0')}` }}
+                        onClick={() => setSelectedToggleColor(color)}
+                    />
+                ))}
+            </div>
+
+            <h2 class="title">Intensity</h2>
+            <input
+                class="slider"
+                type="range"
+                min="0"
+                max="1"
+                step="0.01"
+                value={intensity ?? 0}
+                onInput={(e) => setIntensity(parseFloat((e.target as HTMLInputElement).value))}
+            />
+
+            {switchesLabels && (
+                <>
+                    <h2 class="title">Switches</h2>
+                    <div class="buttons">
+                        {switchesLabels.map((label, index) => (
+                            <button
+                                key={`toggle-${index}`}
+                                class={`button is-pill ${switchesToggle[index] ? 'is-selected' : ''}`}
+                                onClick={() => {
+                                    const newToggles = [...switchesToggle];
+                                    newToggles[index] = !newToggles[index];
+                                    setSwitchesToggle(newToggles);
+                                }}
+                            >
+                                {label || `Toggle ${index + 1}`}
+                            </button>
+                        ))}
+                    </div>
+                </>
+            )}
+        </div>
+    );
+}
@@ -1,6 +1,6 @@
 /*
  * Copyright (c) 2025 Leonard van der Plaat
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -12,20 +12,21 @@ import { $dmxLive } from '../components/menubar.tsx';
 import { capitalize } from '../utils.ts';
 import './stage.css';
 
-const COLORS = [0x000000, 0xff0000, 0x00ff00, 0x0000ff, 0xffff00, 0xff00ff, 0x00ffff, 0xffffff];
+export const COLORS = [0x000000, 0xff0000, 0x00ff00, 0x0000ff, 0xffff00, 0xff00ff, 0x00ffff, 0xffffff];
 const SPEEDS = [null, 22, 50, 100, 200, 250, 500, 1000];
+const PLAYBACK_SPEEDS = [0.5, 1, 2];
 const TWEENS = [
     { type: 'direct', icon: TweenDirect },
     { type: 'linear', icon: TweenLinear },
     { type: 'ease', icon: TweenEase },
 ];
-const MODES = [
+export const MODES = [
     { type: 'black', icon: LightbulbOffIcon },
     { type: 'manual', icon: AccountIcon },
     { type: 'auto', icon: MusicIcon },
 ];
 
-function useIpcState(key: string): [any, (value: any, isUserInitiated?: boolean) => void] {
+export function useIpcState(key: string): [any, (value: any, isUserInitiated?: boolean) => void] {
     const ipc = useContext(IpcContext)!;
     const [value, setValue] = useState(undefined);
     const setMessageType = `set${capitalize(key)}`;
@@ -82,6 +83,38 @@ function TapTempoButton({
     );
 }
 
+function formatRecording(name: string): string {
+    const timestamp = parseInt(name.replace(/^recording-/, ''));
+    return isNaN(timestamp) ? name : new Date(timestamp * 1000).toLocaleString();
+}
+
+type State = {
+    color: number;
+    toggleColor: number;
+    intensity: number;
+    toggleTween: string;
+    toggleSpeed: number | null;
+    strobeSpeed: number | null;
+    mode: string;
+    switchesLabels: string[] | null;
+    switchesToggle: boolean[];
+    switchesPress: boolean[];
+    audioAvailable: boolean;
+    beatSync: boolean;
+    beatSensitivity: number;
+    groups: string[];
+    master: number;
+    groupMasters: number[];
+    blackout: boolean;
+    flash: boolean;
+    isRecording: boolean;
+    playback: string | null;
+    playbackLoop: boolean;
+    playbackSpeed: number;
+    show: string | null;
+    canUndoShow: boolean;
+};
+
 export function StagePage() {
     const ipc = useContext(IpcContext)!;
 
@@ -95,36 +128,92 @@ export function StagePage() {
     const [switchesToggle, setSwitchesToggle] = useIpcState('switchesToggle');
     const [switchesPress, setSwitchesPress] = useIpcState('switchesPress');
     const [selectedMode, setSelectedMode] = useIpcState('mode');
+    const [audioAvailable, setAudioAvailable] = useState(false);
+    const [beatSync, setBeatSync] = useIpcState('beatSync');
+    const [beatSensitivity, setBeatSensitivity] = useIpcState('beatSensitivity');
+    const [isBeat, setIsBeat] = useState(false);
+    const [master, setMaster] = useIpcState('master');
+    const [groups, setGroups] = useState<string[]>([]);
+    const [groupMasters, setGroupMasters] = useIpcState('groupMasters');
+    const [blackout, setBlackout] = useIpcState('blackout');
+    const [flash, setFlash] = useIpcState('flash');
+    const [isRecording, setIsRecording] = useIpcState('isRecording');
+    const [playback, setPlayback] = useIpcState('playback');
+    const [playbackLoop, setPlaybackLoop] = useIpcState('playbackLoop');
+    const [playbackSpeed, setPlaybackSpeed] = useIpcState('playbackSpeed');
+    const [recordings, setRecordings] = useState<string[]>([]);
+    const [shows, setShows] = useState<string[]>([]);
+    const [show, setShow] = useState<string | null>(null);
+    const [canUndoShow, setCanUndoShow] = useState(false);
+    const [showName, setShowName] = useState('');
+
+    const applyState = (state: State) => {
+        setSelectedColor(state.color, false);
+        setSelectedToggleColor(state.toggleColor, false);
+        setIntensity(state.intensity, false);
+        setSelectedToggleTween(state.toggleTween, false);
+        setSelectedToggleSpeed(state.toggleSpeed, false);
+        setSelectedStrobeSpeed(state.strobeSpeed, false);
+        setSelectedMode(state.mode, false);
+        setSwitchesLabels(state.switchesLabels);
+        setSwitchesToggle(state.switchesToggle, false);
+        setSwitchesPress(state.switchesPress, false);
+        setAudioAvailable(state.audioAvailable);
+        setBeatSync(state.beatSync, false);
+        setBeatSensitivity(state.beatSensitivity, false);
+        setGroups(state.groups);
+        setMaster(state.master, false);
+        setGroupMasters(state.groupMasters, false);
+        setBlackout(state.blackout, false);
+        setFlash(state.flash, false);
+        setIsRecording(state.isRecording, false);
+        setPlayback(state.playback, false);
+        setPlaybackLoop(state.playbackLoop, false);
+        setPlaybackSpeed(state.playbackSpeed, false);
+        setShow(state.show);
+        setCanUndoShow(state.canUndoShow);
+    };
+
+    // Reload recordings when a recording is finished
+    useEffect(() => {
+        if (isRecording === false) {
+            (async () => {
+                const { recordings } = (await ipc.request('getRecordings')) as { recordings: string[] };
+                setRecordings(recordings);
+            })();
+        }
+    }, [isRecording]);
+
+    // Keep shows in sync, saving, loading or undoing a show replaces the whole live state
+    useEffect(() => {
+        const listeners = [
+            ipc.on('getShowsResponse', (data) => setShows((data as { shows: string[] }).shows)),
+            ipc.on('showLoaded', (data) => applyState((data as { state: State }).state)),
+        ];
+        ipc.send('getShows');
+        return () => listeners.forEach((l) => l.remove());
+    }, []);
+
+    useEffect(() => {
+        let timeout: number | undefined;
+        const listener = ipc.on('beat', () => {
+            setIsBeat(true);
+            clearTimeout(timeout);
+            timeout = setTimeout(() => setIsBeat(false), 100);
+        });
+        return () => {
+            listener.remove();
+            clearTimeout(timeout);
+        };
+    }, []);
 
     useEffect(() => {
         document.title = 'BassieLight - Stage';
 
         // Load initial state
         (async () => {
-            const { state } = (await ipc.request('getState')) as {
-                state: {
-                    color: number;
-                    toggleColor: number;
-                    intensity: number;
-                    toggleTween: string;
-                    toggleSpeed: number | null;
-                    strobeSpeed: number | null;
-                    mode: string;
-                    switchesLabels: string[] | null;
-                    switchesToggle: boolean[];
-                    switchesPress: boolean[];
-                };
-            };
-            setSelectedColor(state.color, false);
-            setSelectedToggleColor(state.toggleColor, false);
-            setIntensity(state.intensity, false);
-            setSelectedToggleTween(state.toggleTween, false);
-            setSelectedToggleSpeed(state.toggleSpeed, false);
-            setSelectedStrobeSpeed(state.strobeSpeed, false);
-            setSelectedMode(state.mode, false);
-            setSwitchesLabels(state.switchesLabels);
-            setSwitchesToggle(state.switchesToggle, false);
-            setSwitchesPress(state.switchesPress, false);
+            const { state } = (await ipc.request('getState')) as { state: State };
+            applyState(state);
         })();
 
         // Start DMX on mount, stop on unmount
@@ -156,6 +245,52 @@ export function StagePage() {
             </div>
 
             <div class="sidebar">
+                <h2 class="title">Master</h2>
+                <div class="buttons">
+                    <button
+                        class={`button is-pill ${blackout ? 'is-selected' : ''}`}
+                        onClick={() => setBlackout(!blackout)}
+                    >
+                        Blackout
+                    </button>
+                    <button
+                        class={`button is-pill ${flash ? 'is-selected' : ''}`}
+                        onPointerDown={() => setFlash(true)}
+                        onPointerUp={() => setFlash(false)}
+                        onPointerLeave={() => flash && setFlash(false)}
+                    >
+                        Flash
+                    </button>
+                </div>
+                <p class="block">Grand Master</p>
+                <input
+                    class="slider"
+                    type="range"
+                    min="0"
+                    max="1"
+                    step="0.01"
+                    value={master ?? 0}
+                    onInput={(e) => setMaster(parseFloat((e.target as HTMLInputElement).value))}
+                />
+                {groups.map((group, index) => (
+                    <div key={group}>
+                        <p class="block">{group}</p>
+                        <input
+                            class="slider"
+                            type="range"
+                            min="0"
+                            max="1"
+                            step="0.01"
+                            value={groupMasters?.[index] ?? 0}
+                            onInput={(e) => {
+                                const newGroupMasters = [...groupMasters];
+                                newGroupMasters[index] = parseFloat((e.target as HTMLInputElement).value);
+                                setGroupMasters(newGroupMasters);
+                            }}
+                        />
+                    </div>
+                ))}
+
                 <h2 class="title">Color</h2>
                 <div class="buttons">
                     {COLORS.map((color) => (
@@ -233,6 +368,118 @@ export function StagePage() {
                     <TapTempoButton selectedSpeed={selectedStrobeSpeed} onSpeedChange={setSelectedStrobeSpeed} />
                 </div>
 
+                {audioAvailable && (
+                    <>
+                        <h2 class="title">Beat Sync</h2>
+                        <div class="buttons">
+                            <button
+                                class={`button is-pill ${beatSync ? 'is-selected' : ''}`}
+                                onClick={() => setBeatSync(!beatSync)}
+                            >
+                                {beatSync ? 'On' : 'Off'}
+                            </button>
+                            <span class={`beat-indicator ${isBeat ? 'is-active' : ''}`} title="Beat" />
+                        </div>
+                        <p class="block">Sensitivity</p>
+                        <input
+                            class="slider"
+                            type="range"
+                            min="0"
+                            max="1"
+                            step="0.01"
+                            value={beatSensitivity ?? 0}
+                            onInput={(e) => setBeatSensitivity(parseFloat((e.target as HTMLInputElement).value))}
+                        />
+                    </>
+                )}
+
+                <h2 class="title">Recorder</h2>
+                <div class="buttons">
+                    <button
+                        class={`button is-pill ${isRecording ? 'is-selected' : ''}`}
+                        onClick={() => setIsRecording(!isRecording)}
+                    >
+                        {isRecording ? 'Stop' : 'Record'}
+                    </button>
+                    <button
+                        class={`button is-pill ${playbackLoop ? 'is-selected' : ''}`}
+                        onClick={() => setPlaybackLoop(!playbackLoop)}
+                    >
+                        Loop
+                    </button>
+                    {PLAYBACK_SPEEDS.map((speed) => (
+                        <button
+                            key={speed}
+                            class={`button is-pill ${speed === playbackSpeed ? 'is-selected' : ''}`}
+                            onClick={() => setPlaybackSpeed(speed)}
+                        >
+                            {`${speed}x`}
+                        </button>
+                    ))}
+                </div>
+                {recordings.length > 0 && (
+                    <>
+                        <p class="block">Playback</p>
+                        <div class="buttons">
+                            {recordings.map((name) => (
+                                <button
+                                    key={name}
+                                    class={`button is-pill ${name === playback ? 'is-selected' : ''}`}
+                                    onClick={() => setPlayback(name === playback ? null : name)}
+                                >
+                                    {formatRecording(name)}
+                                </button>
+                            ))}
+                        </div>
+                    </>
+                )}
+
+                <h2 class="title">Shows</h2>
+                <div class="buttons">
+                    <input
+                        class="input"
+                        type="text"
+                        placeholder="Show name"
+                        maxLength={64}
+                        value={showName}
+                        onInput={(e) => setShowName((e.target as HTMLInputElement).value)}
+                    />
+                    <button
+                        class="button is-pill"
+                        disabled={showName.trim() === ''}
+                        onClick={() => {
+                            ipc.send('saveShow', { name: showName.trim() });
+                            setShowName('');
+                        }}
+                    >
+                        Save
+                    </button>
+                    <button class="button is-pill" disabled={!canUndoShow} onClick={() => ipc.send('undoShow')}>
+                        Undo
+                    </button>
+                </div>
+                {shows.length > 0 && (
+                    <div class="buttons">
+                        {shows.map((name) => (
+                            <div key={name} class="show">
+                                <button
+                                    class={`button is-pill ${name === show ? 'is-selected' : ''}`}
+                                    onClick={() => ipc.send('loadShow', { name })}
+                                >
+                                    {name}
+                                </button>
+                                <button
+                                    class="button"
+                                    title="Delete show"
+                                    onClick={() => ipc.send('deleteShow', { name })}
+                                >
+                                    &times;
+                                </button>
+                            </div>
+                        ))}
+                    </div>
+                )}
+
                 {switchesLabels && (
                     <>
                         <h2 class="title">Switches</h2>
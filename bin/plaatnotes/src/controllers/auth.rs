@@ -143,7 +143,7 @@ pub(crate) fn auth_logout(_req: &Request, ctx: &Context) -> Result<Response> {
         }
     )?;
 
-    Ok(Response::with_status(Status::NoContent))
+    Ok(Response::no_content())
 }
 
 // MARK: Utils
@@ -240,7 +240,7 @@ pub(crate) fn notes_delete(req: &Request, ctx: &Context) -> Result<Response> {
         .execute("DELETE FROM notes WHERE id = ?", note.id)?;
 
     // Success response
-    Ok(Response::with_status(Status::NoContent))
+    Ok(Response::no_content())
 }
 
 // MARK: Utils
@@ -82,7 +82,7 @@ pub(crate) fn sessions_delete(req: &Request, ctx: &Context) -> Result<Response>
         .execute("DELETE FROM sessions WHERE id = ?", session.id)?;
 
     // Success response
-    Ok(Response::with_status(Status::NoContent))
+    Ok(Response::no_content())
 }
 
 pub(crate) fn users_sessions(req: &Request, ctx: &Context) -> Result<Response> {
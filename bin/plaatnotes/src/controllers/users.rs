@@ -293,7 +293,7 @@ pub(crate) fn users_change_password(req: &Request, ctx: &Context) -> Result<Resp
     )?;
 
     // Success response
-    Ok(Response::with_status(Status::NoContent))
+    Ok(Response::no_content())
 }
 
 pub(crate) fn users_delete(req: &Request, ctx: &Context) -> Result<Response> {
@@ -319,7 +319,7 @@ pub(crate) fn users_delete(req: &Request, ctx: &Context) -> Result<Response> {
         .execute("DELETE FROM users WHERE id = ?", user.id)?;
 
     // Success response
-    Ok(Response::with_status(Status::NoContent))
+    Ok(Response::no_content())
 }
 
 pub(crate) fn users_login(req: &Request, ctx: &Context) -> Result<Response> {
@@ -431,7 +431,7 @@ pub(crate) fn users_notes_trashed_clear(req: &Request, ctx: &Context) -> Result<
         Args { user_id: user.id }
     )?;
 
-    Ok(Response::with_status(Status::NoContent))
+    Ok(Response::no_content())
 }
 
 fn users_notes_reorder_handler(req: &Request, ctx: &Context, filter: &str) -> Result<Response> {
@@ -455,7 +455,7 @@ fn users_notes_reorder_handler(req: &Request, ctx: &Context, filter: &str) -> Re
         Err(e) => return Ok(Response::with_status(e)),
     };
     crate::controllers::notes::notes_reorder_for(ctx, &user, &body.ids, filter)?;
-    Ok(Response::with_status(Status::NoContent))
+    Ok(Response::no_content())
 }
 
 // MARK: Utils
@@ -0,0 +1,77 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, bail};
+use small_http::{Client, Request, Status};
+use url::Url;
+
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_REDIRECTS: usize = 10;
+
+// MARK: Download
+/// Download a database from an HTTP(S) URL to a new temp file and return its path
+pub(crate) fn download_database(url: &str) -> Result<PathBuf> {
+    let url = Url::parse(url)?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        bail!("Only http and https URLs are supported");
+    }
+
+    // Prefix the file name with a timestamp, so downloading the same URL twice gives two files
+    let name = url
+        .path()
+        .rsplit('/')
+        .next()
+        .map(|name| {
+            name.chars()
+                .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+                .collect::<String>()
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "database.db".to_string());
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+    let dir = env::temp_dir().join("SequelExplorer");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{timestamp}-{name}"));
+
+    // Stream the body to the file, so large databases aren't kept in memory
+    if let Err(err) = write_response(&url, &path) {
+        _ = fs::remove_file(&path);
+        return Err(err);
+    }
+    Ok(path)
+}
+
+fn write_response(url: &Url, path: &Path) -> Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    let res = Client::new()
+        .read_timeout(READ_TIMEOUT)
+        .max_redirects(MAX_REDIRECTS)
+        .fetch_to_writer(Request::get(url.to_string()), &mut file)?;
+    file.flush()?;
+    if res.status != Status::Ok {
+        bail!("Server responded with {}", res.status);
+    }
+
+    let mut header = [0; SQLITE_HEADER.len()];
+    if File::open(path)?.read_exact(&mut header).is_err() || &header != SQLITE_HEADER {
+        bail!("Downloaded file is not a SQLite database");
+    }
+    Ok(())
+}
+
+/// Remove a downloaded database with its journal files
+pub(crate) fn remove_downloaded_database(path: &str) {
+    for suffix in ["", "-journal", "-wal", "-shm"] {
+        _ = fs::remove_file(format!("{path}{suffix}"));
+    }
+}
@@ -0,0 +1,273 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::Result;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use bsqlite::{Connection, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::column_value_to_json;
+
+// MARK: Export options
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ExportFormat {
+    Csv,
+    Json,
+    Sql,
+}
+
+impl ExportFormat {
+    pub(crate) const fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Sql => "sql",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum CsvQuote {
+    #[default]
+    Minimal,
+    All,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExportOptions {
+    pub format: ExportFormat,
+    #[serde(default = "default_delimiter")]
+    delimiter: char,
+    #[serde(default)]
+    quote: CsvQuote,
+}
+
+const fn default_delimiter() -> char {
+    ','
+}
+
+// MARK: Export
+/// Export the rows of a query to a file, rows are written while stepping so the result is
+/// never fully loaded in memory. Returns the number of exported rows.
+pub(crate) fn export_query(
+    conn: &Connection,
+    sql: &str,
+    params: Vec<Value>,
+    name: &str,
+    options: &ExportOptions,
+    path: &Path,
+) -> Result<u64> {
+    let mut stmt = conn.prepare::<()>(sql)?;
+    for (index, param) in params.into_iter().enumerate() {
+        stmt.bind_value(index as i32, param)?;
+    }
+    let columns = (0..stmt.column_count())
+        .map(|index| stmt.column_name(index))
+        .collect::<Vec<_>>();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut exporter: Box<dyn Exporter> = match options.format {
+        ExportFormat::Csv => Box::new(CsvExporter {
+            delimiter: options.delimiter,
+            quote: options.quote,
+        }),
+        ExportFormat::Json => Box::new(JsonExporter),
+        ExportFormat::Sql => Box::new(SqlExporter {
+            table: name.to_string(),
+        }),
+    };
+
+    exporter.write_header(&mut writer, &columns)?;
+    let mut rows = 0;
+    while stmt.step()?.is_some() {
+        let values = (0..columns.len() as i32)
+            .map(|index| stmt.column_value(index))
+            .collect::<Vec<_>>();
+        exporter.write_row(&mut writer, &columns, values, rows)?;
+        rows += 1;
+    }
+    exporter.write_footer(&mut writer, rows)?;
+    writer.flush()?;
+    Ok(rows)
+}
+
+trait Exporter {
+    fn write_header(&mut self, writer: &mut dyn Write, columns: &[String]) -> Result<()>;
+    fn write_row(
+        &mut self,
+        writer: &mut dyn Write,
+        columns: &[String],
+        values: Vec<Value>,
+        index: u64,
+    ) -> Result<()>;
+    fn write_footer(&mut self, writer: &mut dyn Write, rows: u64) -> Result<()>;
+}
+
+// MARK: CSV
+struct CsvExporter {
+    delimiter: char,
+    quote: CsvQuote,
+}
+
+impl CsvExporter {
+    fn field(&self, field: &str) -> String {
+        let needs_quotes = match self.quote {
+            CsvQuote::All => true,
+            CsvQuote::Minimal => field.contains([self.delimiter, '"', '\n', '\r']),
+        };
+        if needs_quotes {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+
+    fn write_line(&self, writer: &mut dyn Write, fields: &[String]) -> Result<()> {
+        let line = fields
+            .iter()
+            .map(|field| self.field(field))
+            .collect::<Vec<_>>()
+            .join(&self.delimiter.to_string());
+        writer.write_all(line.as_bytes())?;
+        writer.write_all(b"\r\n")?;
+        Ok(())
+    }
+}
+
+impl Exporter for CsvExporter {
+    fn write_header(&mut self, writer: &mut dyn Write, columns: &[String]) -> Result<()> {
+        self.write_line(writer, columns)
+    }
+
+    fn write_row(
+        &mut self,
+        writer: &mut dyn Write,
+        _columns: &[String],
+        values: Vec<Value>,
+        _index: u64,
+    ) -> Result<()> {
+        let fields = values
+            .into_iter()
+            .map(|value| match value {
+                Value::Null => String::new(),
+                Value::Integer(i) => i.to_string(),
+                Value::Float(f) => f.to_string(),
+                Value::Text(s) => s,
+                Value::Blob(b) => BASE64_STANDARD.encode(b),
+            })
+            .collect::<Vec<_>>();
+        self.write_line(writer, &fields)
+    }
+
+    fn write_footer(&mut self, _writer: &mut dyn Write, _rows: u64) -> Result<()> {
+        Ok(())
+    }
+}
+
+// MARK: JSON
+struct JsonExporter;
+
+impl Exporter for JsonExporter {
+    fn write_header(&mut self, writer: &mut dyn Write, _columns: &[String]) -> Result<()> {
+        writer.write_all(b"[")?;
+        Ok(())
+    }
+
+    fn write_row(
+        &mut self,
+        writer: &mut dyn Write,
+        columns: &[String],
+        values: Vec<Value>,
+        index: u64,
+    ) -> Result<()> {
+        // Objects are written by hand to keep the column order of the result
+        writer.write_all(if index == 0 { b"\n  {" } else { b",\n  {" })?;
+        for (column_index, (column, value)) in columns.iter().zip(values).enumerate() {
+            if column_index > 0 {
+                writer.write_all(b", ")?;
+            }
+            serde_json::to_writer(&mut *writer, column)?;
+            writer.write_all(b": ")?;
+            serde_json::to_writer(&mut *writer, &column_value_to_json(value))?;
+        }
+        writer.write_all(b"}")?;
+        Ok(())
+    }
+
+    fn write_footer(&mut self, writer: &mut dyn Write, rows: u64) -> Result<()> {
+        writer.write_all(if rows > 0 { b"\n]\n" } else { b"]\n" })?;
+        Ok(())
+    }
+}
+
+// MARK: SQL
+struct SqlExporter {
+    table: String,
+}
+
+fn sql_literal(value: Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Integer(i) => i.to_string(),
+        // SQLite reads overflowing literals as infinity and stores NaN as NULL
+        Value::Float(f) if f.is_nan() => "NULL".to_string(),
+        Value::Float(f) if f.is_infinite() && f > 0.0 => "9e999".to_string(),
+        Value::Float(f) if f.is_infinite() => "-9e999".to_string(),
+        Value::Float(f) => format!("{f:?}"),
+        Value::Text(s) => format!("'{}'", s.replace('\'', "''")),
+        Value::Blob(b) => format!(
+            "X'{}'",
+            b.iter()
+                .map(|byte| format!("{byte:02X}"))
+                .collect::<String>()
+        ),
+    }
+}
+
+impl Exporter for SqlExporter {
+    fn write_header(&mut self, writer: &mut dyn Write, _columns: &[String]) -> Result<()> {
+        writer.write_all(b"BEGIN TRANSACTION;\n")?;
+        Ok(())
+    }
+
+    fn write_row(
+        &mut self,
+        writer: &mut dyn Write,
+        columns: &[String],
+        values: Vec<Value>,
+        _index: u64,
+    ) -> Result<()> {
+        writeln!(
+            writer,
+            "INSERT INTO \"{}\" ({}) VALUES ({});",
+            self.table.replace('"', "\"\""),
+            columns
+                .iter()
+                .map(|column| format!("\"{}\"", column.replace('"', "\"\"")))
+                .collect::<Vec<_>>()
+                .join(", "),
+            values
+                .into_iter()
+                .map(sql_literal)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        Ok(())
+    }
+
+    fn write_footer(&mut self, writer: &mut dyn Write, _rows: u64) -> Result<()> {
+        writer.write_all(b"COMMIT;\n")?;
+        Ok(())
+    }
+}
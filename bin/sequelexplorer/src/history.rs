@@ -0,0 +1,130 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use bsqlite::{Connection, FromRow, OpenMode, run_migrations};
+use serde::{Deserialize, Serialize};
+
+use crate::like_pattern;
+use crate::settings::config_dir;
+
+const HISTORY_MAX: i64 = 1000;
+
+#[derive(FromRow, Serialize)]
+pub(crate) struct HistoryEntry {
+    id: i64,
+    database: String,
+    sql: String,
+    duration_ms: f64,
+    error: Option<String>,
+    created_at: i64,
+}
+
+#[derive(FromRow, Serialize)]
+pub(crate) struct Snippet {
+    id: i64,
+    name: String,
+    sql: String,
+    created_at: i64,
+    updated_at: i64,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SnippetBody {
+    pub name: String,
+    pub sql: String,
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as i64)
+}
+
+/// Open the history database in the config directory, the history is only kept in memory
+/// when the config directory isn't available
+pub(crate) fn open_history() -> Result<Connection> {
+    let conn = match config_dir() {
+        Some(config_dir) => Connection::open(config_dir.join("history.db"), OpenMode::ReadWrite)?,
+        None => Connection::open_memory()?,
+    };
+    run_migrations!(conn, "src/migrations")?;
+    Ok(conn)
+}
+
+// MARK: History
+pub(crate) fn add_history_entry(
+    conn: &Connection,
+    database: &str,
+    sql: &str,
+    duration_ms: f64,
+    error: Option<String>,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO history (database, sql, duration_ms, error, created_at) VALUES (?, ?, ?, ?, ?)",
+        (
+            database.to_string(),
+            sql.to_string(),
+            duration_ms,
+            error,
+            now_ms(),
+        ),
+    )?;
+    conn.execute(
+        "DELETE FROM history WHERE id <= (SELECT id FROM history ORDER BY id DESC LIMIT 1 OFFSET ?)",
+        HISTORY_MAX,
+    )?;
+    Ok(())
+}
+
+pub(crate) fn search_history(
+    conn: &Connection,
+    search: &str,
+    limit: i64,
+) -> Result<Vec<HistoryEntry>> {
+    Ok(conn
+        .query::<HistoryEntry>(
+            format!(
+                "SELECT {} FROM history WHERE sql LIKE ? ESCAPE '\\' ORDER BY id DESC LIMIT ?",
+                HistoryEntry::columns()
+            ),
+            (like_pattern(search), limit),
+        )?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+pub(crate) fn clear_history(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM history", ())?;
+    Ok(())
+}
+
+// MARK: Snippets
+pub(crate) fn list_snippets(conn: &Connection) -> Result<Vec<Snippet>> {
+    Ok(conn
+        .query::<Snippet>(
+            format!("SELECT {} FROM snippets ORDER BY name", Snippet::columns()),
+            (),
+        )?
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Save a snippet, a snippet with the same name is overwritten
+pub(crate) fn save_snippet(conn: &Connection, snippet: SnippetBody) -> Result<()> {
+    let now = now_ms();
+    conn.execute(
+        "INSERT INTO snippets (name, sql, created_at, updated_at) VALUES (?, ?, ?, ?) \
+            ON CONFLICT (name) DO UPDATE SET sql = excluded.sql, updated_at = excluded.updated_at",
+        (snippet.name, snippet.sql, now, now),
+    )?;
+    Ok(())
+}
+
+pub(crate) fn delete_snippet(conn: &Connection, id: i64) -> Result<()> {
+    conn.execute("DELETE FROM snippets WHERE id = ?", id)?;
+    Ok(())
+}
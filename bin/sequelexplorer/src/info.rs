@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use anyhow::Result;
+use bsqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::quote;
+
+// MARK: Database info
+#[derive(Serialize)]
+pub(crate) struct DatabaseInfo {
+    sqlite_version: String,
+    page_size: i64,
+    page_count: i64,
+    freelist_count: i64,
+    journal_mode: String,
+    encoding: String,
+    auto_vacuum: &'static str,
+    user_version: i64,
+    /// Sizes of the tables and indexes, `None` when SQLite is built without the dbstat table
+    objects: Option<Vec<ObjectSize>>,
+}
+
+#[derive(Serialize)]
+struct ObjectSize {
+    name: String,
+    r#type: String,
+    table: String,
+    pages: i64,
+    size: i64,
+}
+
+pub(crate) fn database_info(conn: &Connection) -> Result<DatabaseInfo> {
+    let auto_vacuum = match conn.query_some::<i64>("PRAGMA auto_vacuum", ())? {
+        1 => "full",
+        2 => "incremental",
+        _ => "none",
+    };
+    Ok(DatabaseInfo {
+        sqlite_version: conn.query_some("SELECT sqlite_version()", ())?,
+        page_size: conn.query_some("PRAGMA page_size", ())?,
+        page_count: conn.query_some("PRAGMA page_count", ())?,
+        freelist_count: conn.query_some("PRAGMA freelist_count", ())?,
+        journal_mode: conn.query_some("PRAGMA journal_mode", ())?,
+        encoding: conn.query_some("PRAGMA encoding", ())?,
+        auto_vacuum,
+        user_version: conn.query_some("PRAGMA user_version", ())?,
+        objects: object_sizes(conn).ok(),
+    })
+}
+
+fn object_sizes(conn: &Connection) -> Result<Vec<ObjectSize>> {
+    // The schema table itself is in dbstat but not in sqlite_master
+    let objects = conn
+        .query::<(String, String, String, i64, i64)>(
+            "SELECT s.name, COALESCE(m.type, 'table'), COALESCE(m.tbl_name, s.name),
+                COUNT(*), SUM(s.pgsize)
+            FROM dbstat s LEFT JOIN sqlite_master m ON m.name = s.name
+            GROUP BY s.name ORDER BY SUM(s.pgsize) DESC, s.name",
+            (),
+        )?
+        .map(|object| {
+            object.map(|(name, r#type, table, pages, size)| ObjectSize {
+                name,
+                r#type,
+                table,
+                pages,
+                size,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(objects)
+}
+
+// MARK: Maintenance
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum MaintenanceAction {
+    Vacuum,
+    Analyze,
+    IntegrityCheck,
+}
+
+/// Run a maintenance action, `progress` is called with the finished and total steps. ANALYZE
+/// and integrity_check run per table so they can report progress, VACUUM is a single step.
+/// Returns the problems found by the integrity check, or `["ok"]` when there are none.
+pub(crate) fn run_maintenance(
+    conn: &Connection,
+    action: MaintenanceAction,
+    mut progress: impl FnMut(usize, usize),
+) -> Result<Vec<String>> {
+    if matches!(action, MaintenanceAction::Vacuum) {
+        progress(0, 1);
+        conn.execute("VACUUM", ())?;
+        progress(1, 1);
+        return Ok(Vec::new());
+    }
+
+    let tables = conn
+        .query::<String>(
+            "SELECT name FROM sqlite_master
+            WHERE type = 'table' AND sql NOT LIKE 'CREATE VIRTUAL TABLE%' ORDER BY name",
+            (),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    // The integrity check ends with a whole database check, which also covers the free pages
+    let is_check = matches!(action, MaintenanceAction::IntegrityCheck);
+    let total = tables.len() + usize::from(is_check);
+    let mut messages = Vec::new();
+    progress(0, total);
+    for (index, table) in tables.iter().enumerate() {
+        if is_check {
+            collect_problems(
+                conn,
+                format!("PRAGMA integrity_check({})", quote(table)),
+                &mut messages,
+            )?;
+        } else {
+            conn.execute(format!("ANALYZE {}", quote(table)), ())?;
+        }
+        progress(index + 1, total);
+    }
+    if is_check {
+        collect_problems(conn, "PRAGMA integrity_check".to_string(), &mut messages)?;
+        progress(total, total);
+        if messages.is_empty() {
+            messages.push("ok".to_string());
+        }
+    }
+    Ok(messages)
+}
+
+fn collect_problems(conn: &Connection, sql: String, messages: &mut Vec<String>) -> Result<()> {
+    for message in conn.query::<String>(sql, ())? {
+        let message = message?;
+        if message != "ok" && !messages.contains(&message) {
+            messages.push(message);
+        }
+    }
+    Ok(())
+}
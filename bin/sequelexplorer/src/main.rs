@@ -7,7 +7,9 @@
 #![doc = include_str!("../README.md")]
 #![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Instant;
 
 use anyhow::Result;
 use base64::Engine;
@@ -22,6 +24,25 @@ use serde_json::json;
 use small_http::{Request, Response, Status};
 use small_router::RouterBuilder;
 
+use crate::download::{download_database, remove_downloaded_database};
+use crate::export::{ExportOptions, export_query};
+use crate::history::{
+    SnippetBody, add_history_entry, clear_history, delete_snippet, list_snippets, open_history,
+    save_snippet, search_history,
+};
+use crate::info::{MaintenanceAction, database_info, run_maintenance};
+use crate::schema::{
+    SchemaOperation, apply_schema_sql, quote, schema_operation_sql, table_structure,
+};
+use crate::settings::{Favorite, Settings};
+
+mod download;
+mod export;
+mod history;
+mod info;
+mod schema;
+mod settings;
+
 #[derive(Embed)]
 #[folder = "web"]
 struct WebAssets;
@@ -32,32 +53,290 @@ struct WebAssets;
 #[serde(tag = "type", rename_all = "camelCase")]
 enum IpcMessage {
     OpenFileDialog,
-    OpenFileDialogResponse { path: Option<String> },
-    OpenDatabase { path: String },
-    OpenDatabaseResponse { ok: bool, error: Option<String> },
+    OpenFileDialogResponse {
+        path: Option<String>,
+    },
+    OpenDatabase {
+        path: String,
+        #[serde(default = "default_read_only")]
+        read_only: bool,
+    },
+    OpenDatabaseResponse {
+        database: Option<usize>,
+        error: Option<String>,
+    },
+    OpenUrl {
+        url: String,
+        read_only: bool,
+    },
+    OpenUrlResponse {
+        database: Option<usize>,
+        error: Option<String>,
+    },
+    RunQuery {
+        database: usize,
+        sql: String,
+        params: Vec<serde_json::Value>,
+    },
+    RunQueryResponse {
+        result: Option<QueryResult>,
+        error: Option<String>,
+    },
+    CancelQuery,
+    ExportData {
+        database: usize,
+        name: String,
+        sql: String,
+        params: Vec<serde_json::Value>,
+        options: ExportOptions,
+    },
+    ExportDataResponse {
+        path: Option<String>,
+        rows: Option<u64>,
+        error: Option<String>,
+    },
+    RunMaintenance {
+        database: usize,
+        action: MaintenanceAction,
+    },
+    MaintenanceProgress {
+        action: MaintenanceAction,
+        done: usize,
+        total: usize,
+    },
+    RunMaintenanceResponse {
+        messages: Option<Vec<String>>,
+        error: Option<String>,
+    },
+}
+
+/// Databases are opened read-only unless changes are allowed explicitly
+const fn default_read_only() -> bool {
+    true
 }
 
 // MARK: State
-type State = Arc<Mutex<Option<Connection>>>;
+struct Database {
+    path: String,
+    conn: Connection,
+    read_only: bool,
+    /// URL the database was downloaded from to a temp file
+    url: Option<String>,
+}
+
+impl Database {
+    const fn open_mode(&self) -> OpenMode {
+        if self.read_only {
+            OpenMode::ReadOnly
+        } else {
+            OpenMode::ReadWrite
+        }
+    }
+
+    /// Reopen the connection in another mode, so SQLite itself rejects all writes in read-only
+    /// mode. The previous mode is kept when the database can't be reopened
+    fn set_read_only(&mut self, read_only: bool) -> Result<(), String> {
+        if self.read_only == read_only {
+            return Ok(());
+        }
+        if !self.conn.is_autocommit() {
+            return Err("Commit or roll back the pending changes first".to_string());
+        }
+        let previous = self.read_only;
+        self.read_only = read_only;
+        match Connection::open(&self.path, self.open_mode()) {
+            Ok(conn) => {
+                self.conn = conn;
+                Ok(())
+            }
+            Err(e) => {
+                self.read_only = previous;
+                Err(e.to_string())
+            }
+        }
+    }
+}
+
+struct AppState {
+    databases: Vec<Database>,
+    settings: Settings,
+    history: Connection,
+}
+
+type State = Arc<Mutex<AppState>>;
+
+impl AppState {
+    /// Get the path and open mode of a database, to open an extra connection to it
+    fn database_source(&self, database: usize) -> Result<(String, OpenMode), String> {
+        self.databases
+            .get(database)
+            .map(|database| (database.path.clone(), database.open_mode()))
+            .ok_or_else(|| "Database not open".to_string())
+    }
+}
 
 // MARK: Database helpers
-fn get_connection(
-    state: &State,
-) -> Result<std::sync::MutexGuard<'_, Option<Connection>>, Response> {
+/// Lock the state and look up the database of the `:db` route parameter
+fn get_connection<'a>(
+    req: &Request,
+    state: &'a State,
+) -> Result<(MutexGuard<'a, AppState>, usize), Response> {
+    let guard = state.lock().expect("mutex poisoned");
+    match req.params.get("db").and_then(|db| db.parse::<usize>().ok()) {
+        Some(db) if db < guard.databases.len() => Ok((guard, db)),
+        _ => Err(Response::with_json(json!({ "error": "Database not open" }))),
+    }
+}
+
+// MARK: Databases
+#[derive(Serialize)]
+struct DatabaseItem<'a> {
+    path: &'a str,
+    read_only: bool,
+    url: Option<&'a str>,
+}
+
+fn databases_list(_req: &Request, state: &State) -> Result<Response> {
+    let guard = state.lock().expect("mutex poisoned");
+    let databases = guard
+        .databases
+        .iter()
+        .map(|database| DatabaseItem {
+            path: &database.path,
+            read_only: database.read_only,
+            url: database.url.as_deref(),
+        })
+        .collect::<Vec<_>>();
+    Ok(Response::with_json(&databases))
+}
+
+fn databases_close(req: &Request, state: &State) -> Result<Response> {
+    let (mut guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let database = guard.databases.remove(db);
+    drop(guard);
+    if database.url.is_some() {
+        drop(database.conn);
+        remove_downloaded_database(&database.path);
+    }
+    Ok(Response::with_json(json!({ "ok": true })))
+}
+
+#[derive(Deserialize)]
+struct ReadOnlyBody {
+    read_only: bool,
+}
+
+fn databases_read_only(req: &Request, state: &State) -> Result<Response> {
+    let body: ReadOnlyBody = match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+        Ok(b) => b,
+        Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    };
+
+    let (mut guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    match guard.databases[db].set_read_only(body.read_only) {
+        Ok(()) => Ok(Response::with_json(json!({ "ok": true }))),
+        Err(e) => Ok(Response::with_json(json!({ "error": e }))),
+    }
+}
+
+// MARK: Settings
+fn settings_get(_req: &Request, state: &State) -> Result<Response> {
     let guard = state.lock().expect("mutex poisoned");
-    if guard.is_none() {
-        return Err(Response::with_json(json!({ "error": "No database open" })));
+    Ok(Response::with_json(&guard.settings))
+}
+
+fn settings_toggle_favorite(req: &Request, state: &State) -> Result<Response> {
+    let favorite: Favorite = match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+        Ok(f) => f,
+        Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    };
+
+    let mut guard = state.lock().expect("mutex poisoned");
+    guard.settings.toggle_favorite(favorite);
+    guard.settings.save();
+    Ok(Response::with_json(&guard.settings))
+}
+
+// MARK: History
+#[derive(Deserialize)]
+struct HistoryQuery {
+    #[serde(default)]
+    search: String,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+fn history_list(req: &Request, state: &State) -> Result<Response> {
+    let query = match serde_urlencoded::from_str::<HistoryQuery>(req.url.query().unwrap_or("")) {
+        Ok(query) => query,
+        Err(_) => {
+            return Ok(Response::with_json(
+                json!({ "error": "Invalid query parameters" }),
+            ));
+        }
+    };
+
+    let guard = state.lock().expect("mutex poisoned");
+    Ok(Response::with_json(&search_history(
+        &guard.history,
+        &query.search,
+        query.limit,
+    )?))
+}
+
+fn history_clear(_req: &Request, state: &State) -> Result<Response> {
+    let guard = state.lock().expect("mutex poisoned");
+    clear_history(&guard.history)?;
+    Ok(Response::with_json(json!({ "ok": true })))
+}
+
+// MARK: Snippets
+fn snippets_list(_req: &Request, state: &State) -> Result<Response> {
+    let guard = state.lock().expect("mutex poisoned");
+    Ok(Response::with_json(&list_snippets(&guard.history)?))
+}
+
+fn snippets_save(req: &Request, state: &State) -> Result<Response> {
+    let body: SnippetBody = match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+        Ok(b) => b,
+        Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    };
+    if body.name.trim().is_empty() {
+        return Ok(Response::with_json(
+            json!({ "error": "Snippet name is required" }),
+        ));
     }
-    Ok(guard)
+
+    let guard = state.lock().expect("mutex poisoned");
+    save_snippet(&guard.history, body)?;
+    Ok(Response::with_json(&list_snippets(&guard.history)?))
+}
+
+fn snippets_delete(req: &Request, state: &State) -> Result<Response> {
+    let Some(id) = req.params.get("id").and_then(|id| id.parse::<i64>().ok()) else {
+        return Ok(Response::with_json(
+            json!({ "error": "Invalid snippet id" }),
+        ));
+    };
+
+    let guard = state.lock().expect("mutex poisoned");
+    delete_snippet(&guard.history, id)?;
+    Ok(Response::with_json(&list_snippets(&guard.history)?))
 }
 
 // MARK: Tables
-fn db_tables(_req: &Request, state: &State) -> Result<Response> {
-    let guard = match get_connection(state) {
+fn db_tables(req: &Request, state: &State) -> Result<Response> {
+    let (guard, db) = match get_connection(req, state) {
         Ok(g) => g,
         Err(e) => return Ok(e),
     };
-    let conn = guard.as_ref().expect("Connection should be present");
+    let conn = &guard.databases[db].conn;
 
     let table_names: Vec<String> = conn
         .query::<String>(
@@ -75,6 +354,120 @@ struct TableDataQuery {
     offset: i64,
     #[serde(default = "default_limit")]
     limit: i64,
+    #[serde(default)]
+    sort: Option<String>,
+    #[serde(default)]
+    descending: bool,
+    #[serde(default)]
+    search: Option<String>,
+    /// JSON encoded list of [TableFilter]
+    #[serde(default)]
+    filters: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TableFilter {
+    column: String,
+    operator: FilterOperator,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum FilterOperator {
+    Contains,
+    Equals,
+    NotEquals,
+    GreaterThan,
+    LessThan,
+    IsNull,
+    IsNotNull,
+}
+
+fn like_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Build the WHERE and ORDER BY clauses, column names are checked against the table columns
+fn table_data_clauses(
+    query: &TableDataQuery,
+    table_columns: &[(String, String, i64)],
+) -> Result<(String, Vec<String>), String> {
+    let check_column = |column: &str| {
+        if table_columns.iter().any(|(name, _, _)| name == column) {
+            Ok(())
+        } else {
+            Err(format!("Unknown column: {column}"))
+        }
+    };
+
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+    let filters: Vec<TableFilter> = match &query.filters {
+        Some(filters) => serde_json::from_str(filters).map_err(|e| e.to_string())?,
+        None => Vec::new(),
+    };
+    for filter in filters {
+        check_column(&filter.column)?;
+        let column = quote(&filter.column);
+        let (condition, param) = match filter.operator {
+            FilterOperator::Contains => (
+                format!("{column} LIKE ? ESCAPE '\\'"),
+                Some(like_pattern(&filter.value)),
+            ),
+            FilterOperator::Equals => (format!("{column} = ?"), Some(filter.value)),
+            FilterOperator::NotEquals => (format!("{column} != ?"), Some(filter.value)),
+            FilterOperator::GreaterThan => (format!("{column} > ?"), Some(filter.value)),
+            FilterOperator::LessThan => (format!("{column} < ?"), Some(filter.value)),
+            FilterOperator::IsNull => (format!("{column} IS NULL"), None),
+            FilterOperator::IsNotNull => (format!("{column} IS NOT NULL"), None),
+        };
+        conditions.push(condition);
+        params.extend(param);
+    }
+
+    // The global search matches any column with a text affinity
+    if let Some(search) = query.search.as_deref().filter(|search| !search.is_empty()) {
+        let text_columns = table_columns
+            .iter()
+            .filter(|(_, r#type, _)| {
+                let r#type = r#type.to_uppercase();
+                r#type.is_empty()
+                    || r#type.contains("CHAR")
+                    || r#type.contains("CLOB")
+                    || r#type.contains("TEXT")
+            })
+            .collect::<Vec<_>>();
+        if text_columns.is_empty() {
+            conditions.push("0".to_string());
+        } else {
+            conditions.push(format!(
+                "({})",
+                text_columns
+                    .iter()
+                    .map(|(name, _, _)| format!("{} LIKE ? ESCAPE '\\'", quote(name)))
+                    .collect::<Vec<_>>()
+                    .join(" OR ")
+            ));
+            params.extend(text_columns.iter().map(|_| like_pattern(search)));
+        }
+    }
+
+    let mut clauses = String::new();
+    if !conditions.is_empty() {
+        clauses.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+    }
+    if let Some(sort) = &query.sort {
+        check_column(sort)?;
+        let order = if query.descending { "DESC" } else { "ASC" };
+        clauses.push_str(&format!(" ORDER BY {} {order}", quote(sort)));
+    }
+    Ok((clauses, params))
 }
 
 const fn default_limit() -> i64 {
@@ -96,9 +489,11 @@ struct TableData {
     columns: Vec<ColumnInfo>,
     rows: Vec<Vec<serde_json::Value>>,
     total: i64,
+    key_columns: Vec<String>,
+    rowids: Option<Vec<serde_json::Value>>,
 }
 
-#[derive(Serialize)]
+#[derive(Deserialize, Serialize)]
 struct ColumnInfo {
     name: String,
     r#type: String,
@@ -106,7 +501,7 @@ struct ColumnInfo {
     foreign_key: Option<ColumnForeignKey>,
 }
 
-#[derive(Clone, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 struct ColumnForeignKey {
     table: String,
     column: String,
@@ -117,13 +512,14 @@ fn get_foreign_key(
     table: &str,
     column: &str,
 ) -> Result<Option<ColumnForeignKey>> {
-    Ok(conn.query::<(String, String, String)>(
-        &format!("SELECT \"from\", \"table\", \"to\" FROM pragma_foreign_key_list(\"{table}\") WHERE \"from\" = ?"),
-        column.to_string(),
-    )?
-    .next()
-    .transpose()?
-    .map(|(_, table, column)| ColumnForeignKey { table, column }))
+    Ok(conn
+        .query::<(String, String, String)>(
+            "SELECT \"from\", \"table\", \"to\" FROM pragma_foreign_key_list(?) WHERE \"from\" = ?",
+            (table.to_string(), column.to_string()),
+        )?
+        .next()
+        .transpose()?
+        .map(|(_, table, column)| ColumnForeignKey { table, column }))
 }
 
 // MARK: Statement processing
@@ -181,41 +577,264 @@ fn process_statement(
 fn db_table_data(req: &Request, state: &State) -> Result<Response> {
     let name = req.params.get("name").expect("Should be some");
 
-    let query = match req.url.query() {
-        Some(q) => match serde_urlencoded::from_str::<TableDataQuery>(q) {
-            Ok(query) => query,
-            Err(_) => {
-                return Ok(Response::with_json(
-                    json!({ "error": "Invalid query parameters" }),
-                ));
-            }
-        },
-        None => TableDataQuery {
-            offset: 0,
-            limit: 100,
-        },
+    let query = match serde_urlencoded::from_str::<TableDataQuery>(req.url.query().unwrap_or("")) {
+        Ok(query) => query,
+        Err(_) => {
+            return Ok(Response::with_json(
+                json!({ "error": "Invalid query parameters" }),
+            ));
+        }
     };
 
-    let guard = match get_connection(state) {
+    let (guard, db) = match get_connection(req, state) {
         Ok(g) => g,
         Err(e) => return Ok(e),
     };
-    let conn = guard.as_ref().expect("Connection should be present");
+    let conn = &guard.databases[db].conn;
 
-    let total: i64 = conn.query_some::<i64>(&format!("SELECT COUNT(*) FROM \"{name}\""), ())?;
+    let table_columns = conn
+        .query::<(String, String, i64)>(
+            "SELECT name, type, pk FROM pragma_table_info(?)",
+            name.to_string(),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    let (clauses, params) = match table_data_clauses(&query, &table_columns) {
+        Ok(clauses) => clauses,
+        Err(error) => return Ok(Response::with_json(json!({ "error": error }))),
+    };
 
-    let mut stmt = conn.prepare::<()>(format!("SELECT * FROM \"{name}\" LIMIT ? OFFSET ?"))?;
-    stmt.bind_value(0, query.limit)?;
-    stmt.bind_value(1, query.offset)?;
+    let mut stmt = conn.prepare::<i64>(format!("SELECT COUNT(*) FROM {}{clauses}", quote(name)))?;
+    for (index, param) in params.iter().enumerate() {
+        stmt.bind_value(index as i32, param.clone())?;
+    }
+    let total = stmt.next().transpose()?.unwrap_or_default();
+
+    // Rows are identified by their primary key, or by their rowid when the table has none
+    let mut key_columns = table_columns
+        .iter()
+        .filter(|(_, _, pk)| *pk > 0)
+        .collect::<Vec<_>>();
+    key_columns.sort_by_key(|(_, _, pk)| *pk);
+    let key_columns = key_columns
+        .into_iter()
+        .map(|(name, _, _)| name.clone())
+        .collect::<Vec<_>>();
+    let select = if key_columns.is_empty() {
+        "rowid, *"
+    } else {
+        "*"
+    };
+
+    let mut stmt = conn.prepare::<()>(format!(
+        "SELECT {select} FROM {}{clauses} LIMIT ? OFFSET ?",
+        quote(name)
+    ))?;
+    for (index, param) in params.into_iter().enumerate() {
+        stmt.bind_value(index as i32, param)?;
+    }
+    let param_count = stmt.parameter_count();
+    stmt.bind_value(param_count - 2, query.limit)?;
+    stmt.bind_value(param_count - 1, query.offset)?;
+
+    let (mut columns, mut rows) = process_statement(&mut stmt, conn)?;
+    let rowids = if key_columns.is_empty() {
+        if !columns.is_empty() {
+            columns.remove(0);
+        }
+        Some(rows.iter_mut().map(|row| row.remove(0)).collect())
+    } else {
+        None
+    };
+
+    // Empty results return no result columns, use the declared columns so rows can be added
+    if columns.is_empty() {
+        columns = table_columns
+            .into_iter()
+            .map(|(name, r#type, _)| ColumnInfo {
+                name,
+                r#type,
+                is_blob: false,
+                foreign_key: None,
+            })
+            .collect();
+    }
 
-    let (columns, rows) = process_statement(&mut stmt, conn)?;
     Ok(Response::with_json(&TableData {
         columns,
         rows,
         total,
+        key_columns,
+        rowids,
     }))
 }
 
+// MARK: Table editing
+type RowKey = serde_json::Map<String, serde_json::Value>;
+
+#[derive(Deserialize)]
+struct UpdateRowBody {
+    key: RowKey,
+    column: String,
+    value: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct InsertRowBody {
+    values: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct DeleteRowBody {
+    key: RowKey,
+}
+
+fn row_key_condition(key: &RowKey) -> String {
+    key.keys()
+        .map(|column| format!("{} IS ?", quote(column)))
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+/// Run a generated edit statement inside the open transaction, starting one when needed
+fn execute_edit(conn: &Connection, sql: String, params: Vec<serde_json::Value>) -> Response {
+    let began = conn.is_autocommit();
+    let result = (|| {
+        if began {
+            conn.execute("BEGIN", ())?;
+        }
+        execute_query(conn, &sql, params)
+    })();
+    let error = match result {
+        Ok(result) if result.affected_rows > 0 => {
+            return Response::with_json(json!({ "sql": sql }));
+        }
+        Ok(_) => "No rows changed".to_string(),
+        Err(e) => e.to_string(),
+    };
+
+    // Don't leave an empty transaction open when the first edit fails
+    if began && !conn.is_autocommit() {
+        _ = conn.execute("ROLLBACK", ());
+    }
+    Response::with_json(json!({ "error": error }))
+}
+
+fn db_table_update(req: &Request, state: &State) -> Result<Response> {
+    let name = req.params.get("name").expect("Should be some");
+    let body: UpdateRowBody = match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+        Ok(b) => b,
+        Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    };
+    if body.key.is_empty() {
+        return Ok(Response::with_json(json!({ "error": "Row has no key" })));
+    }
+
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    let sql = format!(
+        "UPDATE {} SET {} = ? WHERE {}",
+        quote(name),
+        quote(&body.column),
+        row_key_condition(&body.key)
+    );
+    let mut params = vec![body.value];
+    params.extend(body.key.into_iter().map(|(_, value)| value));
+    Ok(execute_edit(conn, sql, params))
+}
+
+fn db_table_insert(req: &Request, state: &State) -> Result<Response> {
+    let name = req.params.get("name").expect("Should be some");
+    let body: InsertRowBody = match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+        Ok(b) => b,
+        Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    };
+
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    let sql = if body.values.is_empty() {
+        format!("INSERT INTO {} DEFAULT VALUES", quote(name))
+    } else {
+        format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            quote(name),
+            body.values
+                .keys()
+                .map(|column| quote(column))
+                .collect::<Vec<_>>()
+                .join(", "),
+            vec!["?"; body.values.len()].join(", ")
+        )
+    };
+    Ok(execute_edit(
+        conn,
+        sql,
+        body.values.into_iter().map(|(_, value)| value).collect(),
+    ))
+}
+
+fn db_table_delete(req: &Request, state: &State) -> Result<Response> {
+    let name = req.params.get("name").expect("Should be some");
+    let body: DeleteRowBody = match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+        Ok(b) => b,
+        Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    };
+    if body.key.is_empty() {
+        return Ok(Response::with_json(json!({ "error": "Row has no key" })));
+    }
+
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    let sql = format!(
+        "DELETE FROM {} WHERE {}",
+        quote(name),
+        row_key_condition(&body.key)
+    );
+    Ok(execute_edit(
+        conn,
+        sql,
+        body.key.into_iter().map(|(_, value)| value).collect(),
+    ))
+}
+
+// MARK: Transaction
+fn db_transaction_end(req: &Request, state: &State, sql: &str) -> Result<Response> {
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    if conn.is_autocommit() {
+        return Ok(Response::with_json(
+            json!({ "error": "No transaction open" }),
+        ));
+    }
+    match conn.execute(sql, ()) {
+        Ok(()) => Ok(Response::with_json(json!({ "ok": true }))),
+        Err(e) => Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    }
+}
+
+fn db_transaction_commit(req: &Request, state: &State) -> Result<Response> {
+    db_transaction_end(req, state, "COMMIT")
+}
+
+fn db_transaction_rollback(req: &Request, state: &State) -> Result<Response> {
+    db_transaction_end(req, state, "ROLLBACK")
+}
+
 // MARK: Table schema
 #[derive(Serialize)]
 struct TableSchema {
@@ -225,11 +844,11 @@ struct TableSchema {
 fn db_table_schema(req: &Request, state: &State) -> Result<Response> {
     let name = req.params.get("name").expect("Should be some");
 
-    let guard = match get_connection(state) {
+    let (guard, db) = match get_connection(req, state) {
         Ok(g) => g,
         Err(e) => return Ok(e),
     };
-    let conn = guard.as_ref().expect("Connection should be present");
+    let conn = &guard.databases[db].conn;
 
     let sql = conn
         .query::<String>(
@@ -248,39 +867,232 @@ fn db_table_schema(req: &Request, state: &State) -> Result<Response> {
     }
 }
 
+// MARK: Schema editing
+fn db_table_structure(req: &Request, state: &State) -> Result<Response> {
+    let name = req.params.get("name").expect("Should be some");
+
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    Ok(Response::with_json(&table_structure(conn, name)?))
+}
+
+fn db_schema_preview(req: &Request, state: &State) -> Result<Response> {
+    let operation: SchemaOperation =
+        match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+            Ok(o) => o,
+            Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+        };
+
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    match schema_operation_sql(conn, &operation) {
+        Ok(sql) => Ok(Response::with_json(json!({ "sql": sql }))),
+        Err(e) => Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    }
+}
+
+fn db_schema_apply(req: &Request, state: &State) -> Result<Response> {
+    let operation: SchemaOperation =
+        match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+            Ok(o) => o,
+            Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+        };
+
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    match schema_operation_sql(conn, &operation).and_then(|sql| apply_schema_sql(conn, &sql)) {
+        Ok(()) => Ok(Response::with_json(json!({ "ok": true }))),
+        Err(e) => Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    }
+}
+
+// MARK: Schema diagram
+#[derive(Serialize)]
+struct DiagramTable {
+    name: String,
+    columns: Vec<DiagramColumn>,
+}
+
+#[derive(Serialize)]
+struct DiagramColumn {
+    name: String,
+    r#type: String,
+    is_primary_key: bool,
+}
+
+#[derive(Serialize)]
+struct DiagramRelation {
+    table: String,
+    column: String,
+    foreign_table: String,
+    foreign_column: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Diagram {
+    tables: Vec<DiagramTable>,
+    relations: Vec<DiagramRelation>,
+}
+
+fn db_diagram(req: &Request, state: &State) -> Result<Response> {
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    let table_names: Vec<String> = conn
+        .query::<String>(
+            "SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name",
+            (),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut tables = Vec::new();
+    let mut relations = Vec::new();
+    for name in table_names {
+        let columns = conn
+            .query::<(String, String, i64)>(
+                "SELECT name, type, pk FROM pragma_table_info(?)",
+                name.to_string(),
+            )?
+            .map(|column| {
+                column.map(|(name, r#type, pk)| DiagramColumn {
+                    name,
+                    r#type,
+                    is_primary_key: pk > 0,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // The referenced column is NULL when the foreign key points to the primary key
+        for foreign_key in conn.query::<(String, String, Option<String>)>(
+            "SELECT \"from\", \"table\", \"to\" FROM pragma_foreign_key_list(?)",
+            name.clone(),
+        )? {
+            let (column, foreign_table, foreign_column) = foreign_key?;
+            relations.push(DiagramRelation {
+                table: name.clone(),
+                column,
+                foreign_table,
+                foreign_column,
+            });
+        }
+
+        tables.push(DiagramTable { name, columns });
+    }
+    Ok(Response::with_json(&Diagram { tables, relations }))
+}
+
+// MARK: Database info
+fn db_info(req: &Request, state: &State) -> Result<Response> {
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    match database_info(conn) {
+        Ok(info) => Ok(Response::with_json(&info)),
+        Err(e) => Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    }
+}
+
 // MARK: Custom query
 #[derive(Deserialize)]
 struct QueryBody {
     sql: String,
 }
 
-#[derive(Serialize)]
+#[derive(Deserialize, Serialize)]
 struct QueryResult {
     columns: Vec<ColumnInfo>,
     rows: Vec<Vec<serde_json::Value>>,
+    affected_rows: i32,
+    duration_ms: f64,
 }
 
-fn db_query(req: &Request, state: &State) -> Result<Response> {
+fn json_to_value(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Float(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => Value::Text(s),
+        value => Value::Text(value.to_string()),
+    }
+}
+
+fn execute_query(
+    conn: &Connection,
+    sql: &str,
+    params: Vec<serde_json::Value>,
+) -> Result<QueryResult, StatementError> {
+    let start = Instant::now();
+    let mut stmt = conn.prepare::<()>(sql)?;
+    for (index, param) in params.into_iter().enumerate() {
+        stmt.bind_value(index as i32, json_to_value(param))?;
+    }
+    let (columns, rows) = process_statement(&mut stmt, conn)?;
+    Ok(QueryResult {
+        columns,
+        rows,
+        affected_rows: conn.affected_rows(),
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+    })
+}
+
+fn db_query_parameters(req: &Request, state: &State) -> Result<Response> {
     let body: QueryBody = match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
         Ok(b) => b,
         Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
     };
 
-    let guard = match get_connection(state) {
+    let (guard, db) = match get_connection(req, state) {
         Ok(g) => g,
         Err(e) => return Ok(e),
     };
-    let conn = guard.as_ref().expect("Connection should be present");
+    let conn = &guard.databases[db].conn;
 
-    let result = conn
-        .prepare::<()>(&body.sql)
-        .and_then(|mut stmt| process_statement(&mut stmt, conn));
+    match conn.prepare::<()>(&body.sql) {
+        Ok(stmt) => {
+            let parameters = (0..stmt.parameter_count())
+                .map(|i| stmt.parameter_name(i))
+                .collect::<Vec<_>>();
+            Ok(Response::with_json(json!({ "parameters": parameters })))
+        }
+        Err(e) => Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    }
+}
 
-    match result {
-        Ok(qr) => Ok(Response::with_json(&QueryResult {
-            columns: qr.0,
-            rows: qr.1,
-        })),
+fn db_query(req: &Request, state: &State) -> Result<Response> {
+    let body: QueryBody = match serde_json::from_slice(req.body.as_deref().unwrap_or(&[])) {
+        Ok(b) => b,
+        Err(e) => return Ok(Response::with_json(json!({ "error": e.to_string() }))),
+    };
+
+    let (guard, db) = match get_connection(req, state) {
+        Ok(g) => g,
+        Err(e) => return Ok(e),
+    };
+    let conn = &guard.databases[db].conn;
+
+    match execute_query(conn, &body.sql, Vec::new()) {
+        Ok(result) => Ok(Response::with_json(&result)),
         Err(e) => Ok(Response::with_json(json!({ "error": e.to_string() }))),
     }
 }
@@ -288,16 +1100,50 @@ fn db_query(req: &Request, state: &State) -> Result<Response> {
 // MARK: Main
 
 fn main() {
-    let state: State = Arc::new(Mutex::new(None));
+    let state: State = Arc::new(Mutex::new(AppState {
+        databases: Vec::new(),
+        settings: Settings::load(),
+        history: open_history().expect("Can't open query history"),
+    }));
     let event_loop = EventLoopBuilder::new()
         .app_id("nl", "bplaat", "SequelExplorer")
         .build();
 
     let router = RouterBuilder::<State>::with(Arc::clone(&state))
-        .get("/api/tables", db_tables)
-        .get("/api/table/:name/data", db_table_data)
-        .get("/api/table/:name/schema", db_table_schema)
-        .post("/api/query", db_query)
+        .get("/api/settings", settings_get)
+        .post("/api/settings/favorites/toggle", settings_toggle_favorite)
+        .get("/api/history", history_list)
+        .post("/api/history/clear", history_clear)
+        .get("/api/snippets", snippets_list)
+        .post("/api/snippets", snippets_save)
+        .post("/api/snippets/:id/delete", snippets_delete)
+        .get("/api/databases", databases_list)
+        .post("/api/databases/:db/close", databases_close)
+        .post("/api/databases/:db/read_only", databases_read_only)
+        .get("/api/databases/:db/tables", db_tables)
+        .get("/api/databases/:db/diagram", db_diagram)
+        .get("/api/databases/:db/info", db_info)
+        .get("/api/databases/:db/table/:name/data", db_table_data)
+        .get("/api/databases/:db/table/:name/schema", db_table_schema)
+        .get(
+            "/api/databases/:db/table/:name/structure",
+            db_table_structure,
+        )
+        .post("/api/databases/:db/table/:name/update", db_table_update)
+        .post("/api/databases/:db/table/:name/insert", db_table_insert)
+        .post("/api/databases/:db/table/:name/delete", db_table_delete)
+        .post("/api/databases/:db/schema/preview", db_schema_preview)
+        .post("/api/databases/:db/schema/apply", db_schema_apply)
+        .post(
+            "/api/databases/:db/transaction/commit",
+            db_transaction_commit,
+        )
+        .post(
+            "/api/databases/:db/transaction/rollback",
+            db_transaction_rollback,
+        )
+        .post("/api/databases/:db/query", db_query)
+        .post("/api/databases/:db/query/parameters", db_query_parameters)
         .build();
 
     #[allow(unused_mut)]
@@ -320,6 +1166,11 @@ fn main() {
         })
         .build();
 
+    // Queries from the query tab run on their own connection in a background thread, so they
+    // don't block the webview and can be cancelled with sqlite3_interrupt
+    let event_loop_proxy = Arc::new(event_loop.create_proxy());
+    let mut running_query: Option<Connection> = None;
+
     event_loop.run(move |event| match event {
         Event::Webview(WebviewEvent::PageTitleChange(title)) => window.set_title(title),
         Event::Webview(WebviewEvent::MessageReceive(message)) => {
@@ -335,23 +1186,271 @@ fn main() {
                         serde_json::to_string(&response).expect("Failed to serialize response"),
                     );
                 }
-                IpcMessage::OpenDatabase { path } => {
-                    let result = Connection::open(&path, OpenMode::ReadOnly);
-                    let (ok, error) = match result {
-                        Ok(conn) => {
-                            *state.lock().expect("mutex poisoned") = Some(conn);
-                            (true, None)
+                IpcMessage::OpenDatabase { path, read_only } => {
+                    let mut guard = state.lock().expect("mutex poisoned");
+                    let result = match guard.databases.iter().position(|db| db.path == path) {
+                        // Apply the requested mode to an already open database
+                        Some(index) => guard.databases[index]
+                            .set_read_only(read_only)
+                            .map(|()| index),
+                        None => {
+                            let mode = if read_only {
+                                OpenMode::ReadOnly
+                            } else {
+                                OpenMode::ReadWrite
+                            };
+                            Connection::open(&path, mode)
+                                .map(|conn| {
+                                    guard.databases.push(Database {
+                                        path: path.clone(),
+                                        conn,
+                                        read_only,
+                                        url: None,
+                                    });
+                                    guard.databases.len() - 1
+                                })
+                                .map_err(|e| e.to_string())
                         }
-                        Err(e) => (false, Some(e.to_string())),
                     };
-                    let response = IpcMessage::OpenDatabaseResponse { ok, error };
+                    if result.is_ok() {
+                        guard.settings.add_recent_file(&path);
+                        guard.settings.save();
+                    }
+                    drop(guard);
+
+                    let response = match result {
+                        Ok(index) => IpcMessage::OpenDatabaseResponse {
+                            database: Some(index),
+                            error: None,
+                        },
+                        Err(e) => IpcMessage::OpenDatabaseResponse {
+                            database: None,
+                            error: Some(e),
+                        },
+                    };
                     webview.send_ipc_message(
                         serde_json::to_string(&response).expect("Failed to serialize response"),
                     );
                 }
+                IpcMessage::OpenUrl { url, read_only } => {
+                    // Downloading can take a while, so don't block the event loop
+                    let state = Arc::clone(&state);
+                    let event_loop_proxy = event_loop_proxy.clone();
+                    thread::spawn(move || {
+                        let mode = if read_only {
+                            OpenMode::ReadOnly
+                        } else {
+                            OpenMode::ReadWrite
+                        };
+                        let result = download_database(&url).and_then(|path| {
+                            let path = path.to_string_lossy().into_owned();
+                            match Connection::open(&path, mode) {
+                                Ok(conn) => Ok((path, conn)),
+                                Err(e) => {
+                                    remove_downloaded_database(&path);
+                                    Err(e.into())
+                                }
+                            }
+                        });
+                        let response = match result {
+                            Ok((path, conn)) => {
+                                let mut guard = state.lock().expect("mutex poisoned");
+                                guard.databases.push(Database {
+                                    path,
+                                    conn,
+                                    read_only,
+                                    url: Some(url),
+                                });
+                                IpcMessage::OpenUrlResponse {
+                                    database: Some(guard.databases.len() - 1),
+                                    error: None,
+                                }
+                            }
+                            Err(e) => IpcMessage::OpenUrlResponse {
+                                database: None,
+                                error: Some(e.to_string()),
+                            },
+                        };
+                        event_loop_proxy.send_user_event(
+                            serde_json::to_string(&response).expect("Failed to serialize response"),
+                        );
+                    });
+                }
+                IpcMessage::RunQuery {
+                    database,
+                    sql,
+                    params,
+                } => {
+                    let (path, history) = {
+                        let guard = state.lock().expect("mutex poisoned");
+                        (guard.database_source(database), guard.history.clone())
+                    };
+                    let conn = path.and_then(|(path, mode)| {
+                        Connection::open(&path, mode)
+                            .map(|conn| (path, conn))
+                            .map_err(|e| e.to_string())
+                    });
+                    match conn {
+                        Ok((path, conn)) => {
+                            running_query = Some(conn.clone());
+                            let event_loop_proxy = event_loop_proxy.clone();
+                            thread::spawn(move || {
+                                let start = Instant::now();
+                                let (result, error) = match execute_query(&conn, &sql, params) {
+                                    Ok(result) => (Some(result), None),
+                                    Err(e) => (None, Some(e.to_string())),
+                                };
+                                _ = add_history_entry(
+                                    &history,
+                                    &path,
+                                    &sql,
+                                    start.elapsed().as_secs_f64() * 1000.0,
+                                    error.clone(),
+                                );
+                                let response = IpcMessage::RunQueryResponse { result, error };
+                                event_loop_proxy.send_user_event(
+                                    serde_json::to_string(&response)
+                                        .expect("Failed to serialize response"),
+                                );
+                            });
+                        }
+                        Err(error) => {
+                            let response = IpcMessage::RunQueryResponse {
+                                result: None,
+                                error: Some(error),
+                            };
+                            webview.send_ipc_message(
+                                serde_json::to_string(&response)
+                                    .expect("Failed to serialize response"),
+                            );
+                        }
+                    }
+                }
+                IpcMessage::ExportData {
+                    database,
+                    name,
+                    sql,
+                    params,
+                    options,
+                } => {
+                    let extension = options.format.extension();
+                    let path = FileDialog::new()
+                        .title("Export Data")
+                        .set_file_name(format!("{name}.{extension}"))
+                        .add_filter(extension.to_uppercase(), &[extension])
+                        .save_file();
+                    let conn = match &path {
+                        None => Ok(None),
+                        Some(_) => state
+                            .lock()
+                            .expect("mutex poisoned")
+                            .database_source(database)
+                            .and_then(|(db_path, _)| {
+                                Connection::open(db_path, OpenMode::ReadOnly)
+                                    .map(Some)
+                                    .map_err(|e| e.to_string())
+                            }),
+                    };
+                    match (path, conn) {
+                        (Some(path), Ok(Some(conn))) => {
+                            // Exports can take a while for large tables, so write in the background
+                            let event_loop_proxy = event_loop_proxy.clone();
+                            thread::spawn(move || {
+                                let params = params.into_iter().map(json_to_value).collect();
+                                let response =
+                                    match export_query(&conn, &sql, params, &name, &options, &path)
+                                    {
+                                        Ok(rows) => IpcMessage::ExportDataResponse {
+                                            path: Some(path.to_string_lossy().into_owned()),
+                                            rows: Some(rows),
+                                            error: None,
+                                        },
+                                        Err(e) => IpcMessage::ExportDataResponse {
+                                            path: None,
+                                            rows: None,
+                                            error: Some(e.to_string()),
+                                        },
+                                    };
+                                event_loop_proxy.send_user_event(
+                                    serde_json::to_string(&response)
+                                        .expect("Failed to serialize response"),
+                                );
+                            });
+                        }
+                        (_, conn) => {
+                            let response = IpcMessage::ExportDataResponse {
+                                path: None,
+                                rows: None,
+                                error: conn.err(),
+                            };
+                            webview.send_ipc_message(
+                                serde_json::to_string(&response)
+                                    .expect("Failed to serialize response"),
+                            );
+                        }
+                    }
+                }
+                IpcMessage::RunMaintenance { database, action } => {
+                    let conn = state
+                        .lock()
+                        .expect("mutex poisoned")
+                        .database_source(database)
+                        .and_then(|(path, mode)| {
+                            Connection::open(path, mode).map_err(|e| e.to_string())
+                        });
+                    match conn {
+                        Ok(conn) => {
+                            // VACUUM rewrites the whole file, so maintenance runs in the background
+                            let event_loop_proxy = event_loop_proxy.clone();
+                            thread::spawn(move || {
+                                let result = run_maintenance(&conn, action, |done, total| {
+                                    let progress = IpcMessage::MaintenanceProgress {
+                                        action,
+                                        done,
+                                        total,
+                                    };
+                                    event_loop_proxy.send_user_event(
+                                        serde_json::to_string(&progress)
+                                            .expect("Failed to serialize response"),
+                                    );
+                                });
+                                let response = match result {
+                                    Ok(messages) => IpcMessage::RunMaintenanceResponse {
+                                        messages: Some(messages),
+                                        error: None,
+                                    },
+                                    Err(e) => IpcMessage::RunMaintenanceResponse {
+                                        messages: None,
+                                        error: Some(e.to_string()),
+                                    },
+                                };
+                                event_loop_proxy.send_user_event(
+                                    serde_json::to_string(&response)
+                                        .expect("Failed to serialize response"),
+                                );
+                            });
+                        }
+                        Err(error) => {
+                            let response = IpcMessage::RunMaintenanceResponse {
+                                messages: None,
+                                error: Some(error),
+                            };
+                            webview.send_ipc_message(
+                                serde_json::to_string(&response)
+                                    .expect("Failed to serialize response"),
+                            );
+                        }
+                    }
+                }
+                IpcMessage::CancelQuery => {
+                    if let Some(conn) = &running_query {
+                        conn.interrupt();
+                    }
+                }
                 _ => {}
             }
         }
+        Event::UserEvent(message) => webview.send_ipc_message(message),
         _ => {}
     });
 }
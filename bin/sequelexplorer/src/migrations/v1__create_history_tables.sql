@@ -0,0 +1,18 @@
+CREATE TABLE history(
+    id INTEGER PRIMARY KEY,
+    database TEXT NOT NULL,
+    sql TEXT NOT NULL,
+    duration_ms REAL NOT NULL,
+    error TEXT,
+    created_at INTEGER NOT NULL
+) STRICT;
+
+CREATE INDEX history_created_at ON history(created_at);
+
+CREATE TABLE snippets(
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    sql TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+) STRICT;
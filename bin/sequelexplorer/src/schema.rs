@@ -0,0 +1,561 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use anyhow::{Result, bail};
+use bsqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+// MARK: Table structure
+#[derive(Serialize)]
+pub(crate) struct TableStructure {
+    columns: Vec<StructureColumn>,
+    indexes: Vec<StructureIndex>,
+}
+
+#[derive(Serialize)]
+struct StructureColumn {
+    name: String,
+    r#type: String,
+    not_null: bool,
+    default: Option<String>,
+    primary_key: bool,
+}
+
+#[derive(Serialize)]
+struct StructureIndex {
+    name: String,
+    unique: bool,
+    columns: Vec<String>,
+    is_auto: bool,
+}
+
+pub(crate) fn table_structure(conn: &Connection, table: &str) -> Result<TableStructure> {
+    let columns = table_columns(conn, table)?
+        .into_iter()
+        .map(|column| StructureColumn {
+            name: column.name,
+            r#type: column.r#type,
+            not_null: column.not_null,
+            default: column.default,
+            primary_key: column.primary_key > 0,
+        })
+        .collect();
+    let indexes = table_indexes(conn, table)?
+        .into_iter()
+        .map(|index| StructureIndex {
+            name: index.name,
+            unique: index.unique,
+            columns: index.columns,
+            is_auto: index.origin != "c",
+        })
+        .collect();
+    Ok(TableStructure { columns, indexes })
+}
+
+struct TableColumn {
+    name: String,
+    r#type: String,
+    not_null: bool,
+    default: Option<String>,
+    primary_key: i64,
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<TableColumn>> {
+    Ok(conn
+        .query::<(String, String, bool, Option<String>, i64)>(
+            "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?) ORDER BY cid",
+            table.to_string(),
+        )?
+        .map(|column| {
+            column.map(
+                |(name, r#type, not_null, default, primary_key)| TableColumn {
+                    name,
+                    r#type,
+                    not_null,
+                    default,
+                    primary_key,
+                },
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+struct TableIndex {
+    name: String,
+    unique: bool,
+    origin: String,
+    columns: Vec<String>,
+}
+
+fn table_indexes(conn: &Connection, table: &str) -> Result<Vec<TableIndex>> {
+    let indexes = conn
+        .query::<(String, bool, String)>(
+            "SELECT name, \"unique\", origin FROM pragma_index_list(?) ORDER BY name",
+            table.to_string(),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut table_indexes = Vec::new();
+    for (name, unique, origin) in indexes {
+        let columns = conn
+            .query::<Option<String>>(
+                "SELECT name FROM pragma_index_info(?) ORDER BY seqno",
+                name.clone(),
+            )?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .map(|column| column.unwrap_or_else(|| "<expression>".to_string()))
+            .collect();
+        table_indexes.push(TableIndex {
+            name,
+            unique,
+            origin,
+            columns,
+        });
+    }
+    Ok(table_indexes)
+}
+
+// MARK: Schema operations
+#[derive(Deserialize)]
+pub(crate) struct ColumnDefinition {
+    name: String,
+    #[serde(default)]
+    r#type: String,
+    #[serde(default)]
+    primary_key: bool,
+    #[serde(default)]
+    not_null: bool,
+    #[serde(default)]
+    default: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum SchemaOperation {
+    CreateTable {
+        table: String,
+        columns: Vec<ColumnDefinition>,
+    },
+    RenameTable {
+        table: String,
+        new_name: String,
+    },
+    DropTable {
+        table: String,
+    },
+    AddColumn {
+        table: String,
+        column: ColumnDefinition,
+    },
+    RenameColumn {
+        table: String,
+        column: String,
+        new_name: String,
+    },
+    DropColumn {
+        table: String,
+        column: String,
+    },
+    CreateIndex {
+        table: String,
+        name: String,
+        columns: Vec<String>,
+        #[serde(default)]
+        unique: bool,
+    },
+    DropIndex {
+        name: String,
+    },
+}
+
+pub(crate) fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn quote_list(identifiers: &[String]) -> String {
+    identifiers
+        .iter()
+        .map(|identifier| quote(identifier))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn require_name(name: &str, what: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        bail!("The {what} name can't be empty");
+    }
+    Ok(())
+}
+
+fn column_definition_sql(column: &ColumnDefinition, inline_primary_key: bool) -> String {
+    let mut sql = quote(&column.name);
+    if !column.r#type.trim().is_empty() {
+        sql.push(' ');
+        sql.push_str(column.r#type.trim());
+    }
+    if inline_primary_key {
+        sql.push_str(" PRIMARY KEY");
+    }
+    if column.not_null {
+        sql.push_str(" NOT NULL");
+    }
+    if let Some(default) = column.default.as_deref().filter(|d| !d.trim().is_empty()) {
+        sql.push_str(" DEFAULT ");
+        sql.push_str(default.trim());
+    }
+    sql
+}
+
+/// Generate the statements for a schema operation, they are run together in one transaction
+pub(crate) fn schema_operation_sql(
+    conn: &Connection,
+    operation: &SchemaOperation,
+) -> Result<Vec<String>> {
+    Ok(match operation {
+        SchemaOperation::CreateTable { table, columns } => {
+            require_name(table, "table")?;
+            if columns.is_empty() {
+                bail!("A table needs at least one column");
+            }
+            for column in columns {
+                require_name(&column.name, "column")?;
+            }
+            let primary_key = columns
+                .iter()
+                .filter(|column| column.primary_key)
+                .map(|column| column.name.clone())
+                .collect::<Vec<_>>();
+            let mut definitions = columns
+                .iter()
+                .map(|column| {
+                    column_definition_sql(column, primary_key.len() == 1 && column.primary_key)
+                })
+                .collect::<Vec<_>>();
+            if primary_key.len() > 1 {
+                definitions.push(format!("PRIMARY KEY ({})", quote_list(&primary_key)));
+            }
+            vec![format!(
+                "CREATE TABLE {} (\n    {}\n)",
+                quote(table),
+                definitions.join(",\n    ")
+            )]
+        }
+        SchemaOperation::RenameTable { table, new_name } => {
+            require_name(new_name, "table")?;
+            vec![format!(
+                "ALTER TABLE {} RENAME TO {}",
+                quote(table),
+                quote(new_name)
+            )]
+        }
+        SchemaOperation::DropTable { table } => vec![format!("DROP TABLE {}", quote(table))],
+        SchemaOperation::AddColumn { table, column } => {
+            require_name(&column.name, "column")?;
+            if column.primary_key {
+                bail!("SQLite can't add a primary key column to an existing table");
+            }
+            vec![format!(
+                "ALTER TABLE {} ADD COLUMN {}",
+                quote(table),
+                column_definition_sql(column, false)
+            )]
+        }
+        SchemaOperation::RenameColumn {
+            table,
+            column,
+            new_name,
+        } => {
+            require_name(new_name, "column")?;
+            vec![format!(
+                "ALTER TABLE {} RENAME COLUMN {} TO {}",
+                quote(table),
+                quote(column),
+                quote(new_name)
+            )]
+        }
+        SchemaOperation::DropColumn { table, column } => drop_column_sql(conn, table, column)?,
+        SchemaOperation::CreateIndex {
+            table,
+            name,
+            columns,
+            unique,
+        } => {
+            require_name(name, "index")?;
+            if columns.is_empty() {
+                bail!("An index needs at least one column");
+            }
+            vec![format!(
+                "CREATE {}INDEX {} ON {} ({})",
+                if *unique { "UNIQUE " } else { "" },
+                quote(name),
+                quote(table),
+                quote_list(columns)
+            )]
+        }
+        SchemaOperation::DropIndex { name } => vec![format!("DROP INDEX {}", quote(name))],
+    })
+}
+
+// MARK: Drop column
+fn drop_column_sql(conn: &Connection, table: &str, column: &str) -> Result<Vec<String>> {
+    let columns = table_columns(conn, table)?;
+    let Some(dropped) = columns.iter().find(|c| c.name == column) else {
+        bail!("Column {column} not found");
+    };
+    if columns.len() == 1 {
+        bail!("Can't drop the only column of a table");
+    }
+
+    // ALTER TABLE DROP COLUMN refuses primary key, unique, indexed and foreign key columns
+    let indexes = table_indexes(conn, table)?;
+    let is_indexed = indexes
+        .iter()
+        .any(|index| index.columns.iter().any(|c| c == column));
+    let is_foreign_key = conn.query_some::<i64>(
+        "SELECT COUNT(*) FROM pragma_foreign_key_list(?) WHERE \"from\" = ?",
+        (table.to_string(), column.to_string()),
+    )? > 0;
+    if dropped.primary_key == 0 && !is_indexed && !is_foreign_key {
+        return Ok(vec![format!(
+            "ALTER TABLE {} DROP COLUMN {}",
+            quote(table),
+            quote(column)
+        )]);
+    }
+    rebuild_table_sql(conn, table, &columns, &indexes, column)
+}
+
+struct ForeignKey {
+    id: i64,
+    from: Vec<String>,
+    table: String,
+    to: Vec<String>,
+    on_update: String,
+    on_delete: String,
+}
+
+/// The generic table rebuild procedure from https://www.sqlite.org/lang_altertable.html:
+/// create the new table, copy the rows, drop the old table, rename and recreate the indexes
+/// and triggers. Tables with CHECK constraints or COLLATE clauses are refused, because those
+/// can't be read back from the pragmas and would be silently lost.
+fn rebuild_table_sql(
+    conn: &Connection,
+    table: &str,
+    columns: &[TableColumn],
+    indexes: &[TableIndex],
+    dropped_column: &str,
+) -> Result<Vec<String>> {
+    let original_sql = conn
+        .query_some::<String>(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?",
+            table.to_string(),
+        )?
+        .to_uppercase();
+    if has_keyword(&original_sql, "CHECK") || has_keyword(&original_sql, "COLLATE") {
+        bail!("Can't drop a column of a table with CHECK constraints or COLLATE clauses");
+    }
+    let new_table = format!("_{table}_rebuild");
+    let kept = columns
+        .iter()
+        .filter(|column| column.name != dropped_column)
+        .collect::<Vec<_>>();
+
+    let mut primary_key = kept
+        .iter()
+        .filter(|column| column.primary_key > 0)
+        .collect::<Vec<_>>();
+    primary_key.sort_by_key(|column| column.primary_key);
+    let primary_key = primary_key
+        .into_iter()
+        .map(|column| column.name.clone())
+        .collect::<Vec<_>>();
+    // A single INTEGER PRIMARY KEY stays inline so it keeps being the rowid alias
+    let inline_primary_key = primary_key.len() == 1
+        && kept
+            .iter()
+            .any(|c| c.name == primary_key[0] && c.r#type.eq_ignore_ascii_case("INTEGER"));
+
+    let mut definitions = kept
+        .iter()
+        .map(|column| {
+            let mut sql = column_definition_sql(
+                &ColumnDefinition {
+                    name: column.name.clone(),
+                    r#type: column.r#type.clone(),
+                    primary_key: false,
+                    not_null: column.not_null,
+                    default: column.default.clone(),
+                },
+                false,
+            );
+            if inline_primary_key && column.name == primary_key[0] {
+                sql.push_str(" PRIMARY KEY");
+                if original_sql.contains("AUTOINCREMENT") {
+                    sql.push_str(" AUTOINCREMENT");
+                }
+            }
+            sql
+        })
+        .collect::<Vec<_>>();
+    if !primary_key.is_empty() && !inline_primary_key {
+        definitions.push(format!("PRIMARY KEY ({})", quote_list(&primary_key)));
+    }
+    for index in indexes
+        .iter()
+        .filter(|index| index.origin == "u" && !index.columns.iter().any(|c| c == dropped_column))
+    {
+        definitions.push(format!("UNIQUE ({})", quote_list(&index.columns)));
+    }
+
+    // Foreign keys with multiple columns have a row per column with the same id
+    let mut foreign_keys: Vec<ForeignKey> = Vec::new();
+    for foreign_key in conn.query::<(i64, String, String, Option<String>, String, String)>(
+        "SELECT id, \"from\", \"table\", \"to\", on_update, on_delete \
+            FROM pragma_foreign_key_list(?) ORDER BY id, seq",
+        table.to_string(),
+    )? {
+        let (id, from, table, to, on_update, on_delete) = foreign_key?;
+        match foreign_keys.last_mut() {
+            Some(last) if last.id == id => {
+                last.from.push(from);
+                last.to.extend(to);
+            }
+            _ => foreign_keys.push(ForeignKey {
+                id,
+                from: vec![from],
+                table,
+                to: to.into_iter().collect(),
+                on_update,
+                on_delete,
+            }),
+        }
+    }
+    for foreign_key in foreign_keys {
+        if foreign_key.from.iter().any(|c| c == dropped_column) {
+            continue;
+        }
+        let mut sql = format!(
+            "FOREIGN KEY ({}) REFERENCES {}",
+            quote_list(&foreign_key.from),
+            quote(&foreign_key.table)
+        );
+        if !foreign_key.to.is_empty() {
+            sql.push_str(&format!(" ({})", quote_list(&foreign_key.to)));
+        }
+        if foreign_key.on_update != "NO ACTION" {
+            sql.push_str(&format!(" ON UPDATE {}", foreign_key.on_update));
+        }
+        if foreign_key.on_delete != "NO ACTION" {
+            sql.push_str(&format!(" ON DELETE {}", foreign_key.on_delete));
+        }
+        definitions.push(sql);
+    }
+
+    let kept_names = kept
+        .iter()
+        .map(|column| column.name.clone())
+        .collect::<Vec<_>>();
+    let mut statements = vec![
+        format!(
+            "CREATE TABLE {} (\n    {}\n){}",
+            quote(&new_table),
+            definitions.join(",\n    "),
+            if original_sql.contains("WITHOUT ROWID") {
+                " WITHOUT ROWID"
+            } else {
+                ""
+            }
+        ),
+        format!(
+            "INSERT INTO {} ({}) SELECT {} FROM {}",
+            quote(&new_table),
+            quote_list(&kept_names),
+            quote_list(&kept_names),
+            quote(table)
+        ),
+        format!("DROP TABLE {}", quote(table)),
+        format!(
+            "ALTER TABLE {} RENAME TO {}",
+            quote(&new_table),
+            quote(table)
+        ),
+    ];
+
+    // Recreate the explicit indexes and triggers, indexes on the dropped column are dropped
+    let objects = conn
+        .query::<(String, String, String)>(
+            "SELECT type, name, sql FROM sqlite_master \
+                WHERE tbl_name = ? AND type IN ('index', 'trigger') AND sql IS NOT NULL",
+            table.to_string(),
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (r#type, name, sql) in objects {
+        let uses_dropped_column = r#type == "index"
+            && indexes.iter().any(|index| {
+                index.name == name && index.columns.iter().any(|c| c == dropped_column)
+            });
+        if !uses_dropped_column {
+            statements.push(sql);
+        }
+    }
+    Ok(statements)
+}
+
+/// Check if an uppercased SQL statement contains a keyword outside quoted strings and names
+fn has_keyword(sql: &str, keyword: &str) -> bool {
+    let mut chars = sql.chars().peekable();
+    let mut word = String::new();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        if word == keyword {
+            return true;
+        }
+        word.clear();
+        let close = match c {
+            '\'' | '"' | '`' => c,
+            '[' => ']',
+            _ => continue,
+        };
+        // Skip the quoted part, a doubled quote is an escaped quote
+        while let Some(c) = chars.next() {
+            if c == close && (close == ']' || chars.next_if_eq(&close).is_none()) {
+                break;
+            }
+        }
+    }
+    word == keyword
+}
+
+// MARK: Apply
+/// Run the statements of a schema operation in a transaction with foreign key enforcement
+/// disabled, as required by the table rebuild procedure
+pub(crate) fn apply_schema_sql(conn: &Connection, statements: &[String]) -> Result<()> {
+    if !conn.is_autocommit() {
+        bail!("Commit or roll back the pending changes first");
+    }
+
+    let foreign_keys = conn.query_some::<i64>("PRAGMA foreign_keys", ())?;
+    conn.execute("PRAGMA foreign_keys = OFF", ())?;
+    let result = (|| {
+        conn.execute("BEGIN", ())?;
+        for statement in statements {
+            conn.execute(statement, ())?;
+        }
+        if conn.query_some::<i64>("SELECT COUNT(*) FROM pragma_foreign_key_check", ())? > 0 {
+            bail!("The change would violate foreign key constraints");
+        }
+        conn.execute("COMMIT", ())?;
+        Ok(())
+    })();
+    if result.is_err() && !conn.is_autocommit() {
+        _ = conn.execute("ROLLBACK", ());
+    }
+    conn.execute(format!("PRAGMA foreign_keys = {foreign_keys}"), ())?;
+    result
+}
@@ -0,0 +1,73 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::fs::{self, File};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+const RECENT_FILES_MAX: usize = 10;
+
+#[derive(Clone, Deserialize, Serialize, PartialEq)]
+pub(crate) struct Favorite {
+    pub path: String,
+    pub table: String,
+}
+
+/// Settings that are kept between runs, stored as JSON in the config directory
+#[derive(Clone, Default, Deserialize, Serialize)]
+pub(crate) struct Settings {
+    #[serde(default)]
+    pub recent_files: Vec<String>,
+    #[serde(default)]
+    pub favorites: Vec<Favorite>,
+}
+
+/// Directory where the settings and the query history are stored
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    let project_dirs = directories::ProjectDirs::from("nl", "bplaat", "SequelExplorer")?;
+    let config_dir = project_dirs.config_dir();
+    fs::create_dir_all(&config_dir).ok()?;
+    Some(config_dir)
+}
+
+impl Settings {
+    fn path() -> Option<PathBuf> {
+        Some(config_dir()?.join("settings.json"))
+    }
+
+    pub(crate) fn load() -> Settings {
+        Settings::path()
+            .and_then(|path| File::open(path).ok())
+            .and_then(|file| serde_json::from_reader(file).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self) {
+        let Some(path) = Settings::path() else {
+            return;
+        };
+        if let Ok(file) = File::create(path) {
+            _ = serde_json::to_writer_pretty(file, self);
+        }
+    }
+
+    /// Move the path to the front of the recent files list
+    pub(crate) fn add_recent_file(&mut self, path: &str) {
+        self.recent_files.retain(|recent| recent != path);
+        self.recent_files.insert(0, path.to_string());
+        self.recent_files.truncate(RECENT_FILES_MAX);
+    }
+
+    /// Pin the table when it isn't a favorite yet, unpin it otherwise
+    pub(crate) fn toggle_favorite(&mut self, favorite: Favorite) {
+        if self.favorites.contains(&favorite) {
+            self.favorites.retain(|other| *other != favorite);
+        } else {
+            self.favorites.push(favorite);
+        }
+    }
+}
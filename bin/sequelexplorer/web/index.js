@@ -11,6 +11,27 @@ if (navigator.userAgent.includes('bwebview') && navigator.userAgent.includes('Ma
 window.addEventListener('contextmenu', (e) => e.preventDefault());
 
 const PAGE_SIZE = 100;
+const SEARCH_DEBOUNCE = 300;
+const FILTER_OPERATORS = { '=': 'equals', '!=': 'notEquals', '>': 'greaterThan', '<': 'lessThan' };
+const QUERY_PAGE_SIZE = 100;
+const SCHEMA_DIALOG_TITLES = {
+    createTable: 'New table',
+    renameTable: 'Rename table',
+    dropTable: 'Drop table',
+    addColumn: 'Add column',
+    renameColumn: 'Rename column',
+    dropColumn: 'Drop column',
+    createIndex: 'Create index',
+    dropIndex: 'Drop index',
+};
+const DIAGRAM_PADDING = 32;
+const DIAGRAM_TABLE_WIDTH = 240;
+const DIAGRAM_HEADER_HEIGHT = 30;
+const DIAGRAM_ROW_HEIGHT = 22;
+const DIAGRAM_GAP_X = 96;
+const DIAGRAM_GAP_Y = 32;
+const DIAGRAM_CURVE = 48;
+const MAINTENANCE_TITLES = { vacuum: 'VACUUM', analyze: 'ANALYZE', integrityCheck: 'Integrity check' };
 
 function ipcSend(type, data = {}) {
     window.ipc.postMessage(JSON.stringify({ type, ...data }));
@@ -30,11 +51,25 @@ async function ipcRequest(type, data = {}) {
     });
 }
 
+function fileName(path) {
+    return path.replace(/.*[\\/]/, '');
+}
+
+function formatSize(bytes) {
+    const units = ['B', 'KB', 'MB', 'GB'];
+    let unit = 0;
+    while (bytes >= 1024 && unit < units.length - 1) {
+        bytes /= 1024;
+        unit++;
+    }
+    return `${unit === 0 ? bytes : bytes.toFixed(1)} ${units[unit]}`;
+}
+
 PetiteVue.createApp({
-    dbPath: '',
-    dbFileName: '',
-    dbOpened: false,
-    tables: [],
+    databases: [],
+    recentFiles: [],
+    favorites: [],
+    currentDb: null,
     currentTable: null,
     activeTab: 'data',
     rowCount: '',
@@ -45,11 +80,89 @@ PetiteVue.createApp({
     showDataEmpty: false,
     dataEmptyText: 'No rows',
     schemaText: '',
-    queryText: '',
+    schemaStructure: { columns: [], indexes: [] },
+    schemaDialog: null,
+    schemaPreview: null,
+    schemaError: '',
+    diagram: null,
+    diagramError: '',
+    info: null,
+    infoError: '',
+    maintenance: null,
+    maintenanceStatus: '',
+    integrityMessages: null,
     isCustomQuery: false,
+    customQuerySql: '',
     currentOffset: 0,
     currentTotal: 0,
     isLoading: false,
+    sortColumn: null,
+    sortDescending: false,
+    search: '',
+    searchTimeout: null,
+    filters: [],
+    filterInputs: {},
+    keyColumns: [],
+    rowids: null,
+    editingCell: null,
+    editValue: '',
+    editNull: false,
+    draftRow: null,
+    pendingChanges: [],
+    transactionDb: null,
+    showExport: false,
+    exportFormat: 'csv',
+    exportDelimiter: ',',
+    exportQuote: 'minimal',
+    exporting: false,
+    exportStatus: '',
+    querySql: '',
+    queryParams: [],
+    queryRunning: false,
+    queryColumns: [],
+    queryRows: [],
+    queryPage: 0,
+    queryStatus: '',
+    queryMessage: '',
+    queryPanel: null,
+    history: [],
+    historySearch: '',
+    historyTimeout: null,
+    snippets: [],
+    snippetName: '',
+    urlDialog: null,
+
+    get dbOpened() {
+        return this.databases.length > 0;
+    },
+
+    get currentDatabase() {
+        return this.databases[this.currentDb] ?? null;
+    },
+
+    get dbFileName() {
+        return this.currentDatabase?.name ?? '';
+    },
+
+    get closedRecentFiles() {
+        return this.recentFiles.filter((path) => !this.databases.some((database) => database.path === path));
+    },
+
+    get isReadOnly() {
+        return this.currentDatabase?.readOnly ?? false;
+    },
+
+    get isEditable() {
+        return this.currentTable !== null && !this.isCustomQuery;
+    },
+
+    get queryPageCount() {
+        return Math.ceil(this.queryRows.length / QUERY_PAGE_SIZE);
+    },
+
+    get queryPageRows() {
+        return this.queryRows.slice(this.queryPage * QUERY_PAGE_SIZE, (this.queryPage + 1) * QUERY_PAGE_SIZE);
+    },
 
     async init() {
         const observer = new IntersectionObserver(
@@ -62,58 +175,204 @@ PetiteVue.createApp({
         );
         observer.observe(this.$refs.loadSentinel);
 
-        const lastDbPath = localStorage.getItem('lastDbPath');
-        if (lastDbPath) {
-            await this._openDatabaseByPath(lastDbPath);
+        await this.loadSettings();
+
+        // Reopen the databases of the previous session, older sessions only stored the paths
+        const openDatabases = JSON.parse(localStorage.getItem('openDatabases') ?? '[]');
+        for (const entry of openDatabases) {
+            const { path, readOnly } = typeof entry === 'string' ? { path: entry, readOnly: true } : entry;
+            await this._openDatabaseByPath(path, readOnly);
+        }
+        const lastTable = JSON.parse(localStorage.getItem('lastTable') ?? 'null');
+        const lastDb = this.databases.findIndex((database) => database.path === lastTable?.path);
+        if (lastDb !== -1 && this.databases[lastDb].tables.includes(lastTable.table)) {
+            await this.selectTable(lastDb, lastTable.table);
         }
     },
 
+    api(path) {
+        return `/api/databases/${this.currentDb}${path}`;
+    },
+
+    async loadSettings() {
+        const res = await fetch('/api/settings');
+        const { recent_files, favorites } = await res.json();
+        this.recentFiles = recent_files;
+        this.favorites = favorites;
+    },
+
     async openDatabase() {
         const { path } = await ipcRequest('openFileDialog');
         if (!path) return;
-        await this._openDatabaseByPath(path);
+        const db = await this._openDatabaseByPath(path);
+        if (db !== null) this.selectDatabase(db);
     },
 
-    async _openDatabaseByPath(path) {
-        const { ok, error } = await ipcRequest('openDatabase', { path });
-        if (!ok) {
+    async openRecentFile(path) {
+        const db = await this._openDatabaseByPath(path);
+        if (db !== null) this.selectDatabase(db);
+    },
+
+    openUrlDialog() {
+        this.urlDialog = { url: '', readOnly: true, loading: false, error: '' };
+    },
+
+    async openUrl() {
+        const url = this.urlDialog.url.trim();
+        if (!url || this.urlDialog.loading) return;
+        this.urlDialog.loading = true;
+        this.urlDialog.error = '';
+        const { database, error } = await ipcRequest('openUrl', { url, read_only: this.urlDialog.readOnly });
+        if (error) {
+            this.urlDialog.loading = false;
+            this.urlDialog.error = 'Failed to open database: ' + error;
+            return;
+        }
+        this.urlDialog = null;
+        await this.loadDatabases();
+        this.selectDatabase(database);
+    },
+
+    async _openDatabaseByPath(path, readOnly = true) {
+        const { database, error } = await ipcRequest('openDatabase', { path, read_only: readOnly });
+        if (error) {
             alert('Failed to open database:\n' + error);
+            return null;
+        }
+        await this.loadDatabases();
+        await this.loadSettings();
+        if (this.currentDb === null) this.selectDatabase(database);
+        return database;
+    },
+
+    async loadDatabases() {
+        const res = await fetch('/api/databases');
+        const databases = await res.json();
+        this.databases = await Promise.all(
+            databases.map(async ({ path, read_only, url }, db) => {
+                const res = await fetch(`/api/databases/${db}/tables`);
+                return { path, url, readOnly: read_only, name: fileName(url ?? path), tables: await res.json() };
+            }),
+        );
+
+        // Downloaded databases are temp files, so they aren't reopened in the next session
+        const openDatabases = databases
+            .filter(({ url }) => url === null)
+            .map(({ path, read_only }) => ({ path, readOnly: read_only }));
+        localStorage.setItem('openDatabases', JSON.stringify(openDatabases));
+    },
+
+    async toggleReadOnly(db) {
+        if (this.transactionDb === db && this.pendingChanges.length > 0) {
+            alert('Commit or roll back the pending changes before changing the open mode');
             return;
         }
-        this.dbPath = path;
-        this.dbFileName = path.replace(/.*[\\/]/, '');
-        localStorage.setItem('lastDbPath', path);
-        this.dbOpened = true;
-        document.title = `Sequel Explorer - ${this.dbFileName}`;
-        await this.loadTables();
-        const lastTable = localStorage.getItem('lastTableName');
-        if (lastTable && this.tables.includes(lastTable)) {
-            await this.selectTable(lastTable);
+        const res = await fetch(`/api/databases/${db}/read_only`, {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ read_only: !this.databases[db].readOnly }),
+        });
+        const data = await res.json();
+        if (data.error) {
+            alert('Failed to change the open mode:\n' + data.error);
+            return;
         }
+        await this.loadDatabases();
     },
 
     async loadTables() {
-        const res = await fetch('/api/tables');
-        this.tables = await res.json();
+        const res = await fetch(this.api('/tables'));
+        this.currentDatabase.tables = await res.json();
+    },
+
+    selectDatabase(db) {
+        if (db === this.currentDb) return;
+        this.currentDb = db;
+        this.currentTable = null;
+        this.isCustomQuery = false;
+        this.diagram = null;
+        this.info = null;
+        this.maintenanceStatus = '';
+        this.integrityMessages = null;
+        document.title = `Sequel Explorer - ${this.dbFileName}`;
+        if (this.activeTab === 'diagram') this.openDiagram();
+        if (this.activeTab === 'info') this.openInfo();
     },
 
-    async selectTable(name) {
-        if (name === this.currentTable) return;
+    async closeDatabase(db) {
+        if (this.transactionDb === db && this.pendingChanges.length > 0) {
+            alert('Commit or roll back the pending changes before closing the database');
+            return;
+        }
+        await fetch(`/api/databases/${db}/close`, { method: 'POST' });
+        if (this.transactionDb === db) {
+            this.transactionDb = null;
+        } else if (this.transactionDb > db) {
+            this.transactionDb--;
+        }
+        if (this.currentDb === db) {
+            this.currentDb = null;
+            this.currentTable = null;
+            this.diagram = null;
+            this.info = null;
+            document.title = 'Sequel Explorer';
+        } else if (this.currentDb > db) {
+            this.currentDb--;
+        }
+        await this.loadDatabases();
+        if (this.currentDb === null && this.dbOpened) this.selectDatabase(0);
+    },
+
+    async selectTable(db, name) {
+        if (db === this.currentDb && name === this.currentTable) return;
+        this.selectDatabase(db);
         this.currentTable = name;
         this.isCustomQuery = false;
-        this.queryText = '';
-        localStorage.setItem('lastTableName', name);
+        localStorage.setItem('lastTable', JSON.stringify({ path: this.currentDatabase.path, table: name }));
         await this.openTableView(name);
     },
 
+    isFavorite(path, table) {
+        return this.favorites.some((favorite) => favorite.path === path && favorite.table === table);
+    },
+
+    async toggleFavorite(path, table) {
+        const res = await fetch('/api/settings/favorites/toggle', {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ path, table }),
+        });
+        const data = await res.json();
+        if (!data.error) this.favorites = data.favorites;
+    },
+
+    async openFavorite({ path, table }) {
+        let db = this.databases.findIndex((database) => database.path === path);
+        if (db === -1) db = await this._openDatabaseByPath(path);
+        if (db === null) return;
+        if (!this.databases[db].tables.includes(table)) {
+            alert(`Table ${table} not found in ${fileName(path)}`);
+            return;
+        }
+        await this.selectTable(db, table);
+    },
+
     async openTableView(name) {
         document.title = `Sequel Explorer - ${this.dbFileName} - ${name}`;
 
         this.currentOffset = 0;
         this.currentTotal = 0;
         this.isCustomQuery = false;
+        this.sortColumn = null;
+        this.sortDescending = false;
+        this.search = '';
+        this.filters = [];
+        this.filterInputs = {};
         this.columns = [];
         this.rows = [];
+        this.rowids = null;
+        this.editingCell = null;
+        this.draftRow = null;
         this.rowCount = '';
         this.showDataEmpty = false;
         this.showDataLoading = true;
@@ -122,7 +381,11 @@ PetiteVue.createApp({
 
         await this.loadMoreRows(name);
 
-        fetch(`/api/table/${encodeURIComponent(name)}/schema`)
+        this.loadTableSchema(name);
+    },
+
+    async loadTableSchema(name) {
+        fetch(this.api(`/table/${encodeURIComponent(name)}/schema`))
             .then((r) => r.json())
             .then((data) => {
                 this.schemaText = data.error ? 'Error: ' + data.error : data.sql || '';
@@ -130,6 +393,10 @@ PetiteVue.createApp({
             .catch((err) => {
                 this.schemaText = 'Error loading schema: ' + err.message;
             });
+
+        const res = await fetch(this.api(`/table/${encodeURIComponent(name)}/structure`));
+        const data = await res.json();
+        this.schemaStructure = data.error ? { columns: [], indexes: [] } : data;
     },
 
     async loadMoreRows(tableName) {
@@ -139,8 +406,19 @@ PetiteVue.createApp({
         this.isLoading = true;
         this.showDataLoading = true;
 
-        const url = `/api/table/${encodeURIComponent(tableName)}/data?offset=${this.currentOffset}&limit=${PAGE_SIZE}`;
-        const res = await fetch(url);
+        const params = new URLSearchParams({ offset: this.currentOffset, limit: PAGE_SIZE });
+        if (this.sortColumn) {
+            params.set('sort', this.sortColumn);
+            params.set('descending', this.sortDescending);
+        }
+        if (this.search) params.set('search', this.search);
+        if (this.filters.length > 0) {
+            params.set(
+                'filters',
+                JSON.stringify(this.filters.map(({ column, operator, value }) => ({ column, operator, value }))),
+            );
+        }
+        const res = await fetch(this.api(`/table/${encodeURIComponent(tableName)}/data?${params}`));
         const data = await res.json();
 
         this.showDataLoading = false;
@@ -157,18 +435,31 @@ PetiteVue.createApp({
 
         if (this.currentOffset === 0) {
             this.columns = data.columns;
+            this.keyColumns = data.key_columns;
+            this.rowids = data.rowids ? [] : null;
             this.showDataTable = true;
             if (data.rows.length === 0) {
-                this.dataEmptyText = 'No rows';
+                this.dataEmptyText = this.search || this.filters.length > 0 ? 'No matching rows' : 'No rows';
                 this.showDataEmpty = true;
                 return;
             }
         }
 
         this.appendRows(data.rows);
+        if (this.rowids) this.rowids = this.rowids.concat(data.rowids);
         this.currentOffset += data.rows.length;
     },
 
+    async reloadRows() {
+        this.currentOffset = 0;
+        this.currentTotal = 0;
+        this.rows = [];
+        this.rowids = null;
+        this.editingCell = null;
+        this.showDataEmpty = false;
+        await this.loadMoreRows(this.currentTable);
+    },
+
     appendRows(rows) {
         this.rows = this.rows.concat(rows);
     },
@@ -193,10 +484,414 @@ PetiteVue.createApp({
         }
     },
 
+    openSchemaDialog(type, options = {}) {
+        this.schemaDialog = {
+            type,
+            title: SCHEMA_DIALOG_TITLES[type],
+            table: type === 'createTable' ? '' : this.currentTable,
+            name: '',
+            newName: '',
+            columnName: '',
+            column: { name: '', type: '', not_null: false, default: '' },
+            columns: [{ name: 'id', type: 'INTEGER', primary_key: true, not_null: false, default: '' }],
+            indexColumns: [],
+            unique: false,
+            ...options,
+        };
+        this.schemaPreview = null;
+        this.schemaError = '';
+
+        // Drop operations have nothing to fill in, so show their statements right away
+        if (type.startsWith('drop')) this.previewSchemaChange();
+    },
+
+    schemaOperation() {
+        const { type, table, name, newName, columnName, column, columns, indexColumns, unique } = this.schemaDialog;
+        switch (type) {
+            case 'createTable':
+                return { type, table, columns };
+            case 'renameTable':
+                return { type, table, new_name: newName };
+            case 'dropTable':
+                return { type, table };
+            case 'addColumn':
+                return { type, table, column };
+            case 'renameColumn':
+                return { type, table, column: columnName, new_name: newName };
+            case 'dropColumn':
+                return { type, table, column: columnName };
+            case 'createIndex':
+                return { type, table, name, columns: indexColumns, unique };
+            case 'dropIndex':
+                return { type, name };
+        }
+    },
+
+    addSchemaDialogColumn() {
+        this.schemaDialog.columns.push({ name: '', type: '', primary_key: false, not_null: false, default: '' });
+        this.schemaPreview = null;
+    },
+
+    toggleIndexColumn(name) {
+        const indexColumns = this.schemaDialog.indexColumns;
+        this.schemaDialog.indexColumns = indexColumns.includes(name)
+            ? indexColumns.filter((column) => column !== name)
+            : [...indexColumns, name];
+        this.schemaPreview = null;
+    },
+
+    async previewSchemaChange() {
+        const res = await fetch(this.api('/schema/preview'), {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(this.schemaOperation()),
+        });
+        const { sql, error } = await res.json();
+        this.schemaError = error ? 'Error: ' + error : '';
+        this.schemaPreview = error ? null : sql.map((statement) => `${statement};`).join('\n\n');
+    },
+
+    async applySchemaChange() {
+        const operation = this.schemaOperation();
+        const res = await fetch(this.api('/schema/apply'), {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(operation),
+        });
+        const { error } = await res.json();
+        if (error) {
+            this.schemaError = 'Error: ' + error;
+            return;
+        }
+        this.schemaDialog = null;
+        this.diagram = null;
+
+        await this.loadTables();
+        if (operation.type === 'dropTable') {
+            this.currentTable = null;
+            localStorage.removeItem('lastTable');
+            document.title = `Sequel Explorer - ${this.dbFileName}`;
+            return;
+        }
+        const table = operation.type === 'renameTable' ? operation.new_name : operation.table;
+        if (table !== this.currentTable) {
+            await this.selectTable(this.currentDb, table);
+        } else {
+            await this.openTableView(table);
+        }
+        this.activeTab = 'schema';
+    },
+
+    async openDiagram() {
+        this.activeTab = 'diagram';
+        const res = await fetch(this.api('/diagram'));
+        const data = await res.json();
+        if (data.error) {
+            this.diagram = null;
+            this.diagramError = 'Error: ' + data.error;
+            return;
+        }
+        this.diagramError = '';
+        this.diagram = this.layoutDiagram(data);
+    },
+
+    layoutDiagram({ tables, relations }) {
+        const tableNames = new Set(tables.map((table) => table.name));
+        relations = relations.filter(
+            (relation) => tableNames.has(relation.table) && tableNames.has(relation.foreign_table),
+        );
+
+        // Tables are placed in levels, referenced tables are placed left of the tables referencing them
+        const levels = {};
+        const levelOf = (name, visiting = new Set()) => {
+            if (name in levels) return levels[name];
+            if (visiting.has(name)) return 0;
+            visiting.add(name);
+            const references = relations.filter(
+                (relation) => relation.table === name && relation.foreign_table !== name,
+            );
+            const level = Math.max(0, ...references.map((relation) => levelOf(relation.foreign_table, visiting) + 1));
+            visiting.delete(name);
+            levels[name] = level;
+            return level;
+        };
+
+        const levelHeights = [];
+        const boxes = {};
+        for (const table of tables) {
+            const level = levelOf(table.name);
+            const x = DIAGRAM_PADDING + level * (DIAGRAM_TABLE_WIDTH + DIAGRAM_GAP_X);
+            const y = levelHeights[level] ?? DIAGRAM_PADDING;
+            const height = DIAGRAM_HEADER_HEIGHT + table.columns.length * DIAGRAM_ROW_HEIGHT;
+            boxes[table.name] = {
+                name: table.name,
+                x,
+                y,
+                width: DIAGRAM_TABLE_WIDTH,
+                height,
+                headerHeight: DIAGRAM_HEADER_HEIGHT,
+                columns: table.columns.map((column, index) => ({
+                    ...column,
+                    y: y + DIAGRAM_HEADER_HEIGHT + index * DIAGRAM_ROW_HEIGHT + DIAGRAM_ROW_HEIGHT / 2,
+                })),
+            };
+            levelHeights[level] = y + height + DIAGRAM_GAP_Y;
+        }
+
+        // Foreign keys without a referenced column point to the primary key, so to the table header
+        const anchorY = (box, columnName) =>
+            box.columns.find((column) => column.name === columnName)?.y ?? box.y + DIAGRAM_HEADER_HEIGHT / 2;
+        const edges = relations.map((relation) => {
+            const from = boxes[relation.table];
+            const to = boxes[relation.foreign_table];
+            const y1 = anchorY(from, relation.column);
+            const y2 = anchorY(to, relation.foreign_column);
+            // Edges leave and enter the sides facing each other, tables in the same level loop around the right
+            const x1 = from.x > to.x ? from.x : from.x + from.width;
+            const x2 = from.x < to.x ? to.x : to.x + to.width;
+            const c1 = x1 + (from.x > to.x ? -DIAGRAM_CURVE : DIAGRAM_CURVE);
+            const c2 = x2 + (from.x < to.x ? -DIAGRAM_CURVE : DIAGRAM_CURVE);
+            const target = relation.foreign_column
+                ? `${relation.foreign_table}.${relation.foreign_column}`
+                : relation.foreign_table;
+            return {
+                key: `${relation.table}.${relation.column}`,
+                title: `${relation.table}.${relation.column} → ${target}`,
+                d: `M ${x1} ${y1} C ${c1} ${y1}, ${c2} ${y2}, ${x2} ${y2}`,
+            };
+        });
+
+        const boxList = Object.values(boxes);
+        return {
+            tables: boxList,
+            edges,
+            width: Math.max(0, ...boxList.map((box) => box.x + box.width)) + DIAGRAM_PADDING + DIAGRAM_CURVE,
+            height: Math.max(0, ...boxList.map((box) => box.y + box.height)) + DIAGRAM_PADDING,
+        };
+    },
+
+    async openInfo() {
+        this.activeTab = 'info';
+        const res = await fetch(this.api('/info'));
+        const data = await res.json();
+        if (data.error) {
+            this.info = null;
+            this.infoError = 'Error: ' + data.error;
+            return;
+        }
+        this.infoError = '';
+        this.info = data;
+    },
+
+    async runMaintenance(action) {
+        if (this.maintenance !== null) return;
+        if (this.transactionDb === this.currentDb && this.pendingChanges.length > 0) {
+            alert('Commit or roll back the pending changes before running maintenance');
+            return;
+        }
+
+        const db = this.currentDb;
+        const title = MAINTENANCE_TITLES[action];
+        this.maintenance = { action, done: 0, total: 0 };
+        this.maintenanceStatus = `${title}…`;
+        if (action === 'integrityCheck') this.integrityMessages = null;
+        const listener = (event) => {
+            const message = JSON.parse(event.data);
+            if (message.type === 'maintenanceProgress' && message.action === action) {
+                this.maintenance = { action, done: message.done, total: message.total };
+            }
+        };
+        window.ipc.addEventListener('message', listener);
+        const { messages, error } = await ipcRequest('runMaintenance', { database: db, action });
+        window.ipc.removeEventListener('message', listener);
+        this.maintenance = null;
+
+        if (db !== this.currentDb) return;
+        if (error) {
+            this.maintenanceStatus = `${title} failed: ${error}`;
+            return;
+        }
+        this.maintenanceStatus = `${title} finished`;
+        if (action === 'integrityCheck') this.integrityMessages = messages;
+        if (action === 'analyze') await this.loadTables();
+        await this.openInfo();
+    },
+
+    async openDiagramTable(name) {
+        if (name === this.currentTable) {
+            this.activeTab = 'data';
+            return;
+        }
+        await this.selectTable(this.currentDb, name);
+    },
+
+    toggleSort(column) {
+        if (this.sortColumn !== column) {
+            this.sortColumn = column;
+            this.sortDescending = false;
+        } else if (!this.sortDescending) {
+            this.sortDescending = true;
+        } else {
+            this.sortColumn = null;
+            this.sortDescending = false;
+        }
+        this.reloadRows();
+    },
+
+    searchRows() {
+        clearTimeout(this.searchTimeout);
+        this.searchTimeout = setTimeout(() => this.reloadRows(), SEARCH_DEBOUNCE);
+    },
+
+    parseFilter(column, text) {
+        // Filters are written as text, =value, !=value, >value, <value, NULL or !NULL
+        if (/^!?null$/i.test(text)) {
+            return { column, operator: text.startsWith('!') ? 'isNotNull' : 'isNull', value: '' };
+        }
+        const match = text.match(/^(!=|=|>|<)\s*(.*)$/);
+        if (match) return { column, operator: FILTER_OPERATORS[match[1]], value: match[2] };
+        return { column, operator: 'contains', value: text };
+    },
+
+    addFilter(column) {
+        const text = (this.filterInputs[column] ?? '').trim();
+        if (!text) return;
+        this.filters = [...this.filters, this.parseFilter(column, text)];
+        this.filterInputs[column] = '';
+        this.reloadRows();
+    },
+
+    removeFilter(index) {
+        this.filters = this.filters.filter((_, filterIndex) => filterIndex !== index);
+        this.reloadRows();
+    },
+
+    formatFilter({ column, operator, value }) {
+        switch (operator) {
+            case 'contains':
+                return `${column} contains "${value}"`;
+            case 'isNull':
+                return `${column} is NULL`;
+            case 'isNotNull':
+                return `${column} is not NULL`;
+            default: {
+                const symbol = Object.keys(FILTER_OPERATORS).find((key) => FILTER_OPERATORS[key] === operator);
+                return `${column} ${symbol} ${value}`;
+            }
+        }
+    },
+
+    rowKey(rowIdx) {
+        if (this.rowids) return { rowid: this.rowids[rowIdx] };
+        return Object.fromEntries(
+            this.keyColumns.map((name) => {
+                const colIdx = this.columns.findIndex((col) => col.name === name);
+                return [name, this.rows[rowIdx][colIdx]];
+            }),
+        );
+    },
+
+    async editRequest(action, body) {
+        // Each database has its own transaction, only one of them is tracked at a time
+        if (this.pendingChanges.length > 0 && this.transactionDb !== this.currentDb) {
+            alert('Commit or roll back the pending changes of the other database first');
+            return false;
+        }
+        const res = await fetch(this.api(`/table/${encodeURIComponent(this.currentTable)}/${action}`), {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify(body),
+        });
+        const data = await res.json();
+        if (data.error) {
+            alert(`Failed to ${action} row:\n` + data.error);
+            return false;
+        }
+        this.transactionDb = this.currentDb;
+        this.pendingChanges.push(data.sql);
+        return true;
+    },
+
+    isEditing(rowIdx, colIdx) {
+        return this.editingCell?.rowIdx === rowIdx && this.editingCell?.colIdx === colIdx;
+    },
+
+    startEdit(rowIdx, colIdx) {
+        if (!this.isEditable || this.isReadOnly || this.columns[colIdx].is_blob) return;
+        const val = this.rows[rowIdx][colIdx];
+        this.editingCell = { rowIdx, colIdx };
+        this.editValue = val === null ? '' : String(val);
+        this.editNull = val === null;
+        this.$nextTick(() => document.querySelector('.cell-edit-input')?.focus());
+    },
+
+    toggleEditNull() {
+        this.editNull = !this.editNull;
+        if (this.editNull) this.editValue = '';
+    },
+
+    cancelEdit() {
+        this.editingCell = null;
+    },
+
+    async saveEdit() {
+        if (!this.editingCell) return;
+        const { rowIdx, colIdx } = this.editingCell;
+        this.editingCell = null;
+
+        const oldValue = this.rows[rowIdx][colIdx];
+        const value = this.editNull ? null : this.editValue;
+        if (value === (oldValue === null ? null : String(oldValue))) return;
+
+        const key = this.rowKey(rowIdx);
+        if (await this.editRequest('update', { key, column: this.columns[colIdx].name, value })) {
+            this.rows[rowIdx][colIdx] = value;
+        }
+    },
+
+    async deleteRow(rowIdx) {
+        this.editingCell = null;
+        if (await this.editRequest('delete', { key: this.rowKey(rowIdx) })) {
+            this.rows.splice(rowIdx, 1);
+            if (this.rowids) this.rowids.splice(rowIdx, 1);
+            this.currentOffset--;
+            this.currentTotal--;
+            this.rowCount = `${this.currentTotal.toLocaleString()} rows`;
+        }
+    },
+
+    addRow() {
+        this.draftRow = this.columns.map(() => '');
+    },
+
+    async insertDraftRow() {
+        // Empty cells use the column default, NULL inserts a NULL value
+        const values = {};
+        this.columns.forEach((col, colIdx) => {
+            const value = this.draftRow[colIdx];
+            if (value !== '') values[col.name] = value.toUpperCase() === 'NULL' ? null : value;
+        });
+        if (await this.editRequest('insert', { values })) {
+            this.draftRow = null;
+            await this.reloadRows();
+        }
+    },
+
+    async endTransaction(action) {
+        const res = await fetch(`/api/databases/${this.transactionDb}/transaction/${action}`, { method: 'POST' });
+        const data = await res.json();
+        if (data.error) {
+            alert(`Failed to ${action} changes:\n` + data.error);
+            return;
+        }
+        this.pendingChanges = [];
+        if (action === 'rollback' && this.transactionDb === this.currentDb && this.isEditable) await this.reloadRows();
+    },
+
     async navigateToForeignKey(table, column, value) {
         const sql = `SELECT * FROM "${table}" WHERE "${column}" = ${this.formatSqlValue(value)}`;
-        this.queryText = sql;
         this.isCustomQuery = true;
+        this.customQuerySql = sql;
         this.activeTab = 'data';
         this.currentOffset = 0;
         this.currentTotal = 0;
@@ -207,7 +902,7 @@ PetiteVue.createApp({
         this.showDataLoading = true;
         this.showDataTable = false;
 
-        const res = await fetch('/api/query', {
+        const res = await fetch(this.api('/query'), {
             method: 'POST',
             headers: { 'Content-Type': 'application/json' },
             body: JSON.stringify({ sql }),
@@ -234,59 +929,191 @@ PetiteVue.createApp({
         this.appendRows(data.rows);
     },
 
-    async runQuery() {
-        const sql = this.queryText.trim();
-        if (!sql) return;
+    parseQueryParam(value) {
+        if (value === '' || value.toUpperCase() === 'NULL') return null;
+        if (/^-?\d+(\.\d+)?$/.test(value)) return Number(value);
+        return value;
+    },
 
-        this.isCustomQuery = true;
-        this.activeTab = 'data';
-        this.currentOffset = 0;
-        this.currentTotal = 0;
-        this.columns = [];
-        this.rows = [];
-        this.rowCount = '';
-        this.showDataEmpty = false;
-        this.showDataLoading = true;
-        this.showDataTable = false;
+    async runQuery() {
+        const sql = this.querySql.trim();
+        if (!sql || this.queryRunning) return;
 
-        const res = await fetch('/api/query', {
+        // Show parameter inputs first when the query has new parameters
+        const res = await fetch(this.api('/query/parameters'), {
             method: 'POST',
             headers: { 'Content-Type': 'application/json' },
             body: JSON.stringify({ sql }),
         });
+        const { parameters, error: parametersError } = await res.json();
+        if (parametersError) {
+            this.queryColumns = [];
+            this.queryRows = [];
+            this.queryStatus = '';
+            this.queryMessage = 'Error: ' + parametersError;
+            return;
+        }
+        const names = parameters.map((name, index) => name ?? `?${index + 1}`);
+        if (names.join('\n') !== this.queryParams.map((param) => param.name).join('\n')) {
+            this.queryParams = names.map((name) => ({
+                name,
+                value: this.queryParams.find((param) => param.name === name)?.value ?? '',
+            }));
+            if (names.length > 0) {
+                this.queryMessage = 'Fill in the parameters and run again';
+                return;
+            }
+        }
+
+        this.queryRunning = true;
+        this.queryColumns = [];
+        this.queryRows = [];
+        this.queryPage = 0;
+        this.queryStatus = '';
+        this.queryMessage = '';
+
+        const { result, error } = await ipcRequest('runQuery', {
+            database: this.currentDb,
+            sql,
+            params: this.queryParams.map((param) => this.parseQueryParam(param.value)),
+        });
+        this.queryRunning = false;
+        if (this.queryPanel === 'history') this.loadHistory();
+
+        if (error) {
+            this.queryMessage = 'Error: ' + error;
+            return;
+        }
+
+        const duration = `${result.duration_ms.toFixed(1)} ms`;
+        if (result.columns.length === 0) {
+            this.queryStatus = `${result.affected_rows.toLocaleString()} rows affected · ${duration}`;
+            this.queryMessage = 'Query executed';
+            return;
+        }
+        this.queryStatus = `${result.rows.length.toLocaleString()} rows · ${duration}`;
+        this.queryColumns = result.columns;
+        this.queryRows = result.rows;
+    },
+
+    cancelQuery() {
+        ipcSend('cancelQuery');
+    },
+
+    toggleQueryPanel(panel) {
+        this.queryPanel = this.queryPanel === panel ? null : panel;
+        if (this.queryPanel === 'history') this.loadHistory();
+        if (this.queryPanel === 'snippets') this.loadSnippets();
+    },
+
+    async loadHistory() {
+        const params = new URLSearchParams({ search: this.historySearch });
+        const res = await fetch(`/api/history?${params}`);
         const data = await res.json();
-        this.showDataLoading = false;
+        this.history = data.error ? [] : data;
+    },
 
+    searchHistory() {
+        clearTimeout(this.historyTimeout);
+        this.historyTimeout = setTimeout(() => this.loadHistory(), SEARCH_DEBOUNCE);
+    },
+
+    async clearHistory() {
+        await fetch('/api/history/clear', { method: 'POST' });
+        this.history = [];
+    },
+
+    useHistoryEntry(entry) {
+        this.querySql = entry.sql;
+    },
+
+    formatTimestamp(ms) {
+        return new Date(ms).toLocaleString();
+    },
+
+    async loadSnippets() {
+        const res = await fetch('/api/snippets');
+        const data = await res.json();
+        this.snippets = data.error ? [] : data;
+    },
+
+    async saveSnippet() {
+        const name = this.snippetName.trim();
+        const sql = this.querySql.trim();
+        if (!name || !sql) return;
+        const res = await fetch('/api/snippets', {
+            method: 'POST',
+            headers: { 'Content-Type': 'application/json' },
+            body: JSON.stringify({ name, sql }),
+        });
+        const data = await res.json();
         if (data.error) {
-            this.dataEmptyText = 'Error: ' + data.error;
-            this.showDataEmpty = true;
+            alert('Failed to save snippet:\n' + data.error);
             return;
         }
+        this.snippets = data;
+        this.snippetName = '';
+    },
 
-        this.rowCount = `${data.rows.length.toLocaleString()} rows`;
-        this.columns = data.columns;
-        this.showDataTable = true;
+    async deleteSnippet(id) {
+        const res = await fetch(`/api/snippets/${id}/delete`, { method: 'POST' });
+        const data = await res.json();
+        if (!data.error) this.snippets = data;
+    },
 
-        if (data.rows.length === 0) {
-            this.dataEmptyText = 'No rows';
-            this.showDataEmpty = true;
-            return;
+    async runSnippet(snippet) {
+        // Clearing the parameters makes runQuery ask for the values of the snippet placeholders
+        this.querySql = snippet.sql;
+        this.queryParams = [];
+        await this.runQuery();
+    },
+
+    async exportData() {
+        let source;
+        if (this.activeTab === 'query') {
+            source = {
+                name: 'query',
+                sql: this.querySql.trim(),
+                params: this.queryParams.map((param) => this.parseQueryParam(param.value)),
+            };
+        } else if (this.isCustomQuery) {
+            source = { name: this.currentTable, sql: this.customQuerySql, params: [] };
+        } else {
+            source = { name: this.currentTable, sql: `SELECT * FROM "${this.currentTable}"`, params: [] };
         }
+        if (!source.sql || this.exporting) return;
 
-        this.appendRows(data.rows);
+        this.exporting = true;
+        this.exportStatus = 'Exporting…';
+        const { path, rows, error } = await ipcRequest('exportData', {
+            database: this.currentDb,
+            ...source,
+            options: {
+                format: this.exportFormat,
+                delimiter: this.exportDelimiter,
+                quote: this.exportQuote,
+            },
+        });
+        this.exporting = false;
+
+        if (error) {
+            this.exportStatus = 'Error: ' + error;
+        } else if (path) {
+            this.exportStatus = `Exported ${rows.toLocaleString()} rows to ${fileName(path)}`;
+        } else {
+            this.exportStatus = '';
+        }
     },
 
     clearQuery() {
-        this.queryText = '';
         this.isCustomQuery = false;
         if (this.currentTable) this.openTableView(this.currentTable);
     },
 
-    formatCellValue(val, colIdx) {
+    formatCellValue(val, column) {
         if (val === null) return 'NULL';
 
-        const column = this.columns[colIdx];
-        if (column.is_blob) {
+        if (column && column.is_blob) {
             try {
                 const bytes = Uint8Array.fromBase64(val);
                 if (bytes.length === 16) {
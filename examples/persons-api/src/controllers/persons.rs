@@ -195,7 +195,7 @@ pub(crate) fn persons_delete(req: &Request, ctx: &Context) -> Result<Response> {
         .execute("DELETE FROM persons WHERE id = ?", person.id)?;
 
     // Success response
-    Ok(Response::with_status(Status::NoContent))
+    Ok(Response::no_content())
 }
 
 // MARK: Helpers
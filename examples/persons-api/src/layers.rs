@@ -6,7 +6,7 @@
 
 use anyhow::Result;
 use log::info;
-use small_http::{Method, Request, Response, Status};
+use small_http::{Method, Request, Response};
 
 use crate::context::Context;
 
@@ -19,7 +19,7 @@ pub(crate) fn log_pre_layer(req: &Request, _: &mut Context) -> Option<Result<Res
 // MARK: CORS
 pub(crate) fn cors_pre_layer(req: &Request, _: &mut Context) -> Option<Result<Response>> {
     if req.method == Method::Options && req.headers.get("Access-Control-Request-Method").is_some() {
-        Some(Ok(Response::with_status(Status::NoContent)
+        Some(Ok(Response::no_content()
             .header("Access-Control-Allow-Origin", "*")
             .header(
                 "Access-Control-Allow-Methods",
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -37,6 +37,6 @@ impl Default for IndexQuery {
 // MARK: Validate
 impl From<validate::Report> for api::Report {
     fn from(report: validate::Report) -> Self {
-        Self(report.0)
+        Self(report.errors)
     }
 }
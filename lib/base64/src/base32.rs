@@ -0,0 +1,129 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Base32 engines (RFC 4648)
+
+use crate::{DecodeError, Engine};
+
+const ENCODE_TABLE: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// A base32 engine with the RFC 4648 alphabet, decoding also accepts lowercase letters.
+pub struct Base32 {
+    padding: bool,
+}
+
+/// Base32 alphabet with `=` padding.
+pub static BASE32: Base32 = Base32 { padding: true };
+
+/// Base32 alphabet without padding.
+pub static BASE32_NO_PAD: Base32 = Base32 { padding: false };
+
+impl Engine for Base32 {
+    fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+        let mut out = String::with_capacity(input.len().div_ceil(5) * 8);
+        let mut buf = 0u32;
+        let mut bits = 0u32;
+        for &byte in input {
+            buf = (buf << 8) | byte as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                out.push(ENCODE_TABLE[((buf >> bits) & 0x1f) as usize] as char);
+            }
+            buf &= (1 << bits) - 1;
+        }
+        if bits > 0 {
+            out.push(ENCODE_TABLE[((buf << (5 - bits)) & 0x1f) as usize] as char);
+        }
+        if self.padding {
+            while !out.len().is_multiple_of(8) {
+                out.push('=');
+            }
+        }
+        out
+    }
+
+    fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_ref();
+        // Padding can only be followed by more padding up to a whole 8 character block
+        let data_len = input.iter().position(|&b| b == b'=').unwrap_or(input.len());
+        if input[data_len..].iter().any(|&b| b != b'=')
+            || (data_len < input.len() && !input.len().is_multiple_of(8))
+        {
+            return Err(DecodeError);
+        }
+        // A block can't end after 1, 3 or 6 characters, those don't complete a byte
+        if matches!(data_len % 8, 1 | 3 | 6) {
+            return Err(DecodeError);
+        }
+
+        let mut out = Vec::with_capacity(data_len * 5 / 8);
+        let mut buf = 0u32;
+        let mut bits = 0u32;
+        for &byte in &input[..data_len] {
+            let val = match byte {
+                b'A'..=b'Z' => byte - b'A',
+                b'a'..=b'z' => byte - b'a',
+                b'2'..=b'7' => byte - b'2' + 26,
+                _ => return Err(DecodeError),
+            };
+            buf = (buf << 5) | val as u32;
+            bits += 5;
+            if bits >= 8 {
+                bits -= 8;
+                out.push((buf >> bits) as u8);
+                buf &= (1 << bits) - 1;
+            }
+        }
+        // The unused bits of the last character must be zero, so every input has one encoding
+        if buf != 0 {
+            return Err(DecodeError);
+        }
+        Ok(out)
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(BASE32.encode(b""), "");
+        assert_eq!(BASE32.encode(b"f"), "MY======");
+        assert_eq!(BASE32.encode(b"fo"), "MZXQ====");
+        assert_eq!(BASE32.encode(b"foo"), "MZXW6===");
+        assert_eq!(BASE32.encode(b"foob"), "MZXW6YQ=");
+        assert_eq!(BASE32.encode(b"fooba"), "MZXW6YTB");
+        assert_eq!(BASE32.encode(b"foobar"), "MZXW6YTBOI======");
+        assert_eq!(BASE32_NO_PAD.encode(b"foobar"), "MZXW6YTBOI");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(BASE32.decode(b"MY======").expect("decode"), b"f");
+        assert_eq!(BASE32.decode(b"MZXW6YQ=").expect("decode"), b"foob");
+        assert_eq!(
+            BASE32_NO_PAD.decode(b"MZXW6YTBOI").expect("decode"),
+            b"foobar"
+        );
+        assert_eq!(
+            BASE32_NO_PAD.decode(b"mzxw6ytboi").expect("decode"),
+            b"foobar"
+        );
+        assert!(BASE32.decode(b"MZXW1===").is_err());
+        assert!(BASE32.decode(b"MY==A===").is_err());
+        assert!(BASE32.decode(b"MY=").is_err());
+        assert!(BASE32.decode(b"MZ======").is_err());
+        assert!(BASE32_NO_PAD.decode(b"MZX").is_err());
+        assert_eq!(
+            BASE32.decode(b"MZ======").unwrap_err().to_string(),
+            "invalid base64"
+        );
+    }
+}
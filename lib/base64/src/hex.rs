@@ -0,0 +1,74 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Hex engines
+
+use crate::{DecodeError, Engine};
+
+/// A hex engine, decoding accepts both lowercase and uppercase digits.
+pub struct Hex {
+    encode_table: &'static [u8; 16],
+}
+
+/// Lowercase hex digits.
+pub static HEX_LOWER: Hex = Hex {
+    encode_table: b"0123456789abcdef",
+};
+
+/// Uppercase hex digits.
+pub static HEX_UPPER: Hex = Hex {
+    encode_table: b"0123456789ABCDEF",
+};
+
+const fn decode_digit(byte: u8) -> Result<u8, DecodeError> {
+    match byte {
+        b'0'..=b'9' => Ok(byte - b'0'),
+        b'a'..=b'f' => Ok(byte - b'a' + 10),
+        b'A'..=b'F' => Ok(byte - b'A' + 10),
+        _ => Err(DecodeError),
+    }
+}
+
+impl Engine for Hex {
+    fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+        let mut out = String::with_capacity(input.len() * 2);
+        for &byte in input {
+            out.push(self.encode_table[(byte >> 4) as usize] as char);
+            out.push(self.encode_table[(byte & 0x0f) as usize] as char);
+        }
+        out
+    }
+
+    fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, DecodeError> {
+        let input = input.as_ref();
+        if !input.len().is_multiple_of(2) {
+            return Err(DecodeError);
+        }
+        input
+            .chunks(2)
+            .map(|pair| Ok((decode_digit(pair[0])? << 4) | decode_digit(pair[1])?))
+            .collect()
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(HEX_LOWER.encode(b"\x00\x7f\xab"), "007fab");
+        assert_eq!(HEX_UPPER.encode(b"\x00\x7f\xab"), "007FAB");
+        assert_eq!(
+            HEX_LOWER.decode(b"007FaB").expect("decode"),
+            b"\x00\x7f\xab"
+        );
+        assert!(HEX_LOWER.decode(b"abc").is_err());
+        assert!(HEX_LOWER.decode(b"zz").is_err());
+    }
+}
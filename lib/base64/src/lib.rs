@@ -4,18 +4,22 @@
  * SPDX-License-Identifier: MIT
  */
 
-//! A minimal replacement for the [base64](https://crates.io/crates/base64) crate
+//! A minimal replacement for the [base64](https://crates.io/crates/base64) crate, with extra
+//! [base32] and [hex] engines
+
+pub mod base32;
+pub mod hex;
 
 // MARK: Engine
-/// Trait for base64 encoding and decoding engines.
+/// Trait for base64, base32 and hex encoding and decoding engines.
 pub trait Engine {
-    /// Encode bytes to a base64 `String`.
+    /// Encode bytes to an encoded `String`.
     fn encode<T: AsRef<[u8]>>(&self, input: T) -> String;
-    /// Decode a base64 byte string to `Vec<u8>`.
+    /// Decode an encoded byte string to `Vec<u8>`.
     fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, DecodeError>;
 }
 
-/// Error returned when decoding invalid base64 input.
+/// Error returned when decoding invalid base64, base32 or hex input.
 #[derive(Debug)]
 pub struct DecodeError;
 
@@ -158,6 +162,8 @@ pub mod engine {
 
 /// Prelude - import this to get the most common engines and the `Engine` trait.
 pub mod prelude {
+    pub use crate::base32::{BASE32, BASE32_NO_PAD};
+    pub use crate::hex::{HEX_LOWER, HEX_UPPER};
     pub use crate::{
         BASE64_STANDARD, BASE64_STANDARD_NO_PAD, BASE64_URL_SAFE, BASE64_URL_SAFE_NO_PAD, Engine,
     };
@@ -9,8 +9,9 @@
 #![cfg(target_vendor = "apple")]
 #![allow(unsafe_code)]
 
-use std::ffi::c_void;
+use std::ffi::{c_ulong, c_void};
 use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::ops::Deref;
 use std::ptr::NonNull;
 
@@ -18,21 +19,22 @@ use objc2::encode::{Encode, Encoding};
 
 #[link(name = "System", kind = "dylib")]
 unsafe extern "C" {
-    static _NSConcreteMallocBlock: *const c_void;
+    // The address of this symbol is the isa of a block living on the stack
+    static _NSConcreteStackBlock: [*const c_void; 32];
+    fn _Block_copy(block: *const c_void) -> *mut c_void;
+    fn _Block_release(block: *const c_void);
 }
 
+// Block descriptor with copy and dispose helpers, see https://clang.llvm.org/docs/Block-ABI-Apple.html
 #[repr(C)]
 struct BlockDescriptor {
-    reserved: u64,
-    size: u64,
+    reserved: c_ulong,
+    size: c_ulong,
+    copy: unsafe extern "C" fn(*mut c_void, *const c_void),
+    dispose: unsafe extern "C" fn(*mut c_void),
 }
 
-const BLOCK_HAS_DESCRIPTOR: i32 = 1 << 25;
-
-static BLOCK_DESCRIPTOR: BlockDescriptor = BlockDescriptor {
-    reserved: 0,
-    size: size_of::<BlockDescriptor>() as u64,
-};
+const BLOCK_HAS_COPY_DISPOSE: i32 = 1 << 25;
 
 /// An Objective-C block. `F` is the function signature (e.g. `dyn Fn(i64)`).
 /// This type is `repr(C)` matching the ObjC block ABI, so `&Block<F>` is a thin pointer.
@@ -81,40 +83,66 @@ impl_block_call!(A: a, B: b);
 impl_block_call!(A: a, B: b, C: c);
 impl_block_call!(A: a, B: b, C: c, D: d);
 
-/// Inner heap layout for `RcBlock`: ObjC block header immediately followed by the closure.
+/// Inner layout for `RcBlock`: ObjC block header immediately followed by the closure.
 #[repr(C)]
 struct RcBlockInner<F> {
     block: Block<F>,
     closure: F,
 }
 
-/// A heap-allocated ObjC block wrapping a Rust closure.
+impl<F> RcBlockInner<F> {
+    const DESCRIPTOR: BlockDescriptor = BlockDescriptor {
+        reserved: 0,
+        size: size_of::<Self>() as c_ulong,
+        copy: Self::copy_helper,
+        dispose: Self::dispose_helper,
+    };
+
+    // `_Block_copy` already moved the closure bytes into the new block, so there is nothing left
+    // to copy; the source block is a stack block that is never dropped.
+    const unsafe extern "C" fn copy_helper(_dst: *mut c_void, _src: *const c_void) {}
+
+    // Called by `_Block_release` when the last reference to the heap block is released.
+    unsafe extern "C" fn dispose_helper(block: *mut c_void) {
+        // SAFETY: `block` is a heap block created by `_Block_copy` from a `RcBlockInner<F>`,
+        // the runtime calls dispose exactly once before freeing it.
+        unsafe { std::ptr::drop_in_place(&raw mut (*(block as *mut Self)).closure) };
+    }
+}
+
+/// A reference-counted heap ObjC block wrapping a Rust closure.
+///
+/// The block is managed by the ObjC block runtime, so ObjC code can `Block_copy` and
+/// `Block_release` it (e.g. to store a completion handler) independently of this reference.
 pub struct RcBlock<F> {
     inner: NonNull<RcBlockInner<F>>,
 }
 
-// SAFETY: Sending an `RcBlock<F>` to another thread is safe iff F is Send.
-unsafe impl<F: Send> Send for RcBlock<F> {}
-// SAFETY: Sharing `&RcBlock<F>` across threads is safe iff F is Sync.
-unsafe impl<F: Sync> Sync for RcBlock<F> {}
+// SAFETY: Clones share the closure like an `Arc<F>`, so a sent `RcBlock<F>` can call the closure
+// concurrently with other threads and drop it on the thread that releases the last reference.
+unsafe impl<F: Send + Sync> Send for RcBlock<F> {}
+// SAFETY: `&RcBlock<F>` can be cloned into an owned `RcBlock<F>`, so sharing needs the same bounds.
+unsafe impl<F: Send + Sync> Sync for RcBlock<F> {}
 
 impl<F> RcBlock<F> {
     fn make(closure: F, invoke: *const c_void) -> Self {
-        let inner = Box::new(RcBlockInner {
+        // Build the block on the stack like Clang does, the runtime moves it to the heap
+        let stack = ManuallyDrop::new(RcBlockInner {
             block: Block {
-                // SAFETY: `_NSConcreteMallocBlock` is a valid extern static exported by
-                // libSystem; it is always initialised before any Rust code runs.
-                _isa: unsafe { _NSConcreteMallocBlock },
-                _flags: BLOCK_HAS_DESCRIPTOR,
+                _isa: &raw const _NSConcreteStackBlock as *const c_void,
+                _flags: BLOCK_HAS_COPY_DISPOSE,
                 _reserved: 0,
                 _invoke: invoke,
-                _descriptor: &BLOCK_DESCRIPTOR,
+                _descriptor: &RcBlockInner::<F>::DESCRIPTOR,
                 _marker: PhantomData,
             },
             closure,
         });
+        // SAFETY: `stack` is a valid stack block whose descriptor describes its full size, the
+        // closure is moved into the heap copy so the stack block is never dropped.
+        let inner = unsafe { _Block_copy(&*stack as *const RcBlockInner<F> as *const c_void) };
         Self {
-            inner: NonNull::new(Box::into_raw(inner)).expect("Box::into_raw is non-null"),
+            inner: NonNull::new(inner as *mut RcBlockInner<F>).expect("_Block_copy failed"),
         }
     }
 
@@ -124,8 +152,8 @@ impl<F> RcBlock<F> {
         F: Fn() + 'static,
     {
         extern "C" fn invoke_impl<F: Fn()>(block: *const RcBlockInner<F>) {
-            // SAFETY: `block` is a valid non-null pointer to a live `RcBlockInner<F>`
-            // allocated by `Box::into_raw`; it stays alive for the duration of this call.
+            // SAFETY: `block` is the heap block created by `_Block_copy` in `make`, the caller
+            // holds a reference to it so it stays alive for the duration of this call.
             let closure = unsafe { &(*block).closure };
             closure();
         }
@@ -138,8 +166,8 @@ impl<F> RcBlock<F> {
         F: Fn(A) + 'static,
     {
         extern "C" fn invoke_impl<F: Fn(A), A: Copy>(block: *const RcBlockInner<F>, a: A) {
-            // SAFETY: `block` is a valid non-null pointer to a live `RcBlockInner<F>`
-            // allocated by `Box::into_raw`; it stays alive for the duration of this call.
+            // SAFETY: `block` is the heap block created by `_Block_copy` in `make`, the caller
+            // holds a reference to it so it stays alive for the duration of this call.
             let closure = unsafe { &(*block).closure };
             closure(a);
         }
@@ -155,8 +183,8 @@ impl<F> RcBlock<F> {
             block: *const RcBlockInner<F>,
             a: A,
         ) -> R {
-            // SAFETY: `block` is a valid non-null pointer to a live `RcBlockInner<F>`
-            // allocated by `Box::into_raw`; it stays alive for the duration of this call.
+            // SAFETY: `block` is the heap block created by `_Block_copy` in `make`, the caller
+            // holds a reference to it so it stays alive for the duration of this call.
             let closure = unsafe { &(*block).closure };
             closure(a)
         }
@@ -173,8 +201,8 @@ impl<F> RcBlock<F> {
             a: A,
             b: B,
         ) {
-            // SAFETY: `block` is a valid non-null pointer to a live `RcBlockInner<F>`
-            // allocated by `Box::into_raw`; it stays alive for the duration of this call.
+            // SAFETY: `block` is the heap block created by `_Block_copy` in `make`, the caller
+            // holds a reference to it so it stays alive for the duration of this call.
             let closure = unsafe { &(*block).closure };
             closure(a, b);
         }
@@ -192,8 +220,8 @@ impl<F> RcBlock<F> {
             b: B,
             c: C,
         ) {
-            // SAFETY: `block` is a valid non-null pointer to a live `RcBlockInner<F>`
-            // allocated by `Box::into_raw`; it stays alive for the duration of this call.
+            // SAFETY: `block` is the heap block created by `_Block_copy` in `make`, the caller
+            // holds a reference to it so it stays alive for the duration of this call.
             let closure = unsafe { &(*block).closure };
             closure(a, b, c);
         }
@@ -214,8 +242,8 @@ impl<F> RcBlock<F> {
             c: C,
             d: D,
         ) {
-            // SAFETY: `block` is a valid non-null pointer to a live `RcBlockInner<F>`
-            // allocated by `Box::into_raw`; it stays alive for the duration of this call.
+            // SAFETY: `block` is the heap block created by `_Block_copy` in `make`, the caller
+            // holds a reference to it so it stays alive for the duration of this call.
             let closure = unsafe { &(*block).closure };
             closure(a, b, c, d);
         }
@@ -239,17 +267,26 @@ impl<F> RcBlock<F> {
 impl<F> Deref for RcBlock<F> {
     type Target = Block<F>;
     fn deref(&self) -> &Block<F> {
-        // SAFETY: `self.inner` was produced by `NonNull::new(Box::into_raw(...))` in `make`
-        // and is valid until `Drop::drop` runs; we hold `&self` so it cannot be dropped here.
+        // SAFETY: `self.inner` is the heap block returned by `_Block_copy` in `make`, it stays
+        // valid while we hold a reference to it and we hold `&self` so it can't be released here.
         unsafe { &self.inner.as_ref().block }
     }
 }
 
+impl<F> Clone for RcBlock<F> {
+    fn clone(&self) -> Self {
+        // SAFETY: `self.inner` is a live heap block; copying a heap block only increments its
+        // reference count and returns the same pointer.
+        unsafe { _Block_copy(self.inner.as_ptr() as *const c_void) };
+        Self { inner: self.inner }
+    }
+}
+
 impl<F> Drop for RcBlock<F> {
     fn drop(&mut self) {
-        // SAFETY: `self.inner` was produced by `Box::into_raw` in `make`; reconstructing
-        // the `Box` here is the exactly paired deallocation and runs the closure destructor.
-        unsafe { drop(Box::from_raw(self.inner.as_ptr())) };
+        // SAFETY: `self.inner` is a heap block we hold a reference to; the runtime runs the
+        // dispose helper and frees the block when the last reference is released.
+        unsafe { _Block_release(self.inner.as_ptr() as *const c_void) };
     }
 }
 
@@ -399,6 +436,41 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_block_runtime_copy_outlives_rc_block() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        struct DropGuard(Arc<AtomicBool>);
+        impl Drop for DropGuard {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        static RESULT: AtomicI32 = AtomicI32::new(0);
+        let guard = DropGuard(dropped.clone());
+        let block = RcBlock::new::<i32>(move |x: i32| {
+            let _ = &guard;
+            RESULT.store(x, Ordering::SeqCst);
+        });
+
+        // Simulate ObjC code storing a completion handler with Block_copy
+        // SAFETY: `block` derefs to a valid heap block.
+        let copy = unsafe { _Block_copy(&*block as *const Block<_> as *const c_void) };
+        let clone = block.clone();
+        drop(block);
+        drop(clone);
+        assert!(!dropped.load(Ordering::SeqCst));
+
+        // SAFETY: `copy` is the same block with the same invoke signature.
+        let copy_ref = unsafe { &*(copy as *const Block<dyn Fn(i32)>) };
+        copy_ref.call((42,));
+        assert_eq!(RESULT.load(Ordering::SeqCst), 42);
+
+        // SAFETY: `copy` holds the last reference to the block.
+        unsafe { _Block_release(copy) };
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
     #[test]
     fn test_block_call_1_arg_ret() {
         let block = RcBlock::new_ret::<i32, i32>(|x: i32| x * 2);
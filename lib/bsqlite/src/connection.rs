@@ -13,6 +13,8 @@ use std::sync::Arc;
 
 use libsqlite3_sys::*;
 
+#[cfg(feature = "json")]
+use crate::Row;
 use crate::{Bind, FromRow, Statement, StatementError};
 
 // MARK: Inner Connection
@@ -130,6 +132,17 @@ impl InnerConnection {
         // SAFETY: self.0 is a valid open db handle.
         unsafe { sqlite3_last_insert_rowid(self.0) }
     }
+
+    fn interrupt(&self) {
+        // SAFETY: self.0 is a valid open db handle, sqlite3_interrupt is safe to call from any
+        // thread while statements are running on the connection.
+        unsafe { sqlite3_interrupt(self.0) };
+    }
+
+    fn is_autocommit(&self) -> bool {
+        // SAFETY: self.0 is a valid open db handle.
+        unsafe { sqlite3_get_autocommit(self.0) != 0 }
+    }
 }
 
 impl Drop for InnerConnection {
@@ -234,6 +247,19 @@ impl Connection {
             })
     }
 
+    /// Run a query and convert every row to a JSON object keyed by column name, for
+    /// arbitrary queries without a predeclared struct
+    #[cfg(feature = "json")]
+    pub fn query_json(
+        &self,
+        query: impl AsRef<str>,
+        params: impl Bind,
+    ) -> Result<Vec<serde_json::Value>, StatementError> {
+        self.query::<Row>(query.as_ref(), params)?
+            .map(|row| row.map(serde_json::Value::from))
+            .collect()
+    }
+
     /// Execute a SQL script (multiple statements separated by semicolons)
     pub fn execute_script(&self, sql: &str) -> Result<(), StatementError> {
         self.0.execute_script(sql)
@@ -256,6 +282,16 @@ impl Connection {
     pub fn last_insert_row_id(&self) -> i64 {
         self.0.last_insert_row_id()
     }
+
+    /// Interrupt the running statements of the connection, they fail with an interrupted error
+    pub fn interrupt(&self) {
+        self.0.interrupt();
+    }
+
+    /// Check if the connection is in autocommit mode, it isn't while a transaction is open
+    pub fn is_autocommit(&self) -> bool {
+        self.0.is_autocommit()
+    }
 }
 
 // MARK: Macros
@@ -382,6 +418,36 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_interrupt() {
+        let db = Connection::open_memory().unwrap();
+        let interrupt_db = db.clone();
+        let handle = std::thread::spawn(move || {
+            db.query_some::<i64>(
+                "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT MAX(x) FROM c",
+                (),
+            )
+        });
+        // Keep interrupting until the query stops, an interrupt before it starts is a no-op
+        while !handle.is_finished() {
+            interrupt_db.interrupt();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        let result = handle.join().unwrap();
+        assert!(result.err().unwrap().to_string().contains("interrupted"));
+    }
+
+    #[test]
+    fn test_is_autocommit() -> Result<(), StatementError> {
+        let db = Connection::open_memory().unwrap();
+        assert!(db.is_autocommit());
+        db.execute("BEGIN", ())?;
+        assert!(!db.is_autocommit());
+        db.execute("ROLLBACK", ())?;
+        assert!(db.is_autocommit());
+        Ok(())
+    }
+
     #[test]
     fn test_query_some_empty_error() {
         let db = Connection::open_memory().unwrap();
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -11,7 +11,9 @@ pub use crate::bind::Bind;
 pub use crate::connection::{Connection, ConnectionError, OpenMode};
 pub use crate::from_row::FromRow;
 pub use crate::migration::{Migration, MigrationError};
-pub use crate::statement::{ColumnType, RawStatement, Statement, StatementError};
+pub use crate::row::Row;
+pub use crate::schema::export_schema;
+pub use crate::statement::{ColumnType, RawStatement, Rows, Statement, StatementError};
 pub use crate::utils::preprocess_fts_query;
 pub use crate::value::{Value, ValueError};
 
@@ -19,6 +21,8 @@ mod bind;
 mod connection;
 mod from_row;
 mod migration;
+mod row;
+mod schema;
 mod statement;
 mod utils;
 mod value;
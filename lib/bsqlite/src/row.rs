@@ -0,0 +1,207 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::ops::Index;
+
+use crate::value::ValueError;
+use crate::{FromRow, RawStatement, Value};
+
+// MARK: Row
+/// A dynamic row with column-name indexed access, for queries without a predeclared struct
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    columns: Vec<String>,
+    values: Vec<Value>,
+}
+
+impl Row {
+    /// Get the column names of the row
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Get the values of the row
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    /// Get the number of columns in the row
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if the row has no columns
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get the value of a column by name
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.columns
+            .iter()
+            .position(|column| column == name)
+            .map(|index| &self.values[index])
+    }
+
+    /// Get the value of a column by index
+    pub fn get_index(&self, index: usize) -> Option<&Value> {
+        self.values.get(index)
+    }
+
+    /// Get the value of a column by name converted to `T`
+    pub fn try_get<T: TryFrom<Value, Error = ValueError>>(
+        &self,
+        name: &str,
+    ) -> Result<T, ValueError> {
+        T::try_from(
+            self.get(name)
+                .ok_or_else(|| ValueError::new(format!("unknown column: {}", name)))?
+                .clone(),
+        )
+    }
+
+    /// Iterate over the column names and values of the row
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.columns
+            .iter()
+            .map(String::as_str)
+            .zip(self.values.iter())
+    }
+}
+
+impl FromRow for Row {
+    fn from_row(statement: &mut RawStatement) -> Result<Self, ValueError> {
+        let column_count = statement.column_count();
+        Ok(Self {
+            columns: (0..column_count)
+                .map(|index| statement.column_name(index))
+                .collect(),
+            values: (0..column_count)
+                .map(|index| statement.column_value(index))
+                .collect(),
+        })
+    }
+}
+
+impl Index<usize> for Row {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.values[index]
+    }
+}
+
+impl Index<&str> for Row {
+    type Output = Value;
+
+    fn index(&self, name: &str) -> &Self::Output {
+        self.get(name)
+            .unwrap_or_else(|| panic!("unknown column: {}", name))
+    }
+}
+
+// MARK: Serde
+#[cfg(feature = "json")]
+mod serde_impls {
+    use serde::ser::{Serialize, SerializeMap, Serializer};
+
+    use super::*;
+
+    impl Serialize for Value {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                Value::Null => serializer.serialize_none(),
+                Value::Integer(v) => serializer.serialize_i64(*v),
+                Value::Float(v) => serializer.serialize_f64(*v),
+                Value::Text(v) => serializer.serialize_str(v),
+                Value::Blob(v) => serializer.serialize_bytes(v),
+            }
+        }
+    }
+
+    impl Serialize for Row {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.len()))?;
+            for (column, value) in self.iter() {
+                map.serialize_entry(column, value)?;
+            }
+            map.end()
+        }
+    }
+
+    impl From<Row> for serde_json::Value {
+        fn from(row: Row) -> Self {
+            serde_json::Value::Object(
+                row.columns
+                    .into_iter()
+                    .zip(row.values)
+                    .map(|(column, value)| (column, value.into()))
+                    .collect(),
+            )
+        }
+    }
+
+    impl From<Value> for serde_json::Value {
+        fn from(value: Value) -> Self {
+            match value {
+                Value::Null => serde_json::Value::Null,
+                Value::Integer(v) => v.into(),
+                Value::Float(v) => v.into(),
+                Value::Text(v) => v.into(),
+                Value::Blob(v) => v.into(),
+            }
+        }
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod tests {
+    use crate::Connection;
+
+    use super::*;
+
+    #[test]
+    fn test_row_column_access() {
+        let db = Connection::open_memory().unwrap();
+        let row = db
+            .query_some::<Row>("SELECT 1 AS id, 'Alice' AS name, NULL AS age", ())
+            .unwrap();
+        assert_eq!(row.len(), 3);
+        assert_eq!(row.columns(), ["id", "name", "age"]);
+        assert_eq!(row["id"], Value::Integer(1));
+        assert_eq!(row[1], Value::Text("Alice".to_string()));
+        assert_eq!(row.get("age"), Some(&Value::Null));
+        assert_eq!(row.get("unknown"), None);
+        assert_eq!(row.try_get::<String>("name").unwrap(), "Alice");
+        assert_eq!(row.try_get::<Option<i64>>("age").unwrap(), None);
+        assert!(row.try_get::<i64>("unknown").is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_row_json() {
+        let db = Connection::open_memory().unwrap();
+        let rows = db
+            .query_json(
+                "SELECT ? AS id, 'Alice' AS name, 1.5 AS score, NULL AS age, x'0102' AS data",
+                1,
+            )
+            .unwrap();
+        let expected = serde_json::json!([
+            { "id": 1, "name": "Alice", "score": 1.5, "age": null, "data": [1, 2] }
+        ]);
+        assert_eq!(serde_json::Value::Array(rows), expected);
+
+        let row = db
+            .query_some::<Row>("SELECT 1 AS id, 'Alice' AS name", ())
+            .unwrap();
+        assert_eq!(
+            serde_json::to_string(&row).unwrap(),
+            r#"{"id":1,"name":"Alice"}"#
+        );
+    }
+}
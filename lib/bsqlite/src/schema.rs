@@ -0,0 +1,49 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::env;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::{Connection, OpenMode};
+
+// MARK: Export schema
+/// Export the table columns of the database at the `DATABASE_PATH` env variable for the
+/// `#[sqlite(table = "...", check_schema)]` checks of the `FromRow` derive, call this from a
+/// build script. Does nothing when `DATABASE_PATH` isn't set.
+///
+/// ```no_run
+/// // build.rs
+/// bsqlite::export_schema();
+/// ```
+pub fn export_schema() {
+    println!("cargo:rerun-if-env-changed=DATABASE_PATH");
+    let Ok(database_path) = env::var("DATABASE_PATH") else {
+        return;
+    };
+    println!("cargo:rerun-if-changed={database_path}");
+
+    let db = Connection::open(&database_path, OpenMode::ReadOnly)
+        .unwrap_or_else(|err| panic!("Can't open database {database_path}: {err}"));
+    let columns = db
+        .query::<(String, String, String)>(
+            "SELECT m.name, p.name, p.type FROM sqlite_master m, pragma_table_info(m.name) p
+            WHERE m.type IN ('table', 'view') ORDER BY m.name, p.cid",
+            (),
+        )
+        .and_then(|columns| columns.collect::<Result<Vec<_>, _>>())
+        .unwrap_or_else(|err| panic!("Can't read schema of database {database_path}: {err}"));
+
+    let mut schema = String::new();
+    for (table, column, r#type) in columns {
+        _ = writeln!(schema, "{table}\t{column}\t{type}");
+    }
+    let path =
+        PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set")).join("bsqlite_schema.tsv");
+    std::fs::write(&path, schema)
+        .unwrap_or_else(|err| panic!("Can't write {}: {err}", path.display()));
+    println!("cargo:rustc-env=BSQLITE_SCHEMA_PATH={}", path.display());
+}
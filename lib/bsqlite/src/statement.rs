@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -154,6 +154,29 @@ impl RawStatement {
         self.bind_value(index - 1, value)
     }
 
+    /// Get the number of parameters in the statement
+    pub fn parameter_count(&self) -> i32 {
+        // SAFETY: self.0 is a valid prepared statement handle.
+        unsafe { sqlite3_bind_parameter_count(self.0) }
+    }
+
+    /// Get the name of a parameter, anonymous `?` parameters have no name
+    pub fn parameter_name(&self, index: i32) -> Option<String> {
+        // SAFETY: self.0 is a valid prepared statement handle, out of range indexes return null.
+        let name = unsafe { sqlite3_bind_parameter_name(self.0, index + 1) };
+        if !name.is_null() {
+            Some(
+                // SAFETY: name is non-null (checked above) and points to a valid NUL-terminated
+                // UTF-8 string owned by SQLite, valid for the statement lifetime.
+                unsafe { CStr::from_ptr(name) }
+                    .to_string_lossy()
+                    .to_string(),
+            )
+        } else {
+            None
+        }
+    }
+
     /// Step the statement
     pub fn step(&mut self) -> Result<Option<()>, StatementError> {
         // SAFETY: self.0 is a valid prepared statement handle.
@@ -344,6 +367,16 @@ impl<T> Statement<T> {
         self.0.bind_named_value(name, value.into())
     }
 
+    /// Get the number of parameters in the statement
+    pub fn parameter_count(&self) -> i32 {
+        self.0.parameter_count()
+    }
+
+    /// Get the name of a parameter, anonymous `?` parameters have no name
+    pub fn parameter_name(&self, index: i32) -> Option<String> {
+        self.0.parameter_name(index)
+    }
+
     /// Step the statement
     pub fn step(&mut self) -> Result<Option<()>, StatementError> {
         self.0.step()
@@ -383,19 +416,53 @@ impl<T> Statement<T> {
     pub fn column_value(&self, index: i32) -> Value {
         self.0.column_value(index)
     }
+
+    /// Lazily iterate over the rows of the statement read as `R`, only one row is read at a
+    /// time and the statement is reset when the iterator is dropped
+    pub fn query_iter<R: FromRow>(&mut self) -> Rows<'_, R> {
+        Rows {
+            statement: &mut self.0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+fn next_row<T: FromRow>(statement: &mut RawStatement) -> Option<Result<T, StatementError>> {
+    match statement.step() {
+        Ok(Some(())) => {
+            Some(T::from_row(statement).map_err(|e| StatementError { msg: e.to_string() }))
+        }
+        Ok(None) => None,
+        Err(e) => Some(Err(e)),
+    }
 }
 
 impl<T: FromRow> Iterator for Statement<T> {
     type Item = Result<T, StatementError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.step() {
-            Ok(Some(())) => {
-                Some(T::from_row(&mut self.0).map_err(|e| StatementError { msg: e.to_string() }))
-            }
-            Ok(None) => None,
-            Err(e) => Some(Err(e)),
-        }
+        next_row(&mut self.0)
+    }
+}
+
+// MARK: Rows
+/// A lazy row iterator that borrows a statement
+pub struct Rows<'a, T> {
+    statement: &'a mut RawStatement,
+    _marker: PhantomData<T>,
+}
+
+impl<T: FromRow> Iterator for Rows<'_, T> {
+    type Item = Result<T, StatementError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_row(self.statement)
+    }
+}
+
+impl<T> Drop for Rows<'_, T> {
+    fn drop(&mut self) {
+        self.statement.reset();
     }
 }
 
@@ -501,4 +568,47 @@ mod tests {
             "Statement error: Parameter ':missing' not found in statement"
         );
     }
+
+    #[test]
+    fn test_statement_query_iter() -> Result<(), StatementError> {
+        let db = Connection::open_memory().unwrap();
+        db.execute("CREATE TABLE numbers (n INTEGER NOT NULL) STRICT", ())?;
+        for n in 1..=100_i64 {
+            db.execute("INSERT INTO numbers (n) VALUES (?)", n)?;
+        }
+
+        let mut statement = db.prepare::<()>("SELECT n, n * 2 FROM numbers LIMIT ? OFFSET ?")?;
+        statement.bind((3_i64, 10_i64))?;
+        let rows = statement
+            .query_iter::<(i64, i64)>()
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![(11, 22), (12, 24), (13, 26)]);
+
+        // Dropping a partially read iterator resets the statement for the next page
+        let mut rows = statement.query_iter::<i64>();
+        assert_eq!(rows.next().transpose()?, Some(11));
+        drop(rows);
+        statement.bind((2_i64, 50_i64))?;
+        let rows = statement
+            .query_iter::<i64>()
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(rows, vec![51, 52]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_statement_parameters() -> Result<(), StatementError> {
+        let db = Connection::open_memory().unwrap();
+        let statement = db.prepare::<()>("SELECT ?, :name, @other, ?")?;
+
+        assert_eq!(statement.parameter_count(), 4);
+        assert_eq!(statement.parameter_name(0), None);
+        assert_eq!(statement.parameter_name(1).as_deref(), Some(":name"));
+        assert_eq!(statement.parameter_name(2).as_deref(), Some("@other"));
+        assert_eq!(statement.parameter_name(3), None);
+        assert_eq!(statement.parameter_name(4), None);
+
+        Ok(())
+    }
 }
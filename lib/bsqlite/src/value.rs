@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2024 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -8,6 +8,7 @@ use std::error::Error;
 use std::fmt::{self, Display, Formatter};
 
 /// A SQLite value
+#[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     /// A NULL value
     Null,
@@ -472,6 +473,7 @@ mod chrono_impls {
     }
 
     // MARK: From DateTime<Utc>
+    // Stored as whole seconds, the nanoseconds are truncated
     impl From<DateTime<Utc>> for Value {
         fn from(value: DateTime<Utc>) -> Self {
             Value::Integer(value.timestamp())
@@ -651,6 +653,13 @@ mod tests {
             Option::<DateTime<Utc>>::try_from(Value::Null).unwrap(),
             None
         );
+        assert_eq!(
+            DateTime::<Utc>::try_from(Value::from(
+                DateTime::<Utc>::from_timestamp(timestamp, 999_999_999).unwrap()
+            ))
+            .unwrap(),
+            datetime
+        );
 
         assert_eq!(
             NaiveDate::try_from(Value::Text("2024-06-15".to_string()))
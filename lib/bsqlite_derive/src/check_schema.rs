@@ -0,0 +1,178 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::collections::HashMap;
+use std::{env, fs};
+
+// MARK: Affinity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Affinity {
+    Integer,
+    Real,
+    Text,
+    Blob,
+    Numeric,
+}
+
+impl Affinity {
+    // Affinity rules from https://www.sqlite.org/datatype3.html#determination_of_column_affinity
+    fn from_declared_type(declared_type: &str) -> Option<Self> {
+        let declared_type = declared_type.to_uppercase();
+        if declared_type.is_empty() || declared_type == "ANY" {
+            None
+        } else if declared_type.contains("INT") {
+            Some(Self::Integer)
+        } else if ["CHAR", "CLOB", "TEXT"]
+            .iter()
+            .any(|part| declared_type.contains(part))
+        {
+            Some(Self::Text)
+        } else if declared_type.contains("BLOB") {
+            Some(Self::Blob)
+        } else if ["REAL", "FLOA", "DOUB"]
+            .iter()
+            .any(|part| declared_type.contains(part))
+        {
+            Some(Self::Real)
+        } else {
+            Some(Self::Numeric)
+        }
+    }
+
+    fn from_rust_type(ty: &syn::Type) -> Option<Self> {
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+        let generic = match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        };
+        match segment.ident.to_string().as_str() {
+            "Option" => Self::from_rust_type(generic?),
+            "bool" | "i8" | "i16" | "i32" | "i64" | "DateTime" | "NaiveDate" => Some(Self::Integer),
+            "f64" => Some(Self::Real),
+            "String" => Some(Self::Text),
+            "Uuid" => Some(Self::Blob),
+            "Vec" => match generic? {
+                syn::Type::Path(inner) if inner.path.is_ident("u8") => Some(Self::Blob),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn is_compatible(self, column: Self) -> bool {
+        self == column || (column == Self::Numeric && matches!(self, Self::Integer | Self::Real))
+    }
+}
+
+// MARK: Check schema
+/// Check the struct columns against the schema exported by `bsqlite::export_schema()`, the
+/// check is skipped when no schema was exported
+pub(crate) fn check_schema(
+    name: &syn::Ident,
+    table: &str,
+    fields: &[(&syn::Type, String)],
+) -> syn::Result<()> {
+    let Ok(schema_path) = env::var("BSQLITE_SCHEMA_PATH") else {
+        return Ok(());
+    };
+    let schema = fs::read_to_string(&schema_path)
+        .map_err(|err| syn::Error::new(name.span(), format!("Can't read {schema_path}: {err}")))?;
+
+    let columns = schema
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\t');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(column_table), Some(column), Some(declared_type))
+                    if column_table == table =>
+                {
+                    Some((column, declared_type))
+                }
+                _ => None,
+            }
+        })
+        .collect::<HashMap<_, _>>();
+    if columns.is_empty() {
+        return Err(syn::Error::new(
+            name.span(),
+            format!("Table `{table}` doesn't exist in the database schema"),
+        ));
+    }
+
+    let mut mismatches = Vec::new();
+    for (ty, column) in fields {
+        match columns.get(column.as_str()) {
+            None => mismatches.push(format!("column `{column}` doesn't exist")),
+            Some(declared_type) => {
+                let field_affinity = Affinity::from_rust_type(ty);
+                let column_affinity = Affinity::from_declared_type(declared_type);
+                if let (Some(field_affinity), Some(column_affinity)) =
+                    (field_affinity, column_affinity)
+                {
+                    if !field_affinity.is_compatible(column_affinity) {
+                        mismatches.push(format!(
+                            "column `{column}` has type {declared_type} but field is {field_affinity:?}"
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            name.span(),
+            format!(
+                "Struct doesn't match table `{table}`:\n- {}",
+                mismatches.join("\n- ")
+            ),
+        ))
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_affinity() {
+        assert_eq!(
+            Affinity::from_declared_type("varchar(255)"),
+            Some(Affinity::Text)
+        );
+        assert_eq!(
+            Affinity::from_declared_type("BIGINT"),
+            Some(Affinity::Integer)
+        );
+        assert_eq!(
+            Affinity::from_declared_type("DECIMAL(10,2)"),
+            Some(Affinity::Numeric)
+        );
+        assert_eq!(Affinity::from_declared_type(""), None);
+
+        let ty = |ty: &str| syn::parse_str::<syn::Type>(ty).unwrap();
+        assert_eq!(
+            Affinity::from_rust_type(&ty("Option<i64>")),
+            Some(Affinity::Integer)
+        );
+        assert_eq!(
+            Affinity::from_rust_type(&ty("Vec<u8>")),
+            Some(Affinity::Blob)
+        );
+        assert_eq!(Affinity::from_rust_type(&ty("PersonRole")), None);
+
+        assert!(Affinity::Integer.is_compatible(Affinity::Numeric));
+        assert!(!Affinity::Text.is_compatible(Affinity::Integer));
+    }
+}
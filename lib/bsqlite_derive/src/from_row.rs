@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -8,10 +8,42 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
+use crate::check_schema;
+
 pub(crate) fn from_row_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
 
+    // Parse #[sqlite(table = "example", check_schema)] struct attributes
+    let mut table = None;
+    let mut check_schema = false;
+    for attr in &input.attrs {
+        if attr.path().is_ident("sqlite") {
+            let list = attr
+                .parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Meta, syn::token::Comma>::parse_terminated,
+                )
+                .expect("Invalid attribute");
+            for meta in list {
+                match &meta {
+                    syn::Meta::Path(path) if path.is_ident("check_schema") => check_schema = true,
+                    syn::Meta::NameValue(nv) if nv.path.is_ident("table") => {
+                        if let syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(lit_str),
+                            ..
+                        }) = &nv.value
+                        {
+                            table = Some(lit_str.value());
+                        } else {
+                            panic!("Invalid #[sqlite(table)] value")
+                        }
+                    }
+                    _ => panic!("Invalid #[sqlite] struct attribute"),
+                }
+            }
+        }
+    }
+
     // Parse fields and handle #[sqlite(skip)] and #[sqlite(rename = "example")] attributes
     let (fields, has_skipped) = match input.data {
         syn::Data::Struct(data) => {
@@ -64,6 +96,18 @@ pub(crate) fn from_row_derive(input: TokenStream) -> TokenStream {
         _ => panic!("FromRow can only be used on structs"),
     };
 
+    // Check fields against the database schema
+    if check_schema {
+        let table = table.expect("#[sqlite(check_schema)] requires #[sqlite(table = \"...\")]");
+        let fields = fields
+            .iter()
+            .map(|(field, field_name)| (&field.ty, field_name.clone()))
+            .collect::<Vec<_>>();
+        if let Err(err) = check_schema::check_schema(&name, &table, &fields) {
+            return TokenStream::from(err.to_compile_error());
+        }
+    }
+
     // Generate code
     let columns = fields
         .iter()
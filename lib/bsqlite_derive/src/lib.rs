@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -8,6 +8,7 @@
 
 use proc_macro::TokenStream;
 
+mod check_schema;
 mod from_row;
 mod from_value;
 mod run_migrations;
@@ -20,6 +21,10 @@ pub fn run_migrations(input: TokenStream) -> TokenStream {
 }
 
 /// [FromRow] derive for structs
+///
+/// Fields support `#[sqlite(skip)]` and `#[sqlite(rename = "example")]`. With
+/// `#[sqlite(table = "example", check_schema)]` on the struct, the columns are checked at compile
+/// time against the database schema exported by `bsqlite::export_schema()` in a build script.
 #[proc_macro_derive(FromRow, attributes(sqlite))]
 pub fn from_row_derive(input: TokenStream) -> TokenStream {
     from_row::from_row_derive(input)
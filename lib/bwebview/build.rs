@@ -78,6 +78,15 @@ fn main() {
         } else {
             panic!("bwebview requires webkit2gtk-4.1 >= 2.40 or webkit2gtk-4.0 >= 2.20");
         }
+
+        // Notifications are posted with libnotify
+        if env::var("CARGO_FEATURE_NOTIFICATION").is_ok() {
+            pkg_config::Config::new()
+                .probe("libnotify")
+                .unwrap_or_else(|_| {
+                    panic!("bwebview requires libnotify for the notification feature")
+                });
+        }
     }
 
     // Windows requires generating bindings from the WebView2 winmd and linking with WebView2Loader
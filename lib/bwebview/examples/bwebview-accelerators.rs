@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! A bwebview keyboard accelerators example
+
+use bwebview::{Event, EventLoop, WebviewBuilder, WindowBuilder};
+
+const ACCELERATOR_COMMAND_PALETTE: u32 = 1;
+const ACCELERATOR_PLAY_PAUSE: u32 = 2;
+
+fn main() {
+    let event_loop = EventLoop::new();
+
+    let window = WindowBuilder::new()
+        .title("Webview Accelerators Example")
+        .center()
+        .build();
+    let mut webview = WebviewBuilder::new(&window)
+        .load_html("<h1>Press CmdOrCtrl+Shift+P or the play/pause media key</h1><p id=\"log\"></p>")
+        .build();
+
+    webview
+        .register_accelerator("CmdOrCtrl+Shift+P", ACCELERATOR_COMMAND_PALETTE)
+        .expect("Can't register accelerator");
+    if let Err(err) = webview.register_global_accelerator("MediaPlayPause", ACCELERATOR_PLAY_PAUSE)
+    {
+        println!("{err}, falling back to a window accelerator");
+        webview
+            .register_accelerator("MediaPlayPause", ACCELERATOR_PLAY_PAUSE)
+            .expect("Can't register accelerator");
+    }
+
+    event_loop.run(move |event| {
+        if let Event::AcceleratorPressed(id) = event {
+            let action = match id {
+                ACCELERATOR_COMMAND_PALETTE => "Command palette",
+                ACCELERATOR_PLAY_PAUSE => "Play / pause",
+                _ => return,
+            };
+            webview.evaluate_script(format!(
+                "document.getElementById('log').textContent += '{action} pressed. ';"
+            ));
+        }
+    });
+}
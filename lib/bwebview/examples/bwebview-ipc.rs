@@ -32,6 +32,7 @@ fn main() {
         .center()
         .remember_window_state()
         .theme(Theme::Dark)
+        .show_after_load(true)
         .build();
     let mut webview = WebviewBuilder::new(&window).load_html(APP_HTML).build();
 
@@ -59,11 +60,11 @@ fn main() {
         }
 
         // Webview events
-        Event::Webview(WebviewEvent::PageLoadStart) => {
-            println!("Page load started");
+        Event::Webview(WebviewEvent::PageLoadStart(url)) => {
+            println!("Page load started: {url}");
         }
-        Event::Webview(WebviewEvent::PageLoadFinish) => {
-            println!("Page load finished");
+        Event::Webview(WebviewEvent::PageLoadFinish(url)) => {
+            println!("Page load finished: {url}");
             let message = IpcMessage::Hello {
                 name: "Webview".to_string(),
             };
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -15,6 +15,7 @@ fn main() {
         .title("Window A")
         .position(LogicalPoint::new(100.0, 100.0))
         .size(LogicalSize::new(1024.0, 768.0))
+        .window_state_key("window-a")
         .build();
     let mut _webview_a = WebviewBuilder::new(&window_a)
         .load_url("https://example.com")
@@ -24,6 +25,7 @@ fn main() {
         .title("Window B")
         .position(LogicalPoint::new(100.0 + 1024.0, 100.0))
         .size(LogicalSize::new(1024.0, 768.0))
+        .window_state_key("window-b")
         .build();
     let mut _webview_b = WebviewBuilder::new(&window_b)
         .load_url("https://example.com")
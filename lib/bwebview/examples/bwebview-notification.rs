@@ -0,0 +1,51 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! A bwebview notification example
+
+use bwebview::{Event, EventLoop, Notification, WebviewBuilder, WebviewEvent, WindowBuilder};
+
+fn main() {
+    let event_loop = EventLoop::new();
+
+    let window = WindowBuilder::new().title("Notification Example").build();
+    let mut webview = WebviewBuilder::new(&window)
+        .load_html(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Notification Example</title>
+<style>
+body { font-family: sans-serif; padding: 1rem 2rem; display: flex; flex-direction: column; gap: .75rem; }
+button { padding: .5rem 1rem; font-size: 1rem; cursor: pointer; }
+</style>
+</head>
+<body>
+<h1>Notification Example</h1>
+<button onclick="ipc.postMessage('notify')">Show Notification</button>
+<div id="result"></div>
+<script>
+window.ipc.addEventListener('message', e => {
+    document.getElementById('result').textContent = e.data;
+});
+</script>
+</body>
+</html>"#,
+        )
+        .build();
+
+    event_loop.run(move |event| match event {
+        Event::Webview(WebviewEvent::MessageReceive(msg)) if msg == "notify" => {
+            let id = Notification::new("Hello Webview!", "Click this notification").show();
+            webview.send_ipc_message(format!("Notification {id} shown"));
+        }
+        Event::NotificationClicked(id) => {
+            webview.send_ipc_message(format!("Notification {id} clicked"));
+        }
+        _ => {}
+    });
+}
@@ -0,0 +1,63 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! A bwebview background task example
+
+use std::thread;
+use std::time::Duration;
+
+use bwebview::{Event, EventLoop, TaskProgress, WebviewBuilder, WebviewEvent, WindowBuilder};
+
+fn main() {
+    let event_loop = EventLoop::new();
+
+    let window = WindowBuilder::new().title("Task Example").build();
+    let mut webview = WebviewBuilder::new(&window)
+        .load_html(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Task Example</title>
+<style>
+body { font-family: sans-serif; padding: 1rem 2rem; display: flex; flex-direction: column; gap: .75rem; }
+button { padding: .5rem 1rem; font-size: 1rem; cursor: pointer; }
+</style>
+</head>
+<body>
+<h1>Task Example</h1>
+<button onclick="ipc.postMessage('start')">Start Task</button>
+<progress id="progress" max="100" value="0"></progress>
+<div id="result"></div>
+<script>
+window.ipc.addEventListener('message', e => {
+    const message = JSON.parse(e.data);
+    if (message.type === 'progress') document.getElementById('progress').value = message.percent;
+    if (message.type === 'done') document.getElementById('result').textContent = 'Task done';
+});
+</script>
+</body>
+</html>"#,
+        )
+        .build();
+
+    event_loop.run(move |event| match event {
+        Event::Webview(WebviewEvent::MessageReceive(msg)) if msg == "start" => {
+            webview.spawn_task(
+                |progress: &TaskProgress<u32>| {
+                    for percent in (0..=100).step_by(10) {
+                        progress.report(percent);
+                        thread::sleep(Duration::from_millis(200));
+                    }
+                    r#"{"type":"done"}"#.to_string()
+                },
+                |percent| format!(r#"{{"type":"progress","percent":{percent}}}"#),
+            );
+        }
+        Event::UserEvent(message) => webview.send_ipc_message(message),
+        _ => {}
+    });
+}
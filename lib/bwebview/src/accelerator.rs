@@ -0,0 +1,170 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+// MARK: AcceleratorError
+/// Accelerator error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AcceleratorError {
+    /// Accelerator string can't be parsed
+    Invalid(String),
+    /// Global accelerators are not supported on this platform
+    Unsupported,
+    /// Accelerator is already registered by another application
+    AlreadyRegistered,
+}
+
+impl Display for AcceleratorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AcceleratorError::Invalid(accelerator) => {
+                write!(f, "Invalid accelerator: {accelerator}")
+            }
+            AcceleratorError::Unsupported => {
+                write!(f, "Global accelerators are not supported on this platform")
+            }
+            AcceleratorError::AlreadyRegistered => write!(f, "Accelerator is already registered"),
+        }
+    }
+}
+
+impl Error for AcceleratorError {}
+
+// MARK: Key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Key {
+    /// Letter A-Z or digit 0-9, letters are always uppercase
+    Char(char),
+    /// Function key F1-F24
+    Function(u8),
+    Space,
+    Enter,
+    Tab,
+    Escape,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Up,
+    Down,
+    Left,
+    Right,
+    MediaPlayPause,
+    MediaNextTrack,
+    MediaPreviousTrack,
+    MediaStop,
+    VolumeUp,
+    VolumeDown,
+    VolumeMute,
+}
+
+impl FromStr for Key {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next())
+            && c.is_ascii_alphanumeric()
+        {
+            return Ok(Key::Char(c.to_ascii_uppercase()));
+        }
+        if let Some(number) = s.strip_prefix(['F', 'f'])
+            && let Ok(number) = number.parse::<u8>()
+            && (1..=24).contains(&number)
+        {
+            return Ok(Key::Function(number));
+        }
+        match s.to_ascii_lowercase().as_str() {
+            "space" => Ok(Key::Space),
+            "enter" | "return" => Ok(Key::Enter),
+            "tab" => Ok(Key::Tab),
+            "escape" | "esc" => Ok(Key::Escape),
+            "backspace" => Ok(Key::Backspace),
+            "delete" | "del" => Ok(Key::Delete),
+            "insert" => Ok(Key::Insert),
+            "home" => Ok(Key::Home),
+            "end" => Ok(Key::End),
+            "pageup" => Ok(Key::PageUp),
+            "pagedown" => Ok(Key::PageDown),
+            "up" => Ok(Key::Up),
+            "down" => Ok(Key::Down),
+            "left" => Ok(Key::Left),
+            "right" => Ok(Key::Right),
+            "mediaplaypause" => Ok(Key::MediaPlayPause),
+            "medianexttrack" => Ok(Key::MediaNextTrack),
+            "mediaprevioustrack" => Ok(Key::MediaPreviousTrack),
+            "mediastop" => Ok(Key::MediaStop),
+            "volumeup" => Ok(Key::VolumeUp),
+            "volumedown" => Ok(Key::VolumeDown),
+            "volumemute" => Ok(Key::VolumeMute),
+            _ => Err(()),
+        }
+    }
+}
+
+// MARK: Accelerator
+/// Keyboard accelerator like `CmdOrCtrl+Shift+P`
+///
+/// Modifiers are `Ctrl`, `Alt` (or `Option`), `Shift`, `Cmd` (or `Super` / `Meta`, the
+/// Windows key on other platforms) and `CmdOrCtrl` which is `Cmd` on macOS and `Ctrl` elsewhere.
+/// The key is a letter, a digit, `F1`-`F24`, a named key like `Space`, `Enter`, `Escape`,
+/// `Up` or `PageDown` or a media key like `MediaPlayPause` or `VolumeUp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Accelerator {
+    pub(crate) ctrl: bool,
+    pub(crate) alt: bool,
+    pub(crate) shift: bool,
+    pub(crate) meta: bool,
+    pub(crate) key: Key,
+}
+
+impl FromStr for Accelerator {
+    type Err = AcceleratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AcceleratorError::Invalid(s.to_string());
+        let mut parts = s.split('+').map(str::trim).collect::<Vec<_>>();
+        let key = parts
+            .pop()
+            .and_then(|key| key.parse::<Key>().ok())
+            .ok_or_else(invalid)?;
+
+        let mut accelerator = Accelerator {
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+            key,
+        };
+        for modifier in parts {
+            let flag = match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => &mut accelerator.ctrl,
+                "alt" | "option" => &mut accelerator.alt,
+                "shift" => &mut accelerator.shift,
+                "cmd" | "command" | "super" | "meta" => &mut accelerator.meta,
+                "cmdorctrl" | "commandorcontrol" => {
+                    if cfg!(target_os = "macos") {
+                        &mut accelerator.meta
+                    } else {
+                        &mut accelerator.ctrl
+                    }
+                }
+                _ => return Err(invalid()),
+            };
+            if *flag {
+                return Err(invalid());
+            }
+            *flag = true;
+        }
+        Ok(accelerator)
+    }
+}
@@ -23,10 +23,10 @@ pub enum WindowEvent {
 
 /// Webview event
 pub enum WebviewEvent {
-    /// Page load start
-    PageLoadStart,
-    /// Page load finish
-    PageLoadFinish,
+    /// Page load start, contains the page URL
+    PageLoadStart(String),
+    /// Page load finish, contains the page URL
+    PageLoadFinish(String),
     /// Page title change
     PageTitleChange(String),
     /// IPC message receive
@@ -41,4 +41,9 @@ pub enum Event {
     Webview(WebviewEvent),
     /// User event
     UserEvent(String),
+    /// Registered accelerator pressed, contains the accelerator id
+    AcceleratorPressed(u32),
+    /// Notification clicked, contains the notification id
+    #[cfg(feature = "notification")]
+    NotificationClicked(u32),
 }
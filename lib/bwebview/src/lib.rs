@@ -9,19 +9,33 @@
 #![allow(unsafe_code)]
 #![allow(clippy::undocumented_unsafe_blocks)]
 
+pub use accelerator::{Accelerator, AcceleratorError};
 pub use event::*;
 pub use event_loop::*;
 #[cfg(feature = "file_dialog")]
 pub use file_dialog::*;
+#[cfg(feature = "notification")]
+pub use notification::*;
 pub use sizes::*;
+#[cfg(feature = "task")]
+pub use task::*;
 pub use webview::*;
 pub use window::*;
+#[cfg(feature = "remember_window_state")]
+pub use window_state::*;
 
+mod accelerator;
 mod event;
 mod event_loop;
 #[cfg(feature = "file_dialog")]
 mod file_dialog;
+#[cfg(feature = "notification")]
+mod notification;
 mod platforms;
 mod sizes;
+#[cfg(feature = "task")]
+mod task;
 mod webview;
 mod window;
+#[cfg(feature = "remember_window_state")]
+mod window_state;
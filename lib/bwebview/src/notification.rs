@@ -0,0 +1,51 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::platforms::PlatformNotification;
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+pub(crate) trait NotificationInterface {
+    fn show(notification: &Notification, id: u32);
+}
+
+/// Native notification builder
+pub struct Notification {
+    pub(crate) title: String,
+    pub(crate) body: String,
+    pub(crate) icon: Option<PathBuf>,
+}
+
+impl Notification {
+    /// Create new notification
+    pub fn new(title: impl AsRef<str>, body: impl AsRef<str>) -> Self {
+        Self {
+            title: title.as_ref().to_string(),
+            body: body.as_ref().to_string(),
+            icon: None,
+        }
+    }
+
+    /// Set icon image path (Windows only supports .ico files)
+    pub fn icon(mut self, path: impl AsRef<Path>) -> Self {
+        self.icon = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Post notification to the OS notification center, returns the notification id that is
+    /// sent with [`Event::NotificationClicked`](crate::Event::NotificationClicked)
+    ///
+    /// On Windows a window must be created first, clicks are only reported for the most recent
+    /// notification.
+    pub fn show(self) -> u32 {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        PlatformNotification::show(&self, id);
+        id
+    }
+}
@@ -32,10 +32,68 @@ pub(crate) struct GdkRGBA {
     pub blue: f64,
     pub alpha: f64,
 }
+#[repr(C)]
+pub(crate) struct GdkWindow([u8; 0]);
+pub(crate) const GDK_WINDOW_STATE_FULLSCREEN: u32 = 1 << 4;
+#[repr(C)]
+pub(crate) struct GdkKeymap([u8; 0]);
+#[repr(C)]
+pub(crate) struct GdkEventKey {
+    pub r#type: i32,
+    pub window: *mut c_void,
+    pub send_event: i8,
+    pub time: u32,
+    pub state: u32,
+    pub keyval: u32,
+    pub length: i32,
+    pub string: *mut c_char,
+    pub hardware_keycode: u16,
+    pub group: u8,
+    pub is_modifier: u32,
+}
+pub(crate) const GDK_SHIFT_MASK: u32 = 1 << 0;
+pub(crate) const GDK_CONTROL_MASK: u32 = 1 << 2;
+pub(crate) const GDK_MOD1_MASK: u32 = 1 << 3;
+pub(crate) const GDK_SUPER_MASK: u32 = 1 << 26;
+pub(crate) const GDK_KEY_BACKSPACE: u32 = 0xff08;
+pub(crate) const GDK_KEY_TAB: u32 = 0xff09;
+pub(crate) const GDK_KEY_RETURN: u32 = 0xff0d;
+pub(crate) const GDK_KEY_ESCAPE: u32 = 0xff1b;
+pub(crate) const GDK_KEY_HOME: u32 = 0xff50;
+pub(crate) const GDK_KEY_LEFT: u32 = 0xff51;
+pub(crate) const GDK_KEY_UP: u32 = 0xff52;
+pub(crate) const GDK_KEY_RIGHT: u32 = 0xff53;
+pub(crate) const GDK_KEY_DOWN: u32 = 0xff54;
+pub(crate) const GDK_KEY_PAGE_UP: u32 = 0xff55;
+pub(crate) const GDK_KEY_PAGE_DOWN: u32 = 0xff56;
+pub(crate) const GDK_KEY_END: u32 = 0xff57;
+pub(crate) const GDK_KEY_INSERT: u32 = 0xff63;
+pub(crate) const GDK_KEY_F1: u32 = 0xffbe;
+pub(crate) const GDK_KEY_DELETE: u32 = 0xffff;
+pub(crate) const GDK_KEY_AUDIO_LOWER_VOLUME: u32 = 0x1008ff11;
+pub(crate) const GDK_KEY_AUDIO_MUTE: u32 = 0x1008ff12;
+pub(crate) const GDK_KEY_AUDIO_RAISE_VOLUME: u32 = 0x1008ff13;
+pub(crate) const GDK_KEY_AUDIO_PLAY: u32 = 0x1008ff14;
+pub(crate) const GDK_KEY_AUDIO_STOP: u32 = 0x1008ff15;
+pub(crate) const GDK_KEY_AUDIO_PREV: u32 = 0x1008ff16;
+pub(crate) const GDK_KEY_AUDIO_NEXT: u32 = 0x1008ff17;
 #[link(name = "gdk-3")]
 unsafe extern "C" {
     pub(crate) fn gdk_display_get_default() -> *mut GdkDisplay;
     pub(crate) fn gdk_display_get_name(display: *mut GdkDisplay) -> *const c_char;
+    pub(crate) fn gdk_keymap_get_for_display(display: *mut GdkDisplay) -> *mut GdkKeymap;
+    pub(crate) fn gdk_keymap_translate_keyboard_state(
+        keymap: *mut GdkKeymap,
+        hardware_keycode: u32,
+        state: u32,
+        group: i32,
+        keyval: *mut u32,
+        effective_group: *mut i32,
+        level: *mut i32,
+        consumed_modifiers: *mut u32,
+    ) -> bool;
+    pub(crate) fn gdk_keyval_to_upper(keyval: u32) -> u32;
+    pub(crate) fn gdk_window_get_state(window: *mut GdkWindow) -> u32;
 
     // GTK 3.22+ GdkMonitor API
     #[cfg(gtk3_22)]
@@ -7,9 +7,13 @@
 mod gdk;
 mod glib;
 mod gtk;
+#[cfg(feature = "notification")]
+mod notify;
 mod webkit;
 
 pub(crate) use self::gdk::*;
 pub(crate) use self::glib::*;
 pub(crate) use self::gtk::*;
+#[cfg(feature = "notification")]
+pub(crate) use self::notify::*;
 pub(crate) use self::webkit::*;
@@ -0,0 +1,37 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+#![allow(unused)]
+
+use std::ffi::{c_char, c_void};
+
+use super::glib::GError;
+
+// MARK: libnotify
+#[repr(C)]
+pub(crate) struct NotifyNotification([u8; 0]);
+#[link(name = "notify")]
+unsafe extern "C" {
+    pub(crate) fn notify_is_initted() -> bool;
+    pub(crate) fn notify_init(app_name: *const c_char) -> bool;
+    pub(crate) fn notify_notification_new(
+        summary: *const c_char,
+        body: *const c_char,
+        icon: *const c_char,
+    ) -> *mut NotifyNotification;
+    pub(crate) fn notify_notification_add_action(
+        notification: *mut NotifyNotification,
+        action: *const c_char,
+        label: *const c_char,
+        callback: *const c_void,
+        user_data: *mut c_void,
+        free_func: *const c_void,
+    );
+    pub(crate) fn notify_notification_show(
+        notification: *mut NotifyNotification,
+        error: *mut *mut GError,
+    ) -> bool;
+}
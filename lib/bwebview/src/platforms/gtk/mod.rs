@@ -6,6 +6,8 @@
 
 mod event_loop;
 mod file_dialog;
+#[cfg(feature = "notification")]
+mod notification;
 mod headers;
 mod webview;
 mod window;
@@ -13,5 +15,9 @@ mod window;
 pub(crate) use event_loop::{PlatformEventLoop, PlatformEventLoopProxy, PlatformMonitor};
 #[cfg(feature = "file_dialog")]
 pub(crate) use file_dialog::PlatformFileDialog;
+#[cfg(feature = "notification")]
+pub(crate) use notification::PlatformNotification;
 pub(crate) use webview::PlatformWebview;
 pub(crate) use window::PlatformWindow;
+#[cfg(feature = "remember_window_state")]
+pub(crate) use window::config_dir;
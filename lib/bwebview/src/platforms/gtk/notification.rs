@@ -0,0 +1,81 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::env;
+use std::ffi::{CString, c_char, c_void};
+use std::ptr::{null, null_mut};
+
+use super::event_loop::{APP_ID, send_event};
+use super::headers::*;
+
+pub(crate) struct PlatformNotification;
+
+impl crate::NotificationInterface for PlatformNotification {
+    fn show(notification: &crate::Notification, id: u32) {
+        unsafe {
+            if !notify_is_initted() {
+                #[allow(static_mut_refs)]
+                let app_name = match &APP_ID {
+                    Some(app_id) => app_id.application.clone(),
+                    None => env::current_exe()
+                        .ok()
+                        .and_then(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+                        .unwrap_or_else(|| "bwebview".to_string()),
+                };
+                let app_name = CString::new(app_name).expect("Can't convert to CString");
+                notify_init(app_name.as_ptr());
+            }
+
+            let title =
+                CString::new(notification.title.as_str()).expect("Can't convert to CString");
+            let body = CString::new(notification.body.as_str()).expect("Can't convert to CString");
+            let icon = notification.icon.as_ref().map(|icon| {
+                CString::new(icon.to_string_lossy().as_ref()).expect("Can't convert to CString")
+            });
+            let notify_notification = notify_notification_new(
+                title.as_ptr(),
+                body.as_ptr(),
+                icon.as_ref().map_or(null(), |icon| icon.as_ptr()),
+            );
+
+            // The default action is invoked when the notification itself is clicked
+            notify_notification_add_action(
+                notify_notification,
+                c"default".as_ptr(),
+                c"Open".as_ptr(),
+                notification_on_action as *const c_void,
+                id as usize as *mut c_void,
+                null(),
+            );
+            g_signal_connect_data(
+                notify_notification as *mut GObject,
+                c"closed".as_ptr(),
+                notification_on_closed as *const c_void,
+                null(),
+                null(),
+                G_CONNECT_DEFAULT,
+            );
+
+            let mut error = null_mut();
+            if !notify_notification_show(notify_notification, &mut error) {
+                g_error_free(error);
+                g_object_unref(notify_notification as *mut GObject);
+            }
+        }
+    }
+}
+
+extern "C" fn notification_on_action(
+    _notification: *mut NotifyNotification,
+    _action: *const c_char,
+    id: *mut c_void,
+) {
+    send_event(crate::Event::NotificationClicked(id as usize as u32));
+}
+
+extern "C" fn notification_on_closed(notification: *mut NotifyNotification, _data: *mut c_void) {
+    unsafe { g_object_unref(notification as *mut GObject) };
+}
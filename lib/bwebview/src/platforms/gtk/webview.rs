@@ -11,12 +11,17 @@ use std::ptr::{null, null_mut};
 use super::event_loop::send_event;
 use super::headers::*;
 use super::window::PlatformWindow;
-use crate::{InjectionTime, WebviewBuilder, WebviewEvent, WindowEvent};
+use crate::accelerator::Key;
+use crate::{
+    Accelerator, AcceleratorError, InjectionTime, WebviewBuilder, WebviewEvent, WindowEvent,
+};
 
 pub(super) struct WebviewData {
     pub(super) window: *mut GtkWindow,
     pub(super) background_color: Option<u32>,
+    pub(super) show_after_load: bool,
     pub(super) webview: *mut WebKitWebView,
+    pub(super) accelerators: Vec<(Accelerator, u32)>,
 }
 
 pub(crate) struct PlatformWebview(pub(super) Box<WebviewData>);
@@ -26,7 +31,9 @@ impl PlatformWebview {
         PlatformWebview(Box::new(WebviewData {
             window: window.0.window,
             background_color: window.0.background_color,
+            show_after_load: window.0.show_after_load,
             webview: null_mut(),
+            accelerators: Vec::new(),
         }))
     }
 }
@@ -168,6 +175,14 @@ impl PlatformWebview {
                 null(),
                 G_CONNECT_DEFAULT,
             );
+            g_signal_connect_data(
+                webview as *mut GObject,
+                c"load-failed".as_ptr(),
+                webview_on_load_failed as *const c_void,
+                data as *const WebviewData as *const c_void,
+                null(),
+                G_CONNECT_DEFAULT,
+            );
             g_signal_connect_data(
                 webview as *mut GObject,
                 c"notify::title".as_ptr(),
@@ -184,13 +199,25 @@ impl PlatformWebview {
                 null(),
                 G_CONNECT_DEFAULT,
             );
+            g_signal_connect_data(
+                window as *mut GObject,
+                c"key-press-event".as_ptr(),
+                window_on_key_press as *const c_void,
+                data as *const WebviewData as *const c_void,
+                null(),
+                G_CONNECT_DEFAULT,
+            );
             webview
         };
 
         data.webview = webview;
 
-        // Show window
-        unsafe { gtk_widget_show_all(data.window as *mut GtkWidget) };
+        // Show window, or only the webview when the window is shown after the first page load
+        if data.show_after_load {
+            unsafe { gtk_widget_show(webview as *mut GtkWidget) };
+        } else {
+            unsafe { gtk_widget_show_all(data.window as *mut GtkWidget) };
+        }
 
         // Send window created event
         send_event(crate::Event::Window(WindowEvent::Create));
@@ -277,18 +304,128 @@ impl crate::WebviewInterface for PlatformWebview {
             webkit_user_content_manager_add_script(user_content_manager, user_script);
         }
     }
+
+    fn register_accelerator(
+        &mut self,
+        accelerator: Accelerator,
+        id: u32,
+        global: bool,
+    ) -> Result<(), AcceleratorError> {
+        if global {
+            return Err(AcceleratorError::Unsupported);
+        }
+        self.0.accelerators.push((accelerator, id));
+        Ok(())
+    }
+
+    fn unregister_accelerator(&mut self, id: u32) {
+        self.0.accelerators.retain(|(_, other)| *other != id);
+    }
+}
+
+const fn accelerator_keyval(key: Key) -> u32 {
+    match key {
+        Key::Char(c) => c as u32,
+        Key::Function(number) => GDK_KEY_F1 + number as u32 - 1,
+        Key::Space => ' ' as u32,
+        Key::Enter => GDK_KEY_RETURN,
+        Key::Tab => GDK_KEY_TAB,
+        Key::Escape => GDK_KEY_ESCAPE,
+        Key::Backspace => GDK_KEY_BACKSPACE,
+        Key::Delete => GDK_KEY_DELETE,
+        Key::Insert => GDK_KEY_INSERT,
+        Key::Home => GDK_KEY_HOME,
+        Key::End => GDK_KEY_END,
+        Key::PageUp => GDK_KEY_PAGE_UP,
+        Key::PageDown => GDK_KEY_PAGE_DOWN,
+        Key::Up => GDK_KEY_UP,
+        Key::Down => GDK_KEY_DOWN,
+        Key::Left => GDK_KEY_LEFT,
+        Key::Right => GDK_KEY_RIGHT,
+        Key::MediaPlayPause => GDK_KEY_AUDIO_PLAY,
+        Key::MediaNextTrack => GDK_KEY_AUDIO_NEXT,
+        Key::MediaPreviousTrack => GDK_KEY_AUDIO_PREV,
+        Key::MediaStop => GDK_KEY_AUDIO_STOP,
+        Key::VolumeUp => GDK_KEY_AUDIO_RAISE_VOLUME,
+        Key::VolumeDown => GDK_KEY_AUDIO_LOWER_VOLUME,
+        Key::VolumeMute => GDK_KEY_AUDIO_MUTE,
+    }
+}
+
+extern "C" fn window_on_key_press(
+    _window: *mut GtkWindow,
+    event: *mut GdkEventKey,
+    _self: &mut WebviewData,
+) -> bool {
+    let event = unsafe { &*event };
+
+    // Translate the hardware key without modifiers so Shift+1 still matches the 1 key
+    let mut keyval = event.keyval;
+    unsafe {
+        gdk_keymap_translate_keyboard_state(
+            gdk_keymap_get_for_display(gdk_display_get_default()),
+            event.hardware_keycode as u32,
+            0,
+            event.group as i32,
+            &mut keyval,
+            null_mut(),
+            null_mut(),
+            null_mut(),
+        );
+    }
+    let keyval = unsafe { gdk_keyval_to_upper(keyval) };
+
+    for (accelerator, id) in &_self.accelerators {
+        if accelerator_keyval(accelerator.key) == keyval
+            && accelerator.ctrl == (event.state & GDK_CONTROL_MASK != 0)
+            && accelerator.alt == (event.state & GDK_MOD1_MASK != 0)
+            && accelerator.shift == (event.state & GDK_SHIFT_MASK != 0)
+            && accelerator.meta == (event.state & GDK_SUPER_MASK != 0)
+        {
+            send_event(crate::Event::AcceleratorPressed(*id));
+            return true;
+        }
+    }
+    false
 }
 
 extern "C" fn webview_on_load_changed(
-    _webview: *mut WebKitWebView,
+    webview: *mut WebKitWebView,
     event: i32,
     _self: &mut WebviewData,
 ) {
+    let url = unsafe {
+        let url = webkit_web_view_get_uri(webview);
+        if !url.is_null() {
+            CStr::from_ptr(url).to_string_lossy().into_owned()
+        } else {
+            String::new()
+        }
+    };
     if event == WEBKIT_LOAD_STARTED {
-        send_event(crate::Event::Webview(WebviewEvent::PageLoadStart))
+        send_event(crate::Event::Webview(WebviewEvent::PageLoadStart(url)))
+    } else if event == WEBKIT_LOAD_FINISHED {
+        show_window_after_load(_self);
+        send_event(crate::Event::Webview(WebviewEvent::PageLoadFinish(url)))
     }
-    if event == WEBKIT_LOAD_FINISHED {
-        send_event(crate::Event::Webview(WebviewEvent::PageLoadFinish))
+}
+
+extern "C" fn webview_on_load_failed(
+    _webview: *mut WebKitWebView,
+    _event: i32,
+    _failing_uri: *const c_char,
+    _error: *mut c_void,
+    _self: &mut WebviewData,
+) -> bool {
+    // Show the window with the error page, so a failed first load doesn't keep it hidden
+    show_window_after_load(_self);
+    false
+}
+
+fn show_window_after_load(data: &mut WebviewData) {
+    if data.show_after_load {
+        data.show_after_load = false;
+        unsafe { gtk_widget_show_all(data.window as *mut GtkWidget) };
     }
 }
 
@@ -407,7 +544,7 @@ fn webkit_uri_scheme_request_to_http_request(
         let req = unsafe { &mut *(user_data as *mut small_http::Request) };
         let key = unsafe { CStr::from_ptr(key) }.to_string_lossy();
         let value = unsafe { CStr::from_ptr(value) }.to_string_lossy();
-        req.headers.insert(key.to_string(), value.to_string());
+        req.headers.append(key.to_string(), value.to_string());
     }
     unsafe {
         soup_message_headers_foreach(headers, headers_foreach, &mut req as *mut _ as *mut c_void)
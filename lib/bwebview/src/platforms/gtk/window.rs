@@ -7,21 +7,22 @@
 #[cfg(feature = "remember_window_state")]
 use std::env;
 use std::ffi::{CStr, CString, c_void};
-#[cfg(feature = "remember_window_state")]
-use std::fs;
 use std::ptr::{null, null_mut};
 
 #[cfg(feature = "remember_window_state")]
-use super::event_loop::APP_ID;
+use super::event_loop::{APP_ID, PlatformEventLoop};
 use super::event_loop::{primary_monitor_rect, send_event};
 use super::headers::*;
+#[cfg(feature = "remember_window_state")]
+use crate::{EventLoopInterface, RememberWindowState, WindowState};
 use crate::{LogicalPoint, LogicalSize, Theme, WindowBuilder, WindowEvent};
 
 pub(super) struct WindowData {
     pub(super) window: *mut GtkWindow,
     pub(super) background_color: Option<u32>,
+    pub(super) show_after_load: bool,
     #[cfg(feature = "remember_window_state")]
-    pub(super) remember_window_state: bool,
+    pub(super) remember_window_state: Option<RememberWindowState>,
 }
 
 pub(crate) struct PlatformWindow(pub(super) Box<WindowData>);
@@ -50,8 +51,9 @@ impl PlatformWindow {
         let mut window_data = Box::new(WindowData {
             window: null_mut(),
             background_color: builder.background_color,
+            show_after_load: builder.show_after_load,
             #[cfg(feature = "remember_window_state")]
-            remember_window_state: builder.remember_window_state,
+            remember_window_state: RememberWindowState::from_builder(builder),
         });
 
         // Create window
@@ -114,8 +116,8 @@ impl PlatformWindow {
                 }
             }
             #[cfg(feature = "remember_window_state")]
-            if builder.remember_window_state {
-                Self::load_window_state(window);
+            if let Some(remember_window_state) = &window_data.remember_window_state {
+                Self::load_window_state(window, remember_window_state);
             }
 
             g_signal_connect_data(
@@ -160,64 +162,59 @@ impl PlatformWindow {
     }
 
     #[cfg(feature = "remember_window_state")]
-    fn load_window_state(window: *mut GtkWindow) {
-        unsafe {
-            let settings = g_key_file_new();
-            let file = CString::new(config_dir().join("settings.ini").display().to_string())
-                .expect("Can't convert to CString");
-            let mut err = null_mut();
-            g_key_file_load_from_file(settings, file.as_ptr(), 0, &mut err);
-            if err.is_null() {
-                let group = c"window".as_ptr();
-                let x = g_key_file_get_integer(settings, group, c"x".as_ptr(), null_mut());
-                let y = g_key_file_get_integer(settings, group, c"y".as_ptr(), null_mut());
-                gtk_window_move(window, x, y);
-
-                let width = g_key_file_get_integer(settings, group, c"width".as_ptr(), null_mut());
-                let height =
-                    g_key_file_get_integer(settings, group, c"height".as_ptr(), null_mut());
-                gtk_window_set_default_size(window, width, height);
+    fn load_window_state(window: *mut GtkWindow, remember_window_state: &RememberWindowState) {
+        let Some(state) = remember_window_state.load() else {
+            return;
+        };
 
-                let maximized =
-                    g_key_file_get_boolean(settings, group, c"maximized".as_ptr(), null_mut());
-                if maximized {
-                    gtk_window_maximize(window);
-                }
-            } else {
-                g_error_free(err);
+        // Don't restore a position that is no longer on any connected monitor
+        let monitors = PlatformEventLoop
+            .available_monitors()
+            .iter()
+            .map(|monitor| {
+                let rect = monitor.rect();
+                (
+                    LogicalPoint::new(rect.x as f32, rect.y as f32),
+                    LogicalSize::new(rect.width as f32, rect.height as f32),
+                )
+            })
+            .collect::<Vec<_>>();
+        unsafe {
+            if state.is_visible_on(&monitors) {
+                gtk_window_move(window, state.position.x as i32, state.position.y as i32);
+            }
+            gtk_window_set_default_size(window, state.size.width as i32, state.size.height as i32);
+            if state.maximized {
+                gtk_window_maximize(window);
+            }
+            if state.fullscreen {
+                gtk_window_fullscreen(window);
             }
-            g_key_file_free(settings);
         }
     }
 
     #[cfg(feature = "remember_window_state")]
-    fn save_window_state(window: *mut GtkWindow) {
-        fs::create_dir_all(config_dir()).expect("Can't create settings directory");
-        let settings_path = config_dir().join("settings.ini");
-        unsafe {
-            let settings = g_key_file_new();
-            let group = c"window".as_ptr();
-
-            let mut x = 0;
-            let mut y = 0;
+    fn save_window_state(window: *mut GtkWindow, remember_window_state: &RememberWindowState) {
+        let mut x = 0;
+        let mut y = 0;
+        let mut width = 0;
+        let mut height = 0;
+        let (maximized, fullscreen) = unsafe {
             gtk_window_get_position(window, &mut x, &mut y);
-            g_key_file_set_integer(settings, group, c"x".as_ptr(), x);
-            g_key_file_set_integer(settings, group, c"y".as_ptr(), y);
-
-            let mut width = 0;
-            let mut height = 0;
             gtk_window_get_size(window, &mut width, &mut height);
-            g_key_file_set_integer(settings, group, c"width".as_ptr(), width);
-            g_key_file_set_integer(settings, group, c"height".as_ptr(), height);
-
-            let maximized = gtk_window_is_maximized(window);
-            g_key_file_set_boolean(settings, group, c"maximized".as_ptr(), maximized);
-
-            let file = CString::new(settings_path.display().to_string())
-                .expect("Can't convert to CString");
-            g_key_file_save_to_file(settings, file.as_ptr(), null_mut());
-            g_key_file_free(settings);
-        }
+            let gdk_window = gtk_widget_get_window(window as *mut GtkWidget);
+            (
+                gtk_window_is_maximized(window),
+                !gdk_window.is_null()
+                    && gdk_window_get_state(gdk_window) & GDK_WINDOW_STATE_FULLSCREEN != 0,
+            )
+        };
+        remember_window_state.save_keeping_normal_bounds(WindowState {
+            position: LogicalPoint::new(x as f32, y as f32),
+            size: LogicalSize::new(width as f32, height as f32),
+            maximized,
+            fullscreen,
+        });
     }
 }
 
@@ -335,8 +332,8 @@ extern "C" fn window_on_close(
 ) -> bool {
     // Save window state
     #[cfg(feature = "remember_window_state")]
-    if _self.remember_window_state {
-        PlatformWindow::save_window_state(_self.window);
+    if let Some(remember_window_state) = &_self.remember_window_state {
+        PlatformWindow::save_window_state(_self.window, remember_window_state);
     }
 
     // Send window closed event
@@ -345,7 +342,7 @@ extern "C" fn window_on_close(
 }
 
 #[cfg(feature = "remember_window_state")]
-pub(super) fn config_dir() -> std::path::PathBuf {
+pub(crate) fn config_dir() -> std::path::PathBuf {
     let project_dirs = unsafe {
         if let Some(ref app_id) = APP_ID {
             directories::ProjectDirs::from(
@@ -82,16 +82,43 @@ pub(crate) const NS_WINDOW_STYLE_MASK_TITLED: u64 = 1 << 0;
 pub(crate) const NS_WINDOW_STYLE_MASK_CLOSABLE: u64 = 1 << 1;
 pub(crate) const NS_WINDOW_STYLE_MASK_MINIATURIZABLE: u64 = 1 << 2;
 pub(crate) const NS_WINDOW_STYLE_MASK_RESIZABLE: u64 = 1 << 3;
+pub(crate) const NS_WINDOW_STYLE_MASK_FULL_SCREEN: u64 = 1 << 14;
 
 pub(crate) const NS_BACKING_STORE_BUFFERED: u64 = 2;
 
 pub(crate) const NS_WINDOW_TITLE_VISIBILITY_HIDDEN: i64 = 1;
 
+pub(crate) const NS_EVENT_MODIFIER_FLAG_SHIFT: u64 = 1 << 17;
+pub(crate) const NS_EVENT_MODIFIER_FLAG_CONTROL: u64 = 1 << 18;
 pub(crate) const NS_EVENT_MODIFIER_FLAG_OPTION: u64 = 1 << 19;
 pub(crate) const NS_EVENT_MODIFIER_FLAG_COMMAND: u64 = 1 << 20;
 
+pub(crate) const NS_EVENT_TYPE_KEY_DOWN: u64 = 10;
+pub(crate) const NS_EVENT_TYPE_SYSTEM_DEFINED: u64 = 14;
+pub(crate) const NS_EVENT_MASK_KEY_DOWN: u64 = 1 << NS_EVENT_TYPE_KEY_DOWN;
+pub(crate) const NS_EVENT_MASK_SYSTEM_DEFINED: u64 = 1 << NS_EVENT_TYPE_SYSTEM_DEFINED;
+pub(crate) const NX_SUBTYPE_AUX_CONTROL_BUTTONS: i16 = 8;
+
+pub(crate) const NX_KEYTYPE_SOUND_UP: isize = 0;
+pub(crate) const NX_KEYTYPE_SOUND_DOWN: isize = 1;
+pub(crate) const NX_KEYTYPE_MUTE: isize = 7;
+pub(crate) const NX_KEYTYPE_PLAY: isize = 16;
+pub(crate) const NX_KEYTYPE_NEXT: isize = 17;
+pub(crate) const NX_KEYTYPE_PREVIOUS: isize = 18;
+pub(crate) const NX_KEYSTATE_DOWN: isize = 0x0A;
+
 pub(crate) const NS_KEY_VALUE_OBSERVING_OPTION_NEW: u64 = 0x1;
 
+#[cfg(feature = "notification")]
+#[link(name = "UserNotifications", kind = "framework")]
+unsafe extern "C" {}
+
+pub(crate) const UN_AUTHORIZATION_OPTION_SOUND: u64 = 1 << 1;
+pub(crate) const UN_AUTHORIZATION_OPTION_ALERT: u64 = 1 << 2;
+pub(crate) const UN_NOTIFICATION_PRESENTATION_OPTION_SOUND: u64 = 1 << 1;
+pub(crate) const UN_NOTIFICATION_PRESENTATION_OPTION_LIST: u64 = 1 << 3;
+pub(crate) const UN_NOTIFICATION_PRESENTATION_OPTION_BANNER: u64 = 1 << 4;
+
 pub(crate) const NS_MODAL_RESPONSE_OK: i64 = 1;
 
 #[repr(transparent)]
@@ -52,7 +52,10 @@ impl AppDelegate {
             let windows_count: usize = msg_send![windows, count];
             for i in 0..windows_count {
                 let window: *mut Object = msg_send![windows, objectAtIndex:i];
-                let _: () = msg_send![window, makeKeyAndOrderFront:null::<Object>()];
+                let alpha: f64 = msg_send![window, alphaValue];
+                if alpha > 0.0 {
+                    let _: () = msg_send![window, makeKeyAndOrderFront:null::<Object>()];
+                }
                 send_event(Event::Window(WindowEvent::Create));
             }
         }
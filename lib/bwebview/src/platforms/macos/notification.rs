@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::ptr::{null, null_mut};
+use std::{env, fs};
+
+use block2::{Block, RcBlock};
+use objc2::runtime::{AnyObject as Object, Bool};
+use objc2::{class, define_class, msg_send};
+
+use super::cocoa::*;
+use super::event_loop::send_event;
+
+// MARK: NotificationDelegate
+define_class!(
+    #[unsafe(super(NSObject))]
+    struct NotificationDelegate;
+
+    impl NotificationDelegate {
+        #[unsafe(method(userNotificationCenter:willPresentNotification:withCompletionHandler:))]
+        fn _will_present_notification(&self, _: *mut Object, _: *mut Object, completion_handler: &Block<dyn Fn(u64)>) {
+            self.will_present_notification(completion_handler);
+        }
+
+        #[unsafe(method(userNotificationCenter:didReceiveNotificationResponse:withCompletionHandler:))]
+        fn _did_receive_notification_response(&self, _: *mut Object, response: *mut Object, completion_handler: &Block<dyn Fn()>) {
+            self.did_receive_notification_response(response, completion_handler);
+        }
+    }
+);
+
+impl NotificationDelegate {
+    fn will_present_notification(&self, completion_handler: &Block<dyn Fn(u64)>) {
+        // Also show notifications while the app is in the foreground
+        completion_handler.call((UN_NOTIFICATION_PRESENTATION_OPTION_BANNER
+            | UN_NOTIFICATION_PRESENTATION_OPTION_LIST
+            | UN_NOTIFICATION_PRESENTATION_OPTION_SOUND,));
+    }
+
+    fn did_receive_notification_response(
+        &self,
+        response: *mut Object,
+        completion_handler: &Block<dyn Fn()>,
+    ) {
+        let identifier: NSString = unsafe {
+            let notification: *mut Object = msg_send![response, notification];
+            let request: *mut Object = msg_send![notification, request];
+            msg_send![request, identifier]
+        };
+        if let Ok(id) = identifier.to_string().parse() {
+            send_event(crate::Event::NotificationClicked(id));
+        }
+        completion_handler.call(());
+    }
+}
+
+// MARK: PlatformNotification
+static mut NOTIFICATION_DELEGATE: *mut Object = null_mut();
+
+pub(crate) struct PlatformNotification;
+
+impl crate::NotificationInterface for PlatformNotification {
+    fn show(notification: &crate::Notification, id: u32) {
+        unsafe {
+            let center: *mut Object =
+                msg_send![class!(UNUserNotificationCenter), currentNotificationCenter];
+            if NOTIFICATION_DELEGATE.is_null() {
+                NOTIFICATION_DELEGATE = msg_send![NotificationDelegate::class(), new];
+                let _: () = msg_send![center, setDelegate:NOTIFICATION_DELEGATE];
+                let block = RcBlock::new(|_granted: Bool, _error: *mut Object| {});
+                let _: () = msg_send![center,
+                    requestAuthorizationWithOptions:UN_AUTHORIZATION_OPTION_ALERT | UN_AUTHORIZATION_OPTION_SOUND,
+                    completionHandler:&*block];
+            }
+
+            let content: *mut Object = msg_send![class!(UNMutableNotificationContent), new];
+            let content: *mut Object = msg_send![content, autorelease];
+            let _: () = msg_send![content, setTitle:NSString::from_str(&notification.title)];
+            let _: () = msg_send![content, setBody:NSString::from_str(&notification.body)];
+            if let Some(icon) = &notification.icon {
+                // Attachments are moved into the notification store, so attach a copy
+                let extension = icon.extension().unwrap_or_default().to_string_lossy();
+                let path = env::temp_dir().join(format!("bwebview-notification-{id}.{extension}"));
+                if fs::copy(icon, &path).is_ok() {
+                    let url: *mut Object = msg_send![class!(NSURL),
+                        fileURLWithPath:NSString::from_str(path.to_string_lossy())];
+                    let attachment: *mut Object = msg_send![class!(UNNotificationAttachment),
+                        attachmentWithIdentifier:ns_string!("icon"),
+                        URL:url,
+                        options:null::<Object>(),
+                        error:null_mut::<*mut Object>()];
+                    if !attachment.is_null() {
+                        let attachments: *mut Object =
+                            msg_send![class!(NSArray), arrayWithObject:attachment];
+                        let _: () = msg_send![content, setAttachments:attachments];
+                    }
+                }
+            }
+
+            let request: *mut Object = msg_send![class!(UNNotificationRequest),
+                requestWithIdentifier:NSString::from_str(id.to_string()),
+                content:content,
+                trigger:null::<Object>()];
+            let _: () = msg_send![center, addNotificationRequest:request, withCompletionHandler:null::<Object>()];
+        }
+    }
+}
@@ -8,7 +8,7 @@ use std::cell::Cell;
 use std::ffi::c_void;
 use std::ptr::{null, null_mut};
 
-use block2::Block;
+use block2::{Block, RcBlock};
 use objc2::runtime::{AnyObject as Object, Bool};
 use objc2::{class, define_class, msg_send};
 
@@ -16,7 +16,8 @@ use super::cocoa::*;
 use super::event_loop::send_event;
 use super::webkit::*;
 use super::window::PlatformWindow;
-use crate::{InjectionTime, WebviewBuilder, WebviewEvent};
+use crate::accelerator::Key;
+use crate::{Accelerator, AcceleratorError, InjectionTime, WebviewBuilder, WebviewEvent};
 
 // MARK: WebviewDelegate
 define_class!(
@@ -25,10 +26,10 @@ define_class!(
 
     impl WebviewDelegate {
         #[unsafe(method(webView:didStartProvisionalNavigation:))]
-        fn _did_start_provisional_navigation(&self, _: *mut Object, _: *mut Object) { self.did_start_provisional_navigation(); }
+        fn _did_start_provisional_navigation(&self, webview: *mut Object, _: *mut Object) { self.did_start_provisional_navigation(webview); }
 
         #[unsafe(method(webView:didFinishNavigation:))]
-        fn _did_finish_navigation(&self, _: *mut Object, _: *mut Object) { self.did_finish_navigation(); }
+        fn _did_finish_navigation(&self, webview: *mut Object, _: *mut Object) { self.did_finish_navigation(webview); }
 
         #[unsafe(method(observeValueForKeyPath:ofObject:change:context:))]
         fn _observe_value(&self, key_path: NSString, _: *mut Object, change: *mut Object, _: *mut c_void) {
@@ -48,12 +49,25 @@ define_class!(
 );
 
 impl WebviewDelegate {
-    fn did_start_provisional_navigation(&self) {
-        send_event(crate::Event::Webview(WebviewEvent::PageLoadStart));
+    fn did_start_provisional_navigation(&self, webview: *mut Object) {
+        send_event(crate::Event::Webview(WebviewEvent::PageLoadStart(
+            webview_url(webview).unwrap_or_default(),
+        )));
     }
 
-    fn did_finish_navigation(&self) {
-        send_event(crate::Event::Webview(WebviewEvent::PageLoadFinish));
+    fn did_finish_navigation(&self, webview: *mut Object) {
+        // Show window that waited for the first page load
+        unsafe {
+            let window: *mut Object = msg_send![webview, window];
+            let alpha: f64 = msg_send![window, alphaValue];
+            if alpha == 0.0 {
+                let _: () = msg_send![window, setAlphaValue:1.0f64];
+                let _: () = msg_send![window, makeKeyAndOrderFront:null::<Object>()];
+            }
+        }
+        send_event(crate::Event::Webview(WebviewEvent::PageLoadFinish(
+            webview_url(webview).unwrap_or_default(),
+        )));
     }
 
     fn observe_value(&self, key_path: NSString, change: *mut Object) {
@@ -109,6 +123,9 @@ pub(super) struct WebviewData {
     pub(super) window: *mut Object,
     pub(super) background_color: Option<u32>,
     pub(super) webview: *mut Object,
+    pub(super) accelerators: Vec<(Accelerator, u32, bool)>,
+    pub(super) local_monitor: *mut Object,
+    pub(super) global_monitor: *mut Object,
 }
 
 pub(crate) struct PlatformWebview(pub(super) Box<WebviewData>);
@@ -119,6 +136,9 @@ impl PlatformWebview {
             window: window.0.window,
             background_color: window.0.background_color,
             webview: null_mut(),
+            accelerators: Vec::new(),
+            local_monitor: null_mut(),
+            global_monitor: null_mut(),
         }))
     }
 }
@@ -225,17 +245,21 @@ impl PlatformWebview {
     }
 }
 
+fn webview_url(webview: *mut Object) -> Option<String> {
+    unsafe {
+        let url: *mut Object = msg_send![webview, URL];
+        if !url.is_null() {
+            let url: NSString = msg_send![url, absoluteString];
+            Some(url.to_string())
+        } else {
+            None
+        }
+    }
+}
+
 impl crate::WebviewInterface for PlatformWebview {
     fn url(&self) -> Option<String> {
-        unsafe {
-            let url: *mut Object = msg_send![self.0.webview, URL];
-            if !url.is_null() {
-                let url: NSString = msg_send![url, absoluteString];
-                Some(url.to_string())
-            } else {
-                None
-            }
-        }
+        webview_url(self.0.webview)
     }
 
     fn load_url(&mut self, url: impl AsRef<str>) {
@@ -287,6 +311,139 @@ impl crate::WebviewInterface for PlatformWebview {
             }
         }
     }
+
+    fn register_accelerator(
+        &mut self,
+        accelerator: Accelerator,
+        id: u32,
+        global: bool,
+    ) -> Result<(), AcceleratorError> {
+        let data = &*self.0 as *const WebviewData;
+        unsafe {
+            // Local monitor sees key presses for our own windows and can swallow them
+            if self.0.local_monitor.is_null() {
+                let block = RcBlock::new(move |event: *mut Object| -> *mut Object {
+                    match event_accelerator_id(&(*data).accelerators, event, false) {
+                        Some(id) => {
+                            send_event(crate::Event::AcceleratorPressed(id));
+                            null_mut()
+                        }
+                        None => event,
+                    }
+                });
+                let monitor: *mut Object = msg_send![class!(NSEvent),
+                    addLocalMonitorForEventsMatchingMask:NS_EVENT_MASK_KEY_DOWN | NS_EVENT_MASK_SYSTEM_DEFINED,
+                    handler:&*block];
+                self.0.local_monitor = msg_send![monitor, retain];
+            }
+
+            // Global monitor sees key presses for other applications, this needs accessibility permissions
+            if global && self.0.global_monitor.is_null() {
+                let block = RcBlock::new(move |event: *mut Object| {
+                    if let Some(id) = event_accelerator_id(&(*data).accelerators, event, true) {
+                        send_event(crate::Event::AcceleratorPressed(id));
+                    }
+                });
+                let monitor: *mut Object = msg_send![class!(NSEvent),
+                    addGlobalMonitorForEventsMatchingMask:NS_EVENT_MASK_KEY_DOWN | NS_EVENT_MASK_SYSTEM_DEFINED,
+                    handler:&*block];
+                self.0.global_monitor = msg_send![monitor, retain];
+            }
+        }
+        self.0.accelerators.push((accelerator, id, global));
+        Ok(())
+    }
+
+    fn unregister_accelerator(&mut self, id: u32) {
+        self.0.accelerators.retain(|(_, other, _)| *other != id);
+    }
+}
+
+// MARK: Accelerators
+/// Letters and digits by ANSI virtual key code, the gaps are other keys
+const ANSI_KEY_CODES: &[u8] = b"ASDFHGZXCV_BQWERYT123465_97_80_OU_IP_LJ_K____NM";
+
+/// Virtual key codes for F1-F20
+const FUNCTION_KEY_CODES: [u16; 20] = [
+    0x7A, 0x78, 0x63, 0x76, 0x60, 0x61, 0x62, 0x64, 0x65, 0x6D, 0x67, 0x6F, 0x69, 0x6B, 0x71, 0x6A,
+    0x40, 0x4F, 0x50, 0x5A,
+];
+
+fn accelerator_key_code(key: Key) -> Option<u16> {
+    match key {
+        Key::Char(c) => ANSI_KEY_CODES
+            .iter()
+            .position(|&other| other == c as u8)
+            .map(|index| index as u16),
+        Key::Function(number) => FUNCTION_KEY_CODES.get(number as usize - 1).copied(),
+        Key::Space => Some(0x31),
+        Key::Enter => Some(0x24),
+        Key::Tab => Some(0x30),
+        Key::Escape => Some(0x35),
+        Key::Backspace => Some(0x33),
+        Key::Delete => Some(0x75),
+        Key::Insert => Some(0x72),
+        Key::Home => Some(0x73),
+        Key::End => Some(0x77),
+        Key::PageUp => Some(0x74),
+        Key::PageDown => Some(0x79),
+        Key::Left => Some(0x7B),
+        Key::Right => Some(0x7C),
+        Key::Down => Some(0x7D),
+        Key::Up => Some(0x7E),
+        _ => None,
+    }
+}
+
+const fn accelerator_media_key(key: Key) -> Option<isize> {
+    match key {
+        Key::MediaPlayPause => Some(NX_KEYTYPE_PLAY),
+        Key::MediaNextTrack => Some(NX_KEYTYPE_NEXT),
+        Key::MediaPreviousTrack => Some(NX_KEYTYPE_PREVIOUS),
+        Key::VolumeUp => Some(NX_KEYTYPE_SOUND_UP),
+        Key::VolumeDown => Some(NX_KEYTYPE_SOUND_DOWN),
+        Key::VolumeMute => Some(NX_KEYTYPE_MUTE),
+        _ => None,
+    }
+}
+
+/// Find the id of the accelerator that matches a key down or media key event
+fn event_accelerator_id(
+    accelerators: &[(Accelerator, u32, bool)],
+    event: *mut Object,
+    global_only: bool,
+) -> Option<u32> {
+    let event_type: u64 = unsafe { msg_send![event, r#type] };
+    let flags: u64 = unsafe { msg_send![event, modifierFlags] };
+    let (key_code, media_key) = if event_type == NS_EVENT_TYPE_KEY_DOWN {
+        let key_code: u16 = unsafe { msg_send![event, keyCode] };
+        (Some(key_code), None)
+    } else if event_type == NS_EVENT_TYPE_SYSTEM_DEFINED {
+        let subtype: i16 = unsafe { msg_send![event, subtype] };
+        let data1: isize = unsafe { msg_send![event, data1] };
+        if subtype != NX_SUBTYPE_AUX_CONTROL_BUTTONS || (data1 >> 8) & 0xFF != NX_KEYSTATE_DOWN {
+            return None;
+        }
+        (None, Some((data1 >> 16) & 0xFFFF))
+    } else {
+        return None;
+    };
+
+    accelerators
+        .iter()
+        .find(|(accelerator, _, global)| {
+            let key_matches = match key_code {
+                Some(key_code) => accelerator_key_code(accelerator.key) == Some(key_code),
+                None => accelerator_media_key(accelerator.key) == media_key,
+            };
+            (!global_only || *global)
+                && key_matches
+                && accelerator.ctrl == (flags & NS_EVENT_MODIFIER_FLAG_CONTROL != 0)
+                && accelerator.alt == (flags & NS_EVENT_MODIFIER_FLAG_OPTION != 0)
+                && accelerator.shift == (flags & NS_EVENT_MODIFIER_FLAG_SHIFT != 0)
+                && accelerator.meta == (flags & NS_EVENT_MODIFIER_FLAG_COMMAND != 0)
+        })
+        .map(|(_, id, _)| *id)
 }
 
 #[cfg(feature = "custom_protocol")]
@@ -4,18 +4,29 @@
  * SPDX-License-Identifier: MIT
  */
 
-use std::ptr::null_mut;
+use std::cell::Cell;
+#[cfg(feature = "remember_window_state")]
+use std::env;
+use std::ptr::{null, null_mut};
 
 use objc2::runtime::{AnyObject as Object, Bool};
-use objc2::{class, define_class, msg_send};
+use objc2::{class, define_class, msg_send, sel};
 
 use super::cocoa::*;
 use super::event_loop::send_event;
 use crate::{LogicalPoint, LogicalSize, MacosTitlebarStyle, Theme, WindowBuilder, WindowEvent};
+#[cfg(feature = "remember_window_state")]
+use crate::{RememberWindowState, WindowState};
 
 // MARK: WindowDelegate
+struct WindowDelegateIvars {
+    #[cfg(feature = "remember_window_state")]
+    remember_window_state: Cell<*mut RememberWindowState>,
+}
+
 define_class!(
     #[unsafe(super(NSObject))]
+    #[ivars = WindowDelegateIvars]
     struct WindowDelegate;
 
     impl WindowDelegate {
@@ -26,7 +37,7 @@ define_class!(
         fn _window_did_resize(&self, notification: *mut Object) { self.window_did_resize(notification); }
 
         #[unsafe(method(windowWillClose:))]
-        fn _window_will_close(&self, _: *mut Object) { self.window_will_close(); }
+        fn _window_will_close(&self, notification: *mut Object) { self.window_will_close(notification); }
 
         #[unsafe(method(windowWillEnterFullScreen:))]
         fn _window_will_enter_fullscreen(&self, _: *mut Object) { self.window_will_enter_fullscreen(); }
@@ -56,7 +67,15 @@ impl WindowDelegate {
         ))));
     }
 
-    fn window_will_close(&self) {
+    fn window_will_close(&self, notification: *mut Object) {
+        #[cfg(feature = "remember_window_state")]
+        {
+            let remember_window_state = self.ivars().remember_window_state.get();
+            if !remember_window_state.is_null() {
+                let window: *mut Object = unsafe { msg_send![notification, object] };
+                PlatformWindow::save_window_state(window, unsafe { &*remember_window_state });
+            }
+        }
         send_event(crate::Event::Window(WindowEvent::Close));
     }
 
@@ -169,8 +188,16 @@ impl PlatformWindow {
                 let _: () = msg_send![window, setMinSize:NSSize::new(min_size.width as f64, min_size.height as f64)];
             }
             #[cfg(feature = "remember_window_state")]
-            if builder.remember_window_state {
-                let _: Bool = msg_send![window, setFrameAutosaveName:ns_string!("window")];
+            if let Some(remember_window_state) = RememberWindowState::from_builder(builder) {
+                Self::load_window_state(window, &remember_window_state);
+                (*(window_delegate as *const WindowDelegate))
+                    .ivars()
+                    .remember_window_state
+                    .set(Box::leak(Box::new(remember_window_state)));
+            }
+            if builder.show_after_load {
+                // Transparent windows are not shown at launch, the webview shows them after load
+                let _: () = msg_send![window, setAlphaValue:0.0f64];
             }
             let _: () = msg_send![window, setDelegate:window_delegate];
             window
@@ -179,6 +206,58 @@ impl PlatformWindow {
         window_data.window = window;
         PlatformWindow(window_data)
     }
+
+    #[cfg(feature = "remember_window_state")]
+    fn load_window_state(window: *mut Object, remember_window_state: &RememberWindowState) {
+        let Some(state) = remember_window_state.load() else {
+            return;
+        };
+
+        // Don't restore a position that is no longer on any connected screen
+        let mut monitors = Vec::new();
+        unsafe {
+            let screens: *mut Object = msg_send![class!(NSScreen), screens];
+            let count: usize = msg_send![screens, count];
+            for i in 0..count {
+                let screen: *mut Object = msg_send![screens, objectAtIndex:i];
+                let frame: NSRect = msg_send![screen, frame];
+                monitors.push((
+                    LogicalPoint::new(frame.origin.x as f32, frame.origin.y as f32),
+                    LogicalSize::new(frame.size.width as f32, frame.size.height as f32),
+                ));
+            }
+        }
+        let frame: NSRect = unsafe { msg_send![window, frame] };
+        let origin = if state.is_visible_on(&monitors) {
+            NSPoint::new(state.position.x as f64, state.position.y as f64)
+        } else {
+            frame.origin
+        };
+        let size = NSSize::new(state.size.width as f64, state.size.height as f64);
+        unsafe {
+            let _: () = msg_send![window, setFrame:NSRect::new(origin, size), display:false];
+            if state.maximized {
+                let _: () = msg_send![window, zoom:null::<Object>()];
+            }
+            if state.fullscreen {
+                // Fullscreen can only be entered once the window is on screen
+                let _: () = msg_send![window, performSelector:sel!(toggleFullScreen:), withObject:null::<Object>(), afterDelay:0.0f64];
+            }
+        }
+    }
+
+    #[cfg(feature = "remember_window_state")]
+    fn save_window_state(window: *mut Object, remember_window_state: &RememberWindowState) {
+        let frame: NSRect = unsafe { msg_send![window, frame] };
+        let style_mask: u64 = unsafe { msg_send![window, styleMask] };
+        let zoomed: Bool = unsafe { msg_send![window, isZoomed] };
+        remember_window_state.save_keeping_normal_bounds(WindowState {
+            position: LogicalPoint::new(frame.origin.x as f32, frame.origin.y as f32),
+            size: LogicalSize::new(frame.size.width as f32, frame.size.height as f32),
+            maximized: zoomed == Bool::YES,
+            fullscreen: style_mask & NS_WINDOW_STYLE_MASK_FULL_SCREEN != 0,
+        });
+    }
 }
 
 impl crate::WindowInterface for PlatformWindow {
@@ -256,3 +335,17 @@ impl crate::WindowInterface for PlatformWindow {
         )
     }
 }
+
+#[cfg(feature = "remember_window_state")]
+pub(crate) fn config_dir() -> std::path::PathBuf {
+    directories::ProjectDirs::from_path(std::path::PathBuf::from(
+        env::current_exe()
+            .expect("Can't get current process name")
+            .file_name()
+            .expect("Can't get current process name")
+            .to_string_lossy()
+            .into_owned(),
+    ))
+    .expect("Can't get dirs")
+    .config_dir()
+}
@@ -0,0 +1,93 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::env;
+use std::ffi::CString;
+use std::mem::{self, size_of};
+use std::ptr::null_mut;
+
+use super::event_loop::FIRST_HWND;
+use super::win32::*;
+
+pub(super) const WM_NOTIFICATION: u32 = WM_USER + 2;
+const NOTIFICATION_ICON_ID: u32 = 1;
+
+/// Balloon clicks don't carry the notification, so the last shown notification is clicked
+pub(super) static mut LAST_NOTIFICATION_ID: Option<u32> = None;
+static mut ICON_ADDED: bool = false;
+
+pub(crate) struct PlatformNotification;
+
+impl crate::NotificationInterface for PlatformNotification {
+    fn show(notification: &crate::Notification, id: u32) {
+        let Some(hwnd) = (unsafe { FIRST_HWND }) else {
+            return;
+        };
+        unsafe {
+            let mut data: NOTIFYICONDATAW = mem::zeroed();
+            data.cbSize = size_of::<NOTIFYICONDATAW>() as u32;
+            data.hWnd = hwnd;
+            data.uID = NOTIFICATION_ICON_ID;
+
+            // Balloon notifications need a tray icon, use the executable icon
+            if !ICON_ADDED {
+                let executable_path = CString::new(
+                    env::current_exe()
+                        .expect("Can't get current exe path")
+                        .display()
+                        .to_string(),
+                )
+                .expect("Can't convert to CString");
+                ExtractIconExA(executable_path.as_ptr(), 0, null_mut(), &mut data.hIcon, 1);
+                data.uFlags = NIF_MESSAGE | NIF_ICON;
+                data.uCallbackMessage = WM_NOTIFICATION;
+                Shell_NotifyIconW(NIM_ADD, &data);
+                ICON_ADDED = true;
+            }
+
+            data.uFlags = NIF_INFO;
+            copy_wide_string(&mut data.szInfoTitle, &notification.title);
+            copy_wide_string(&mut data.szInfo, &notification.body);
+            data.dwInfoFlags = NIIF_INFO;
+            if let Some(icon) = &notification.icon {
+                let balloon_icon = LoadImageW(
+                    null_mut(),
+                    icon.display().to_string().to_wide_string().as_ptr(),
+                    IMAGE_ICON,
+                    0,
+                    0,
+                    LR_LOADFROMFILE,
+                );
+                if !balloon_icon.is_null() {
+                    data.hBalloonIcon = balloon_icon;
+                    data.dwInfoFlags = NIIF_USER | NIIF_LARGE_ICON;
+                }
+            }
+            LAST_NOTIFICATION_ID = Some(id);
+            Shell_NotifyIconW(NIM_MODIFY, &data);
+        }
+    }
+}
+
+/// Remove the tray icon when the window is destroyed
+pub(super) fn remove_notification_icon(hwnd: HWND) {
+    unsafe {
+        if ICON_ADDED {
+            let mut data: NOTIFYICONDATAW = mem::zeroed();
+            data.cbSize = size_of::<NOTIFYICONDATAW>() as u32;
+            data.hWnd = hwnd;
+            data.uID = NOTIFICATION_ICON_ID;
+            Shell_NotifyIconW(NIM_DELETE, &data);
+            ICON_ADDED = false;
+        }
+    }
+}
+
+fn copy_wide_string(dest: &mut [u16], text: &str) {
+    let text = text.to_wide_string();
+    let len = text.len().min(dest.len()) - 1;
+    dest[..len].copy_from_slice(&text[..len]);
+}
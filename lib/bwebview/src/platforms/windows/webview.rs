@@ -14,7 +14,8 @@ use super::win32::*;
 use super::window::{PlatformWindow, WindowData, config_dir};
 #[cfg(feature = "custom_protocol")]
 use crate::CustomProtocol;
-use crate::{InjectionTime, WebviewBuilder, WebviewEvent};
+use crate::accelerator::Key;
+use crate::{Accelerator, AcceleratorError, InjectionTime, WebviewBuilder, WebviewEvent};
 
 pub(super) struct WebviewData {
     pub(super) hwnd: HWND,
@@ -27,6 +28,7 @@ pub(super) struct WebviewData {
     pub(super) webview: Option<*mut ICoreWebView2>,
     pub(super) controller: Option<*mut ICoreWebView2Controller>,
     pub(super) window_data: *mut WindowData,
+    pub(super) accelerators: Vec<(Accelerator, u32)>,
 }
 
 pub(crate) struct PlatformWebview {
@@ -47,6 +49,7 @@ impl PlatformWebview {
             webview: None,
             controller: None,
             window_data,
+            accelerators: Vec::new(),
         });
         PlatformWebview { webview_data }
     }
@@ -184,6 +187,96 @@ impl crate::WebviewInterface for PlatformWebview {
             }
         }
     }
+
+    fn register_accelerator(
+        &mut self,
+        accelerator: Accelerator,
+        id: u32,
+        global: bool,
+    ) -> Result<(), AcceleratorError> {
+        if !global {
+            self.webview_data.accelerators.push((accelerator, id));
+            return Ok(());
+        }
+
+        let mut modifiers = MOD_NOREPEAT;
+        if accelerator.ctrl {
+            modifiers |= MOD_CONTROL;
+        }
+        if accelerator.alt {
+            modifiers |= MOD_ALT;
+        }
+        if accelerator.shift {
+            modifiers |= MOD_SHIFT;
+        }
+        if accelerator.meta {
+            modifiers |= MOD_WIN;
+        }
+        let window_data = unsafe { &mut *self.webview_data.window_data };
+        let hotkey = window_data
+            .hotkeys
+            .iter()
+            .map(|(hotkey, _)| *hotkey)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        if unsafe {
+            RegisterHotKey(
+                self.webview_data.hwnd,
+                hotkey,
+                modifiers,
+                accelerator_virtual_key(accelerator.key),
+            )
+        } == FALSE
+        {
+            return Err(AcceleratorError::AlreadyRegistered);
+        }
+        window_data.hotkeys.push((hotkey, id));
+        Ok(())
+    }
+
+    fn unregister_accelerator(&mut self, id: u32) {
+        self.webview_data
+            .accelerators
+            .retain(|(_, other)| *other != id);
+        let hwnd = self.webview_data.hwnd;
+        let window_data = unsafe { &mut *self.webview_data.window_data };
+        window_data.hotkeys.retain(|(hotkey, other)| {
+            if *other == id {
+                unsafe { UnregisterHotKey(hwnd, *hotkey) };
+            }
+            *other != id
+        });
+    }
+}
+
+const fn accelerator_virtual_key(key: Key) -> u32 {
+    match key {
+        Key::Char(c) => c as u32,
+        Key::Function(number) => 0x70 + number as u32 - 1,
+        Key::Space => 0x20,
+        Key::Enter => 0x0D,
+        Key::Tab => 0x09,
+        Key::Escape => 0x1B,
+        Key::Backspace => 0x08,
+        Key::Delete => 0x2E,
+        Key::Insert => 0x2D,
+        Key::Home => 0x24,
+        Key::End => 0x23,
+        Key::PageUp => 0x21,
+        Key::PageDown => 0x22,
+        Key::Left => 0x25,
+        Key::Up => 0x26,
+        Key::Right => 0x27,
+        Key::Down => 0x28,
+        Key::VolumeMute => 0xAD,
+        Key::VolumeDown => 0xAE,
+        Key::VolumeUp => 0xAF,
+        Key::MediaNextTrack => 0xB0,
+        Key::MediaPreviousTrack => 0xB1,
+        Key::MediaStop => 0xB2,
+        Key::MediaPlayPause => 0xB3,
+    }
 }
 
 const extern "system" fn unimplemented_query_interface(
@@ -319,6 +412,21 @@ extern "system" fn controller_created(
         }
 
         // Setup event handlers
+        {
+            static VTBL: ICoreWebView2AcceleratorKeyPressedEventHandlerVtbl =
+                ICoreWebView2AcceleratorKeyPressedEventHandlerVtbl {
+                    QueryInterface: unimplemented_query_interface,
+                    AddRef: unimplemented_add_ref,
+                    Release: unimplemented_release,
+                    Invoke: accelerator_key_pressed,
+                };
+            let accelerator_key_pressed_handler =
+                Box::into_raw(Box::new(ICoreWebView2AcceleratorKeyPressedEventHandler {
+                    lpVtbl: &VTBL,
+                    user_data: (*_this).user_data,
+                }));
+            (*controller).add_AcceleratorKeyPressed(accelerator_key_pressed_handler, null_mut());
+        }
         {
             static VTBL: ICoreWebView2NavigationStartingEventHandlerVtbl =
                 ICoreWebView2NavigationStartingEventHandlerVtbl {
@@ -417,23 +525,70 @@ extern "system" fn controller_created(
     }
 }
 
+extern "system" fn accelerator_key_pressed(
+    _this: *mut ICoreWebView2AcceleratorKeyPressedEventHandler,
+    _sender: *mut ICoreWebView2Controller,
+    args: *mut ICoreWebView2AcceleratorKeyPressedEventArgs,
+) -> HRESULT {
+    let _self = unsafe { &*((*_this).user_data as *const WebviewData) };
+    unsafe {
+        let mut kind = 0;
+        (*args).get_KeyEventKind(&mut kind);
+        if kind != COREWEBVIEW2_KEY_EVENT_KIND_KEY_DOWN
+            && kind != COREWEBVIEW2_KEY_EVENT_KIND_SYSTEM_KEY_DOWN
+        {
+            return S_OK;
+        }
+        let mut virtual_key = 0;
+        (*args).get_VirtualKey(&mut virtual_key);
+
+        let is_down = |virtual_key| GetKeyState(virtual_key) < 0;
+        if let Some((_, id)) = _self.accelerators.iter().find(|(accelerator, _)| {
+            accelerator_virtual_key(accelerator.key) == virtual_key
+                && accelerator.ctrl == is_down(VK_CONTROL)
+                && accelerator.alt == is_down(VK_MENU)
+                && accelerator.shift == is_down(VK_SHIFT)
+                && accelerator.meta == (is_down(VK_LWIN) || is_down(VK_RWIN))
+        }) {
+            (*args).put_Handled(TRUE);
+            send_event(crate::Event::AcceleratorPressed(*id));
+        }
+    }
+    S_OK
+}
+
 extern "system" fn navigation_starting(
     _this: *mut ICoreWebView2NavigationStartingEventHandler,
     _sender: *mut ICoreWebView2,
-    _args: *mut ICoreWebView2NavigationStartingEventArgs,
+    args: *mut ICoreWebView2NavigationStartingEventArgs,
 ) -> HRESULT {
     let _self = unsafe { &*((*_this).user_data as *const WebviewData) };
-    send_event(crate::Event::Webview(WebviewEvent::PageLoadStart));
+    let mut uri = LPWSTR::default();
+    unsafe { (*args).get_Uri(uri.as_mut_ptr()) };
+    send_event(crate::Event::Webview(WebviewEvent::PageLoadStart(
+        uri.to_string(),
+    )));
     S_OK
 }
 
 extern "system" fn navigation_completed(
     _this: *mut ICoreWebView2NavigationCompletedEventHandler,
-    _sender: *mut ICoreWebView2,
+    sender: *mut ICoreWebView2,
     _args: *mut ICoreWebView2NavigationCompletedEventArgs,
 ) -> HRESULT {
     let _self = unsafe { &*((*_this).user_data as *const WebviewData) };
-    send_event(crate::Event::Webview(WebviewEvent::PageLoadFinish));
+
+    // Show window that waited for the first page load
+    let window_data = unsafe { &mut *_self.window_data };
+    if let Some(show_cmd) = window_data.pending_show_cmd.take() {
+        unsafe { ShowWindow(_self.hwnd, show_cmd) };
+    }
+
+    let mut source = LPWSTR::default();
+    unsafe { (*sender).get_Source(source.as_mut_ptr()) };
+    send_event(crate::Event::Webview(WebviewEvent::PageLoadFinish(
+        source.to_string(),
+    )));
     S_OK
 }
 
@@ -156,10 +156,13 @@ pub(crate) const WM_SIZE: u32 = 0x0005;
 pub(crate) const WM_CLOSE: u32 = 0x0010;
 pub(crate) const WM_ERASEBKGND: u32 = 0x0014;
 pub(crate) const WM_GETMINMAXINFO: u32 = 0x0024;
+pub(crate) const WM_HOTKEY: u32 = 0x0312;
 pub(crate) const WM_DPICHANGED: u32 = 0x02E0;
 pub(crate) const WM_USER: u32 = 0x0400;
 
+pub(crate) const SW_HIDE: i32 = 0;
 pub(crate) const SW_SHOWNORMAL: i32 = 1;
+pub(crate) const SW_SHOWMAXIMIZED: i32 = 3;
 pub(crate) const SW_RESTORE: i32 = 9;
 pub(crate) const SW_SHOWDEFAULT: i32 = 10;
 
@@ -183,6 +186,21 @@ pub(crate) const SWP_NOZORDER: u32 = 0x0004;
 pub(crate) const SWP_NOACTIVATE: u32 = 0x0010;
 pub(crate) const SWP_NOREPOSITION: u32 = 0x0200;
 
+pub(crate) const MOD_ALT: u32 = 0x0001;
+pub(crate) const MOD_CONTROL: u32 = 0x0002;
+pub(crate) const MOD_SHIFT: u32 = 0x0004;
+pub(crate) const MOD_WIN: u32 = 0x0008;
+pub(crate) const MOD_NOREPEAT: u32 = 0x4000;
+
+pub(crate) const VK_SHIFT: i32 = 0x10;
+pub(crate) const VK_CONTROL: i32 = 0x11;
+pub(crate) const VK_MENU: i32 = 0x12;
+pub(crate) const VK_LWIN: i32 = 0x5B;
+pub(crate) const VK_RWIN: i32 = 0x5C;
+
+pub(crate) const IMAGE_ICON: u32 = 1;
+pub(crate) const LR_LOADFROMFILE: u32 = 0x00000010;
+
 #[link(name = "user32")]
 unsafe extern "system" {
     pub(crate) fn ExtractIconExA(
@@ -276,6 +294,17 @@ unsafe extern "system" {
     pub(crate) fn SetWindowPlacement(hWnd: HWND, lpwndpl: *const WINDOWPLACEMENT) -> BOOL;
     pub(crate) fn FindWindowA(lpClassName: *const c_char, lpWindowName: *const c_char) -> HWND;
     pub(crate) fn SetForegroundWindow(hWnd: HWND) -> BOOL;
+    pub(crate) fn RegisterHotKey(hWnd: HWND, id: i32, fsModifiers: u32, vk: u32) -> BOOL;
+    pub(crate) fn UnregisterHotKey(hWnd: HWND, id: i32) -> BOOL;
+    pub(crate) fn GetKeyState(nVirtKey: i32) -> i16;
+    pub(crate) fn LoadImageW(
+        hInst: HMODULE,
+        name: *const u16,
+        type_: u32,
+        cx: i32,
+        cy: i32,
+        fuLoad: u32,
+    ) -> HANDLE;
 }
 
 #[cfg(target_pointer_width = "32")]
@@ -297,8 +326,39 @@ pub(crate) unsafe fn SetWindowLong(hwnd: HWND, index: i32, value: isize) -> isiz
 }
 
 // MARK: shell32.dll
+#[repr(C)]
+pub(crate) struct NOTIFYICONDATAW {
+    pub(crate) cbSize: u32,
+    pub(crate) hWnd: HWND,
+    pub(crate) uID: u32,
+    pub(crate) uFlags: u32,
+    pub(crate) uCallbackMessage: u32,
+    pub(crate) hIcon: HICON,
+    pub(crate) szTip: [u16; 128],
+    pub(crate) dwState: u32,
+    pub(crate) dwStateMask: u32,
+    pub(crate) szInfo: [u16; 256],
+    pub(crate) uVersion: u32,
+    pub(crate) szInfoTitle: [u16; 64],
+    pub(crate) dwInfoFlags: u32,
+    pub(crate) guidItem: GUID,
+    pub(crate) hBalloonIcon: HICON,
+}
+
+pub(crate) const NIM_ADD: u32 = 0x00000000;
+pub(crate) const NIM_MODIFY: u32 = 0x00000001;
+pub(crate) const NIM_DELETE: u32 = 0x00000002;
+pub(crate) const NIF_MESSAGE: u32 = 0x00000001;
+pub(crate) const NIF_ICON: u32 = 0x00000002;
+pub(crate) const NIF_INFO: u32 = 0x00000010;
+pub(crate) const NIIF_INFO: u32 = 0x00000001;
+pub(crate) const NIIF_USER: u32 = 0x00000004;
+pub(crate) const NIIF_LARGE_ICON: u32 = 0x00000020;
+pub(crate) const NIN_BALLOONUSERCLICK: u32 = WM_USER + 5;
+
 #[link(name = "shell32")]
 unsafe extern "system" {
+    pub(crate) fn Shell_NotifyIconW(dwMessage: u32, lpData: *const NOTIFYICONDATAW) -> BOOL;
     pub(crate) fn ShellExecuteA(
         hwnd: HWND,
         lpOperation: *const c_char,
@@ -5,15 +5,17 @@
  */
 
 use std::ffi::{CString, c_void};
-use std::fs::File;
-use std::io::Read;
 use std::path::PathBuf;
 use std::ptr::{null, null_mut};
 use std::{env, mem};
 
+#[cfg(feature = "remember_window_state")]
+use super::event_loop::PlatformEventLoop;
 use super::event_loop::{APP_ID, FIRST_HWND, WM_SEND_MESSAGE, send_event};
 use super::webview2::*;
 use super::win32::*;
+#[cfg(feature = "remember_window_state")]
+use crate::{EventLoopInterface, RememberWindowState, WindowState};
 use crate::{LogicalPoint, LogicalSize, Theme, WindowBuilder, WindowEvent};
 
 pub(super) struct WindowData {
@@ -22,8 +24,10 @@ pub(super) struct WindowData {
     pub(super) min_size: Option<LogicalSize>,
     pub(super) background_color: Option<u32>,
     #[cfg(feature = "remember_window_state")]
-    pub(super) remember_window_state: bool,
+    pub(super) remember_window_state: Option<RememberWindowState>,
     pub(super) resize_callback: Option<Box<dyn Fn(i32, i32)>>,
+    pub(super) hotkeys: Vec<(i32, u32)>,
+    pub(super) pending_show_cmd: Option<i32>,
 }
 
 pub(crate) struct PlatformWindow(pub(super) Box<WindowData>);
@@ -81,6 +85,9 @@ impl PlatformWindow {
         }
 
         // Create window
+        #[cfg(feature = "remember_window_state")]
+        let remember_window_state = RememberWindowState::from_builder(builder);
+        let mut pending_show_cmd = None;
         let hwnd = unsafe {
             let style = if builder.should_fullscreen {
                 WS_POPUP
@@ -173,27 +180,68 @@ impl PlatformWindow {
             }
 
             #[cfg(feature = "remember_window_state")]
-            let should_show_window = if builder.remember_window_state {
-                if let Ok(mut file) = File::open(config_dir().join("window.bin")) {
-                    let size = size_of::<WINDOWPLACEMENT>();
-                    let mut buffer = vec![0u8; size];
-                    if file.read_exact(&mut buffer).is_ok() {
-                        let window_placement = std::ptr::read(buffer.as_ptr() as *const _);
-                        SetWindowPlacement(hwnd, &window_placement);
-                        false
-                    } else {
-                        true
-                    }
+            let should_show_window = if let Some(state) = remember_window_state
+                .as_ref()
+                .and_then(RememberWindowState::load)
+            {
+                let to_physical =
+                    |value: f32| value as i32 * dpi as i32 / USER_DEFAULT_SCREEN_DPI as i32;
+                let to_logical =
+                    |value: i32| (value * USER_DEFAULT_SCREEN_DPI as i32 / dpi as i32) as f32;
+
+                // Don't restore a position that is no longer on any connected monitor
+                let monitors = PlatformEventLoop
+                    .available_monitors()
+                    .iter()
+                    .map(|monitor| {
+                        let rect = monitor.rect();
+                        (
+                            LogicalPoint::new(to_logical(rect.left), to_logical(rect.top)),
+                            LogicalSize::new(
+                                to_logical(rect.right - rect.left),
+                                to_logical(rect.bottom - rect.top),
+                            ),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                let (left, top) = if state.is_visible_on(&monitors) {
+                    (to_physical(state.position.x), to_physical(state.position.y))
+                } else {
+                    let mut window_rect = RECT::default();
+                    GetWindowRect(hwnd, &mut window_rect);
+                    (window_rect.left, window_rect.top)
+                };
+
+                let mut window_placement: WINDOWPLACEMENT = mem::zeroed();
+                window_placement.length = size_of::<WINDOWPLACEMENT>() as u32;
+                window_placement.showCmd = if state.maximized {
+                    SW_SHOWMAXIMIZED
                 } else {
-                    true
+                    SW_SHOWNORMAL
+                } as u32;
+                window_placement.rcNormalPosition = RECT {
+                    left,
+                    top,
+                    right: left + to_physical(state.size.width),
+                    bottom: top + to_physical(state.size.height),
+                };
+                if builder.show_after_load {
+                    pending_show_cmd = Some(window_placement.showCmd as i32);
+                    window_placement.showCmd = SW_HIDE as u32;
                 }
+                SetWindowPlacement(hwnd, &window_placement);
+                false
             } else {
                 true
             };
             #[cfg(not(feature = "remember_window_state"))]
             let should_show_window = true;
             if should_show_window {
-                ShowWindow(hwnd, SW_SHOWDEFAULT);
+                if builder.show_after_load {
+                    pending_show_cmd = Some(SW_SHOWDEFAULT);
+                } else {
+                    ShowWindow(hwnd, SW_SHOWDEFAULT);
+                }
             }
             UpdateWindow(hwnd);
             hwnd
@@ -213,8 +261,10 @@ impl PlatformWindow {
             min_size: builder.min_size,
             background_color: builder.background_color,
             #[cfg(feature = "remember_window_state")]
-            remember_window_state: builder.remember_window_state,
+            remember_window_state,
             resize_callback: None,
+            hotkeys: Vec::new(),
+            pending_show_cmd,
         });
         unsafe {
             SetWindowLong(
@@ -409,20 +459,44 @@ unsafe extern "system" fn window_proc(
             send_event(*event);
             0
         }
+        WM_HOTKEY => {
+            if let Some((_, id)) = _self
+                .hotkeys
+                .iter()
+                .find(|(hotkey, _)| *hotkey == w_param as i32)
+            {
+                send_event(crate::Event::AcceleratorPressed(*id));
+            }
+            0
+        }
+        #[cfg(feature = "notification")]
+        super::notification::WM_NOTIFICATION => {
+            if l_param as u32 == NIN_BALLOONUSERCLICK
+                && let Some(id) = unsafe { super::notification::LAST_NOTIFICATION_ID }
+            {
+                send_event(crate::Event::NotificationClicked(id));
+            }
+            0
+        }
         WM_CLOSE => {
             #[cfg(feature = "remember_window_state")]
-            if _self.remember_window_state {
-                unsafe {
-                    use std::io::Write;
-                    let mut window_placement = mem::zeroed();
-                    GetWindowPlacement(hwnd, &mut window_placement);
-                    if let Ok(mut file) = File::create(config_dir().join("window.bin")) {
-                        _ = file.write_all(std::slice::from_raw_parts(
-                            &window_placement as *const _ as *const u8,
-                            size_of::<WINDOWPLACEMENT>(),
-                        ));
-                    }
-                }
+            if let Some(remember_window_state) = &_self.remember_window_state {
+                let mut window_placement: WINDOWPLACEMENT = unsafe { mem::zeroed() };
+                window_placement.length = size_of::<WINDOWPLACEMENT>() as u32;
+                unsafe { GetWindowPlacement(hwnd, &mut window_placement) };
+                let to_logical =
+                    |value: i32| (value * USER_DEFAULT_SCREEN_DPI as i32 / _self.dpi as i32) as f32;
+                let rect = &window_placement.rcNormalPosition;
+                remember_window_state.save(&WindowState {
+                    position: LogicalPoint::new(to_logical(rect.left), to_logical(rect.top)),
+                    size: LogicalSize::new(
+                        to_logical(rect.right - rect.left),
+                        to_logical(rect.bottom - rect.top),
+                    ),
+                    maximized: window_placement.showCmd == SW_SHOWMAXIMIZED as u32,
+                    // Fullscreen windows are created by the builder and can't be toggled
+                    fullscreen: false,
+                });
             }
 
             send_event(crate::Event::Window(WindowEvent::Close));
@@ -430,6 +504,8 @@ unsafe extern "system" fn window_proc(
             0
         }
         WM_DESTROY => {
+            #[cfg(feature = "notification")]
+            super::notification::remove_notification_icon(hwnd);
             unsafe { PostQuitMessage(0) };
             0
         }
@@ -437,7 +513,7 @@ unsafe extern "system" fn window_proc(
     }
 }
 
-pub(super) fn config_dir() -> PathBuf {
+pub(crate) fn config_dir() -> PathBuf {
     let project_dirs = unsafe {
         if let Some(ref app_id) = APP_ID {
             directories::ProjectDirs::from(
@@ -0,0 +1,59 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::sync::OnceLock;
+
+use threadpool::{ThreadPool, ThreadPoolBuilder};
+
+use crate::platforms::PlatformEventLoopProxy;
+use crate::{EventLoopProxy, Webview};
+
+// MARK: TaskProgress
+/// Progress reporter passed to a task spawned with [`Webview::spawn_task`]
+pub struct TaskProgress<P> {
+    proxy: EventLoopProxy,
+    on_progress: Box<dyn Fn(P) -> String + Send>,
+}
+
+impl<P> TaskProgress<P> {
+    /// Report progress, the progress is converted to an IPC message and sent to the event loop
+    pub fn report(&self, progress: P) {
+        self.proxy.send_user_event((self.on_progress)(progress));
+    }
+}
+
+// MARK: Webview
+fn task_pool() -> &'static ThreadPool {
+    static POOL: OnceLock<ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        ThreadPoolBuilder::new()
+            .name_prefix("bwebview-task")
+            .build()
+    })
+}
+
+impl Webview {
+    /// Run a long operation on a background thread pool so it doesn't freeze the UI
+    ///
+    /// The task can report progress with the [`TaskProgress`] handle, `on_progress` converts
+    /// each progress value to an IPC message. The message returned by the task is sent when it
+    /// completes. All messages arrive as [`Event::UserEvent`](crate::Event::UserEvent) on the
+    /// event loop, forward them with [`Webview::send_ipc_message`].
+    pub fn spawn_task<P: 'static>(
+        &self,
+        task: impl FnOnce(&TaskProgress<P>) -> String + Send + 'static,
+        on_progress: impl Fn(P) -> String + Send + 'static,
+    ) {
+        let progress = TaskProgress {
+            proxy: EventLoopProxy::new(PlatformEventLoopProxy::new()),
+            on_progress: Box::new(on_progress),
+        };
+        task_pool().execute(move || {
+            let message = task(&progress);
+            progress.proxy.send_user_event(message);
+        });
+    }
+}
@@ -4,8 +4,8 @@
  * SPDX-License-Identifier: MIT
  */
 
-use crate::Window;
 use crate::platforms::PlatformWebview;
+use crate::{Accelerator, AcceleratorError, Window};
 
 // MARK: CustomProtocol
 #[cfg(feature = "custom_protocol")]
@@ -148,6 +148,13 @@ pub(crate) trait WebviewInterface {
     fn evaluate_script(&mut self, script: impl AsRef<str>);
     fn add_user_script(&mut self, script: impl AsRef<str>, injection_time: InjectionTime);
     fn set_background_color(&mut self, color: u32);
+    fn register_accelerator(
+        &mut self,
+        accelerator: Accelerator,
+        id: u32,
+        global: bool,
+    ) -> Result<(), AcceleratorError>;
+    fn unregister_accelerator(&mut self, id: u32);
 }
 
 // MARK: Webview
@@ -187,6 +194,36 @@ impl Webview {
         self.platform.set_background_color(color)
     }
 
+    /// Register a keyboard accelerator like `CmdOrCtrl+Shift+P`, pressing it while the window is
+    /// focused sends an [`Event::AcceleratorPressed`](crate::Event::AcceleratorPressed) with the id
+    pub fn register_accelerator(
+        &mut self,
+        accelerator: impl AsRef<str>,
+        id: u32,
+    ) -> Result<(), AcceleratorError> {
+        let accelerator = accelerator.as_ref().parse::<Accelerator>()?;
+        self.platform.register_accelerator(accelerator, id, false)
+    }
+
+    /// Register a global (system-wide) keyboard accelerator that also fires when the window
+    /// isn't focused, useful for media keys
+    ///
+    /// Supported on Windows and macOS, where the app needs accessibility permissions to receive
+    /// key presses while it's in the background. GTK returns [`AcceleratorError::Unsupported`].
+    pub fn register_global_accelerator(
+        &mut self,
+        accelerator: impl AsRef<str>,
+        id: u32,
+    ) -> Result<(), AcceleratorError> {
+        let accelerator = accelerator.as_ref().parse::<Accelerator>()?;
+        self.platform.register_accelerator(accelerator, id, true)
+    }
+
+    /// Unregister all accelerators with id
+    pub fn unregister_accelerator(&mut self, id: u32) {
+        self.platform.unregister_accelerator(id)
+    }
+
     /// Send IPC message
     pub fn send_ipc_message(&mut self, message: impl AsRef<str>) {
         let mut json = String::with_capacity(message.as_ref().len() + 2);
@@ -4,6 +4,11 @@
  * SPDX-License-Identifier: MIT
  */
 
+#[cfg(feature = "remember_window_state")]
+use std::rc::Rc;
+
+#[cfg(feature = "remember_window_state")]
+use crate::WindowStateStore;
 use crate::platforms::{PlatformMonitor, PlatformWindow};
 use crate::{LogicalPoint, LogicalSize};
 
@@ -41,10 +46,13 @@ pub struct WindowBuilder<'a> {
     pub(crate) theme: Option<Theme>,
     pub(crate) background_color: Option<u32>,
     #[cfg(feature = "remember_window_state")]
-    pub(crate) remember_window_state: bool,
+    pub(crate) window_state_key: Option<String>,
+    #[cfg(feature = "remember_window_state")]
+    pub(crate) window_state_store: Option<Rc<dyn WindowStateStore>>,
     pub(crate) monitor: Option<&'a PlatformMonitor>,
     pub(crate) should_center: bool,
     pub(crate) should_fullscreen: bool,
+    pub(crate) show_after_load: bool,
     #[cfg(target_os = "macos")]
     pub(crate) macos_titlebar_style: MacosTitlebarStyle,
 }
@@ -63,10 +71,13 @@ impl<'a> Default for WindowBuilder<'a> {
             theme: None,
             background_color: None,
             #[cfg(feature = "remember_window_state")]
-            remember_window_state: false,
+            window_state_key: None,
+            #[cfg(feature = "remember_window_state")]
+            window_state_store: None,
             monitor: None,
             should_center: false,
             should_fullscreen: false,
+            show_after_load: false,
             #[cfg(target_os = "macos")]
             macos_titlebar_style: MacosTitlebarStyle::Default,
         }
@@ -121,10 +132,23 @@ impl<'a> WindowBuilder<'a> {
         self
     }
 
-    /// Set remember window state
+    /// Remember window position, size, maximized and fullscreen state between launches
     #[cfg(feature = "remember_window_state")]
-    pub const fn remember_window_state(mut self) -> Self {
-        self.remember_window_state = true;
+    pub fn remember_window_state(self) -> Self {
+        self.window_state_key("window")
+    }
+
+    /// Remember window state under a key, so multiple windows each restore their own state
+    #[cfg(feature = "remember_window_state")]
+    pub fn window_state_key(mut self, key: impl Into<String>) -> Self {
+        self.window_state_key = Some(key.into());
+        self
+    }
+
+    /// Set the store where remembered window state is persisted, defaults to [`crate::IniWindowStateStore`]
+    #[cfg(feature = "remember_window_state")]
+    pub fn window_state_store(mut self, store: impl WindowStateStore + 'static) -> Self {
+        self.window_state_store = Some(Rc::new(store));
         self
     }
 
@@ -146,6 +170,13 @@ impl<'a> WindowBuilder<'a> {
         self
     }
 
+    /// Keep the window hidden until the webview finished loading its first page, this
+    /// prevents a white flash on startup
+    pub const fn show_after_load(mut self, show_after_load: bool) -> Self {
+        self.show_after_load = show_after_load;
+        self
+    }
+
     /// Set macOS titlebar style
     #[cfg(target_os = "macos")]
     pub const fn macos_titlebar_style(mut self, style: MacosTitlebarStyle) -> Self {
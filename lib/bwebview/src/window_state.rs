@@ -0,0 +1,268 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::{LogicalPoint, LogicalSize, WindowBuilder};
+
+// Minimum visible part of a restored window in each direction, so it can still be grabbed
+const MIN_VISIBLE_SIZE: f32 = 64.0;
+
+// MARK: WindowState
+/// Remembered window state
+#[derive(Debug, Clone, Copy)]
+pub struct WindowState {
+    /// Position of the window on the virtual screen
+    pub position: LogicalPoint,
+    /// Size of the window when it is not maximized or fullscreen
+    pub size: LogicalSize,
+    /// Window is maximized
+    pub maximized: bool,
+    /// Window is fullscreen
+    pub fullscreen: bool,
+}
+
+impl WindowState {
+    /// Check if enough of the window is visible on one of the monitors, given as virtual screen rects
+    pub(crate) fn is_visible_on(&self, monitors: &[(LogicalPoint, LogicalSize)]) -> bool {
+        monitors.iter().any(|(position, size)| {
+            let visible_width = (self.position.x + self.size.width).min(position.x + size.width)
+                - self.position.x.max(position.x);
+            let visible_height = (self.position.y + self.size.height).min(position.y + size.height)
+                - self.position.y.max(position.y);
+            visible_width >= MIN_VISIBLE_SIZE.min(self.size.width)
+                && visible_height >= MIN_VISIBLE_SIZE.min(self.size.height)
+        })
+    }
+}
+
+// MARK: WindowStateStore
+/// Store where remembered window states are persisted
+pub trait WindowStateStore {
+    /// Load the window state of a window key
+    fn load(&self, key: &str) -> Option<WindowState>;
+
+    /// Save the window state of a window key
+    fn save(&self, key: &str, state: &WindowState);
+}
+
+// MARK: IniWindowStateStore
+/// Window state store that keeps every window key as a section of an ini file
+pub struct IniWindowStateStore {
+    path: PathBuf,
+}
+
+impl IniWindowStateStore {
+    /// Create new ini window state store
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn read_sections(&self) -> Vec<(String, Vec<(String, String)>)> {
+        let mut sections: Vec<(String, Vec<(String, String)>)> = Vec::new();
+        for line in fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim)
+        {
+            if line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                sections.push((name.to_string(), Vec::new()));
+            } else if let Some((key, value)) = line.split_once('=')
+                && let Some((_, entries)) = sections.last_mut()
+            {
+                entries.push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        sections
+    }
+}
+
+impl Default for IniWindowStateStore {
+    /// Create ini window state store at `settings.ini` in the app config directory
+    fn default() -> Self {
+        Self::new(crate::platforms::config_dir().join("settings.ini"))
+    }
+}
+
+impl WindowStateStore for IniWindowStateStore {
+    fn load(&self, key: &str) -> Option<WindowState> {
+        let sections = self.read_sections();
+        let (_, entries) = sections.iter().find(|(name, _)| name == key)?;
+        let get = |name: &str| {
+            entries
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        Some(WindowState {
+            position: LogicalPoint::new(get("x")?.parse().ok()?, get("y")?.parse().ok()?),
+            size: LogicalSize::new(get("width")?.parse().ok()?, get("height")?.parse().ok()?),
+            maximized: get("maximized") == Some("true"),
+            fullscreen: get("fullscreen") == Some("true"),
+        })
+    }
+
+    fn save(&self, key: &str, state: &WindowState) {
+        let entries = vec![
+            ("x".to_string(), state.position.x.to_string()),
+            ("y".to_string(), state.position.y.to_string()),
+            ("width".to_string(), state.size.width.to_string()),
+            ("height".to_string(), state.size.height.to_string()),
+            ("maximized".to_string(), state.maximized.to_string()),
+            ("fullscreen".to_string(), state.fullscreen.to_string()),
+        ];
+
+        // Replace the section of this window key and keep the sections of other windows
+        let mut sections = self.read_sections();
+        if let Some((_, section)) = sections.iter_mut().find(|(name, _)| name == key) {
+            *section = entries;
+        } else {
+            sections.push((key.to_string(), entries));
+        }
+
+        let mut contents = String::new();
+        for (name, entries) in &sections {
+            if !contents.is_empty() {
+                contents.push('\n');
+            }
+            contents.push_str(&format!("[{name}]\n"));
+            for (key, value) in entries {
+                contents.push_str(&format!("{key}={value}\n"));
+            }
+        }
+        if let Some(dir) = self.path.parent() {
+            _ = fs::create_dir_all(dir);
+        }
+        _ = fs::write(&self.path, contents);
+    }
+}
+
+// MARK: RememberWindowState
+pub(crate) struct RememberWindowState {
+    key: String,
+    store: Rc<dyn WindowStateStore>,
+}
+
+impl RememberWindowState {
+    pub(crate) fn from_builder(builder: &WindowBuilder) -> Option<Self> {
+        builder.window_state_key.as_ref().map(|key| Self {
+            key: key.clone(),
+            store: builder
+                .window_state_store
+                .clone()
+                .unwrap_or_else(|| Rc::new(IniWindowStateStore::default())),
+        })
+    }
+
+    pub(crate) fn load(&self) -> Option<WindowState> {
+        self.store.load(&self.key)
+    }
+
+    pub(crate) fn save(&self, state: &WindowState) {
+        self.store.save(&self.key, state);
+    }
+
+    /// Save for platforms that only know the maximized or fullscreen bounds, which keeps the
+    /// previous normal bounds so the window doesn't restore at the maximized size
+    pub(crate) fn save_keeping_normal_bounds(&self, mut state: WindowState) {
+        if (state.maximized || state.fullscreen)
+            && let Some(previous) = self.load()
+        {
+            state.position = previous.position;
+            state.size = previous.size;
+        }
+        self.save(&state);
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn state(x: f32, y: f32, width: f32, height: f32) -> WindowState {
+        WindowState {
+            position: LogicalPoint::new(x, y),
+            size: LogicalSize::new(width, height),
+            maximized: false,
+            fullscreen: false,
+        }
+    }
+
+    #[test]
+    fn test_is_visible_on() {
+        let monitors = [
+            (
+                LogicalPoint::new(0.0, 0.0),
+                LogicalSize::new(1920.0, 1080.0),
+            ),
+            (
+                LogicalPoint::new(1920.0, 0.0),
+                LogicalSize::new(1280.0, 1024.0),
+            ),
+        ];
+        assert!(state(100.0, 100.0, 800.0, 600.0).is_visible_on(&monitors));
+        assert!(state(2000.0, 100.0, 800.0, 600.0).is_visible_on(&monitors));
+        // Partly off screen but enough left to grab
+        assert!(state(1856.0, 1016.0, 800.0, 600.0).is_visible_on(&monitors));
+        assert!(!state(1900.0, 1050.0, 800.0, 600.0).is_visible_on(&monitors));
+        // Completely off screen or on a disconnected monitor
+        assert!(!state(-900.0, 100.0, 800.0, 600.0).is_visible_on(&monitors));
+        assert!(!state(4000.0, 100.0, 800.0, 600.0).is_visible_on(&monitors));
+        assert!(!state(100.0, 100.0, 800.0, 600.0).is_visible_on(&[]));
+        // Small windows only have to be fully visible
+        assert!(state(1880.0, 1040.0, 40.0, 40.0).is_visible_on(&monitors[..1]));
+        assert!(!state(1890.0, 1040.0, 40.0, 40.0).is_visible_on(&monitors[..1]));
+    }
+
+    #[test]
+    fn test_ini_window_state_store_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "bwebview-window-state-{}/settings.ini",
+            std::process::id()
+        ));
+        let store = IniWindowStateStore::new(&path);
+        assert!(store.load("main").is_none());
+
+        store.save("main", &state(10.0, 20.0, 800.0, 600.0));
+        store.save(
+            "settings",
+            &WindowState {
+                maximized: true,
+                ..state(-1280.0, 0.5, 640.0, 480.0)
+            },
+        );
+        store.save(
+            "main",
+            &WindowState {
+                fullscreen: true,
+                ..state(30.0, 40.0, 1024.0, 768.0)
+            },
+        );
+
+        // Saving a key replaces its section and keeps the other sections
+        let main = store.load("main").unwrap();
+        assert_eq!((main.position.x, main.position.y), (30.0, 40.0));
+        assert_eq!((main.size.width, main.size.height), (1024.0, 768.0));
+        assert!(!main.maximized && main.fullscreen);
+        let settings = store.load("settings").unwrap();
+        assert_eq!((settings.position.x, settings.position.y), (-1280.0, 0.5));
+        assert_eq!((settings.size.width, settings.size.height), (640.0, 480.0));
+        assert!(settings.maximized && !settings.fullscreen);
+        assert!(store.load("about").is_none());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap().matches("[main]").count(),
+            1
+        );
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -14,6 +14,7 @@ pub(crate) const DAYS_IN_WEEK: i64 = 7;
 pub(crate) const SECS_IN_MIN: i64 = 60;
 pub(crate) const SECS_IN_HOUR: i64 = 60 * SECS_IN_MIN;
 pub(crate) const SECS_IN_DAY: i64 = 24 * SECS_IN_HOUR;
+pub(crate) const NANOS_IN_SEC: u32 = 1_000_000_000;
 
 pub(crate) static DAYS_IN_MONTHS: [u8; MONTHS_IN_YEAR as usize] =
     [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -8,27 +8,78 @@ use std::fmt::{self, Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::ops::{Add, Sub};
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
-use crate::consts::{DAY_NAMES, DAYS_IN_WEEK, MONTH_NAMES, SECS_IN_DAY, SECS_IN_HOUR, SECS_IN_MIN};
-use crate::utils::timestamp_to_ymd;
+use crate::consts::{
+    DAY_NAMES, DAYS_IN_WEEK, MONTH_NAMES, NANOS_IN_SEC, SECS_IN_DAY, SECS_IN_HOUR, SECS_IN_MIN,
+};
+use crate::utils::{parse_fraction_nanos, timestamp_to_ymd, write_fraction_nanos};
 use crate::{NaiveDate, NaiveDateTime, ParseError, TimeZone};
 
 // MARK: DateTime
 /// A DateTime in UTC timezone
 #[derive(Clone, Copy, PartialEq, Eq)]
-pub struct DateTime<T: TimeZone>(i64, PhantomData<T>);
+pub struct DateTime<T: TimeZone>(i64, u32, PhantomData<T>);
 
 impl<T: TimeZone> DateTime<T> {
+    /// Create a [DateTime] from a unix timestamp and nanoseconds
+    pub const fn from_timestamp(secs: i64, nsecs: u32) -> Option<Self> {
+        if nsecs >= NANOS_IN_SEC {
+            return None;
+        }
+        Some(Self(secs, nsecs, PhantomData))
+    }
+
     /// Create a [DateTime] from a unix timestamp
     pub const fn from_timestamp_secs(secs: i64) -> Option<Self> {
-        Some(Self(secs, PhantomData))
+        Self::from_timestamp(secs, 0)
+    }
+
+    /// Create a [DateTime] from a unix timestamp in milliseconds
+    pub const fn from_timestamp_millis(millis: i64) -> Option<Self> {
+        Self::from_timestamp(
+            millis.div_euclid(1000),
+            millis.rem_euclid(1000) as u32 * 1_000_000,
+        )
+    }
+
+    /// Create a [DateTime] from a [SystemTime] preserving its precision
+    pub fn from_system_time(time: SystemTime) -> Self {
+        match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(duration) => Self(
+                duration.as_secs() as i64,
+                duration.subsec_nanos(),
+                PhantomData,
+            ),
+            Err(err) => {
+                let duration = err.duration();
+                if duration.subsec_nanos() == 0 {
+                    Self(-(duration.as_secs() as i64), 0, PhantomData)
+                } else {
+                    Self(
+                        -(duration.as_secs() as i64) - 1,
+                        NANOS_IN_SEC - duration.subsec_nanos(),
+                        PhantomData,
+                    )
+                }
+            }
+        }
+    }
+
+    /// Convert into a [SystemTime] preserving the precision
+    pub fn into_system_time(self) -> SystemTime {
+        if self.0 >= 0 {
+            SystemTime::UNIX_EPOCH + Duration::new(self.0 as u64, self.1)
+        } else {
+            SystemTime::UNIX_EPOCH - Duration::from_secs(self.0.unsigned_abs())
+                + Duration::from_nanos(self.1 as u64)
+        }
     }
 
     /// Get the NaiveDateTime
     pub const fn naive_utc(&self) -> NaiveDateTime {
         #[allow(deprecated)]
-        NaiveDateTime::from_timestamp(self.0, 0).expect("Should be some")
+        NaiveDateTime::from_timestamp(self.0, self.1).expect("Should be some")
     }
 
     /// Get the unix timestamp of the date and time
@@ -36,6 +87,29 @@ impl<T: TimeZone> DateTime<T> {
         self.0
     }
 
+    /// Get the unix timestamp of the date and time in milliseconds
+    pub const fn timestamp_millis(&self) -> i64 {
+        self.0 * 1000 + (self.1 / 1_000_000) as i64
+    }
+
+    /// Get the unix timestamp of the date and time in nanoseconds, none when it doesn't fit
+    pub const fn timestamp_nanos_opt(&self) -> Option<i64> {
+        match self.0.checked_mul(NANOS_IN_SEC as i64) {
+            Some(nanos) => nanos.checked_add(self.1 as i64),
+            None => None,
+        }
+    }
+
+    /// Get the nanoseconds since the last whole second
+    pub const fn timestamp_subsec_nanos(&self) -> u32 {
+        self.1
+    }
+
+    /// Get the milliseconds since the last whole second
+    pub const fn timestamp_subsec_millis(&self) -> u32 {
+        self.1 / 1_000_000
+    }
+
     /// Format to RFC 2822 string
     pub fn to_rfc2822(&self) -> String {
         let (year, month, day) = timestamp_to_ymd(self.0);
@@ -63,7 +137,12 @@ impl<T: TimeZone> Add<Duration> for DateTime<T> {
     type Output = Self;
 
     fn add(self, duration: Duration) -> Self::Output {
-        Self(self.0 + duration.as_secs() as i64, PhantomData)
+        let nsecs = self.1 + duration.subsec_nanos();
+        Self(
+            self.0 + duration.as_secs() as i64 + (nsecs / NANOS_IN_SEC) as i64,
+            nsecs % NANOS_IN_SEC,
+            PhantomData,
+        )
     }
 }
 
@@ -71,7 +150,31 @@ impl<T: TimeZone> Sub<Duration> for DateTime<T> {
     type Output = Self;
 
     fn sub(self, duration: Duration) -> Self::Output {
-        Self(self.0 - duration.as_secs() as i64, PhantomData)
+        if self.1 >= duration.subsec_nanos() {
+            Self(
+                self.0 - duration.as_secs() as i64,
+                self.1 - duration.subsec_nanos(),
+                PhantomData,
+            )
+        } else {
+            Self(
+                self.0 - duration.as_secs() as i64 - 1,
+                self.1 + NANOS_IN_SEC - duration.subsec_nanos(),
+                PhantomData,
+            )
+        }
+    }
+}
+
+impl From<SystemTime> for DateTime<crate::Utc> {
+    fn from(time: SystemTime) -> Self {
+        Self::from_system_time(time)
+    }
+}
+
+impl<T: TimeZone> From<DateTime<T>> for SystemTime {
+    fn from(datetime: DateTime<T>) -> Self {
+        datetime.into_system_time()
     }
 }
 
@@ -97,21 +200,27 @@ impl<T: TimeZone> FromStr for DateTime<T> {
             .ok_or(ParseError)?
             .parse()
             .map_err(|_| ParseError)?;
-        let second: u32 = time_parts
-            .next()
-            .ok_or(ParseError)?
-            .parse()
-            .map_err(|_| ParseError)?;
+        let second_part = time_parts.next().ok_or(ParseError)?;
+        let (second, fraction) = match second_part.split_once('.') {
+            Some((second, fraction)) => (second, Some(fraction)),
+            None => (second_part, None),
+        };
+        let second: u32 = second.parse().map_err(|_| ParseError)?;
         if time_parts.next().is_some() || hour >= 24 || minute >= 60 || second >= 60 {
             return Err(ParseError);
         }
+        let nsecs = match fraction {
+            Some(fraction) => parse_fraction_nanos(fraction)?,
+            None => 0,
+        };
 
-        Self::from_timestamp_secs(
+        Self::from_timestamp(
             NaiveDate::from_str(date_part)?
                 .and_hms_opt(hour, minute, second)
                 .ok_or(ParseError)?
                 .and_utc()
                 .timestamp(),
+            nsecs,
         )
         .ok_or(ParseError)
     }
@@ -123,14 +232,16 @@ impl<T: TimeZone> Display for DateTime<T> {
         let day_sec = self.0.rem_euclid(SECS_IN_DAY);
         write!(
             f,
-            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
             year,
             month,
             day,
             day_sec / SECS_IN_HOUR,
             (day_sec % SECS_IN_HOUR) / SECS_IN_MIN,
             day_sec % SECS_IN_MIN
-        )
+        )?;
+        write_fraction_nanos(f, self.1)?;
+        f.write_str("Z")
     }
 }
 
@@ -220,6 +331,67 @@ mod test {
         assert_eq!(datetime.to_string(), "1969-12-20T10:13:20Z");
     }
 
+    #[test]
+    fn test_subsec_nanos() {
+        let datetime = DateTime::<Utc>::from_timestamp(1609459345, 123_000_000).unwrap();
+        assert_eq!(datetime.timestamp_subsec_millis(), 123);
+        assert_eq!(datetime.timestamp_millis(), 1609459345123);
+        assert_eq!(datetime.to_rfc3339(), "2021-01-01T00:02:25.123Z");
+        let datetime = DateTime::<Utc>::from_timestamp(1609459345, 123_456_000).unwrap();
+        assert_eq!(datetime.to_rfc3339(), "2021-01-01T00:02:25.123456Z");
+        let datetime = DateTime::<Utc>::from_timestamp(1609459345, 123_456_789).unwrap();
+        assert_eq!(datetime.to_rfc3339(), "2021-01-01T00:02:25.123456789Z");
+        assert_eq!(datetime.timestamp_nanos_opt(), Some(1609459345123456789));
+        assert!(DateTime::<Utc>::from_timestamp(0, 1_000_000_000).is_none());
+
+        let datetime = DateTime::<Utc>::from_timestamp_millis(-1).unwrap();
+        assert_eq!(datetime.timestamp(), -1);
+        assert_eq!(datetime.timestamp_subsec_millis(), 999);
+    }
+
+    #[test]
+    fn test_from_str_fraction() {
+        let datetime: DateTime<Utc> = "2021-01-01T00:02:25.123Z".parse().unwrap();
+        assert_eq!(datetime.timestamp(), 1609459345);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 123_000_000);
+        let datetime: DateTime<Utc> = "2021-01-01T00:02:25.123456789Z".parse().unwrap();
+        assert_eq!(datetime.timestamp_subsec_nanos(), 123_456_789);
+        let datetime: DateTime<Utc> = "2021-01-01T00:02:25.5Z".parse().unwrap();
+        assert_eq!(datetime.timestamp_subsec_nanos(), 500_000_000);
+
+        assert!("2021-01-01T00:02:25.Z".parse::<DateTime::<Utc>>().is_err());
+        assert!(
+            "2021-01-01T00:02:25.1234567890Z"
+                .parse::<DateTime::<Utc>>()
+                .is_err()
+        );
+        assert!(
+            "2021-01-01T00:02:25.12aZ"
+                .parse::<DateTime::<Utc>>()
+                .is_err()
+        );
+        assert!(
+            "2021-01-01T00:02:25.-1Z"
+                .parse::<DateTime::<Utc>>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_system_time() {
+        let time = SystemTime::UNIX_EPOCH + Duration::new(1609459345, 123_456_789);
+        let datetime = DateTime::<Utc>::from_system_time(time);
+        assert_eq!(datetime.timestamp(), 1609459345);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 123_456_789);
+        assert_eq!(datetime.into_system_time(), time);
+
+        let time = SystemTime::UNIX_EPOCH - Duration::new(1, 250_000_000);
+        let datetime = DateTime::<Utc>::from(time);
+        assert_eq!(datetime.timestamp(), -2);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 750_000_000);
+        assert_eq!(SystemTime::from(datetime), time);
+    }
+
     #[test]
     fn test_add_duration() {
         let datetime = DateTime::<Utc>::from_timestamp_secs(1609459200).unwrap();
@@ -232,5 +404,14 @@ mod test {
         let datetime = DateTime::<Utc>::from_timestamp_secs(1609459200).unwrap();
         let new_datetime = datetime - Duration::from_secs(1);
         assert_eq!(new_datetime.timestamp(), 1609459199);
+
+        let datetime = DateTime::<Utc>::from_timestamp(1609459200, 100_000_000).unwrap();
+        let new_datetime = datetime - Duration::from_millis(200);
+        assert_eq!(new_datetime.timestamp(), 1609459199);
+        assert_eq!(new_datetime.timestamp_subsec_millis(), 900);
+        assert_eq!(
+            (new_datetime + Duration::from_millis(200)).timestamp_subsec_millis(),
+            100
+        );
     }
 }
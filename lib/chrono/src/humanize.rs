@@ -0,0 +1,207 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::time::Duration;
+
+use crate::consts::{SECS_IN_DAY, SECS_IN_HOUR, SECS_IN_MIN};
+use crate::{DateTime, ParseError, TimeZone};
+
+const SECS_IN_WEEK: i64 = 7 * SECS_IN_DAY;
+const SECS_IN_MONTH: i64 = 30 * SECS_IN_DAY;
+const SECS_IN_YEAR: i64 = 365 * SECS_IN_DAY;
+
+// MARK: Locale
+/// Unit of a humanized relative time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    /// Minutes
+    Minute,
+    /// Hours
+    Hour,
+    /// Days
+    Day,
+    /// Months
+    Month,
+    /// Years
+    Year,
+}
+
+/// Locale used to format humanized relative times
+pub trait HumanizeLocale {
+    /// Format a time less than a minute away from the reference
+    fn now(&self) -> String;
+
+    /// Format `count` units in the past or in the future
+    fn relative(&self, count: i64, unit: TimeUnit, is_future: bool) -> String;
+}
+
+/// English humanize locale
+pub struct English;
+
+impl HumanizeLocale for English {
+    fn now(&self) -> String {
+        "just now".to_string()
+    }
+
+    fn relative(&self, count: i64, unit: TimeUnit, is_future: bool) -> String {
+        let unit = match unit {
+            TimeUnit::Minute => "minute",
+            TimeUnit::Hour => "hour",
+            TimeUnit::Day => "day",
+            TimeUnit::Month => "month",
+            TimeUnit::Year => "year",
+        };
+        let plural = if count == 1 { "" } else { "s" };
+        if is_future {
+            format!("in {count} {unit}{plural}")
+        } else {
+            format!("{count} {unit}{plural} ago")
+        }
+    }
+}
+
+// MARK: DateTime
+impl<T: TimeZone> DateTime<T> {
+    /// Format relative to now like "3 minutes ago" or "in 2 days"
+    #[cfg(feature = "now")]
+    pub fn humanize(&self) -> String {
+        self.humanize_since(
+            &DateTime::<T>::from_system_time(std::time::SystemTime::now()),
+            &English,
+        )
+    }
+
+    /// Format relative to `reference` with `locale`
+    pub fn humanize_since(&self, reference: &DateTime<T>, locale: &dyn HumanizeLocale) -> String {
+        let secs = self.timestamp() - reference.timestamp();
+        let is_future = secs > 0;
+        let secs = secs.abs();
+        let (count, unit) = if secs < SECS_IN_MIN {
+            return locale.now();
+        } else if secs < SECS_IN_HOUR {
+            (secs / SECS_IN_MIN, TimeUnit::Minute)
+        } else if secs < SECS_IN_DAY {
+            (secs / SECS_IN_HOUR, TimeUnit::Hour)
+        } else if secs < SECS_IN_MONTH {
+            (secs / SECS_IN_DAY, TimeUnit::Day)
+        } else if secs < SECS_IN_YEAR {
+            (secs / SECS_IN_MONTH, TimeUnit::Month)
+        } else {
+            (secs / SECS_IN_YEAR, TimeUnit::Year)
+        };
+        locale.relative(count, unit, is_future)
+    }
+}
+
+// MARK: Duration parsing
+/// Parse a human duration like "1h30m", "2d" or "1w 2d", supported units are `ms`, `s`, `m`,
+/// `h`, `d` and `w`
+pub fn parse_duration(s: &str) -> Result<Duration, ParseError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseError);
+    }
+
+    let mut duration = Duration::ZERO;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !c.is_ascii_digit()).ok_or(ParseError)?;
+        let count: u64 = rest[..number_len].parse().map_err(|_| ParseError)?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let part = match &rest[..unit_len] {
+            "ms" => Duration::from_millis(count),
+            "s" => Duration::from_secs(count),
+            "m" => Duration::from_secs(count.checked_mul(SECS_IN_MIN as u64).ok_or(ParseError)?),
+            "h" => Duration::from_secs(count.checked_mul(SECS_IN_HOUR as u64).ok_or(ParseError)?),
+            "d" => Duration::from_secs(count.checked_mul(SECS_IN_DAY as u64).ok_or(ParseError)?),
+            "w" => Duration::from_secs(count.checked_mul(SECS_IN_WEEK as u64).ok_or(ParseError)?),
+            _ => return Err(ParseError),
+        };
+        duration = duration.checked_add(part).ok_or(ParseError)?;
+        rest = rest[unit_len..].trim_start();
+    }
+    Ok(duration)
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Utc;
+
+    #[test]
+    fn test_humanize_since() {
+        let reference = DateTime::<Utc>::from_timestamp_secs(1_700_000_000).unwrap();
+        let humanize = |secs: i64| {
+            DateTime::<Utc>::from_timestamp_secs(1_700_000_000 + secs)
+                .unwrap()
+                .humanize_since(&reference, &English)
+        };
+        assert_eq!(humanize(0), "just now");
+        assert_eq!(humanize(-59), "just now");
+        assert_eq!(humanize(-60), "1 minute ago");
+        assert_eq!(humanize(-3 * 60 - 30), "3 minutes ago");
+        assert_eq!(humanize(2 * 3600), "in 2 hours");
+        assert_eq!(humanize(2 * 86400), "in 2 days");
+        assert_eq!(humanize(-45 * 86400), "1 month ago");
+        assert_eq!(humanize(3 * 365 * 86400), "in 3 years");
+    }
+
+    #[test]
+    fn test_humanize_locale() {
+        struct Dutch;
+        impl HumanizeLocale for Dutch {
+            fn now(&self) -> String {
+                "zojuist".to_string()
+            }
+            fn relative(&self, count: i64, unit: TimeUnit, is_future: bool) -> String {
+                assert_eq!(unit, TimeUnit::Minute);
+                if is_future {
+                    format!("over {count} minuten")
+                } else {
+                    format!("{count} minuten geleden")
+                }
+            }
+        }
+
+        let reference = DateTime::<Utc>::from_timestamp_secs(0).unwrap();
+        let datetime = DateTime::<Utc>::from_timestamp_secs(-300).unwrap();
+        assert_eq!(
+            datetime.humanize_since(&reference, &Dutch),
+            "5 minuten geleden"
+        );
+        assert_eq!(reference.humanize_since(&reference, &Dutch), "zojuist");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(
+            parse_duration("1h30m").unwrap(),
+            Duration::from_secs(90 * 60)
+        );
+        assert_eq!(
+            parse_duration("2d").unwrap(),
+            Duration::from_secs(2 * 86400)
+        );
+        assert_eq!(
+            parse_duration(" 1w 2d ").unwrap(),
+            Duration::from_secs(9 * 86400)
+        );
+        assert_eq!(
+            parse_duration("1s500ms").unwrap(),
+            Duration::from_millis(1500)
+        );
+
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("5y").is_err());
+        assert!(parse_duration("1h-30m").is_err());
+    }
+}
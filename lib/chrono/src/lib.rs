@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -10,12 +10,14 @@ use std::error::Error;
 use std::fmt::{self, Display, Formatter};
 
 pub use datetime::DateTime;
+pub use humanize::{English, HumanizeLocale, TimeUnit, parse_duration};
 pub use naive::date::NaiveDate;
 pub use naive::datetime::NaiveDateTime;
 pub use timezone::{TimeZone, Utc};
 
 mod consts;
 mod datetime;
+mod humanize;
 mod naive;
 mod timezone;
 mod utils;
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -9,21 +9,23 @@ use std::ops::{Add, Sub};
 use std::str::FromStr;
 use std::time::Duration;
 
-use crate::consts::{SECS_IN_DAY, SECS_IN_HOUR, SECS_IN_MIN};
-use crate::utils::timestamp_to_ymd;
+use crate::consts::{NANOS_IN_SEC, SECS_IN_DAY, SECS_IN_HOUR, SECS_IN_MIN};
+use crate::utils::{parse_fraction_nanos, timestamp_to_ymd, write_fraction_nanos};
 use crate::{DateTime, NaiveDate, ParseError, Utc};
 
 // MARK: NaiveDateTime
 /// A DateTime without timezone
 #[derive(Clone, Copy, PartialEq, Eq)]
-pub struct NaiveDateTime(i64);
+pub struct NaiveDateTime(i64, u32);
 
 impl NaiveDateTime {
-    /// Create a [NaiveDateTime] from a unix timestamp
+    /// Create a [NaiveDateTime] from a unix timestamp and nanoseconds
     #[deprecated]
-    #[allow(unused_variables)]
     pub const fn from_timestamp(secs: i64, nsecs: u32) -> Option<Self> {
-        Some(Self(secs))
+        if nsecs >= NANOS_IN_SEC {
+            return None;
+        }
+        Some(Self(secs, nsecs))
     }
 
     /// Get the [NaiveDate] of the date and time
@@ -33,7 +35,7 @@ impl NaiveDateTime {
 
     /// Get the [DateTime] in UTC timezone
     pub const fn and_utc(&self) -> DateTime<Utc> {
-        DateTime::<Utc>::from_timestamp_secs(self.0).expect("Should be some")
+        DateTime::<Utc>::from_timestamp(self.0, self.1).expect("Should be some")
     }
 
     /// Get the unix timestamp of the [NaiveDateTime]
@@ -41,14 +43,19 @@ impl NaiveDateTime {
     pub const fn timestamp(&self) -> i64 {
         self.0
     }
+
+    /// Get the nanoseconds since the last whole second
+    #[deprecated]
+    pub const fn timestamp_subsec_nanos(&self) -> u32 {
+        self.1
+    }
 }
 
 impl Add<Duration> for NaiveDateTime {
     type Output = Self;
 
     fn add(self, duration: Duration) -> Self::Output {
-        #[allow(deprecated)]
-        Self::from_timestamp(self.0 + duration.as_secs() as i64, 0).expect("Should be some")
+        (self.and_utc() + duration).naive_utc()
     }
 }
 
@@ -56,8 +63,7 @@ impl Sub<Duration> for NaiveDateTime {
     type Output = Self;
 
     fn sub(self, duration: Duration) -> Self::Output {
-        #[allow(deprecated)]
-        Self::from_timestamp(self.0 - duration.as_secs() as i64, 0).expect("Should be some")
+        (self.and_utc() - duration).naive_utc()
     }
 }
 
@@ -83,18 +89,24 @@ impl FromStr for NaiveDateTime {
             .ok_or(ParseError)?
             .parse()
             .map_err(|_| ParseError)?;
-        let second: u32 = time_parts
-            .next()
-            .ok_or(ParseError)?
-            .parse()
-            .map_err(|_| ParseError)?;
+        let second_part = time_parts.next().ok_or(ParseError)?;
+        let (second, fraction) = match second_part.split_once('.') {
+            Some((second, fraction)) => (second, Some(fraction)),
+            None => (second_part, None),
+        };
+        let second: u32 = second.parse().map_err(|_| ParseError)?;
         if time_parts.next().is_some() || hour >= 24 || minute >= 60 || second >= 60 {
             return Err(ParseError);
         }
+        let nsecs = match fraction {
+            Some(fraction) => parse_fraction_nanos(fraction)?,
+            None => 0,
+        };
 
-        NaiveDate::from_str(date_part)?
+        let datetime = NaiveDate::from_str(date_part)?
             .and_hms_opt(hour, minute, second)
-            .ok_or(ParseError)
+            .ok_or(ParseError)?;
+        Ok(Self(datetime.0, nsecs))
     }
 }
 
@@ -111,7 +123,8 @@ impl Display for NaiveDateTime {
             day_sec / SECS_IN_HOUR,
             (day_sec % SECS_IN_HOUR) / SECS_IN_MIN,
             day_sec % SECS_IN_MIN
-        )
+        )?;
+        write_fraction_nanos(f, self.1)
     }
 }
 
@@ -150,6 +163,22 @@ mod test {
         assert_eq!(datetime.timestamp(), 1609459345);
     }
 
+    #[test]
+    fn test_nanos() {
+        let datetime = NaiveDateTime::from_timestamp(1609459345, 500_000_000).unwrap();
+        assert_eq!(datetime.timestamp_subsec_nanos(), 500_000_000);
+        assert_eq!(datetime.and_utc().timestamp_subsec_nanos(), 500_000_000);
+        assert_eq!(datetime.and_utc().naive_utc(), datetime);
+        assert!(NaiveDateTime::from_timestamp(0, 1_000_000_000).is_none());
+
+        let datetime = datetime + Duration::from_millis(600);
+        assert_eq!(datetime.timestamp(), 1609459346);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 100_000_000);
+        let datetime = datetime - Duration::from_millis(200);
+        assert_eq!(datetime.timestamp(), 1609459345);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 900_000_000);
+    }
+
     #[test]
     fn test_from_str() {
         let datetime: NaiveDateTime = "2019-02-28 12:00:00".parse().unwrap();
@@ -159,7 +188,11 @@ mod test {
         let datetime: NaiveDateTime = "1969-12-20 10:13:20".parse().unwrap();
         assert_eq!(datetime.timestamp(), -1000000);
 
+        let datetime: NaiveDateTime = "2019-02-28 12:00:00.250".parse().unwrap();
+        assert_eq!(datetime.timestamp_subsec_nanos(), 250_000_000);
+
         assert!("invalid-datetime".parse::<NaiveDateTime>().is_err());
+        assert!("2019-02-28 12:00:00.".parse::<NaiveDateTime>().is_err());
         assert!("2020-02-29 12:00:00Z".parse::<NaiveDateTime>().is_err());
         assert!("2020-02-29 12:00:00T".parse::<NaiveDateTime>().is_err());
         assert!("2020-02-30 12:00:00".parse::<NaiveDateTime>().is_err());
@@ -178,6 +211,8 @@ mod test {
         assert_eq!(datetime.to_string(), "2020-02-29 12:00:00");
         let datetime = NaiveDateTime::from_timestamp(-1000000, 0).unwrap();
         assert_eq!(datetime.to_string(), "1969-12-20 10:13:20");
+        let datetime = NaiveDateTime::from_timestamp(1551355200, 123_000_000).unwrap();
+        assert_eq!(datetime.to_string(), "2019-02-28 12:00:00.123");
     }
 
     #[test]
@@ -1,11 +1,9 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
-use std::time::SystemTime;
-
 /// A timezone
 pub trait TimeZone {}
 
@@ -19,9 +17,6 @@ impl Utc {
     /// Get the current [DateTime] in UTC timezone
     #[cfg(feature = "now")]
     pub fn now() -> crate::DateTime<Self> {
-        let now = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .expect("Time went backwards");
-        crate::DateTime::<Self>::from_timestamp_secs(now.as_secs() as i64).expect("Should be some")
+        crate::DateTime::<Self>::from_system_time(std::time::SystemTime::now())
     }
 }
@@ -1,9 +1,12 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
+use std::fmt::{self, Formatter};
+
+use crate::ParseError;
 use crate::consts::{
     DAYS_IN_MONTHS, DAYS_IN_MONTHS_LEAP, DAYS_IN_YEAR, DAYS_IN_YEAR_LEAP, EPOCH_YEAR, SECS_IN_DAY,
 };
@@ -52,3 +55,25 @@ pub(crate) fn timestamp_to_ymd(timestamp: i64) -> (u32, u32, u32) {
 
     (year, month, day as u32 + 1)
 }
+
+pub(crate) fn parse_fraction_nanos(fraction: &str) -> Result<u32, ParseError> {
+    if fraction.is_empty() || fraction.len() > 9 || !fraction.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(ParseError);
+    }
+    let nanos: u32 = fraction.parse().map_err(|_| ParseError)?;
+    Ok(nanos * 10_u32.pow(9 - fraction.len() as u32))
+}
+
+/// Write the shortest of millisecond, microsecond or nanosecond precision
+pub(crate) fn write_fraction_nanos(f: &mut Formatter<'_>, nsecs: u32) -> fmt::Result {
+    if nsecs.is_multiple_of(1_000_000) {
+        if nsecs != 0 {
+            write!(f, ".{:03}", nsecs / 1_000_000)?;
+        }
+        Ok(())
+    } else if nsecs.is_multiple_of(1_000) {
+        write!(f, ".{:06}", nsecs / 1_000)
+    } else {
+        write!(f, ".{nsecs:09}")
+    }
+}
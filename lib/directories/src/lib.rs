@@ -8,6 +8,7 @@
 
 #![allow(unused)]
 
+use std::io;
 use std::path::PathBuf;
 
 #[cfg(not(any(unix, windows)))]
@@ -87,6 +88,18 @@ pub(crate) mod windows {
         data3: 0x4FCF,
         data4: [0x9D, 0x55, 0x7B, 0x8E, 0x7F, 0x15, 0x70, 0x91],
     };
+    pub(crate) const FOLDERID_DESKTOP: Guid = Guid {
+        data1: 0xB4BFCC3A,
+        data2: 0xDB2C,
+        data3: 0x424C,
+        data4: [0xB0, 0x29, 0x7F, 0xE9, 0x9A, 0x87, 0xC6, 0x41],
+    };
+    pub(crate) const FOLDERID_TEMPLATES: Guid = Guid {
+        data1: 0xA63293E8,
+        data2: 0x664E,
+        data3: 0x48DB,
+        data4: [0xA0, 0x79, 0xDF, 0x75, 0x9E, 0x05, 0x09, 0xF7],
+    };
     pub(crate) const FOLDERID_ROAMING_APPDATA: Guid = Guid {
         data1: 0x3EB685DB,
         data2: 0x65F9,
@@ -115,11 +128,86 @@ pub(crate) mod windows {
     }
 }
 
+// MARK: BaseDirs
+/// Provides paths of user-invisible standard directories
+///
+/// When created with [`BaseDirs::with_prefix`] every directory can be overridden with an env
+/// variable, like `MYAPP_HOME_DIR`, `MYAPP_EXECUTABLE_DIR` and `MYAPP_RUNTIME_DIR`
+pub struct BaseDirs {
+    home_dir: PathBuf,
+    env_prefix: Option<String>,
+}
+
+impl BaseDirs {
+    /// Creates a BaseDirs struct which holds the paths to user-invisible directories
+    pub fn new() -> Option<Self> {
+        let home_dir = std::env::home_dir()?;
+        Some(Self {
+            home_dir,
+            env_prefix: None,
+        })
+    }
+
+    /// Creates a BaseDirs struct whose directories can be overridden with env variables for an
+    /// application prefix like `myapp`
+    pub fn with_prefix(prefix: &str) -> io::Result<Self> {
+        let env_prefix = env_prefix(prefix);
+        Ok(Self {
+            home_dir: home_dir(&env_prefix)?,
+            env_prefix: Some(env_prefix),
+        })
+    }
+
+    fn env_override(&self, name: &str) -> Option<PathBuf> {
+        env_override(self.env_prefix.as_deref()?, name)
+    }
+
+    /// Returns the path to the user’s home directory
+    pub fn home_dir(&self) -> PathBuf {
+        self.home_dir.clone()
+    }
+
+    /// Returns the path to the user’s executable directory, only available on Linux
+    pub fn executable_dir(&self) -> Option<PathBuf> {
+        if let Some(executable_dir) = self.env_override("EXECUTABLE") {
+            return Some(executable_dir);
+        }
+        cfg_select! {
+            all(unix, not(target_os = "macos")) => {
+                let xdg_bin = std::env::var("XDG_BIN_HOME").map(PathBuf::from);
+                Some(xdg_bin.unwrap_or_else(|_| self.home_dir.join(".local").join("bin")))
+            }
+            _ => {
+                None
+            }
+        }
+    }
+
+    /// Returns the path to the user’s runtime directory, only available on Linux
+    pub fn runtime_dir(&self) -> Option<PathBuf> {
+        if let Some(runtime_dir) = self.env_override("RUNTIME") {
+            return Some(runtime_dir);
+        }
+        cfg_select! {
+            all(unix, not(target_os = "macos")) => {
+                std::env::var("XDG_RUNTIME_DIR").ok().map(PathBuf::from)
+            }
+            _ => {
+                None
+            }
+        }
+    }
+}
+
 // MARK: ProjectDirs
 /// Computes the location of cache, config or data directories for a specific application
+///
+/// Every directory can be overridden with an env variable named after the application, like
+/// `MYAPP_CACHE_DIR`, `MYAPP_CONFIG_DIR` and `MYAPP_RUNTIME_DIR` for the application `myapp`
 pub struct ProjectDirs {
     home_dir: PathBuf,
     project_path: PathBuf,
+    env_prefix: String,
 }
 
 impl ProjectDirs {
@@ -127,6 +215,7 @@ impl ProjectDirs {
     pub fn from(qualifier: &str, organization: &str, application: &str) -> Option<Self> {
         Some(Self {
             home_dir: std::env::home_dir()?,
+            env_prefix: env_prefix(application),
             project_path: {
                 cfg_select! {
                     all(unix, not(target_os = "macos")) => {
@@ -150,12 +239,37 @@ impl ProjectDirs {
     pub fn from_path(project_path: PathBuf) -> Option<Self> {
         Some(Self {
             home_dir: std::env::home_dir()?,
+            env_prefix: env_prefix(&project_path.file_name()?.to_string_lossy()),
             project_path,
         })
     }
 
+    /// Creates a ProjectDirs struct for an application prefix like `myapp` and creates its
+    /// directories, so they can be used right away
+    pub fn with_prefix(prefix: &str) -> io::Result<Self> {
+        let env_prefix = env_prefix(prefix);
+        let project_dirs = Self {
+            home_dir: home_dir(&env_prefix)?,
+            env_prefix,
+            project_path: PathBuf::from(prefix),
+        };
+        std::fs::create_dir_all(project_dirs.cache_dir())?;
+        std::fs::create_dir_all(project_dirs.config_dir())?;
+        if let Some(runtime_dir) = project_dirs.runtime_dir() {
+            std::fs::create_dir_all(runtime_dir)?;
+        }
+        Ok(project_dirs)
+    }
+
+    fn env_override(&self, name: &str) -> Option<PathBuf> {
+        env_override(&self.env_prefix, name)
+    }
+
     /// Returns the path to the project’s cache directory
     pub fn cache_dir(&self) -> PathBuf {
+        if let Some(cache_dir) = self.env_override("CACHE") {
+            return cache_dir;
+        }
         let cache_dir = {
             cfg_select! {
                 all(unix, not(target_os = "macos")) => {
@@ -178,6 +292,9 @@ impl ProjectDirs {
 
     /// Returns the path to the project’s config directory
     pub fn config_dir(&self) -> PathBuf {
+        if let Some(config_dir) = self.env_override("CONFIG") {
+            return config_dir;
+        }
         let config_dir = {
             cfg_select! {
                 all(unix, not(target_os = "macos")) => {
@@ -197,23 +314,94 @@ impl ProjectDirs {
         };
         config_dir.join(&self.project_path)
     }
+
+    /// Returns the path to the project’s runtime directory, only available on Linux
+    pub fn runtime_dir(&self) -> Option<PathBuf> {
+        if let Some(runtime_dir) = self.env_override("RUNTIME") {
+            return Some(runtime_dir);
+        }
+        cfg_select! {
+            all(unix, not(target_os = "macos")) => {
+                let xdg_runtime = std::env::var("XDG_RUNTIME_DIR").ok().map(PathBuf::from)?;
+                Some(xdg_runtime.join(&self.project_path))
+            }
+            _ => {
+                None
+            }
+        }
+    }
+}
+
+// Env variable prefix for an application name, like `MY_APP` for `my-app`
+fn env_prefix(application: &str) -> String {
+    application
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn env_override(env_prefix: &str, name: &str) -> Option<PathBuf> {
+    std::env::var_os(format!("{env_prefix}_{name}_DIR"))
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+fn home_dir(env_prefix: &str) -> io::Result<PathBuf> {
+    env_override(env_prefix, "HOME")
+        .or_else(std::env::home_dir)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Can't find home directory"))
 }
 
 // MARK: UserDirs
 /// Provides paths of user-facing standard directories
+///
+/// When created with [`UserDirs::with_prefix`] every directory can be overridden with an env
+/// variable, like `MYAPP_HOME_DIR`, `MYAPP_AUDIO_DIR` and `MYAPP_DESKTOP_DIR`
 pub struct UserDirs {
     home_dir: PathBuf,
+    env_prefix: Option<String>,
 }
 
 impl UserDirs {
     /// Creates a UserDirs struct which holds the paths to user-facing directories
     pub fn new() -> Option<Self> {
         let home_dir = std::env::home_dir()?;
-        Some(Self { home_dir })
+        Some(Self {
+            home_dir,
+            env_prefix: None,
+        })
+    }
+
+    /// Creates a UserDirs struct whose directories can be overridden with env variables for an
+    /// application prefix like `myapp`
+    pub fn with_prefix(prefix: &str) -> io::Result<Self> {
+        let env_prefix = env_prefix(prefix);
+        Ok(Self {
+            home_dir: home_dir(&env_prefix)?,
+            env_prefix: Some(env_prefix),
+        })
+    }
+
+    fn env_override(&self, name: &str) -> Option<PathBuf> {
+        env_override(self.env_prefix.as_deref()?, name)
+    }
+
+    /// Returns the path to the user’s home directory
+    pub fn home_dir(&self) -> PathBuf {
+        self.home_dir.clone()
     }
 
     /// Returns the path to the user’s audio directory
     pub fn audio_dir(&self) -> PathBuf {
+        if let Some(audio_dir) = self.env_override("AUDIO") {
+            return audio_dir;
+        }
         cfg_select! {
             all(unix, not(target_os = "macos")) => {
                 let xdg_music = std::env::var("XDG_MUSIC_DIR").map(PathBuf::from);
@@ -230,4 +418,133 @@ impl UserDirs {
             }
         }
     }
+
+    /// Returns the path to the user’s desktop directory
+    pub fn desktop_dir(&self) -> PathBuf {
+        if let Some(desktop_dir) = self.env_override("DESKTOP") {
+            return desktop_dir;
+        }
+        cfg_select! {
+            all(unix, not(target_os = "macos")) => {
+                let xdg_desktop = std::env::var("XDG_DESKTOP_DIR").map(PathBuf::from);
+                xdg_desktop.unwrap_or_else(|_| self.home_dir.join("Desktop"))
+            }
+            target_os = "macos" => {
+                self.home_dir.join("Desktop")
+            }
+            windows => {
+                windows::get_known_folder_path(&windows::FOLDERID_DESKTOP)
+            }
+            _ => {
+                unreachable!()
+            }
+        }
+    }
+
+    /// Returns the path to the user’s font directory, not available on Windows
+    pub fn font_dir(&self) -> Option<PathBuf> {
+        if let Some(font_dir) = self.env_override("FONT") {
+            return Some(font_dir);
+        }
+        cfg_select! {
+            all(unix, not(target_os = "macos")) => {
+                let xdg_data = std::env::var("XDG_DATA_HOME").map(PathBuf::from);
+                let data_dir =
+                    xdg_data.unwrap_or_else(|_| self.home_dir.join(".local").join("share"));
+                Some(data_dir.join("fonts"))
+            }
+            target_os = "macos" => {
+                Some(self.home_dir.join("Library").join("Fonts"))
+            }
+            _ => {
+                None
+            }
+        }
+    }
+
+    /// Returns the path to the user’s template directory, not available on macOS
+    pub fn template_dir(&self) -> Option<PathBuf> {
+        if let Some(template_dir) = self.env_override("TEMPLATE") {
+            return Some(template_dir);
+        }
+        cfg_select! {
+            all(unix, not(target_os = "macos")) => {
+                let xdg_templates = std::env::var("XDG_TEMPLATES_DIR").map(PathBuf::from);
+                Some(xdg_templates.unwrap_or_else(|_| self.home_dir.join("Templates")))
+            }
+            windows => {
+                Some(windows::get_known_folder_path(&windows::FOLDERID_TEMPLATES))
+            }
+            _ => {
+                None
+            }
+        }
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_env_prefix() {
+        assert_eq!(env_prefix("TodoApp"), "TODOAPP");
+        assert_eq!(env_prefix("my-app.v2"), "MY_APP_V2");
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_with_prefix_env_override() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("directories-test-{}", std::process::id()));
+        // SAFETY: The env variables are unique to this test
+        unsafe {
+            std::env::set_var("DIRECTORIES_TEST_CACHE_DIR", temp_dir.join("cache"));
+            std::env::set_var("DIRECTORIES_TEST_CONFIG_DIR", temp_dir.join("config"));
+            std::env::set_var("DIRECTORIES_TEST_RUNTIME_DIR", temp_dir.join("runtime"));
+        }
+
+        let project_dirs = ProjectDirs::with_prefix("directories-test").unwrap();
+        assert_eq!(project_dirs.cache_dir(), temp_dir.join("cache"));
+        assert_eq!(project_dirs.config_dir(), temp_dir.join("config"));
+        assert_eq!(project_dirs.runtime_dir(), Some(temp_dir.join("runtime")));
+        assert!(temp_dir.join("cache").is_dir());
+        assert!(temp_dir.join("config").is_dir());
+        assert!(temp_dir.join("runtime").is_dir());
+        std::fs::remove_dir_all(temp_dir).unwrap();
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_with_prefix_create_error() {
+        let file = std::env::temp_dir().join(format!("directories-file-{}", std::process::id()));
+        std::fs::write(&file, "").unwrap();
+        // SAFETY: The env variable is unique to this test
+        unsafe { std::env::set_var("DIRECTORIES_FILE_TEST_CACHE_DIR", file.join("cache")) };
+
+        assert!(ProjectDirs::with_prefix("directories-file-test").is_err());
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    #[allow(unsafe_code)]
+    fn test_base_and_user_dirs_env_override() {
+        // SAFETY: The env variables are unique to this test
+        unsafe {
+            std::env::set_var("DIRECTORIES_DIRS_TEST_HOME_DIR", "/sandbox");
+            std::env::set_var("DIRECTORIES_DIRS_TEST_RUNTIME_DIR", "/sandbox/run");
+            std::env::set_var("DIRECTORIES_DIRS_TEST_AUDIO_DIR", "/sandbox/audio");
+            std::env::set_var("DIRECTORIES_DIRS_TEST_FONT_DIR", "/sandbox/fonts");
+        }
+
+        let base_dirs = BaseDirs::with_prefix("directories-dirs-test").unwrap();
+        assert_eq!(base_dirs.home_dir(), PathBuf::from("/sandbox"));
+        assert_eq!(base_dirs.runtime_dir(), Some(PathBuf::from("/sandbox/run")));
+
+        let user_dirs = UserDirs::with_prefix("directories-dirs-test").unwrap();
+        assert_eq!(user_dirs.home_dir(), PathBuf::from("/sandbox"));
+        assert_eq!(user_dirs.audio_dir(), PathBuf::from("/sandbox/audio"));
+        assert_eq!(user_dirs.font_dir(), Some(PathBuf::from("/sandbox/fonts")));
+    }
 }
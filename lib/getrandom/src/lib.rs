@@ -7,18 +7,50 @@
 //! A minimal replacement for the [getrandom](https://crates.io/crates/getrandom) crate
 
 use std::io::Error;
+use std::mem::MaybeUninit;
+use std::slice;
 
 /// Fill buffer with crypto random bytes
 #[allow(unsafe_code)]
 pub fn fill(buf: &mut [u8]) -> Result<(), Error> {
+    // SAFETY: buf is a valid mutable byte slice of buf.len() bytes.
+    unsafe { fill_raw(buf.as_mut_ptr(), buf.len()) }
+}
+
+/// Fill possibly uninitialized buffer with crypto random bytes, returns the initialized buffer
+#[allow(unsafe_code)]
+pub fn fill_uninit(buf: &mut [MaybeUninit<u8>]) -> Result<&mut [u8], Error> {
+    // SAFETY: MaybeUninit<u8> has the same layout as u8 and fill_raw only writes to the buffer.
+    unsafe { fill_raw(buf.as_mut_ptr().cast(), buf.len())? };
+    // SAFETY: fill_raw succeeded, so all buf.len() bytes are initialized.
+    Ok(unsafe { slice::from_raw_parts_mut(buf.as_mut_ptr().cast(), buf.len()) })
+}
+
+/// Generate a crypto random u32
+pub fn u32() -> Result<u32, Error> {
+    let mut bytes = [0; 4];
+    fill(&mut bytes)?;
+    Ok(u32::from_ne_bytes(bytes))
+}
+
+/// Generate a crypto random u64
+pub fn u64() -> Result<u64, Error> {
+    let mut bytes = [0; 8];
+    fill(&mut bytes)?;
+    Ok(u64::from_ne_bytes(bytes))
+}
+
+// SAFETY: ptr must be valid for writes of len bytes, which may be uninitialized.
+#[allow(unsafe_code)]
+unsafe fn fill_raw(ptr: *mut u8, len: usize) -> Result<(), Error> {
     cfg_select! {
         any(target_os = "macos", target_os = "openbsd") => {
             unsafe extern "C" {
                 fn getentropy(buf: *mut u8, buflen: usize) -> i32;
             }
-            for chunk in buf.chunks_mut(256) {
-                // SAFETY: chunk is a valid mutable byte slice with length <= 256, satisfying getentropy's requirements.
-                if unsafe { getentropy(chunk.as_mut_ptr(), chunk.len()) } != 0 {
+            for offset in (0..len).step_by(256) {
+                // SAFETY: the chunk lies within ptr..ptr + len and is at most 256 bytes, satisfying getentropy's requirements.
+                if unsafe { getentropy(ptr.add(offset), (len - offset).min(256)) } != 0 {
                     return Err(Error::other("getentropy failed"));
                 }
             }
@@ -45,15 +77,21 @@ pub fn fill(buf: &mut [u8]) -> Result<(), Error> {
             }
             static GETRANDOM: std::sync::LazyLock<Option<GetrandomFn>> = std::sync::LazyLock::new(resolve_getrandom);
             if let Some(getrandom) = *GETRANDOM {
-                // SAFETY: buf is a valid mutable byte slice; getrandom was resolved and has the correct signature.
-                let n = unsafe { getrandom(buf.as_mut_ptr(), buf.len(), 0) };
-                if n >= 0 && n as usize == buf.len() {
+                // SAFETY: ptr is valid for writes of len bytes; getrandom was resolved and has the correct signature.
+                let n = unsafe { getrandom(ptr, len, 0) };
+                if n >= 0 && n as usize == len {
                     return Ok(());
                 }
                 // Fall through to /dev/urandom if getrandom fails or returns ENOSYS
             }
 
             use std::io::Read;
+            // Zero the buffer first so it can be passed to read_exact as an initialized slice
+            // SAFETY: ptr is valid for writes of len bytes.
+            let buf = unsafe {
+                std::ptr::write_bytes(ptr, 0, len);
+                slice::from_raw_parts_mut(ptr, len)
+            };
             let mut file = std::fs::File::open("/dev/urandom")
                 .map_err(|_| Error::other("failed to open /dev/urandom"))?;
             file.read_exact(buf)
@@ -74,11 +112,15 @@ pub fn fill(buf: &mut [u8]) -> Result<(), Error> {
             unsafe extern "system" {
                 fn ProcessPrng(pbData: *mut u8, cbData: usize) -> i32;
             }
-            // SAFETY: buf is a valid mutable byte slice; ProcessPrng is a documented Windows API that fills it.
-            if unsafe { ProcessPrng(buf.as_mut_ptr(), buf.len()) } == 0 {
+            // SAFETY: ptr is valid for writes of len bytes; ProcessPrng is a documented Windows API that fills it.
+            if unsafe { ProcessPrng(ptr, len) } == 0 {
                 return Err(Error::other("ProcessPrng failed"));
             }
         }
+        all(target_arch = "wasm32", target_os = "unknown") => {
+            // SAFETY: ptr is valid for writes of len bytes.
+            unsafe { wasm::fill_raw(ptr, len)? };
+        }
         _ => {
             compile_error!("Unsupported platform");
         }
@@ -86,6 +128,46 @@ pub fn fill(buf: &mut [u8]) -> Result<(), Error> {
     Ok(())
 }
 
+// MARK: Wasm
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod wasm {
+    use std::io::Error;
+
+    use js_sys::Uint8Array;
+    use wasm_bindgen::prelude::*;
+
+    // getRandomValues fills at most 65536 bytes per call
+    const MAX_CHUNK_LEN: usize = 65536;
+
+    #[wasm_bindgen]
+    extern "C" {
+        type Crypto;
+
+        #[wasm_bindgen(method, js_name = getRandomValues, catch)]
+        fn get_random_values(this: &Crypto, buf: &Uint8Array) -> Result<(), JsValue>;
+    }
+
+    // SAFETY: ptr must be valid for writes of len bytes, which may be uninitialized.
+    #[allow(unsafe_code)]
+    pub(crate) unsafe fn fill_raw(ptr: *mut u8, len: usize) -> Result<(), Error> {
+        let crypto = js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("crypto"))
+            .ok()
+            .filter(|crypto| crypto.is_object())
+            .ok_or_else(|| Error::other("crypto.getRandomValues is unavailable"))?
+            .unchecked_into::<Crypto>();
+        let buf = Uint8Array::new_with_length(len.min(MAX_CHUNK_LEN) as u32);
+        for offset in (0..len).step_by(MAX_CHUNK_LEN) {
+            let chunk = buf.subarray(0, (len - offset).min(MAX_CHUNK_LEN) as u32);
+            crypto
+                .get_random_values(&chunk)
+                .map_err(|_| Error::other("crypto.getRandomValues failed"))?;
+            // SAFETY: the chunk lies within ptr..ptr + len.
+            unsafe { chunk.raw_copy_to_ptr(ptr.add(offset)) };
+        }
+        Ok(())
+    }
+}
+
 // MARK: Tests
 #[cfg(test)]
 mod test {
@@ -101,4 +183,20 @@ mod test {
 
         assert_ne!(buf1, buf2);
     }
+
+    #[test]
+    fn test_fill_uninit() {
+        let mut buf = [MaybeUninit::<u8>::uninit(); 300];
+        let bytes = fill_uninit(&mut buf).unwrap();
+        assert_eq!(bytes.len(), 300);
+        assert!(bytes.iter().any(|&b| b != 0));
+
+        assert!(fill_uninit(&mut []).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_ne!((u32().unwrap(), u32().unwrap()), (0, 0));
+        assert_ne!(u64().unwrap(), u64().unwrap());
+    }
 }
@@ -13,6 +13,7 @@ fn main() {
             cc::Build::new()
                 .file("sqlite3/sqlite3.c")
                 .define("SQLITE_ENABLE_COLUMN_METADATA", None)
+                .define("SQLITE_ENABLE_DBSTAT_VTAB", None)
                 .define("SQLITE_ENABLE_FTS5", None)
                 .compile("sqlite3");
         }
@@ -66,6 +66,8 @@ unsafe extern "C" {
     pub fn sqlite3_changes(db: *mut sqlite3) -> i32;
     pub fn sqlite3_last_insert_rowid(db: *mut sqlite3) -> i64;
     pub fn sqlite3_errmsg(db: *mut sqlite3) -> *const c_char;
+    pub fn sqlite3_interrupt(db: *mut sqlite3);
+    pub fn sqlite3_get_autocommit(db: *mut sqlite3) -> c_int;
     pub fn sqlite3_close(db: *mut sqlite3) -> c_int;
 
     // sqlite3_stmt
@@ -75,6 +77,8 @@ unsafe extern "C" {
     pub fn sqlite3_reset(pStmt: *mut sqlite3_stmt) -> c_int;
     pub fn sqlite3_finalize(pStmt: *mut sqlite3_stmt) -> c_int;
 
+    pub fn sqlite3_bind_parameter_count(pStmt: *mut sqlite3_stmt) -> c_int;
+    pub fn sqlite3_bind_parameter_name(pStmt: *mut sqlite3_stmt, i: c_int) -> *const c_char;
     pub fn sqlite3_bind_parameter_index(pStmt: *mut sqlite3_stmt, zName: *const c_char) -> c_int;
     pub fn sqlite3_bind_null(pStmt: *mut sqlite3_stmt, i: c_int) -> c_int;
     pub fn sqlite3_bind_int64(pStmt: *mut sqlite3_stmt, i: c_int, value: i64) -> c_int;
@@ -9,70 +9,155 @@
 #![allow(non_camel_case_types)]
 #![allow(clippy::upper_case_acronyms)]
 
-use std::net::IpAddr;
+use std::io::{Error, ErrorKind};
+use std::net::{IpAddr, UdpSocket};
 
-/// Returns the local IPv4 address of the machine.
+/// A network interface address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkInterface {
+    /// Interface name
+    pub name: String,
+    /// IPv4 or IPv6 address
+    pub addr: IpAddr,
+    /// Network mask of the address
+    pub netmask: Option<IpAddr>,
+    /// Whether the interface is a loopback interface
+    pub is_loopback: bool,
+    /// Whether the interface is up and running
+    pub is_up: bool,
+}
+
+/// Returns the local IPv4 address of the machine, preferring the address of the interface that
+/// carries the default route.
+pub fn local_ip() -> Result<IpAddr, Error> {
+    find_local_ip(
+        default_route_ip("0.0.0.0:0", "8.8.8.8:80"),
+        |addr| matches!(addr, IpAddr::V4(ip) if !ip.is_link_local()),
+    )
+}
+
+/// Returns the local IPv6 address of the machine, preferring the address of the interface that
+/// carries the default route.
+pub fn local_ipv6() -> Result<IpAddr, Error> {
+    find_local_ip(
+        default_route_ip("[::]:0", "[2001:4860:4860::8888]:80"),
+        |addr| matches!(addr, IpAddr::V6(ip) if !ip.is_unicast_link_local()),
+    )
+}
+
+fn default_route_ip(bind_addr: &str, remote_addr: &str) -> Option<IpAddr> {
+    // Connecting a UDP socket sends no packets, but lets the OS pick the source address of the
+    // route to the remote address, which is the default route for a public address
+    let socket = UdpSocket::bind(bind_addr).ok()?;
+    socket.connect(remote_addr).ok()?;
+    socket.local_addr().ok().map(|addr| addr.ip())
+}
+
+fn find_local_ip(
+    default_route_ip: Option<IpAddr>,
+    filter: impl Fn(&IpAddr) -> bool,
+) -> Result<IpAddr, Error> {
+    let netifs = list_network_interfaces()?;
+    let candidates = || {
+        netifs
+            .iter()
+            .filter(|netif| netif.is_up && !netif.is_loopback && filter(&netif.addr))
+    };
+    // Fall back to the first matching interface when there is no default route
+    candidates()
+        .find(|netif| Some(netif.addr) == default_route_ip)
+        .or_else(|| candidates().next())
+        .map(|netif| netif.addr)
+        .ok_or_else(|| Error::new(ErrorKind::NotFound, "No local IP address found"))
+}
+
+/// Returns the interface name and address of every IPv4 and IPv6 address of the machine.
+pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
+    Ok(list_network_interfaces()?
+        .into_iter()
+        .map(|netif| (netif.name, netif.addr))
+        .collect())
+}
+
+/// Returns every IPv4 and IPv6 address of the machine with its interface metadata, one entry
+/// per address.
 #[allow(unsafe_code)]
-pub fn local_ip() -> Result<IpAddr, std::io::Error> {
+pub fn list_network_interfaces() -> Result<Vec<NetworkInterface>, Error> {
     cfg_select! {
         unix => {
+            use std::ffi::CStr;
+            use std::net::{Ipv4Addr, Ipv6Addr};
+
+            // SAFETY: addr must be null or point to a valid sockaddr of its address family.
+            unsafe fn sockaddr_to_ip(addr: *const libc::sockaddr) -> Option<IpAddr> {
+                if addr.is_null() {
+                    return None;
+                }
+                // SAFETY: addr is non-null and points to a valid sockaddr; the family check
+                // ensures the full sockaddr_in / sockaddr_in6 is readable.
+                unsafe {
+                    match (*addr).sa_family as i32 {
+                        libc::AF_INET => {
+                            let addr_in = (addr as *const libc::sockaddr_in).read_unaligned();
+                            Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(addr_in.sin_addr.s_addr))))
+                        }
+                        libc::AF_INET6 => {
+                            let addr_in6 = (addr as *const libc::sockaddr_in6).read_unaligned();
+                            Some(IpAddr::V6(Ipv6Addr::from(addr_in6.sin6_addr.s6_addr)))
+                        }
+                        _ => None,
+                    }
+                }
+            }
+
             let mut ifaddrs: *mut libc::ifaddrs = std::ptr::null_mut();
             // SAFETY: ifaddrs is a valid out-pointer; getifaddrs will initialize it on success.
             if unsafe { libc::getifaddrs(&mut ifaddrs) } != 0 {
-                return Err(std::io::Error::last_os_error());
+                return Err(Error::last_os_error());
             }
-            let mut result = Err(std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "No valid network interface found",
-            ));
 
+            let mut netifs = Vec::new();
             let mut current = ifaddrs;
             while !current.is_null() {
                 // SAFETY: current is non-null (checked in the while condition), pointing to a valid ifaddrs node.
-                let addr = unsafe { (*current).ifa_addr };
-                // SAFETY: current is non-null, as above.
-                let flags = unsafe { (*current).ifa_flags };
-                if !addr.is_null()
-                    // SAFETY: addr is non-null (checked above), pointing to a valid sockaddr.
-                    && unsafe { (*addr).sa_family } as u32 == libc::AF_INET as u32
-                    && (flags & libc::IFF_LOOPBACK as u32 == 0)
-                    && (flags & libc::IFF_UP as u32 != 0)
-                    && (flags & libc::IFF_RUNNING as u32 != 0)
-                {
-                    let mut addr_in = std::mem::MaybeUninit::<libc::sockaddr_in>::uninit();
-                    // SAFETY: addr points to a valid sockaddr_in (verified via AF_INET family check); addr_in is a correctly sized MaybeUninit target.
-                    unsafe {
-                        std::ptr::copy_nonoverlapping(
-                            addr as *const u8,
-                            addr_in.as_mut_ptr() as *mut u8,
-                            size_of::<libc::sockaddr_in>(),
-                        )
-                    };
-                    // SAFETY: addr_in was fully initialized by copy_nonoverlapping above.
-                    let addr_in = unsafe { addr_in.assume_init() };
-                    result = Ok(IpAddr::V4(std::net::Ipv4Addr::from(u32::from_be(
-                        addr_in.sin_addr.s_addr,
-                    ))));
-                    break;
+                let ifaddr = unsafe { &*current };
+                // SAFETY: ifa_addr is null or a valid sockaddr owned by the ifaddrs list.
+                if let Some(addr) = unsafe { sockaddr_to_ip(ifaddr.ifa_addr) } {
+                    let flags = ifaddr.ifa_flags;
+                    netifs.push(NetworkInterface {
+                        // SAFETY: ifa_name is a valid null-terminated C string owned by the ifaddrs list.
+                        name: unsafe { CStr::from_ptr(ifaddr.ifa_name) }
+                            .to_string_lossy()
+                            .into_owned(),
+                        addr,
+                        // SAFETY: ifa_netmask is null or a valid sockaddr owned by the ifaddrs list.
+                        netmask: unsafe { sockaddr_to_ip(ifaddr.ifa_netmask) },
+                        is_loopback: flags & libc::IFF_LOOPBACK as u32 != 0,
+                        is_up: flags & libc::IFF_UP as u32 != 0
+                            && flags & libc::IFF_RUNNING as u32 != 0,
+                    });
                 }
-                // SAFETY: current is non-null; ifa_next is a valid pointer to the next node or null.
-                current = unsafe { (*current).ifa_next };
+                current = ifaddr.ifa_next;
             }
 
             // SAFETY: ifaddrs was obtained from a successful getifaddrs call and has not been freed yet.
             unsafe { libc::freeifaddrs(ifaddrs) };
-            result
+            Ok(netifs)
         }
         windows => {
             use std::ffi::c_void;
+            use std::net::{Ipv4Addr, Ipv6Addr};
 
             const AF_UNSPEC: u32 = 0;
-            const AF_INET: u32 = 2;
+            const AF_INET: u16 = 2;
+            const AF_INET6: u16 = 23;
             const GAA_FLAG_SKIP_ANYCAST: u32 = 0x0002;
             const GAA_FLAG_SKIP_MULTICAST: u32 = 0x0004;
             const GAA_FLAG_SKIP_DNS_SERVER: u32 = 0x0008;
             const ERROR_BUFFER_OVERFLOW: u32 = 111;
             const NO_ERROR: u32 = 0;
+            const IF_TYPE_SOFTWARE_LOOPBACK: u32 = 24;
+            const IF_OPER_STATUS_UP: i32 = 1;
 
             #[repr(C)]
             struct SOCKET_ADDRESS {
@@ -99,6 +184,15 @@ pub fn local_ip() -> Result<IpAddr, std::io::Error> {
                 s_addr: u32,
             }
 
+            #[repr(C)]
+            struct SOCKADDR_IN6 {
+                sin6_family: u16,
+                sin6_port: u16,
+                sin6_flowinfo: u32,
+                sin6_addr: [u8; 16],
+                sin6_scope_id: u32,
+            }
+
             #[repr(C)]
             struct IP_ADAPTER_UNICAST_ADDRESS {
                 length: u32,
@@ -121,6 +215,18 @@ pub fn local_ip() -> Result<IpAddr, std::io::Error> {
                 next: *mut IP_ADAPTER_ADDRESSES,
                 adapter_name: *mut i8,
                 first_unicast_address: *mut IP_ADAPTER_UNICAST_ADDRESS,
+                first_anycast_address: *mut c_void,
+                first_multicast_address: *mut c_void,
+                first_dns_server_address: *mut c_void,
+                dns_suffix: *mut u16,
+                description: *mut u16,
+                friendly_name: *mut u16,
+                physical_address: [u8; 8],
+                physical_address_length: u32,
+                flags: u32,
+                mtu: u32,
+                if_type: u32,
+                oper_status: i32,
                 // ... more fields exist but we don't need them
             }
 
@@ -148,13 +254,13 @@ pub fn local_ip() -> Result<IpAddr, std::io::Error> {
                 )
             };
             if result != ERROR_BUFFER_OVERFLOW && result != NO_ERROR {
-                return Err(std::io::Error::from_raw_os_error(result as i32));
+                return Err(Error::from_raw_os_error(result as i32));
             }
 
-            // Second call to get the actual data
-            let mut buffer: Vec<u8> = vec![0u8; buffer_size as usize];
+            // Second call to get the actual data, u64 elements keep the adapter structs aligned
+            let mut buffer: Vec<u64> = vec![0; (buffer_size as usize).div_ceil(size_of::<u64>())];
             let adapter_addresses = buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES;
-            // SAFETY: buffer is sized to buffer_size bytes as reported by the previous call; adapter_addresses points into it.
+            // SAFETY: buffer is at least buffer_size bytes as reported by the previous call; adapter_addresses points into it.
             let result = unsafe {
                 GetAdaptersAddresses(
                     AF_UNSPEC,
@@ -165,15 +271,24 @@ pub fn local_ip() -> Result<IpAddr, std::io::Error> {
                 )
             };
             if result != NO_ERROR {
-                return Err(std::io::Error::from_raw_os_error(result as i32));
+                return Err(Error::from_raw_os_error(result as i32));
             }
 
             // Iterate through adapters
+            let mut netifs = Vec::new();
             let mut current_adapter = adapter_addresses;
-            let mut best_ipv4_addr: Option<IpAddr> = None;
             while !current_adapter.is_null() {
                 // SAFETY: current_adapter is non-null (checked in while condition), pointing to a valid IP_ADAPTER_ADDRESSES node.
                 let adapter = unsafe { &*current_adapter };
+                let name = if adapter.friendly_name.is_null() {
+                    String::new()
+                } else {
+                    // SAFETY: friendly_name is a valid null-terminated UTF-16 string inside buffer.
+                    unsafe {
+                        let len = (0..).take_while(|&i| *adapter.friendly_name.add(i) != 0).count();
+                        String::from_utf16_lossy(std::slice::from_raw_parts(adapter.friendly_name, len))
+                    }
+                };
 
                 // Iterate through unicast addresses
                 let mut current_address = adapter.first_unicast_address;
@@ -181,31 +296,50 @@ pub fn local_ip() -> Result<IpAddr, std::io::Error> {
                     // SAFETY: current_address is non-null (checked in while condition), pointing to a valid IP_ADAPTER_UNICAST_ADDRESS node.
                     let unicast_addr = unsafe { &*current_address };
                     let socket_addr = unicast_addr.address.lp_socket_addr;
-                    if !socket_addr.is_null() {
-                        // SAFETY: socket_addr is non-null (checked above); sa_family is the first field of SOCKADDR.
-                        let family = unsafe { (*socket_addr).sa_family } as u32;
-                        if family == AF_INET {
-                            let sockaddr_in = socket_addr as *const SOCKADDR_IN;
-                            // SAFETY: family confirmed this points to a valid SOCKADDR_IN, so reading sin_addr is sound.
-                            let ip_bytes = unsafe { (*sockaddr_in).sin_addr.s_addr.to_ne_bytes() };
-                            if ip_bytes[0] != 127 && !(ip_bytes[0] == 169 && ip_bytes[1] == 254) {
-                                best_ipv4_addr = Some(IpAddr::from(ip_bytes));
+                    let prefix_len = unicast_addr.on_link_prefix_length as u32;
+                    let addr = if socket_addr.is_null() {
+                        None
+                    } else {
+                        // SAFETY: socket_addr is non-null; the family check ensures it points to a
+                        // valid SOCKADDR_IN or SOCKADDR_IN6.
+                        unsafe {
+                            match (*socket_addr).sa_family {
+                                AF_INET => {
+                                    let sockaddr_in = &*(socket_addr as *const SOCKADDR_IN);
+                                    Some((
+                                        IpAddr::from(sockaddr_in.sin_addr.s_addr.to_ne_bytes()),
+                                        IpAddr::V4(Ipv4Addr::from(
+                                            u32::MAX.checked_shl(32 - prefix_len).unwrap_or(0),
+                                        )),
+                                    ))
+                                }
+                                AF_INET6 => {
+                                    let sockaddr_in6 = &*(socket_addr as *const SOCKADDR_IN6);
+                                    Some((
+                                        IpAddr::from(sockaddr_in6.sin6_addr),
+                                        IpAddr::V6(Ipv6Addr::from(
+                                            u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0),
+                                        )),
+                                    ))
+                                }
+                                _ => None,
                             }
                         }
+                    };
+                    if let Some((addr, netmask)) = addr {
+                        netifs.push(NetworkInterface {
+                            name: name.clone(),
+                            addr,
+                            netmask: Some(netmask),
+                            is_loopback: adapter.if_type == IF_TYPE_SOFTWARE_LOOPBACK,
+                            is_up: adapter.oper_status == IF_OPER_STATUS_UP,
+                        });
                     }
-                    // SAFETY: current_address is non-null; next is a valid pointer to the next node or null.
-                    current_address = unsafe { (*current_address).next };
+                    current_address = unicast_addr.next;
                 }
                 current_adapter = adapter.next;
             }
-
-            if let Some(ipv4) = best_ipv4_addr {
-                return Ok(ipv4);
-            }
-            Err(std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                "No local IP address found",
-            ))
+            Ok(netifs)
         }
         _ => {
             compile_error!("Unsupported platform")
@@ -223,4 +357,42 @@ mod test {
         let ip = local_ip().expect("Failed to get local IP address");
         assert!(matches!(ip, IpAddr::V4(_)));
     }
+
+    #[test]
+    fn test_local_ip_default_route() {
+        // Without a default route local_ip falls back to the first matching interface
+        if let Some(ip) = default_route_ip("0.0.0.0:0", "8.8.8.8:80") {
+            assert_eq!(local_ip().unwrap(), ip);
+        }
+    }
+
+    #[test]
+    fn test_local_ipv6() {
+        // Not every machine has a non link-local IPv6 address
+        if let Ok(ip) = local_ipv6() {
+            assert!(matches!(ip, IpAddr::V6(ip) if !ip.is_loopback()));
+        }
+    }
+
+    #[test]
+    fn test_list_network_interfaces() {
+        let netifs = list_network_interfaces().expect("Failed to list network interfaces");
+        let loopback = netifs
+            .iter()
+            .find(|netif| netif.addr == IpAddr::from([127, 0, 0, 1]))
+            .expect("Loopback address should be listed");
+        assert!(loopback.is_loopback);
+        assert!(!loopback.name.is_empty());
+        assert_eq!(loopback.netmask, Some(IpAddr::from([255, 0, 0, 0])));
+
+        let local = local_ip().expect("Failed to get local IP address");
+        assert!(
+            netifs
+                .iter()
+                .any(|netif| netif.addr == local && !netif.is_loopback)
+        );
+
+        let netifas = list_afinet_netifas().expect("Failed to list network interfaces");
+        assert_eq!(netifas.len(), netifs.len());
+    }
 }
@@ -8,101 +8,281 @@
 
 #![allow(missing_docs)]
 
+use std::borrow::Cow;
+use std::error::Error;
 use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
 // MARK: Mime
 /// A MIME type
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct Mime {
-    type_: &'static str,
-    subtype: &'static str,
-    suffix: Option<&'static str>,
+    essence: Cow<'static, str>,
+    slash: usize,
+    plus: Option<usize>,
+    params: Vec<(String, String)>,
 }
 
 impl Mime {
     /// Create a new `Mime` instance
-    pub const fn new(
-        type_: &'static str,
-        subtype: &'static str,
-        suffix: Option<&'static str>,
-    ) -> Self {
+    pub fn new(type_: &str, subtype: &str, suffix: Option<&str>) -> Self {
+        let mut essence = format!("{type_}/{subtype}");
+        let plus = suffix.map(|suffix| {
+            let plus = essence.len();
+            essence.push('+');
+            essence.push_str(suffix);
+            plus
+        });
         Self {
-            type_,
-            subtype,
-            suffix,
+            essence: Cow::Owned(essence.to_ascii_lowercase()),
+            slash: type_.len(),
+            plus,
+            params: Vec::new(),
         }
     }
 
+    /// Create a new `Mime` instance from a static lowercase essence string like `image/svg+xml`
+    pub const fn from_static(essence: &'static str) -> Self {
+        let bytes = essence.as_bytes();
+        let mut slash = bytes.len();
+        let mut plus = None;
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'/' && slash == bytes.len() {
+                slash = i;
+            } else if bytes[i] == b'+' && slash != bytes.len() {
+                plus = Some(i);
+            }
+            i += 1;
+        }
+        Self {
+            essence: Cow::Borrowed(essence),
+            slash,
+            plus,
+            params: Vec::new(),
+        }
+    }
+
+    /// Parse a `Mime` from a string like `text/html; charset=utf-8`
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        Self::from_str(s)
+    }
+
     /// Type
-    pub const fn type_(&self) -> &str {
-        self.type_
+    pub fn type_(&self) -> &str {
+        &self.essence[..self.slash]
     }
 
     /// Subtype
-    pub const fn subtype(&self) -> &str {
-        self.subtype
+    pub fn subtype(&self) -> &str {
+        &self.essence[self.slash + 1..self.plus.unwrap_or(self.essence.len())]
     }
 
     /// Suffix
-    pub const fn suffix(&self) -> Option<&str> {
-        self.suffix
+    pub fn suffix(&self) -> Option<&str> {
+        self.plus.map(|plus| &self.essence[plus + 1..])
+    }
+
+    /// Essence, the MIME type without parameters
+    pub fn essence_str(&self) -> &str {
+        &self.essence
+    }
+
+    /// Iterate over all parameters
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Get a parameter value by case insensitive name
+    pub fn get_param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param_name, _)| param_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get the charset parameter
+    pub fn charset(&self) -> Option<&str> {
+        self.get_param("charset")
+    }
+
+    /// Set a parameter, replacing an existing parameter with the same name
+    pub fn with_param(mut self, name: &str, value: impl Into<String>) -> Self {
+        let name = name.to_ascii_lowercase();
+        let value = value.into();
+        if let Some(param) = self.params.iter_mut().find(|(n, _)| *n == name) {
+            param.1 = value;
+        } else {
+            self.params.push((name, value));
+        }
+        self
+    }
+}
+
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes().all(|c| {
+            c.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&c)
+        })
+}
+
+fn param_value_eq(name: &str, a: &str, b: &str) -> bool {
+    if name == "charset" {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+impl PartialEq for Mime {
+    fn eq(&self, other: &Self) -> bool {
+        self.essence == other.essence
+            && self.params.len() == other.params.len()
+            && self.params.iter().all(|(name, value)| {
+                other
+                    .get_param(name)
+                    .is_some_and(|other_value| param_value_eq(name, value, other_value))
+            })
+    }
+}
+
+impl Eq for Mime {}
+
+impl FromStr for Mime {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (essence, mut rest) = s.split_once(';').unwrap_or((s, ""));
+        let (type_, subtype) = essence.trim().split_once('/').ok_or(ParseError)?;
+        let (subtype, suffix) = match subtype.split_once('+') {
+            Some((subtype, suffix)) => (subtype, Some(suffix)),
+            None => (subtype, None),
+        };
+        if !is_token(type_) || !is_token(subtype) || suffix.is_some_and(|s| !is_token(s)) {
+            return Err(ParseError);
+        }
+        let mut mime = Self::new(type_, subtype, suffix);
+
+        // Parse parameters
+        loop {
+            rest = rest.trim_start_matches([' ', '\t', ';']);
+            if rest.is_empty() {
+                break;
+            }
+            let (name, value) = rest.split_once('=').ok_or(ParseError)?;
+            let name = name.trim();
+            if !is_token(name) {
+                return Err(ParseError);
+            }
+            let value = value.trim_start();
+            let value = if let Some(quoted) = value.strip_prefix('"') {
+                let mut unescaped = String::new();
+                let mut chars = quoted.char_indices();
+                let mut end = None;
+                while let Some((i, c)) = chars.next() {
+                    match c {
+                        '\\' => unescaped.push(chars.next().ok_or(ParseError)?.1),
+                        '"' => {
+                            end = Some(i);
+                            break;
+                        }
+                        _ => unescaped.push(c),
+                    }
+                }
+                rest = &quoted[end.ok_or(ParseError)? + 1..];
+                unescaped
+            } else {
+                let (value, next) = value.split_once(';').unwrap_or((value, ""));
+                rest = next;
+                let value = value.trim_end();
+                if !is_token(value) {
+                    return Err(ParseError);
+                }
+                value.to_string()
+            };
+            mime = mime.with_param(name, value);
+        }
+        Ok(mime)
     }
 }
 
 impl Display for Mime {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}/{}", self.type_, self.subtype)?;
-        if let Some(suffix) = self.suffix {
-            write!(f, "+{suffix}")?;
+        write!(f, "{}", self.essence)?;
+        for (name, value) in &self.params {
+            if is_token(value) {
+                write!(f, "; {name}={value}")?;
+            } else {
+                write!(
+                    f,
+                    "; {name}=\"{}\"",
+                    value.replace('\\', "\\\\").replace('"', "\\\"")
+                )?;
+            }
         }
         Ok(())
     }
 }
 
+// MARK: ParseError
+/// Mime parse error
+#[derive(Debug)]
+pub struct ParseError;
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Mime parse error")
+    }
+}
+
+impl Error for ParseError {}
+
 // MARK: Common MIME types
-pub const APPLICATION_GZIP: Mime = Mime::new("application", "gzip", None);
-pub const APPLICATION_JAVASCRIPT: Mime = Mime::new("application", "javascript", None);
-pub const APPLICATION_JSON: Mime = Mime::new("application", "json", None);
-pub const APPLICATION_MANIFEST_JSON: Mime = Mime::new("application", "manifest+json", None);
-pub const APPLICATION_OCTET_STREAM: Mime = Mime::new("application", "octet-stream", None);
-pub const APPLICATION_PDF: Mime = Mime::new("application", "pdf", None);
-pub const APPLICATION_WASM: Mime = Mime::new("application", "wasm", None);
-pub const APPLICATION_X_TAR: Mime = Mime::new("application", "x-tar", None);
-pub const APPLICATION_YAML: Mime = Mime::new("application", "yaml", None);
-pub const APPLICATION_ZIP: Mime = Mime::new("application", "zip", None);
-
-pub const AUDIO_AAC: Mime = Mime::new("audio", "aac", None);
-pub const AUDIO_FLAC: Mime = Mime::new("audio", "flac", None);
-pub const AUDIO_MPEG: Mime = Mime::new("audio", "mpeg", None);
-pub const AUDIO_OGG: Mime = Mime::new("audio", "ogg", None);
-pub const AUDIO_OPUS: Mime = Mime::new("audio", "opus", None);
-pub const AUDIO_WAV: Mime = Mime::new("audio", "wav", None);
-
-pub const FONT_OTF: Mime = Mime::new("font", "otf", None);
-pub const FONT_TTF: Mime = Mime::new("font", "ttf", None);
-pub const FONT_WOFF: Mime = Mime::new("font", "woff", None);
-pub const FONT_WOFF2: Mime = Mime::new("font", "woff2", None);
-
-pub const IMAGE_AVIF: Mime = Mime::new("image", "avif", None);
-pub const IMAGE_BMP: Mime = Mime::new("image", "bmp", None);
-pub const IMAGE_GIF: Mime = Mime::new("image", "gif", None);
-pub const IMAGE_JPEG: Mime = Mime::new("image", "jpeg", None);
-pub const IMAGE_PNG: Mime = Mime::new("image", "png", None);
-pub const IMAGE_SVG: Mime = Mime::new("image", "svg", Some("xml"));
-pub const IMAGE_TIFF: Mime = Mime::new("image", "tiff", None);
-pub const IMAGE_WEBP: Mime = Mime::new("image", "webp", None);
-pub const IMAGE_X_ICON: Mime = Mime::new("image", "x-icon", None);
-
-pub const TEXT_CSV: Mime = Mime::new("text", "csv", None);
-pub const TEXT_CSS: Mime = Mime::new("text", "css", None);
-pub const TEXT_HTML: Mime = Mime::new("text", "html", None);
-pub const TEXT_MARKDOWN: Mime = Mime::new("text", "markdown", None);
-pub const TEXT_PLAIN: Mime = Mime::new("text", "plain", None);
-pub const TEXT_XML: Mime = Mime::new("text", "xml", None);
-
-pub const VIDEO_MP4: Mime = Mime::new("video", "mp4", None);
-pub const VIDEO_OGG: Mime = Mime::new("video", "ogg", None);
-pub const VIDEO_WEBM: Mime = Mime::new("video", "webm", None);
+pub const APPLICATION_GZIP: Mime = Mime::from_static("application/gzip");
+pub const APPLICATION_JAVASCRIPT: Mime = Mime::from_static("application/javascript");
+pub const APPLICATION_JSON: Mime = Mime::from_static("application/json");
+pub const APPLICATION_MANIFEST_JSON: Mime = Mime::from_static("application/manifest+json");
+pub const APPLICATION_OCTET_STREAM: Mime = Mime::from_static("application/octet-stream");
+pub const APPLICATION_PDF: Mime = Mime::from_static("application/pdf");
+pub const APPLICATION_WASM: Mime = Mime::from_static("application/wasm");
+pub const APPLICATION_X_TAR: Mime = Mime::from_static("application/x-tar");
+pub const APPLICATION_YAML: Mime = Mime::from_static("application/yaml");
+pub const APPLICATION_ZIP: Mime = Mime::from_static("application/zip");
+
+pub const AUDIO_AAC: Mime = Mime::from_static("audio/aac");
+pub const AUDIO_FLAC: Mime = Mime::from_static("audio/flac");
+pub const AUDIO_MPEG: Mime = Mime::from_static("audio/mpeg");
+pub const AUDIO_OGG: Mime = Mime::from_static("audio/ogg");
+pub const AUDIO_OPUS: Mime = Mime::from_static("audio/opus");
+pub const AUDIO_WAV: Mime = Mime::from_static("audio/wav");
+
+pub const FONT_OTF: Mime = Mime::from_static("font/otf");
+pub const FONT_TTF: Mime = Mime::from_static("font/ttf");
+pub const FONT_WOFF: Mime = Mime::from_static("font/woff");
+pub const FONT_WOFF2: Mime = Mime::from_static("font/woff2");
+
+pub const IMAGE_AVIF: Mime = Mime::from_static("image/avif");
+pub const IMAGE_BMP: Mime = Mime::from_static("image/bmp");
+pub const IMAGE_GIF: Mime = Mime::from_static("image/gif");
+pub const IMAGE_JPEG: Mime = Mime::from_static("image/jpeg");
+pub const IMAGE_PNG: Mime = Mime::from_static("image/png");
+pub const IMAGE_SVG: Mime = Mime::from_static("image/svg+xml");
+pub const IMAGE_TIFF: Mime = Mime::from_static("image/tiff");
+pub const IMAGE_WEBP: Mime = Mime::from_static("image/webp");
+pub const IMAGE_X_ICON: Mime = Mime::from_static("image/x-icon");
+
+pub const TEXT_CSV: Mime = Mime::from_static("text/csv");
+pub const TEXT_CSS: Mime = Mime::from_static("text/css");
+pub const TEXT_HTML: Mime = Mime::from_static("text/html");
+pub const TEXT_MARKDOWN: Mime = Mime::from_static("text/markdown");
+pub const TEXT_PLAIN: Mime = Mime::from_static("text/plain");
+pub const TEXT_XML: Mime = Mime::from_static("text/xml");
+
+pub const VIDEO_MP4: Mime = Mime::from_static("video/mp4");
+pub const VIDEO_OGG: Mime = Mime::from_static("video/ogg");
+pub const VIDEO_WEBM: Mime = Mime::from_static("video/webm");
 
 // MARK: Tests
 #[cfg(test)]
@@ -124,4 +304,58 @@ mod test {
         assert_eq!(IMAGE_SVG.to_string(), "image/svg+xml");
         assert_eq!(TEXT_XML.to_string(), "text/xml");
     }
+
+    #[test]
+    fn test_accessors() {
+        assert_eq!(IMAGE_SVG.type_(), "image");
+        assert_eq!(IMAGE_SVG.subtype(), "svg");
+        assert_eq!(IMAGE_SVG.suffix(), Some("xml"));
+        assert_eq!(IMAGE_SVG.essence_str(), "image/svg+xml");
+        assert_eq!(IMAGE_SVG, Mime::new("image", "svg", Some("xml")));
+        assert_eq!(TEXT_HTML.suffix(), None);
+        assert_eq!(TEXT_HTML.charset(), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let mime = Mime::parse("text/html; charset=utf-8").unwrap();
+        assert_eq!(mime.essence_str(), "text/html");
+        assert_eq!(mime.get_param("charset"), Some("utf-8"));
+        assert_eq!(mime.get_param("CHARSET"), Some("utf-8"));
+        assert_eq!(mime.charset(), Some("utf-8"));
+        assert_eq!(mime.to_string(), "text/html; charset=utf-8");
+
+        let mime: Mime = "Application/Vnd.Api+JSON".parse().unwrap();
+        assert_eq!(mime.type_(), "application");
+        assert_eq!(mime.subtype(), "vnd.api");
+        assert_eq!(mime.suffix(), Some("json"));
+        assert_eq!(mime.params().count(), 0);
+
+        let mime = Mime::parse("multipart/form-data; boundary=\"a; b\\\"c\";x=1").unwrap();
+        assert_eq!(mime.get_param("boundary"), Some("a; b\"c"));
+        assert_eq!(mime.get_param("x"), Some("1"));
+        assert_eq!(
+            mime.to_string(),
+            "multipart/form-data; boundary=\"a; b\\\"c\"; x=1"
+        );
+
+        assert!(Mime::parse("").is_err());
+        assert!(Mime::parse("text").is_err());
+        assert!(Mime::parse("text/").is_err());
+        assert!(Mime::parse("text/html; charset").is_err());
+        assert!(Mime::parse("text/html; charset=\"utf-8").is_err());
+    }
+
+    #[test]
+    fn test_eq_ignores_param_order() {
+        let a = Mime::parse("text/plain; charset=UTF-8; format=flowed").unwrap();
+        let b = Mime::parse("text/plain;format=flowed;charset=utf-8").unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, TEXT_PLAIN);
+        assert_ne!(a, Mime::parse("text/plain; charset=utf-8; format=fixed").unwrap());
+        assert_eq!(
+            TEXT_PLAIN.with_param("charset", "utf-8"),
+            Mime::parse("text/plain; charset=utf-8").unwrap()
+        );
+    }
 }
@@ -10,76 +10,104 @@ use std::path::Path;
 
 use mime::Mime;
 
+use crate::mime_types::MIME_TYPES;
+
+mod mime_types;
+
 /// Create a new `MimeGuess` from a file path
 pub fn from_path(path: impl AsRef<Path>) -> MimeGuess {
     MimeGuess::from_path(path.as_ref())
 }
 
+/// Create a new `MimeGuess` from a file extension
+pub fn from_ext(extension: &str) -> MimeGuess {
+    MimeGuess::from_ext(extension)
+}
+
+/// Get all known file extensions for a MIME type
+pub fn extensions_for(mime: &Mime) -> Vec<&'static str> {
+    MIME_TYPES
+        .iter()
+        .filter(|(_, mimes)| mimes.contains(&mime.essence_str()))
+        .map(|(extension, _)| *extension)
+        .collect()
+}
+
+// MARK: MimeGuess
 /// MimeGuess
+#[derive(Debug, Clone, Copy)]
 pub struct MimeGuess {
-    extension: String,
+    mimes: &'static [&'static str],
 }
 
 impl MimeGuess {
     /// Create a new `MimeGuess` from a file path
     pub fn from_path(path: impl AsRef<Path>) -> Self {
-        let extension = path
-            .as_ref()
-            .extension()
-            .and_then(|ext| ext.to_str())
-            .map_or_else(String::new, |ext| ext.to_string());
-        Self { extension }
+        Self::from_ext(
+            path.as_ref()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Create a new `MimeGuess` from a file extension
+    pub fn from_ext(extension: &str) -> Self {
+        let extension = extension.to_ascii_lowercase();
+        let mimes = MIME_TYPES
+            .binary_search_by(|(ext, _)| (*ext).cmp(extension.as_str()))
+            .map_or(&[] as &[&str], |index| MIME_TYPES[index].1);
+        Self { mimes }
+    }
+
+    /// Returns true if no MIME types are guessed
+    pub const fn is_empty(&self) -> bool {
+        self.mimes.is_empty()
+    }
+
+    /// Returns the number of guessed MIME types
+    pub const fn count(&self) -> usize {
+        self.mimes.len()
+    }
+
+    /// Get the first guessed MIME type
+    pub fn first(&self) -> Option<Mime> {
+        self.mimes.first().map(|mime| Mime::from_static(mime))
+    }
+
+    /// Get the first guessed MIME type as a string
+    pub const fn first_raw(&self) -> Option<&'static str> {
+        self.mimes.first().copied()
     }
 
     /// Guess MIME type or return `application/octet-stream` if unknown
     pub fn first_or_octet_stream(&self) -> Mime {
-        match self.extension.as_str() {
-            // Text
-            "html" | "htm" => mime::TEXT_HTML,
-            "css" => mime::TEXT_CSS,
-            "js" | "mjs" => mime::APPLICATION_JAVASCRIPT,
-            "json" => mime::APPLICATION_JSON,
-            "xml" => mime::TEXT_XML,
-            "txt" => mime::TEXT_PLAIN,
-            "csv" => mime::TEXT_CSV,
-            "md" | "markdown" => mime::TEXT_MARKDOWN,
-            "yaml" | "yml" => mime::APPLICATION_YAML,
-            // Web
-            "wasm" => mime::APPLICATION_WASM,
-            "webmanifest" => mime::APPLICATION_MANIFEST_JSON,
-            // Images
-            "png" => mime::IMAGE_PNG,
-            "jpg" | "jpeg" => mime::IMAGE_JPEG,
-            "gif" => mime::IMAGE_GIF,
-            "svg" => mime::IMAGE_SVG,
-            "webp" => mime::IMAGE_WEBP,
-            "ico" => mime::IMAGE_X_ICON,
-            "avif" => mime::IMAGE_AVIF,
-            "bmp" => mime::IMAGE_BMP,
-            "tiff" | "tif" => mime::IMAGE_TIFF,
-            // Fonts
-            "woff" => mime::FONT_WOFF,
-            "woff2" => mime::FONT_WOFF2,
-            "ttf" => mime::FONT_TTF,
-            "otf" => mime::FONT_OTF,
-            // Audio
-            "mp3" => mime::AUDIO_MPEG,
-            "wav" => mime::AUDIO_WAV,
-            "ogg" => mime::AUDIO_OGG,
-            "opus" => mime::AUDIO_OPUS,
-            "flac" => mime::AUDIO_FLAC,
-            "m4a" | "aac" => mime::AUDIO_AAC,
-            // Video
-            "mp4" => mime::VIDEO_MP4,
-            "webm" => mime::VIDEO_WEBM,
-            "ogv" => mime::VIDEO_OGG,
-            // Documents & archives
-            "pdf" => mime::APPLICATION_PDF,
-            "zip" => mime::APPLICATION_ZIP,
-            "gz" => mime::APPLICATION_GZIP,
-            "tar" => mime::APPLICATION_X_TAR,
-            _ => mime::APPLICATION_OCTET_STREAM,
-        }
+        self.first().unwrap_or(mime::APPLICATION_OCTET_STREAM)
+    }
+
+    /// Guess MIME type or return `text/plain` if unknown
+    pub fn first_or_text_plain(&self) -> Mime {
+        self.first().unwrap_or(mime::TEXT_PLAIN)
+    }
+
+    /// Iterate over all guessed MIME types
+    pub fn iter(&self) -> impl Iterator<Item = Mime> {
+        self.mimes.iter().map(|mime| Mime::from_static(mime))
+    }
+
+    /// Iterate over all guessed MIME types as strings
+    pub fn iter_raw(&self) -> impl Iterator<Item = &'static str> {
+        self.mimes.iter().copied()
+    }
+}
+
+impl IntoIterator for MimeGuess {
+    type Item = Mime;
+    type IntoIter =
+        std::iter::Map<std::slice::Iter<'static, &'static str>, fn(&&'static str) -> Mime>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.mimes.iter().map(|mime| Mime::from_static(mime))
     }
 }
 
@@ -151,4 +179,64 @@ mod test {
         // Fallback
         assert_eq!(MimeGuess::from_path("unknown.xyz").first_or_octet_stream(), mime::APPLICATION_OCTET_STREAM);
     }
+
+    #[test]
+    fn test_table_sorted() {
+        for window in MIME_TYPES.windows(2) {
+            assert!(
+                window[0].0 < window[1].0,
+                "{} >= {}",
+                window[0].0,
+                window[1].0
+            );
+        }
+        for (extension, mimes) in MIME_TYPES {
+            assert_eq!(*extension, extension.to_ascii_lowercase());
+            assert!(!mimes.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_from_ext() {
+        assert_eq!(from_ext("html").first_or_octet_stream(), mime::TEXT_HTML);
+        assert_eq!(from_ext("HTML").first_or_octet_stream(), mime::TEXT_HTML);
+        assert_eq!(from_ext("woff2").first_raw(), Some("font/woff2"));
+        assert_eq!(
+            from_ext("docx").first_raw(),
+            Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document")
+        );
+        assert!(from_ext("xyz").is_empty());
+        assert_eq!(from_ext("xyz").first(), None);
+        assert_eq!(from_ext("xyz").first_or_text_plain(), mime::TEXT_PLAIN);
+        assert_eq!(
+            from_path("Makefile").first_or_octet_stream(),
+            mime::APPLICATION_OCTET_STREAM
+        );
+    }
+
+    #[test]
+    fn test_iter() {
+        let guess = from_ext("xml");
+        assert_eq!(guess.count(), 2);
+        assert_eq!(
+            guess.iter().collect::<Vec<_>>(),
+            vec![mime::TEXT_XML, Mime::new("application", "xml", None)]
+        );
+        assert_eq!(
+            from_ext("svg").iter_raw().collect::<Vec<_>>(),
+            vec!["image/svg+xml"]
+        );
+        assert_eq!(from_ext("svg").into_iter().next(), Some(mime::IMAGE_SVG));
+    }
+
+    #[test]
+    fn test_extensions_for() {
+        assert_eq!(extensions_for(&mime::TEXT_HTML), vec!["htm", "html"]);
+        assert_eq!(
+            extensions_for(&mime::IMAGE_JPEG),
+            vec!["jfif", "jpe", "jpeg", "jpg"]
+        );
+        assert_eq!(extensions_for(&mime::IMAGE_SVG), vec!["svg", "svgz"]);
+        assert!(extensions_for(&Mime::new("application", "x-unknown", None)).is_empty());
+    }
 }
@@ -0,0 +1,278 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+// MARK: Mime types table
+/// File extension to MIME types table, sorted by extension so it can be binary searched,
+/// the first MIME type of an entry is the preferred one
+pub(crate) static MIME_TYPES: &[(&str, &[&str])] = &[
+    ("3g2", &["video/3gpp2"]),
+    ("3gp", &["video/3gpp", "audio/3gpp"]),
+    ("7z", &["application/x-7z-compressed"]),
+    ("aac", &["audio/aac"]),
+    ("abw", &["application/x-abiword"]),
+    ("ai", &["application/postscript"]),
+    ("aif", &["audio/aiff", "audio/x-aiff"]),
+    ("aifc", &["audio/aiff", "audio/x-aiff"]),
+    ("aiff", &["audio/aiff", "audio/x-aiff"]),
+    ("apk", &["application/vnd.android.package-archive"]),
+    ("apng", &["image/apng"]),
+    ("appcache", &["text/cache-manifest"]),
+    ("arc", &["application/x-freearc"]),
+    ("asf", &["video/x-ms-asf"]),
+    ("asm", &["text/x-asm"]),
+    ("atom", &["application/atom+xml"]),
+    ("au", &["audio/basic"]),
+    ("avi", &["video/x-msvideo"]),
+    ("avif", &["image/avif"]),
+    ("azw", &["application/vnd.amazon.ebook"]),
+    ("bash", &["application/x-sh"]),
+    ("bat", &["application/x-msdownload"]),
+    ("bin", &["application/octet-stream"]),
+    ("bmp", &["image/bmp"]),
+    ("bz", &["application/x-bzip"]),
+    ("bz2", &["application/x-bzip2"]),
+    ("c", &["text/x-c"]),
+    ("cab", &["application/vnd.ms-cab-compressed"]),
+    ("cbor", &["application/cbor"]),
+    ("cc", &["text/x-c"]),
+    ("cda", &["application/x-cdf"]),
+    ("cer", &["application/pkix-cert"]),
+    ("cgm", &["image/cgm"]),
+    ("class", &["application/java-vm"]),
+    ("conf", &["text/plain"]),
+    ("cpio", &["application/x-cpio"]),
+    ("cpp", &["text/x-c"]),
+    ("crl", &["application/pkix-crl"]),
+    ("crt", &["application/x-x509-ca-cert"]),
+    ("csh", &["application/x-csh"]),
+    ("css", &["text/css"]),
+    ("csv", &["text/csv"]),
+    ("cur", &["image/x-icon"]),
+    ("cxx", &["text/x-c"]),
+    ("dart", &["text/x-dart"]),
+    ("deb", &["application/vnd.debian.binary-package"]),
+    ("der", &["application/x-x509-ca-cert"]),
+    ("diff", &["text/x-diff"]),
+    ("djvu", &["image/vnd.djvu"]),
+    ("dll", &["application/x-msdownload"]),
+    ("dmg", &["application/x-apple-diskimage"]),
+    ("doc", &["application/msword"]),
+    (
+        "docm",
+        &["application/vnd.ms-word.document.macroenabled.12"],
+    ),
+    (
+        "docx",
+        &["application/vnd.openxmlformats-officedocument.wordprocessingml.document"],
+    ),
+    ("dot", &["application/msword"]),
+    (
+        "dotx",
+        &["application/vnd.openxmlformats-officedocument.wordprocessingml.template"],
+    ),
+    ("dtd", &["application/xml-dtd"]),
+    ("dvi", &["application/x-dvi"]),
+    ("dwg", &["image/vnd.dwg"]),
+    ("dxf", &["image/vnd.dxf"]),
+    ("eml", &["message/rfc822"]),
+    ("eot", &["application/vnd.ms-fontobject"]),
+    ("eps", &["application/postscript"]),
+    ("epub", &["application/epub+zip"]),
+    ("exe", &["application/x-msdownload"]),
+    ("f4v", &["video/x-f4v"]),
+    ("flac", &["audio/flac"]),
+    ("flv", &["video/x-flv"]),
+    ("gif", &["image/gif"]),
+    ("glb", &["model/gltf-binary"]),
+    ("gltf", &["model/gltf+json"]),
+    ("go", &["text/x-go"]),
+    ("gpx", &["application/gpx+xml"]),
+    ("gz", &["application/gzip"]),
+    ("h", &["text/x-c"]),
+    ("heic", &["image/heic"]),
+    ("heif", &["image/heif"]),
+    ("hh", &["text/x-c"]),
+    ("hpp", &["text/x-c"]),
+    ("htm", &["text/html"]),
+    ("html", &["text/html"]),
+    ("ical", &["text/calendar"]),
+    ("ico", &["image/x-icon", "image/vnd.microsoft.icon"]),
+    ("ics", &["text/calendar"]),
+    ("ini", &["text/plain"]),
+    ("iso", &["application/x-iso9660-image"]),
+    ("jar", &["application/java-archive"]),
+    ("java", &["text/x-java-source"]),
+    ("jfif", &["image/jpeg"]),
+    ("jpe", &["image/jpeg"]),
+    ("jpeg", &["image/jpeg"]),
+    ("jpg", &["image/jpeg"]),
+    ("jpgv", &["video/jpeg"]),
+    ("js", &["application/javascript", "text/javascript"]),
+    ("json", &["application/json"]),
+    ("jsonld", &["application/ld+json"]),
+    ("jsx", &["text/jsx"]),
+    ("jxl", &["image/jxl"]),
+    ("key", &["application/vnd.apple.keynote"]),
+    ("kml", &["application/vnd.google-earth.kml+xml"]),
+    ("kmz", &["application/vnd.google-earth.kmz"]),
+    ("kt", &["text/x-kotlin"]),
+    ("latex", &["application/x-latex"]),
+    ("less", &["text/less"]),
+    ("log", &["text/plain"]),
+    ("lua", &["text/x-lua"]),
+    ("lz", &["application/x-lzip"]),
+    ("lzma", &["application/x-lzma"]),
+    ("m", &["text/x-objcsrc"]),
+    ("m1v", &["video/mpeg"]),
+    ("m2v", &["video/mpeg"]),
+    ("m3u", &["audio/x-mpegurl"]),
+    ("m3u8", &["application/vnd.apple.mpegurl"]),
+    ("m4a", &["audio/aac", "audio/mp4"]),
+    ("m4b", &["audio/mp4"]),
+    ("m4v", &["video/x-m4v"]),
+    ("man", &["text/troff"]),
+    ("manifest", &["text/cache-manifest"]),
+    ("map", &["application/json"]),
+    ("markdown", &["text/markdown"]),
+    ("md", &["text/markdown"]),
+    ("mdb", &["application/x-msaccess"]),
+    ("mid", &["audio/midi", "audio/x-midi"]),
+    ("midi", &["audio/midi", "audio/x-midi"]),
+    ("mjs", &["application/javascript", "text/javascript"]),
+    ("mka", &["audio/x-matroska"]),
+    ("mkv", &["video/x-matroska"]),
+    ("mm", &["text/x-objcsrc"]),
+    ("mobi", &["application/x-mobipocket-ebook"]),
+    ("mov", &["video/quicktime"]),
+    ("mp2", &["audio/mpeg"]),
+    ("mp3", &["audio/mpeg"]),
+    ("mp4", &["video/mp4", "audio/mp4", "application/mp4"]),
+    ("mp4a", &["audio/mp4"]),
+    ("mpd", &["application/dash+xml"]),
+    ("mpeg", &["video/mpeg"]),
+    ("mpg", &["video/mpeg"]),
+    ("mpga", &["audio/mpeg"]),
+    ("mpkg", &["application/vnd.apple.installer+xml"]),
+    ("msg", &["application/vnd.ms-outlook"]),
+    ("msi", &["application/x-msdownload"]),
+    ("mts", &["video/mp2t"]),
+    ("numbers", &["application/vnd.apple.numbers"]),
+    ("odg", &["application/vnd.oasis.opendocument.graphics"]),
+    ("odp", &["application/vnd.oasis.opendocument.presentation"]),
+    ("ods", &["application/vnd.oasis.opendocument.spreadsheet"]),
+    ("odt", &["application/vnd.oasis.opendocument.text"]),
+    ("oga", &["audio/ogg"]),
+    ("ogg", &["audio/ogg"]),
+    ("ogv", &["video/ogg"]),
+    ("ogx", &["application/ogg"]),
+    ("opus", &["audio/opus"]),
+    ("otf", &["font/otf"]),
+    ("p12", &["application/x-pkcs12"]),
+    ("pages", &["application/vnd.apple.pages"]),
+    ("patch", &["text/x-diff"]),
+    ("pdf", &["application/pdf"]),
+    ("pem", &["application/x-pem-file"]),
+    ("pfx", &["application/x-pkcs12"]),
+    ("php", &["application/x-httpd-php"]),
+    ("pict", &["image/x-pict"]),
+    ("pkg", &["application/octet-stream"]),
+    ("pl", &["text/x-perl"]),
+    ("png", &["image/png"]),
+    ("pot", &["application/vnd.ms-powerpoint"]),
+    (
+        "potx",
+        &["application/vnd.openxmlformats-officedocument.presentationml.template"],
+    ),
+    ("ppm", &["image/x-portable-pixmap"]),
+    ("pps", &["application/vnd.ms-powerpoint"]),
+    (
+        "ppsx",
+        &["application/vnd.openxmlformats-officedocument.presentationml.slideshow"],
+    ),
+    ("ppt", &["application/vnd.ms-powerpoint"]),
+    (
+        "pptx",
+        &["application/vnd.openxmlformats-officedocument.presentationml.presentation"],
+    ),
+    ("ps", &["application/postscript"]),
+    ("psd", &["image/vnd.adobe.photoshop"]),
+    ("py", &["text/x-python"]),
+    ("qt", &["video/quicktime"]),
+    (
+        "rar",
+        &["application/vnd.rar", "application/x-rar-compressed"],
+    ),
+    ("rb", &["text/x-ruby"]),
+    ("rdf", &["application/rdf+xml"]),
+    ("rpm", &["application/x-rpm"]),
+    ("rs", &["text/x-rust"]),
+    ("rss", &["application/rss+xml"]),
+    ("rtf", &["application/rtf", "text/rtf"]),
+    ("sass", &["text/x-sass"]),
+    ("scss", &["text/x-scss"]),
+    ("sh", &["application/x-sh"]),
+    ("sql", &["application/sql"]),
+    ("srt", &["application/x-subrip"]),
+    ("svg", &["image/svg+xml"]),
+    ("svgz", &["image/svg+xml"]),
+    ("swf", &["application/x-shockwave-flash"]),
+    ("swift", &["text/x-swift"]),
+    ("tar", &["application/x-tar"]),
+    ("tex", &["application/x-tex"]),
+    ("text", &["text/plain"]),
+    ("tga", &["image/x-tga"]),
+    ("tgz", &["application/gzip"]),
+    ("tif", &["image/tiff"]),
+    ("tiff", &["image/tiff"]),
+    ("toml", &["application/toml"]),
+    ("torrent", &["application/x-bittorrent"]),
+    ("ts", &["video/mp2t"]),
+    ("tsv", &["text/tab-separated-values"]),
+    ("tsx", &["text/tsx"]),
+    ("ttc", &["font/collection"]),
+    ("ttf", &["font/ttf"]),
+    ("txt", &["text/plain"]),
+    ("vcard", &["text/vcard"]),
+    ("vcf", &["text/vcard"]),
+    ("vsd", &["application/vnd.visio"]),
+    ("vtt", &["text/vtt"]),
+    ("wasm", &["application/wasm"]),
+    ("wav", &["audio/wav", "audio/x-wav"]),
+    ("weba", &["audio/webm"]),
+    ("webm", &["video/webm"]),
+    ("webmanifest", &["application/manifest+json"]),
+    ("webp", &["image/webp"]),
+    ("wma", &["audio/x-ms-wma"]),
+    ("wmv", &["video/x-ms-wmv"]),
+    ("woff", &["font/woff"]),
+    ("woff2", &["font/woff2"]),
+    ("xhtml", &["application/xhtml+xml"]),
+    ("xls", &["application/vnd.ms-excel"]),
+    (
+        "xlsb",
+        &["application/vnd.ms-excel.sheet.binary.macroenabled.12"],
+    ),
+    ("xlsm", &["application/vnd.ms-excel.sheet.macroenabled.12"]),
+    (
+        "xlsx",
+        &["application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"],
+    ),
+    ("xlt", &["application/vnd.ms-excel"]),
+    (
+        "xltx",
+        &["application/vnd.openxmlformats-officedocument.spreadsheetml.template"],
+    ),
+    ("xml", &["text/xml", "application/xml"]),
+    ("xsd", &["application/xml"]),
+    ("xsl", &["application/xml"]),
+    ("xslt", &["application/xslt+xml"]),
+    ("xul", &["application/vnd.mozilla.xul+xml"]),
+    ("xz", &["application/x-xz"]),
+    ("yaml", &["application/yaml", "text/yaml"]),
+    ("yml", &["application/yaml", "text/yaml"]),
+    ("zip", &["application/zip", "application/x-zip-compressed"]),
+    ("zst", &["application/zstd"]),
+];
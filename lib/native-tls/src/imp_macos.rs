@@ -6,27 +6,36 @@
 
 //! macOS TLS implementation using Security.framework SecureTransport
 
-use std::ffi::c_void;
+use std::ffi::{CStr, c_void};
 use std::io::{self, Read, Write};
 use std::os::raw::{c_char, c_int};
 use std::slice;
 
-use crate::{Error, HandshakeError};
+use crate::{Error, HandshakeError, Protocol, TlsConnectorBuilder};
 
 // MARK: FFI types and constants
 type SSLContextRef = *mut c_void;
 type SSLConnectionRef = *const c_void;
 type SecTrustRef = *mut c_void;
 type CFErrorRef = *mut c_void;
+type CFTypeRef = *const c_void;
+type CFArrayRef = *const c_void;
+type CFDictionaryRef = *const c_void;
+type CFStringRef = *const c_void;
+type SecPolicyRef = *const c_void;
 type OSStatus = i32;
 type SSLReadFunc = unsafe extern "C" fn(SSLConnectionRef, *mut c_void, *mut usize) -> OSStatus;
 type SSLWriteFunc = unsafe extern "C" fn(SSLConnectionRef, *const c_void, *mut usize) -> OSStatus;
 
+const SSL_SERVER_SIDE: u32 = 0;
 const SSL_CLIENT_SIDE: u32 = 1;
 const SSL_STREAM_TYPE: u32 = 0;
 // kSSLSessionOptionBreakOnServerAuth = 0: pause handshake after server cert received for manual trust eval
 const SSL_OPT_BREAK_ON_SERVER_AUTH: i32 = 0;
-// kTLSProtocol12 = 8 (SSLProtocol is c_int)
+// SSLProtocol values (SSLProtocol is c_int)
+const K_SSL_PROTOCOL_3: c_int = 2;
+const K_TLS_PROTOCOL_1: c_int = 4;
+const K_TLS_PROTOCOL_11: c_int = 7;
 const K_TLS_PROTOCOL_12: c_int = 8;
 const NO_ERR: OSStatus = 0;
 const ERR_SSL_WOULD_BLOCK: OSStatus = -9803;
@@ -36,6 +45,13 @@ const ERR_SSL_CLOSED_NO_NOTIFY: OSStatus = -9816;
 // errSSLPeerAuthCompleted: returned when break-on-server-auth fires and the server cert is ready
 const ERR_SSL_PEER_AUTH_COMPLETED: OSStatus = -9841;
 const IO_ERR: OSStatus = -36;
+const K_CF_STRING_ENCODING_UTF8: u32 = 0x08000100;
+
+// Opaque CFArrayCallBacks / CFDictionaryKeyCallBacks / CFDictionaryValueCallBacks structs
+#[repr(C)]
+struct CFCallBacks {
+    _private: [u8; 0],
+}
 
 #[link(name = "Security", kind = "framework")]
 unsafe extern "C" {
@@ -48,10 +64,18 @@ unsafe extern "C" {
     fn SSLSetIOFuncs(ctx: SSLContextRef, read: SSLReadFunc, write: SSLWriteFunc) -> OSStatus;
     fn SSLSetConnection(ctx: SSLContextRef, connection: SSLConnectionRef) -> OSStatus;
     fn SSLSetProtocolVersionMin(ctx: SSLContextRef, min_ver: c_int) -> OSStatus;
+    fn SSLSetProtocolVersionMax(ctx: SSLContextRef, max_ver: c_int) -> OSStatus;
+    fn SSLSetALPNProtocols(ctx: SSLContextRef, protocols: CFArrayRef) -> OSStatus;
+    fn SSLCopyALPNProtocols(ctx: SSLContextRef, protocols: *mut CFArrayRef) -> OSStatus;
     fn SSLSetPeerDomainName(ctx: SSLContextRef, name: *const c_char, len: usize) -> OSStatus;
     fn SSLHandshake(ctx: SSLContextRef) -> OSStatus;
     fn SSLCopyPeerTrust(ctx: SSLContextRef, trust: *mut SecTrustRef) -> OSStatus;
     fn SecTrustEvaluateWithError(trust: SecTrustRef, error: *mut CFErrorRef) -> u8;
+    fn SecTrustSetPolicies(trust: SecTrustRef, policies: CFTypeRef) -> OSStatus;
+    fn SecTrustSetAnchorCertificates(trust: SecTrustRef, anchors: CFArrayRef) -> OSStatus;
+    fn SecTrustSetAnchorCertificatesOnly(trust: SecTrustRef, only: u8) -> OSStatus;
+    fn SecPolicyCreateSSL(server: u8, hostname: CFStringRef) -> SecPolicyRef;
+    fn SecCertificateCreateWithData(alloc: *const c_void, data: CFTypeRef) -> CFTypeRef;
     fn SSLRead(
         ctx: SSLContextRef,
         data: *mut c_void,
@@ -65,11 +89,54 @@ unsafe extern "C" {
         processed: *mut usize,
     ) -> OSStatus;
     fn SSLClose(ctx: SSLContextRef) -> OSStatus;
+    fn SSLSetCertificate(ctx: SSLContextRef, cert_refs: CFArrayRef) -> OSStatus;
+    fn SecPKCS12Import(
+        pkcs12_data: CFTypeRef,
+        options: CFDictionaryRef,
+        items: *mut CFArrayRef,
+    ) -> OSStatus;
+    static kSecImportExportPassphrase: CFStringRef;
+    static kSecImportItemIdentity: CFStringRef;
+    static kSecImportItemCertChain: CFStringRef;
 }
 
 #[link(name = "Foundation", kind = "framework")]
 unsafe extern "C" {
     fn CFRelease(cf: *const c_void);
+    fn CFDataCreate(alloc: *const c_void, bytes: *const u8, length: isize) -> CFTypeRef;
+    fn CFStringCreateWithBytes(
+        alloc: *const c_void,
+        bytes: *const u8,
+        num_bytes: isize,
+        encoding: u32,
+        is_external_representation: u8,
+    ) -> CFStringRef;
+    fn CFDictionaryCreate(
+        alloc: *const c_void,
+        keys: *const CFTypeRef,
+        values: *const CFTypeRef,
+        num_values: isize,
+        key_call_backs: *const CFCallBacks,
+        value_call_backs: *const CFCallBacks,
+    ) -> CFDictionaryRef;
+    fn CFDictionaryGetValue(dict: CFDictionaryRef, key: CFTypeRef) -> CFTypeRef;
+    fn CFArrayCreateMutable(
+        alloc: *const c_void,
+        capacity: isize,
+        call_backs: *const CFCallBacks,
+    ) -> CFArrayRef;
+    fn CFArrayAppendValue(array: CFArrayRef, value: CFTypeRef);
+    fn CFArrayGetCount(array: CFArrayRef) -> isize;
+    fn CFArrayGetValueAtIndex(array: CFArrayRef, index: isize) -> CFTypeRef;
+    fn CFStringGetCString(
+        string: CFStringRef,
+        buffer: *mut c_char,
+        buffer_size: isize,
+        encoding: u32,
+    ) -> u8;
+    static kCFTypeArrayCallBacks: CFCallBacks;
+    static kCFTypeDictionaryKeyCallBacks: CFCallBacks;
+    static kCFTypeDictionaryValueCallBacks: CFCallBacks;
 }
 
 // MARK: IoFuncs
@@ -89,7 +156,7 @@ unsafe extern "C" fn ssl_read_cb(
         return IO_ERR;
     }
     // SAFETY: conn was set via SSLSetConnection with a &IoFuncs pointer that lives as long as the
-    // SSL context; alignment and size are guaranteed by the Box<IoFuncs> in connect_inner.
+    // SSL context; alignment and size are guaranteed by the Box<IoFuncs> in new_context.
     let io = unsafe { &mut *(conn as *mut IoFuncs) };
     // SAFETY: data_len is non-null (checked above).
     let total = unsafe { *data_len };
@@ -98,7 +165,7 @@ unsafe extern "C" fn ssl_read_cb(
     let mut got = 0usize;
     // SecureTransport requires all `total` bytes to be read before returning noErr
     while got < total {
-        // SAFETY: io.read_fn and io.stream_ptr are set in connect_inner and valid for SSL context lifetime.
+        // SAFETY: io.read_fn and io.stream_ptr are set in new_context and valid for SSL context lifetime.
         match unsafe { (io.read_fn)(io.stream_ptr, buf.as_mut_ptr().add(got), total - got) } {
             Ok(0) => {
                 // SAFETY: data_len is non-null.
@@ -140,7 +207,7 @@ unsafe extern "C" fn ssl_write_cb(
     let mut sent = 0usize;
     // SecureTransport requires all `total` bytes to be written before returning noErr
     while sent < total {
-        // SAFETY: io.write_fn and io.stream_ptr are set in connect_inner and valid for SSL context lifetime.
+        // SAFETY: io.write_fn and io.stream_ptr are set in new_context and valid for SSL context lifetime.
         match unsafe { (io.write_fn)(io.stream_ptr, buf.as_ptr().add(sent), total - sent) } {
             Ok(0) => {
                 // SAFETY: data_len is non-null.
@@ -166,7 +233,7 @@ unsafe extern "C" fn ssl_write_cb(
 }
 
 unsafe fn do_read<S: Read>(ptr: *mut c_void, buf: *mut u8, len: usize) -> io::Result<usize> {
-    // SAFETY: ptr derives from Box<S>.as_ref() in connect_inner; buf/len come from the SSL layer.
+    // SAFETY: ptr derives from Box<S>.as_ref() in new_context; buf/len come from the SSL layer.
     let stream = unsafe { &mut *(ptr as *mut S) };
     // SAFETY: buf/len are provided by SecureTransport and guaranteed valid for len bytes.
     let slice = unsafe { slice::from_raw_parts_mut(buf, len) };
@@ -174,31 +241,331 @@ unsafe fn do_read<S: Read>(ptr: *mut c_void, buf: *mut u8, len: usize) -> io::Re
 }
 
 unsafe fn do_write<S: Write>(ptr: *mut c_void, buf: *const u8, len: usize) -> io::Result<usize> {
-    // SAFETY: ptr derives from Box<S>.as_ref() in connect_inner; buf/len come from the SSL layer.
+    // SAFETY: ptr derives from Box<S>.as_ref() in new_context; buf/len come from the SSL layer.
     let stream = unsafe { &mut *(ptr as *mut S) };
     // SAFETY: buf/len are provided by SecureTransport and guaranteed valid for len bytes.
     let slice = unsafe { slice::from_raw_parts(buf, len) };
     stream.write(slice)
 }
 
+fn cf_string(value: &str) -> CFStringRef {
+    // SAFETY: value is a valid UTF-8 slice for the given length; the caller releases the result.
+    unsafe {
+        CFStringCreateWithBytes(
+            std::ptr::null(),
+            value.as_ptr(),
+            value.len() as isize,
+            K_CF_STRING_ENCODING_UTF8,
+            0,
+        )
+    }
+}
+
+// Create an SSL context for the given side with the stream attached through the I/O callbacks.
+fn new_context<S: Read + Write>(
+    side: u32,
+    stream: S,
+) -> Result<(SSLContextRef, Box<IoFuncs>, Box<S>), Error> {
+    // SAFETY: null allocator selects the default CF allocator; enum values are valid.
+    let ctx = unsafe { SSLCreateContext(std::ptr::null(), side, SSL_STREAM_TYPE) };
+    if ctx.is_null() {
+        return Err(Error("Failed to create SSL context".to_string()));
+    }
+
+    // Store stream in a Box to ensure a stable heap address for the lifetime of the SSL context
+    let stream = Box::new(stream);
+    let io = Box::new(IoFuncs {
+        stream_ptr: stream.as_ref() as *const S as *mut c_void,
+        read_fn: do_read::<S>,
+        write_fn: do_write::<S>,
+    });
+
+    // Register I/O callbacks - IOFuncs must be set before Connection
+    // SAFETY: ctx is non-null; callback signatures match SSLReadFunc/SSLWriteFunc.
+    let status = unsafe { SSLSetIOFuncs(ctx, ssl_read_cb, ssl_write_cb) };
+    if status != NO_ERR {
+        // SSLSetIOFuncs failed so IO funcs are not registered; CFRelease alone suffices.
+        // SAFETY: ctx is non-null; not freed elsewhere.
+        unsafe { CFRelease(ctx as *const c_void) };
+        return Err(Error(format!("SSLSetIOFuncs failed: {status}")));
+    }
+    // SAFETY: ctx is non-null; io.as_ref() is a stable heap pointer for the SSL context lifetime.
+    let status =
+        unsafe { SSLSetConnection(ctx, io.as_ref() as *const IoFuncs as SSLConnectionRef) };
+    if status != NO_ERR {
+        // IO funcs are registered; Apple docs require SSLClose before CFRelease in this state.
+        // SAFETY: ctx is non-null with registered IO funcs.
+        unsafe {
+            SSLClose(ctx);
+            CFRelease(ctx as *const c_void);
+        }
+        return Err(Error(format!("SSLSetConnection failed: {status}")));
+    }
+    Ok((ctx, io, stream))
+}
+
+// Limit the protocol versions of an SSL context. No SSLSetProtocolVersionMax call for TLS 1.3 or
+// no maximum: without it SecureTransport negotiates the highest version it supports (TLS 1.2 in
+// practice). Setting the max to an unrecognised kTLSProtocol13 constant causes
+// errSSLIllegalParam on current SecureTransport versions.
+fn set_protocol_versions(
+    ctx: SSLContextRef,
+    min: Option<Protocol>,
+    max: Option<Protocol>,
+) -> Result<(), Error> {
+    let protocol_value = |protocol| match protocol {
+        Protocol::Sslv3 => Some(K_SSL_PROTOCOL_3),
+        Protocol::Tlsv10 => Some(K_TLS_PROTOCOL_1),
+        Protocol::Tlsv11 => Some(K_TLS_PROTOCOL_11),
+        Protocol::Tlsv12 => Some(K_TLS_PROTOCOL_12),
+        Protocol::Tlsv13 => None,
+    };
+    if let Some(min) = min {
+        let Some(min) = protocol_value(min) else {
+            return Err(Error(
+                "TLS 1.3 is not supported by the SecureTransport backend".to_string(),
+            ));
+        };
+        // SAFETY: ctx is non-null; min is a valid SSLProtocol value.
+        let status = unsafe { SSLSetProtocolVersionMin(ctx, min) };
+        if status != NO_ERR {
+            return Err(Error(format!("SSLSetProtocolVersionMin failed: {status}")));
+        }
+    }
+    if let Some(max) = max.and_then(protocol_value) {
+        // SAFETY: ctx is non-null; max is a valid SSLProtocol value.
+        let status = unsafe { SSLSetProtocolVersionMax(ctx, max) };
+        if status != NO_ERR {
+            return Err(Error(format!("SSLSetProtocolVersionMax failed: {status}")));
+        }
+    }
+    Ok(())
+}
+
+fn set_alpn_protocols(ctx: SSLContextRef, protocols: &[String]) -> Result<(), Error> {
+    // SAFETY: the array retains the strings appended to it and is released after the call.
+    let status = unsafe {
+        let array = CFArrayCreateMutable(std::ptr::null(), 0, &raw const kCFTypeArrayCallBacks);
+        for protocol in protocols {
+            let protocol = cf_string(protocol);
+            CFArrayAppendValue(array, protocol);
+            CFRelease(protocol);
+        }
+        let status = SSLSetALPNProtocols(ctx, array);
+        CFRelease(array);
+        status
+    };
+    if status != NO_ERR {
+        return Err(Error(format!("SSLSetALPNProtocols failed: {status}")));
+    }
+    Ok(())
+}
+
+fn negotiated_alpn(ctx: SSLContextRef) -> Option<Vec<u8>> {
+    let mut protocols: CFArrayRef = std::ptr::null();
+    // SAFETY: ctx is non-null; &mut protocols is a valid output pointer.
+    let status = unsafe { SSLCopyALPNProtocols(ctx, &mut protocols) };
+    if status != NO_ERR || protocols.is_null() {
+        return None;
+    }
+    // SAFETY: protocols is a CFArray of CFStrings owned by us; ALPN protocols are at most
+    // 255 bytes so they fit in buffer including the NUL terminator.
+    unsafe {
+        let mut buffer = [0 as c_char; 256];
+        let protocol = if CFArrayGetCount(protocols) > 0
+            && CFStringGetCString(
+                CFArrayGetValueAtIndex(protocols, 0),
+                buffer.as_mut_ptr(),
+                buffer.len() as isize,
+                K_CF_STRING_ENCODING_UTF8,
+            ) != 0
+        {
+            Some(CStr::from_ptr(buffer.as_ptr()).to_bytes().to_vec())
+        } else {
+            None
+        };
+        CFRelease(protocols);
+        protocol
+    }
+}
+
+// Evaluate the server trust with the connector's hostname and root certificate settings
+fn evaluate_trust(
+    trust: SecTrustRef,
+    builder: &TlsConnectorBuilder,
+    domain: &str,
+) -> Result<(), Error> {
+    // SAFETY: trust is non-null; every CF object created here is released before returning.
+    unsafe {
+        let hostname = if builder.accept_invalid_hostnames {
+            std::ptr::null()
+        } else {
+            cf_string(domain)
+        };
+        let policy = SecPolicyCreateSSL(1, hostname);
+        if !hostname.is_null() {
+            CFRelease(hostname);
+        }
+        let status = SecTrustSetPolicies(trust, policy);
+        CFRelease(policy);
+        if status != NO_ERR {
+            return Err(Error(format!("SecTrustSetPolicies failed: {status}")));
+        }
+
+        if !builder.root_certificates.is_empty() || builder.disable_built_in_roots {
+            let anchors =
+                CFArrayCreateMutable(std::ptr::null(), 0, &raw const kCFTypeArrayCallBacks);
+            for cert in &builder.root_certificates {
+                let data = CFDataCreate(std::ptr::null(), cert.0.as_ptr(), cert.0.len() as isize);
+                let cert = SecCertificateCreateWithData(std::ptr::null(), data);
+                CFRelease(data);
+                if cert.is_null() {
+                    CFRelease(anchors);
+                    return Err(Error("Invalid root certificate".to_string()));
+                }
+                CFArrayAppendValue(anchors, cert);
+                CFRelease(cert);
+            }
+            let mut status = SecTrustSetAnchorCertificates(trust, anchors);
+            CFRelease(anchors);
+            if status == NO_ERR {
+                status =
+                    SecTrustSetAnchorCertificatesOnly(trust, builder.disable_built_in_roots as u8);
+            }
+            if status != NO_ERR {
+                return Err(Error(format!(
+                    "SecTrustSetAnchorCertificates failed: {status}"
+                )));
+            }
+        }
+
+        let mut cf_error: CFErrorRef = std::ptr::null_mut();
+        let trusted = SecTrustEvaluateWithError(trust, &mut cf_error);
+        if !cf_error.is_null() {
+            CFRelease(cf_error);
+        }
+        if trusted == 0 {
+            return Err(Error("Certificate trust validation failed".to_string()));
+        }
+    }
+    Ok(())
+}
+
+// MARK: Identity
+/// A certificate identity and certificate chain imported from a PKCS#12 archive
+pub struct Identity {
+    // CFArray of the SecIdentityRef followed by the intermediate SecCertificateRefs,
+    // in the format expected by SSLSetCertificate
+    certs: CFArrayRef,
+}
+
+// SAFETY: the CFArray and the Security objects it holds are immutable after import.
+unsafe impl Send for Identity {}
+// SAFETY: the CFArray and the Security objects it holds are immutable after import.
+unsafe impl Sync for Identity {}
+
+impl Identity {
+    /// Import a DER-encoded PKCS#12 archive containing a certificate, private key and chain
+    pub fn from_pkcs12(der: &[u8], password: &str) -> Result<Self, Error> {
+        // SAFETY: der and password are valid slices for the given lengths; the CF objects
+        // created here are released before returning, the imported items are retained by certs.
+        unsafe {
+            let data = CFDataCreate(std::ptr::null(), der.as_ptr(), der.len() as isize);
+            let passphrase = cf_string(password);
+            if data.is_null() || passphrase.is_null() {
+                if !data.is_null() {
+                    CFRelease(data);
+                }
+                if !passphrase.is_null() {
+                    CFRelease(passphrase);
+                }
+                return Err(Error("Failed to allocate PKCS#12 import data".to_string()));
+            }
+            let keys = [kSecImportExportPassphrase];
+            let values = [passphrase];
+            let options = CFDictionaryCreate(
+                std::ptr::null(),
+                keys.as_ptr(),
+                values.as_ptr(),
+                1,
+                &raw const kCFTypeDictionaryKeyCallBacks,
+                &raw const kCFTypeDictionaryValueCallBacks,
+            );
+            let mut items: CFArrayRef = std::ptr::null();
+            let status = SecPKCS12Import(data, options, &mut items);
+            CFRelease(options);
+            CFRelease(passphrase);
+            CFRelease(data);
+            if status != NO_ERR || items.is_null() {
+                return Err(Error(format!("SecPKCS12Import failed: {status}")));
+            }
+
+            let (identity, chain) = if CFArrayGetCount(items) > 0 {
+                let item = CFArrayGetValueAtIndex(items, 0);
+                (
+                    CFDictionaryGetValue(item, kSecImportItemIdentity),
+                    CFDictionaryGetValue(item, kSecImportItemCertChain),
+                )
+            } else {
+                (std::ptr::null(), std::ptr::null())
+            };
+            if identity.is_null() {
+                CFRelease(items);
+                return Err(Error(
+                    "PKCS#12 archive does not contain a certificate and private key".to_string(),
+                ));
+            }
+
+            let certs = CFArrayCreateMutable(std::ptr::null(), 0, &raw const kCFTypeArrayCallBacks);
+            CFArrayAppendValue(certs, identity);
+            // The chain starts with the leaf certificate, which the identity already contains
+            if !chain.is_null() {
+                for index in 1..CFArrayGetCount(chain) {
+                    CFArrayAppendValue(certs, CFArrayGetValueAtIndex(chain, index));
+                }
+            }
+            CFRelease(items);
+            Ok(Self { certs })
+        }
+    }
+
+    /// Parse a PEM-encoded certificate chain and PKCS#8 private key, which is not supported by
+    /// the SecureTransport backend
+    pub fn from_pkcs8(_pem: &[u8], _key: &[u8]) -> Result<Self, Error> {
+        Err(Error(
+            "PKCS#8 identities are not supported by the SecureTransport backend, use Identity::from_pkcs12"
+                .to_string(),
+        ))
+    }
+}
+
+impl Drop for Identity {
+    fn drop(&mut self) {
+        // SAFETY: self.certs is a non-null CFArray owned by this identity; Drop is called once.
+        unsafe { CFRelease(self.certs) };
+    }
+}
+
 // MARK: TlsConnector
 /// A TLS connector using SecureTransport
 pub struct TlsConnector {
-    accept_invalid_certs: bool,
+    builder: TlsConnectorBuilder,
 }
 
 impl TlsConnector {
     /// Create a new TLS connector
-    pub const fn new() -> Result<Self, Error> {
-        Ok(Self {
-            accept_invalid_certs: false,
-        })
+    pub fn new() -> Result<Self, Error> {
+        Self::from_builder(&TlsConnectorBuilder::default())
     }
 
-    #[cfg(test)]
-    pub(crate) fn new_danger_accept_invalid_certs() -> Result<Self, Error> {
+    pub(crate) fn from_builder(builder: &TlsConnectorBuilder) -> Result<Self, Error> {
+        if builder.min_protocol == Some(Protocol::Tlsv13) {
+            return Err(Error(
+                "TLS 1.3 is not supported by the SecureTransport backend".to_string(),
+            ));
+        }
         Ok(Self {
-            accept_invalid_certs: true,
+            builder: builder.clone(),
         })
     }
 
@@ -217,10 +584,18 @@ impl TlsConnector {
         domain: &str,
         stream: S,
     ) -> Result<TlsStream<S>, Error> {
-        // SAFETY: null allocator selects the default CF allocator; enum values are valid.
-        let ctx = unsafe { SSLCreateContext(std::ptr::null(), SSL_CLIENT_SIDE, SSL_STREAM_TYPE) };
-        if ctx.is_null() {
-            return Err(Error("Failed to create SSL context".to_string()));
+        let builder = &self.builder;
+        let (ctx, io, stream) = new_context(SSL_CLIENT_SIDE, stream)?;
+        // TlsStream owns ctx from here on, so Drop closes and releases it on error
+        let mut tls = TlsStream {
+            ctx,
+            io,
+            stream,
+            alpn: None,
+        };
+        set_protocol_versions(ctx, builder.min_protocol, builder.max_protocol)?;
+        if !builder.alpn_protocols.is_empty() {
+            set_alpn_protocols(ctx, &builder.alpn_protocols)?;
         }
 
         // Break handshake after server cert is received so we can do manual trust evaluation.
@@ -228,71 +603,23 @@ impl TlsConnector {
         // SAFETY: ctx is non-null; option/value are valid SSLSessionOption values.
         let status = unsafe { SSLSetSessionOption(ctx, SSL_OPT_BREAK_ON_SERVER_AUTH, 1) };
         if status != NO_ERR {
-            // SAFETY: ctx is non-null and was just created; not freed elsewhere.
-            unsafe { CFRelease(ctx as *const c_void) };
             return Err(Error(format!("SSLSetSessionOption failed: {status}")));
         }
 
-        // Require TLS 1.2 minimum. No SSLSetProtocolVersionMax call: without it SecureTransport
-        // negotiates the highest version it supports (TLS 1.2 in practice), which is the correct
-        // "minimum 1.2, negotiate as high as possible" policy. Setting the max to an unrecognised
-        // kTLSProtocol13 constant causes errSSLIllegalParam on current SecureTransport versions.
-        // SAFETY: ctx is non-null; K_TLS_PROTOCOL_12 is a valid SSLProtocol value.
-        let status = unsafe { SSLSetProtocolVersionMin(ctx, K_TLS_PROTOCOL_12) };
-        if status != NO_ERR {
-            // SAFETY: ctx is non-null; not freed elsewhere.
-            unsafe { CFRelease(ctx as *const c_void) };
-            return Err(Error(format!("SSLSetProtocolVersionMin failed: {status}")));
-        }
-
-        // Store stream in a Box to ensure a stable heap address for the lifetime of the SSL context
-        let stream = Box::new(stream);
-        let io = Box::new(IoFuncs {
-            stream_ptr: stream.as_ref() as *const S as *mut c_void,
-            read_fn: do_read::<S>,
-            write_fn: do_write::<S>,
-        });
-
-        // Register I/O callbacks - IOFuncs must be set before Connection
-        // SAFETY: ctx is non-null; callback signatures match SSLReadFunc/SSLWriteFunc.
-        let status = unsafe { SSLSetIOFuncs(ctx, ssl_read_cb, ssl_write_cb) };
-        if status != NO_ERR {
-            // SSLSetIOFuncs failed so IO funcs are not registered; CFRelease alone suffices.
-            // SAFETY: ctx is non-null; not freed elsewhere.
-            unsafe { CFRelease(ctx as *const c_void) };
-            return Err(Error(format!("SSLSetIOFuncs failed: {status}")));
-        }
-        // SAFETY: ctx is non-null; io.as_ref() is a stable heap pointer for the SSL context lifetime.
-        let status =
-            unsafe { SSLSetConnection(ctx, io.as_ref() as *const IoFuncs as SSLConnectionRef) };
-        if status != NO_ERR {
-            // IO funcs are registered; Apple docs require SSLClose before CFRelease in this state.
-            // SAFETY: ctx is non-null with registered IO funcs.
-            unsafe {
-                SSLClose(ctx);
-                CFRelease(ctx as *const c_void);
+        if builder.use_sni {
+            // Set SNI
+            let domain_bytes = domain.as_bytes();
+            // SAFETY: ctx is non-null; domain_bytes pointer/length are valid for the call duration.
+            let status = unsafe {
+                SSLSetPeerDomainName(
+                    ctx,
+                    domain_bytes.as_ptr() as *const c_char,
+                    domain_bytes.len(),
+                )
+            };
+            if status != NO_ERR {
+                return Err(Error(format!("SSLSetPeerDomainName failed: {status}")));
             }
-            return Err(Error(format!("SSLSetConnection failed: {status}")));
-        }
-
-        // Set SNI
-        let domain_bytes = domain.as_bytes();
-        // SAFETY: ctx is non-null; domain_bytes pointer/length are valid for the call duration.
-        let status = unsafe {
-            SSLSetPeerDomainName(
-                ctx,
-                domain_bytes.as_ptr() as *const c_char,
-                domain_bytes.len(),
-            )
-        };
-        if status != NO_ERR {
-            // IO funcs and connection are registered; SSLClose is required before CFRelease.
-            // SAFETY: ctx is non-null with registered IO funcs and connection.
-            unsafe {
-                SSLClose(ctx);
-                CFRelease(ctx as *const c_void);
-            }
-            return Err(Error(format!("SSLSetPeerDomainName failed: {status}")));
         }
 
         // Perform handshake loop; handle trust evaluation break-point
@@ -303,50 +630,31 @@ impl TlsConnector {
                 NO_ERR => break,
                 ERR_SSL_WOULD_BLOCK => continue,
                 ERR_SSL_PEER_AUTH_COMPLETED => {
+                    if builder.accept_invalid_certs {
+                        continue;
+                    }
                     // Server cert is ready; evaluate trust manually using the modern API
                     let mut trust: SecTrustRef = std::ptr::null_mut();
                     // SAFETY: ctx is non-null; &mut trust is a valid output pointer.
                     let st = unsafe { SSLCopyPeerTrust(ctx, &mut trust) };
                     if st != NO_ERR || trust.is_null() {
-                        // SAFETY: ctx is non-null with an active SSL connection.
-                        unsafe {
-                            SSLClose(ctx);
-                            CFRelease(ctx as *const c_void);
-                        }
                         return Err(Error(format!("SSLCopyPeerTrust failed: {st}")));
                     }
-                    let mut cf_error: CFErrorRef = std::ptr::null_mut();
-                    // SAFETY: trust is non-null (checked above); &mut cf_error is a valid output pointer.
-                    let trusted = unsafe { SecTrustEvaluateWithError(trust, &mut cf_error) };
+                    let result = evaluate_trust(trust, builder, domain);
                     // SAFETY: trust is a non-null CF object returned by SSLCopyPeerTrust.
                     unsafe { CFRelease(trust) };
-                    if !cf_error.is_null() {
-                        // SAFETY: cf_error is non-null.
-                        unsafe { CFRelease(cf_error) };
-                    }
-                    if trusted == 0 && !self.accept_invalid_certs {
-                        // SAFETY: ctx is non-null with an active SSL connection.
-                        unsafe {
-                            SSLClose(ctx);
-                            CFRelease(ctx as *const c_void);
-                        }
-                        return Err(Error("Certificate trust validation failed".to_string()));
-                    }
+                    result?;
                     // Trust OK; resume handshake
                     continue;
                 }
-                _ => {
-                    // SAFETY: ctx is non-null with an active SSL connection.
-                    unsafe {
-                        SSLClose(ctx);
-                        CFRelease(ctx as *const c_void);
-                    }
-                    return Err(Error(format!("TLS handshake failed: {status}")));
-                }
+                _ => return Err(Error(format!("TLS handshake failed: {status}"))),
             }
         }
 
-        Ok(TlsStream { ctx, io, stream })
+        if !builder.alpn_protocols.is_empty() {
+            tls.alpn = negotiated_alpn(ctx);
+        }
+        Ok(tls)
     }
 }
 
@@ -356,6 +664,52 @@ impl Default for TlsConnector {
     }
 }
 
+// MARK: TlsAcceptor
+/// A TLS acceptor using SecureTransport
+pub struct TlsAcceptor {
+    identity: Identity,
+}
+
+impl TlsAcceptor {
+    /// Create a new TLS acceptor with the given identity
+    pub const fn new(identity: Identity) -> Result<Self, Error> {
+        Ok(Self { identity })
+    }
+
+    /// Perform a server-side TLS handshake over the given stream
+    pub fn accept<S: Read + Write>(&self, stream: S) -> Result<TlsStream<S>, HandshakeError<S>> {
+        self.accept_inner(stream).map_err(HandshakeError::new)
+    }
+
+    fn accept_inner<S: Read + Write>(&self, stream: S) -> Result<TlsStream<S>, Error> {
+        let (ctx, io, stream) = new_context(SSL_SERVER_SIDE, stream)?;
+        // TlsStream owns ctx from here on, so Drop closes and releases it on error
+        let tls = TlsStream {
+            ctx,
+            io,
+            stream,
+            alpn: None,
+        };
+        set_protocol_versions(ctx, Some(Protocol::Tlsv12), None)?;
+
+        // SAFETY: ctx is non-null; identity.certs is a CFArray in the format SSLSetCertificate expects.
+        let status = unsafe { SSLSetCertificate(tls.ctx, self.identity.certs) };
+        if status != NO_ERR {
+            return Err(Error(format!("SSLSetCertificate failed: {status}")));
+        }
+
+        loop {
+            // SAFETY: ctx is non-null and fully configured.
+            let status = unsafe { SSLHandshake(tls.ctx) };
+            match status {
+                NO_ERR => return Ok(tls),
+                ERR_SSL_WOULD_BLOCK => continue,
+                _ => return Err(Error(format!("TLS handshake failed: {status}"))),
+            }
+        }
+    }
+}
+
 // MARK: TlsStream
 /// A TLS stream backed by SecureTransport
 pub struct TlsStream<S> {
@@ -364,6 +718,7 @@ pub struct TlsStream<S> {
     #[expect(dead_code)]
     io: Box<IoFuncs>,
     stream: Box<S>,
+    alpn: Option<Vec<u8>>,
 }
 
 // SAFETY: SSLContextRef is safe to transfer between threads after the handshake completes.
@@ -371,6 +726,11 @@ pub struct TlsStream<S> {
 unsafe impl<S: Send> Send for TlsStream<S> {}
 
 impl<S> TlsStream<S> {
+    /// Returns the ALPN protocol negotiated during the handshake
+    pub fn negotiated_alpn(&self) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.alpn.clone())
+    }
+
     /// Returns a reference to the underlying stream
     pub fn get_ref(&self) -> &S {
         &self.stream
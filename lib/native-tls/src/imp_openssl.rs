@@ -16,17 +16,18 @@
 //!
 //! The two code paths below implement the same public API:
 //!
-//! - **1.0.x** (`#[cfg(openssl_v10x)]`): uses `SSLv23_client_method`,
+//! - **1.0.x** (`#[cfg(openssl_v10x)]`): uses `SSLv23_client_method`/`SSLv23_server_method`,
 //!   `BIO_new_bio_pair` for I/O pumping and `X509_check_host` for hostname
 //!   verification (all available since OpenSSL 1.0.2).
 //! - **1.1.x / 3.x / 4.x** (`#[cfg(not(openssl_v10x))]`): uses
-//!   `TLS_client_method`, a custom BIO method and `SSL_set1_host` (1.1.x/3.x)
+//!   `TLS_client_method`/`TLS_server_method`, a custom BIO method and `SSL_set1_host` (1.1.x/3.x)
 //!   or `SSL_set1_dnsname` (4.x, where `SSL_set1_host` is deprecated).
 
-use std::ffi::{CString, c_char, c_int, c_long, c_ulong, c_void};
+use std::ffi::{CString, c_char, c_int, c_long, c_uint, c_ulong, c_void};
 use std::io::{self, Read, Write};
+use std::slice;
 
-use crate::{Error, HandshakeError};
+use crate::{Error, HandshakeError, Protocol, TlsConnectorBuilder};
 
 // MARK: Common FFI - available in all supported OpenSSL versions
 unsafe extern "C" {
@@ -39,16 +40,64 @@ unsafe extern "C" {
     fn SSL_free(ssl: *mut c_void);
     fn SSL_set_bio(ssl: *mut c_void, rbio: *mut c_void, wbio: *mut c_void);
     fn SSL_connect(ssl: *mut c_void) -> c_int;
+    fn SSL_accept(ssl: *mut c_void) -> c_int;
     fn SSL_read(ssl: *mut c_void, buf: *mut c_void, num: c_int) -> c_int;
     fn SSL_write(ssl: *mut c_void, buf: *const c_void, num: c_int) -> c_int;
     fn SSL_shutdown(ssl: *mut c_void) -> c_int;
     fn SSL_get_error(ssl: *const c_void, ret: c_int) -> c_int;
     fn SSL_ctrl(ssl: *mut c_void, cmd: c_int, larg: c_long, parg: *mut c_void) -> c_long;
+    fn SSL_CTX_use_certificate(ctx: *mut c_void, cert: *mut c_void) -> c_int;
+    fn SSL_CTX_use_PrivateKey(ctx: *mut c_void, pkey: *mut c_void) -> c_int;
+    fn SSL_CTX_check_private_key(ctx: *const c_void) -> c_int;
+    fn SSL_CTX_get_cert_store(ctx: *const c_void) -> *mut c_void;
+    // ALPN (available since OpenSSL 1.0.2)
+    fn SSL_CTX_set_alpn_protos(ctx: *mut c_void, protos: *const u8, protos_len: c_uint) -> c_int;
+    fn SSL_get0_alpn_selected(ssl: *const c_void, data: *mut *const u8, len: *mut c_uint);
+    // Certificate and key loading
+    fn BIO_new_mem_buf(buf: *const c_void, len: c_int) -> *mut c_void;
+    fn BIO_free(bio: *mut c_void) -> c_int;
+    fn PEM_read_bio_X509(
+        bio: *mut c_void,
+        x: *mut *mut c_void,
+        cb: *const c_void,
+        u: *mut c_void,
+    ) -> *mut c_void;
+    fn PEM_read_bio_PrivateKey(
+        bio: *mut c_void,
+        x: *mut *mut c_void,
+        cb: *const c_void,
+        u: *mut c_void,
+    ) -> *mut c_void;
+    fn d2i_X509(a: *mut *mut c_void, pp: *mut *const u8, length: c_long) -> *mut c_void;
+    fn X509_STORE_add_cert(store: *mut c_void, cert: *mut c_void) -> c_int;
+    fn d2i_PKCS12(a: *mut *mut c_void, pp: *mut *const u8, length: c_long) -> *mut c_void;
+    fn PKCS12_parse(
+        p12: *mut c_void,
+        pass: *const c_char,
+        pkey: *mut *mut c_void,
+        cert: *mut *mut c_void,
+        ca: *mut *mut c_void,
+    ) -> c_int;
+    fn PKCS12_free(p12: *mut c_void);
+    fn X509_free(cert: *mut c_void);
+    fn EVP_PKEY_free(pkey: *mut c_void);
+    // STACK_OF functions were renamed with an OPENSSL_ prefix in 1.1.0
+    #[cfg_attr(not(openssl_v10x), link_name = "OPENSSL_sk_num")]
+    fn sk_num(stack: *const c_void) -> c_int;
+    #[cfg_attr(not(openssl_v10x), link_name = "OPENSSL_sk_value")]
+    fn sk_value(stack: *const c_void, index: c_int) -> *mut c_void;
+    #[cfg_attr(not(openssl_v10x), link_name = "OPENSSL_sk_free")]
+    fn sk_free(stack: *mut c_void);
     // OpenSSL error queue - available in all versions
     fn ERR_get_error() -> c_ulong;
     fn ERR_error_string(err: c_ulong, buf: *mut c_char) -> *mut c_char;
+    fn ERR_clear_error();
 }
 
+type PemReadFn =
+    unsafe extern "C" fn(*mut c_void, *mut *mut c_void, *const c_void, *mut c_void) -> *mut c_void;
+type HandshakeFn = unsafe extern "C" fn(*mut c_void) -> c_int;
+
 // Common constants
 const SSL_VERIFY_NONE: c_int = 0x00;
 const SSL_VERIFY_PEER: c_int = 0x01;
@@ -59,6 +108,8 @@ const SSL_ERROR_WANT_WRITE: c_int = 3;
 // SSL_set_tlsext_host_name macro equivalent (SSL_ctrl cmd)
 const SSL_CTRL_SET_TLSEXT_HOSTNAME: c_int = 55;
 const TLSEXT_NAMETYPE_HOST_NAME: c_long = 0;
+// SSL_CTX_add1_chain_cert macro equivalent (SSL_CTX_ctrl cmd, available since 1.0.2)
+const SSL_CTRL_CHAIN_CERT: c_int = 89;
 
 // Drain the OpenSSL per-thread error queue into a human-readable string.
 fn openssl_error_string() -> String {
@@ -87,6 +138,250 @@ fn ssl_error(context: &str, code: c_int) -> Error {
     }
 }
 
+fn new_ssl(ctx: *mut c_void) -> Result<*mut c_void, Error> {
+    // SAFETY: ctx is a non-null SSL_CTX owned by the connector or acceptor.
+    let ssl = unsafe { SSL_new(ctx) };
+    if ssl.is_null() {
+        return Err(Error(format!(
+            "Failed to create SSL: {}",
+            openssl_error_string()
+        )));
+    }
+    Ok(ssl)
+}
+
+// Read all consecutive PEM objects of one kind from a memory buffer.
+fn read_pem_objects(data: &[u8], read: PemReadFn) -> Vec<*mut c_void> {
+    let mut objects = Vec::new();
+    // SAFETY: data is a valid slice; the read-only memory BIO does not outlive it.
+    let bio = unsafe { BIO_new_mem_buf(data.as_ptr() as *const c_void, data.len() as c_int) };
+    if bio.is_null() {
+        return objects;
+    }
+    loop {
+        // SAFETY: bio is non-null; null out/callback/userdata arguments request a new object.
+        let object = unsafe {
+            read(
+                bio,
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+            )
+        };
+        if object.is_null() {
+            break;
+        }
+        objects.push(object);
+    }
+    // SAFETY: bio is non-null; the final failed read leaves a "no start line" error we discard.
+    unsafe {
+        BIO_free(bio);
+        ERR_clear_error();
+    }
+    objects
+}
+
+// Configure certificate verification, extra root certificates and ALPN on a client SSL_CTX.
+fn configure_connector(ctx: *mut c_void, builder: &TlsConnectorBuilder) -> Result<(), Error> {
+    // SAFETY: ctx is a non-null SSL_CTX; a null callback selects the default verification.
+    unsafe {
+        SSL_CTX_set_verify(
+            ctx,
+            if builder.accept_invalid_certs {
+                SSL_VERIFY_NONE
+            } else {
+                SSL_VERIFY_PEER
+            },
+            std::ptr::null(),
+        );
+    }
+    if !builder.accept_invalid_certs {
+        // SAFETY: ctx is a non-null SSL_CTX.
+        if !builder.disable_built_in_roots && unsafe { SSL_CTX_set_default_verify_paths(ctx) } != 1
+        {
+            return Err(Error("Failed to load CA certificates".to_string()));
+        }
+        for cert in &builder.root_certificates {
+            let mut der_ptr = cert.0.as_ptr();
+            // SAFETY: der_ptr and the length describe the certificate's DER bytes.
+            let x509 =
+                unsafe { d2i_X509(std::ptr::null_mut(), &mut der_ptr, cert.0.len() as c_long) };
+            if x509.is_null() {
+                return Err(Error(format!(
+                    "Invalid root certificate: {}",
+                    openssl_error_string()
+                )));
+            }
+            // SAFETY: ctx and x509 are non-null; the store takes its own reference to x509.
+            let ok = unsafe {
+                let ok = X509_STORE_add_cert(SSL_CTX_get_cert_store(ctx), x509);
+                X509_free(x509);
+                ok
+            };
+            if ok != 1 {
+                return Err(Error(format!(
+                    "Failed to add root certificate: {}",
+                    openssl_error_string()
+                )));
+            }
+        }
+    }
+
+    let alpn = builder.alpn_wire_format();
+    if !alpn.is_empty() {
+        // SAFETY: ctx is non-null; alpn is a valid buffer that OpenSSL copies.
+        let status = unsafe { SSL_CTX_set_alpn_protos(ctx, alpn.as_ptr(), alpn.len() as c_uint) };
+        // SSL_CTX_set_alpn_protos returns 0 on success, unlike most OpenSSL functions
+        if status != 0 {
+            return Err(Error("Failed to set ALPN protocols".to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn negotiated_alpn(ssl: *mut c_void) -> Option<Vec<u8>> {
+    let mut data: *const u8 = std::ptr::null();
+    let mut len: c_uint = 0;
+    // SAFETY: ssl is non-null; data/len are valid output locations.
+    unsafe { SSL_get0_alpn_selected(ssl, &mut data, &mut len) };
+    if data.is_null() || len == 0 {
+        return None;
+    }
+    // SAFETY: OpenSSL returned a pointer to len bytes owned by ssl.
+    Some(unsafe { slice::from_raw_parts(data, len as usize) }.to_vec())
+}
+
+// MARK: Identity
+/// A certificate, private key and optional certificate chain for a TlsAcceptor
+pub struct Identity {
+    cert: *mut c_void,
+    key: *mut c_void,
+    chain: Vec<*mut c_void>,
+}
+
+// SAFETY: X509 and EVP_PKEY objects are reference-counted and never mutated after loading.
+unsafe impl Send for Identity {}
+// SAFETY: X509 and EVP_PKEY objects are reference-counted and never mutated after loading.
+unsafe impl Sync for Identity {}
+
+impl Identity {
+    /// Parse a DER-encoded PKCS#12 archive containing a certificate, private key and chain
+    pub fn from_pkcs12(der: &[u8], password: &str) -> Result<Self, Error> {
+        let password_c =
+            CString::new(password).map_err(|_| Error("Invalid PKCS#12 password".to_string()))?;
+        let mut der_ptr = der.as_ptr();
+        // SAFETY: der_ptr and der.len() describe a valid slice that d2i_PKCS12 only reads from.
+        let p12 = unsafe { d2i_PKCS12(std::ptr::null_mut(), &mut der_ptr, der.len() as c_long) };
+        if p12.is_null() {
+            return Err(Error(format!(
+                "Failed to parse PKCS#12 archive: {}",
+                openssl_error_string()
+            )));
+        }
+
+        let mut cert = std::ptr::null_mut();
+        let mut key = std::ptr::null_mut();
+        let mut ca = std::ptr::null_mut();
+        // SAFETY: p12 is non-null; password_c is a valid C string; outputs are valid locals.
+        let ok = unsafe { PKCS12_parse(p12, password_c.as_ptr(), &mut key, &mut cert, &mut ca) };
+        // SAFETY: p12 is non-null and no longer needed after parsing.
+        unsafe { PKCS12_free(p12) };
+        if ok != 1 {
+            return Err(Error(format!(
+                "Failed to decrypt PKCS#12 archive: {}",
+                openssl_error_string()
+            )));
+        }
+
+        let mut chain = Vec::new();
+        if !ca.is_null() {
+            // SAFETY: ca is a non-null STACK_OF(X509); its certificates are moved into chain
+            // before the stack itself is freed.
+            unsafe {
+                for index in 0..sk_num(ca) {
+                    chain.push(sk_value(ca, index));
+                }
+                sk_free(ca);
+            }
+        }
+        let identity = Self { cert, key, chain };
+        if identity.cert.is_null() || identity.key.is_null() {
+            return Err(Error(
+                "PKCS#12 archive does not contain a certificate and private key".to_string(),
+            ));
+        }
+        Ok(identity)
+    }
+
+    /// Parse a PEM-encoded certificate chain and a PEM-encoded PKCS#8 private key
+    pub fn from_pkcs8(pem: &[u8], key: &[u8]) -> Result<Self, Error> {
+        let mut certs = read_pem_objects(pem, PEM_read_bio_X509).into_iter();
+        let mut keys = read_pem_objects(key, PEM_read_bio_PrivateKey).into_iter();
+        let identity = Self {
+            cert: certs.next().unwrap_or(std::ptr::null_mut()),
+            key: keys.next().unwrap_or(std::ptr::null_mut()),
+            chain: certs.collect(),
+        };
+        for key in keys {
+            // SAFETY: key is an EVP_PKEY returned by PEM_read_bio_PrivateKey and not used elsewhere.
+            unsafe { EVP_PKEY_free(key) };
+        }
+        if identity.cert.is_null() {
+            return Err(Error("No certificate found in PEM data".to_string()));
+        }
+        if identity.key.is_null() {
+            return Err(Error("No private key found in PEM data".to_string()));
+        }
+        Ok(identity)
+    }
+}
+
+impl Drop for Identity {
+    fn drop(&mut self) {
+        // SAFETY: all objects are owned by this identity; X509_free and EVP_PKEY_free accept null.
+        unsafe {
+            X509_free(self.cert);
+            EVP_PKEY_free(self.key);
+            for cert in &self.chain {
+                X509_free(*cert);
+            }
+        }
+    }
+}
+
+// Install an identity into a server SSL_CTX; OpenSSL takes its own references to all objects.
+fn use_identity(ctx: *mut c_void, identity: &Identity) -> Result<(), Error> {
+    // SAFETY: ctx is a non-null SSL_CTX; the identity's X509/EVP_PKEY objects are valid.
+    unsafe {
+        if SSL_CTX_use_certificate(ctx, identity.cert) != 1 {
+            return Err(Error(format!(
+                "Failed to use certificate: {}",
+                openssl_error_string()
+            )));
+        }
+        if SSL_CTX_use_PrivateKey(ctx, identity.key) != 1 {
+            return Err(Error(format!(
+                "Failed to use private key: {}",
+                openssl_error_string()
+            )));
+        }
+        for cert in &identity.chain {
+            if SSL_CTX_ctrl(ctx, SSL_CTRL_CHAIN_CERT, 1, *cert) != 1 {
+                return Err(Error(format!(
+                    "Failed to add chain certificate: {}",
+                    openssl_error_string()
+                )));
+            }
+        }
+        if SSL_CTX_check_private_key(ctx) != 1 {
+            return Err(Error(
+                "Private key does not match the certificate".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // MARK: OpenSSL 1.0.x path
 // Uses BIO_new_bio_pair + explicit I/O pumping because the BIO method API
@@ -95,6 +390,7 @@ fn ssl_error(context: &str, code: c_int) -> Error {
 #[cfg(openssl_v10x)]
 unsafe extern "C" {
     fn SSLv23_client_method() -> *const c_void;
+    fn SSLv23_server_method() -> *const c_void;
     // OpenSSL 1.0.x requires explicit initialization before any SSL use
     fn SSL_library_init() -> c_int;
     fn SSL_load_error_strings();
@@ -108,10 +404,8 @@ unsafe extern "C" {
     ) -> c_int;
     fn BIO_read(bio: *mut c_void, buf: *mut c_void, len: c_int) -> c_int;
     fn BIO_write(bio: *mut c_void, buf: *const c_void, len: c_int) -> c_int;
-    fn BIO_free(bio: *mut c_void) -> c_int;
     // X509 hostname verification (available since OpenSSL 1.0.2)
     fn SSL_get_peer_certificate(ssl: *const c_void) -> *mut c_void;
-    fn X509_free(cert: *mut c_void);
     fn X509_check_host(
         cert: *mut c_void,
         chk: *const c_char,
@@ -132,6 +426,8 @@ const SSL_OP_NO_SSLV3: c_long = 0x02000000;
 const SSL_OP_NO_TLSV1: c_long = 0x04000000;
 #[cfg(openssl_v10x)]
 const SSL_OP_NO_TLSV1_1: c_long = 0x10000000;
+#[cfg(openssl_v10x)]
+const SSL_OP_NO_TLSV1_2: c_long = 0x08000000;
 
 // Drain all pending encrypted output from network_bio to the TCP stream.
 #[cfg(openssl_v10x)]
@@ -163,12 +459,129 @@ fn feed_network_bio(bio: *mut c_void, stream: &mut impl Read) -> io::Result<usiz
     Ok(n)
 }
 
+// OpenSSL 1.0.x requires explicit one-time initialization; 1.1+ does this automatically.
+#[cfg(openssl_v10x)]
+fn init_openssl() {
+    static INIT: std::sync::Once = std::sync::Once::new();
+    INIT.call_once(|| {
+        // SAFETY: these init functions are idempotent; Once ensures single execution.
+        unsafe {
+            SSL_library_init();
+            SSL_load_error_strings();
+            OPENSSL_add_all_algorithms_noconf();
+        }
+    });
+}
+
+// 1.0.x has no min/max version API, so disable every protocol outside the range; SSLv2 is
+// always disabled and TLS 1.3 is not supported at all.
+#[cfg(openssl_v10x)]
+fn set_protocol_versions(
+    ctx: *mut c_void,
+    min: Option<Protocol>,
+    max: Option<Protocol>,
+) -> Result<(), Error> {
+    if min == Some(Protocol::Tlsv13) {
+        return Err(Error("TLS 1.3 requires OpenSSL 1.1.1+".to_string()));
+    }
+    let mut opts = SSL_OP_NO_SSLV2;
+    for (protocol, flag) in [
+        (Protocol::Sslv3, SSL_OP_NO_SSLV3),
+        (Protocol::Tlsv10, SSL_OP_NO_TLSV1),
+        (Protocol::Tlsv11, SSL_OP_NO_TLSV1_1),
+        (Protocol::Tlsv12, SSL_OP_NO_TLSV1_2),
+    ] {
+        if min.is_some_and(|min| protocol < min) || max.is_some_and(|max| protocol > max) {
+            opts |= flag;
+        }
+    }
+    // SAFETY: ctx is a non-null SSL_CTX; SSL_CTRL_OPTIONS only sets option flags.
+    unsafe { SSL_CTX_ctrl(ctx, SSL_CTRL_OPTIONS, opts, std::ptr::null_mut()) };
+    Ok(())
+}
+
+// Run SSL_connect or SSL_accept to completion, pumping the BIO pair over the stream.
+#[cfg(openssl_v10x)]
+fn handshake<S: Read + Write>(
+    ssl: *mut c_void,
+    stream: S,
+    step: HandshakeFn,
+) -> Result<TlsStream<S>, Error> {
+    // Create a BIO pair: SSL holds internal_bio; we pump via network_bio
+    let mut internal_bio: *mut c_void = std::ptr::null_mut();
+    let mut network_bio: *mut c_void = std::ptr::null_mut();
+    // SAFETY: &mut internal_bio and &mut network_bio are valid output pointers; buf sizes of 0
+    // request the default OpenSSL buffer size.
+    if unsafe { BIO_new_bio_pair(&mut internal_bio, 0, &mut network_bio, 0) } != 1 {
+        // SAFETY: ssl is non-null; not freed elsewhere.
+        unsafe { SSL_free(ssl) };
+        return Err(Error(format!(
+            "Failed to create BIO pair: {}",
+            openssl_error_string()
+        )));
+    }
+    // SSL_set_bio takes ownership of internal_bio; SSL_free will release it
+    // SAFETY: ssl, internal_bio are non-null; ownership of internal_bio transfers to ssl.
+    unsafe { SSL_set_bio(ssl, internal_bio, internal_bio) };
+
+    let mut stream = Box::new(stream);
+
+    // Handshake inside a closure for clean cleanup
+    let result: Result<(), Error> = (|| {
+        loop {
+            // SAFETY: ssl is non-null and configured with a BIO pair.
+            let ret = unsafe { step(ssl) };
+            if ret == 1 {
+                break;
+            }
+            // SAFETY: ssl is non-null.
+            let err = unsafe { SSL_get_error(ssl, ret) };
+            match err {
+                SSL_ERROR_WANT_READ => {
+                    flush_network_bio(network_bio, &mut *stream)
+                        .map_err(|e| Error(e.to_string()))?;
+                    let n = feed_network_bio(network_bio, &mut *stream)
+                        .map_err(|e| Error(e.to_string()))?;
+                    if n == 0 {
+                        return Err(Error("TLS handshake: unexpected EOF".to_string()));
+                    }
+                }
+                SSL_ERROR_WANT_WRITE => {
+                    flush_network_bio(network_bio, &mut *stream)
+                        .map_err(|e| Error(e.to_string()))?;
+                }
+                _ => {
+                    return Err(ssl_error("TLS handshake failed", err));
+                }
+            }
+        }
+        // Flush any final handshake messages (e.g. Finished)
+        flush_network_bio(network_bio, &mut *stream).map_err(|e| Error(e.to_string()))
+    })();
+
+    if let Err(e) = result {
+        // SAFETY: ssl and network_bio are non-null; not freed elsewhere.
+        unsafe {
+            SSL_free(ssl);
+            BIO_free(network_bio);
+        }
+        return Err(e);
+    }
+
+    Ok(TlsStream {
+        ssl,
+        network_bio,
+        stream,
+    })
+}
+
 // MARK: TlsConnector (1.0.x)
 #[cfg(openssl_v10x)]
 /// A TLS connector using OpenSSL 1.0.x
 pub struct TlsConnector {
     ctx: *mut c_void,
-    accept_invalid_certs: bool,
+    verify_hostname: bool,
+    use_sni: bool,
 }
 
 #[cfg(openssl_v10x)]
@@ -182,25 +595,11 @@ unsafe impl Sync for TlsConnector {}
 impl TlsConnector {
     /// Create a new TLS connector
     pub fn new() -> Result<Self, Error> {
-        Self::new_with_accept_invalid_certs(false)
-    }
-
-    #[cfg(test)]
-    pub(crate) fn new_danger_accept_invalid_certs() -> Result<Self, Error> {
-        Self::new_with_accept_invalid_certs(true)
+        Self::from_builder(&TlsConnectorBuilder::default())
     }
 
-    fn new_with_accept_invalid_certs(accept_invalid_certs: bool) -> Result<Self, Error> {
-        // OpenSSL 1.0.x requires explicit one-time initialization; 1.1+ does this automatically.
-        static INIT: std::sync::Once = std::sync::Once::new();
-        INIT.call_once(|| {
-            // SAFETY: these init functions are idempotent; Once ensures single execution.
-            unsafe {
-                SSL_library_init();
-                SSL_load_error_strings();
-                OPENSSL_add_all_algorithms_noconf();
-            }
-        });
+    pub(crate) fn from_builder(builder: &TlsConnectorBuilder) -> Result<Self, Error> {
+        init_openssl();
 
         // SAFETY: SSLv23_client_method returns a valid method pointer valid for the process lifetime.
         let ctx = unsafe { SSL_CTX_new(SSLv23_client_method()) };
@@ -210,29 +609,14 @@ impl TlsConnector {
                 openssl_error_string()
             )));
         }
-        // SAFETY: ctx is non-null; constants and null callback are valid for SSL_CTX_ctrl/set_verify.
-        unsafe {
-            // Disable SSLv2, SSLv3, TLS 1.0 and TLS 1.1 to require TLS 1.2+
-            let opts = SSL_OP_NO_SSLV2 | SSL_OP_NO_SSLV3 | SSL_OP_NO_TLSV1 | SSL_OP_NO_TLSV1_1;
-            SSL_CTX_ctrl(ctx, SSL_CTRL_OPTIONS, opts, std::ptr::null_mut());
-            SSL_CTX_set_verify(
-                ctx,
-                if accept_invalid_certs {
-                    SSL_VERIFY_NONE
-                } else {
-                    SSL_VERIFY_PEER
-                },
-                std::ptr::null(),
-            );
-            if !accept_invalid_certs && SSL_CTX_set_default_verify_paths(ctx) != 1 {
-                SSL_CTX_free(ctx);
-                return Err(Error("Failed to load CA certificates".to_string()));
-            }
-        }
-        Ok(Self {
+        let connector = Self {
             ctx,
-            accept_invalid_certs,
-        })
+            verify_hostname: !builder.accept_invalid_certs && !builder.accept_invalid_hostnames,
+            use_sni: builder.use_sni,
+        };
+        set_protocol_versions(ctx, builder.min_protocol, builder.max_protocol)?;
+        configure_connector(ctx, builder)?;
+        Ok(connector)
     }
 
     /// Perform a TLS handshake over the given stream
@@ -250,83 +634,29 @@ impl TlsConnector {
         domain: &str,
         stream: S,
     ) -> Result<TlsStream<S>, Error> {
-        // SAFETY: self.ctx is a non-null SSL_CTX valid for the connector's lifetime.
-        let ssl = unsafe { SSL_new(self.ctx) };
-        if ssl.is_null() {
-            return Err(Error(format!(
-                "Failed to create SSL: {}",
-                openssl_error_string()
-            )));
-        }
-
         let domain_c =
             CString::new(domain).map_err(|_| Error("Invalid domain name".to_string()))?;
+        let ssl = new_ssl(self.ctx)?;
 
-        // Set SNI hostname
-        // SAFETY: ssl is non-null; domain_c is a valid C string valid for the call duration.
-        unsafe {
-            SSL_ctrl(
-                ssl,
-                SSL_CTRL_SET_TLSEXT_HOSTNAME,
-                TLSEXT_NAMETYPE_HOST_NAME,
-                domain_c.as_ptr() as *mut c_void,
-            );
-        }
-
-        // Create a BIO pair: SSL holds internal_bio; we pump via network_bio
-        let mut internal_bio: *mut c_void = std::ptr::null_mut();
-        let mut network_bio: *mut c_void = std::ptr::null_mut();
-        // SAFETY: &mut internal_bio and &mut network_bio are valid output pointers; buf sizes of 0
-        // request the default OpenSSL buffer size.
-        if unsafe { BIO_new_bio_pair(&mut internal_bio, 0, &mut network_bio, 0) } != 1 {
-            // SAFETY: ssl is non-null; not freed elsewhere.
-            unsafe { SSL_free(ssl) };
-            return Err(Error(format!(
-                "Failed to create BIO pair: {}",
-                openssl_error_string()
-            )));
+        if self.use_sni {
+            // Set SNI hostname
+            // SAFETY: ssl is non-null; domain_c is a valid C string valid for the call duration.
+            unsafe {
+                SSL_ctrl(
+                    ssl,
+                    SSL_CTRL_SET_TLSEXT_HOSTNAME,
+                    TLSEXT_NAMETYPE_HOST_NAME,
+                    domain_c.as_ptr() as *mut c_void,
+                );
+            }
         }
-        // SSL_set_bio takes ownership of internal_bio; SSL_free will release it
-        // SAFETY: ssl, internal_bio are non-null; ownership of internal_bio transfers to ssl.
-        unsafe { SSL_set_bio(ssl, internal_bio, internal_bio) };
-
-        let mut stream = Box::new(stream);
 
-        // Handshake + hostname verification inside a closure for clean cleanup
-        let result: Result<(), Error> = (|| {
-            loop {
-                // SAFETY: ssl is non-null and configured with a BIO pair.
-                let ret = unsafe { SSL_connect(ssl) };
-                if ret == 1 {
-                    break;
-                }
-                // SAFETY: ssl is non-null.
-                let err = unsafe { SSL_get_error(ssl, ret) };
-                match err {
-                    SSL_ERROR_WANT_READ => {
-                        flush_network_bio(network_bio, &mut *stream)
-                            .map_err(|e| Error(e.to_string()))?;
-                        let n = feed_network_bio(network_bio, &mut *stream)
-                            .map_err(|e| Error(e.to_string()))?;
-                        if n == 0 {
-                            return Err(Error("TLS handshake: unexpected EOF".to_string()));
-                        }
-                    }
-                    SSL_ERROR_WANT_WRITE => {
-                        flush_network_bio(network_bio, &mut *stream)
-                            .map_err(|e| Error(e.to_string()))?;
-                    }
-                    _ => {
-                        return Err(ssl_error("TLS handshake failed", err));
-                    }
-                }
-            }
-            // Flush any final handshake messages (e.g. Finished)
-            flush_network_bio(network_bio, &mut *stream).map_err(|e| Error(e.to_string()))?;
+        let stream = handshake(ssl, stream, SSL_connect)?;
 
+        if self.verify_hostname {
             // Verify hostname via X509_check_host (OpenSSL 1.0.2+)
-            // SAFETY: ssl is non-null; the returned cert pointer must be freed with X509_free.
-            let cert = unsafe { SSL_get_peer_certificate(ssl) };
+            // SAFETY: stream.ssl is non-null; the returned cert pointer must be freed with X509_free.
+            let cert = unsafe { SSL_get_peer_certificate(stream.ssl) };
             if cert.is_null() {
                 return Err(Error("No peer certificate".to_string()));
             }
@@ -342,28 +672,13 @@ impl TlsConnector {
             };
             // SAFETY: cert is a non-null X509 object returned by SSL_get_peer_certificate.
             unsafe { X509_free(cert) };
-            if ok != 1 && !self.accept_invalid_certs {
+            if ok != 1 {
                 return Err(Error(format!(
                     "Hostname verification failed for '{domain}'"
                 )));
             }
-            Ok(())
-        })();
-
-        if let Err(e) = result {
-            // SAFETY: ssl and network_bio are non-null; not freed elsewhere.
-            unsafe {
-                SSL_free(ssl);
-                BIO_free(network_bio);
-            }
-            return Err(e);
         }
-
-        Ok(TlsStream {
-            ssl,
-            network_bio,
-            stream,
-        })
+        Ok(stream)
     }
 }
 
@@ -382,6 +697,56 @@ impl Drop for TlsConnector {
     }
 }
 
+// MARK: TlsAcceptor (1.0.x)
+#[cfg(openssl_v10x)]
+/// A TLS acceptor using OpenSSL 1.0.x
+pub struct TlsAcceptor {
+    ctx: *mut c_void,
+}
+
+#[cfg(openssl_v10x)]
+// SAFETY: SSL_CTX is reference-counted by OpenSSL and is safe to share across threads.
+unsafe impl Send for TlsAcceptor {}
+#[cfg(openssl_v10x)]
+// SAFETY: SSL_CTX is reference-counted by OpenSSL and is safe to use from multiple threads.
+unsafe impl Sync for TlsAcceptor {}
+
+#[cfg(openssl_v10x)]
+impl TlsAcceptor {
+    /// Create a new TLS acceptor with the given identity
+    pub fn new(identity: Identity) -> Result<Self, Error> {
+        init_openssl();
+
+        // SAFETY: SSLv23_server_method returns a valid method pointer valid for the process lifetime.
+        let ctx = unsafe { SSL_CTX_new(SSLv23_server_method()) };
+        if ctx.is_null() {
+            return Err(Error(format!(
+                "Failed to create SSL_CTX: {}",
+                openssl_error_string()
+            )));
+        }
+        let acceptor = Self { ctx };
+        set_protocol_versions(acceptor.ctx, Some(Protocol::Tlsv12), None)?;
+        use_identity(acceptor.ctx, &identity)?;
+        Ok(acceptor)
+    }
+
+    /// Perform a server-side TLS handshake over the given stream
+    pub fn accept<S: Read + Write>(&self, stream: S) -> Result<TlsStream<S>, HandshakeError<S>> {
+        new_ssl(self.ctx)
+            .and_then(|ssl| handshake(ssl, stream, SSL_accept))
+            .map_err(HandshakeError::new)
+    }
+}
+
+#[cfg(openssl_v10x)]
+impl Drop for TlsAcceptor {
+    fn drop(&mut self) {
+        // SAFETY: self.ctx is non-null; Drop is called at most once.
+        unsafe { SSL_CTX_free(self.ctx) };
+    }
+}
+
 // MARK: TlsStream (1.0.x)
 #[cfg(openssl_v10x)]
 /// A TLS stream backed by OpenSSL 1.0.x (BIO pair I/O pump)
@@ -398,6 +763,11 @@ unsafe impl<S: Send> Send for TlsStream<S> {}
 
 #[cfg(openssl_v10x)]
 impl<S> TlsStream<S> {
+    /// Returns the ALPN protocol negotiated during the handshake
+    pub fn negotiated_alpn(&self) -> Result<Option<Vec<u8>>, Error> {
+        Ok(negotiated_alpn(self.ssl))
+    }
+
     /// Returns a reference to the underlying stream
     pub fn get_ref(&self) -> &S {
         &self.stream
@@ -514,6 +884,7 @@ impl<S> Drop for TlsStream<S> {
 #[cfg(not(openssl_v10x))]
 unsafe extern "C" {
     fn TLS_client_method() -> *const c_void;
+    fn TLS_server_method() -> *const c_void;
     // SSL_set1_host: available in 1.1.x and 3.x (deprecated in 4.x)
     #[cfg(not(openssl_v4xx))]
     fn SSL_set1_host(ssl: *mut c_void, hostname: *const c_char) -> c_int;
@@ -555,6 +926,12 @@ const SSL_CTRL_SET_MIN_PROTO_VERSION: c_int = 123;
 #[cfg(not(openssl_v10x))]
 const SSL_CTRL_SET_MAX_PROTO_VERSION: c_int = 124;
 #[cfg(not(openssl_v10x))]
+const SSL3_VERSION: c_long = 0x0300;
+#[cfg(not(openssl_v10x))]
+const TLS1_VERSION: c_long = 0x0301;
+#[cfg(not(openssl_v10x))]
+const TLS1_1_VERSION: c_long = 0x0302;
+#[cfg(not(openssl_v10x))]
 const TLS1_2_VERSION: c_long = 0x0303;
 #[cfg(not(openssl_v10x))]
 const TLS1_3_VERSION: c_long = 0x0304;
@@ -650,7 +1027,7 @@ unsafe extern "C" fn bio_write(bio: *mut c_void, buf: *const c_char, len: c_int)
 }
 
 #[cfg(not(openssl_v10x))]
-unsafe extern "C" fn bio_ctrl(
+const unsafe extern "C" fn bio_ctrl(
     _bio: *mut c_void,
     cmd: c_int,
     _larg: c_long,
@@ -667,21 +1044,19 @@ unsafe extern "C" fn bio_create(bio: *mut c_void) -> c_int {
 }
 
 #[cfg(not(openssl_v10x))]
-unsafe extern "C" fn bio_destroy(_bio: *mut c_void) -> c_int {
+const unsafe extern "C" fn bio_destroy(_bio: *mut c_void) -> c_int {
     // IoFuncs is owned by TlsStream; do not free here
     1
 }
 
 #[cfg(not(openssl_v10x))]
 unsafe fn do_read<S: Read>(ptr: *mut c_void, buf: *mut u8, len: usize) -> io::Result<usize> {
-    use std::slice;
     // SAFETY: ptr derives from Box<S>.as_ref() in connect_inner; buf/len come from OpenSSL.
     unsafe { (*(ptr as *mut S)).read(slice::from_raw_parts_mut(buf, len)) }
 }
 
 #[cfg(not(openssl_v10x))]
 unsafe fn do_write<S: Write>(ptr: *mut c_void, buf: *const u8, len: usize) -> io::Result<usize> {
-    use std::slice;
     // SAFETY: ptr derives from Box<S>.as_ref() in connect_inner; buf/len come from OpenSSL.
     unsafe { (*(ptr as *mut S)).write(slice::from_raw_parts(buf, len)) }
 }
@@ -706,6 +1081,7 @@ struct BioMethodPtr(*mut c_void);
 // SAFETY: BIO_METHOD is immutable after creation; OpenSSL guarantees thread safety for reads.
 #[cfg(not(openssl_v10x))]
 unsafe impl Send for BioMethodPtr {}
+// SAFETY: See above, the shared BIO_METHOD is only read after creation.
 #[cfg(not(openssl_v10x))]
 unsafe impl Sync for BioMethodPtr {}
 
@@ -721,12 +1097,111 @@ impl Drop for BioMethodPtr {
 static BIO_METHOD: std::sync::LazyLock<BioMethodPtr> =
     std::sync::LazyLock::new(|| BioMethodPtr(make_bio_method()));
 
+// A version of 0 means the lowest or highest version the library supports
+#[cfg(not(openssl_v10x))]
+const fn protocol_version(protocol: Option<Protocol>) -> c_long {
+    match protocol {
+        None => 0,
+        Some(Protocol::Sslv3) => SSL3_VERSION,
+        Some(Protocol::Tlsv10) => TLS1_VERSION,
+        Some(Protocol::Tlsv11) => TLS1_1_VERSION,
+        Some(Protocol::Tlsv12) => TLS1_2_VERSION,
+        Some(Protocol::Tlsv13) => TLS1_3_VERSION,
+    }
+}
+
+#[cfg(not(openssl_v10x))]
+fn set_protocol_versions(
+    ctx: *mut c_void,
+    min: Option<Protocol>,
+    max: Option<Protocol>,
+) -> Result<(), Error> {
+    // SAFETY: ctx is a non-null SSL_CTX; version constants are valid arguments.
+    let ok = unsafe {
+        SSL_CTX_ctrl(
+            ctx,
+            SSL_CTRL_SET_MIN_PROTO_VERSION,
+            protocol_version(min),
+            std::ptr::null_mut(),
+        ) == 1
+            && SSL_CTX_ctrl(
+                ctx,
+                SSL_CTRL_SET_MAX_PROTO_VERSION,
+                protocol_version(max),
+                std::ptr::null_mut(),
+            ) == 1
+    };
+    if !ok {
+        return Err(Error(format!(
+            "Unsupported protocol version: {}",
+            openssl_error_string()
+        )));
+    }
+    Ok(())
+}
+
+// Attach the stream to ssl through the custom BIO and run SSL_connect or SSL_accept.
+#[cfg(not(openssl_v10x))]
+fn handshake<S: Read + Write>(
+    ssl: *mut c_void,
+    stream: S,
+    step: HandshakeFn,
+) -> Result<TlsStream<S>, Error> {
+    let stream = Box::new(stream);
+    let io = Box::new(IoFuncs {
+        stream_ptr: stream.as_ref() as *const S as *mut c_void,
+        read_fn: do_read::<S>,
+        write_fn: do_write::<S>,
+    });
+
+    // SAFETY: BIO_METHOD.0 is non-null (initialized by LazyLock).
+    let bio = unsafe { BIO_new(BIO_METHOD.0) };
+    if bio.is_null() {
+        // SAFETY: ssl is non-null; not freed elsewhere.
+        unsafe { SSL_free(ssl) };
+        return Err(Error(format!(
+            "Failed to create BIO: {}",
+            openssl_error_string()
+        )));
+    }
+    // SAFETY: bio and ssl are non-null; ownership of bio (rbio=wbio) transfers to ssl.
+    unsafe {
+        BIO_set_data(bio, io.as_ref() as *const IoFuncs as *mut c_void);
+        SSL_set_bio(ssl, bio, bio);
+    }
+
+    loop {
+        // SAFETY: ssl is non-null and configured with a BIO.
+        let ret = unsafe { step(ssl) };
+        if ret == 1 {
+            break;
+        }
+        // SAFETY: ssl is non-null.
+        let err = unsafe { SSL_get_error(ssl, ret) };
+        match err {
+            SSL_ERROR_WANT_READ | SSL_ERROR_WANT_WRITE => continue,
+            _ => {
+                // SAFETY: ssl is non-null; BIO is owned by ssl and freed via SSL_free.
+                unsafe { SSL_free(ssl) };
+                return Err(ssl_error("TLS handshake failed", err));
+            }
+        }
+    }
+
+    Ok(TlsStream {
+        ssl,
+        _io: io,
+        stream,
+    })
+}
+
 // MARK: TlsConnector (1.1.x / 3.x / 4.x)
 #[cfg(not(openssl_v10x))]
 /// A TLS connector using OpenSSL 1.1.x, 3.x or 4.x
 pub struct TlsConnector {
     ctx: *mut c_void,
-    accept_invalid_certs: bool,
+    verify_hostname: bool,
+    use_sni: bool,
 }
 
 #[cfg(not(openssl_v10x))]
@@ -740,15 +1215,10 @@ unsafe impl Sync for TlsConnector {}
 impl TlsConnector {
     /// Create a new TLS connector
     pub fn new() -> Result<Self, Error> {
-        Self::new_with_accept_invalid_certs(false)
+        Self::from_builder(&TlsConnectorBuilder::default())
     }
 
-    #[cfg(test)]
-    pub(crate) fn new_danger_accept_invalid_certs() -> Result<Self, Error> {
-        Self::new_with_accept_invalid_certs(true)
-    }
-
-    fn new_with_accept_invalid_certs(accept_invalid_certs: bool) -> Result<Self, Error> {
+    pub(crate) fn from_builder(builder: &TlsConnectorBuilder) -> Result<Self, Error> {
         // SAFETY: TLS_client_method returns a valid method pointer for the process lifetime.
         let ctx = unsafe { SSL_CTX_new(TLS_client_method()) };
         if ctx.is_null() {
@@ -757,39 +1227,14 @@ impl TlsConnector {
                 openssl_error_string()
             )));
         }
-        // SAFETY: ctx is non-null; version constants and null callback are valid arguments.
-        unsafe {
-            // Require TLS 1.2 minimum, allow up to TLS 1.3
-            SSL_CTX_ctrl(
-                ctx,
-                SSL_CTRL_SET_MIN_PROTO_VERSION,
-                TLS1_2_VERSION,
-                std::ptr::null_mut(),
-            );
-            SSL_CTX_ctrl(
-                ctx,
-                SSL_CTRL_SET_MAX_PROTO_VERSION,
-                TLS1_3_VERSION,
-                std::ptr::null_mut(),
-            );
-            SSL_CTX_set_verify(
-                ctx,
-                if accept_invalid_certs {
-                    SSL_VERIFY_NONE
-                } else {
-                    SSL_VERIFY_PEER
-                },
-                std::ptr::null(),
-            );
-            if !accept_invalid_certs && SSL_CTX_set_default_verify_paths(ctx) != 1 {
-                SSL_CTX_free(ctx);
-                return Err(Error("Failed to load CA certificates".to_string()));
-            }
-        }
-        Ok(Self {
+        let connector = Self {
             ctx,
-            accept_invalid_certs,
-        })
+            verify_hostname: !builder.accept_invalid_certs && !builder.accept_invalid_hostnames,
+            use_sni: builder.use_sni,
+        };
+        set_protocol_versions(ctx, builder.min_protocol, builder.max_protocol)?;
+        configure_connector(ctx, builder)?;
+        Ok(connector)
     }
 
     /// Perform a TLS handshake over the given stream
@@ -807,27 +1252,21 @@ impl TlsConnector {
         domain: &str,
         stream: S,
     ) -> Result<TlsStream<S>, Error> {
-        // SAFETY: self.ctx is a non-null SSL_CTX valid for the connector's lifetime.
-        let ssl = unsafe { SSL_new(self.ctx) };
-        if ssl.is_null() {
-            return Err(Error(format!(
-                "Failed to create SSL: {}",
-                openssl_error_string()
-            )));
-        }
-
         let domain_c =
             CString::new(domain).map_err(|_| Error("Invalid domain name".to_string()))?;
+        let ssl = new_ssl(self.ctx)?;
         // SAFETY: ssl is non-null; domain_c is a valid C string for the call duration.
         unsafe {
-            // Set SNI hostname
-            SSL_ctrl(
-                ssl,
-                SSL_CTRL_SET_TLSEXT_HOSTNAME,
-                TLSEXT_NAMETYPE_HOST_NAME,
-                domain_c.as_ptr() as *mut c_void,
-            );
-            if !self.accept_invalid_certs {
+            if self.use_sni {
+                // Set SNI hostname
+                SSL_ctrl(
+                    ssl,
+                    SSL_CTRL_SET_TLSEXT_HOSTNAME,
+                    TLSEXT_NAMETYPE_HOST_NAME,
+                    domain_c.as_ptr() as *mut c_void,
+                );
+            }
+            if self.verify_hostname {
                 // Enable built-in hostname verification against the certificate.
                 // SSL_set1_dnsname is the new name in OpenSSL 4.x; older versions use SSL_set1_host.
                 #[cfg(openssl_v4xx)]
@@ -840,53 +1279,7 @@ impl TlsConnector {
                 }
             }
         }
-
-        let stream = Box::new(stream);
-        let io = Box::new(IoFuncs {
-            stream_ptr: stream.as_ref() as *const S as *mut c_void,
-            read_fn: do_read::<S>,
-            write_fn: do_write::<S>,
-        });
-
-        // SAFETY: BIO_METHOD.0 is non-null (initialized by LazyLock).
-        let bio = unsafe { BIO_new(BIO_METHOD.0) };
-        if bio.is_null() {
-            // SAFETY: ssl is non-null; not freed elsewhere.
-            unsafe { SSL_free(ssl) };
-            return Err(Error(format!(
-                "Failed to create BIO: {}",
-                openssl_error_string()
-            )));
-        }
-        // SAFETY: bio and ssl are non-null; ownership of bio (rbio=wbio) transfers to ssl.
-        unsafe {
-            BIO_set_data(bio, io.as_ref() as *const IoFuncs as *mut c_void);
-            SSL_set_bio(ssl, bio, bio);
-        }
-
-        loop {
-            // SAFETY: ssl is non-null and configured with a BIO.
-            let ret = unsafe { SSL_connect(ssl) };
-            if ret == 1 {
-                break;
-            }
-            // SAFETY: ssl is non-null.
-            let err = unsafe { SSL_get_error(ssl, ret) };
-            match err {
-                SSL_ERROR_WANT_READ | SSL_ERROR_WANT_WRITE => continue,
-                _ => {
-                    // SAFETY: ssl is non-null; BIO is owned by ssl and freed via SSL_free.
-                    unsafe { SSL_free(ssl) };
-                    return Err(ssl_error("TLS handshake failed", err));
-                }
-            }
-        }
-
-        Ok(TlsStream {
-            ssl,
-            _io: io,
-            stream,
-        })
+        handshake(ssl, stream, SSL_connect)
     }
 }
 
@@ -905,6 +1298,54 @@ impl Drop for TlsConnector {
     }
 }
 
+// MARK: TlsAcceptor (1.1.x / 3.x / 4.x)
+#[cfg(not(openssl_v10x))]
+/// A TLS acceptor using OpenSSL 1.1.x, 3.x or 4.x
+pub struct TlsAcceptor {
+    ctx: *mut c_void,
+}
+
+#[cfg(not(openssl_v10x))]
+// SAFETY: SSL_CTX is reference-counted and thread-safe for concurrent use.
+unsafe impl Send for TlsAcceptor {}
+#[cfg(not(openssl_v10x))]
+// SAFETY: SSL_CTX is reference-counted and thread-safe for concurrent use.
+unsafe impl Sync for TlsAcceptor {}
+
+#[cfg(not(openssl_v10x))]
+impl TlsAcceptor {
+    /// Create a new TLS acceptor with the given identity
+    pub fn new(identity: Identity) -> Result<Self, Error> {
+        // SAFETY: TLS_server_method returns a valid method pointer for the process lifetime.
+        let ctx = unsafe { SSL_CTX_new(TLS_server_method()) };
+        if ctx.is_null() {
+            return Err(Error(format!(
+                "Failed to create SSL_CTX: {}",
+                openssl_error_string()
+            )));
+        }
+        let acceptor = Self { ctx };
+        set_protocol_versions(acceptor.ctx, Some(Protocol::Tlsv12), None)?;
+        use_identity(acceptor.ctx, &identity)?;
+        Ok(acceptor)
+    }
+
+    /// Perform a server-side TLS handshake over the given stream
+    pub fn accept<S: Read + Write>(&self, stream: S) -> Result<TlsStream<S>, HandshakeError<S>> {
+        new_ssl(self.ctx)
+            .and_then(|ssl| handshake(ssl, stream, SSL_accept))
+            .map_err(HandshakeError::new)
+    }
+}
+
+#[cfg(not(openssl_v10x))]
+impl Drop for TlsAcceptor {
+    fn drop(&mut self) {
+        // SAFETY: self.ctx is non-null; Drop is called at most once.
+        unsafe { SSL_CTX_free(self.ctx) };
+    }
+}
+
 // MARK: TlsStream (1.1.x / 3.x / 4.x)
 #[cfg(not(openssl_v10x))]
 /// A TLS stream backed by OpenSSL 1.1.x, 3.x or 4.x
@@ -921,6 +1362,11 @@ unsafe impl<S: Send> Send for TlsStream<S> {}
 
 #[cfg(not(openssl_v10x))]
 impl<S> TlsStream<S> {
+    /// Returns the ALPN protocol negotiated during the handshake
+    pub fn negotiated_alpn(&self) -> Result<Option<Vec<u8>>, Error> {
+        Ok(negotiated_alpn(self.ssl))
+    }
+
     /// Returns a reference to the underlying stream
     pub fn get_ref(&self) -> &S {
         &self.stream
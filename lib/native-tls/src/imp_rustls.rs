@@ -12,10 +12,134 @@
 use std::io::{self, Read, Write};
 use std::sync::Arc;
 
-use rustls::pki_types::ServerName;
-use rustls::{ClientConfig, ClientConnection, RootCertStore};
+use rustls::client::WebPkiServerVerifier;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{
+    CertificateError, ClientConfig, ClientConnection, Connection, DigitallySignedStruct,
+    RootCertStore, ServerConfig, ServerConnection, SignatureScheme,
+};
 
-use crate::{Error, HandshakeError};
+use crate::{Error, HandshakeError, Protocol, TlsConnectorBuilder};
+
+// MARK: Identity
+/// A certificate chain and private key for a TlsAcceptor
+pub struct Identity {
+    chain: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+}
+
+impl Identity {
+    /// Parse a DER-encoded PKCS#12 archive, which is not supported by the rustls backend
+    pub fn from_pkcs12(_der: &[u8], _password: &str) -> Result<Self, Error> {
+        Err(Error(
+            "PKCS#12 identities are not supported by the rustls backend, use Identity::from_pkcs8"
+                .to_string(),
+        ))
+    }
+
+    /// Parse a PEM-encoded certificate chain and a PEM-encoded PKCS#8 private key
+    pub fn from_pkcs8(pem: &[u8], key: &[u8]) -> Result<Self, Error> {
+        let chain = CertificateDer::pem_slice_iter(pem)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error(format!("Failed to parse certificate PEM: {e}")))?;
+        if chain.is_empty() {
+            return Err(Error("No certificate found in PEM data".to_string()));
+        }
+        let key = PrivateKeyDer::from_pem_slice(key)
+            .map_err(|e| Error(format!("Failed to parse private key PEM: {e}")))?;
+        Ok(Self { chain, key })
+    }
+}
+
+// MARK: Verifiers
+#[derive(Debug)]
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        rustls::crypto::ring::default_provider()
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+// Verifies the certificate chain like WebPkiServerVerifier but ignores hostname mismatches
+#[derive(Debug)]
+struct NoHostnameVerification(Arc<WebPkiServerVerifier>);
+
+impl ServerCertVerifier for NoHostnameVerification {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        match self
+            .0
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+        {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            )) => Ok(ServerCertVerified::assertion()),
+            result => result,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.0.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.0.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.supported_verify_schemes()
+    }
+}
 
 // MARK: TlsConnector
 /// A TLS connector using rustls with embedded CA roots
@@ -26,64 +150,62 @@ pub struct TlsConnector {
 impl TlsConnector {
     /// Create a new TLS connector
     pub fn new() -> Result<Self, Error> {
-        let mut roots = RootCertStore::empty();
-        roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
-        let config = ClientConfig::builder()
-            .with_root_certificates(roots)
-            .with_no_client_auth();
-        Ok(Self {
-            config: Arc::new(config),
-        })
+        Self::from_builder(&TlsConnectorBuilder::default())
     }
 
-    #[cfg(test)]
-    pub(crate) fn new_danger_accept_invalid_certs() -> Result<Self, Error> {
-        #[derive(Debug)]
-        struct NoCertificateVerification;
-
-        impl rustls::client::danger::ServerCertVerifier for NoCertificateVerification {
-            fn verify_server_cert(
-                &self,
-                _end_entity: &rustls::pki_types::CertificateDer<'_>,
-                _intermediates: &[rustls::pki_types::CertificateDer<'_>],
-                _server_name: &ServerName<'_>,
-                _ocsp_response: &[u8],
-                _now: rustls::pki_types::UnixTime,
-            ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
-                Ok(rustls::client::danger::ServerCertVerified::assertion())
-            }
+    pub(crate) fn from_builder(builder: &TlsConnectorBuilder) -> Result<Self, Error> {
+        // rustls only implements TLS 1.2 and TLS 1.3
+        let versions = [
+            (Protocol::Tlsv12, &rustls::version::TLS12),
+            (Protocol::Tlsv13, &rustls::version::TLS13),
+        ]
+        .into_iter()
+        .filter(|(protocol, _)| {
+            builder.min_protocol.is_none_or(|min| *protocol >= min)
+                && builder.max_protocol.is_none_or(|max| *protocol <= max)
+        })
+        .map(|(_, version)| version)
+        .collect::<Vec<_>>();
+        if versions.is_empty() {
+            return Err(Error(
+                "The rustls backend only supports TLS 1.2 and TLS 1.3".to_string(),
+            ));
+        }
+        let config = ClientConfig::builder_with_protocol_versions(&versions);
 
-            fn verify_tls12_signature(
-                &self,
-                _message: &[u8],
-                _cert: &rustls::pki_types::CertificateDer<'_>,
-                _dss: &rustls::DigitallySignedStruct,
-            ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error>
-            {
-                Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+        let mut config = if builder.accept_invalid_certs {
+            config
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(NoCertificateVerification))
+                .with_no_client_auth()
+        } else {
+            let mut roots = RootCertStore::empty();
+            if !builder.disable_built_in_roots {
+                roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
             }
-
-            fn verify_tls13_signature(
-                &self,
-                _message: &[u8],
-                _cert: &rustls::pki_types::CertificateDer<'_>,
-                _dss: &rustls::DigitallySignedStruct,
-            ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error>
-            {
-                Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+            for cert in &builder.root_certificates {
+                roots
+                    .add(CertificateDer::from(cert.0.clone()))
+                    .map_err(|e| Error(format!("Invalid root certificate: {e}")))?;
             }
-
-            fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
-                rustls::crypto::ring::default_provider()
-                    .signature_verification_algorithms
-                    .supported_schemes()
+            if builder.accept_invalid_hostnames {
+                let verifier = WebPkiServerVerifier::builder(Arc::new(roots))
+                    .build()
+                    .map_err(|e| Error(e.to_string()))?;
+                config
+                    .dangerous()
+                    .with_custom_certificate_verifier(Arc::new(NoHostnameVerification(verifier)))
+                    .with_no_client_auth()
+            } else {
+                config.with_root_certificates(roots).with_no_client_auth()
             }
-        }
-
-        let config = ClientConfig::builder()
-            .dangerous()
-            .with_custom_certificate_verifier(Arc::new(NoCertificateVerification))
-            .with_no_client_auth();
+        };
+        config.alpn_protocols = builder
+            .alpn_protocols
+            .iter()
+            .map(|protocol| protocol.as_bytes().to_vec())
+            .collect();
+        config.enable_sni = builder.use_sni;
         Ok(Self {
             config: Arc::new(config),
         })
@@ -112,7 +234,10 @@ impl TlsConnector {
             conn.complete_io(&mut stream)
                 .map_err(|e| Error(e.to_string()))?;
         }
-        Ok(TlsStream { conn, stream })
+        Ok(TlsStream {
+            conn: conn.into(),
+            stream,
+        })
     }
 }
 
@@ -122,14 +247,56 @@ impl Default for TlsConnector {
     }
 }
 
+// MARK: TlsAcceptor
+/// A TLS acceptor using rustls
+pub struct TlsAcceptor {
+    config: Arc<ServerConfig>,
+}
+
+impl TlsAcceptor {
+    /// Create a new TLS acceptor with the given identity
+    pub fn new(identity: Identity) -> Result<Self, Error> {
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(identity.chain, identity.key)
+            .map_err(|e| Error(e.to_string()))?;
+        Ok(Self {
+            config: Arc::new(config),
+        })
+    }
+
+    /// Perform a server-side TLS handshake over the given stream
+    pub fn accept<S: Read + Write>(&self, stream: S) -> Result<TlsStream<S>, HandshakeError<S>> {
+        self.accept_inner(stream).map_err(HandshakeError::new)
+    }
+
+    fn accept_inner<S: Read + Write>(&self, mut stream: S) -> Result<TlsStream<S>, Error> {
+        let mut conn =
+            ServerConnection::new(self.config.clone()).map_err(|e| Error(e.to_string()))?;
+        while conn.is_handshaking() {
+            conn.complete_io(&mut stream)
+                .map_err(|e| Error(e.to_string()))?;
+        }
+        Ok(TlsStream {
+            conn: conn.into(),
+            stream,
+        })
+    }
+}
+
 // MARK: TlsStream
 /// A TLS stream backed by rustls
 pub struct TlsStream<S> {
-    conn: ClientConnection,
+    conn: Connection,
     stream: S,
 }
 
 impl<S> TlsStream<S> {
+    /// Returns the ALPN protocol negotiated during the handshake
+    pub fn negotiated_alpn(&self) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.conn.alpn_protocol().map(<[u8]>::to_vec))
+    }
+
     /// Returns a reference to the underlying stream
     pub const fn get_ref(&self) -> &S {
         &self.stream
@@ -143,16 +310,25 @@ impl<S> TlsStream<S> {
 
 impl<S: Read + Write> Read for TlsStream<S> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        rustls::Stream::new(&mut self.conn, &mut self.stream).read(buf)
+        match &mut self.conn {
+            Connection::Client(conn) => rustls::Stream::new(conn, &mut self.stream).read(buf),
+            Connection::Server(conn) => rustls::Stream::new(conn, &mut self.stream).read(buf),
+        }
     }
 }
 
 impl<S: Read + Write> Write for TlsStream<S> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        rustls::Stream::new(&mut self.conn, &mut self.stream).write(buf)
+        match &mut self.conn {
+            Connection::Client(conn) => rustls::Stream::new(conn, &mut self.stream).write(buf),
+            Connection::Server(conn) => rustls::Stream::new(conn, &mut self.stream).write(buf),
+        }
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        rustls::Stream::new(&mut self.conn, &mut self.stream).flush()
+        match &mut self.conn {
+            Connection::Client(conn) => rustls::Stream::new(conn, &mut self.stream).flush(),
+            Connection::Server(conn) => rustls::Stream::new(conn, &mut self.stream).flush(),
+        }
     }
 }
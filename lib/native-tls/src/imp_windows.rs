@@ -6,11 +6,11 @@
 
 //! Windows TLS implementation using SChannel (SSPI)
 
-use std::ffi::c_void;
+use std::ffi::{c_char, c_void};
 use std::io::{self, Read, Write};
 use std::slice;
 
-use crate::{Error, HandshakeError};
+use crate::{Error, HandshakeError, Protocol, TlsConnectorBuilder};
 
 // MARK: SSPI types
 #[repr(C)]
@@ -82,8 +82,101 @@ struct SchannelCred {
     dw_credentials_format: u32,
 }
 
+#[repr(C)]
+struct CryptDataBlob {
+    cb_data: u32,
+    pb_data: *mut u8,
+}
+
+// SecPkgContext_ApplicationProtocol
+#[repr(C)]
+struct SecPkgContextApplicationProtocol {
+    proto_nego_status: u32,
+    proto_nego_ext: u32,
+    protocol_id_size: u8,
+    protocol_id: [u8; 255],
+}
+
+// CERT_CONTEXT
+#[repr(C)]
+struct CertContext {
+    dw_cert_encoding_type: u32,
+    pb_cert_encoded: *const u8,
+    cb_cert_encoded: u32,
+    p_cert_info: *mut c_void,
+    h_cert_store: *mut c_void,
+}
+
+#[repr(C)]
+struct CertTrustStatus {
+    dw_error_status: u32,
+    dw_info_status: u32,
+}
+
+// Leading fields of CERT_CHAIN_CONTEXT, only read through pointers returned by Crypt32
+#[repr(C)]
+struct CertChainContext {
+    cb_size: u32,
+    trust_status: CertTrustStatus,
+    c_chain: u32,
+    rgp_chain: *const *const CertSimpleChain,
+}
+
+// Leading fields of CERT_SIMPLE_CHAIN
+#[repr(C)]
+struct CertSimpleChain {
+    cb_size: u32,
+    trust_status: CertTrustStatus,
+    c_element: u32,
+    rgp_element: *const *const CertChainElement,
+}
+
+// Leading fields of CERT_CHAIN_ELEMENT
+#[repr(C)]
+struct CertChainElement {
+    cb_size: u32,
+    p_cert_context: *const CertContext,
+}
+
+// CERT_CHAIN_PARA with an empty CERT_USAGE_MATCH
+#[repr(C)]
+struct CertChainPara {
+    cb_size: u32,
+    dw_usage_type: u32,
+    c_usage_identifier: u32,
+    rgpsz_usage_identifier: *mut *mut c_char,
+}
+
+// SSL_EXTRA_CERT_CHAIN_POLICY_PARA
+#[repr(C)]
+struct SslExtraCertChainPolicyPara {
+    cb_size: u32,
+    dw_auth_type: u32,
+    fdw_checks: u32,
+    pwsz_server_name: *const u16,
+}
+
+// CERT_CHAIN_POLICY_PARA
+#[repr(C)]
+struct CertChainPolicyPara {
+    cb_size: u32,
+    dw_flags: u32,
+    pv_extra_policy_para: *mut c_void,
+}
+
+// CERT_CHAIN_POLICY_STATUS
+#[repr(C)]
+struct CertChainPolicyStatus {
+    cb_size: u32,
+    dw_error: u32,
+    l_chain_index: i32,
+    l_element_index: i32,
+    pv_extra_policy_status: *mut c_void,
+}
+
 // MARK: Constants
 const SCHANNEL_CRED_VERSION: u32 = 4;
+const SECPKG_CRED_INBOUND: u32 = 1;
 const SECPKG_CRED_OUTBOUND: u32 = 2;
 const SECBUFFER_EMPTY: u32 = 0;
 const SECBUFFER_DATA: u32 = 1;
@@ -91,13 +184,34 @@ const SECBUFFER_TOKEN: u32 = 2;
 const SECBUFFER_EXTRA: u32 = 5;
 const SECBUFFER_STREAM_TRAILER: u32 = 6;
 const SECBUFFER_STREAM_HEADER: u32 = 7;
+const SECBUFFER_APPLICATION_PROTOCOLS: u32 = 18;
 const SECBUFFER_VERSION: u32 = 0;
 const SECPKG_ATTR_STREAM_SIZES: u32 = 4;
+const SECPKG_ATTR_APPLICATION_PROTOCOL: u32 = 35;
+const SECPKG_ATTR_REMOTE_CERT_CONTEXT: u32 = 0x53;
+const SEC_APPLICATION_PROTOCOL_NEGOTIATION_EXT_ALPN: u32 = 2;
+const SEC_APPLICATION_PROTOCOL_NEGOTIATION_STATUS_SUCCESS: u32 = 1;
 const SCH_CRED_AUTO_CRED_VALIDATION: u32 = 0x00000020;
 const SCH_CRED_MANUAL_CRED_VALIDATION: u32 = 0x00000008;
 const SCH_CRED_NO_DEFAULT_CREDS: u32 = 0x00000010;
+const SCH_CRED_NO_SERVERNAME_CHECK: u32 = 0x00000004;
+const SP_PROT_SSL3_CLIENT: u32 = 0x00000020;
+const SP_PROT_TLS1_0_CLIENT: u32 = 0x00000080;
+const SP_PROT_TLS1_1_CLIENT: u32 = 0x00000200;
 const SP_PROT_TLS1_2_CLIENT: u32 = 0x00000800;
 const SP_PROT_TLS1_3_CLIENT: u32 = 0x00002000;
+const SP_PROT_TLS1_2_SERVER: u32 = 0x00000400;
+const SP_PROT_TLS1_3_SERVER: u32 = 0x00001000;
+const X509_ASN_ENCODING: u32 = 0x00000001;
+const PKCS_7_ASN_ENCODING: u32 = 0x00010000;
+// CERT_COMPARE_HAS_PRIVATE_KEY << CERT_COMPARE_SHIFT
+const CERT_FIND_HAS_PRIVATE_KEY: u32 = 21 << 16;
+const CERT_STORE_PROV_MEMORY: usize = 2;
+const CERT_STORE_ADD_ALWAYS: u32 = 4;
+const CERT_CHAIN_POLICY_SSL: usize = 4;
+const CERT_CHAIN_POLICY_ALLOW_UNKNOWN_CA_FLAG: u32 = 0x00000010;
+const AUTHTYPE_SERVER: u32 = 2;
+const SECURITY_FLAG_IGNORE_CERT_CN_INVALID: u32 = 0x00001000;
 
 const ISC_REQ_SEQUENCE_DETECT: u32 = 0x00000008;
 const ISC_REQ_REPLAY_DETECT: u32 = 0x00000004;
@@ -112,6 +226,19 @@ const ISC_FLAGS: u32 = ISC_REQ_SEQUENCE_DETECT
     | ISC_REQ_ALLOCATE_MEMORY
     | ISC_REQ_STREAM;
 
+const ASC_REQ_REPLAY_DETECT: u32 = 0x00000004;
+const ASC_REQ_SEQUENCE_DETECT: u32 = 0x00000008;
+const ASC_REQ_CONFIDENTIALITY: u32 = 0x00000010;
+const ASC_REQ_ALLOCATE_MEMORY: u32 = 0x00000100;
+const ASC_REQ_EXTENDED_ERROR: u32 = 0x00008000;
+const ASC_REQ_STREAM: u32 = 0x00010000;
+const ASC_FLAGS: u32 = ASC_REQ_SEQUENCE_DETECT
+    | ASC_REQ_REPLAY_DETECT
+    | ASC_REQ_CONFIDENTIALITY
+    | ASC_REQ_EXTENDED_ERROR
+    | ASC_REQ_ALLOCATE_MEMORY
+    | ASC_REQ_STREAM;
+
 const SEC_E_OK: i32 = 0;
 const SEC_I_CONTINUE_NEEDED: i32 = 0x00090312u32 as i32;
 const SEC_E_INCOMPLETE_MESSAGE: i32 = 0x80090318u32 as i32;
@@ -158,6 +285,17 @@ unsafe extern "system" {
         context_attr: *mut u32,
         expiry: *mut TimeStamp,
     ) -> i32;
+    fn AcceptSecurityContext(
+        credential: *mut CredHandle,
+        context: *mut CtxtHandle,
+        input: *mut SecBufferDesc,
+        context_req: u32,
+        target_data_rep: u32,
+        new_context: *mut CtxtHandle,
+        output: *mut SecBufferDesc,
+        context_attr: *mut u32,
+        expiry: *mut TimeStamp,
+    ) -> i32;
     fn FreeContextBuffer(ctx_buf: *mut c_void) -> i32;
     fn DeleteSecurityContext(context: *mut CtxtHandle) -> i32;
     fn FreeCredentialsHandle(credential: *mut CredHandle) -> i32;
@@ -170,14 +308,99 @@ unsafe extern "system" {
         qop: *mut u32,
     ) -> i32;
     fn QueryContextAttributesW(context: *mut CtxtHandle, attr: u32, buf: *mut c_void) -> i32;
+    fn PFXImportCertStore(pfx: *mut CryptDataBlob, password: *const u16, flags: u32)
+    -> *mut c_void;
+    fn CertFindCertificateInStore(
+        store: *mut c_void,
+        encoding_type: u32,
+        find_flags: u32,
+        find_type: u32,
+        find_para: *const c_void,
+        prev_cert_context: *const c_void,
+    ) -> *const c_void;
+    fn CertFreeCertificateContext(cert_context: *const c_void) -> i32;
+    fn CertCloseStore(store: *mut c_void, flags: u32) -> i32;
+    fn CertOpenStore(
+        store_provider: *const c_char,
+        encoding_type: u32,
+        crypt_prov: usize,
+        flags: u32,
+        para: *const c_void,
+    ) -> *mut c_void;
+    fn CertAddEncodedCertificateToStore(
+        store: *mut c_void,
+        encoding_type: u32,
+        cert_encoded: *const u8,
+        cert_encoded_len: u32,
+        add_disposition: u32,
+        cert_context: *mut *const c_void,
+    ) -> i32;
+    fn CertAddCertificateContextToStore(
+        store: *mut c_void,
+        cert_context: *const c_void,
+        add_disposition: u32,
+        store_context: *mut *const c_void,
+    ) -> i32;
+    fn CertEnumCertificatesInStore(
+        store: *mut c_void,
+        prev_cert_context: *const c_void,
+    ) -> *const c_void;
+    fn CertGetCertificateChain(
+        chain_engine: *mut c_void,
+        cert_context: *const c_void,
+        time: *const c_void,
+        additional_store: *mut c_void,
+        chain_para: *const CertChainPara,
+        flags: u32,
+        reserved: *mut c_void,
+        chain_context: *mut *const CertChainContext,
+    ) -> i32;
+    fn CertVerifyCertificateChainPolicy(
+        policy_oid: *const c_char,
+        chain_context: *const CertChainContext,
+        policy_para: *const CertChainPolicyPara,
+        policy_status: *mut CertChainPolicyStatus,
+    ) -> i32;
+    fn CertFreeCertificateChain(chain_context: *const CertChainContext);
 }
 
 fn to_utf16(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(std::iter::once(0)).collect()
 }
 
-fn acquire_cred(accept_invalid_certs: bool) -> Result<CredHandle, Error> {
-    let mut cred = CredHandle::INVALID;
+// SChannel only checks certificates against the system store, so extra or exclusive roots and
+// accepting invalid certificates need manual validation after the handshake
+const fn manual_validation(builder: &TlsConnectorBuilder) -> bool {
+    builder.accept_invalid_certs
+        || builder.disable_built_in_roots
+        || !builder.root_certificates.is_empty()
+}
+
+fn enabled_protocols(builder: &TlsConnectorBuilder) -> u32 {
+    [
+        (Protocol::Sslv3, SP_PROT_SSL3_CLIENT),
+        (Protocol::Tlsv10, SP_PROT_TLS1_0_CLIENT),
+        (Protocol::Tlsv11, SP_PROT_TLS1_1_CLIENT),
+        (Protocol::Tlsv12, SP_PROT_TLS1_2_CLIENT),
+        (Protocol::Tlsv13, SP_PROT_TLS1_3_CLIENT),
+    ]
+    .into_iter()
+    .filter(|(protocol, _)| {
+        builder.min_protocol.is_none_or(|min| *protocol >= min)
+            && builder.max_protocol.is_none_or(|max| *protocol <= max)
+    })
+    .fold(0, |protocols, (_, flag)| protocols | flag)
+}
+
+fn acquire_cred(builder: &TlsConnectorBuilder) -> Result<CredHandle, Error> {
+    let mut dw_flags = if manual_validation(builder) {
+        SCH_CRED_MANUAL_CRED_VALIDATION | SCH_CRED_NO_DEFAULT_CREDS
+    } else {
+        SCH_CRED_AUTO_CRED_VALIDATION | SCH_CRED_NO_DEFAULT_CREDS
+    };
+    if builder.accept_invalid_hostnames {
+        dw_flags |= SCH_CRED_NO_SERVERNAME_CHECK;
+    }
     let mut cred_data = SchannelCred {
         dw_version: SCHANNEL_CRED_VERSION,
         c_creds: 0,
@@ -187,26 +410,51 @@ fn acquire_cred(accept_invalid_certs: bool) -> Result<CredHandle, Error> {
         aph_mappers: std::ptr::null_mut(),
         c_supported_algs: 0,
         palg_supported_algs: std::ptr::null_mut(),
-        grbit_enabled_protocols: SP_PROT_TLS1_2_CLIENT | SP_PROT_TLS1_3_CLIENT,
+        grbit_enabled_protocols: enabled_protocols(builder),
         dw_minimum_cipher_strength: 0,
         dw_maximum_cipher_strength: 0,
         dw_session_lifespan: 0,
-        dw_flags: if accept_invalid_certs {
-            SCH_CRED_MANUAL_CRED_VALIDATION | SCH_CRED_NO_DEFAULT_CREDS
-        } else {
-            SCH_CRED_AUTO_CRED_VALIDATION | SCH_CRED_NO_DEFAULT_CREDS
-        },
+        dw_flags,
+        dw_credentials_format: 0,
+    };
+    acquire_cred_handle(SECPKG_CRED_OUTBOUND, &mut cred_data)
+}
+
+fn acquire_server_cred(cert: *const c_void) -> Result<CredHandle, Error> {
+    let mut certs = [cert as *mut c_void];
+    let mut cred_data = SchannelCred {
+        dw_version: SCHANNEL_CRED_VERSION,
+        c_creds: 1,
+        pa_cred: certs.as_mut_ptr(),
+        h_root_store: std::ptr::null_mut(),
+        c_mappers: 0,
+        aph_mappers: std::ptr::null_mut(),
+        c_supported_algs: 0,
+        palg_supported_algs: std::ptr::null_mut(),
+        grbit_enabled_protocols: SP_PROT_TLS1_2_SERVER | SP_PROT_TLS1_3_SERVER,
+        dw_minimum_cipher_strength: 0,
+        dw_maximum_cipher_strength: 0,
+        dw_session_lifespan: 0,
+        dw_flags: 0,
         dw_credentials_format: 0,
     };
+    acquire_cred_handle(SECPKG_CRED_INBOUND, &mut cred_data)
+}
+
+fn acquire_cred_handle(
+    credential_use: u32,
+    cred_data: &mut SchannelCred,
+) -> Result<CredHandle, Error> {
+    let mut cred = CredHandle::INVALID;
     let mut expiry = TimeStamp { low: 0, high: 0 };
     // SAFETY: all pointer arguments are either null (meaning "default") or point to valid local variables.
     let status = unsafe {
         AcquireCredentialsHandleW(
             std::ptr::null(),
             SCHANNEL_NAME_W.as_ptr(),
-            SECPKG_CRED_OUTBOUND,
+            credential_use,
             std::ptr::null(),
-            &mut cred_data as *mut SchannelCred as *mut c_void,
+            cred_data as *mut SchannelCred as *mut c_void,
             std::ptr::null(),
             std::ptr::null(),
             &mut cred,
@@ -222,9 +470,20 @@ fn acquire_cred(accept_invalid_certs: bool) -> Result<CredHandle, Error> {
     Ok(cred)
 }
 
+// SEC_APPLICATION_PROTOCOLS with a single ALPN protocol list
+fn alpn_buffer(wire: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(10 + wire.len());
+    buffer.extend_from_slice(&((6 + wire.len()) as u32).to_ne_bytes());
+    buffer.extend_from_slice(&SEC_APPLICATION_PROTOCOL_NEGOTIATION_EXT_ALPN.to_ne_bytes());
+    buffer.extend_from_slice(&(wire.len() as u16).to_ne_bytes());
+    buffer.extend_from_slice(wire);
+    buffer
+}
+
 fn do_handshake<S: Read + Write>(
     cred: &mut CredHandle,
-    domain: &[u16],
+    target_name: *const u16,
+    alpn: &[u8],
     stream: &mut S,
 ) -> Result<CtxtHandle, Error> {
     let mut ctx = CtxtHandle::INVALID;
@@ -247,17 +506,33 @@ fn do_handshake<S: Read + Write>(
         let mut expiry = TimeStamp { low: 0, high: 0 };
 
         let status = if first_call {
+            let mut alpn_buf = alpn_buffer(alpn);
+            let mut alpn_sec_buf = SecBuffer {
+                cb_buffer: alpn_buf.len() as u32,
+                buffer_type: SECBUFFER_APPLICATION_PROTOCOLS,
+                pv_buffer: alpn_buf.as_mut_ptr() as *mut c_void,
+            };
+            let mut alpn_desc = SecBufferDesc {
+                ul_version: SECBUFFER_VERSION,
+                c_buffers: 1,
+                p_buffers: &mut alpn_sec_buf,
+            };
             // SAFETY: cred is a valid credential handle; null context on first call is per-spec.
+            // target_name is null or a valid UTF-16 string; alpn_desc points into alpn_buf.
             // out_desc, ctx_attrs, expiry are valid output locations. ISC_FLAGS are valid flags.
             unsafe {
                 InitializeSecurityContextW(
                     cred,
                     std::ptr::null_mut(),
-                    domain.as_ptr(),
+                    target_name,
                     ISC_FLAGS,
                     0,
                     0,
-                    std::ptr::null_mut(),
+                    if alpn.is_empty() {
+                        std::ptr::null_mut()
+                    } else {
+                        &mut alpn_desc
+                    },
                     0,
                     &mut ctx,
                     &mut out_desc,
@@ -288,7 +563,7 @@ fn do_handshake<S: Read + Write>(
                 InitializeSecurityContextW(
                     cred,
                     &mut ctx,
-                    domain.as_ptr(),
+                    target_name,
                     ISC_FLAGS,
                     0,
                     0,
@@ -366,6 +641,122 @@ fn do_handshake<S: Read + Write>(
     }
 }
 
+// Server-side handshake; returns the context and any ciphertext received after the handshake.
+fn do_accept_handshake<S: Read + Write>(
+    cred: &mut CredHandle,
+    stream: &mut S,
+) -> Result<(CtxtHandle, Vec<u8>), Error> {
+    let mut ctx = CtxtHandle::INVALID;
+    let mut in_data: Vec<u8> = Vec::new();
+    let mut first_call = true;
+    let mut need_read = true;
+
+    loop {
+        if need_read {
+            let mut chunk = vec![0u8; 16384];
+            let n = stream.read(&mut chunk).map_err(|e| Error(e.to_string()))?;
+            if n == 0 {
+                return Err(Error("Connection closed during TLS handshake".to_string()));
+            }
+            in_data.extend_from_slice(&chunk[..n]);
+        }
+
+        let mut in_bufs = [
+            SecBuffer {
+                cb_buffer: in_data.len() as u32,
+                buffer_type: SECBUFFER_TOKEN,
+                pv_buffer: in_data.as_mut_ptr() as *mut c_void,
+            },
+            SecBuffer {
+                cb_buffer: 0,
+                buffer_type: SECBUFFER_EMPTY,
+                pv_buffer: std::ptr::null_mut(),
+            },
+        ];
+        let mut in_desc = SecBufferDesc {
+            ul_version: SECBUFFER_VERSION,
+            c_buffers: 2,
+            p_buffers: in_bufs.as_mut_ptr(),
+        };
+        // Prepare output buffers (ASC_REQ_ALLOCATE_MEMORY = SChannel allocates)
+        let mut out_buf = SecBuffer {
+            cb_buffer: 0,
+            buffer_type: SECBUFFER_TOKEN,
+            pv_buffer: std::ptr::null_mut(),
+        };
+        let mut out_desc = SecBufferDesc {
+            ul_version: SECBUFFER_VERSION,
+            c_buffers: 1,
+            p_buffers: &mut out_buf,
+        };
+        let mut ctx_attrs: u32 = 0;
+        let mut expiry = TimeStamp { low: 0, high: 0 };
+
+        // SAFETY: cred is a valid credential handle; a null context on the first call is per-spec.
+        // in_desc points into in_data; out_desc, ctx_attrs and expiry are valid output locations.
+        let status = unsafe {
+            AcceptSecurityContext(
+                cred,
+                if first_call {
+                    std::ptr::null_mut()
+                } else {
+                    &mut ctx
+                },
+                &mut in_desc,
+                ASC_FLAGS,
+                0,
+                &mut ctx,
+                &mut out_desc,
+                &mut ctx_attrs,
+                &mut expiry,
+            )
+        };
+
+        // Retain unconsumed input bytes (SECBUFFER_EXTRA) for the next call, see do_handshake.
+        // For SEC_E_INCOMPLETE_MESSAGE keep all of in_data so we can append more.
+        if status != SEC_E_INCOMPLETE_MESSAGE {
+            first_call = false;
+            if in_bufs[1].buffer_type == SECBUFFER_EXTRA && in_bufs[1].cb_buffer > 0 {
+                let extra_len = in_bufs[1].cb_buffer as usize;
+                let total_len = in_data.len();
+                in_data.drain(..total_len - extra_len);
+            } else {
+                in_data.clear();
+            }
+        }
+
+        // Send output token if any
+        if !out_buf.pv_buffer.is_null() && out_buf.cb_buffer > 0 {
+            // SAFETY: SChannel allocated out_buf.pv_buffer (ASC_REQ_ALLOCATE_MEMORY) and
+            // cb_buffer bytes are valid; FreeContextBuffer releases SChannel-allocated memory.
+            let data = unsafe {
+                slice::from_raw_parts(out_buf.pv_buffer as *const u8, out_buf.cb_buffer as usize)
+            };
+            let result = stream.write_all(data);
+            // SAFETY: out_buf.pv_buffer is non-null and was allocated by SChannel.
+            unsafe { FreeContextBuffer(out_buf.pv_buffer) };
+            result.map_err(|e| Error(e.to_string()))?;
+        }
+
+        match status {
+            // Leftover bytes are already application data for the TlsStream
+            SEC_E_OK => return Ok((ctx, in_data)),
+            SEC_I_CONTINUE_NEEDED => need_read = in_data.is_empty(),
+            SEC_E_INCOMPLETE_MESSAGE => need_read = true,
+            _ => {
+                if !ctx.is_invalid() {
+                    // SAFETY: ctx is a valid (non-invalid) context handle.
+                    unsafe { DeleteSecurityContext(&mut ctx) };
+                }
+                return Err(Error(format!(
+                    "TLS handshake error: 0x{:08x}",
+                    status as u32
+                )));
+            }
+        }
+    }
+}
+
 fn query_stream_sizes(ctx: &mut CtxtHandle) -> Result<SecPkgContextStreamSizes, Error> {
     let mut sizes = SecPkgContextStreamSizes::default();
     // SAFETY: ctx is a valid context handle; &mut sizes is a valid output location for the attribute.
@@ -385,24 +776,314 @@ fn query_stream_sizes(ctx: &mut CtxtHandle) -> Result<SecPkgContextStreamSizes,
     Ok(sizes)
 }
 
+fn query_negotiated_alpn(ctx: &mut CtxtHandle) -> Result<Option<Vec<u8>>, Error> {
+    let mut protocol = SecPkgContextApplicationProtocol {
+        proto_nego_status: 0,
+        proto_nego_ext: 0,
+        protocol_id_size: 0,
+        protocol_id: [0; 255],
+    };
+    // SAFETY: ctx is a valid context handle; &mut protocol is a valid output location.
+    let status = unsafe {
+        QueryContextAttributesW(
+            ctx,
+            SECPKG_ATTR_APPLICATION_PROTOCOL,
+            &mut protocol as *mut SecPkgContextApplicationProtocol as *mut c_void,
+        )
+    };
+    if status != SEC_E_OK {
+        return Err(Error(format!(
+            "QueryContextAttributesW failed: 0x{:08x}",
+            status as u32
+        )));
+    }
+    if protocol.proto_nego_status != SEC_APPLICATION_PROTOCOL_NEGOTIATION_STATUS_SUCCESS
+        || protocol.proto_nego_ext != SEC_APPLICATION_PROTOCOL_NEGOTIATION_EXT_ALPN
+    {
+        return Ok(None);
+    }
+    Ok(Some(
+        protocol.protocol_id[..protocol.protocol_id_size as usize].to_vec(),
+    ))
+}
+
+// Verify the server certificate chain against the system and extra roots
+fn verify_peer_cert(
+    ctx: &mut CtxtHandle,
+    builder: &TlsConnectorBuilder,
+    domain: &[u16],
+) -> Result<(), Error> {
+    let mut peer: *const CertContext = std::ptr::null();
+    // SAFETY: ctx is a valid context handle; &mut peer is a valid output location.
+    let status = unsafe {
+        QueryContextAttributesW(
+            ctx,
+            SECPKG_ATTR_REMOTE_CERT_CONTEXT,
+            &mut peer as *mut *const CertContext as *mut c_void,
+        )
+    };
+    if status != SEC_E_OK || peer.is_null() {
+        return Err(Error("No peer certificate".to_string()));
+    }
+
+    // SAFETY: a null provider parameter is valid for a memory store.
+    let store = unsafe {
+        CertOpenStore(
+            CERT_STORE_PROV_MEMORY as *const c_char,
+            0,
+            0,
+            0,
+            std::ptr::null(),
+        )
+    };
+    let result = if store.is_null() {
+        Err(Error(format!(
+            "CertOpenStore failed: {}",
+            io::Error::last_os_error()
+        )))
+    } else {
+        // SAFETY: peer is a valid certificate context and store a valid memory store, both
+        // released below.
+        let result = unsafe { verify_peer_chain(peer, store, builder, domain) };
+        // SAFETY: store is a valid store that is not used afterwards.
+        unsafe { CertCloseStore(store, 0) };
+        result
+    };
+    // SAFETY: peer was returned by QueryContextAttributesW and is not used afterwards.
+    unsafe { CertFreeCertificateContext(peer as *const c_void) };
+    result
+}
+
+// SAFETY: peer must be a valid certificate context and store a valid writable certificate store.
+unsafe fn verify_peer_chain(
+    peer: *const CertContext,
+    store: *mut c_void,
+    builder: &TlsConnectorBuilder,
+    domain: &[u16],
+) -> Result<(), Error> {
+    // Collect the extra roots and the intermediates sent by the server in one store
+    for cert in &builder.root_certificates {
+        // SAFETY: store is valid; the pointer and length describe the certificate's DER bytes.
+        if unsafe {
+            CertAddEncodedCertificateToStore(
+                store,
+                X509_ASN_ENCODING | PKCS_7_ASN_ENCODING,
+                cert.0.as_ptr(),
+                cert.0.len() as u32,
+                CERT_STORE_ADD_ALWAYS,
+                std::ptr::null_mut(),
+            )
+        } == 0
+        {
+            return Err(Error(format!(
+                "Invalid root certificate: {}",
+                io::Error::last_os_error()
+            )));
+        }
+    }
+    // SAFETY: peer is a valid certificate context, its store holds the server-sent certificates.
+    let peer_store = unsafe { (*peer).h_cert_store };
+    let mut cert = std::ptr::null();
+    loop {
+        // SAFETY: peer_store is valid; the previous context is freed by the enumeration.
+        cert = unsafe { CertEnumCertificatesInStore(peer_store, cert) };
+        if cert.is_null() {
+            break;
+        }
+        // SAFETY: store and cert are valid; the store takes its own copy of cert.
+        unsafe {
+            CertAddCertificateContextToStore(
+                store,
+                cert,
+                CERT_STORE_ADD_ALWAYS,
+                std::ptr::null_mut(),
+            )
+        };
+    }
+
+    let chain_para = CertChainPara {
+        cb_size: size_of::<CertChainPara>() as u32,
+        dw_usage_type: 0,
+        c_usage_identifier: 0,
+        rgpsz_usage_identifier: std::ptr::null_mut(),
+    };
+    let mut chain: *const CertChainContext = std::ptr::null();
+    // SAFETY: peer and store are valid; a null engine and time select the defaults;
+    // &mut chain is a valid output location.
+    if unsafe {
+        CertGetCertificateChain(
+            std::ptr::null_mut(),
+            peer as *const c_void,
+            std::ptr::null(),
+            store,
+            &chain_para,
+            0,
+            std::ptr::null_mut(),
+            &mut chain,
+        )
+    } == 0
+    {
+        return Err(Error(format!(
+            "CertGetCertificateChain failed: {}",
+            io::Error::last_os_error()
+        )));
+    }
+
+    // SAFETY: chain is a valid chain context with at least one simple chain of at least one
+    // element, the last element being the root of the chain.
+    let root_der = unsafe {
+        let simple = &**(*chain).rgp_chain;
+        let root = &*(**simple.rgp_element.add(simple.c_element as usize - 1)).p_cert_context;
+        slice::from_raw_parts(root.pb_cert_encoded, root.cb_cert_encoded as usize)
+    };
+    let is_extra_root = builder
+        .root_certificates
+        .iter()
+        .any(|cert| cert.0 == root_der);
+
+    let mut ssl_para = SslExtraCertChainPolicyPara {
+        cb_size: size_of::<SslExtraCertChainPolicyPara>() as u32,
+        dw_auth_type: AUTHTYPE_SERVER,
+        fdw_checks: if builder.accept_invalid_hostnames {
+            SECURITY_FLAG_IGNORE_CERT_CN_INVALID
+        } else {
+            0
+        },
+        pwsz_server_name: domain.as_ptr(),
+    };
+    let policy_para = CertChainPolicyPara {
+        cb_size: size_of::<CertChainPolicyPara>() as u32,
+        // Extra roots are not in the system store, so their chains end in an unknown CA
+        dw_flags: if is_extra_root {
+            CERT_CHAIN_POLICY_ALLOW_UNKNOWN_CA_FLAG
+        } else {
+            0
+        },
+        pv_extra_policy_para: &mut ssl_para as *mut SslExtraCertChainPolicyPara as *mut c_void,
+    };
+    let mut policy_status = CertChainPolicyStatus {
+        cb_size: size_of::<CertChainPolicyStatus>() as u32,
+        dw_error: 0,
+        l_chain_index: 0,
+        l_element_index: 0,
+        pv_extra_policy_status: std::ptr::null_mut(),
+    };
+    // SAFETY: chain is a valid chain context; the policy structures are valid for the call.
+    let ok = unsafe {
+        CertVerifyCertificateChainPolicy(
+            CERT_CHAIN_POLICY_SSL as *const c_char,
+            chain,
+            &policy_para,
+            &mut policy_status,
+        )
+    };
+    // SAFETY: chain is a valid chain context that is not used afterwards.
+    unsafe { CertFreeCertificateChain(chain) };
+
+    if ok == 0 || policy_status.dw_error != 0 {
+        return Err(Error(format!(
+            "Certificate verification failed: 0x{:08x}",
+            policy_status.dw_error
+        )));
+    }
+    if builder.disable_built_in_roots && !is_extra_root {
+        return Err(Error(
+            "Certificate is not signed by an added root certificate".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// MARK: Identity
+/// A certificate and private key imported into a temporary certificate store
+pub struct Identity {
+    store: *mut c_void,
+    cert: *const c_void,
+}
+
+// SAFETY: certificate stores and contexts are reference-counted and not mutated after import.
+unsafe impl Send for Identity {}
+// SAFETY: certificate stores and contexts are reference-counted and not mutated after import.
+unsafe impl Sync for Identity {}
+
+impl Identity {
+    /// Import a DER-encoded PKCS#12 archive containing a certificate, private key and chain
+    pub fn from_pkcs12(der: &[u8], password: &str) -> Result<Self, Error> {
+        let password_w = to_utf16(password);
+        let mut blob = CryptDataBlob {
+            cb_data: der.len() as u32,
+            pb_data: der.as_ptr() as *mut u8,
+        };
+        // SAFETY: blob describes the der slice, which PFXImportCertStore only reads;
+        // password_w is a valid null-terminated UTF-16 string.
+        let store = unsafe { PFXImportCertStore(&mut blob, password_w.as_ptr(), 0) };
+        if store.is_null() {
+            return Err(Error(format!(
+                "PFXImportCertStore failed: {}",
+                io::Error::last_os_error()
+            )));
+        }
+        // SAFETY: store is a valid certificate store; a null find_para is valid for this find type.
+        let cert = unsafe {
+            CertFindCertificateInStore(
+                store,
+                X509_ASN_ENCODING | PKCS_7_ASN_ENCODING,
+                0,
+                CERT_FIND_HAS_PRIVATE_KEY,
+                std::ptr::null(),
+                std::ptr::null(),
+            )
+        };
+        if cert.is_null() {
+            // SAFETY: store is a valid certificate store that is not used afterwards.
+            unsafe { CertCloseStore(store, 0) };
+            return Err(Error(
+                "PKCS#12 archive does not contain a certificate with a private key".to_string(),
+            ));
+        }
+        Ok(Self { store, cert })
+    }
+
+    /// Parse a PEM-encoded certificate chain and PKCS#8 private key, which is not supported by
+    /// the SChannel backend
+    pub fn from_pkcs8(_pem: &[u8], _key: &[u8]) -> Result<Self, Error> {
+        Err(Error(
+            "PKCS#8 identities are not supported by the SChannel backend, use Identity::from_pkcs12"
+                .to_string(),
+        ))
+    }
+}
+
+impl Drop for Identity {
+    fn drop(&mut self) {
+        // SAFETY: cert and store are valid handles owned by this identity; Drop is called once.
+        unsafe {
+            CertFreeCertificateContext(self.cert);
+            CertCloseStore(self.store, 0);
+        }
+    }
+}
+
 // MARK: TlsConnector
 /// A TLS connector using SChannel
 pub struct TlsConnector {
-    accept_invalid_certs: bool,
+    builder: TlsConnectorBuilder,
 }
 
 impl TlsConnector {
     /// Create a new TLS connector
-    pub const fn new() -> Result<Self, Error> {
-        Ok(Self {
-            accept_invalid_certs: false,
-        })
+    pub fn new() -> Result<Self, Error> {
+        Self::from_builder(&TlsConnectorBuilder::default())
     }
 
-    #[cfg(test)]
-    pub(crate) fn new_danger_accept_invalid_certs() -> Result<Self, Error> {
+    pub(crate) fn from_builder(builder: &TlsConnectorBuilder) -> Result<Self, Error> {
+        if enabled_protocols(builder) == 0 {
+            return Err(Error(
+                "No protocol versions supported by SChannel are enabled".to_string(),
+            ));
+        }
         Ok(Self {
-            accept_invalid_certs: true,
+            builder: builder.clone(),
         })
     }
 
@@ -419,22 +1100,43 @@ impl TlsConnector {
     fn connect_inner<S: Read + Write>(
         &self,
         domain: &str,
-        stream: S,
+        mut stream: S,
     ) -> Result<TlsStream<S>, Error> {
-        let mut cred = acquire_cred(self.accept_invalid_certs)?;
+        let builder = &self.builder;
+        let mut cred = acquire_cred(builder)?;
         let domain_w = to_utf16(domain);
-        let mut stream = stream;
-        let ctx = do_handshake(&mut cred, &domain_w, &mut stream)?;
-        let mut ctx = ctx;
-        let sizes = query_stream_sizes(&mut ctx)?;
-        Ok(TlsStream {
+        let target_name = if builder.use_sni {
+            domain_w.as_ptr()
+        } else {
+            std::ptr::null()
+        };
+        let alpn = builder.alpn_wire_format();
+        let ctx = match do_handshake(&mut cred, target_name, &alpn, &mut stream) {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                // SAFETY: cred is a valid credential handle that is not used afterwards.
+                unsafe { FreeCredentialsHandle(&mut cred) };
+                return Err(e);
+            }
+        };
+        // TlsStream owns cred and ctx from here on, so Drop releases them on error
+        let mut tls = TlsStream {
             cred,
             ctx,
             stream,
-            sizes,
+            sizes: SecPkgContextStreamSizes::default(),
+            alpn: None,
             enc_buf: Vec::new(),
             dec_buf: Vec::new(),
-        })
+        };
+        if manual_validation(builder) && !builder.accept_invalid_certs {
+            verify_peer_cert(&mut tls.ctx, builder, &domain_w)?;
+        }
+        if !alpn.is_empty() {
+            tls.alpn = query_negotiated_alpn(&mut tls.ctx)?;
+        }
+        tls.sizes = query_stream_sizes(&mut tls.ctx)?;
+        Ok(tls)
     }
 }
 
@@ -444,6 +1146,48 @@ impl Default for TlsConnector {
     }
 }
 
+// MARK: TlsAcceptor
+/// A TLS acceptor using SChannel
+pub struct TlsAcceptor {
+    identity: Identity,
+}
+
+impl TlsAcceptor {
+    /// Create a new TLS acceptor with the given identity
+    pub const fn new(identity: Identity) -> Result<Self, Error> {
+        Ok(Self { identity })
+    }
+
+    /// Perform a server-side TLS handshake over the given stream
+    pub fn accept<S: Read + Write>(&self, stream: S) -> Result<TlsStream<S>, HandshakeError<S>> {
+        self.accept_inner(stream).map_err(HandshakeError::new)
+    }
+
+    fn accept_inner<S: Read + Write>(&self, mut stream: S) -> Result<TlsStream<S>, Error> {
+        let mut cred = acquire_server_cred(self.identity.cert)?;
+        let (ctx, enc_buf) = match do_accept_handshake(&mut cred, &mut stream) {
+            Ok(result) => result,
+            Err(e) => {
+                // SAFETY: cred is a valid credential handle that is not used afterwards.
+                unsafe { FreeCredentialsHandle(&mut cred) };
+                return Err(e);
+            }
+        };
+        // TlsStream owns cred and ctx from here on, so Drop releases them on error
+        let mut tls = TlsStream {
+            cred,
+            ctx,
+            stream,
+            sizes: SecPkgContextStreamSizes::default(),
+            alpn: None,
+            enc_buf,
+            dec_buf: Vec::new(),
+        };
+        tls.sizes = query_stream_sizes(&mut tls.ctx)?;
+        Ok(tls)
+    }
+}
+
 // MARK: TlsStream
 /// A TLS stream backed by SChannel
 pub struct TlsStream<S> {
@@ -451,11 +1195,17 @@ pub struct TlsStream<S> {
     ctx: CtxtHandle,
     stream: S,
     sizes: SecPkgContextStreamSizes,
+    alpn: Option<Vec<u8>>,
     enc_buf: Vec<u8>, // buffered ciphertext read from stream
     dec_buf: Vec<u8>, // leftover plaintext after a decrypt
 }
 
 impl<S> TlsStream<S> {
+    /// Returns the ALPN protocol negotiated during the handshake
+    pub fn negotiated_alpn(&self) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.alpn.clone())
+    }
+
     /// Returns a reference to the underlying stream
     pub const fn get_ref(&self) -> &S {
         &self.stream
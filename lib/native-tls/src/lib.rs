@@ -10,6 +10,8 @@
 use std::fmt::{self, Display, Formatter};
 use std::marker::PhantomData;
 
+use base64::prelude::*;
+
 // vendored feature: use rustls on all platforms (no native backend)
 #[cfg(feature = "vendored")]
 mod imp_rustls;
@@ -77,12 +79,200 @@ impl<S> Display for HandshakeError<S> {
 
 impl<S: fmt::Debug> std::error::Error for HandshakeError<S> {}
 
+// MARK: Protocol
+/// SSL/TLS protocol versions
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Protocol {
+    /// SSL 3.0
+    Sslv3,
+    /// TLS 1.0
+    Tlsv10,
+    /// TLS 1.1
+    Tlsv11,
+    /// TLS 1.2
+    Tlsv12,
+    /// TLS 1.3
+    Tlsv13,
+}
+
+// MARK: Certificate
+/// An X.509 certificate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate(Vec<u8>);
+
+impl Certificate {
+    /// Create a certificate from DER-encoded data
+    pub fn from_der(der: &[u8]) -> Result<Self, Error> {
+        // A DER certificate always starts with an ASN.1 SEQUENCE tag
+        if der.first() != Some(&0x30) {
+            return Err(Error("Invalid DER certificate".to_string()));
+        }
+        Ok(Self(der.to_vec()))
+    }
+
+    /// Create a certificate from the first certificate in PEM-encoded data
+    pub fn from_pem(pem: &[u8]) -> Result<Self, Error> {
+        let pem =
+            std::str::from_utf8(pem).map_err(|_| Error("Invalid PEM certificate".to_string()))?;
+        let body = pem
+            .split_once("-----BEGIN CERTIFICATE-----")
+            .and_then(|(_, rest)| rest.split_once("-----END CERTIFICATE-----"))
+            .map(|(body, _)| body)
+            .ok_or_else(|| Error("No certificate found in PEM data".to_string()))?;
+        let body = body
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect::<String>();
+        let der = BASE64_STANDARD
+            .decode(body)
+            .map_err(|_| Error("Invalid base64 in PEM certificate".to_string()))?;
+        Self::from_der(&der)
+    }
+
+    /// Returns the DER-encoded certificate
+    pub fn to_der(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.0.clone())
+    }
+}
+
+// MARK: TlsConnectorBuilder
+/// A builder for TlsConnector
+#[derive(Debug, Clone)]
+pub struct TlsConnectorBuilder {
+    alpn_protocols: Vec<String>,
+    accept_invalid_certs: bool,
+    accept_invalid_hostnames: bool,
+    use_sni: bool,
+    root_certificates: Vec<Certificate>,
+    disable_built_in_roots: bool,
+    min_protocol: Option<Protocol>,
+    max_protocol: Option<Protocol>,
+}
+
+impl Default for TlsConnectorBuilder {
+    fn default() -> Self {
+        Self {
+            alpn_protocols: Vec::new(),
+            accept_invalid_certs: false,
+            accept_invalid_hostnames: false,
+            use_sni: true,
+            root_certificates: Vec::new(),
+            disable_built_in_roots: false,
+            min_protocol: Some(Protocol::Tlsv12),
+            max_protocol: None,
+        }
+    }
+}
+
+impl TlsConnectorBuilder {
+    /// Set the ALPN protocols to request, in order of preference (e.g. `["h2", "http/1.1"]`)
+    pub fn request_alpns(&mut self, protocols: &[&str]) -> &mut Self {
+        self.alpn_protocols = protocols.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Accept invalid, untrusted and expired certificates
+    ///
+    /// **Warning**: this disables all certificate verification, only use it for local development
+    pub const fn danger_accept_invalid_certs(&mut self, accept: bool) -> &mut Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Accept certificates that are not valid for the connected domain
+    ///
+    /// **Warning**: this disables hostname verification, only use it for local development
+    pub const fn danger_accept_invalid_hostnames(&mut self, accept: bool) -> &mut Self {
+        self.accept_invalid_hostnames = accept;
+        self
+    }
+
+    /// Send the domain via Server Name Indication (SNI), enabled by default
+    pub const fn use_sni(&mut self, use_sni: bool) -> &mut Self {
+        self.use_sni = use_sni;
+        self
+    }
+
+    /// Add an extra trusted root certificate
+    pub fn add_root_certificate(&mut self, cert: Certificate) -> &mut Self {
+        self.root_certificates.push(cert);
+        self
+    }
+
+    /// Only trust the root certificates added with [`Self::add_root_certificate`]
+    pub const fn disable_built_in_roots(&mut self, disable: bool) -> &mut Self {
+        self.disable_built_in_roots = disable;
+        self
+    }
+
+    /// Set the minimum protocol version, `None` means the lowest version the backend supports
+    ///
+    /// Defaults to TLS 1.2
+    pub const fn min_protocol_version(&mut self, protocol: Option<Protocol>) -> &mut Self {
+        self.min_protocol = protocol;
+        self
+    }
+
+    /// Set the maximum protocol version, `None` means the highest version the backend supports
+    pub const fn max_protocol_version(&mut self, protocol: Option<Protocol>) -> &mut Self {
+        self.max_protocol = protocol;
+        self
+    }
+
+    /// Build the TlsConnector
+    pub fn build(&self) -> Result<TlsConnector, Error> {
+        if let (Some(min), Some(max)) = (self.min_protocol, self.max_protocol)
+            && min > max
+        {
+            return Err(Error(
+                "Minimum protocol version is higher than the maximum".to_string(),
+            ));
+        }
+        // ALPN protocols are sent with a single length byte
+        if self
+            .alpn_protocols
+            .iter()
+            .any(|protocol| protocol.is_empty() || protocol.len() > 255)
+        {
+            return Err(Error(
+                "ALPN protocols must be 1 to 255 bytes long".to_string(),
+            ));
+        }
+        TlsConnector::from_builder(self)
+    }
+
+    // ALPN protocol list in TLS wire format: each protocol prefixed by its length
+    #[cfg(not(any(feature = "vendored", target_os = "macos")))]
+    fn alpn_wire_format(&self) -> Vec<u8> {
+        let mut wire = Vec::new();
+        for protocol in &self.alpn_protocols {
+            wire.push(protocol.len() as u8);
+            wire.extend_from_slice(protocol.as_bytes());
+        }
+        wire
+    }
+}
+
 // MARK: TlsConnector
 /// A TLS connector for creating TLS connections
 pub use imp::TlsConnector;
+
+impl TlsConnector {
+    /// Returns a new builder for a TlsConnector
+    pub fn builder() -> TlsConnectorBuilder {
+        TlsConnectorBuilder::default()
+    }
+}
 // MARK: TlsStream
 /// A TLS stream wrapping an underlying I/O stream
 pub use imp::TlsStream;
+// MARK: Identity
+/// A server certificate, private key and certificate chain
+pub use imp::Identity;
+// MARK: TlsAcceptor
+/// A TLS acceptor for accepting server-side TLS connections
+pub use imp::TlsAcceptor;
 
 // MARK: Tests
 #[cfg(test)]
@@ -104,11 +294,22 @@ mod tests {
     const EXPIRED_LOCALHOST_CERT_PEM: &[u8] =
         include_bytes!("../tests/certs/expired-localhost.crt");
     const EXPIRED_LOCALHOST_KEY_PEM: &[u8] = include_bytes!("../tests/certs/expired-localhost.key");
+    const CA_CERT_PEM: &[u8] = include_bytes!("../tests/certs/ca.crt");
+    const CA_LOCALHOST_CERT_PEM: &[u8] = include_bytes!("../tests/certs/ca-localhost.crt");
+    const CA_LOCALHOST_KEY_PEM: &[u8] = include_bytes!("../tests/certs/ca-localhost.key");
+    const CA_WRONG_HOST_CERT_PEM: &[u8] = include_bytes!("../tests/certs/ca-wrong-host.crt");
+    const CA_WRONG_HOST_KEY_PEM: &[u8] = include_bytes!("../tests/certs/ca-wrong-host.key");
+    #[cfg(not(feature = "vendored"))]
+    const LOCALHOST_P12: &[u8] = include_bytes!("../tests/certs/localhost.p12");
+    #[cfg(not(feature = "vendored"))]
+    const LOCALHOST_P12_PASSWORD: &str = "password";
 
     fn local_https_get(server_addr: std::net::SocketAddr, path: &str) -> String {
         let tcp = TcpStream::connect(server_addr).expect("TCP connect failed");
-        let connector =
-            TlsConnector::new_danger_accept_invalid_certs().expect("TlsConnector::new failed");
+        let connector = TlsConnector::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .expect("TlsConnector::build failed");
         let mut tls = connector
             .connect("localhost", tcp)
             .expect("TLS handshake failed");
@@ -220,11 +421,11 @@ mod tests {
         (server_addr, server)
     }
 
-    fn assert_local_tls_server_rejected(
+    fn local_tls_connect(
+        connector: &TlsConnector,
         domain: &str,
-        cert_pem: &'static [u8],
-        key_pem: &'static [u8],
-    ) {
+        config: rustls::ServerConfig,
+    ) -> Result<TlsStream<TcpStream>, HandshakeError<TcpStream>> {
         let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).expect("bind failed");
         let server_addr = listener.local_addr().expect("local_addr failed");
         let server = thread::spawn(move || {
@@ -234,19 +435,222 @@ mod tests {
             tcp.set_write_timeout(Some(Duration::from_secs(5)))
                 .expect("set_write_timeout failed");
 
-            let mut conn =
-                rustls::ServerConnection::new(Arc::new(local_tls_server_config(cert_pem, key_pem)))
-                    .unwrap();
-            let _ = conn.complete_io(&mut tcp);
+            let mut conn = rustls::ServerConnection::new(Arc::new(config))
+                .expect("test TLS server connection should build");
+            while conn.is_handshaking() {
+                if conn.complete_io(&mut tcp).is_err() {
+                    break;
+                }
+            }
         });
 
         let tcp = TcpStream::connect(server_addr).expect("TCP connect failed");
-        let connector = TlsConnector::new().expect("TlsConnector::new failed");
         let result = connector.connect(domain, tcp);
+        server.join().expect("test TLS server thread panicked");
+        result
+    }
+
+    fn assert_local_tls_server_rejected(
+        domain: &str,
+        cert_pem: &'static [u8],
+        key_pem: &'static [u8],
+    ) {
+        let connector = TlsConnector::new().expect("TlsConnector::new failed");
+        let result = local_tls_connect(
+            &connector,
+            domain,
+            local_tls_server_config(cert_pem, key_pem),
+        );
         assert!(result.is_err(), "Expected TLS certificate to be rejected");
+    }
+
+    fn ca_connector(configure: impl FnOnce(&mut TlsConnectorBuilder)) -> TlsConnector {
+        let mut builder = TlsConnector::builder();
+        builder
+            .add_root_certificate(Certificate::from_pem(CA_CERT_PEM).expect("CA should parse"))
+            .disable_built_in_roots(true);
+        configure(&mut builder);
+        builder.build().expect("TlsConnector::build failed")
+    }
+
+    fn local_identity() -> Identity {
+        #[cfg(feature = "vendored")]
+        let identity = Identity::from_pkcs8(LOCALHOST_CERT_PEM, LOCALHOST_KEY_PEM);
+        #[cfg(not(feature = "vendored"))]
+        let identity = Identity::from_pkcs12(LOCALHOST_P12, LOCALHOST_P12_PASSWORD);
+        identity.expect("test identity should load")
+    }
+
+    fn spawn_acceptor_server(
+        acceptor: TlsAcceptor,
+        response_body: &'static [u8],
+    ) -> (std::net::SocketAddr, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).expect("bind failed");
+        let server_addr = listener.local_addr().expect("local_addr failed");
+        let server = thread::spawn(move || {
+            let (tcp, _) = listener.accept().expect("accept failed");
+            tcp.set_read_timeout(Some(Duration::from_secs(5)))
+                .expect("set_read_timeout failed");
+            let mut tls = acceptor.accept(tcp).expect("TLS accept failed");
+
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let bytes_read = tls.read(&mut buffer).expect("read request failed");
+                assert!(bytes_read > 0, "connection closed before HTTP request");
+                request.extend_from_slice(&buffer[..bytes_read]);
+            }
+
+            let mut response = format!(
+                "HTTP/1.0 200 OK\r\nContent-Length: {}\r\n\r\n",
+                response_body.len()
+            )
+            .into_bytes();
+            response.extend_from_slice(response_body);
+            tls.write_all(&response).expect("write response failed");
+            tls.flush().expect("flush response failed");
+        });
+        (server_addr, server)
+    }
+
+    #[test]
+    fn test_acceptor_handshake_succeeds() {
+        let acceptor = TlsAcceptor::new(local_identity()).expect("TlsAcceptor::new failed");
+        let (server_addr, server) = spawn_acceptor_server(acceptor, b"accepted");
+        let response = local_https_get(server_addr, "/");
+        assert!(
+            response.ends_with("accepted"),
+            "Expected accepted response, got: {response}"
+        );
         server.join().expect("test TLS server thread panicked");
     }
 
+    #[cfg(any(feature = "vendored", not(any(target_os = "macos", windows))))]
+    #[test]
+    fn test_acceptor_pkcs8_identity() {
+        let identity = Identity::from_pkcs8(LOCALHOST_CERT_PEM, LOCALHOST_KEY_PEM)
+            .expect("PKCS#8 identity should load");
+        let acceptor = TlsAcceptor::new(identity).expect("TlsAcceptor::new failed");
+        let (server_addr, server) = spawn_acceptor_server(acceptor, b"pkcs8");
+        let response = local_https_get(server_addr, "/");
+        assert!(response.ends_with("pkcs8"), "Expected pkcs8 response");
+        server.join().expect("test TLS server thread panicked");
+    }
+
+    #[cfg(not(feature = "vendored"))]
+    #[test]
+    fn test_identity_pkcs12_wrong_password_rejected() {
+        assert!(Identity::from_pkcs12(LOCALHOST_P12, "wrong").is_err());
+        assert!(Identity::from_pkcs12(b"not a pkcs12 archive", LOCALHOST_P12_PASSWORD).is_err());
+    }
+
+    #[test]
+    fn test_identity_invalid_pem_rejected() {
+        assert!(Identity::from_pkcs8(b"not a certificate", LOCALHOST_KEY_PEM).is_err());
+        assert!(Identity::from_pkcs8(LOCALHOST_CERT_PEM, b"not a key").is_err());
+    }
+
+    #[test]
+    fn test_certificate_pem_and_der() {
+        let cert = Certificate::from_pem(CA_CERT_PEM).unwrap();
+        let der = cert.to_der().unwrap();
+        assert_eq!(der[0], 0x30);
+        assert_eq!(Certificate::from_der(&der).unwrap().to_der().unwrap(), der);
+        assert!(Certificate::from_pem(b"not a certificate").is_err());
+        assert!(Certificate::from_der(b"not a certificate").is_err());
+    }
+
+    #[test]
+    fn test_builder_root_certificate() {
+        let config = || local_tls_server_config(CA_LOCALHOST_CERT_PEM, CA_LOCALHOST_KEY_PEM);
+        assert_local_tls_server_rejected("localhost", CA_LOCALHOST_CERT_PEM, CA_LOCALHOST_KEY_PEM);
+        assert!(local_tls_connect(&ca_connector(|_| {}), "localhost", config()).is_ok());
+        assert!(
+            local_tls_connect(
+                &ca_connector(|builder| {
+                    builder.use_sni(false);
+                }),
+                "localhost",
+                config()
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_builder_accept_invalid_hostnames() {
+        let config = || local_tls_server_config(CA_WRONG_HOST_CERT_PEM, CA_WRONG_HOST_KEY_PEM);
+        assert!(local_tls_connect(&ca_connector(|_| {}), "localhost", config()).is_err());
+        let connector = ca_connector(|builder| {
+            builder.danger_accept_invalid_hostnames(true);
+        });
+        assert!(local_tls_connect(&connector, "localhost", config()).is_ok());
+
+        // Accepting invalid hostnames must still verify the certificate chain
+        let connector = TlsConnector::builder()
+            .danger_accept_invalid_hostnames(true)
+            .build()
+            .unwrap();
+        let config = local_tls_server_config(WRONG_HOST_CERT_PEM, WRONG_HOST_KEY_PEM);
+        assert!(local_tls_connect(&connector, "localhost", config).is_err());
+    }
+
+    #[test]
+    fn test_builder_alpn() {
+        let config = || {
+            let mut config = local_tls_server_config(LOCALHOST_CERT_PEM, LOCALHOST_KEY_PEM);
+            config.alpn_protocols = vec![b"h2".to_vec()];
+            config
+        };
+
+        let connector = TlsConnector::builder()
+            .danger_accept_invalid_certs(true)
+            .request_alpns(&["h2", "http/1.1"])
+            .build()
+            .unwrap();
+        let tls = local_tls_connect(&connector, "localhost", config()).unwrap();
+        assert_eq!(tls.negotiated_alpn().unwrap(), Some(b"h2".to_vec()));
+
+        let connector = TlsConnector::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap();
+        let tls = local_tls_connect(&connector, "localhost", config()).unwrap();
+        assert_eq!(tls.negotiated_alpn().unwrap(), None);
+
+        assert!(
+            TlsConnector::builder()
+                .request_alpns(&[""])
+                .build()
+                .is_err()
+        );
+        assert!(
+            TlsConnector::builder()
+                .request_alpns(&[&"a".repeat(256)])
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_builder_protocol_versions() {
+        let connector = TlsConnector::builder()
+            .danger_accept_invalid_certs(true)
+            .max_protocol_version(Some(Protocol::Tlsv12))
+            .build()
+            .unwrap();
+        let config = local_tls_server_config(LOCALHOST_CERT_PEM, LOCALHOST_KEY_PEM);
+        assert!(local_tls_connect(&connector, "localhost", config).is_ok());
+
+        assert!(
+            TlsConnector::builder()
+                .min_protocol_version(Some(Protocol::Tlsv13))
+                .max_protocol_version(Some(Protocol::Tlsv12))
+                .build()
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_handshake_succeeds() {
         let (server_addr, server) = spawn_local_https_server(b"ok", 1);
@@ -24,6 +24,13 @@ unsafe extern "C" {
     #[cfg(target_arch = "x86_64")]
     pub fn objc_msgSend_stret(ret: *mut c_void, receiver: *mut Object, sel: *const c_void, ...);
     pub fn objc_msgSendSuper(receiver: *const objc_super, sel: *const c_void, ...) -> *mut c_void;
+    #[cfg(target_arch = "x86_64")]
+    pub fn objc_msgSendSuper_stret(
+        ret: *mut c_void,
+        receiver: *const objc_super,
+        sel: *const c_void,
+        ...
+    );
 
     pub fn object_getClass(obj: *const Object) -> *mut c_void;
     pub fn class_getInstanceVariable(cls: *const c_void, name: *const c_char) -> *const c_void;
@@ -55,4 +62,7 @@ unsafe extern "C" {
 
     pub fn objc_autoreleasePoolPush() -> *mut c_void;
     pub fn objc_autoreleasePoolPop(pool: *mut c_void);
+    pub fn objc_retain(obj: *mut Object) -> *mut Object;
+    pub fn objc_release(obj: *mut Object);
+    pub fn objc_autorelease(obj: *mut Object) -> *mut Object;
 }
@@ -18,6 +18,8 @@ pub mod encode;
 pub mod ffi;
 #[doc(hidden)]
 pub mod macros;
+/// NotificationCenter and key-value observing helpers
+pub mod observe;
 /// Reference counting utilities
 pub mod rc;
 /// Runtime
@@ -6,8 +6,8 @@
 
 use std::ffi::c_void;
 
-use crate::ffi::objc_msgSend;
-use crate::runtime::{AnyObject, Sel};
+use crate::ffi::{objc_msgSend, objc_msgSendSuper, objc_super};
+use crate::runtime::{AnyClass, AnyObject, Sel};
 
 /// Get class by name
 #[macro_export]
@@ -59,6 +59,16 @@ pub trait MessageSend {
     /// # Safety
     /// Caller must ensure `obj` is a valid ObjC object and `sel` is a valid selector.
     unsafe fn invoke<R: crate::Encode>(obj: *mut AnyObject, sel: Sel, args: Self) -> R;
+
+    /// # Safety
+    /// Caller must ensure `obj` is a valid ObjC object, `superclass` is a superclass of its
+    /// class and `sel` is a valid selector.
+    unsafe fn invoke_super<R: crate::Encode>(
+        obj: *mut AnyObject,
+        superclass: *mut AnyObject,
+        sel: Sel,
+        args: Self,
+    ) -> R;
 }
 macro_rules! message_send_impl {
     ($($a:ident : $t:ident),*) => (
@@ -94,6 +104,42 @@ macro_rules! message_send_impl {
                     imp(obj, sel.0, $($a,)*)
                 }
             }
+
+            #[inline(always)]
+            unsafe fn invoke_super<R: crate::Encode>(
+                obj: *mut AnyObject,
+                superclass: *mut AnyObject,
+                sel: Sel,
+                ($($a,)*): Self,
+            ) -> R {
+                let sup = objc_super {
+                    receiver: obj,
+                    super_class: superclass as *const AnyClass,
+                };
+                #[cfg(target_arch = "x86_64")]
+                // SAFETY: same as `invoke`, with `objc_msgSendSuper` taking a pointer to the
+                // `objc_super` struct on the stack instead of the receiver.
+                unsafe {
+                    if const { size_of::<R>() > 16 } {
+                        let mut ret = std::mem::zeroed();
+                        let imp: unsafe extern "C" fn (*mut R, *const objc_super, *const c_void, $($t,)*) =
+                            std::mem::transmute(crate::ffi::objc_msgSendSuper_stret as *const c_void);
+                        imp(&mut ret, &sup, sel.0, $($a,)*);
+                        ret
+                    } else {
+                        let imp: unsafe extern "C" fn (*const objc_super, *const c_void, $($t,)*) -> R =
+                            std::mem::transmute(objc_msgSendSuper as *const c_void);
+                        imp(&sup, sel.0, $($a,)*)
+                    }
+                }
+                #[cfg(not(target_arch = "x86_64"))]
+                // SAFETY: see the x86_64 branch above for the full justification.
+                unsafe {
+                    let imp: unsafe extern "C" fn (*const objc_super, *const c_void, $($t,)*) -> R =
+                        std::mem::transmute(objc_msgSendSuper as *const c_void);
+                    imp(&sup, sel.0, $($a,)*)
+                }
+            }
         }
     );
 }
@@ -122,6 +168,42 @@ macro_rules! msg_send {
     );
 }
 
+/// Send message to the superclass implementation of a method, e.g. from an overriding method
+#[macro_export]
+macro_rules! msg_send_super {
+    ($receiver:expr, $superclass:expr, $sel:ident) => (
+        $crate::macros::MessageSend::invoke_super($receiver, $superclass, $crate::sel!($sel), ())
+    );
+    ($receiver:expr, $superclass:expr $(,$sel:ident : $arg:expr)+) => (
+        $crate::macros::MessageSend::invoke_super(
+            $receiver,
+            $superclass,
+            $crate::sel!($($sel:)+),
+            ($($arg,)+),
+        )
+    );
+}
+
+/// Send message to object and wrap the returned object in a [`Retained`](crate::rc::Retained)
+///
+/// Objects returned by the `alloc`, `new`, `init`, `copy` and `mutableCopy` method families are
+/// already retained, other returned objects are retained. Panics when the returned object is null.
+#[macro_export]
+macro_rules! msg_send_id {
+    ($receiver:expr, $sel:ident) => (
+        $crate::rc::Retained::__from_msg_send(
+            $crate::msg_send![$receiver, $sel],
+            stringify!($sel),
+        )
+    );
+    ($receiver:expr $(,$sel:ident : $arg:expr)+) => (
+        $crate::rc::Retained::__from_msg_send(
+            $crate::msg_send![$receiver $(,$sel : $arg)+],
+            [$(stringify!($sel)),+][0],
+        )
+    );
+}
+
 // MARK: Tests
 #[cfg(test)]
 mod test {
@@ -245,6 +327,44 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_msg_send_super() {
+        extern "C" fn hash(_this: *mut AnyObject, _cmd: crate::runtime::Sel) -> u64 {
+            42
+        }
+        extern "C" fn is_equal(
+            this: *mut AnyObject,
+            _cmd: crate::runtime::Sel,
+            other: *mut AnyObject,
+        ) -> crate::runtime::Bool {
+            // SAFETY: `this` is an instance of a direct NSObject subclass.
+            unsafe { msg_send_super![this, class!(NSObject), isEqual: other] }
+        }
+
+        let mut builder =
+            crate::runtime::ClassBuilder::new(c"TestSuperClass", class!(NSObject)).unwrap();
+        assert!(builder.add_method(sel!(hash), hash as extern "C" fn(_, _) -> _));
+        assert!(builder.add_method(sel!(isEqual:), is_equal as extern "C" fn(_, _, _) -> _));
+        let class = builder.register();
+
+        // SAFETY: `class` is a registered NSObject subclass; all selectors match their signatures.
+        unsafe {
+            let obj: *mut AnyObject = msg_send![class, new];
+            let overridden: u64 = msg_send![obj, hash];
+            assert_eq!(overridden, 42);
+            let original: u64 = msg_send_super![obj, class!(NSObject), hash];
+            assert_eq!(original, obj as u64);
+
+            let other: *mut AnyObject = msg_send![class, new];
+            let equal: crate::runtime::Bool = msg_send![obj, isEqual: obj];
+            assert_eq!(equal, crate::runtime::Bool::YES);
+            let equal: crate::runtime::Bool = msg_send![obj, isEqual: other];
+            assert_eq!(equal, crate::runtime::Bool::NO);
+            let _: () = msg_send![other, release];
+            let _: () = msg_send![obj, release];
+        }
+    }
+
     #[test]
     fn test_sel_macro_single_name() {
         // SAFETY: `sel!(length)` is a registered selector; sel_getName returns a valid null-terminated C string.
@@ -0,0 +1,260 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::ffi::{CStr, c_void};
+use std::ptr::null_mut;
+use std::sync::OnceLock;
+
+use crate::ffi::{class_getInstanceVariable, ivar_getOffset, object_getClass};
+use crate::rc::Retained;
+use crate::runtime::{AnyObject, ClassBuilder, Sel};
+use crate::{class, msg_send, msg_send_id, sel};
+
+#[link(name = "Foundation", kind = "framework")]
+unsafe extern "C" {}
+
+const NS_KEY_VALUE_OBSERVING_OPTION_NEW: usize = 1;
+
+// Foundation calls observers on the thread that posts the notification or changes the value,
+// so the closure must be callable from any thread
+type Callback = Box<dyn Fn(*mut AnyObject) + Send + Sync>;
+
+const CALLBACK_IVAR: &CStr = c"_callback";
+
+// MARK: Observer class
+// NSObject subclass that forwards notifications and key-value changes to a boxed Rust closure
+fn observer_class() -> *mut AnyObject {
+    static CLASS: OnceLock<usize> = OnceLock::new();
+    *CLASS.get_or_init(|| {
+        extern "C" fn observe_notification(
+            this: *mut AnyObject,
+            _cmd: Sel,
+            notification: *mut AnyObject,
+        ) {
+            // SAFETY: this method is only registered on the observer class.
+            unsafe { call_callback(this, notification) };
+        }
+
+        extern "C" fn observe_value(
+            this: *mut AnyObject,
+            _cmd: Sel,
+            _key_path: *mut AnyObject,
+            _object: *mut AnyObject,
+            change: *mut AnyObject,
+            _context: *mut c_void,
+        ) {
+            // SAFETY: this method is only registered on the observer class.
+            unsafe { call_callback(this, change) };
+        }
+
+        let mut builder = ClassBuilder::new(c"Objc2ClosureObserver", class!(NSObject))
+            .expect("class \"Objc2ClosureObserver\" already registered");
+        builder.add_ivar::<*mut c_void>(CALLBACK_IVAR);
+        builder.add_method(
+            sel!(observeNotification:),
+            observe_notification as extern "C" fn(_, _, _),
+        );
+        builder.add_method(
+            sel!(observeValueForKeyPath:ofObject:change:context:),
+            observe_value as extern "C" fn(_, _, _, _, _, _),
+        );
+        builder.register() as usize
+    }) as *mut AnyObject
+}
+
+/// # Safety
+/// `this` must be a valid instance of the observer class.
+unsafe fn callback_slot(this: *mut AnyObject) -> *mut *mut Callback {
+    // SAFETY: the caller guarantees `this` is an observer instance, which has the callback
+    // ivar of pointer size.
+    unsafe {
+        let ivar = class_getInstanceVariable(object_getClass(this), CALLBACK_IVAR.as_ptr());
+        (this as *mut u8).offset(ivar_getOffset(ivar)) as *mut *mut Callback
+    }
+}
+
+/// # Safety
+/// `this` must be a valid instance of the observer class.
+unsafe fn call_callback(this: *mut AnyObject, argument: *mut AnyObject) {
+    // SAFETY: the caller guarantees `this` is an observer instance, the slot is null or a
+    // callback boxed by `new_observer` that is only freed after the observer is removed.
+    unsafe {
+        let callback = *callback_slot(this);
+        if !callback.is_null() {
+            (*callback)(argument);
+        }
+    }
+}
+
+fn new_observer(callback: Callback) -> Retained<AnyObject> {
+    // SAFETY: the observer class is a registered NSObject subclass, `new` returns a +1 object
+    // whose callback slot we initialize before handing it to Foundation.
+    unsafe {
+        let observer: *mut AnyObject = msg_send![observer_class(), new];
+        *callback_slot(observer) = Box::into_raw(Box::new(callback));
+        Retained::from_raw(observer).expect("Can't create observer")
+    }
+}
+
+fn free_callback(observer: &Retained<AnyObject>) {
+    // SAFETY: `observer` was created by `new_observer` and is no longer registered, so the
+    // callback can't be called anymore while it is freed.
+    unsafe {
+        let slot = callback_slot(Retained::as_ptr(observer));
+        if !(*slot).is_null() {
+            drop(Box::from_raw(*slot));
+            *slot = null_mut();
+        }
+    }
+}
+
+fn ns_string(string: &CStr) -> Retained<AnyObject> {
+    // SAFETY: `string` is a valid null-terminated C string.
+    unsafe { msg_send_id![class!(NSString), stringWithUTF8String: string.as_ptr()] }
+}
+
+// MARK: NotificationCenter
+/// Closure observing notifications of the default notification center, stops observing when
+/// dropped
+pub struct NotificationObserver {
+    center: Retained<AnyObject>,
+    observer: Retained<AnyObject>,
+}
+
+/// Call `callback` with the `NSNotification` for every notification named `name` that is
+/// posted by `object`, or by any object when `None`. The callback runs on the thread that posts
+/// the notification
+pub fn observe_notification(
+    name: &CStr,
+    object: Option<&AnyObject>,
+    callback: impl Fn(*mut AnyObject) + Send + Sync + 'static,
+) -> NotificationObserver {
+    let observer = new_observer(Box::new(callback));
+    let name = ns_string(name);
+    let object = object.map_or(null_mut(), |object| {
+        object as *const AnyObject as *mut AnyObject
+    });
+    // SAFETY: all objects are valid, the observer implements the `observeNotification:`
+    // selector and is removed from the center before it is released.
+    unsafe {
+        let center: Retained<AnyObject> = msg_send_id![class!(NSNotificationCenter), defaultCenter];
+        let _: () = msg_send![
+            Retained::as_ptr(&center),
+            addObserver: Retained::as_ptr(&observer),
+            selector: sel!(observeNotification:),
+            name: Retained::as_ptr(&name),
+            object: object
+        ];
+        NotificationObserver { center, observer }
+    }
+}
+
+impl Drop for NotificationObserver {
+    fn drop(&mut self) {
+        // SAFETY: the center and observer are valid objects kept alive by this token.
+        unsafe {
+            let _: () = msg_send![
+                Retained::as_ptr(&self.center),
+                removeObserver: Retained::as_ptr(&self.observer)
+            ];
+        }
+        free_callback(&self.observer);
+    }
+}
+
+// MARK: Key-value observing
+/// Closure observing a key path of an object, stops observing when dropped
+///
+/// The observed object is retained so it can't be deallocated while it is observed.
+pub struct KeyValueObserver {
+    object: Retained<AnyObject>,
+    key_path: Retained<AnyObject>,
+    observer: Retained<AnyObject>,
+}
+
+/// Call `callback` with the change dictionary every time the value at `key_path` of `object`
+/// changes. The callback runs on the thread that changes the value
+pub fn observe_key_path(
+    object: &AnyObject,
+    key_path: &CStr,
+    callback: impl Fn(*mut AnyObject) + Send + Sync + 'static,
+) -> KeyValueObserver {
+    let observer = new_observer(Box::new(callback));
+    let key_path = ns_string(key_path);
+    // SAFETY: all objects are valid, the observer implements the
+    // `observeValueForKeyPath:ofObject:change:context:` selector and is removed from the
+    // object before it is released.
+    unsafe {
+        let object =
+            Retained::retain(object as *const AnyObject as *mut AnyObject).expect("Should be some");
+        let _: () = msg_send![
+            Retained::as_ptr(&object),
+            addObserver: Retained::as_ptr(&observer),
+            forKeyPath: Retained::as_ptr(&key_path),
+            options: NS_KEY_VALUE_OBSERVING_OPTION_NEW,
+            context: null_mut::<c_void>()
+        ];
+        KeyValueObserver {
+            object,
+            key_path,
+            observer,
+        }
+    }
+}
+
+impl Drop for KeyValueObserver {
+    fn drop(&mut self) {
+        // SAFETY: the object, key path and observer are valid objects kept alive by this token.
+        unsafe {
+            let _: () = msg_send![
+                Retained::as_ptr(&self.object),
+                removeObserver: Retained::as_ptr(&self.observer),
+                forKeyPath: Retained::as_ptr(&self.key_path)
+            ];
+        }
+        free_callback(&self.observer);
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_observe_notification() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let observer = observe_notification(c"Objc2TestNotification", None, {
+            let calls = calls.clone();
+            move |notification| {
+                assert!(!notification.is_null());
+                calls.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let post = || {
+            let name = ns_string(c"Objc2TestNotification");
+            // SAFETY: the center and name are valid objects.
+            unsafe {
+                let center: *mut AnyObject = msg_send![class!(NSNotificationCenter), defaultCenter];
+                let _: () = msg_send![
+                    center,
+                    postNotificationName: Retained::as_ptr(&name),
+                    object: null_mut::<AnyObject>()
+                ];
+            }
+        };
+        post();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        drop(observer);
+        post();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
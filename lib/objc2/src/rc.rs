@@ -4,7 +4,14 @@
  * SPDX-License-Identifier: MIT
  */
 
-use crate::ffi::{objc_autoreleasePoolPop, objc_autoreleasePoolPush};
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Deref;
+use std::ptr::NonNull;
+
+use crate::ffi::{
+    objc_autorelease, objc_autoreleasePoolPop, objc_autoreleasePoolPush, objc_release, objc_retain,
+};
+use crate::runtime::AnyObject;
 
 /// A token representing an active autorelease pool
 pub struct AutoreleasePool(());
@@ -25,6 +32,127 @@ where
     }
 }
 
+// MARK: Retained
+/// A strong reference to an Objective-C object, released when dropped
+pub struct Retained<T> {
+    ptr: NonNull<T>,
+}
+
+impl<T> Retained<T> {
+    /// Take ownership of an object pointer with a +1 retain count (e.g. from `new`, `alloc`,
+    /// `init` or `copy`), returns `None` for null
+    ///
+    /// # Safety
+    /// `ptr` must be null or a valid Objective-C object whose retain count the caller owns.
+    pub const unsafe fn from_raw(ptr: *mut T) -> Option<Self> {
+        match NonNull::new(ptr) {
+            Some(ptr) => Some(Self { ptr }),
+            None => None,
+        }
+    }
+
+    /// Retain an object pointer the caller does not own (e.g. an autoreleased return value),
+    /// returns `None` for null
+    ///
+    /// # Safety
+    /// `ptr` must be null or a valid Objective-C object.
+    pub unsafe fn retain(ptr: *mut T) -> Option<Self> {
+        // SAFETY: the caller guarantees ptr is null or a valid object; objc_retain accepts null.
+        unsafe { Self::from_raw(objc_retain(ptr as *mut AnyObject) as *mut T) }
+    }
+
+    /// Returns the raw object pointer without changing the retain count
+    pub const fn as_ptr(this: &Self) -> *mut T {
+        this.ptr.as_ptr()
+    }
+
+    /// Consume the reference and return the raw object pointer, the caller must release it
+    pub const fn into_raw(this: Self) -> *mut T {
+        let ptr = this.ptr.as_ptr();
+        std::mem::forget(this);
+        ptr
+    }
+
+    /// Hand the reference over to the current autorelease pool
+    pub fn autorelease_ptr(this: Self) -> *mut T {
+        // SAFETY: into_raw returns a valid object whose retain count we own.
+        unsafe { objc_autorelease(Self::into_raw(this) as *mut AnyObject) as *mut T }
+    }
+
+    /// Hand the reference over to the given autorelease pool, which keeps it alive for its lifetime
+    pub fn autorelease(this: Self, _pool: &AutoreleasePool) -> &T {
+        // SAFETY: the pool token is only handed out inside autoreleasepool, so the object
+        // stays alive until the pool is popped, which outlives the borrow of the token.
+        unsafe { &*Self::autorelease_ptr(this) }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn __from_msg_send(ptr: *mut T, sel: &str) -> Self {
+        // SAFETY: the caller (msg_send_id!) passes the object returned by the selector sel.
+        let retained = unsafe {
+            if returns_retained(sel) {
+                Self::from_raw(ptr)
+            } else {
+                Self::retain(ptr)
+            }
+        };
+        retained.unwrap_or_else(|| panic!("unexpected null object returned from {sel}"))
+    }
+}
+
+// Methods in the alloc, new, init, copy and mutableCopy families return a +1 retained object,
+// see https://clang.llvm.org/docs/AutomaticReferenceCounting.html#method-families
+const fn returns_retained(sel: &str) -> bool {
+    const FAMILIES: [&str; 5] = ["alloc", "new", "init", "copy", "mutableCopy"];
+    let sel = sel.as_bytes();
+    let mut i = 0;
+    while i < FAMILIES.len() {
+        let family = FAMILIES[i].as_bytes();
+        if sel.len() >= family.len() {
+            let mut j = 0;
+            while j < family.len() && sel[j] == family[j] {
+                j += 1;
+            }
+            // The family name must be followed by a non-lowercase character
+            if j == family.len() && (sel.len() == j || !sel[j].is_ascii_lowercase()) {
+                return true;
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+impl<T> Deref for Retained<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: ptr is a valid object kept alive by our retain count.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> Clone for Retained<T> {
+    fn clone(&self) -> Self {
+        // SAFETY: ptr is a valid object; objc_retain returns the same non-null pointer.
+        unsafe { objc_retain(self.ptr.as_ptr() as *mut AnyObject) };
+        Self { ptr: self.ptr }
+    }
+}
+
+impl<T> Drop for Retained<T> {
+    fn drop(&mut self) {
+        // SAFETY: ptr is a valid object whose retain count we own; Drop is called once.
+        unsafe { objc_release(self.ptr.as_ptr() as *mut AnyObject) };
+    }
+}
+
+impl<T> Debug for Retained<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Retained").field(&self.ptr).finish()
+    }
+}
+
 // MARK: Tests
 #[cfg(test)]
 mod test {
@@ -58,6 +186,64 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_returns_retained() {
+        assert!(returns_retained("new"));
+        assert!(returns_retained("alloc"));
+        assert!(returns_retained("initWithBytes"));
+        assert!(returns_retained("copy"));
+        assert!(returns_retained("mutableCopyWithZone"));
+        assert!(!returns_retained("newline"));
+        assert!(!returns_retained("initialize"));
+        assert!(!returns_retained("string"));
+        assert!(!returns_retained("copyright"));
+    }
+
+    #[test]
+    fn test_retained_retain_and_release() {
+        #[link(name = "Foundation", kind = "framework")]
+        unsafe extern "C" {}
+
+        // SAFETY: NSObject is a valid Foundation class; new returns a +1 retained object.
+        let obj: Retained<AnyObject> = unsafe { crate::msg_send_id![crate::class!(NSObject), new] };
+        let retain_count = || -> u64 {
+            // SAFETY: obj is kept alive by the Retained reference.
+            unsafe { crate::msg_send![Retained::as_ptr(&obj), retainCount] }
+        };
+        assert_eq!(retain_count(), 1);
+        let clone = obj.clone();
+        assert_eq!(Retained::as_ptr(&clone), Retained::as_ptr(&obj));
+        assert_eq!(retain_count(), 2);
+        drop(clone);
+        assert_eq!(retain_count(), 1);
+
+        // SAFETY: obj is a valid object, retain adds a reference we own.
+        let other = unsafe { Retained::retain(Retained::as_ptr(&obj)) }.unwrap();
+        assert_eq!(retain_count(), 2);
+        drop(other);
+        assert_eq!(retain_count(), 1);
+
+        // SAFETY: a null pointer is not an object.
+        assert!(unsafe { Retained::<AnyObject>::from_raw(std::ptr::null_mut()) }.is_none());
+    }
+
+    #[test]
+    fn test_retained_autorelease() {
+        #[link(name = "Foundation", kind = "framework")]
+        unsafe extern "C" {}
+
+        autoreleasepool(|pool| {
+            // SAFETY: NSString is a valid Foundation class; string returns an autoreleased object.
+            let string: Retained<AnyObject> =
+                unsafe { crate::msg_send_id![crate::class!(NSString), string] };
+            let string = Retained::autorelease(string, pool);
+            // SAFETY: string stays alive until the pool is drained.
+            let length: u64 =
+                unsafe { crate::msg_send![string as *const AnyObject as *mut AnyObject, length] };
+            assert_eq!(length, 0);
+        });
+    }
+
     #[test]
     fn test_autoreleasepool_with_objc_objects() {
         #[link(name = "Foundation", kind = "framework")]
@@ -67,10 +253,9 @@ mod test {
             // SAFETY: NSObject is a valid Foundation class; new returns a fully initialized object;
             // autorelease hands ownership to the pool, which releases it when drained.
             unsafe {
-                let obj: *mut crate::runtime::AnyObject =
-                    crate::msg_send![crate::class!(NSObject), new];
+                let obj: *mut AnyObject = crate::msg_send![crate::class!(NSObject), new];
                 assert!(!obj.is_null());
-                let _: *mut crate::runtime::AnyObject = crate::msg_send![obj, autorelease];
+                let _: *mut AnyObject = crate::msg_send![obj, autorelease];
             }
         });
     }
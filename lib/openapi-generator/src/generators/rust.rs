@@ -1,31 +1,29 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 use std::fmt::Write;
-use std::path::Path;
 
 use indexmap::IndexMap;
 
-use crate::openapi::Schema;
+use crate::openapi::{Schema, SchemaValue};
 use crate::utils::ToCase;
 
-pub(crate) fn generate_schemas(schemas: IndexMap<String, Schema>, output_path: &Path) {
+pub(crate) fn generate_schemas(schemas: IndexMap<String, Schema>) -> String {
     // Generate code for schemas
     let mut code_schemas = IndexMap::new();
     for (name, schema) in schemas {
         schema_generate_code(&mut code_schemas, name.clone(), &schema);
     }
 
-    // Write .rs file
-    let mut code = "// This file is generated by openapi-generator, do not edit!\n\n".to_string();
+    // Concat schemas code
+    let mut code = String::new();
     for code_schema in code_schemas.values() {
         code.push_str(code_schema);
     }
-    std::fs::write(output_path, code)
-        .unwrap_or_else(|_| panic!("Failed to write: {}", output_path.display()));
+    code
 }
 
 fn schema_generate_code(
@@ -41,22 +39,24 @@ fn schema_generate_code(
     }
 
     if let Some(r#enum) = &schema.r#enum {
-        let mut code = format!(
-            "#[derive(Copy, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]\npub(crate) enum {name} {{\n"
-        );
-        for variant in r#enum {
-            _ = writeln!(
-                code,
-                "    #[serde(rename = \"{}\")]\n    {},",
-                variant.to_snake_case(),
-                variant.to_student_case()
-            );
-        }
-        code.push_str("}\n\n");
+        let code = if schema.r#type.as_deref() == Some("integer") {
+            integer_enum_generate_code(&name, r#enum)
+        } else {
+            string_enum_generate_code(&name, r#enum)
+        };
         code_schemas.insert(name.clone(), code);
         return name;
     }
 
+    if let Some(r#const) = &schema.r#const {
+        let is_integer = schema.r#type.as_deref() == Some("integer");
+        code_schemas.insert(
+            name.clone(),
+            const_generate_code(&name, r#const, is_integer),
+        );
+        return name;
+    }
+
     if let Some(additional_properties) = &schema.additional_properties {
         let field_type = schema_generate_code(code_schemas, name.clone(), additional_properties);
         let code = format!(
@@ -139,3 +139,117 @@ fn schema_generate_code(
         _ => panic!("Unsupported type"),
     }
 }
+
+fn variant_name(value: &SchemaValue) -> String {
+    match value {
+        SchemaValue::Integer(value) if *value < 0 => format!("ValueMinus{}", value.unsigned_abs()),
+        SchemaValue::Integer(value) => format!("Value{value}"),
+        SchemaValue::String(value) => {
+            let name = value
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+                .to_student_case();
+            if name.starts_with(|c: char| c.is_ascii_digit()) {
+                format!("Value{name}")
+            } else {
+                name
+            }
+        }
+    }
+}
+
+fn string_enum_generate_code(name: &str, r#enum: &[SchemaValue]) -> String {
+    let mut code = format!(
+        "#[derive(Copy, Clone, Eq, PartialEq, serde::Deserialize, serde::Serialize)]\npub(crate) enum {name} {{\n"
+    );
+    for value in r#enum {
+        _ = writeln!(
+            code,
+            "    #[serde(rename = \"{}\")]\n    {},",
+            value.to_string().escape_default(),
+            variant_name(value)
+        );
+    }
+    code.push_str("}\n\n");
+    code
+}
+
+fn integer_enum_generate_code(name: &str, r#enum: &[SchemaValue]) -> String {
+    let mut code =
+        format!("#[derive(Copy, Clone, Eq, PartialEq)]\n#[repr(i64)]\npub(crate) enum {name} {{\n");
+    for value in r#enum {
+        let SchemaValue::Integer(integer) = value else {
+            panic!("Integer enum values should be integers");
+        };
+        _ = writeln!(code, "    {} = {integer},", variant_name(value));
+    }
+    _ = write!(
+        code,
+        "}}\n\nimpl serde::Serialize for {name} {{\n    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {{\n        serializer.serialize_i64(*self as i64)\n    }}\n}}\n\n"
+    );
+    _ = write!(
+        code,
+        "impl<'de> serde::Deserialize<'de> for {name} {{\n    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {{\n        match <i64 as serde::Deserialize>::deserialize(deserializer)? {{\n"
+    );
+    for value in r#enum {
+        if let SchemaValue::Integer(integer) = value {
+            _ = writeln!(
+                code,
+                "            {integer} => Ok(Self::{}),",
+                variant_name(value)
+            );
+        }
+    }
+    code.push_str("            value => Err(serde::de::Error::custom(format!(\"invalid value: {value}\"))),\n        }\n    }\n}\n\n");
+    code
+}
+
+fn const_generate_code(name: &str, r#const: &SchemaValue, is_integer: bool) -> String {
+    let (value_type, value, serialize, deserialize_type) = match (r#const, is_integer) {
+        (SchemaValue::Integer(value), true) => ("i64", value.to_string(), "serialize_i64", "i64"),
+        (SchemaValue::String(_), true) => panic!("Integer const value should be an integer"),
+        (value, false) => (
+            "&'static str",
+            format!("\"{}\"", value.to_string().escape_default()),
+            "serialize_str",
+            "String",
+        ),
+    };
+    format!(
+        "#[derive(Copy, Clone, Default, Eq, PartialEq)]\npub(crate) struct {name};\n\n\
+        impl {name} {{\n    pub(crate) const VALUE: {value_type} = {value};\n}}\n\n\
+        impl serde::Serialize for {name} {{\n    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {{\n        serializer.{serialize}(Self::VALUE)\n    }}\n}}\n\n\
+        impl<'de> serde::Deserialize<'de> for {name} {{\n    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {{\n        let value = <{deserialize_type} as serde::Deserialize>::deserialize(deserializer)?;\n        if value == Self::VALUE {{\n            Ok(Self)\n        }} else {{\n            Err(serde::de::Error::custom(format!(\"expected {{:?}}\", Self::VALUE)))\n        }}\n    }}\n}}\n\n"
+    )
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generate_enum_const_schemas() {
+        let schemas = serde_yaml::from_str::<IndexMap<String, Schema>>(
+            r#"
+Status:
+  type: string
+  enum: [active, in-progress]
+Priority:
+  type: integer
+  enum: [1, -1]
+Kind:
+  type: string
+  const: user
+"#,
+        )
+        .unwrap();
+        let code = generate_schemas(schemas);
+        assert!(code.contains("    #[serde(rename = \"in-progress\")]\n    InProgress,"));
+        assert!(code.contains(
+            "#[repr(i64)]\npub(crate) enum Priority {\n    Value1 = 1,\n    ValueMinus1 = -1,\n}"
+        ));
+        assert!(code.contains("            -1 => Ok(Self::ValueMinus1),"));
+        assert!(code.contains("pub(crate) struct Kind;"));
+        assert!(code.contains("    pub(crate) const VALUE: &'static str = \"user\";"));
+    }
+}
@@ -1,31 +1,29 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 use std::fmt::Write;
-use std::path::Path;
 
 use indexmap::IndexMap;
 
-use crate::openapi::Schema;
+use crate::openapi::{Schema, SchemaValue};
 use crate::utils::ToCase;
 
-pub(crate) fn generate_schemas(schemas: IndexMap<String, Schema>, output_path: &Path) {
+pub(crate) fn generate_schemas(schemas: IndexMap<String, Schema>) -> String {
     // Generate code for schemas
     let mut code_schemas = IndexMap::new();
     for (name, schema) in schemas {
         schema_generate_code(&mut code_schemas, name.clone(), &schema);
     }
 
-    // Write .rs file
-    let mut code = "// This file is generated by openapi-generator, do not edit!\n\n".to_string();
+    // Concat schemas code
+    let mut code = String::new();
     for code_schema in code_schemas.values() {
         code.push_str(code_schema);
     }
-    std::fs::write(output_path, code)
-        .unwrap_or_else(|_| panic!("Failed to write: {}", output_path.display()));
+    code
 }
 
 fn schema_generate_code(
@@ -46,13 +44,22 @@ fn schema_generate_code(
             if variant_index > 0 {
                 code.push_str(" | ");
             }
-            code.push_str(&format!("\"{}\"", variant.to_snake_case()));
+            code.push_str(&value_generate_code(schema, variant));
         }
         code.push_str(";\n\n");
         code_schemas.insert(name.clone(), code);
         return name;
     }
 
+    if let Some(r#const) = &schema.r#const {
+        let code = format!(
+            "export type {name} = {};\n\n",
+            value_generate_code(schema, r#const)
+        );
+        code_schemas.insert(name.clone(), code);
+        return name;
+    }
+
     if let Some(additional_properties) = &schema.additional_properties {
         let field_type = schema_generate_code(code_schemas, name.clone(), additional_properties);
         let code = format!("export type {name} = {{ [key: string]: {field_type} }};\n\n",);
@@ -101,3 +108,12 @@ fn schema_generate_code(
         _ => panic!("Unsupported type"),
     }
 }
+
+fn value_generate_code(schema: &Schema, value: &SchemaValue) -> String {
+    match value {
+        SchemaValue::Integer(value) if schema.r#type.as_deref() == Some("integer") => {
+            value.to_string()
+        }
+        value => format!("\"{}\"", value.to_string().escape_default()),
+    }
+}
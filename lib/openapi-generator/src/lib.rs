@@ -1,16 +1,16 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 //! OpenAPI Generator
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 mod generators;
-pub(crate) mod openapi;
+pub mod openapi;
 mod utils;
 
 /// Generator type
@@ -32,14 +32,38 @@ impl FromStr for Generator {
     }
 }
 
-/// Generate schemas for build.rs
+impl Generator {
+    const fn name(&self) -> &'static str {
+        match self {
+            Generator::Rust => "rust",
+            Generator::TypeScript => "typescript",
+        }
+    }
+}
+
+/// Generate schemas for build.rs, the output is only rewritten when the spec, its referenced
+/// files or the generator changed
 pub fn generate_schemas_build(
     spec_path: impl AsRef<Path>,
     output_path: impl AsRef<Path>,
     generator: Generator,
 ) {
-    generate_schemas(spec_path.as_ref(), output_path.as_ref(), generator);
-    println!("cargo::rerun-if-changed={}", spec_path.as_ref().display());
+    fn inner(spec_path: &Path, output_path: &Path, generator: Generator) {
+        let spec_files = openapi::spec_files(spec_path);
+        for spec_file in &spec_files {
+            println!("cargo::rerun-if-changed={}", spec_file.display());
+        }
+
+        // Skip generation when the output has the same spec hash
+        let hash = spec_hash(&spec_files, &generator);
+        if std::fs::read_to_string(output_path)
+            .is_ok_and(|output| output.lines().nth(1) == Some(hash_line(&hash).as_str()))
+        {
+            return;
+        }
+        generate(spec_path, output_path, generator, &hash);
+    }
+    inner(spec_path.as_ref(), output_path.as_ref(), generator);
 }
 
 /// Generate schemas
@@ -48,26 +72,48 @@ pub fn generate_schemas(
     output_path: impl AsRef<Path>,
     generator: Generator,
 ) {
-    fn inner(spec_path: &Path, generator: Generator, output_path: &Path) {
-        // Read spec file
-        let text = std::fs::read_to_string(spec_path).expect("Failed to read spec file");
-        let spec =
-            serde_yaml::from_str::<openapi::OpenApi>(&text).expect("Failed to deserialize yaml");
+    fn inner(spec_path: &Path, output_path: &Path, generator: Generator) {
+        let hash = spec_hash(&openapi::spec_files(spec_path), &generator);
+        generate(spec_path, output_path, generator, &hash);
+    }
+    inner(spec_path.as_ref(), output_path.as_ref(), generator);
+}
 
-        // Generate dirs if needed
-        if let Some(parent) = output_path.parent() {
-            std::fs::create_dir_all(parent).expect("Failed to create output dir");
-        }
+fn spec_hash(spec_files: &[PathBuf], generator: &Generator) -> String {
+    let mut data = format!("{} {}\n", env!("CARGO_PKG_VERSION"), generator.name()).into_bytes();
+    for spec_file in spec_files {
+        data.extend(
+            std::fs::read(spec_file)
+                .unwrap_or_else(|_| panic!("Failed to read spec file: {}", spec_file.display())),
+        );
+    }
+    format!("{:016x}", utils::fnv1a_hash(&data))
+}
 
-        // Run generator
-        match generator {
-            Generator::Rust => {
-                generators::rust::generate_schemas(spec.components.schemas, output_path)
-            }
-            Generator::TypeScript => {
-                generators::typescript::generate_schemas(spec.components.schemas, output_path)
-            }
-        }
+fn hash_line(hash: &str) -> String {
+    format!("// Spec hash: {hash}")
+}
+
+fn generate(spec_path: &Path, output_path: &Path, generator: Generator, hash: &str) {
+    // Read spec file
+    let spec = openapi::read_spec(spec_path);
+
+    // Generate dirs if needed
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).expect("Failed to create output dir");
     }
-    inner(spec_path.as_ref(), generator, output_path.as_ref());
+
+    // Run generator
+    let code = match generator {
+        Generator::Rust => generators::rust::generate_schemas(spec.components.schemas),
+        Generator::TypeScript => generators::typescript::generate_schemas(spec.components.schemas),
+    };
+
+    // Write output file atomically
+    let code = format!(
+        "// This file is generated by openapi-generator, do not edit!\n{}\n\n{code}",
+        hash_line(hash)
+    );
+    utils::write_atomic(output_path, code)
+        .unwrap_or_else(|_| panic!("Failed to write: {}", output_path.display()));
 }
@@ -1,32 +1,730 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
+//! OpenAPI specification model
+
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
 use indexmap::IndexMap;
-use serde::Deserialize;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer};
+
+/// Read and parse an OpenAPI specification file, JSON specs are parsed by the YAML parser
+/// because YAML is a superset of JSON
+pub fn read_spec(spec_path: impl AsRef<Path>) -> OpenApi {
+    let spec_path = spec_path.as_ref();
+    let text = std::fs::read_to_string(spec_path)
+        .unwrap_or_else(|_| panic!("Failed to read spec file: {}", spec_path.display()));
+    serde_yaml::from_str::<OpenApi>(&text).unwrap_or_else(|error| {
+        panic!("Failed to parse spec file {}: {error}", spec_path.display())
+    })
+}
+
+/// Get the spec file and the files it references with external `$ref`s
+pub(crate) fn spec_files(spec_path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![spec_path.to_path_buf()];
+    let mut index = 0;
+    while index < files.len() {
+        let text = std::fs::read_to_string(&files[index])
+            .unwrap_or_else(|_| panic!("Failed to read spec file: {}", files[index].display()));
+        let dir = files[index].parent().unwrap_or(Path::new("")).to_path_buf();
+        for r#ref in external_refs(&text) {
+            let path = dir.join(r#ref);
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        index += 1;
+    }
+    files
+}
+
+fn external_refs(text: &str) -> Vec<String> {
+    match serde_yaml::from_str::<RefNode>(text) {
+        Ok(RefNode::Other(refs)) => refs
+            .into_iter()
+            .filter_map(|r#ref| {
+                let path = r#ref
+                    .split_once('#')
+                    .map_or(r#ref.as_str(), |(path, _)| path);
+                (!path.is_empty() && !has_url_scheme(path)).then(|| path.to_string())
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// A value of a spec document that only keeps the `$ref` values of its mappings, so mentions of
+// `$ref` in descriptions or examples aren't matched
+enum RefNode {
+    String(String),
+    Other(Vec<String>),
+}
+
+impl<'de> Deserialize<'de> for RefNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RefNodeVisitor;
+
+        impl<'de> Visitor<'de> for RefNodeVisitor {
+            type Value = RefNode;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("any value")
+            }
+
+            fn visit_bool<E: de::Error>(self, _value: bool) -> Result<Self::Value, E> {
+                Ok(RefNode::Other(Vec::new()))
+            }
+
+            fn visit_i64<E: de::Error>(self, _value: i64) -> Result<Self::Value, E> {
+                Ok(RefNode::Other(Vec::new()))
+            }
+
+            fn visit_u64<E: de::Error>(self, _value: u64) -> Result<Self::Value, E> {
+                Ok(RefNode::Other(Vec::new()))
+            }
+
+            fn visit_f64<E: de::Error>(self, _value: f64) -> Result<Self::Value, E> {
+                Ok(RefNode::Other(Vec::new()))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(RefNode::String(value.to_string()))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(RefNode::Other(Vec::new()))
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(RefNode::Other(Vec::new()))
+            }
+
+            fn visit_some<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                RefNode::deserialize(deserializer)
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut refs = Vec::new();
+                while let Some(node) = seq.next_element::<RefNode>()? {
+                    if let RefNode::Other(node_refs) = node {
+                        refs.extend(node_refs);
+                    }
+                }
+                Ok(RefNode::Other(refs))
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut refs = Vec::new();
+                while let Some(key) = map.next_key::<RefNode>()? {
+                    match (key, map.next_value::<RefNode>()?) {
+                        (RefNode::String(key), RefNode::String(value)) if key == "$ref" => {
+                            refs.push(value)
+                        }
+                        (_, RefNode::Other(value_refs)) => refs.extend(value_refs),
+                        _ => {}
+                    }
+                }
+                Ok(RefNode::Other(refs))
+            }
+        }
 
+        deserializer.deserialize_any(RefNodeVisitor)
+    }
+}
+
+// Remote refs like `https://example.com/schema.yaml` aren't local files
+fn has_url_scheme(path: &str) -> bool {
+    path.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+// MARK: OpenApi
+/// OpenAPI document
 #[derive(Deserialize)]
-pub(crate) struct OpenApi {
+pub struct OpenApi {
+    /// Paths with their operations
+    #[serde(default)]
+    pub paths: IndexMap<String, PathItem>,
+    /// Reusable components
     pub components: Components,
+    /// Security requirements for all operations
+    #[serde(default)]
+    pub security: Vec<SecurityRequirement>,
 }
 
+/// Reusable components
 #[derive(Deserialize)]
-pub(crate) struct Components {
+pub struct Components {
+    /// Schemas
+    #[serde(default)]
     pub schemas: IndexMap<String, Schema>,
+    /// Parameters
+    #[serde(default)]
+    pub parameters: IndexMap<String, Parameter>,
+    /// Request bodies
+    #[serde(default, rename = "requestBodies")]
+    pub request_bodies: IndexMap<String, RequestBody>,
+    /// Responses
+    #[serde(default)]
+    pub responses: IndexMap<String, Response>,
+    /// Security schemes
+    #[serde(default, rename = "securitySchemes")]
+    pub security_schemes: IndexMap<String, SecurityScheme>,
+}
+
+// MARK: Paths
+/// Path item with the operations per method
+#[derive(Deserialize)]
+pub struct PathItem {
+    /// Parameters for all operations of this path
+    #[serde(default)]
+    pub parameters: Vec<Parameter>,
+    /// GET operation
+    pub get: Option<Operation>,
+    /// PUT operation
+    pub put: Option<Operation>,
+    /// POST operation
+    pub post: Option<Operation>,
+    /// DELETE operation
+    pub delete: Option<Operation>,
+    /// OPTIONS operation
+    pub options: Option<Operation>,
+    /// HEAD operation
+    pub head: Option<Operation>,
+    /// PATCH operation
+    pub patch: Option<Operation>,
+    /// TRACE operation
+    pub trace: Option<Operation>,
+}
+
+impl PathItem {
+    /// Iterate over the defined operations with their lowercase method name
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("get", &self.get),
+            ("put", &self.put),
+            ("post", &self.post),
+            ("delete", &self.delete),
+            ("options", &self.options),
+            ("head", &self.head),
+            ("patch", &self.patch),
+            ("trace", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|operation| (method, operation)))
+    }
+}
+
+/// Operation on a path
+#[derive(Deserialize)]
+pub struct Operation {
+    /// Operation id
+    #[serde(rename = "operationId")]
+    pub operation_id: Option<String>,
+    /// Summary
+    pub summary: Option<String>,
+    /// Description
+    pub description: Option<String>,
+    /// Tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Parameters
+    #[serde(default)]
+    pub parameters: Vec<Parameter>,
+    /// Request body
+    #[serde(rename = "requestBody")]
+    pub request_body: Option<RequestBody>,
+    /// Responses per status code
+    #[serde(default)]
+    pub responses: IndexMap<String, Response>,
+    /// Security requirements, overrides the document security requirements
+    pub security: Option<Vec<SecurityRequirement>>,
+}
+
+/// Security requirement, security scheme names with their required scopes
+pub type SecurityRequirement = IndexMap<String, Vec<String>>;
+
+// MARK: Parameter
+/// Parameter location
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ParameterLocation {
+    /// Path parameter
+    Path,
+    /// Query parameter
+    Query,
+    /// Header parameter
+    Header,
+    /// Cookie parameter
+    Cookie,
+}
+
+/// Parameter or parameter reference
+#[derive(Deserialize)]
+pub struct Parameter {
+    /// Reference to a parameter component
+    #[serde(rename = "$ref")]
+    pub r#ref: Option<String>,
+    /// Name
+    pub name: Option<String>,
+    /// Location
+    pub r#in: Option<ParameterLocation>,
+    /// Description
+    pub description: Option<String>,
+    /// Is required
+    #[serde(default)]
+    pub required: bool,
+    /// Schema
+    pub schema: Option<Schema>,
+}
+
+// MARK: Bodies
+/// Request body or request body reference
+#[derive(Deserialize)]
+pub struct RequestBody {
+    /// Reference to a request body component
+    #[serde(rename = "$ref")]
+    pub r#ref: Option<String>,
+    /// Description
+    pub description: Option<String>,
+    /// Is required
+    #[serde(default)]
+    pub required: bool,
+    /// Content per media type
+    #[serde(default)]
+    pub content: IndexMap<String, MediaType>,
 }
 
+/// Response or response reference
 #[derive(Deserialize)]
-pub(crate) struct Schema {
+pub struct Response {
+    /// Reference to a response component
     #[serde(rename = "$ref")]
     pub r#ref: Option<String>,
+    /// Description
+    pub description: Option<String>,
+    /// Headers
+    #[serde(default)]
+    pub headers: IndexMap<String, Header>,
+    /// Content per media type
+    #[serde(default)]
+    pub content: IndexMap<String, MediaType>,
+}
+
+/// Response header
+#[derive(Deserialize)]
+pub struct Header {
+    /// Description
+    pub description: Option<String>,
+    /// Is required
+    #[serde(default)]
+    pub required: bool,
+    /// Schema
+    pub schema: Option<Schema>,
+}
+
+/// Media type content
+#[derive(Deserialize)]
+pub struct MediaType {
+    /// Schema
+    pub schema: Option<Schema>,
+}
+
+// MARK: Security
+/// Security scheme type
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SecuritySchemeType {
+    /// API key
+    #[serde(rename = "apiKey")]
+    ApiKey,
+    /// HTTP authentication
+    #[serde(rename = "http")]
+    Http,
+    /// OAuth 2.0
+    #[serde(rename = "oauth2")]
+    OAuth2,
+    /// OpenID Connect
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect,
+    /// Mutual TLS
+    #[serde(rename = "mutualTLS")]
+    MutualTls,
+}
+
+/// Security scheme
+#[derive(Deserialize)]
+pub struct SecurityScheme {
+    /// Type
+    pub r#type: SecuritySchemeType,
+    /// Description
+    pub description: Option<String>,
+    /// API key name
+    pub name: Option<String>,
+    /// API key location
+    pub r#in: Option<ParameterLocation>,
+    /// HTTP authentication scheme
+    pub scheme: Option<String>,
+    /// HTTP bearer token format
+    #[serde(rename = "bearerFormat")]
+    pub bearer_format: Option<String>,
+    /// OpenID Connect discovery url
+    #[serde(rename = "openIdConnectUrl")]
+    pub open_id_connect_url: Option<String>,
+}
+
+// MARK: Schema
+/// Schema or schema reference
+#[derive(Deserialize)]
+pub struct Schema {
+    /// Reference to a schema component
+    #[serde(rename = "$ref")]
+    pub r#ref: Option<String>,
+    /// Type
     pub r#type: Option<String>,
+    /// Format
     pub format: Option<String>,
+    /// Object properties
     pub properties: Option<IndexMap<String, Schema>>,
+    /// Object additional properties
     #[serde(rename = "additionalProperties")]
     pub additional_properties: Option<Box<Schema>>,
+    /// Required object properties
     pub required: Option<Vec<String>>,
+    /// Array items
     pub items: Option<Box<Schema>>,
-    pub r#enum: Option<Vec<String>>,
+    /// Enum values
+    pub r#enum: Option<Vec<SchemaValue>>,
+    /// Const value
+    pub r#const: Option<SchemaValue>,
+}
+
+/// Enum or const value of a schema
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SchemaValue {
+    /// Integer value
+    Integer(i64),
+    /// String value
+    String(String),
+}
+
+impl Display for SchemaValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaValue::Integer(value) => write!(f, "{value}"),
+            SchemaValue::String(value) => f.write_str(value),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SchemaValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SchemaValueVisitor;
+
+        impl Visitor<'_> for SchemaValueVisitor {
+            type Value = SchemaValue;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("an integer or string value")
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(SchemaValue::Integer(value))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                i64::try_from(value)
+                    .map(SchemaValue::Integer)
+                    .map_err(|_| E::custom("integer out of range"))
+            }
+
+            fn visit_i128<E: de::Error>(self, value: i128) -> Result<Self::Value, E> {
+                i64::try_from(value)
+                    .map(SchemaValue::Integer)
+                    .map_err(|_| E::custom("integer out of range"))
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(SchemaValue::String(value.to_string()))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(SchemaValue::String(value.to_string()))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(SchemaValue::String(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(SchemaValueVisitor)
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_external_refs() {
+        let refs = external_refs(
+            r#"
+schema:
+  $ref: '#/components/schemas/User'
+other:
+  $ref: "schemas/user.yaml#/User"
+items: { "$ref": "common.yaml" }
+remote:
+  $ref: 'https://example.com/schemas.yaml#/Remote'
+description: 'Use $ref: other.yaml to reference'
+properties:
+  $ref:
+    type: string
+"#,
+        );
+        assert_eq!(refs, ["schemas/user.yaml", "common.yaml"]);
+
+        let refs = external_refs(
+            r##"{"a":{"$ref":"user.json#/User"},"b":{"$ref":"#/components/schemas/B"},"c":{"$ref": "common.json"}}"##,
+        );
+        assert_eq!(refs, ["user.json", "common.json"]);
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = serde_yaml::from_str::<OpenApi>(
+            r#"
+openapi: 3.1.0
+paths:
+  /users/{userId}:
+    parameters:
+      - $ref: '#/components/parameters/UserId'
+    get:
+      operationId: getUser
+      tags: [Users]
+      security:
+        - BearerAuth: []
+      parameters:
+        - name: X-Trace
+          in: header
+          schema:
+            type: string
+      responses:
+        '200':
+          description: User
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+        '404':
+          $ref: '#/components/responses/NotFound'
+    put:
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/User'
+      responses:
+        '200':
+          description: Updated
+components:
+  securitySchemes:
+    BearerAuth:
+      type: http
+      scheme: bearer
+      bearerFormat: token
+  parameters:
+    UserId:
+      name: userId
+      in: path
+      required: true
+      schema:
+        type: string
+        format: uuid
+  responses:
+    NotFound:
+      description: Not found
+  schemas:
+    User:
+      type: object
+      properties:
+        id:
+          type: string
+"#,
+        )
+        .unwrap();
+
+        let path = &spec.paths["/users/{userId}"];
+        assert_eq!(
+            path.parameters[0].r#ref.as_deref(),
+            Some("#/components/parameters/UserId")
+        );
+        let methods = path.operations().map(|(m, _)| m).collect::<Vec<_>>();
+        assert_eq!(methods, ["get", "put"]);
+
+        let get = path.get.as_ref().unwrap();
+        assert_eq!(get.operation_id.as_deref(), Some("getUser"));
+        assert_eq!(get.tags, ["Users"]);
+        assert!(get.security.as_ref().unwrap()[0]["BearerAuth"].is_empty());
+        assert_eq!(get.parameters[0].r#in, Some(ParameterLocation::Header));
+        assert!(!get.parameters[0].required);
+        assert_eq!(
+            get.responses["200"].content["application/json"]
+                .schema
+                .as_ref()
+                .unwrap()
+                .r#ref
+                .as_deref(),
+            Some("#/components/schemas/User")
+        );
+        assert_eq!(
+            get.responses["404"].r#ref.as_deref(),
+            Some("#/components/responses/NotFound")
+        );
+
+        let put = path.put.as_ref().unwrap();
+        assert!(put.request_body.as_ref().unwrap().required);
+
+        let user_id = &spec.components.parameters["UserId"];
+        assert_eq!(user_id.r#in, Some(ParameterLocation::Path));
+        assert!(user_id.required);
+        let bearer = &spec.components.security_schemes["BearerAuth"];
+        assert_eq!(bearer.r#type, SecuritySchemeType::Http);
+        assert_eq!(bearer.scheme.as_deref(), Some("bearer"));
+        assert_eq!(bearer.bearer_format.as_deref(), Some("token"));
+        assert_eq!(
+            spec.components.responses["NotFound"].description.as_deref(),
+            Some("Not found")
+        );
+        assert!(spec.components.schemas.contains_key("User"));
+    }
+
+    #[test]
+    fn test_parse_json_spec() {
+        let spec = serde_yaml::from_str::<OpenApi>(
+            r##"{
+	"openapi": "3.1.0",
+	"paths": {
+		"/users": {
+			"get": {"operationId": "getUsers", "responses": {"200": {"$ref": "#/components/responses/Users"}}}
+		}
+	},
+	"components": {
+		"schemas": {"User": {"type": "object", "properties": {"id": {"type": "string"}}}}
+	}
+}"##,
+        )
+        .unwrap();
+        let get = spec.paths["/users"].get.as_ref().unwrap();
+        assert_eq!(get.operation_id.as_deref(), Some("getUsers"));
+        assert_eq!(
+            get.responses["200"].r#ref.as_deref(),
+            Some("#/components/responses/Users")
+        );
+        assert!(spec.components.schemas["User"].properties.is_some());
+    }
+
+    #[test]
+    fn test_parse_anchors() {
+        let schemas = serde_yaml::from_str::<IndexMap<String, Schema>>(
+            r#"
+Timestamps: &timestamps
+  createdAt: &datetime
+    type: string
+    format: date-time
+  updatedAt: *datetime
+User:
+  type: object
+  properties:
+    id:
+      type: string
+    <<: *timestamps
+    updatedAt:
+      type: integer
+"#,
+        )
+        .unwrap();
+        let properties = schemas["User"].properties.as_ref().unwrap();
+        assert_eq!(
+            properties.keys().collect::<Vec<_>>(),
+            ["id", "createdAt", "updatedAt"]
+        );
+        assert_eq!(properties["createdAt"].format.as_deref(), Some("date-time"));
+        assert_eq!(properties["updatedAt"].r#type.as_deref(), Some("integer"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = serde_yaml::from_str::<OpenApi>(
+            r#"
+paths:
+  /users:
+    get:
+      parameters:
+        - name: page
+          in: body
+components: {}
+"#,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error.path(), "/paths/~1users/get/parameters/0/in");
+        assert_eq!(
+            error
+                .location()
+                .map(|location| (location.line(), location.column())),
+            Some((7, 15))
+        );
+    }
+
+    #[test]
+    fn test_parse_enum_const_schema() {
+        let schemas = serde_yaml::from_str::<IndexMap<String, Schema>>(
+            r#"
+Status:
+  type: string
+  enum: [active, in-progress]
+Priority:
+  type: integer
+  enum: [1, 2, -1]
+Kind:
+  type: string
+  const: user
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            schemas["Status"].r#enum,
+            Some(vec![
+                SchemaValue::String("active".to_string()),
+                SchemaValue::String("in-progress".to_string())
+            ])
+        );
+        assert_eq!(
+            schemas["Priority"].r#enum,
+            Some(vec![
+                SchemaValue::Integer(1),
+                SchemaValue::Integer(2),
+                SchemaValue::Integer(-1)
+            ])
+        );
+        assert_eq!(
+            schemas["Kind"].r#const,
+            Some(SchemaValue::String("user".to_string()))
+        );
+    }
 }
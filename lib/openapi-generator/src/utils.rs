@@ -1,9 +1,12 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
+use std::io;
+use std::path::Path;
+
 // MARK: ToCase
 pub(crate) trait ToCase {
     fn to_student_case(&self) -> String;
@@ -41,6 +44,30 @@ impl ToCase for str {
     }
 }
 
+// MARK: Hash
+/// FNV-1a 64-bit hash
+pub(crate) fn fnv1a_hash(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in data {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+// MARK: Write atomic
+/// Write file by writing a temporary file next to it and renaming it over the path
+pub(crate) fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path).inspect_err(|_| {
+        _ = std::fs::remove_file(&tmp_path);
+    })
+}
+
+// MARK: Tests
 #[cfg(test)]
 mod test {
     use super::*;
@@ -53,6 +80,22 @@ mod test {
         assert_eq!("".to_student_case(), "");
     }
 
+    #[test]
+    fn test_fnv1a_hash() {
+        assert_eq!(fnv1a_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let path =
+            std::env::temp_dir().join(format!("openapi-generator-{}.txt", std::process::id()));
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn test_to_snake_case() {
         assert_eq!("helloWorld".to_snake_case(), "hello_world");
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -14,13 +14,21 @@ use quote::quote;
 use syn::{DeriveInput, Expr, Lit, Meta, parse_macro_input};
 
 /// [Embed] derive
-#[proc_macro_derive(Embed, attributes(folder))]
+///
+/// - `#[folder = "..."]` folder to embed
+/// - `#[exclude_release = "*.map"]` skip files matching the glob in release builds, can be
+///   repeated
+/// - `#[preprocess = "path::to::function"]` transform files with a `fn(&str, &[u8]) -> Vec<u8>`
+///   function that gets the file path and data, runs once per file on first access
+#[proc_macro_derive(Embed, attributes(folder, exclude_release, preprocess))]
 pub fn validate_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
 
-    // Extract the #[folder = "..."] attribute
+    // Extract the #[folder = "..."], #[exclude_release = "..."] and #[preprocess = "..."] attributes
     let mut folder_path = None;
+    let mut exclude_release = Vec::new();
+    let mut preprocess = None;
     for attr in input.attrs {
         if attr.path().is_ident("folder") {
             if let Meta::NameValue(meta) = &attr.meta
@@ -28,10 +36,31 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                 && let Lit::Str(lit_str) = &expr.lit
             {
                 folder_path = Some(lit_str.value());
-                break;
+                continue;
             }
             panic!("Invalid #[folder = \"...\"] attribute");
         }
+        if attr.path().is_ident("exclude_release") {
+            if let Meta::NameValue(meta) = &attr.meta
+                && let Expr::Lit(expr) = &meta.value
+                && let Lit::Str(lit_str) = &expr.lit
+            {
+                exclude_release.push(lit_str.value());
+                continue;
+            }
+            panic!("Invalid #[exclude_release = \"...\"] attribute");
+        }
+        if attr.path().is_ident("preprocess") {
+            if let Meta::NameValue(meta) = &attr.meta
+                && let Expr::Lit(expr) = &meta.value
+                && let Lit::Str(lit_str) = &expr.lit
+                && let Ok(path) = lit_str.parse::<syn::Path>()
+            {
+                preprocess = Some(path);
+                continue;
+            }
+            panic!("Invalid #[preprocess = \"...\"] attribute");
+        }
     }
     let mut folder_path = match folder_path {
         Some(path) => path,
@@ -89,37 +118,64 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
         (file_path.to_string(), const_ident)
     };
 
+    // Files excluded from release builds are only compiled in with debug assertions
+    let files: Vec<_> = files
+        .iter()
+        .map(|path| {
+            let (file_path, const_ident) = to_const_name(path);
+            let cfg = if exclude_release
+                .iter()
+                .any(|pattern| glob_match(pattern.as_bytes(), file_path.as_bytes()))
+            {
+                quote! { #[cfg(debug_assertions)] }
+            } else {
+                quote! {}
+            };
+            (path, file_path, const_ident, cfg)
+        })
+        .collect();
+
     // Create consts for all files
     let embed_files: Vec<_> = files
         .iter()
-        .map(|path| {
-            let (_, const_ident) = to_const_name(path);
+        .map(|(path, _, const_ident, cfg)| {
             quote! {
+                #cfg
                 const #const_ident: &[u8] = include_bytes!(#path);
             }
         })
         .collect();
 
-    // Create a mapping of file paths to consts
+    // Create a mapping of file paths to consts, preprocessed data is cached in a static
     let embed_mapping: Vec<_> = files
         .iter()
-        .map(|path| {
-            let (file_path, const_ident) = to_const_name(path);
+        .map(|(_, file_path, const_ident, cfg)| {
+            let data = match &preprocess {
+                Some(preprocess) => quote! {{
+                    static DATA: std::sync::OnceLock<Vec<u8>> = std::sync::OnceLock::new();
+                    DATA.get_or_init(|| #preprocess(#file_path, #const_ident)).as_slice()
+                }},
+                None => quote! { #const_ident },
+            };
             quote! {
+                #cfg
                 #file_path => Some(rust_embed::EmbeddedFile {
-                    data: std::borrow::Cow::Borrowed(#const_ident),
+                    data: std::borrow::Cow::Borrowed(#data),
                 }),
             }
         })
         .collect();
+    let overlay_data = match &preprocess {
+        Some(preprocess) => quote! { std::borrow::Cow::Owned(#preprocess(file_path, &file.data)) },
+        None => quote! { file.data },
+    };
 
     // Collect relative file paths for iter()
-    let file_paths: Vec<_> = files
+    let file_paths: Vec<_> = files.iter().map(|(_, file_path, _, _)| file_path).collect();
+    let release_file_paths: Vec<_> = files
         .iter()
-        .map(|path| {
-            let (file_path, _) = to_const_name(path);
-            file_path
-        })
+        .filter(|(_, _, _, cfg)| cfg.is_empty())
+        .map(|(_, file_path, _, _)| file_path)
         .collect();
 
     TokenStream::from(quote! {
@@ -133,10 +189,19 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
             fn iter() -> impl Iterator<Item = std::borrow::Cow<'static, str>> {
                 <Self as rust_embed::RustEmbed>::iter()
             }
+
+            fn set_overlay_dir(dir: impl Into<std::path::PathBuf>) {
+                <Self as rust_embed::RustEmbed>::set_overlay_dir(dir)
+            }
         }
 
         impl rust_embed::RustEmbed for #name {
             fn get(file_path: &str) -> Option<rust_embed::EmbeddedFile> {
+                if let Some(file) = Self::overlay().get(file_path) {
+                    return Some(rust_embed::EmbeddedFile {
+                        data: #overlay_data,
+                    });
+                }
                 match file_path {
                     #(#embed_mapping)*
                     _ => None,
@@ -144,8 +209,44 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
             }
 
             fn iter() -> impl Iterator<Item = std::borrow::Cow<'static, str>> {
-                [#(std::borrow::Cow::Borrowed(#file_paths),)*].into_iter()
+                let file_paths: &[&str] = if cfg!(debug_assertions) {
+                    &[#(#file_paths,)*]
+                } else {
+                    &[#(#release_file_paths,)*]
+                };
+                file_paths.iter().map(|file_path| std::borrow::Cow::Borrowed(*file_path))
+            }
+
+            fn overlay() -> &'static rust_embed::Overlay {
+                static OVERLAY: rust_embed::Overlay = rust_embed::Overlay::new();
+                &OVERLAY
             }
         }
     })
 }
+
+// Match a path against a glob pattern, `*` matches any characters and `?` matches one character
+fn glob_match(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((b'*', rest)) => (0..=path.len()).any(|index| glob_match(rest, &path[index..])),
+        Some((b'?', rest)) => !path.is_empty() && glob_match(rest, &path[1..]),
+        Some((c, rest)) => path.first() == Some(c) && glob_match(rest, &path[1..]),
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(b"*.map", b"app.js.map"));
+        assert!(glob_match(b"*.map", b"js/app.js.map"));
+        assert!(glob_match(b"js/*.js", b"js/app.js"));
+        assert!(glob_match(b"index.htm?", b"index.html"));
+        assert!(!glob_match(b"*.map", b"app.js"));
+        assert!(!glob_match(b"css/*", b"js/app.js"));
+    }
+}
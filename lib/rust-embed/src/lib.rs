@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -7,9 +7,16 @@
 //! A minimal replacement for the [rust-embed](https://crates.io/crates/rust-embed) crate
 
 use std::borrow::Cow;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
 
 pub use rust_embed_impl::Embed;
 
+// The derive macro generates paths to this crate, so tests can use it here
+#[cfg(test)]
+extern crate self as rust_embed;
+
 /// A trait that allows you to access embedded files
 pub trait RustEmbed {
     /// Get an embedded file by its path.
@@ -17,6 +24,61 @@ pub trait RustEmbed {
 
     /// Iterate over the relative paths of all embedded files.
     fn iter() -> impl Iterator<Item = Cow<'static, str>>;
+
+    /// Get the runtime overlay that is checked before the embedded files.
+    fn overlay() -> &'static Overlay;
+
+    /// Set the directory with files that override the embedded files.
+    fn set_overlay_dir(dir: impl Into<PathBuf>) {
+        Self::overlay().set_dir(dir);
+    }
+}
+
+/// A runtime directory with files that override embedded files
+///
+/// Files that exist in the overlay directory are returned by `get()` instead of the embedded
+/// file with the same path, so end users can replace single assets without rebuilding.
+#[derive(Default)]
+pub struct Overlay(RwLock<Option<PathBuf>>);
+
+impl Overlay {
+    /// Create a new overlay without a directory
+    pub const fn new() -> Self {
+        Self(RwLock::new(None))
+    }
+
+    /// Get the overlay directory
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.0.read().expect("Can't lock overlay").clone()
+    }
+
+    /// Set the overlay directory
+    pub fn set_dir(&self, dir: impl Into<PathBuf>) {
+        *self.0.write().expect("Can't lock overlay") = Some(dir.into());
+    }
+
+    /// Remove the overlay directory
+    pub fn clear(&self) {
+        *self.0.write().expect("Can't lock overlay") = None;
+    }
+
+    /// Read a file from the overlay directory
+    pub fn get(&self, file_path: &str) -> Option<EmbeddedFile> {
+        // Only allow plain relative paths so files outside the overlay directory can't be read
+        if !Path::new(file_path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return None;
+        }
+        let path = self.dir()?.join(file_path);
+        if !path.is_file() {
+            return None;
+        }
+        Some(EmbeddedFile {
+            data: Cow::Owned(fs::read(path).ok()?),
+        })
+    }
 }
 
 /// A file embedded in the binary
@@ -24,3 +86,57 @@ pub struct EmbeddedFile {
     /// The content of the file
     pub data: Cow<'static, [u8]>,
 }
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overlay() {
+        let dir = std::env::temp_dir().join(format!("rust-embed-overlay-{}", std::process::id()));
+        fs::create_dir_all(dir.join("css")).unwrap();
+        fs::write(dir.join("css/style.css"), "body {}").unwrap();
+
+        let overlay = Overlay::new();
+        assert!(overlay.get("css/style.css").is_none());
+
+        overlay.set_dir(&dir);
+        assert_eq!(
+            overlay.get("css/style.css").unwrap().data.as_ref(),
+            b"body {}"
+        );
+        assert!(overlay.get("css/missing.css").is_none());
+        assert!(overlay.get("css").is_none());
+        assert!(overlay.get("../style.css").is_none());
+        assert!(overlay.get("/etc/hosts").is_none());
+
+        overlay.clear();
+        assert!(overlay.get("css/style.css").is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[derive(Embed)]
+    #[folder = "src"]
+    #[exclude_release = "*.rs"]
+    #[preprocess = "first_line"]
+    struct Sources;
+
+    fn first_line(_file_path: &str, data: &[u8]) -> Vec<u8> {
+        data.split(|byte| *byte == b'\n')
+            .next()
+            .unwrap_or_default()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_exclude_release_and_preprocess() {
+        if cfg!(debug_assertions) {
+            assert_eq!(Sources::get("lib.rs").unwrap().data.as_ref(), b"/*");
+            assert_eq!(Sources::iter().collect::<Vec<_>>(), ["lib.rs"]);
+        } else {
+            assert!(Sources::get("lib.rs").is_none());
+            assert_eq!(Sources::iter().count(), 0);
+        }
+    }
+}
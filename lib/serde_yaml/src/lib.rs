@@ -10,29 +10,103 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
 
-use granit_parser::{Event, Parser, ScalarStyle, Tag};
+use granit_parser::{Event, Marker, Parser, ScalarStyle, Tag};
 use serde::de::{
     self, Deserialize, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
     VariantAccess, Visitor,
 };
 
+/// A location in the YAML input.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Location {
+    index: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Location {
+    fn from_marker(marker: &Marker) -> Self {
+        Self {
+            index: marker.index(),
+            line: marker.line(),
+            column: marker.col() + 1,
+        }
+    }
+
+    /// The character index in the input, starting at 0.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The line in the input, starting at 1.
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column in the input, starting at 1.
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+}
+
 /// An error produced while parsing or deserializing YAML.
 #[derive(Debug)]
 pub struct Error {
     message: String,
+    location: Option<Location>,
+    path: Vec<String>,
 }
 
 impl Error {
     fn custom(message: impl Into<String>) -> Self {
         Self {
             message: message.into(),
+            location: None,
+            path: Vec::new(),
         }
     }
+
+    /// Set the location when the error doesn't have a more precise one yet.
+    fn at(mut self, location: Location) -> Self {
+        self.location.get_or_insert(location);
+        self
+    }
+
+    /// Prepend a mapping key or sequence index to the path.
+    fn in_path(mut self, segment: String) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+
+    /// The location in the input where the error happened, if known.
+    pub const fn location(&self) -> Option<Location> {
+        self.location
+    }
+
+    /// The JSON pointer to the value that failed to deserialize, like `/paths/~1users/get`,
+    /// empty when the error is about the whole document.
+    pub fn path(&self) -> String {
+        self.path
+            .iter()
+            .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+            .collect()
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        formatter.write_str(&self.message)
+        if !self.path.is_empty() {
+            write!(formatter, "{}: ", self.path())?;
+        }
+        formatter.write_str(&self.message)?;
+        if let Some(location) = self.location {
+            write!(
+                formatter,
+                " at line {} column {}",
+                location.line, location.column
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -53,7 +127,13 @@ where
 }
 
 #[derive(Clone)]
-enum Node<'de> {
+struct Node<'de> {
+    value: Value<'de>,
+    location: Location,
+}
+
+#[derive(Clone)]
+enum Value<'de> {
     Scalar(Scalar<'de>),
     Sequence(Vec<Node<'de>>),
     Mapping(Vec<(Node<'de>, Node<'de>)>),
@@ -80,11 +160,13 @@ enum Container<'de> {
     Sequence {
         values: Vec<Node<'de>>,
         anchor: usize,
+        location: Location,
     },
     Mapping {
         values: Vec<(Node<'de>, Node<'de>)>,
         key: Option<Node<'de>>,
         anchor: usize,
+        location: Location,
     },
 }
 
@@ -105,7 +187,10 @@ fn parse(input: &str) -> Result<Node<'_>, Error> {
     let mut anchors = HashMap::new();
 
     for next in Parser::new_from_str(input) {
-        let (event, _) = next.map_err(|error| Error::custom(error.to_string()))?;
+        let (event, span) = next.map_err(|error| {
+            Error::custom(error.info()).at(Location::from_marker(error.marker()))
+        })?;
+        let location = Location::from_marker(&span.start);
         match event {
             Event::Nothing
             | Event::StreamStart
@@ -114,36 +199,44 @@ fn parse(input: &str) -> Result<Node<'_>, Error> {
             | Event::DocumentEnd
             | Event::Comment(_, _) => {}
             Event::Scalar(value, style, anchor, tag) => {
-                let node = Node::Scalar(Scalar {
-                    value,
-                    plain: style == ScalarStyle::Plain,
-                    tag: scalar_tag(tag.as_ref()),
-                });
+                let node = Node {
+                    value: Value::Scalar(Scalar {
+                        value,
+                        plain: style == ScalarStyle::Plain,
+                        tag: scalar_tag(tag.as_ref()),
+                    }),
+                    location,
+                };
                 finish_node(node, anchor, &mut stack, &mut anchors, &mut root)?;
             }
             Event::SequenceStart(_, anchor, _) => {
                 stack.push(Container::Sequence {
                     values: Vec::new(),
                     anchor,
+                    location,
                 });
             }
             Event::SequenceEnd => {
-                let Some(Container::Sequence { values, anchor }) = stack.pop() else {
-                    return Err(Error::custom("unexpected YAML sequence end"));
-                };
-                finish_node(
-                    Node::Sequence(values),
+                let Some(Container::Sequence {
+                    values,
                     anchor,
-                    &mut stack,
-                    &mut anchors,
-                    &mut root,
-                )?;
+                    location,
+                }) = stack.pop()
+                else {
+                    return Err(Error::custom("unexpected YAML sequence end").at(location));
+                };
+                let node = Node {
+                    value: Value::Sequence(values),
+                    location,
+                };
+                finish_node(node, anchor, &mut stack, &mut anchors, &mut root)?;
             }
             Event::MappingStart(_, anchor, _) => {
                 stack.push(Container::Mapping {
                     values: Vec::new(),
                     key: None,
                     anchor,
+                    location,
                 });
             }
             Event::MappingEnd => {
@@ -151,26 +244,24 @@ fn parse(input: &str) -> Result<Node<'_>, Error> {
                     values,
                     key,
                     anchor,
+                    location,
                 }) = stack.pop()
                 else {
-                    return Err(Error::custom("unexpected YAML mapping end"));
+                    return Err(Error::custom("unexpected YAML mapping end").at(location));
                 };
-                if key.is_some() {
-                    return Err(Error::custom("YAML mapping key has no value"));
+                if let Some(key) = key {
+                    return Err(Error::custom("YAML mapping key has no value").at(key.location));
                 }
-                finish_node(
-                    Node::Mapping(values),
-                    anchor,
-                    &mut stack,
-                    &mut anchors,
-                    &mut root,
-                )?;
+                let node = Node {
+                    value: Value::Mapping(merge_keys(values)?),
+                    location,
+                };
+                finish_node(node, anchor, &mut stack, &mut anchors, &mut root)?;
             }
             Event::Alias(anchor) => {
-                let node = anchors
-                    .get(&anchor)
-                    .cloned()
-                    .ok_or_else(|| Error::custom(format!("unknown YAML anchor {anchor}")))?;
+                let node = anchors.get(&anchor).cloned().ok_or_else(|| {
+                    Error::custom(format!("unknown YAML anchor {anchor}")).at(location)
+                })?;
                 finish_node(node, 0, &mut stack, &mut anchors, &mut root)?;
             }
         }
@@ -202,14 +293,79 @@ fn finish_node<'de>(
             }
         }
         None => {
+            let location = node.location;
             if root.replace(node).is_some() {
-                return Err(Error::custom("multiple YAML documents are not supported"));
+                return Err(Error::custom("multiple YAML documents are not supported").at(location));
             }
         }
     }
     Ok(())
 }
 
+/// Resolve `<<` merge keys, the merged entries are inserted at the place of the merge key and
+/// explicit keys of the mapping override them, like earlier merged mappings override later ones
+fn merge_keys<'de>(
+    values: Vec<(Node<'de>, Node<'de>)>,
+) -> Result<Vec<(Node<'de>, Node<'de>)>, Error> {
+    if !values.iter().any(|(key, _)| key.is_merge_key()) {
+        return Ok(values);
+    }
+
+    let explicit_keys = values
+        .iter()
+        .filter(|(key, _)| !key.is_merge_key())
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    let mut merged: Vec<(Node<'de>, Node<'de>)> = Vec::new();
+    for (key, value) in values {
+        if !key.is_merge_key() {
+            merged.push((key, value));
+            continue;
+        }
+
+        let mappings = match value.value {
+            Value::Sequence(values) => values,
+            _ => vec![value],
+        };
+        for mapping in mappings {
+            let Value::Mapping(values) = mapping.value else {
+                return Err(
+                    Error::custom("YAML merge key must reference a mapping").at(mapping.location)
+                );
+            };
+            for (key, value) in values {
+                if !explicit_keys.iter().any(|other| other.is_same_key(&key))
+                    && !merged.iter().any(|(other, _)| other.is_same_key(&key))
+                {
+                    merged.push((key, value));
+                }
+            }
+        }
+    }
+    Ok(merged)
+}
+
+impl Node<'_> {
+    fn is_merge_key(&self) -> bool {
+        matches!(&self.value, Value::Scalar(scalar) if scalar.plain && scalar.value == "<<")
+    }
+
+    fn is_same_key(&self, other: &Self) -> bool {
+        match (&self.value, &other.value) {
+            (Value::Scalar(a), Value::Scalar(b)) => a.value == b.value,
+            _ => false,
+        }
+    }
+
+    /// Mapping key as a path segment for errors
+    fn path_segment(&self) -> String {
+        match &self.value {
+            Value::Scalar(scalar) => scalar.value.to_string(),
+            _ => "?".to_string(),
+        }
+    }
+}
+
 impl Scalar<'_> {
     fn is_null(&self) -> bool {
         self.tag == ScalarTag::Null
@@ -279,7 +435,7 @@ macro_rules! deserialize_signed {
         where
             V: Visitor<'de>,
         {
-            let Node::Scalar(scalar) = self else {
+            let Value::Scalar(scalar) = self else {
                 return Err(Error::custom("expected a YAML integer"));
             };
             let value = parse_signed(&scalar.value)?;
@@ -296,7 +452,7 @@ macro_rules! deserialize_unsigned {
         where
             V: Visitor<'de>,
         {
-            let Node::Scalar(scalar) = self else {
+            let Value::Scalar(scalar) = self else {
                 return Err(Error::custom("expected a YAML unsigned integer"));
             };
             let value = parse_unsigned(&scalar.value)?;
@@ -307,7 +463,7 @@ macro_rules! deserialize_unsigned {
     };
 }
 
-impl<'de> de::Deserializer<'de> for Node<'de> {
+impl<'de> de::Deserializer<'de> for Value<'de> {
     type Error = Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
@@ -317,6 +473,7 @@ impl<'de> de::Deserializer<'de> for Node<'de> {
         match self {
             Self::Sequence(values) => visitor.visit_seq(NodeSeqAccess {
                 values: values.into_iter(),
+                index: 0,
             }),
             Self::Mapping(values) => visitor.visit_map(NodeMapAccess {
                 values: values.into_iter(),
@@ -517,6 +674,7 @@ impl<'de> de::Deserializer<'de> for Node<'de> {
         };
         visitor.visit_seq(NodeSeqAccess {
             values: values.into_iter(),
+            index: 0,
         })
     }
 
@@ -581,7 +739,7 @@ impl<'de> de::Deserializer<'de> for Node<'de> {
                 let (variant, value) = values
                     .pop()
                     .ok_or_else(|| Error::custom("expected one YAML enum variant"))?;
-                let Self::Scalar(variant) = variant else {
+                let Value::Scalar(variant) = variant.value else {
                     return Err(Error::custom("YAML enum variant must be a string"));
                 };
                 visitor.visit_enum(NodeEnumAccess {
@@ -608,8 +766,63 @@ impl<'de> de::Deserializer<'de> for Node<'de> {
     }
 }
 
+macro_rules! forward_located {
+    ($($name:ident($($arg:ident: $type:ty),*)),* $(,)?) => {
+        $(
+            fn $name<V>(self, $($arg: $type,)* visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                let location = self.location;
+                self.value
+                    .$name($($arg,)* visitor)
+                    .map_err(|error| error.at(location))
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Node<'de> {
+    type Error = Error;
+
+    forward_located! {
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(length: usize),
+        deserialize_tuple_struct(name: &'static str, length: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    }
+}
+
 struct NodeSeqAccess<'de> {
     values: std::vec::IntoIter<Node<'de>>,
+    index: usize,
 }
 
 impl<'de> SeqAccess<'de> for NodeSeqAccess<'de> {
@@ -619,10 +832,14 @@ impl<'de> SeqAccess<'de> for NodeSeqAccess<'de> {
     where
         T: DeserializeSeed<'de>,
     {
-        self.values
-            .next()
-            .map(|value| seed.deserialize(value))
-            .transpose()
+        let Some(value) = self.values.next() else {
+            return Ok(None);
+        };
+        let index = self.index;
+        self.index += 1;
+        seed.deserialize(value)
+            .map(Some)
+            .map_err(|error| error.in_path(index.to_string()))
     }
 
     fn size_hint(&self) -> Option<usize> {
@@ -632,7 +849,7 @@ impl<'de> SeqAccess<'de> for NodeSeqAccess<'de> {
 
 struct NodeMapAccess<'de> {
     values: std::vec::IntoIter<(Node<'de>, Node<'de>)>,
-    value: Option<Node<'de>>,
+    value: Option<(String, Node<'de>)>,
 }
 
 impl<'de> MapAccess<'de> for NodeMapAccess<'de> {
@@ -645,7 +862,7 @@ impl<'de> MapAccess<'de> for NodeMapAccess<'de> {
         let Some((key, value)) = self.values.next() else {
             return Ok(None);
         };
-        self.value = Some(value);
+        self.value = Some((key.path_segment(), value));
         seed.deserialize(key).map(Some)
     }
 
@@ -653,11 +870,11 @@ impl<'de> MapAccess<'de> for NodeMapAccess<'de> {
     where
         V: DeserializeSeed<'de>,
     {
-        seed.deserialize(
-            self.value
-                .take()
-                .ok_or_else(|| Error::custom("YAML mapping value requested before its key"))?,
-        )
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| Error::custom("YAML mapping value requested before its key"))?;
+        seed.deserialize(value).map_err(|error| error.in_path(key))
     }
 
     fn size_hint(&self) -> Option<usize> {
@@ -811,4 +1028,66 @@ mod tests {
         let values: Vec<String> = super::from_str("- &name demo\n- *name\n").expect("valid YAML");
         assert_eq!(values, ["demo", "demo"]);
     }
+
+    #[test]
+    fn deserialize_merge_keys() {
+        let values: BTreeMap<String, BTreeMap<String, String>> = super::from_str(
+            "base: &base\n  a: x\n  b: y\nother: &other\n  b: ignored\n  c: z\nmerged:\n  b: override\n  <<: [*base, *other]\n",
+        )
+        .expect("valid YAML");
+        assert_eq!(
+            values["merged"],
+            BTreeMap::from([
+                ("a".to_string(), "x".to_string()),
+                ("b".to_string(), "override".to_string()),
+                ("c".to_string(), "z".to_string()),
+            ])
+        );
+
+        let error =
+            super::from_str::<BTreeMap<String, String>>("base: &base x\nmerged:\n  <<: *base\n")
+                .expect_err("invalid merge");
+        assert_eq!(
+            error.to_string(),
+            "YAML merge key must reference a mapping at line 1 column 13"
+        );
+    }
+
+    #[test]
+    fn deserialize_json_document() {
+        let config: Config = super::from_str(
+            "{\n\t\"name\": \"d\\u00e9mo\",\"enabled\":false,\n\t\"ports\": [8080],\n\t\"labels\": {},\n\t\"optional\": \"a\\/b\"\n}\n",
+        )
+        .expect("valid JSON");
+        assert_eq!(config.name, "d\u{e9}mo");
+        assert!(!config.enabled);
+        assert_eq!(config.ports, [8080]);
+        assert_eq!(config.optional.as_deref(), Some("a/b"));
+    }
+
+    #[test]
+    fn error_location_and_path() {
+        let error = super::from_str::<Config>(
+            "name: demo\nenabled: true\nports: [80, http]\nlabels: {}\noptional: null\n",
+        )
+        .expect_err("invalid port");
+        let location = error.location().expect("location");
+        assert_eq!((location.line(), location.column()), (3, 13));
+        assert_eq!(error.path(), "/ports/1");
+        assert_eq!(
+            error.to_string(),
+            "/ports/1: invalid unsigned integer `http` at line 3 column 13"
+        );
+
+        let error = super::from_str::<BTreeMap<String, BTreeMap<String, Config>>>(
+            "paths:\n  /users:\n    name: demo\n",
+        )
+        .expect_err("missing field");
+        assert_eq!(error.path(), "/paths/~1users");
+        assert_eq!(error.location().map(|location| location.line()), Some(3));
+
+        let error = super::from_str::<Config>("name: [demo\n").expect_err("invalid YAML");
+        assert!(error.path().is_empty());
+        assert_eq!(error.location().map(|location| location.line()), Some(1));
+    }
 }
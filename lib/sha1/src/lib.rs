@@ -6,6 +6,8 @@
 
 //! A minimal replacement for the [sha1](https://crates.io/crates/sha1) crate
 
+use subtle::ConstantTimeEq as _;
+
 /// SHA-1 hasher
 pub struct Sha1 {
     state: [u32; 5],
@@ -36,6 +38,16 @@ impl Sha1 {
         h.finalize_reset()
     }
 
+    /// Compute the SHA-1 digest of the given data as a lowercase hex string
+    pub fn digest_hex(data: impl AsRef<[u8]>) -> String {
+        let mut hex = String::with_capacity(40);
+        for byte in Self::digest(data) {
+            hex.push(char::from_digit((byte >> 4) as u32, 16).expect("Should be hex digit"));
+            hex.push(char::from_digit((byte & 0xf) as u32, 16).expect("Should be hex digit"));
+        }
+        hex
+    }
+
     /// Update the hasher with new data
     pub fn update(&mut self, data: impl AsRef<[u8]>) {
         let mut data = data.as_ref();
@@ -538,6 +550,13 @@ impl Sha1 {
     }
 }
 
+// MARK: Utils
+/// Compare two byte slices in constant time, use this to verify digests, signatures or
+/// handshake keys without leaking timing information about where they differ
+pub fn constant_time_eq(a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> bool {
+    bool::from(a.as_ref().ct_eq(b.as_ref()))
+}
+
 // MARK: Digest impl
 impl digest::Digest for Sha1 {
     const BLOCK_SIZE: usize = 64;
@@ -579,6 +598,30 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_sha1_digest_hex() {
+        assert_eq!(
+            Sha1::digest_hex("abc"),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            Sha1::digest_hex(""),
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+        );
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(constant_time_eq("", ""));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"abcd"));
+        assert!(constant_time_eq(
+            Sha1::digest("hello world"),
+            Sha1::digest("hello world")
+        ));
+    }
+
     #[test]
     fn test_sha1_million_a() {
         let mut hasher = Sha1::new();
@@ -1,12 +1,18 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 //! A minimal replacement for the [simple_logger](https://crates.io/crates/simple_logger) crate
 
-use std::env;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{env, panic, process, thread};
 
 use chrono::Utc;
 use log::{Level, LevelFilter, Metadata, Record};
@@ -14,15 +20,19 @@ use log::{Level, LevelFilter, Metadata, Record};
 // MARK: SimpleLogger
 /// Simple logger that logs to stdout
 pub struct SimpleLogger {
-    max_level: LevelFilter,
+    max_level: LevelHandle,
     use_colors: bool,
+    use_json: bool,
 }
 
 impl Default for SimpleLogger {
     fn default() -> Self {
         Self {
-            max_level: LevelFilter::Info,
-            use_colors: env::var("NO_COLOR").is_err() && env::var("CI").is_err(),
+            max_level: LevelHandle::new(LevelFilter::Info),
+            use_colors: env::var("NO_COLOR").is_err()
+                && env::var("CI").is_err()
+                && terminal_size::is_tty(terminal_size::Stream::Stdout),
+            use_json: false,
         }
     }
 }
@@ -36,61 +46,185 @@ impl SimpleLogger {
     /// Create a new logger with the given log level
     pub fn new_with_level(level: LevelFilter) -> Self {
         SimpleLogger {
-            max_level: level,
+            max_level: LevelHandle::new(level),
             ..Default::default()
         }
     }
 
+    /// Log records as JSON objects, one per line
+    pub const fn with_json(mut self, use_json: bool) -> Self {
+        self.use_json = use_json;
+        self
+    }
+
+    /// Get a shared handle to change the log level of this logger at runtime
+    pub fn handle(&self) -> LevelHandle {
+        self.max_level.clone()
+    }
+
     /// Set global logger to this logger
     pub fn init(self) -> Result<(), log::SetLoggerError> {
-        #[cfg(windows)]
         if self.use_colors {
-            _ = enable_ansi_support::enable_ansi_support();
+            _ = terminal_size::enable_ansi_support();
         }
 
-        log::set_max_level(self.max_level);
+        log::set_max_level(self.max_level.max_level());
         log::set_boxed_logger(Box::new(self))
     }
 }
 
 impl log::Log for SimpleLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= self.max_level
+        metadata.level() <= self.max_level.max_level()
     }
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            let level = if self.use_colors {
-                match record.level() {
-                    Level::Error => "\x1b[31mE\x1b[0m", // Red
-                    Level::Warn => "\x1b[33mW\x1b[0m",  // Yellow
-                    Level::Info => "\x1b[32mI\x1b[0m",  // Green
-                    Level::Debug => "\x1b[34mD\x1b[0m", // Blue
-                    Level::Trace => "\x1b[35mT\x1b[0m", // Magenta
-                }
-            } else {
-                match record.level() {
-                    Level::Error => "E",
-                    Level::Warn => "W",
-                    Level::Info => "I",
-                    Level::Debug => "D",
-                    Level::Trace => "T",
-                }
-            };
-
-            println!(
-                "{} {} {}: {}",
-                Utc::now().to_rfc3339(),
-                level,
-                record.target(),
-                record.args()
-            );
+            println!("{}", self.format(&Utc::now().to_rfc3339(), record));
         }
     }
 
     fn flush(&self) {}
 }
 
+impl SimpleLogger {
+    fn format(&self, time: &str, record: &Record) -> String {
+        FIELDS.with(|fields| {
+            if self.use_json {
+                self.format_json(time, record, &fields.borrow())
+            } else {
+                self.format_text(time, record, &fields.borrow())
+            }
+        })
+    }
+
+    fn format_text(&self, time: &str, record: &Record, fields: &[(String, String)]) -> String {
+        let level = if self.use_colors {
+            match record.level() {
+                Level::Error => "\x1b[31mE\x1b[0m", // Red
+                Level::Warn => "\x1b[33mW\x1b[0m",  // Yellow
+                Level::Info => "\x1b[32mI\x1b[0m",  // Green
+                Level::Debug => "\x1b[34mD\x1b[0m", // Blue
+                Level::Trace => "\x1b[35mT\x1b[0m", // Magenta
+            }
+        } else {
+            match record.level() {
+                Level::Error => "E",
+                Level::Warn => "W",
+                Level::Info => "I",
+                Level::Debug => "D",
+                Level::Trace => "T",
+            }
+        };
+
+        let mut line = format!("{} {} {}: {}", time, level, record.target(), record.args());
+        for (key, value) in fields {
+            _ = write!(line, " {key}={value}");
+        }
+        line
+    }
+
+    fn format_json(&self, time: &str, record: &Record, fields: &[(String, String)]) -> String {
+        let mut line = format!(
+            "{{\"time\":{},\"level\":{},\"target\":{},\"message\":{}",
+            json_string(time),
+            json_string(record.level().as_str()),
+            json_string(record.target()),
+            json_string(&record.args().to_string())
+        );
+        for (key, value) in fields {
+            _ = write!(line, ",{}:{}", json_string(key), json_string(value));
+        }
+        line.push('}');
+        line
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    string.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            c if c.is_control() => _ = write!(string, "\\u{:04x}", c as u32),
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
+
+// MARK: Fields
+thread_local! {
+    static FIELDS: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Guard of scoped log fields, the fields are removed when it is dropped
+#[must_use = "the fields are removed when the guard is dropped"]
+pub struct FieldsGuard {
+    len: usize,
+    // Fields are thread-local, so the guard must be dropped on the thread that created it
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for FieldsGuard {
+    fn drop(&mut self) {
+        FIELDS.with(|fields| fields.borrow_mut().truncate(self.len));
+    }
+}
+
+/// Add key-value fields to every log record of this thread until the returned guard is
+/// dropped, like a request id for all logs of handling a request. Scopes can be nested.
+pub fn with_fields(fields: &[(impl ToString, impl ToString)]) -> FieldsGuard {
+    FIELDS.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let len = stack.len();
+        stack.extend(
+            fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string())),
+        );
+        FieldsGuard {
+            len,
+            _not_send: PhantomData,
+        }
+    })
+}
+
+// MARK: LevelHandle
+/// Shared handle to change the log level of a [SimpleLogger] at runtime, for example from a GUI
+/// toggle or a signal handler
+#[derive(Clone)]
+pub struct LevelHandle(Arc<AtomicUsize>);
+
+impl LevelHandle {
+    fn new(level: LevelFilter) -> Self {
+        Self(Arc::new(AtomicUsize::new(level as usize)))
+    }
+
+    /// Get the current max log level
+    pub fn max_level(&self) -> LevelFilter {
+        match self.0.load(Ordering::Relaxed) {
+            0 => LevelFilter::Off,
+            1 => LevelFilter::Error,
+            2 => LevelFilter::Warn,
+            3 => LevelFilter::Info,
+            4 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+
+    /// Change the max log level without restarting the logger
+    pub fn set_max_level_runtime(&self, level: LevelFilter) {
+        self.0.store(level as usize, Ordering::Relaxed);
+        log::set_max_level(level);
+    }
+}
+
 // MARK: Utils
 
 /// Initialize the global logger with default settings
@@ -102,3 +236,79 @@ pub fn init() -> Result<(), log::SetLoggerError> {
 pub fn init_with_level(level: LevelFilter) -> Result<(), log::SetLoggerError> {
     SimpleLogger::new_with_level(level).init()
 }
+
+/// Install a panic hook that logs panics with a backtrace through the logger and then aborts
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        log::error!(
+            "thread '{}' {}\n{}",
+            thread::current().name().unwrap_or("<unnamed>"),
+            info,
+            Backtrace::force_capture()
+        );
+        log::logger().flush();
+        process::abort();
+    }));
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn format(use_json: bool) -> String {
+        let logger = SimpleLogger {
+            max_level: LevelHandle::new(LevelFilter::Info),
+            use_colors: false,
+            use_json,
+        };
+        logger.format(
+            "2026-01-01T00:00:00+00:00",
+            &Record::builder()
+                .args(format_args!("Hello \"world\""))
+                .level(Level::Info)
+                .target("app")
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_with_fields() {
+        assert_eq!(
+            format(false),
+            "2026-01-01T00:00:00+00:00 I app: Hello \"world\""
+        );
+        {
+            let _guard = with_fields(&[("request_id", "abc")]);
+            {
+                let _guard = with_fields(&[("user_id", 42)]);
+                assert_eq!(
+                    format(false),
+                    "2026-01-01T00:00:00+00:00 I app: Hello \"world\" request_id=abc user_id=42"
+                );
+                assert_eq!(
+                    format(true),
+                    r#"{"time":"2026-01-01T00:00:00+00:00","level":"INFO","target":"app","message":"Hello \"world\"","request_id":"abc","user_id":"42"}"#
+                );
+            }
+            assert_eq!(
+                format(false),
+                "2026-01-01T00:00:00+00:00 I app: Hello \"world\" request_id=abc"
+            );
+
+            // Fields are scoped to the thread
+            thread::spawn(|| {
+                assert_eq!(
+                    format(false),
+                    "2026-01-01T00:00:00+00:00 I app: Hello \"world\""
+                );
+            })
+            .join()
+            .unwrap();
+        }
+        assert_eq!(
+            format(false),
+            "2026-01-01T00:00:00+00:00 I app: Hello \"world\""
+        );
+    }
+}
@@ -1,33 +1,42 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 use std::collections::HashMap;
-use std::io::{self, Read, Write};
-use std::net::TcpStream;
+use std::env;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 #[cfg(feature = "tls")]
 use native_tls::TlsStream;
 
+use url::Url;
+
+use crate::enums::{Method, Status};
 use crate::header_map::HeaderMap;
+use crate::proxy::ChunkedReader;
 use crate::request::{FetchError, Request};
 use crate::response::Response;
 use crate::KEEP_ALIVE_TIMEOUT;
 
 // MARK: MaybeHttpsStream
+/// Client connection that is either plain TCP or TLS
 #[allow(clippy::large_enum_variant)]
-pub(crate) enum MaybeHttpsStream {
+pub enum MaybeHttpsStream {
+    /// Plain TCP connection
     Plain(TcpStream),
+    /// TLS connection
     #[cfg(feature = "tls")]
     Tls(TlsStream<TcpStream>),
 }
 
 impl MaybeHttpsStream {
-    pub(crate) fn set_read_timeout(&self, dur: Option<Duration>) -> io::Result<()> {
+    /// Set read timeout of the underlying TCP stream
+    pub fn set_read_timeout(&self, dur: Option<Duration>) -> io::Result<()> {
         match self {
             Self::Plain(s) => s.set_read_timeout(dur),
             #[cfg(feature = "tls")]
@@ -65,112 +74,452 @@ impl Write for MaybeHttpsStream {
 }
 
 // MARK: HTTP Client
-/// HTTP client
-#[derive(Default, Clone)]
+/// HTTP client with a keep-alive connection pool per host
+#[derive(Clone)]
 pub struct Client {
     connection_pool: Arc<Mutex<ConnectionPool>>,
     headers: HeaderMap,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    max_redirects: usize,
+    http_proxy: Option<Url>,
+    https_proxy: Option<Url>,
+    no_proxy: Vec<String>,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self {
+            connection_pool: Arc::default(),
+            headers: HeaderMap::new(),
+            connect_timeout: None,
+            read_timeout: Some(KEEP_ALIVE_TIMEOUT),
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            http_proxy: proxy_from_env("http_proxy"),
+            https_proxy: proxy_from_env("https_proxy"),
+            no_proxy: env::var("no_proxy")
+                .or_else(|_| env::var("NO_PROXY"))
+                .map(|no_proxy| {
+                    no_proxy
+                        .split(',')
+                        .map(|host| host.trim().trim_start_matches('.').to_lowercase())
+                        .filter(|host| !host.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
 }
 
 impl Client {
-    /// Create a new HTTP client
+    /// Create a new HTTP client, proxies are read from the `HTTP_PROXY`, `HTTPS_PROXY` and
+    /// `NO_PROXY` environment variables
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// Set header
+    /// Add header, keeps existing values with the same name
     pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
-        self.headers.insert(name.into(), value.into());
+        self.headers.append(name.into(), value.into());
+        self
+    }
+
+    /// Set connect timeout
+    pub const fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set read timeout
+    pub const fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum number of redirects to follow, defaults to 10. 0 disables following redirects
+    pub const fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// Set proxy for all requests
+    pub fn proxy(mut self, url: impl AsRef<str>) -> Self {
+        let proxy = parse_proxy(url.as_ref());
+        self.http_proxy = proxy.clone();
+        self.https_proxy = proxy;
+        self.no_proxy.clear();
+        self
+    }
+
+    /// Disable proxies, also the ones from the environment
+    pub fn no_proxy(mut self) -> Self {
+        self.http_proxy = None;
+        self.https_proxy = None;
         self
     }
 
-    /// Fetch a request
-    pub fn fetch(&mut self, mut request: Request) -> Result<Response, FetchError> {
+    /// Fetch a request, following redirects
+    pub fn fetch(&mut self, request: Request) -> Result<Response, FetchError> {
+        self.fetch_with(request, Self::fetch_once)
+    }
+
+    /// Fetch a request and write the response body to `writer` while it is received, so large
+    /// bodies aren't kept in memory. Follows redirects like [`Client::fetch`], the returned
+    /// response has an empty body
+    pub fn fetch_to_writer(
+        &mut self,
+        request: Request,
+        writer: &mut dyn Write,
+    ) -> Result<Response, FetchError> {
+        self.fetch_with(request, |client, request| {
+            let is_head = request.method == Method::Head;
+            let (host, port, is_https) = target(&request.url)?;
+            let proxy = client.proxy_for(&host, is_https);
+            let absolute_target = proxy.is_some() && !is_https;
+            let mut stream = client.connect(&host, port, is_https, proxy)?;
+            stream
+                .set_read_timeout(client.read_timeout)
+                .map_err(|_| FetchError)?;
+            request.write_to_stream_with_target(&mut stream, false, absolute_target);
+
+            // Read head and copy the body, redirect bodies are skipped
+            let mut reader = BufReader::new(stream);
+            let res = Response::read_head(&mut reader).map_err(|_| FetchError)?;
+            if is_head
+                || matches!(res.status, Status::NoContent | Status::NotModified)
+                || client.redirect_location(&res).is_some()
+            {
+                return Ok(res);
+            }
+            let is_chunked = res
+                .headers
+                .get("Transfer-Encoding")
+                .map_or(false, |transfer_encoding| {
+                    transfer_encoding.eq_ignore_ascii_case("chunked")
+                });
+            if is_chunked {
+                io::copy(&mut ChunkedReader::new(reader), writer).map_err(|_| FetchError)?;
+            } else if let Some(len) = res.headers.content_length() {
+                let copied = io::copy(&mut reader.take(len), writer).map_err(|_| FetchError)?;
+                if copied != len {
+                    return Err(FetchError);
+                }
+            } else {
+                io::copy(&mut reader, writer).map_err(|_| FetchError)?;
+            }
+            Ok(res)
+        })
+    }
+
+    fn fetch_with(
+        &mut self,
+        mut request: Request,
+        mut fetch_once: impl FnMut(&mut Self, Request) -> Result<Response, FetchError>,
+    ) -> Result<Response, FetchError> {
         // Add client headers to request
         for (name, value) in &self.headers {
             request = request.header(name, value);
         }
 
+        let mut redirects = 0;
+        loop {
+            let res = fetch_once(self, request.clone())?;
+            let location = match self.redirect_location(&res) {
+                Some(location) => location,
+                None => return Ok(res),
+            };
+            if redirects == self.max_redirects {
+                return Err(FetchError);
+            }
+            redirects += 1;
+            request = redirect_request(request, res.status, location).ok_or(FetchError)?;
+        }
+    }
+
+    // Location of a redirect response that should be followed
+    fn redirect_location<'a>(&self, res: &'a Response) -> Option<&'a str> {
+        if self.max_redirects == 0 {
+            return None;
+        }
+        match res.status {
+            Status::MovedPermanently
+            | Status::Found
+            | Status::SeeOther
+            | Status::TemporaryRedirect
+            | Status::PermanentRedirect => res.headers.get("Location"),
+            _ => None,
+        }
+    }
+
+    /// Send a request that upgrades the connection to another protocol like WebSocket, the
+    /// connection is returned instead of pooled. The `ws` and `wss` schemes are treated like
+    /// `http` and `https`
+    pub fn upgrade(
+        &mut self,
+        mut request: Request,
+    ) -> Result<(Response, MaybeHttpsStream), FetchError> {
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        let (host, port, is_https) = target(&request.url)?;
+        let proxy = self.proxy_for(&host, is_https);
+        let absolute_target = proxy.is_some() && !is_https;
+        let mut stream = self.connect(&host, port, is_https, proxy)?;
+        let res = self.send(&mut stream, request, absolute_target)?;
+        stream.set_read_timeout(None).map_err(|_| FetchError)?;
+        Ok((res, stream))
+    }
+
+    fn fetch_once(&mut self, request: Request) -> Result<Response, FetchError> {
         // Build connection key and address
-        let host = request.url.host().ok_or(FetchError)?.to_string();
-        let is_https = request.url.scheme() == "https";
-        let port = request
-            .url
-            .port()
-            .unwrap_or(if is_https { 443 } else { 80 });
-        let conn_key = format!("{}://{}:{}", request.url.scheme(), host, port);
-        let tcp_addr = format!("{host}:{port}");
-
-        // Get or create connection
-        let mut stream = self
-            .connection_pool
-            .lock()
-            .expect("Can't lock connection pool")
-            .take_connection(&conn_key, &tcp_addr, is_https, &host)
-            .ok_or(FetchError)?;
+        let (host, port, is_https) = target(&request.url)?;
+        let proxy = self.proxy_for(&host, is_https);
+        let conn_key = match (proxy, is_https) {
+            // Plain HTTP requests to the same proxy can share connections
+            (Some(proxy), false) => format!("proxy {proxy}"),
+            (Some(proxy), true) => format!("https://{host}:{port} via {proxy}"),
+            (None, _) => format!("{}://{}:{}", request.url.scheme(), host, port),
+        };
+        let absolute_target = proxy.is_some() && !is_https;
+
+        // Try a pooled connection first, it could be closed by the server in the meantime. So
+        // only idempotent requests use one, because they can safely be sent again
+        let pooled = if is_idempotent(request.method) {
+            self.connection_pool
+                .lock()
+                .expect("Can't lock connection pool")
+                .take_connection(&conn_key)
+        } else {
+            None
+        };
+        if let Some(mut stream) = pooled {
+            if let Ok(res) = self.send(&mut stream, request.clone(), absolute_target) {
+                self.return_connection(&conn_key, stream, &res);
+                return Ok(res);
+            }
+        }
+
+        // Open a new connection
+        let mut stream = self.connect(&host, port, is_https, proxy)?;
+        let res = self.send(&mut stream, request, absolute_target)?;
+        self.return_connection(&conn_key, stream, &res);
+        Ok(res)
+    }
+
+    fn send(
+        &self,
+        stream: &mut MaybeHttpsStream,
+        request: Request,
+        absolute_target: bool,
+    ) -> Result<Response, FetchError> {
         stream
-            .set_read_timeout(Some(KEEP_ALIVE_TIMEOUT))
+            .set_read_timeout(self.read_timeout)
             .map_err(|_| FetchError)?;
+        request.write_to_stream_with_target(stream, true, absolute_target);
+        Response::read_from_stream(stream).map_err(|_| FetchError)
+    }
 
-        // Send request and read response
-        request.write_to_stream(&mut stream, true);
-        let res = Response::read_from_stream(&mut stream).map_err(|_| FetchError)?;
-
-        // Return connection
+    fn return_connection(&self, key: &str, stream: MaybeHttpsStream, res: &Response) {
+        if res
+            .headers
+            .get("Connection")
+            .map_or(false, |connection| connection.eq_ignore_ascii_case("close"))
+        {
+            return;
+        }
         self.connection_pool
             .lock()
             .expect("Can't lock connection pool")
-            .return_connection(&conn_key, stream);
-        Ok(res)
+            .return_connection(key, stream);
     }
-}
 
-// MARK: ConnectionPool
-#[derive(Default)]
-struct ConnectionPool {
-    connections: HashMap<String, Vec<MaybeHttpsStream>>,
-}
+    fn proxy_for(&self, host: &str, is_https: bool) -> Option<&Url> {
+        let host = host.to_lowercase();
+        if self.no_proxy.iter().any(|no_proxy| {
+            no_proxy == "*"
+                || host == *no_proxy
+                || host
+                    .strip_suffix(no_proxy.as_str())
+                    .map_or(false, |prefix| prefix.ends_with('.'))
+        }) {
+            return None;
+        }
+        if is_https {
+            self.https_proxy.as_ref()
+        } else {
+            self.http_proxy.as_ref()
+        }
+    }
 
-impl ConnectionPool {
-    fn take_connection(
-        &mut self,
-        key: &str,
-        tcp_addr: &str,
-        is_https: bool,
+    pub(crate) fn connect(
+        &self,
         host: &str,
-    ) -> Option<MaybeHttpsStream> {
-        if !self.connections.contains_key(key) {
-            self.connections.insert(key.to_string(), Vec::new());
+        port: u16,
+        is_https: bool,
+        proxy: Option<&Url>,
+    ) -> Result<MaybeHttpsStream, FetchError> {
+        let tcp = if let Some(proxy) = proxy {
+            let mut tcp = self.connect_tcp(&format!(
+                "{}:{}",
+                proxy.host().ok_or(FetchError)?,
+                proxy.port().unwrap_or(80)
+            ))?;
+            if is_https {
+                // Open a tunnel through the proxy to the host
+                tcp.set_read_timeout(self.read_timeout)
+                    .map_err(|_| FetchError)?;
+                write!(
+                    tcp,
+                    "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n\r\n"
+                )
+                .map_err(|_| FetchError)?;
+                let res = Response::read_from_stream(&mut tcp).map_err(|_| FetchError)?;
+                if res.status != Status::Ok {
+                    return Err(FetchError);
+                }
+            }
+            tcp
+        } else {
+            self.connect_tcp(&format!("{host}:{port}"))?
+        };
+
+        #[cfg(feature = "tls")]
+        if is_https {
+            use native_tls::TlsConnector;
+            let connector = TlsConnector::new().map_err(|_| FetchError)?;
+            let tls = connector.connect(host, tcp).map_err(|_| FetchError)?;
+            return Ok(MaybeHttpsStream::Tls(tls));
+        }
+        #[cfg(not(feature = "tls"))]
+        if is_https {
+            return Err(FetchError);
         }
 
-        if let Some(connections) = self.connections.get_mut(key) {
-            if let Some(conn) = connections.pop() {
-                return Some(conn);
-            }
+        Ok(MaybeHttpsStream::Plain(tcp))
+    }
 
-            let tcp = TcpStream::connect(tcp_addr).ok()?;
+    fn connect_tcp(&self, addr: &str) -> Result<TcpStream, FetchError> {
+        if let Some(timeout) = self.connect_timeout {
+            addr.to_socket_addrs()
+                .map_err(|_| FetchError)?
+                .find_map(|addr| TcpStream::connect_timeout(&addr, timeout).ok())
+                .ok_or(FetchError)
+        } else {
+            TcpStream::connect(addr).map_err(|_| FetchError)
+        }
+    }
+}
 
-            #[cfg(feature = "tls")]
-            if is_https {
-                use native_tls::TlsConnector;
-                let connector = TlsConnector::new().ok()?;
-                let tls = connector.connect(host, tcp).ok()?;
-                return Some(MaybeHttpsStream::Tls(tls));
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+// Host, port and if TLS is used for a request URL
+fn target(url: &Url) -> Result<(String, u16, bool), FetchError> {
+    let host = url.host().ok_or(FetchError)?.to_string();
+    let is_https = matches!(url.scheme(), "https" | "wss");
+    let port = url.port().unwrap_or(if is_https { 443 } else { 80 });
+    Ok((host, port, is_https))
+}
+
+const fn is_idempotent(method: Method) -> bool {
+    !matches!(method, Method::Post | Method::Patch | Method::Connect)
+}
+
+fn proxy_from_env(name: &str) -> Option<Url> {
+    // The uppercase HTTP_PROXY variable is ignored for CGI scripts, because there it can be set
+    // by the Proxy request header of the client
+    let is_cgi = env::var_os("REQUEST_METHOD").is_some();
+    env::var(name)
+        .or_else(|_| {
+            let upper = name.to_uppercase();
+            if is_cgi && upper == "HTTP_PROXY" {
+                Err(env::VarError::NotPresent)
+            } else {
+                env::var(upper)
             }
-            // Suppress unused warnings when tls feature is disabled
-            let _ = is_https;
-            let _ = host;
+        })
+        .ok()
+        .and_then(|url| parse_proxy(&url))
+}
 
-            return Some(MaybeHttpsStream::Plain(tcp));
-        }
+fn parse_proxy(url: &str) -> Option<Url> {
+    let url = url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return None;
+    }
+    // Proxy addresses are often given without scheme
+    let url = if url.contains("://") {
+        format!("{url}/")
+    } else {
+        format!("http://{url}/")
+    };
+    Url::parse(&url).ok().filter(|url| url.scheme() == "http")
+}
+
+fn redirect_request(mut request: Request, status: Status, location: &str) -> Option<Request> {
+    let url = resolve_location(&request.url, location)?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return None;
+    }
+
+    // Change the method to GET like browsers do, except for 307 and 308 redirects
+    if status == Status::SeeOther && request.method != Method::Head
+        || matches!(status, Status::MovedPermanently | Status::Found)
+            && request.method == Method::Post
+    {
+        request.method = Method::Get;
+        request.body = None;
+        request.headers.remove("Content-Type");
+    }
+
+    // Don't leak credentials to other origins
+    if target(&url).ok() != target(&request.url).ok() || url.scheme() != request.url.scheme() {
+        request.headers.remove("Authorization");
+        request.headers.remove("Cookie");
+    }
+    request.url = url;
+    Some(request)
+}
+
+fn resolve_location(base: &Url, location: &str) -> Option<Url> {
+    if location.contains("://") {
+        return Url::parse(location).ok();
+    }
+    if let Some(location) = location.strip_prefix("//") {
+        return Url::parse(&format!("{}://{}", base.scheme(), location)).ok();
+    }
 
-        None
+    let mut authority = base.host()?.to_string();
+    if let Some(port) = base.port() {
+        authority = format!("{authority}:{port}");
+    }
+    let path = if location.starts_with('/') {
+        location.to_string()
+    } else if location.starts_with('?') {
+        format!("{}{}", base.path(), location)
+    } else {
+        let dir = &base.path()[..base.path().rfind('/').map_or(0, |index| index + 1)];
+        format!("{}{}", if dir.is_empty() { "/" } else { dir }, location)
+    };
+    Url::parse(&format!("{}://{}{}", base.scheme(), authority, path)).ok()
+}
+
+// MARK: ConnectionPool
+#[derive(Default)]
+struct ConnectionPool {
+    connections: HashMap<String, Vec<MaybeHttpsStream>>,
+}
+
+impl ConnectionPool {
+    fn take_connection(&mut self, key: &str) -> Option<MaybeHttpsStream> {
+        self.connections.get_mut(key)?.pop()
     }
 
     fn return_connection(&mut self, key: &str, conn: MaybeHttpsStream) {
-        if let Some(connections) = self.connections.get_mut(key) {
-            connections.push(conn);
-        }
+        self.connections
+            .entry(key.to_string())
+            .or_default()
+            .push(conn);
     }
 }
 
@@ -178,11 +527,29 @@ impl ConnectionPool {
 #[cfg(test)]
 mod test {
     use std::io::{Read, Write};
-    use std::net::{Ipv4Addr, TcpListener};
+    use std::net::{Ipv4Addr, SocketAddr, TcpListener};
     use std::thread;
 
     use super::*;
 
+    fn start_redirect_server() -> SocketAddr {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            crate::serve_single_threaded(listener, |req| match req.url.path() {
+                "/old" => {
+                    Response::with_status(Status::MovedPermanently).header("Location", "/new")
+                }
+                "/new" => Response::with_body("new"),
+                "/form" => Response::with_status(Status::SeeOther).header("Location", "result"),
+                "/result" => Response::with_body(req.method.to_string()),
+                "/loop" => Response::with_redirect("/loop"),
+                _ => Response::with_status(Status::NotFound),
+            });
+        });
+        server_addr
+    }
+
     #[test]
     fn test_client_multiple_requests() {
         // Start test server
@@ -209,4 +576,148 @@ mod test {
                 .unwrap();
         }
     }
+
+    #[test]
+    fn test_client_follows_redirects() {
+        let server_addr = start_redirect_server();
+        let mut client = Client::new().no_proxy();
+
+        let res = client
+            .fetch(Request::get(format!("http://{server_addr}/old")))
+            .unwrap();
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.body, b"new");
+
+        // 303 See Other changes the method to GET
+        let res = client
+            .fetch(Request::post(format!("http://{server_addr}/form")).body("data"))
+            .unwrap();
+        assert_eq!(res.body, b"GET");
+
+        let mut client = Client::new().no_proxy().max_redirects(3);
+        assert!(client
+            .fetch(Request::get(format!("http://{server_addr}/loop")))
+            .is_err());
+
+        let mut client = Client::new().no_proxy().max_redirects(0);
+        let res = client
+            .fetch(Request::get(format!("http://{server_addr}/old")))
+            .unwrap();
+        assert_eq!(res.status, Status::MovedPermanently);
+    }
+
+    #[test]
+    fn test_client_fetch_to_writer() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            crate::serve_single_threaded(listener, |req| match req.url.path() {
+                "/old" => Response::with_redirect("/new"),
+                "/new" => Response::with_body("new"),
+                "/chunked" => {
+                    Response::with_body_stream(io::Cursor::new(b"Streamed body".to_vec()), None)
+                }
+                _ => Response::with_status(Status::NotFound),
+            });
+        });
+        let mut client = Client::new().no_proxy();
+
+        let mut body = Vec::new();
+        let res = client
+            .fetch_to_writer(Request::get(format!("http://{server_addr}/old")), &mut body)
+            .unwrap();
+        assert_eq!(res.status, Status::Ok);
+        assert!(res.body.is_empty());
+        assert_eq!(body, b"new");
+
+        let mut body = Vec::new();
+        client
+            .fetch_to_writer(
+                Request::get(format!("http://{server_addr}/chunked")),
+                &mut body,
+            )
+            .unwrap();
+        assert_eq!(body, b"Streamed body");
+    }
+
+    #[test]
+    fn test_client_proxy() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let proxy_addr = listener.local_addr().unwrap();
+        let proxy = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0; 512];
+            let n = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nproxy")
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let mut client = Client::new().proxy(proxy_addr.to_string());
+        let res = client
+            .fetch(Request::get("http://example.com/path?q=1"))
+            .unwrap();
+        assert_eq!(res.body, b"proxy");
+        let req = proxy.join().unwrap();
+        assert!(req.starts_with("GET http://example.com/path?q=1 HTTP/1.1\r\n"));
+        assert!(req.contains("Host: example.com\r\n"));
+    }
+
+    #[test]
+    fn test_client_read_timeout() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (_stream, _) = listener.accept().unwrap();
+            thread::sleep(Duration::from_secs(5));
+        });
+
+        let mut client = Client::new()
+            .no_proxy()
+            .connect_timeout(Duration::from_secs(1))
+            .read_timeout(Duration::from_millis(100));
+        assert!(client
+            .fetch(Request::get(format!("http://{server_addr}/")))
+            .is_err());
+    }
+
+    #[test]
+    fn test_resolve_location() {
+        let base = Url::parse("http://localhost:8080/a/b?c=d").unwrap();
+        let resolve = |location| resolve_location(&base, location).unwrap().to_string();
+        assert_eq!(resolve("https://example.com/"), "https://example.com/");
+        assert_eq!(resolve("//example.com/x"), "http://example.com/x");
+        assert_eq!(resolve("/x?y=z"), "http://localhost:8080/x?y=z");
+        assert_eq!(resolve("x"), "http://localhost:8080/a/x");
+        assert_eq!(resolve("?e=f"), "http://localhost:8080/a/b?e=f");
+    }
+
+    #[test]
+    fn test_redirect_request_strips_credentials() {
+        let request = Request::get("http://localhost:8080/a")
+            .header("Authorization", "Bearer secret")
+            .header("Cookie", "session=secret");
+        let same_origin = redirect_request(request.clone(), Status::Found, "/b").unwrap();
+        assert!(same_origin.headers.get("Authorization").is_some());
+        for location in [
+            "http://localhost:8081/b",
+            "https://localhost:8080/b",
+            "http://example.com:8080/b",
+        ] {
+            let redirected = redirect_request(request.clone(), Status::Found, location).unwrap();
+            assert!(redirected.headers.get("Authorization").is_none());
+            assert!(redirected.headers.get("Cookie").is_none());
+        }
+    }
+
+    #[test]
+    fn test_parse_proxy() {
+        assert_eq!(
+            parse_proxy("proxy.local:3128").unwrap().to_string(),
+            "http://proxy.local:3128/"
+        );
+        assert!(parse_proxy("").is_none());
+        assert!(parse_proxy("socks5://proxy.local:1080").is_none());
+    }
 }
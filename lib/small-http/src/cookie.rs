@@ -0,0 +1,308 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::collections::hash_map::Iter;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::time::{Duration, SystemTime};
+
+use crate::date::http_date;
+use crate::header_map::HeaderMap;
+
+// MARK: Cookies
+/// Cookies parsed from the `Cookie` request header
+#[derive(Debug, Default, Clone)]
+pub struct Cookies(HashMap<String, String>);
+
+impl Cookies {
+    /// Create empty cookies map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse cookies from a `Cookie` header value, the first occurrence of a name wins
+    pub fn parse(header: &str) -> Self {
+        let mut cookies = Self::new();
+        cookies.parse_header(header);
+        cookies
+    }
+
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let mut cookies = Self::new();
+        for value in headers.get_all("Cookie") {
+            cookies.parse_header(value);
+        }
+        cookies
+    }
+
+    fn parse_header(&mut self, header: &str) {
+        for pair in header.split(';') {
+            if let Some((name, value)) = pair.split_once('=') {
+                let name = name.trim();
+                if name.is_empty() {
+                    continue;
+                }
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .unwrap_or(value);
+                self.0
+                    .entry(name.to_string())
+                    .or_insert_with(|| value.to_string());
+            }
+        }
+    }
+
+    /// Get cookie value by name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    /// Returns whether a cookie with name exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Get number of cookies
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no cookies
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get iterator over cookies
+    pub fn iter(&self) -> Iter<'_, String, String> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Cookies {
+    type Item = (&'a String, &'a String);
+    type IntoIter = Iter<'a, String, String>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+// MARK: SameSite
+/// Cookie SameSite attribute
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SameSite {
+    /// Only sent on same-site requests
+    Strict,
+    /// Also sent on top-level cross-site navigations
+    Lax,
+    /// Always sent, requires the Secure attribute
+    None,
+}
+
+impl Display for SameSite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        })
+    }
+}
+
+// MARK: SetCookie
+/// `Set-Cookie` response header builder
+#[derive(Debug, Clone)]
+pub struct SetCookie {
+    name: String,
+    value: String,
+    expires: Option<SystemTime>,
+    max_age: Option<Duration>,
+    domain: Option<String>,
+    path: Option<String>,
+    same_site: Option<SameSite>,
+    secure: bool,
+    http_only: bool,
+}
+
+// Characters that would end the cookie or header line early
+fn assert_cookie_safe(kind: &str, value: &str) {
+    assert!(
+        !value.contains([';', '\r', '\n']),
+        "Cookie {kind} can't contain ';', CR or LF"
+    );
+}
+
+impl SetCookie {
+    /// Create new cookie with name and value
+    ///
+    /// # Panics
+    ///
+    /// Panics when the name or value contains `;`, CR or LF
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        let (name, value) = (name.into(), value.into());
+        assert_cookie_safe("name", &name);
+        assert_cookie_safe("value", &value);
+        Self {
+            name,
+            value,
+            expires: None,
+            max_age: None,
+            domain: None,
+            path: None,
+            same_site: None,
+            secure: false,
+            http_only: false,
+        }
+    }
+
+    /// Create cookie that removes the cookie with name from the client
+    pub fn removal(name: impl Into<String>) -> Self {
+        Self::new(name, "")
+            .expires(SystemTime::UNIX_EPOCH)
+            .max_age(Duration::ZERO)
+    }
+
+    /// Set expires time
+    pub const fn expires(mut self, expires: SystemTime) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    /// Set max age
+    pub const fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Set domain, panics when it contains `;`, CR or LF
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        let domain = domain.into();
+        assert_cookie_safe("domain", &domain);
+        self.domain = Some(domain);
+        self
+    }
+
+    /// Set path, panics when it contains `;`, CR or LF
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        assert_cookie_safe("path", &path);
+        self.path = Some(path);
+        self
+    }
+
+    /// Set SameSite attribute
+    pub const fn same_site(mut self, same_site: SameSite) -> Self {
+        self.same_site = Some(same_site);
+        self
+    }
+
+    /// Set Secure attribute
+    pub const fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Set HttpOnly attribute
+    pub const fn http_only(mut self, http_only: bool) -> Self {
+        self.http_only = http_only;
+        self
+    }
+}
+
+impl Display for SetCookie {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)?;
+        if let Some(expires) = self.expires {
+            write!(f, "; Expires={}", http_date(expires))?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age.as_secs())?;
+        }
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={}", domain)?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={}", same_site)?;
+        }
+        if self.secure || self.same_site == Some(SameSite::None) {
+            f.write_str("; Secure")?;
+        }
+        if self.http_only {
+            f.write_str("; HttpOnly")?;
+        }
+        Ok(())
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Request, Response};
+
+    #[test]
+    fn test_parse_cookies() {
+        let cookies = Cookies::parse("session=abc; theme=\"dark\";empty=; =bad; session=other");
+        assert_eq!(cookies.len(), 3);
+        assert_eq!(cookies.get("session"), Some("abc"));
+        assert_eq!(cookies.get("theme"), Some("dark"));
+        assert_eq!(cookies.get("empty"), Some(""));
+        assert!(!cookies.contains("other"));
+
+        let req = Request::get("http://localhost/")
+            .header("Cookie", "a=1")
+            .header("cookie", "b=2");
+        let cookies = req.cookies();
+        assert_eq!(cookies.get("a"), Some("1"));
+        assert_eq!(cookies.get("b"), Some("2"));
+    }
+
+    #[test]
+    fn test_set_cookie() {
+        assert_eq!(SetCookie::new("a", "1").to_string(), "a=1");
+        assert_eq!(
+            SetCookie::new("session", "abc")
+                .expires(SystemTime::UNIX_EPOCH + Duration::from_secs(784111777))
+                .max_age(Duration::from_secs(3600))
+                .domain("example.com")
+                .path("/")
+                .same_site(SameSite::Lax)
+                .secure(true)
+                .http_only(true)
+                .to_string(),
+            "session=abc; Expires=Sun, 06 Nov 1994 08:49:37 GMT; Max-Age=3600; \
+             Domain=example.com; Path=/; SameSite=Lax; Secure; HttpOnly"
+        );
+        assert_eq!(
+            SetCookie::new("a", "1")
+                .same_site(SameSite::None)
+                .to_string(),
+            "a=1; SameSite=None; Secure"
+        );
+        assert_eq!(
+            SetCookie::removal("a").to_string(),
+            "a=; Expires=Thu, 01 Jan 1970 00:00:00 GMT; Max-Age=0"
+        );
+
+        let res = Response::new()
+            .set_cookie(SetCookie::new("a", "1"))
+            .set_cookie(SetCookie::new("b", "2"));
+        let values = res.headers.get_all("Set-Cookie").collect::<Vec<_>>();
+        assert_eq!(values, ["a=1", "b=2"]);
+    }
+
+    #[test]
+    fn test_set_cookie_rejects_unsafe() {
+        for (name, value) in [("a", "1; Domain=evil.com"), ("a\r\nX", "1"), ("a", "1\n")] {
+            assert!(std::panic::catch_unwind(|| SetCookie::new(name, value)).is_err());
+        }
+        assert!(std::panic::catch_unwind(|| SetCookie::new("a", "1").path("/; Secure")).is_err());
+    }
+}
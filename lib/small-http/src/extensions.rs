@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type ExtensionMap = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
+// MARK: Extensions
+/// Typed map of values attached to a request or response by layers, at most one value per type
+///
+/// Values can be inserted through a shared reference, so a layer that only gets `&Request` can
+/// still attach data (like an authenticated user or a trace id) for downstream handlers.
+#[derive(Default)]
+pub struct Extensions(Mutex<ExtensionMap>);
+
+impl Extensions {
+    /// Create new empty extensions map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn map(&self) -> MutexGuard<'_, ExtensionMap> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Insert value, replaces and returns the existing value of the same type
+    pub fn insert<T: Any + Send + Sync>(&self, value: T) -> Option<Arc<T>> {
+        self.map()
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .and_then(|value| value.downcast::<T>().ok())
+    }
+
+    /// Get value by type
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.map()
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.clone().downcast::<T>().ok())
+    }
+
+    /// Check if a value of type is present
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.map().contains_key(&TypeId::of::<T>())
+    }
+
+    /// Remove and return value by type
+    pub fn remove<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.map()
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast::<T>().ok())
+    }
+
+    /// Get number of values
+    pub fn len(&self) -> usize {
+        self.map().len()
+    }
+
+    /// Check if there are no values
+    pub fn is_empty(&self) -> bool {
+        self.map().is_empty()
+    }
+
+    /// Remove all values
+    pub fn clear(&self) {
+        self.map().clear();
+    }
+}
+
+impl Clone for Extensions {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.map().clone()))
+    }
+}
+
+impl Debug for Extensions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct User(&'static str);
+
+    #[test]
+    fn test_extensions() {
+        let extensions = Extensions::new();
+        assert!(extensions.is_empty());
+        assert_eq!(extensions.insert(User("Bassie")), None);
+        assert_eq!(extensions.insert(42u32), None);
+        assert_eq!(extensions.len(), 2);
+
+        assert_eq!(extensions.get::<User>().as_deref(), Some(&User("Bassie")));
+        assert_eq!(extensions.get::<u32>().as_deref(), Some(&42));
+        assert_eq!(extensions.get::<u64>(), None);
+        assert!(extensions.contains::<User>());
+
+        let old = extensions.insert(User("Adriaan"));
+        assert_eq!(old.as_deref(), Some(&User("Bassie")));
+        assert_eq!(extensions.get::<User>().as_deref(), Some(&User("Adriaan")));
+
+        assert_eq!(extensions.remove::<u32>().as_deref(), Some(&42));
+        assert!(!extensions.contains::<u32>());
+    }
+
+    #[test]
+    fn test_extensions_clone() {
+        let extensions = Extensions::new();
+        extensions.insert(User("Bassie"));
+        let cloned = extensions.clone();
+        cloned.insert(1u8);
+        assert_eq!(extensions.len(), 1);
+        assert_eq!(cloned.len(), 2);
+        assert_eq!(cloned.get::<User>().as_deref(), Some(&User("Bassie")));
+    }
+}
@@ -0,0 +1,298 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Static file serving helpers
+
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Take};
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::date::{format_http_date, parse_http_date};
+use crate::enums::{Method, Status};
+use crate::request::{percent_decode, Request};
+use crate::response::Response;
+
+// MARK: Serve
+/// Serve a file, supporting `Range`, `If-None-Match` and `If-Modified-Since` requests
+pub fn serve_file(req: &Request, path: impl AsRef<Path>) -> Response {
+    let path = path.as_ref();
+    if req.method != Method::Get && req.method != Method::Head {
+        return Response::with_status(Status::MethodNotAllowed).header("Allow", "GET, HEAD");
+    }
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() => metadata,
+        _ => return Response::with_status(Status::NotFound),
+    };
+
+    // Validators
+    let len = metadata.len();
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|modified| modified.as_secs());
+    let etag = format!("\"{:x}-{:x}\"", modified.unwrap_or(0), len);
+    let mut res = Response::with_header(
+        "Content-Type",
+        mime_guess::from_path(path)
+            .first_or_octet_stream()
+            .to_string(),
+    )
+    .header("ETag", &etag)
+    .header("Accept-Ranges", "bytes");
+    if let Some(modified) = modified {
+        res = res.header("Last-Modified", format_http_date(modified));
+    }
+
+    // Conditional requests
+    let not_modified = if let Some(if_none_match) = req.headers.get("If-None-Match") {
+        if_none_match
+            .split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == "*" || tag == etag)
+    } else if let Some(if_modified_since) = req.headers.get("If-Modified-Since") {
+        match (modified, parse_http_date(if_modified_since)) {
+            (Some(modified), Some(since)) => modified <= since,
+            _ => false,
+        }
+    } else {
+        false
+    };
+    if not_modified {
+        return res.status(Status::NotModified);
+    }
+
+    // Range requests, If-Range falls back to the full file when the file changed
+    let range = req
+        .headers
+        .get("Range")
+        .filter(|_| {
+            req.headers
+                .get("If-Range")
+                .map_or(true, |if_range| if_range == etag)
+        })
+        .map(|range| parse_range(range, len));
+    let (start, end) = match range {
+        Some(Some(Some((start, end)))) => {
+            res = res
+                .status(Status::PartialContent)
+                .header("Content-Range", format!("bytes {start}-{end}/{len}"));
+            (start, end + 1)
+        }
+        Some(Some(None)) => {
+            return res
+                .status(Status::RangeNotSatisfiable)
+                .header("Content-Range", format!("bytes */{len}"));
+        }
+        _ => (0, len),
+    };
+
+    // Stream the file, so large files aren't read into memory
+    match open_file_range(path, start, end) {
+        Ok(file) => res.body_stream(file, Some(end - start)),
+        Err(_) => Response::with_status(Status::InternalServerError),
+    }
+}
+
+/// Serve a file from a directory based on the request path, `index.html` is served for
+/// directories and paths that would escape the directory are rejected
+pub fn serve_dir(req: &Request, root: impl AsRef<Path>) -> Response {
+    let root = root.as_ref();
+    let path = match resolve_path(root, req.url.path()) {
+        Some(path) => path,
+        None => return Response::with_status(Status::NotFound),
+    };
+    let path = if path.is_dir() {
+        path.join("index.html")
+    } else {
+        path
+    };
+
+    // Also reject symlinks that point outside the directory
+    match (root.canonicalize(), path.canonicalize()) {
+        (Ok(root), Ok(path)) if path.starts_with(&root) => serve_file(req, path),
+        _ => Response::with_status(Status::NotFound),
+    }
+}
+
+/// Resolve a percent-encoded url path to a path inside `root`, returns `None` when a segment
+/// could escape the directory
+pub fn resolve_path(root: &Path, url_path: &str) -> Option<PathBuf> {
+    let mut path = root.to_path_buf();
+    for segment in url_path.split('/') {
+        let segment = percent_decode(segment)?;
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+        // Segments must be plain file names, this rejects '..', separators and drive prefixes
+        let mut components = Path::new(&segment).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(name)), None)
+                if name == segment.as_str() && !segment.contains(['\\', ':', '\0']) =>
+            {
+                path.push(name)
+            }
+            _ => return None,
+        }
+    }
+    Some(path)
+}
+
+/// Parses a single byte range, returns `None` for unsupported ranges and `Some(None)` for
+/// unsatisfiable ranges
+fn parse_range(range: &str, len: u64) -> Option<Option<(u64, u64)>> {
+    let range = range.trim().strip_prefix("bytes=")?;
+    if range.contains(',') {
+        return None;
+    }
+    let (start, end) = range.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+    let range = if start.is_empty() {
+        let suffix = end.parse::<u64>().ok()?;
+        (len.saturating_sub(suffix), len.checked_sub(1)?)
+    } else {
+        let start = start.parse::<u64>().ok()?;
+        let end = if end.is_empty() {
+            len.saturating_sub(1)
+        } else {
+            end.parse::<u64>().ok()?.min(len.saturating_sub(1))
+        };
+        (start, end)
+    };
+    Some(if range.0 <= range.1 && range.0 < len {
+        Some(range)
+    } else {
+        None
+    })
+}
+
+fn open_file_range(path: &Path, start: u64, end: u64) -> io::Result<Take<File>> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    Ok(file.take(end - start))
+}
+
+/// Formats a system time as HTTP date, useful for `Last-Modified` and `Expires` headers
+pub fn http_date(time: SystemTime) -> String {
+    crate::date::http_date(time)
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Write the response and return its body, the file is streamed while writing
+    fn body(mut res: Response) -> Vec<u8> {
+        let mut stream = Vec::new();
+        res.write_to_stream(&mut stream, &Request::get("http://localhost/"), false);
+        let start = stream
+            .windows(4)
+            .position(|window| window == b"\r\n\r\n")
+            .unwrap()
+            + 4;
+        stream.split_off(start)
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("small-http-fs-{}-{}", name, std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("hello.txt"), "Hello, world!").unwrap();
+        fs::write(dir.join("sub/index.html"), "<h1>Index</h1>").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_serve_file() {
+        let dir = test_dir("file");
+        let res = serve_file(&Request::get("http://localhost/"), dir.join("hello.txt"));
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.headers.get("Content-Type"), Some("text/plain"));
+        assert!(res.headers.get("Last-Modified").is_some());
+        let etag = res.headers.get("ETag").unwrap().to_string();
+        assert_eq!(body(res), b"Hello, world!");
+
+        let res = serve_file(
+            &Request::get("http://localhost/").header("If-None-Match", &etag),
+            dir.join("hello.txt"),
+        );
+        assert_eq!(res.status, Status::NotModified);
+        assert!(res.body.is_empty());
+
+        let res = serve_file(
+            &Request::get("http://localhost/")
+                .header("If-Modified-Since", http_date(SystemTime::now())),
+            dir.join("hello.txt"),
+        );
+        assert_eq!(res.status, Status::NotModified);
+
+        let res = serve_file(&Request::post("http://localhost/"), dir.join("hello.txt"));
+        assert_eq!(res.status, Status::MethodNotAllowed);
+
+        let res = serve_file(&Request::get("http://localhost/"), dir.join("missing.txt"));
+        assert_eq!(res.status, Status::NotFound);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_serve_file_range() {
+        let dir = test_dir("range");
+        let path = dir.join("hello.txt");
+        let range = |range: &str| {
+            serve_file(
+                &Request::get("http://localhost/").header("Range", range),
+                &path,
+            )
+        };
+
+        let res = range("bytes=0-4");
+        assert_eq!(res.status, Status::PartialContent);
+        assert_eq!(res.headers.get("Content-Range"), Some("bytes 0-4/13"));
+        assert_eq!(body(res), b"Hello");
+
+        assert_eq!(body(range("bytes=7-")), b"world!");
+        assert_eq!(body(range("bytes=-6")), b"world!");
+
+        let res = range("bytes=20-30");
+        assert_eq!(res.status, Status::RangeNotSatisfiable);
+        assert_eq!(res.headers.get("Content-Range"), Some("bytes */13"));
+
+        // Multiple ranges are not supported, so the full file is served
+        let res = range("bytes=0-1,3-4");
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(body(res), b"Hello, world!");
+
+        let res = serve_file(
+            &Request::get("http://localhost/")
+                .header("Range", "bytes=0-4")
+                .header("If-Range", "\"outdated\""),
+            &path,
+        );
+        assert_eq!(res.status, Status::Ok);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_serve_dir() {
+        let dir = test_dir("dir");
+        let serve = |path: &str| serve_dir(&Request::get(format!("http://localhost{path}")), &dir);
+
+        assert_eq!(body(serve("/hello.txt")), b"Hello, world!");
+        assert_eq!(body(serve("/sub/")), b"<h1>Index</h1>");
+        assert_eq!(body(serve("/sub")), b"<h1>Index</h1>");
+        assert_eq!(serve("/missing").status, Status::NotFound);
+        assert_eq!(serve("/../hello.txt").status, Status::NotFound);
+        assert_eq!(
+            serve("/sub/%2e%2e/%2e%2e/etc/passwd").status,
+            Status::NotFound
+        );
+        assert_eq!(serve("/sub%2f..%2fhello.txt").status, Status::NotFound);
+        assert_eq!(serve("/sub/%2E%2E/hello.txt").status, Status::NotFound);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,12 +1,13 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 use std::slice::{Iter, IterMut};
 
-/// HeaderMap
+/// HeaderMap with case-insensitive names, multiple values per name and insertion ordered
+/// iteration
 #[derive(Default, Clone)]
 pub struct HeaderMap(Vec<(String, String)>);
 
@@ -16,7 +17,7 @@ impl HeaderMap {
         Self::default()
     }
 
-    /// Get header value
+    /// Get first header value
     pub fn get(&self, name: &str) -> Option<&str> {
         self.0
             .iter()
@@ -24,11 +25,48 @@ impl HeaderMap {
             .map(|(_, v)| v.as_str())
     }
 
-    /// Insert header
+    /// Get all header values with name
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Check if header exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|(n, _)| n.eq_ignore_ascii_case(name))
+    }
+
+    /// Insert header, replaces all existing values with name
     pub fn insert(&mut self, name: String, value: String) {
+        match self
+            .0
+            .iter()
+            .position(|(n, _)| n.eq_ignore_ascii_case(&name))
+        {
+            Some(index) => {
+                self.0[index].1 = value;
+                let mut i = 0;
+                self.0.retain(|(n, _)| {
+                    i += 1;
+                    i - 1 <= index || !n.eq_ignore_ascii_case(&name)
+                });
+            }
+            None => self.0.push((name, value)),
+        }
+    }
+
+    /// Append header, keeps existing values with name
+    pub fn append(&mut self, name: String, value: String) {
         self.0.push((name, value));
     }
 
+    /// Remove all headers with name
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+    }
+
     /// Get number of headers
     pub fn len(&self) -> usize {
         self.0.len()
@@ -43,6 +81,16 @@ impl HeaderMap {
     pub fn iter(&self) -> Iter<'_, (String, String)> {
         self.0.iter()
     }
+
+    /// Get the parsed Content-Length header
+    pub fn content_length(&self) -> Option<u64> {
+        self.get("Content-Length")?.trim().parse().ok()
+    }
+
+    /// Get the parsed Content-Type header
+    pub fn content_type(&self) -> Option<mime::Mime> {
+        self.get("Content-Type")?.parse().ok()
+    }
 }
 
 impl IntoIterator for HeaderMap {
@@ -71,3 +119,61 @@ impl<'a> IntoIterator for &'a mut HeaderMap {
         self.0.iter_mut()
     }
 }
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_case_insensitive_get() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Type".to_string(), "text/plain".to_string());
+        assert_eq!(headers.get("content-type"), Some("text/plain"));
+        assert!(headers.contains("CONTENT-TYPE"));
+        assert!(!headers.contains("Content-Length"));
+    }
+
+    #[test]
+    fn test_insert_replaces_and_append_keeps() {
+        let mut headers = HeaderMap::new();
+        headers.append("Set-Cookie".to_string(), "a=1".to_string());
+        headers.append("X-Other".to_string(), "1".to_string());
+        headers.append("set-cookie".to_string(), "b=2".to_string());
+        assert_eq!(
+            headers.get_all("Set-Cookie").collect::<Vec<_>>(),
+            ["a=1", "b=2"]
+        );
+
+        headers.insert("Set-Cookie".to_string(), "c=3".to_string());
+        assert_eq!(headers.get_all("Set-Cookie").collect::<Vec<_>>(), ["c=3"]);
+        assert_eq!(
+            headers
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            ["Set-Cookie", "X-Other"]
+        );
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(headers.content_length(), None);
+        assert!(headers.content_type().is_none());
+
+        headers.insert("Content-Length".to_string(), "42".to_string());
+        headers.insert(
+            "Content-Type".to_string(),
+            "application/json; charset=utf-8".to_string(),
+        );
+        assert_eq!(headers.content_length(), Some(42));
+        assert_eq!(
+            headers.content_type().unwrap().essence_str(),
+            "application/json"
+        );
+
+        headers.insert("Content-Length".to_string(), "invalid".to_string());
+        assert_eq!(headers.content_length(), None);
+    }
+}
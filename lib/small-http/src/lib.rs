@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2023-2025 Bastiaan van der Plaat
+ * Copyright (c) 2023-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -8,23 +8,40 @@
 
 use std::time::Duration;
 
-pub use crate::client::Client;
+pub use crate::client::{Client, MaybeHttpsStream};
+pub use crate::cookie::{Cookies, SameSite, SetCookie};
 pub use crate::enums::{Method, Status};
+pub use crate::extensions::Extensions;
 pub use crate::header_map::HeaderMap;
+pub use crate::multipart::{Multipart, MultipartError, MultipartForm, Part};
+pub use crate::proxy::proxy;
 pub use crate::request::Request;
+#[cfg(feature = "request-id")]
+pub use crate::request_id::{request_id, RequestId};
 pub use crate::response::Response;
-#[cfg(feature = "multi-threaded")]
-pub use crate::serve::serve;
 #[cfg(feature = "cgi")]
 pub use crate::serve::serve_cgi;
-pub use crate::serve::serve_single_threaded;
+#[cfg(feature = "multi-threaded")]
+pub use crate::serve::{serve, serve_with_limits};
+pub use crate::serve::{serve_single_threaded, serve_single_threaded_with_limits, Limits};
+pub use crate::sse::{Event, EventStream};
 
 mod client;
+mod cookie;
+mod date;
 mod enums;
+mod extensions;
+#[cfg(feature = "fs")]
+pub mod fs;
 mod header_map;
+mod multipart;
+mod proxy;
 mod request;
+#[cfg(feature = "request-id")]
+mod request_id;
 mod response;
 mod serve;
+mod sse;
 
 // MARK: Constants
 pub(crate) const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(5);
@@ -0,0 +1,391 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::header_map::HeaderMap;
+use crate::request::Request;
+
+// MARK: Part
+/// Multipart form data part
+pub struct Part<'a> {
+    /// Field name
+    pub name: String,
+    /// File name, only set for file fields
+    pub filename: Option<String>,
+    /// Content type
+    pub content_type: Option<String>,
+    /// Headers
+    pub headers: HeaderMap,
+    /// Body
+    pub body: &'a [u8],
+}
+
+impl<'a> Part<'a> {
+    /// Get a reader for the body
+    pub const fn reader(&self) -> &'a [u8] {
+        self.body
+    }
+
+    /// Get body as text
+    pub fn text(&self) -> Option<&'a str> {
+        std::str::from_utf8(self.body).ok()
+    }
+}
+
+// MARK: Multipart
+/// Multipart form data parser, iterates over the parts of a `multipart/form-data` body
+pub struct Multipart<'a> {
+    rest: &'a [u8],
+    delimiter: Vec<u8>,
+    started: bool,
+}
+
+impl<'a> Multipart<'a> {
+    /// Create multipart parser for body with boundary
+    pub fn new(body: &'a [u8], boundary: &str) -> Self {
+        Self {
+            rest: body,
+            delimiter: format!("--{boundary}").into_bytes(),
+            started: false,
+        }
+    }
+
+    /// Create multipart parser for request body
+    pub fn from_request(req: &'a Request) -> Result<Self, MultipartError> {
+        let content_type = req
+            .content_type()
+            .ok_or_else(|| MultipartError("Missing Content-Type".to_string()))?;
+        if content_type.essence_str() != "multipart/form-data" {
+            return Err(MultipartError(
+                "Content-Type is not multipart/form-data".to_string(),
+            ));
+        }
+        let boundary = content_type
+            .get_param("boundary")
+            .ok_or_else(|| MultipartError("Missing boundary".to_string()))?;
+        Ok(Self::new(req.body.as_deref().unwrap_or(&[]), boundary))
+    }
+
+    fn next_part(&mut self) -> Result<Option<Part<'a>>, MultipartError> {
+        // Skip preamble before the first delimiter
+        if !self.started {
+            self.started = true;
+            let start = find(self.rest, &self.delimiter)
+                .ok_or_else(|| MultipartError("Missing first boundary".to_string()))?;
+            self.rest = &self.rest[start + self.delimiter.len()..];
+        }
+
+        // Delimiter is followed by -- for the last one or CRLF
+        if self.rest.starts_with(b"--") {
+            self.rest = &[];
+            return Ok(None);
+        }
+        self.rest = self
+            .rest
+            .strip_prefix(b"\r\n")
+            .ok_or_else(|| MultipartError("Invalid boundary line".to_string()))?;
+
+        // Read headers
+        let (header_lines, body_start) = if self.rest.starts_with(b"\r\n") {
+            (&self.rest[..0], 2)
+        } else {
+            let end = find(self.rest, b"\r\n\r\n")
+                .ok_or_else(|| MultipartError("Missing end of headers".to_string()))?;
+            (&self.rest[..end], end + 4)
+        };
+        let mut headers = HeaderMap::new();
+        for line in String::from_utf8_lossy(header_lines)
+            .split("\r\n")
+            .filter(|line| !line.is_empty())
+        {
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| MultipartError("Can't parse header line".to_string()))?;
+            headers.append(name.trim().to_string(), value.trim().to_string());
+        }
+        self.rest = &self.rest[body_start..];
+
+        // Read body until next delimiter
+        let mut body_delimiter = b"\r\n".to_vec();
+        body_delimiter.extend_from_slice(&self.delimiter);
+        let body_end = find(self.rest, &body_delimiter)
+            .ok_or_else(|| MultipartError("Missing closing boundary".to_string()))?;
+        let body = &self.rest[..body_end];
+        self.rest = &self.rest[body_end + body_delimiter.len()..];
+
+        // Parse Content-Disposition
+        let disposition = headers
+            .get("Content-Disposition")
+            .ok_or_else(|| MultipartError("Missing Content-Disposition".to_string()))?;
+        let params = parse_disposition_params(disposition);
+        let name = params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("name"))
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| MultipartError("Missing field name".to_string()))?;
+        let filename = params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("filename"))
+            .map(|(_, value)| value.clone());
+        let content_type = headers.get("Content-Type").map(|value| value.to_string());
+
+        Ok(Some(Part {
+            name,
+            filename,
+            content_type,
+            headers,
+            body,
+        }))
+    }
+}
+
+impl<'a> Iterator for Multipart<'a> {
+    type Item = Result<Part<'a>, MultipartError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.started && self.rest.is_empty() {
+            return None;
+        }
+        match self.next_part() {
+            Ok(part) => part.map(Ok),
+            Err(err) => {
+                self.started = true;
+                self.rest = &[];
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn parse_disposition_params(disposition: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = disposition.split_once(';').map_or("", |(_, rest)| rest);
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ';']);
+        let (key, value) = match rest.split_once('=') {
+            Some(param) => param,
+            None => break,
+        };
+        let value = value.trim_start();
+        let value = if let Some(quoted) = value.strip_prefix('"') {
+            let mut unescaped = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some((_, c)) = chars.next() {
+                            unescaped.push(c);
+                        }
+                    }
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+                    _ => unescaped.push(c),
+                }
+            }
+            rest = &quoted[end..];
+            unescaped
+        } else {
+            let (value, next) = value.split_once(';').unwrap_or((value, ""));
+            rest = next;
+            value.trim_end().to_string()
+        };
+        params.push((key.trim().to_string(), value));
+    }
+    params
+}
+
+// MARK: MultipartForm
+/// Multipart form data body builder
+pub struct MultipartForm {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Default for MultipartForm {
+    fn default() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos() as u64);
+        Self {
+            boundary: format!(
+                "----small-http-{:016x}{:08x}",
+                nanos,
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ),
+            body: Vec::new(),
+        }
+    }
+}
+
+impl MultipartForm {
+    /// Create new multipart form
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add text field
+    pub fn text(self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.part(name.as_ref(), None, None, value.as_ref().as_bytes())
+    }
+
+    /// Add file field
+    pub fn file(
+        self,
+        name: impl AsRef<str>,
+        filename: impl AsRef<str>,
+        content_type: impl AsRef<str>,
+        data: impl AsRef<[u8]>,
+    ) -> Self {
+        self.part(
+            name.as_ref(),
+            Some(filename.as_ref()),
+            Some(content_type.as_ref()),
+            data.as_ref(),
+        )
+    }
+
+    fn part(
+        mut self,
+        name: &str,
+        filename: Option<&str>,
+        content_type: Option<&str>,
+        data: &[u8],
+    ) -> Self {
+        self.body
+            .extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+        let mut disposition = format!("form-data; name=\"{}\"", escape_quoted(name));
+        if let Some(filename) = filename {
+            disposition.push_str(&format!("; filename=\"{}\"", escape_quoted(filename)));
+        }
+        self.body
+            .extend_from_slice(format!("Content-Disposition: {disposition}\r\n").as_bytes());
+        if let Some(content_type) = content_type {
+            let content_type = content_type.replace(['\r', '\n'], "");
+            self.body
+                .extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+        }
+        self.body.extend_from_slice(b"\r\n");
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+        self
+    }
+
+    /// Get the Content-Type header value with boundary
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Finish the form and get the body
+    pub fn into_body(mut self) -> Vec<u8> {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        self.body
+    }
+}
+
+fn escape_quoted(value: &str) -> String {
+    // Escape like browsers do for form field names and file names
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+// MARK: MultipartError
+/// Multipart parse error
+#[derive(Debug)]
+pub struct MultipartError(String);
+
+impl Display for MultipartError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid multipart body: {}", self.0)
+    }
+}
+
+impl Error for MultipartError {}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_multipart() {
+        let body = b"preamble\r\n--XyZ\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nHello\r\n--XyZ\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"a \\\"b\\\".txt\"\r\nContent-Type: text/plain\r\n\r\nline 1\r\nline 2\r\n--XyZ--\r\n";
+        let req = Request::post("http://localhost/")
+            .header("Content-Type", "multipart/form-data; boundary=XyZ")
+            .body(&body[..]);
+
+        let parts = Multipart::from_request(&req)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].name, "title");
+        assert_eq!(parts[0].filename, None);
+        assert_eq!(parts[0].text(), Some("Hello"));
+        assert_eq!(parts[1].name, "upload");
+        assert_eq!(parts[1].filename.as_deref(), Some("a \"b\".txt"));
+        assert_eq!(parts[1].content_type.as_deref(), Some("text/plain"));
+        let mut contents = String::new();
+        parts[1].reader().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "line 1\r\nline 2");
+    }
+
+    #[test]
+    fn test_multipart_form_roundtrip() {
+        let form = MultipartForm::new().text("name", "Bastiaan").file(
+            "avatar",
+            "avatar.png",
+            "image/png",
+            [0x89, b'P', b'N', b'G'],
+        );
+        let req = Request::post("http://localhost/").multipart(form);
+
+        let mut parts = Multipart::from_request(&req).unwrap();
+        let part = parts.next().unwrap().unwrap();
+        assert_eq!(part.name, "name");
+        assert_eq!(part.body, b"Bastiaan");
+        let part = parts.next().unwrap().unwrap();
+        assert_eq!(part.name, "avatar");
+        assert_eq!(part.filename.as_deref(), Some("avatar.png"));
+        assert_eq!(part.content_type.as_deref(), Some("image/png"));
+        assert_eq!(part.body, [0x89, b'P', b'N', b'G']);
+        assert!(parts.next().is_none());
+    }
+
+    #[test]
+    fn test_multipart_errors() {
+        let req = Request::post("http://localhost/").header("Content-Type", "multipart/form-data");
+        assert!(Multipart::from_request(&req).is_err());
+
+        let req = Request::post("http://localhost/").header("Content-Type", "text/plain");
+        assert!(Multipart::from_request(&req).is_err());
+
+        let mut parts = Multipart::new(
+            b"--XyZ\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nno end",
+            "XyZ",
+        );
+        assert!(parts.next().unwrap().is_err());
+        assert!(parts.next().is_none());
+    }
+}
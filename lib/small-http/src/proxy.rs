@@ -0,0 +1,336 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::io::{self, BufRead, BufReader, Read};
+use std::str::FromStr;
+use std::time::Duration;
+
+use url::Url;
+
+use crate::client::{Client, MaybeHttpsStream};
+use crate::enums::{Method, Status};
+use crate::header_map::HeaderMap;
+use crate::request::Request;
+use crate::response::Response;
+
+// Headers that only apply to a single connection and are not forwarded
+const HOP_BY_HOP_HEADERS: [&str; 10] = [
+    "Connection",
+    "Keep-Alive",
+    "Proxy-Authenticate",
+    "Proxy-Authorization",
+    "TE",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+    "Host",
+    "Content-Length",
+];
+
+// Upstream servers that stop responding are answered with 502 Bad Gateway
+const UPSTREAM_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Headers that are hop-by-hop, including the ones listed in the Connection header
+struct HopByHop(Vec<String>);
+
+impl HopByHop {
+    fn new(headers: &HeaderMap) -> Self {
+        Self(
+            headers
+                .get_all("Connection")
+                .flat_map(|connection| connection.split(','))
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect(),
+        )
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        HOP_BY_HOP_HEADERS
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case(name))
+            || self
+                .0
+                .iter()
+                .any(|header| header.eq_ignore_ascii_case(name))
+    }
+}
+
+// MARK: Proxy
+/// Forward a request to an upstream server and stream its response back, the request path and
+/// query are appended to `upstream_base_url`. The Host header is rewritten to the upstream and
+/// X-Forwarded-For, X-Forwarded-Host and X-Forwarded-Proto headers are added. Returns a
+/// 502 Bad Gateway response when the upstream can't be reached or doesn't respond within
+/// 30 seconds.
+pub fn proxy(req: &Request, upstream_base_url: impl AsRef<str>) -> Response {
+    match proxy_inner(req, upstream_base_url.as_ref()) {
+        Some(res) => res,
+        None => Response::with_status(Status::BadGateway).body("502 Bad Gateway"),
+    }
+}
+
+fn proxy_inner(req: &Request, upstream_base_url: &str) -> Option<Response> {
+    // Build upstream request
+    let mut url = format!(
+        "{}{}",
+        upstream_base_url.trim_end_matches('/'),
+        req.url.path()
+    );
+    if let Some(query) = req.url.query() {
+        url.push('?');
+        url.push_str(query);
+    }
+    let url = Url::from_str(&url).ok()?;
+    let mut upstream_req = Request::with_method(req.method).url(url);
+    let hop_by_hop = HopByHop::new(&req.headers);
+    for (name, value) in &req.headers {
+        if !hop_by_hop.contains(name) && !name.eq_ignore_ascii_case("X-Forwarded-For") {
+            upstream_req.headers.append(name.clone(), value.clone());
+        }
+    }
+    let client_ip = req.client_addr.ip().to_string();
+    upstream_req.headers.insert(
+        "X-Forwarded-For".to_string(),
+        match req.headers.get("X-Forwarded-For") {
+            Some(forwarded_for) => format!("{}, {}", forwarded_for, client_ip),
+            None => client_ip,
+        },
+    );
+    if let Some(host) = req.headers.get("Host") {
+        upstream_req
+            .headers
+            .insert("X-Forwarded-Host".to_string(), host.to_string());
+    }
+    upstream_req.headers.insert(
+        "X-Forwarded-Proto".to_string(),
+        req.url.scheme().to_string(),
+    );
+    upstream_req.body = req.body.clone();
+
+    // Send request to upstream
+    let host = upstream_req.url.host()?.to_string();
+    let is_https = upstream_req.url.scheme() == "https";
+    let port = upstream_req
+        .url
+        .port()
+        .unwrap_or(if is_https { 443 } else { 80 });
+    let mut stream = Client::new()
+        .read_timeout(UPSTREAM_READ_TIMEOUT)
+        .connect(&host, port, is_https, None)
+        .ok()?;
+    stream.set_read_timeout(Some(UPSTREAM_READ_TIMEOUT)).ok()?;
+    upstream_req.write_to_stream(&mut stream, false);
+
+    // Read upstream response head and stream the body back
+    let mut reader = BufReader::new(stream);
+    let upstream_res = Response::read_head(&mut reader).ok()?;
+    let mut res = Response::with_status(upstream_res.status);
+    let hop_by_hop = HopByHop::new(&upstream_res.headers);
+    for (name, value) in upstream_res.headers.iter() {
+        if !hop_by_hop.contains(name) {
+            res.headers.append(name.clone(), value.clone());
+        }
+    }
+
+    let has_body = req.method != Method::Head
+        && !matches!(upstream_res.status, Status::NoContent | Status::NotModified);
+    if !has_body {
+        return Some(res);
+    }
+    let is_chunked = upstream_res
+        .headers
+        .get("Transfer-Encoding")
+        .map_or(false, |transfer_encoding| {
+            transfer_encoding.eq_ignore_ascii_case("chunked")
+        });
+    Some(if is_chunked {
+        res.body_stream(ChunkedReader::new(reader), None)
+    } else if let Some(len) = upstream_res.headers.content_length() {
+        res.body_stream(reader.take(len), Some(len))
+    } else {
+        res.body_stream(reader, None)
+    })
+}
+
+// MARK: ChunkedReader
+// Reader that decodes a chunked transfer encoded body
+pub(crate) struct ChunkedReader {
+    inner: BufReader<MaybeHttpsStream>,
+    remaining: u64,
+    done: bool,
+}
+
+impl ChunkedReader {
+    pub(crate) const fn new(inner: BufReader<MaybeHttpsStream>) -> Self {
+        Self {
+            inner,
+            remaining: 0,
+            done: false,
+        }
+    }
+
+    fn read_line(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.inner.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(line)
+    }
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.done || buf.is_empty() {
+            return Ok(0);
+        }
+        if self.remaining == 0 {
+            // Read chunk size line; strip optional chunk extensions (;...)
+            let size_line = self.read_line()?;
+            let hex = size_line.split(';').next().unwrap_or("").trim();
+            self.remaining = u64::from_str_radix(hex, 16)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid chunk size"))?;
+            if self.remaining == 0 {
+                // Skip trailers until the empty line that ends the body
+                while self.read_line()? != "\r\n" {}
+                self.done = true;
+                return Ok(0);
+            }
+        }
+
+        let max = buf.len().min(self.remaining as usize);
+        let size = self.inner.read(&mut buf[..max])?;
+        if size == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.remaining -= size as u64;
+        if self.remaining == 0 {
+            // Read trailing CRLF after chunk data
+            let mut crlf = [0; 2];
+            self.inner.read_exact(&mut crlf)?;
+        }
+        Ok(size)
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Write};
+    use std::net::{Ipv4Addr, TcpListener};
+    use std::sync::mpsc;
+    use std::thread;
+
+    use super::*;
+
+    fn start_server(handler: fn(&Request) -> Response) -> String {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || crate::serve_single_threaded(listener, handler));
+        format!("http://{addr}")
+    }
+
+    #[test]
+    fn test_proxy() {
+        let upstream = start_server(|req| {
+            Response::with_header("X-Upstream", "1").body(format!(
+                "{} {}?{} host={} for={} proto={} body={}",
+                req.method,
+                req.url.path(),
+                req.url.query().unwrap_or_default(),
+                req.headers.get("Host").unwrap_or_default(),
+                req.headers.get("X-Forwarded-For").unwrap_or_default(),
+                req.headers.get("X-Forwarded-Proto").unwrap_or_default(),
+                String::from_utf8_lossy(req.body.as_deref().unwrap_or_default())
+            ))
+        });
+        let upstream_host = upstream.trim_start_matches("http://").to_string();
+        let upstream_url = format!("{upstream}/api");
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            crate::serve_single_threaded(listener, move |req| proxy(req, &upstream_url))
+        });
+
+        let res = Request::post(format!("http://{addr}/users?page=2"))
+            .body("Hello")
+            .fetch()
+            .unwrap();
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.headers.get("X-Upstream"), Some("1"));
+        assert_eq!(
+            String::from_utf8(res.body).unwrap(),
+            format!(
+                "POST /api/users?page=2 host={upstream_host} for=127.0.0.1 proto=http body=Hello"
+            )
+        );
+    }
+
+    #[test]
+    fn test_proxy_strips_connection_headers() {
+        // Raw upstream, so the Connection header of its response isn't rewritten
+        let upstream = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let upstream_url = format!("http://{}", upstream.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let (mut stream, _) = upstream.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut head = String::new();
+            while reader.read_line(&mut head).unwrap() > 2 {}
+            tx.send(head).unwrap();
+            stream
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nConnection: close, X-Internal\r\nX-Internal: 1\r\n\
+                    Content-Length: 2\r\n\r\nok",
+                )
+                .unwrap();
+        });
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            crate::serve_single_threaded(listener, move |req| proxy(req, &upstream_url))
+        });
+
+        let res = Request::get(format!("http://{addr}/"))
+            .header("Connection", "keep-alive, X-Secret")
+            .header("X-Secret", "1")
+            .header("X-Custom", "1")
+            .fetch()
+            .unwrap();
+        assert_eq!(res.headers.get("X-Internal"), None);
+        assert_eq!(res.body, b"ok");
+        let head = rx.recv().unwrap();
+        assert!(!head.contains("X-Secret"));
+        assert!(head.contains("X-Custom: 1"));
+    }
+
+    #[test]
+    fn test_proxy_chunked_response() {
+        let upstream = start_server(|_| {
+            Response::with_body_stream(Cursor::new(b"Streamed body".to_vec()), None)
+        });
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            crate::serve_single_threaded(listener, move |req| proxy(req, &upstream))
+        });
+
+        let res = Request::get(format!("http://{addr}/")).fetch().unwrap();
+        assert_eq!(res.body, b"Streamed body");
+    }
+
+    #[test]
+    fn test_proxy_bad_gateway() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let upstream = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let res = proxy(&Request::get("http://localhost/"), upstream);
+        assert_eq!(res.status, Status::BadGateway);
+    }
+}
@@ -1,21 +1,28 @@
 /*
- * Copyright (c) 2023-2025 Bastiaan van der Plaat
+ * Copyright (c) 2023-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
-use std::io::{BufRead, BufReader, Read, Write};
+use std::io::{self, BufRead, BufReader, Read, Write};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr, TcpStream};
 use std::str::{self, FromStr};
+use std::sync::Arc;
+use std::time::Instant;
 
 use url::Url;
 
-use crate::enums::{Method, Version};
+use crate::cookie::Cookies;
+use crate::enums::{Method, Status, Version};
+use crate::extensions::Extensions;
 use crate::header_map::HeaderMap;
+use crate::multipart::MultipartForm;
 use crate::response::Response;
+use crate::serve::Limits;
 use crate::KEEP_ALIVE_TIMEOUT;
 
 // MARK: Request
@@ -32,6 +39,10 @@ pub struct Request {
     pub headers: HeaderMap,
     /// Parameters (mostly added for small-router)
     pub params: HashMap<String, String>,
+    /// Shared state objects by type (mostly added for small-router)
+    pub states: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+    /// Per request values attached by layers
+    pub extensions: Extensions,
     /// Body
     pub body: Option<Vec<u8>>,
     /// Client address
@@ -46,6 +57,8 @@ impl Default for Request {
             method: Method::Get,
             headers: HeaderMap::new(),
             params: HashMap::new(),
+            states: HashMap::new(),
+            extensions: Extensions::new(),
             body: None,
             client_addr: (Ipv4Addr::LOCALHOST, 0).into(),
         }
@@ -140,9 +153,9 @@ impl Request {
         self
     }
 
-    /// Set header
+    /// Add header, keeps existing values with the same name
     pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
-        self.headers.insert(name.into(), value.into());
+        self.headers.append(name.into(), value.into());
         self
     }
 
@@ -180,38 +193,115 @@ impl Request {
         self
     }
 
+    /// Set multipart form data body
+    pub fn multipart(mut self, form: MultipartForm) -> Self {
+        self.headers
+            .insert("Content-Type".to_string(), form.content_type());
+        self.body = Some(form.into_body());
+        self
+    }
+
+    /// Get the parsed Content-Type header
+    pub fn content_type(&self) -> Option<mime::Mime> {
+        self.headers.content_type()
+    }
+
+    /// Get the cookies parsed from the Cookie headers
+    pub fn cookies(&self) -> Cookies {
+        Cookies::from_headers(&self.headers)
+    }
+
+    /// Get the percent-decoded segments of the URL path, empty segments are skipped.
+    ///
+    /// Returns `None` when a segment has invalid percent-encoding, is not UTF-8 or is `..`. The
+    /// server doesn't reject these requests, the handler should respond with `400 Bad Request`
+    /// (small-router already does). The raw path stays available as `url.path()`.
+    pub fn path_segments(&self) -> Option<Vec<String>> {
+        decode_path_segments(self.url.path())
+    }
+
+    /// Get the percent-decoded URL path, see [`Request::path_segments`]
+    pub fn decoded_path(&self) -> Option<String> {
+        self.path_segments()
+            .map(|segments| format!("/{}", segments.join("/")))
+    }
+
+    /// Parse the URL query string into a map of decoded fields, when a field occurs multiple
+    /// times the last value wins.
+    pub fn query(&self) -> HashMap<String, String> {
+        form_urlencoded::parse(self.url.query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect()
+    }
+
+    /// Parse the URL query string into a type (requires `form` feature)
+    ///
+    /// Returns `Status::BadRequest` if the query cannot be deserialized.
+    #[cfg(feature = "form")]
+    pub fn query_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, Status> {
+        serde_urlencoded::from_str(self.url.query().unwrap_or_default())
+            .map_err(|_| Status::BadRequest)
+    }
+
+    /// Parse an `application/x-www-form-urlencoded` body into a map of decoded fields,
+    /// when a field occurs multiple times the last value wins.
+    ///
+    /// Returns `Status::UnsupportedMediaType` if the Content-Type is not URL-encoded or has a non UTF-8 charset.
+    pub fn form(&self) -> Result<HashMap<String, String>, Status> {
+        Ok(form_urlencoded::parse(self.form_body()?)
+            .into_owned()
+            .collect())
+    }
+
+    /// Parse an `application/x-www-form-urlencoded` body into a type (requires `form` feature)
+    ///
+    /// Returns `Status::UnsupportedMediaType` if the Content-Type is not URL-encoded or has a non UTF-8 charset.
+    /// Returns `Status::BadRequest` if the body cannot be deserialized.
+    #[cfg(feature = "form")]
+    pub fn form_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, Status> {
+        serde_urlencoded::from_bytes(self.form_body()?).map_err(|_| Status::BadRequest)
+    }
+
+    fn form_body(&self) -> Result<&[u8], Status> {
+        let content_type = self.content_type().ok_or(Status::UnsupportedMediaType)?;
+        if content_type.essence_str() != "application/x-www-form-urlencoded"
+            || content_type
+                .charset()
+                .map_or(false, |charset| !charset.eq_ignore_ascii_case("utf-8"))
+        {
+            return Err(Status::UnsupportedMediaType);
+        }
+        Ok(self.body.as_deref().unwrap_or(&[]))
+    }
+
     /// Parse the request body based on the Content-Type header.
     ///
     /// Supports:
     /// - `application/json` (requires `json` feature)
     /// - `application/x-www-form-urlencoded` (requires `form` feature)
     ///
-    /// Returns `Status::UnsupportedMediaType` if the Content-Type is missing or unsupported.
+    /// Returns `Status::UnsupportedMediaType` if the Content-Type is missing, unsupported or has a non UTF-8 charset.
     /// Returns `Status::BadRequest` if the body cannot be deserialized.
     #[cfg(any(feature = "json", feature = "form"))]
-    pub fn parse_body<T: serde::de::DeserializeOwned>(&self) -> Result<T, crate::Status> {
-        let content_type = self
-            .headers
-            .get("Content-Type")
-            .unwrap_or_default()
-            .split(';')
-            .next()
-            .unwrap_or_default()
-            .trim()
-            .to_lowercase();
+    pub fn parse_body<T: serde::de::DeserializeOwned>(&self) -> Result<T, Status> {
+        let content_type = self.content_type().ok_or(Status::UnsupportedMediaType)?;
+        if content_type
+            .charset()
+            .map_or(false, |charset| !charset.eq_ignore_ascii_case("utf-8"))
+        {
+            return Err(Status::UnsupportedMediaType);
+        }
 
         let body = self.body.as_deref().unwrap_or(&[]);
 
-        match content_type.as_str() {
+        match content_type.essence_str() {
             #[cfg(feature = "json")]
-            "application/json" => {
-                serde_json::from_slice(body).map_err(|_| crate::Status::BadRequest)
-            }
+            "application/json" => serde_json::from_slice(body).map_err(|_| Status::BadRequest),
             #[cfg(feature = "form")]
             "application/x-www-form-urlencoded" => {
-                serde_urlencoded::from_bytes(body).map_err(|_| crate::Status::BadRequest)
+                serde_urlencoded::from_bytes(body).map_err(|_| Status::BadRequest)
             }
-            _ => Err(crate::Status::UnsupportedMediaType),
+            _ => Err(Status::UnsupportedMediaType),
         }
     }
 }
@@ -220,71 +310,94 @@ impl Request {
     pub(crate) fn read_from_stream(
         stream: &mut dyn Read,
         client_addr: SocketAddr,
+        limits: &Limits,
     ) -> Result<Request, InvalidRequestError> {
-        let mut reader = BufReader::new(stream);
+        let start = Instant::now();
+        let mut reader = BufReader::new(DeadlineReader {
+            inner: stream,
+            deadline: start + limits.header_timeout.min(limits.request_timeout),
+        });
 
-        // Read first line
-        let (method, path, version) = {
+        // Read request line and header lines
+        let mut header_size = 0;
+        let mut read_header_line = |reader: &mut BufReader<DeadlineReader>, what: &str| {
             let mut line = String::new();
             reader
                 .by_ref()
                 .take(crate::MAX_HEADER_LINE)
                 .read_line(&mut line)
-                .map_err(|_| InvalidRequestError("Can't read first line".to_string()))?;
+                .map_err(|err| InvalidRequestError::from_io(err, format!("Can't read {what}")))?;
+            header_size += line.len();
+            if header_size > limits.max_header_size {
+                return Err(InvalidRequestError::with_status(
+                    Status::RequestHeaderFieldsTooLarge,
+                    "Headers too large".to_string(),
+                ));
+            }
+            Ok(line)
+        };
+
+        // Read first line
+        let (method, path, version) = {
+            let line = read_header_line(&mut reader, "first line")?;
             let mut parts = line.split(' ');
             (
                 parts
                     .next()
-                    .ok_or(InvalidRequestError(
+                    .ok_or(InvalidRequestError::new(
                         "Can't read 1st part of first line".to_string(),
                     ))?
                     .trim()
                     .parse()
-                    .map_err(|_| InvalidRequestError("Can't parse method".to_string()))?,
+                    .map_err(|_| InvalidRequestError::new("Can't parse method".to_string()))?,
                 parts
                     .next()
-                    .ok_or(InvalidRequestError(
+                    .ok_or(InvalidRequestError::new(
                         "Can't read 2st part of first line".to_string(),
                     ))?
                     .trim()
                     .to_string(),
                 parts
                     .next()
-                    .ok_or(InvalidRequestError(
+                    .ok_or(InvalidRequestError::new(
                         "Can't read 3st part of first line".to_string(),
                     ))?
                     .trim()
                     .to_string()
                     .parse()
-                    .map_err(|_| InvalidRequestError("Can't parse HTTP version".to_string()))?,
+                    .map_err(|_| {
+                        InvalidRequestError::new("Can't parse HTTP version".to_string())
+                    })?,
             )
         };
 
         // Read headers
         let mut headers = HeaderMap::new();
         loop {
-            let mut line = String::new();
-            reader
-                .by_ref()
-                .take(crate::MAX_HEADER_LINE)
-                .read_line(&mut line)
-                .map_err(|_| InvalidRequestError("Can't read header line".to_string()))?;
+            let line = read_header_line(&mut reader, "header line")?;
             if line == "\r\n" {
                 break;
             }
             if headers.len() >= crate::MAX_HEADERS {
-                return Err(InvalidRequestError("Too many headers".to_string()));
+                return Err(InvalidRequestError::with_status(
+                    Status::RequestHeaderFieldsTooLarge,
+                    "Too many headers".to_string(),
+                ));
             }
-            let split = line
-                .find(':')
-                .ok_or(InvalidRequestError("Can't parse header line".to_string()))?;
-            headers.insert(
+            let split = line.find(':').ok_or(InvalidRequestError::new(
+                "Can't parse header line".to_string(),
+            ))?;
+            headers.append(
                 line[0..split].trim().to_string(),
                 line[split + 1..].trim().to_string(),
             );
         }
 
         // Read body
+        reader.get_mut().deadline = start + limits.request_timeout;
+        let too_large = || {
+            InvalidRequestError::with_status(Status::PayloadTooLarge, "Body too large".to_string())
+        };
         let mut body = None;
         let transfer_encoding = headers.get("Transfer-Encoding").map(|s| s.to_lowercase());
         if transfer_encoding.as_deref() == Some("chunked") {
@@ -295,44 +408,47 @@ impl Request {
                     .by_ref()
                     .take(crate::MAX_HEADER_LINE)
                     .read_line(&mut size_line)
-                    .map_err(|_| InvalidRequestError("Can't read chunk size".to_string()))?;
+                    .map_err(|err| {
+                        InvalidRequestError::from_io(err, "Can't read chunk size".to_string())
+                    })?;
                 // Strip optional chunk extensions (;...) and whitespace
                 let hex = size_line.split(';').next().unwrap_or("").trim();
                 let chunk_size = usize::from_str_radix(hex, 16)
-                    .map_err(|_| InvalidRequestError("Can't parse chunk size".to_string()))?;
+                    .map_err(|_| InvalidRequestError::new("Can't parse chunk size".to_string()))?;
                 if chunk_size == 0 {
                     break;
                 }
-                if chunks.len().saturating_add(chunk_size) > crate::MAX_REQUEST_BODY {
-                    return Err(InvalidRequestError("Chunked body too large".to_string()));
+                if chunks.len().saturating_add(chunk_size) > limits.max_body_size {
+                    return Err(too_large());
                 }
                 let prev_len = chunks.len();
                 chunks.resize(prev_len + chunk_size, 0);
-                reader
-                    .read_exact(&mut chunks[prev_len..])
-                    .map_err(|_| InvalidRequestError("Can't read chunk data".to_string()))?;
+                reader.read_exact(&mut chunks[prev_len..]).map_err(|err| {
+                    InvalidRequestError::from_io(err, "Can't read chunk data".to_string())
+                })?;
                 // Consume trailing CRLF after chunk data
                 let mut crlf = [0u8; 2];
-                reader.read_exact(&mut crlf).map_err(|_| {
-                    InvalidRequestError("Can't read chunk trailing CRLF".to_string())
+                reader.read_exact(&mut crlf).map_err(|err| {
+                    InvalidRequestError::from_io(err, "Can't read chunk trailing CRLF".to_string())
                 })?;
             }
             body = Some(chunks);
         } else if transfer_encoding.is_some() {
-            return Err(InvalidRequestError(
+            return Err(InvalidRequestError::new(
                 "Unsupported Transfer-Encoding".to_string(),
             ));
         } else if let Some(content_length) = headers.get("Content-Length") {
             let content_length = content_length
                 .parse()
-                .map_err(|_| InvalidRequestError("Can't parse Content-Length".to_string()))?;
-            if content_length > crate::MAX_REQUEST_BODY {
-                return Err(InvalidRequestError("Content-Length too large".to_string()));
+                .map_err(|_| InvalidRequestError::new("Can't parse Content-Length".to_string()))?;
+            if content_length > limits.max_body_size {
+                return Err(too_large());
             }
             if content_length > 0 {
                 let mut buffer = vec![0; content_length];
-                reader.read_exact(&mut buffer).map_err(|_| {
-                    InvalidRequestError(
+                reader.read_exact(&mut buffer).map_err(|err| {
+                    InvalidRequestError::from_io(
+                        err,
                         "Can't read Content-Length amount of bytes from stream".to_string(),
                     )
                 })?;
@@ -344,7 +460,7 @@ impl Request {
         let url = Url::from_str(&if version == Version::Http1_1 {
             format!(
                 "http://{}{}",
-                headers.get("Host").ok_or(InvalidRequestError(
+                headers.get("Host").ok_or(InvalidRequestError::new(
                     "HTTP version is 1.1 but Host header is not set".to_string()
                 ))?,
                 path
@@ -352,7 +468,7 @@ impl Request {
         } else {
             format!("http://localhost{path}")
         })
-        .map_err(|_| InvalidRequestError("Can't parse request url".to_string()))?;
+        .map_err(|_| InvalidRequestError::new("Can't parse request url".to_string()))?;
 
         Ok(Request {
             version,
@@ -360,6 +476,8 @@ impl Request {
             method,
             headers,
             params: HashMap::new(),
+            states: HashMap::new(),
+            extensions: Extensions::new(),
             body,
             client_addr,
         })
@@ -373,11 +491,11 @@ impl Request {
         let method = env::var("REQUEST_METHOD")
             .ok()
             .and_then(|m| m.parse().ok())
-            .ok_or(InvalidRequestError(
+            .ok_or(InvalidRequestError::new(
                 "Can't read REQUEST_METHOD from env".to_string(),
             ))?;
         let mut path = env::var("PATH_INFO")
-            .map_err(|_| InvalidRequestError("Can't read PATH_INFO from env".to_string()))?;
+            .map_err(|_| InvalidRequestError::new("Can't read PATH_INFO from env".to_string()))?;
         if path.is_empty() {
             path = "/".to_string();
         }
@@ -404,12 +522,14 @@ impl Request {
         if let Ok(content_length) = env::var("CONTENT_LENGTH") {
             if let Ok(content_length) = content_length.parse::<usize>() {
                 if content_length > crate::MAX_REQUEST_BODY {
-                    return Err(InvalidRequestError("Content-Length too large".to_string()));
+                    return Err(InvalidRequestError::new(
+                        "Content-Length too large".to_string(),
+                    ));
                 }
                 if content_length > 0 {
                     let mut buffer = vec![0; content_length];
-                    std::io::stdin().read_exact(&mut buffer).map_err(|_| {
-                        InvalidRequestError(
+                    io::stdin().read_exact(&mut buffer).map_err(|_| {
+                        InvalidRequestError::new(
                             "Can't read Content-Length amount of bytes from stdin".to_string(),
                         )
                     })?;
@@ -427,7 +547,7 @@ impl Request {
             format!("{remote_addr}:{remote_port}")
                 .parse()
                 .map_err(|_| {
-                    InvalidRequestError("Can't parse REMOTE_ADDR and REMOTE_PORT".to_string())
+                    InvalidRequestError::new("Can't parse REMOTE_ADDR and REMOTE_PORT".to_string())
                 })?
         } else {
             (Ipv4Addr::LOCALHOST, 0).into()
@@ -437,7 +557,7 @@ impl Request {
         let url = Url::from_str(&if version == Version::Http1_1 {
             format!(
                 "http://{}{}",
-                headers.get("Host").ok_or(InvalidRequestError(
+                headers.get("Host").ok_or(InvalidRequestError::new(
                     "HTTP version is 1.1 but Host header is not set".to_string()
                 ))?,
                 path
@@ -445,7 +565,7 @@ impl Request {
         } else {
             format!("http://localhost{path}")
         })
-        .map_err(|_| InvalidRequestError("Can't parse request url".to_string()))?;
+        .map_err(|_| InvalidRequestError::new("Can't parse request url".to_string()))?;
 
         Ok(Request {
             version,
@@ -453,11 +573,20 @@ impl Request {
             method,
             headers,
             params: HashMap::new(),
+            states: HashMap::new(),
+            extensions: Extensions::new(),
             body,
             client_addr,
         })
     }
 
+    /// Get shared state object by type
+    pub fn state<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.states
+            .get(&TypeId::of::<T>())
+            .and_then(|state| state.downcast_ref::<T>())
+    }
+
     /// Get client IP address, respecting X-Forwarded-For and X-Real-IP proxy headers
     pub fn ip(&self) -> IpAddr {
         self.headers
@@ -469,17 +598,25 @@ impl Request {
     }
 
     /// Write request to TCP stream
-    pub fn write_to_stream(mut self, stream: &mut dyn Write, keep_alive: bool) {
+    pub fn write_to_stream(self, stream: &mut dyn Write, keep_alive: bool) {
+        self.write_to_stream_with_target(stream, keep_alive, false);
+    }
+
+    /// Write request to TCP stream, with the absolute URL as request target when sent to a proxy
+    pub(crate) fn write_to_stream_with_target(
+        mut self,
+        stream: &mut dyn Write,
+        keep_alive: bool,
+        absolute_target: bool,
+    ) {
         // Finish headers
         let host = self.url.host().expect("No host in URL");
-        self.headers.insert(
-            "Host".to_string(),
-            if let Some(port) = self.url.port() {
-                format!("{}:{}", &host, port)
-            } else {
-                host.to_string()
-            },
-        );
+        let authority = if let Some(port) = self.url.port() {
+            format!("{}:{}", &host, port)
+        } else {
+            host.to_string()
+        };
+        self.headers.insert("Host".to_string(), authority.clone());
         self.headers.insert(
             "Content-Length".to_string(),
             if let Some(body) = &self.body {
@@ -489,7 +626,8 @@ impl Request {
             }
             .to_string(),
         );
-        if self.version == Version::Http1_1 {
+        // Keep an explicitly set Connection header, like the one of an upgrade request
+        if self.version == Version::Http1_1 && !self.headers.contains("Connection") {
             if keep_alive {
                 self.headers
                     .insert("Connection".to_string(), "keep-alive".to_string());
@@ -505,11 +643,19 @@ impl Request {
 
         // Write request
         let path = self.url.path();
-        let path = if let Some(query) = self.url.query() {
+        let mut path = if let Some(query) = self.url.query() {
             format!("{}?{}", &path, query)
         } else {
             path.to_string()
         };
+        if absolute_target {
+            // Proxies only know HTTP URLs, also for WebSocket upgrades
+            let scheme = match self.url.scheme() {
+                "ws" => "http",
+                scheme => scheme,
+            };
+            path = format!("{scheme}://{authority}{path}");
+        }
         _ = write!(stream, "{} {} HTTP/1.1\r\n", self.method, path);
         for (name, value) in &self.headers {
             let safe_name = name.replace(['\r', '\n'], "");
@@ -545,18 +691,88 @@ impl Request {
     }
 }
 
+// MARK: DeadlineReader
+struct DeadlineReader<'a> {
+    inner: &'a mut dyn Read,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if Instant::now() >= self.deadline {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "Request read deadline exceeded",
+            ));
+        }
+        self.inner.read(buf)
+    }
+}
+
 // MARK: InvalidRequestError
 #[derive(Debug)]
-pub(crate) struct InvalidRequestError(String);
+pub(crate) struct InvalidRequestError {
+    pub(crate) status: Status,
+    message: String,
+}
+
+impl InvalidRequestError {
+    pub(crate) const fn new(message: String) -> Self {
+        Self::with_status(Status::BadRequest, message)
+    }
+
+    pub(crate) const fn with_status(status: Status, message: String) -> Self {
+        Self { status, message }
+    }
+
+    fn from_io(err: io::Error, message: String) -> Self {
+        match err.kind() {
+            io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => {
+                Self::with_status(Status::RequestTimeout, message)
+            }
+            _ => Self::new(message),
+        }
+    }
+}
 
 impl Display for InvalidRequestError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "Invalid request: {}", self.0)
+        write!(f, "Invalid request: {}", self.message)
     }
 }
 
 impl Error for InvalidRequestError {}
 
+// MARK: Percent decoding
+pub(crate) fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = input.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+fn decode_path_segments(path: &str) -> Option<Vec<String>> {
+    let mut segments = Vec::new();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        let segment = percent_decode(segment)?;
+        if segment == ".." {
+            return None;
+        }
+        segments.push(segment);
+    }
+    Some(segments)
+}
+
 // MARK: FetchError
 #[derive(Debug)]
 pub struct FetchError;
@@ -575,9 +791,9 @@ mod test {
     use std::io::{Read, Write};
     use std::net::{Ipv4Addr, Shutdown, TcpListener};
     use std::thread;
+    use std::time::Duration;
 
     use super::*;
-    use crate::enums::Status;
 
     fn fetch_from_local_server(response: &'static [u8]) -> Response {
         let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
@@ -603,8 +819,12 @@ mod test {
     fn test_read_from_stream() {
         let raw_request = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
         let mut stream = &raw_request[..];
-        let request =
-            Request::read_from_stream(&mut stream, (Ipv4Addr::LOCALHOST, 12345).into()).unwrap();
+        let request = Request::read_from_stream(
+            &mut stream,
+            (Ipv4Addr::LOCALHOST, 12345).into(),
+            &Limits::default(),
+        )
+        .unwrap();
         assert_eq!(request.method, Method::Get);
         assert_eq!(request.url.to_string(), "http://localhost/");
         assert_eq!(request.version, Version::Http1_1);
@@ -616,8 +836,12 @@ mod test {
         let raw_request =
             b"POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: 13\r\n\r\nHello, world!";
         let mut stream = &raw_request[..];
-        let request =
-            Request::read_from_stream(&mut stream, (Ipv4Addr::LOCALHOST, 12345).into()).unwrap();
+        let request = Request::read_from_stream(
+            &mut stream,
+            (Ipv4Addr::LOCALHOST, 12345).into(),
+            &Limits::default(),
+        )
+        .unwrap();
         assert_eq!(request.method, Method::Post);
         assert_eq!(request.url.to_string(), "http://localhost/");
         assert_eq!(request.version, Version::Http1_1);
@@ -630,8 +854,12 @@ mod test {
         let raw_request =
             b"POST / HTTP/1.1\r\nhost: localhost\r\ncontent-Length: 13\r\n\r\nHello, world!";
         let mut stream = &raw_request[..];
-        let request =
-            Request::read_from_stream(&mut stream, (Ipv4Addr::LOCALHOST, 12345).into()).unwrap();
+        let request = Request::read_from_stream(
+            &mut stream,
+            (Ipv4Addr::LOCALHOST, 12345).into(),
+            &Limits::default(),
+        )
+        .unwrap();
         assert_eq!(request.method, Method::Post);
         assert_eq!(request.url.to_string(), "http://localhost/");
         assert_eq!(request.version, Version::Http1_1);
@@ -643,10 +871,167 @@ mod test {
     fn test_invalid_request_error() {
         let raw_request = b"INVALID REQUEST";
         let mut stream = &raw_request[..];
-        let result = Request::read_from_stream(&mut stream, (Ipv4Addr::LOCALHOST, 12345).into());
+        let result = Request::read_from_stream(
+            &mut stream,
+            (Ipv4Addr::LOCALHOST, 12345).into(),
+            &Limits::default(),
+        );
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_read_from_stream_limits() {
+        let client_addr = (Ipv4Addr::LOCALHOST, 12345).into();
+        let limits = Limits::new().max_header_size(64).max_body_size(8);
+
+        let raw_request =
+            b"POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: 13\r\n\r\nHello, world!";
+        let err = Request::read_from_stream(&mut &raw_request[..], client_addr, &limits)
+            .err()
+            .unwrap();
+        assert_eq!(err.status, Status::PayloadTooLarge);
+
+        let raw_request = b"POST / HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\nd\r\nHello, world!\r\n0\r\n\r\n";
+        let err = Request::read_from_stream(&mut &raw_request[..], client_addr, &limits)
+            .err()
+            .unwrap();
+        assert_eq!(err.status, Status::PayloadTooLarge);
+
+        let raw_request = format!(
+            "GET / HTTP/1.1\r\nHost: localhost\r\nX-Big: {}\r\n\r\n",
+            "a".repeat(64)
+        );
+        let err = Request::read_from_stream(&mut raw_request.as_bytes(), client_addr, &limits)
+            .err()
+            .unwrap();
+        assert_eq!(err.status, Status::RequestHeaderFieldsTooLarge);
+
+        let raw_request = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let limits = Limits::new().header_timeout(Duration::ZERO);
+        let err = Request::read_from_stream(&mut &raw_request[..], client_addr, &limits)
+            .err()
+            .unwrap();
+        assert_eq!(err.status, Status::RequestTimeout);
+    }
+
+    #[test]
+    fn test_form() {
+        let request = Request::post("http://localhost/")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body("name=Bassie+van+der&email=bassie%40example.com&empty=&name=Bastiaan");
+        let form = request.form().unwrap();
+        assert_eq!(form.len(), 3);
+        assert_eq!(form["name"], "Bastiaan");
+        assert_eq!(form["email"], "bassie@example.com");
+        assert_eq!(form["empty"], "");
+
+        let request = Request::post("http://localhost/").body("name=Bassie");
+        assert_eq!(request.form().err(), Some(Status::UnsupportedMediaType));
+        let request = request.header("Content-Type", "application/json");
+        assert_eq!(request.form().err(), Some(Status::UnsupportedMediaType));
+    }
+
+    #[test]
+    #[cfg(feature = "form")]
+    fn test_form_as() {
+        #[derive(serde::Deserialize)]
+        struct Login {
+            email: String,
+            remember: bool,
+        }
+
+        let request = Request::post("http://localhost/")
+            .urlencoded(&[("email", "bassie@example.com"), ("remember", "true")]);
+        let login = request.form_as::<Login>().unwrap();
+        assert_eq!(login.email, "bassie@example.com");
+        assert!(login.remember);
+
+        let request = Request::post("http://localhost/").urlencoded(&[("email", "bassie")]);
+        assert_eq!(request.form_as::<Login>().err(), Some(Status::BadRequest));
+    }
+
+    #[test]
+    fn test_read_from_stream_undecodable_path() {
+        // Paths that can't be decoded are left to the handler, see `Request::path_segments`
+        let client_addr = (Ipv4Addr::LOCALHOST, 12345).into();
+        for path in [
+            "/static/../secret",
+            "/static/%2e%2E/secret",
+            "/%ZZ",
+            "/%C3%28",
+        ] {
+            let raw_request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n");
+            let request = Request::read_from_stream(
+                &mut raw_request.as_bytes(),
+                client_addr,
+                &Limits::default(),
+            )
+            .unwrap();
+            assert!(request.path_segments().is_none());
+        }
+    }
+
+    #[test]
+    fn test_path_segments() {
+        let request = Request::get("http://localhost/hello/J%C3%BCrgen/a%2Fb/");
+        assert_eq!(
+            request.path_segments().unwrap(),
+            vec!["hello", "J\u{fc}rgen", "a/b"]
+        );
+        assert_eq!(request.decoded_path().unwrap(), "/hello/J\u{fc}rgen/a/b");
+        assert_eq!(request.url.path(), "/hello/J%C3%BCrgen/a%2Fb/");
+
+        assert!(Request::get("http://localhost/a/%2e%2e")
+            .path_segments()
+            .is_none());
+    }
+
+    #[test]
+    fn test_query() {
+        let request = Request::get("http://localhost/search?q=J%C3%BCrgen+Bassie&page=2&page=3");
+        let query = request.query();
+        assert_eq!(query["q"], "J\u{fc}rgen Bassie");
+        assert_eq!(query["page"], "3");
+        assert!(Request::get("http://localhost/").query().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "form")]
+    fn test_query_as() {
+        #[derive(serde::Deserialize)]
+        struct Search {
+            q: String,
+            page: u32,
+        }
+
+        let search = Request::get("http://localhost/?q=a%26b&page=2")
+            .query_as::<Search>()
+            .unwrap();
+        assert_eq!(search.q, "a&b");
+        assert_eq!(search.page, 2);
+        assert_eq!(
+            Request::get("http://localhost/?page=x")
+                .query_as::<Search>()
+                .err(),
+            Some(Status::BadRequest)
+        );
+    }
+
+    #[test]
+    fn test_content_type() {
+        let request = Request::post("http://localhost/")
+            .header("Content-Type", "Application/JSON; Charset=UTF-8");
+        let content_type = request.content_type().unwrap();
+        assert_eq!(content_type.essence_str(), "application/json");
+        assert_eq!(content_type.charset(), Some("UTF-8"));
+
+        assert!(Request::new().content_type().is_none());
+        assert!(Request::new()
+            .header("Content-Type", "invalid")
+            .content_type()
+            .is_none());
+    }
+
     #[test]
     fn test_write_to_stream() {
         let request = Request::get("http://localhost/").header("Host", "localhost");
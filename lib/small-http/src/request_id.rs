@@ -0,0 +1,97 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::fmt::{self, Display, Formatter};
+
+use uuid::Uuid;
+
+use crate::request::Request;
+use crate::response::Response;
+
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+// MARK: RequestId
+/// Id of a request, available in the request extensions when handled by [`request_id`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Get the request id as string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for RequestId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|byte| byte.is_ascii_graphic())
+}
+
+// MARK: Layer
+/// Handle a request with a request id, the id is taken from a valid X-Request-Id request header
+/// or generated as uuid v7. The id is inserted as [`RequestId`] in the request extensions before
+/// calling `handler` and added as X-Request-Id header to the response.
+///
+/// ```rs
+/// small_http::serve(listener, |req| {
+///     small_http::request_id(req, |req| {
+///         let id = req.extensions.get::<small_http::RequestId>();
+///         Response::with_body("Hello World!")
+///     })
+/// });
+/// ```
+pub fn request_id(req: &Request, handler: impl FnOnce(&Request) -> Response) -> Response {
+    let id = match req.headers.get(REQUEST_ID_HEADER) {
+        Some(id) if is_valid_request_id(id) => id.to_string(),
+        _ => Uuid::now_v7().to_string(),
+    };
+    req.extensions.insert(RequestId(id.clone()));
+
+    let mut res = handler(req);
+    #[cfg(feature = "log")]
+    log::info!("[{}] {} {} {}", id, req.method, req.url.path(), res.status);
+    res.extensions.insert(RequestId(id.clone()));
+    res.headers.insert(REQUEST_ID_HEADER.to_string(), id);
+    res
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_request_id_generated() {
+        let req = Request::get("http://localhost/");
+        let res = request_id(&req, |req| {
+            let id = req.extensions.get::<RequestId>().unwrap();
+            Response::with_body(id.to_string())
+        });
+        let header = res.headers.get("X-Request-Id").unwrap();
+        assert_eq!(header.len(), 36);
+        assert_eq!(res.body, header.as_bytes());
+        assert_eq!(res.extensions.get::<RequestId>().unwrap().as_str(), header);
+    }
+
+    #[test]
+    fn test_request_id_forwarded() {
+        let req = Request::get("http://localhost/").header("X-Request-Id", "abc-123");
+        let res = request_id(&req, |_| Response::new());
+        assert_eq!(res.headers.get("X-Request-Id"), Some("abc-123"));
+
+        let req = Request::get("http://localhost/").header("X-Request-Id", "invalid id");
+        let res = request_id(&req, |_| Response::new());
+        assert_ne!(res.headers.get("X-Request-Id"), Some("invalid id"));
+    }
+}
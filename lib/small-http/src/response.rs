@@ -1,17 +1,22 @@
 /*
- * Copyright (c) 2023-2025 Bastiaan van der Plaat
+ * Copyright (c) 2023-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
-use std::io::{BufRead, BufReader, Read, Write};
+use std::io::{self, BufRead, BufReader, Read, Write};
 use std::net::TcpStream;
+#[cfg(feature = "fs")]
+use std::path::Path;
 
-use crate::enums::{Status, Version};
+use crate::cookie::SetCookie;
+use crate::enums::{Method, Status, Version};
+use crate::extensions::Extensions;
 use crate::header_map::HeaderMap;
 use crate::request::Request;
+use crate::sse::EventStream;
 use crate::KEEP_ALIVE_TIMEOUT;
 
 // MARK: Response
@@ -24,9 +29,17 @@ pub struct Response {
     pub headers: HeaderMap,
     /// Body
     pub body: Vec<u8>,
+    /// Per response values attached by handlers and layers
+    pub extensions: Extensions,
+    pub(crate) body_stream: Option<BodyStream>,
     pub(crate) takeover: Option<Box<dyn FnOnce(TcpStream) + Send + 'static>>,
 }
 
+pub(crate) struct BodyStream {
+    reader: Box<dyn Read + Send + 'static>,
+    len: Option<u64>,
+}
+
 impl Response {
     /// Create new response
     pub fn new() -> Self {
@@ -52,9 +65,9 @@ impl Response {
         Self::default().header(name.into(), value.into())
     }
 
-    /// Set header
+    /// Add header, keeps existing values with the same name
     pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
-        self.headers.insert(name.into(), value.into());
+        self.headers.append(name.into(), value.into());
         self
     }
 
@@ -72,6 +85,41 @@ impl Response {
         self
     }
 
+    /// Create new response with a body that is streamed from a reader, when the length is
+    /// unknown the body is sent with chunked transfer encoding
+    pub fn with_body_stream(reader: impl Read + Send + 'static, len: Option<u64>) -> Self {
+        Self::default().body_stream(reader, len)
+    }
+
+    /// Set body that is streamed from a reader, when the length is unknown the body is sent
+    /// with chunked transfer encoding
+    pub fn body_stream(mut self, reader: impl Read + Send + 'static, len: Option<u64>) -> Self {
+        self.body_stream = Some(BodyStream {
+            reader: Box::new(reader),
+            len,
+        });
+        self
+    }
+
+    /// Create new response that streams a file, sets the `Content-Type` and `Last-Modified`
+    /// headers
+    #[cfg(feature = "fs")]
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)?;
+        let metadata = file.metadata()?;
+        let mut res = Self::with_header(
+            "Content-Type",
+            mime_guess::from_path(path)
+                .first_or_octet_stream()
+                .to_string(),
+        );
+        if let Ok(modified) = metadata.modified() {
+            res = res.header("Last-Modified", crate::date::http_date(modified));
+        }
+        Ok(res.body_stream(file, Some(metadata.len())))
+    }
+
     /// Create new response with json body
     #[cfg(feature = "json")]
     pub fn with_json(value: impl serde::Serialize) -> Self {
@@ -89,18 +137,63 @@ impl Response {
         self
     }
 
-    /// Create new response with redirect header
+    /// Create new `204 No Content` response
+    pub fn no_content() -> Self {
+        Self::with_status(Status::NoContent)
+    }
+
+    /// Create new problem details response with json body as described in RFC 9457
+    #[cfg(feature = "json")]
+    pub fn problem(status: Status, title: impl Into<String>, detail: impl Into<String>) -> Self {
+        let mut res = Self::with_status(status).json(serde_json::json!({
+            "type": "about:blank",
+            "title": title.into(),
+            "status": status as i32,
+            "detail": detail.into(),
+        }));
+        res.headers.insert(
+            "Content-Type".to_string(),
+            "application/problem+json".to_string(),
+        );
+        res
+    }
+
+    /// Create new `302 Found` redirect response
     pub fn with_redirect(location: impl Into<String>) -> Self {
         Self::default().redirect(location.into())
     }
 
-    /// Set redirect header
+    /// Set `302 Found` status and redirect header
     pub fn redirect(mut self, location: impl Into<String>) -> Self {
-        self.status = Status::TemporaryRedirect;
+        self.status = Status::Found;
+        self.headers.insert("Location".to_string(), location.into());
+        self
+    }
+
+    /// Create new `301 Moved Permanently` redirect response
+    pub fn with_redirect_permanent(location: impl Into<String>) -> Self {
+        Self::default().redirect_permanent(location.into())
+    }
+
+    /// Set `301 Moved Permanently` status and redirect header
+    pub fn redirect_permanent(mut self, location: impl Into<String>) -> Self {
+        self.status = Status::MovedPermanently;
         self.headers.insert("Location".to_string(), location.into());
         self
     }
 
+    /// Add Set-Cookie header
+    pub fn set_cookie(mut self, cookie: SetCookie) -> Self {
+        self.headers
+            .append("Set-Cookie".to_string(), cookie.to_string());
+        self
+    }
+
+    /// Create new Server-Sent Events response, events are pushed with the returned handle
+    pub fn sse() -> (Self, EventStream) {
+        crate::sse::sse()
+    }
+
     /// Set takeover function
     pub fn takeover(mut self, f: impl FnOnce(TcpStream) + Send + 'static) -> Self {
         self.takeover = Some(Box::new(f));
@@ -116,39 +209,7 @@ impl Response {
     /// Read response from stream
     pub fn read_from_stream(stream: &mut dyn Read) -> Result<Self, InvalidResponseError> {
         let mut reader = BufReader::new(stream);
-
-        // Read first line
-        let mut res = {
-            let mut line = String::new();
-            reader
-                .read_line(&mut line)
-                .map_err(|_| InvalidResponseError)?;
-            let mut parts = line.splitn(3, ' ');
-            let _http_version = parts.next().ok_or(InvalidResponseError)?;
-            let status_code = parts
-                .next()
-                .ok_or(InvalidResponseError)?
-                .parse::<i32>()
-                .map_err(|_| InvalidResponseError)?;
-            Response::default()
-                .status(Status::try_from(status_code).map_err(|_| InvalidResponseError)?)
-        };
-
-        // Read headers
-        loop {
-            let mut line = String::new();
-            reader
-                .read_line(&mut line)
-                .map_err(|_| InvalidResponseError)?;
-            if line == "\r\n" {
-                break;
-            }
-            let split = line.find(':').ok_or(InvalidResponseError)?;
-            res.headers.insert(
-                line[0..split].trim().to_string(),
-                line[split + 1..].trim().to_string(),
-            );
-        }
+        let mut res = Self::read_head(&mut reader)?;
 
         // Read body
         if let Some(transfer_encoding) = res.headers.get("Transfer-Encoding") {
@@ -206,12 +267,52 @@ impl Response {
         Ok(res)
     }
 
+    /// Read status line and headers, leaves the body in the reader
+    pub(crate) fn read_head(reader: &mut dyn BufRead) -> Result<Self, InvalidResponseError> {
+        // Read first line
+        let mut res = {
+            let mut line = String::new();
+            reader
+                .read_line(&mut line)
+                .map_err(|_| InvalidResponseError)?;
+            let mut parts = line.splitn(3, ' ');
+            let _http_version = parts.next().ok_or(InvalidResponseError)?;
+            let status_code = parts
+                .next()
+                .ok_or(InvalidResponseError)?
+                .parse::<i32>()
+                .map_err(|_| InvalidResponseError)?;
+            Response::default()
+                .status(Status::try_from(status_code).map_err(|_| InvalidResponseError)?)
+        };
+
+        // Read headers
+        loop {
+            let mut line = String::new();
+            reader
+                .read_line(&mut line)
+                .map_err(|_| InvalidResponseError)?;
+            if line == "\r\n" {
+                break;
+            }
+            let split = line.find(':').ok_or(InvalidResponseError)?;
+            res.headers.append(
+                line[0..split].trim().to_string(),
+                line[split + 1..].trim().to_string(),
+            );
+        }
+
+        Ok(res)
+    }
+
+    /// Write response to stream, returns false when the body couldn't be written completely,
+    /// the connection must then be closed because the client can't find the end of the body
     pub(crate) fn write_to_stream(
         &mut self,
         stream: &mut dyn Write,
         req: &Request,
         keep_alive: bool,
-    ) {
+    ) -> bool {
         self.finish_headers(req, keep_alive);
 
         _ = write!(stream, "{} {}\r\n", req.version, self.status);
@@ -222,13 +323,19 @@ impl Response {
         }
         _ = write!(stream, "\r\n");
         // HEAD responses must not include a message body
-        if req.method != crate::enums::Method::Head {
-            _ = stream.write_all(&self.body);
+        if req.method != Method::Head {
+            if let Some(body_stream) = self.body_stream.take() {
+                let chunked = body_stream.len.is_none() && req.version == Version::Http1_1;
+                return body_stream.write_to(stream, chunked).is_ok();
+            } else {
+                return stream.write_all(&self.body).is_ok();
+            }
         }
+        true
     }
 
     #[cfg(feature = "cgi")]
-    pub(crate) fn write_to_cgi_stdout(&self, stdout: &mut dyn Write) {
+    pub(crate) fn write_to_cgi_stdout(&mut self, stdout: &mut dyn Write) {
         _ = writeln!(stdout, "Status: {}", self.status);
         for (name, value) in &self.headers {
             let safe_name = name.replace(['\r', '\n'], "");
@@ -236,15 +343,41 @@ impl Response {
             _ = writeln!(stdout, "{safe_name}: {safe_value}");
         }
         _ = writeln!(stdout);
-        _ = stdout.write_all(&self.body);
+        if let Some(body_stream) = self.body_stream.take() {
+            _ = body_stream.write_to(stdout, false);
+        } else {
+            _ = stdout.write_all(&self.body);
+        }
     }
 
     fn finish_headers(&mut self, req: &Request, keep_alive: bool) {
         #[cfg(feature = "date")]
         self.headers
             .insert("Date".to_string(), chrono::Utc::now().to_rfc2822());
-        self.headers
-            .insert("Content-Length".to_string(), self.body.len().to_string());
+        // Takeover responses don't have a body, their connection is used for other data
+        if self.takeover.is_none() {
+            match self.body_stream.as_ref().map(|body_stream| body_stream.len) {
+                Some(Some(len)) => {
+                    self.headers
+                        .insert("Content-Length".to_string(), len.to_string());
+                }
+                // HTTP/1.0 connections are closed after the response, which ends the body
+                Some(None) => {
+                    if req.version == Version::Http1_1 {
+                        self.headers
+                            .insert("Transfer-Encoding".to_string(), "chunked".to_string());
+                    }
+                }
+                // HEAD responses keep the Content-Length of the GET response they stand in for
+                None if req.method == Method::Head
+                    && self.body.is_empty()
+                    && self.headers.get("Content-Length").is_some() => {}
+                None => {
+                    self.headers
+                        .insert("Content-Length".to_string(), self.body.len().to_string());
+                }
+            }
+        }
         if req.version == Version::Http1_1 {
             if keep_alive && req.headers.get("Connection") != Some("close") {
                 if self.headers.get("Connection").is_none() {
@@ -263,6 +396,37 @@ impl Response {
     }
 }
 
+// MARK: BodyStream
+impl BodyStream {
+    fn write_to(self, stream: &mut dyn Write, chunked: bool) -> io::Result<()> {
+        let mut reader = self.reader;
+        if !chunked {
+            return match self.len {
+                // A reader that ends early leaves the client waiting for the missing bytes
+                Some(len) => match io::copy(&mut reader.take(len), stream)? {
+                    copied if copied == len => Ok(()),
+                    _ => Err(io::ErrorKind::UnexpectedEof.into()),
+                },
+                None => io::copy(&mut reader, stream).map(|_| ()),
+            };
+        }
+
+        let mut buffer = [0; 16 * 1024];
+        loop {
+            let size = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(size) => size,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            write!(stream, "{size:x}\r\n")?;
+            stream.write_all(&buffer[..size])?;
+            stream.write_all(b"\r\n")?;
+        }
+        stream.write_all(b"0\r\n\r\n")
+    }
+}
+
 // MARK: InvalidResponseError
 /// Invalid response error
 #[derive(Debug)]
@@ -328,6 +492,38 @@ mod test {
         assert_eq!(response.body, b"Bastiaan");
     }
 
+    #[test]
+    fn test_redirect() {
+        let res = Response::with_redirect("/login");
+        assert_eq!(res.status, Status::Found);
+        assert_eq!(res.headers.get("Location"), Some("/login"));
+
+        let res = Response::new().redirect_permanent("https://example.com/");
+        assert_eq!(res.status, Status::MovedPermanently);
+        assert_eq!(res.headers.get("Location"), Some("https://example.com/"));
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_problem() {
+        let res = Response::problem(Status::NotFound, "Not Found", "Person doesn't exist");
+        assert_eq!(res.status, Status::NotFound);
+        assert_eq!(
+            res.headers.get("Content-Type"),
+            Some("application/problem+json")
+        );
+        let body: serde_json::Value = res.into_json().unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "type": "about:blank",
+                "title": "Not Found",
+                "status": 404,
+                "detail": "Person doesn't exist",
+            })
+        );
+    }
+
     #[test]
     #[cfg(feature = "json")]
     fn test_parse_response_with_json() {
@@ -383,6 +579,100 @@ mod test {
         assert!(response_text.contains("\r\n\r\n"));
     }
 
+    #[test]
+    fn test_write_head_response() {
+        let request = Request {
+            method: Method::Head,
+            version: Version::Http1_1,
+            ..Default::default()
+        };
+
+        let mut response = Response::with_status(Status::Ok).body("Hello, world!");
+        let mut response_stream = Vec::new();
+        response.write_to_stream(&mut response_stream, &request, true);
+        let response_text = String::from_utf8(response_stream).unwrap();
+        assert!(response_text.contains("Content-Length: 13"));
+        assert!(response_text.ends_with("\r\n\r\n"));
+
+        let mut response = Response::with_status(Status::Ok).header("Content-Length", "13");
+        let mut response_stream = Vec::new();
+        response.write_to_stream(&mut response_stream, &request, true);
+        let response_text = String::from_utf8(response_stream).unwrap();
+        assert!(response_text.contains("Content-Length: 13"));
+        assert!(response_text.ends_with("\r\n\r\n"));
+    }
+
+    #[test]
+    fn test_write_response_with_body_stream() {
+        let mut response = Response::with_body_stream(&b"Hello, world!"[..], Some(13));
+        let mut response_stream = Vec::new();
+        let request = Request {
+            version: Version::Http1_1,
+            ..Default::default()
+        };
+        response.write_to_stream(&mut response_stream, &request, true);
+
+        let response_text = String::from_utf8(response_stream).unwrap();
+        assert!(response_text.contains("Content-Length: 13"));
+        assert!(!response_text.contains("Transfer-Encoding"));
+        assert!(response_text.ends_with("\r\n\r\nHello, world!"));
+    }
+
+    #[test]
+    fn test_write_response_with_short_body_stream() {
+        let mut response = Response::with_body_stream(&b"Hello"[..], Some(13));
+        let mut response_stream = Vec::new();
+        let request = Request {
+            version: Version::Http1_1,
+            ..Default::default()
+        };
+        assert!(!response.write_to_stream(&mut response_stream, &request, true));
+    }
+
+    #[test]
+    fn test_write_response_with_chunked_body_stream() {
+        let mut response = Response::with_body_stream(&b"Bastiaan"[..], None);
+        let mut response_stream = Vec::new();
+        let request = Request {
+            version: Version::Http1_1,
+            ..Default::default()
+        };
+        response.write_to_stream(&mut response_stream, &request, true);
+
+        let mut response_stream = response_stream.as_slice();
+        let response = Response::read_from_stream(&mut response_stream).unwrap();
+        assert_eq!(
+            response.headers.get("Transfer-Encoding").unwrap(),
+            "chunked"
+        );
+        assert!(response.headers.get("Content-Length").is_none());
+        assert_eq!(response.body, b"Bastiaan");
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_response_from_file() {
+        let path =
+            std::env::temp_dir().join(format!("small-http-from-file-{}.txt", std::process::id()));
+        std::fs::write(&path, "Hello, world!").unwrap();
+        let mut response = Response::from_file(&path).unwrap();
+        assert_eq!(response.headers.get("Content-Type"), Some("text/plain"));
+        assert!(response.headers.get("Last-Modified").is_some());
+
+        let mut response_stream = Vec::new();
+        let request = Request {
+            version: Version::Http1_1,
+            ..Default::default()
+        };
+        response.write_to_stream(&mut response_stream, &request, true);
+        let response_text = String::from_utf8(response_stream).unwrap();
+        assert!(response_text.contains("Content-Length: 13"));
+        assert!(response_text.ends_with("\r\n\r\nHello, world!"));
+        std::fs::remove_file(path).unwrap();
+
+        assert!(Response::from_file("missing.txt").is_err());
+    }
+
     #[test]
     #[cfg(feature = "json")]
     fn test_write_response_with_json() {
@@ -11,10 +11,72 @@ use std::time::Duration;
 use crate::request::Request;
 use crate::response::Response;
 
+// MARK: Limits
+/// Request read limits used by the HTTP server
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub(crate) max_header_size: usize,
+    pub(crate) max_body_size: usize,
+    pub(crate) header_timeout: Duration,
+    pub(crate) request_timeout: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_header_size: 64 * 1024,
+            max_body_size: crate::MAX_REQUEST_BODY,
+            header_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+impl Limits {
+    /// Create default limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set max size of the request line and headers, larger requests get a 431 response
+    pub const fn max_header_size(mut self, max_header_size: usize) -> Self {
+        self.max_header_size = max_header_size;
+        self
+    }
+
+    /// Set max body size, larger requests get a 413 response
+    pub const fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Set max time to read the request line and headers, slower requests get a 408 response
+    pub const fn header_timeout(mut self, header_timeout: Duration) -> Self {
+        self.header_timeout = header_timeout;
+        self
+    }
+
+    /// Set max time to read the whole request, slower requests get a 408 response
+    pub const fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+}
+
+// MARK: Serve
 /// Start HTTP server single threaded
 pub fn serve_single_threaded(
     listener: TcpListener,
     handler: impl Fn(&Request) -> Response + 'static,
+) {
+    serve_single_threaded_with_limits(listener, Limits::default(), handler);
+}
+
+/// Start HTTP server single threaded with custom request limits
+pub fn serve_single_threaded_with_limits(
+    listener: TcpListener,
+    limits: Limits,
+    handler: impl Fn(&Request) -> Response + 'static,
 ) {
     // Listen for incoming tcp clients
     for stream in listener.incoming() {
@@ -27,7 +89,7 @@ pub fn serve_single_threaded(
         let client_addr = stream
             .peer_addr()
             .expect("Can't get tcp stream client addr");
-        match Request::read_from_stream(&mut stream, client_addr) {
+        match Request::read_from_stream(&mut stream, client_addr, &limits) {
             Ok(request) => {
                 // Handle request and write response
                 let mut response = handler(&request);
@@ -40,7 +102,7 @@ pub fn serve_single_threaded(
             }
             Err(err) => {
                 // Invalid request received
-                _ = write!(stream, "HTTP/1.0 400 Bad Request\r\n\r\n");
+                _ = write!(stream, "HTTP/1.0 {}\r\n\r\n", err.status);
                 cfg_select! {
                     feature = "log" => { log::error!("Invalid http request: {err:?}"); }
                     _ => { eprintln!("[small-http] Error invalid http request: {err:?}"); }
@@ -55,6 +117,16 @@ pub fn serve_single_threaded(
 pub fn serve(
     listener: TcpListener,
     handler: impl Fn(&Request) -> Response + Clone + Send + 'static,
+) {
+    serve_with_limits(listener, Limits::default(), handler);
+}
+
+/// Start HTTP server with custom request limits
+#[cfg(feature = "multi-threaded")]
+pub fn serve_with_limits(
+    listener: TcpListener,
+    limits: Limits,
+    handler: impl Fn(&Request) -> Response + Clone + Send + 'static,
 ) {
     // Create thread pool with workers
     // FIXME: The current thread pool doesn't spawn extra threads so http server could be overwhelmed with long running requests.
@@ -94,11 +166,11 @@ pub fn serve(
             let client_addr = stream
                 .peer_addr()
                 .expect("Can't get tcp stream client addr");
-            match Request::read_from_stream(&mut stream, client_addr) {
+            match Request::read_from_stream(&mut stream, client_addr, &limits) {
                 Ok(request) => {
                     // Handle request and write response
                     let mut response = handler(&request);
-                    response.write_to_stream(&mut stream, &request, true);
+                    let is_complete = response.write_to_stream(&mut stream, &request, true);
 
                     // If the response has a takeover function, start thread and move tcp stream
                     if let Some(takeover) = response.takeover.take() {
@@ -106,8 +178,9 @@ pub fn serve(
                         return;
                     }
 
-                    // Close connection if HTTP/1.0 or Connection: close
-                    if request.version == crate::enums::Version::Http1_0
+                    // Close connection if HTTP/1.0, Connection: close or the body is incomplete
+                    if !is_complete
+                        || request.version == crate::enums::Version::Http1_0
                         || request.headers.get("Connection") == Some("close")
                     {
                         return;
@@ -115,7 +188,7 @@ pub fn serve(
                 }
                 Err(err) => {
                     // Invalid request received
-                    _ = write!(stream, "HTTP/1.0 400 Bad Request\r\n\r\n");
+                    _ = write!(stream, "HTTP/1.0 {}\r\n\r\n", err.status);
                     cfg_select! {
                         feature = "log" => { log::error!("Invalid http request: {err:?}"); }
                         _ => { eprintln!("[small-http] Error invalid http request: {err:?}"); }
@@ -138,7 +211,7 @@ pub fn serve_cgi(handler: impl Fn(&Request) -> Response) {
             return;
         }
     };
-    let response = handler(&request);
+    let mut response = handler(&request);
     let mut stdout = std::io::stdout().lock();
     response.write_to_cgi_stdout(&mut stdout);
     _ = stdout.flush();
@@ -269,4 +342,29 @@ mod test {
             assert_eq!(res.status, expected_status);
         }
     }
+
+    #[test]
+    fn test_serve_slow_request_timeout() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            serve_single_threaded_with_limits(
+                listener,
+                Limits::new().header_timeout(Duration::from_millis(200)),
+                |_req| Response::with_status(Status::Ok),
+            );
+        });
+
+        // Send headers slower than the header timeout allows
+        let mut stream = TcpStream::connect(addr).unwrap();
+        for line in ["GET / HTTP/1.1\r\n", "Host: localhost\r\n", "X-Slow: 1\r\n"] {
+            stream.write_all(line.as_bytes()).unwrap();
+            thread::sleep(Duration::from_millis(150));
+        }
+        _ = stream.write_all(b"\r\n");
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).unwrap();
+        assert!(response.starts_with(b"HTTP/1.0 408 Request Timeout"));
+    }
 }
@@ -0,0 +1,269 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::enums::Status;
+use crate::response::Response;
+
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// MARK: Event
+/// Server-Sent Event
+#[derive(Default, Clone)]
+pub struct Event {
+    event: Option<String>,
+    data: String,
+    id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl Event {
+    /// Create new event with data
+    pub fn new(data: impl Into<String>) -> Self {
+        Self {
+            data: data.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set event name
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.event = Some(event.into());
+        self
+    }
+
+    /// Set event id
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set reconnection time
+    pub const fn retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    fn write_to(&self, buffer: &mut Vec<u8>) {
+        if let Some(event) = &self.event {
+            _ = writeln!(buffer, "event: {}", event.replace(['\r', '\n'], ""));
+        }
+        if let Some(id) = &self.id {
+            _ = writeln!(buffer, "id: {}", id.replace(['\r', '\n', '\0'], ""));
+        }
+        if let Some(retry) = self.retry {
+            _ = writeln!(buffer, "retry: {}", retry.as_millis());
+        }
+        for line in self.data.split('\n') {
+            _ = writeln!(buffer, "data: {}", line.trim_end_matches('\r'));
+        }
+        buffer.push(b'\n');
+    }
+}
+
+// MARK: EventStream
+#[derive(Default)]
+struct EventStreamState {
+    stream: Option<TcpStream>,
+    pending: Vec<u8>,
+    closed: bool,
+}
+
+/// Handle to push Server-Sent Events to a client, can be cloned and kept after the handler
+/// returned
+#[derive(Clone, Default)]
+pub struct EventStream {
+    state: Arc<Mutex<EventStreamState>>,
+}
+
+impl EventStream {
+    /// Send event, events sent before the client is connected are buffered
+    pub fn send(&self, event: Event) -> io::Result<()> {
+        let mut buffer = Vec::new();
+        event.write_to(&mut buffer);
+        self.write(&buffer)
+    }
+
+    /// Send event with only data
+    pub fn send_data(&self, data: impl Into<String>) -> io::Result<()> {
+        self.send(Event::new(data))
+    }
+
+    /// Returns whether the client has disconnected
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().expect("Can't lock event stream").closed
+    }
+
+    fn write(&self, data: &[u8]) -> io::Result<()> {
+        let mut state = self.state.lock().expect("Can't lock event stream");
+        if state.closed {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "Event stream closed",
+            ));
+        }
+        let result = match &mut state.stream {
+            Some(stream) => stream.write_all(data).and_then(|_| stream.flush()),
+            None => {
+                state.pending.extend_from_slice(data);
+                Ok(())
+            }
+        };
+        if result.is_err() {
+            state.closed = true;
+        }
+        result
+    }
+
+    fn run(self, stream: TcpStream) {
+        // Attach stream and flush buffered events
+        {
+            let mut state = self.state.lock().expect("Can't lock event stream");
+            let pending = std::mem::take(&mut state.pending);
+            let attached = stream.try_clone().and_then(|mut attached| {
+                attached.write_all(&pending)?;
+                Ok(attached)
+            });
+            match attached {
+                Ok(attached) => state.stream = Some(attached),
+                Err(_) => {
+                    state.closed = true;
+                    return;
+                }
+            }
+        }
+
+        // Watch for client disconnects and send keep-alive comments
+        let mut stream = stream;
+        if stream.set_read_timeout(Some(POLL_INTERVAL)).is_err() {
+            self.close();
+            return;
+        }
+        let mut last_keep_alive = Instant::now();
+        let mut buffer = [0; 512];
+        loop {
+            // Stop when all handles are dropped
+            if Arc::strong_count(&self.state) == 1 {
+                self.close();
+                return;
+            }
+            match stream.read(&mut buffer) {
+                Ok(0) => {
+                    self.close();
+                    return;
+                }
+                Ok(_) => {}
+                Err(err)
+                    if err.kind() == io::ErrorKind::WouldBlock
+                        || err.kind() == io::ErrorKind::TimedOut => {}
+                Err(_) => {
+                    self.close();
+                    return;
+                }
+            }
+            if last_keep_alive.elapsed() >= KEEP_ALIVE_INTERVAL {
+                if self.write(b": keep-alive\n\n").is_err() {
+                    return;
+                }
+                last_keep_alive = Instant::now();
+            }
+        }
+    }
+
+    fn close(&self) {
+        let mut state = self.state.lock().expect("Can't lock event stream");
+        state.closed = true;
+        if let Some(stream) = state.stream.take() {
+            _ = stream.shutdown(std::net::Shutdown::Both);
+        }
+    }
+}
+
+pub(crate) fn sse() -> (Response, EventStream) {
+    let events = EventStream::default();
+    let takeover_events = events.clone();
+    let res = Response::with_status(Status::Ok)
+        .header("Content-Type", "text/event-stream")
+        .header("Cache-Control", "no-cache")
+        .header("Connection", "close")
+        .takeover(move |stream| takeover_events.run(stream));
+    (res, events)
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use std::io::{BufRead, BufReader};
+    use std::net::{Ipv4Addr, TcpListener};
+    use std::sync::mpsc;
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_event_format() {
+        let mut buffer = Vec::new();
+        Event::new("line 1\nline 2")
+            .event("update")
+            .id("1")
+            .retry(Duration::from_secs(3))
+            .write_to(&mut buffer);
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "event: update\nid: 1\nretry: 3000\ndata: line 1\ndata: line 2\n\n"
+        );
+    }
+
+    #[test]
+    fn test_sse_stream() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            crate::serve_single_threaded(listener, move |_| {
+                let (res, events) = Response::sse();
+                events.send(Event::new("buffered").event("hello")).unwrap();
+                tx.send(events).unwrap();
+                res
+            });
+        });
+
+        let mut stream = TcpStream::connect(server_addr).unwrap();
+        stream
+            .write_all(b"GET /events HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let events = rx.recv().unwrap();
+        events.send_data("live").unwrap();
+
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut lines = Vec::new();
+        while lines.last().map(String::as_str) != Some("data: live") {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            lines.push(line.trim_end().to_string());
+        }
+        assert_eq!(lines[0], "HTTP/1.1 200 OK");
+        assert!(lines.contains(&"Content-Type: text/event-stream".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("Content-Length")));
+        assert!(lines.contains(&"event: hello".to_string()));
+        assert!(lines.contains(&"data: buffered".to_string()));
+
+        // Closing the connection is detected
+        drop(reader);
+        stream.shutdown(std::net::Shutdown::Both).unwrap();
+        let start = Instant::now();
+        while !events.is_closed() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(events.send_data("gone").is_err());
+    }
+}
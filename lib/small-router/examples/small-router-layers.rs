@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -15,7 +15,7 @@ use small_router::RouterBuilder;
 /// Pre-layer that processes CORS requests
 fn cors_pre_layer(req: &Request, _: &mut ()) -> Option<Result<Response>> {
     if req.method == Method::Options && req.headers.get("Access-Control-Request-Method").is_some() {
-        Some(Ok(Response::with_status(Status::NoContent)
+        Some(Ok(Response::no_content()
             .header("Access-Control-Allow-Origin", "*")
             .header(
                 "Access-Control-Allow-Methods",
@@ -0,0 +1,48 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! A simple small-router example with multiple typed shared states
+
+use std::net::{Ipv4Addr, TcpListener};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+use small_http::{Request, Response};
+use small_router::RouterBuilder;
+
+struct Config {
+    app_name: String,
+}
+
+struct Stats {
+    visits: AtomicU64,
+}
+
+fn home(req: &Request, _ctx: &()) -> Result<Response> {
+    let config = req.state::<Config>().context("No config")?;
+    let stats = req.state::<Stats>().context("No stats")?;
+    let visits = stats.visits.fetch_add(1, Ordering::Relaxed) + 1;
+    Ok(Response::with_body(format!(
+        "Welcome to {}, visit {visits}",
+        config.app_name
+    )))
+}
+
+fn main() {
+    let router = RouterBuilder::new()
+        .state(Config {
+            app_name: "State Example".to_string(),
+        })
+        .state(Stats {
+            visits: AtomicU64::new(0),
+        })
+        .get("/", home)
+        .build();
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 8080))
+        .unwrap_or_else(|_| panic!("Can't bind to port"));
+    small_http::serve(listener, move |req| router.handle(req));
+}
@@ -0,0 +1,456 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! ETag generation and in-memory response caching
+//!
+//! Register the [`etag_post_layer`] to add ETags to responses and answer `If-None-Match`
+//! requests with `304 Not Modified`. To also cache whole GET responses, add a
+//! [`ResponseCache`] to the router context, implement [`CacheContext`] and register the
+//! [`cache_pre_layer`] and [`cache_post_layer`] layers before the routes that may be cached:
+//!
+//! ```rs
+//! #[derive(Clone)]
+//! struct Context {
+//!     cache: ResponseCache,
+//! }
+//!
+//! impl CacheContext for Context {
+//!     fn response_cache(&self) -> &ResponseCache {
+//!         &self.cache
+//!     }
+//! }
+//!
+//! let router = RouterBuilder::with(Context {
+//!     cache: ResponseCache::new(100, Duration::from_secs(60)),
+//! })
+//! .pre_layer(cache_pre_layer)
+//! .post_layer(cache_post_layer)
+//! .post_layer(etag_post_layer)
+//! .build();
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use small_http::{HeaderMap, Method, Request, Response, Status};
+
+// MARK: ETag
+/// Generate a strong ETag for a body, a quoted FNV-1a hash
+pub fn etag(body: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in body {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("\"{:016x}\"", hash)
+}
+
+/// Post layer that adds an ETag to successful GET and HEAD responses and answers matching
+/// `If-None-Match` requests with `304 Not Modified`
+pub fn etag_post_layer<T>(req: &Request, _ctx: &T, mut res: Response) -> Result<Response> {
+    // Streamed and empty responses are skipped, their body is not known here
+    if !matches!(req.method, Method::Get | Method::Head)
+        || res.status != Status::Ok
+        || res.body.is_empty()
+    {
+        return Ok(res);
+    }
+
+    let etag = match res.headers.get("ETag") {
+        Some(etag) => etag.to_string(),
+        None => etag(&res.body),
+    };
+    let not_modified = req
+        .headers
+        .get("If-None-Match")
+        .map_or(false, |if_none_match| {
+            if_none_match
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag)
+        });
+    if not_modified {
+        let mut not_modified = Response::with_status(Status::NotModified);
+        for (name, value) in &res.headers {
+            if !name.eq_ignore_ascii_case("Content-Type")
+                && !name.eq_ignore_ascii_case("Content-Length")
+            {
+                not_modified.headers.append(name.clone(), value.clone());
+            }
+        }
+        not_modified.headers.insert("ETag".to_string(), etag);
+        Ok(not_modified)
+    } else {
+        res.headers.insert("ETag".to_string(), etag);
+        Ok(res)
+    }
+}
+
+// MARK: ResponseCache
+struct CacheEntry {
+    status: Status,
+    headers: HeaderMap,
+    body: Vec<u8>,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    fn to_response(&self) -> Response {
+        let mut res = Response::with_status(self.status).body(self.body.clone());
+        res.headers = self.headers.clone();
+        res
+    }
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    // Request header names from the `Vary` header of the last cached response per URL
+    vary: HashMap<String, Vec<String>>,
+    tick: u64,
+}
+
+/// In-memory LRU cache for GET responses with a time to live, clones share the same cache
+#[derive(Clone)]
+pub struct ResponseCache {
+    capacity: usize,
+    ttl: Duration,
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl ResponseCache {
+    /// Create new response cache that holds at most `capacity` responses for `ttl`
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            state: Arc::new(Mutex::new(CacheState::default())),
+        }
+    }
+
+    /// Remove all cached responses
+    pub fn clear(&self) {
+        self.state
+            .lock()
+            .expect("Can't lock response cache")
+            .entries
+            .clear();
+    }
+
+    fn get(&self, req: &Request) -> Option<Response> {
+        let mut state = self.state.lock().expect("Can't lock response cache");
+        let url = req.url.to_string();
+        let key = cache_key(req, state.vary.get(&url).map_or(&[][..], Vec::as_slice));
+        state.tick += 1;
+        let tick = state.tick;
+        match state.entries.get_mut(&key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                entry.last_used = tick;
+                Some(entry.to_response())
+            }
+            Some(_) => {
+                state.entries.remove(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, req: &Request, vary: Vec<String>, res: &Response) {
+        let mut state = self.state.lock().expect("Can't lock response cache");
+        let key = cache_key(req, &vary);
+        state.vary.insert(req.url.to_string(), vary);
+        let now = Instant::now();
+        if state
+            .entries
+            .get(&key)
+            .map_or(false, |entry| entry.expires_at > now)
+        {
+            return;
+        }
+
+        // Make room by dropping expired entries first, then the least recently used one
+        if state.entries.len() >= self.capacity {
+            state.entries.retain(|_, entry| entry.expires_at > now);
+            let CacheState { entries, vary, .. } = &mut *state;
+            vary.retain(|url, _| {
+                entries
+                    .keys()
+                    .any(|key| key.split('\n').next() == Some(url))
+            });
+        }
+        if state.entries.len() >= self.capacity {
+            if let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            {
+                state.entries.remove(&oldest);
+            }
+        }
+        if self.capacity == 0 {
+            return;
+        }
+
+        state.tick += 1;
+        let entry = CacheEntry {
+            status: res.status,
+            headers: res.headers.clone(),
+            body: res.body.clone(),
+            expires_at: now + self.ttl,
+            last_used: state.tick,
+        };
+        state.entries.insert(key, entry);
+    }
+}
+
+// MARK: Layers
+/// Router context that contains a response cache
+pub trait CacheContext {
+    /// Get the response cache
+    fn response_cache(&self) -> &ResponseCache;
+}
+
+// The key is the URL followed by the values of the request headers the response varies on
+fn cache_key(req: &Request, vary: &[String]) -> String {
+    let mut key = req.url.to_string();
+    for name in vary {
+        key.push('\n');
+        key.push_str(name);
+        key.push('=');
+        key.push_str(&req.headers.get_all(name).collect::<Vec<_>>().join(", "));
+    }
+    key
+}
+
+// Requests with credentials can get a personal response, so they never use the cache
+fn is_cacheable_request(req: &Request) -> bool {
+    req.method == Method::Get
+        && !req.headers.contains("Authorization")
+        && !req.headers.contains("Cookie")
+}
+
+/// Pre layer that answers GET requests from the response cache, requests with an
+/// `Authorization` or `Cookie` header are skipped
+pub fn cache_pre_layer<T: CacheContext>(req: &Request, ctx: &mut T) -> Option<Result<Response>> {
+    if !is_cacheable_request(req) {
+        return None;
+    }
+    ctx.response_cache().get(req).map(Ok)
+}
+
+/// Post layer that stores successful GET responses in the response cache, responses with
+/// cookies, a `no-store` / `private` Cache-Control header or `Vary: *` are not cached
+pub fn cache_post_layer<T: CacheContext>(
+    req: &Request,
+    ctx: &T,
+    res: Response,
+) -> Result<Response> {
+    let vary = res
+        .headers
+        .get_all("Vary")
+        .flat_map(|vary| vary.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+    let cacheable = is_cacheable_request(req)
+        && res.status == Status::Ok
+        && !res.body.is_empty()
+        && !res.headers.contains("Set-Cookie")
+        && !res
+            .headers
+            .get("Cache-Control")
+            .map_or(false, |cache_control| {
+                let cache_control = cache_control.to_ascii_lowercase();
+                cache_control.contains("no-store") || cache_control.contains("private")
+            })
+        && !vary.iter().any(|name| name == "*");
+    if cacheable {
+        ctx.response_cache().insert(req, vary, &res);
+    }
+    Ok(res)
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::RouterBuilder;
+
+    #[derive(Clone)]
+    struct Context {
+        cache: ResponseCache,
+        calls: Arc<AtomicU32>,
+    }
+
+    impl CacheContext for Context {
+        fn response_cache(&self) -> &ResponseCache {
+            &self.cache
+        }
+    }
+
+    fn counter(_req: &Request, ctx: &Context) -> Result<Response> {
+        let calls = ctx.calls.fetch_add(1, Ordering::Relaxed) + 1;
+        Ok(Response::with_body(format!("Call {calls}")))
+    }
+
+    fn context(capacity: usize, ttl: Duration) -> Context {
+        Context {
+            cache: ResponseCache::new(capacity, ttl),
+            calls: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    #[test]
+    fn test_etag_layer() {
+        let router = RouterBuilder::new()
+            .post_layer(etag_post_layer)
+            .get("/", |_, _| Ok(Response::with_body("Hello, world!")))
+            .post("/", |_, _| Ok(Response::with_body("Posted")))
+            .build();
+
+        let res = router.handle(&Request::get("http://localhost/"));
+        let etag = res.headers.get("ETag").unwrap().to_string();
+        assert_eq!(etag, super::etag(b"Hello, world!"));
+
+        let res = router.handle(&Request::get("http://localhost/").header("If-None-Match", &etag));
+        assert_eq!(res.status, Status::NotModified);
+        assert!(res.body.is_empty());
+        assert_eq!(res.headers.get("ETag"), Some(etag.as_str()));
+
+        let res =
+            router.handle(&Request::get("http://localhost/").header("If-None-Match", "\"x\""));
+        assert_eq!(res.status, Status::Ok);
+
+        let res = router.handle(&Request::post("http://localhost/"));
+        assert!(res.headers.get("ETag").is_none());
+    }
+
+    #[test]
+    fn test_cache_layers() {
+        let router = RouterBuilder::with(context(10, Duration::from_secs(60)))
+            .pre_layer(cache_pre_layer)
+            .post_layer(cache_post_layer)
+            .get("/", counter)
+            .post("/", counter)
+            .build();
+
+        assert_eq!(
+            router.handle(&Request::get("http://localhost/")).body,
+            b"Call 1"
+        );
+        assert_eq!(
+            router.handle(&Request::get("http://localhost/")).body,
+            b"Call 1"
+        );
+        assert_eq!(
+            router
+                .handle(&Request::get("http://localhost/?page=2"))
+                .body,
+            b"Call 2"
+        );
+        assert_eq!(
+            router.handle(&Request::post("http://localhost/")).body,
+            b"Call 3"
+        );
+    }
+
+    #[test]
+    fn test_cache_skips_private() {
+        let router = RouterBuilder::with(context(10, Duration::from_secs(60)))
+            .pre_layer(cache_pre_layer)
+            .post_layer(cache_post_layer)
+            .get("/", counter)
+            .get("/cookie", |req, ctx| {
+                Ok(counter(req, ctx)?.header("Set-Cookie", "session=secret"))
+            })
+            .get("/private", |req, ctx| {
+                Ok(counter(req, ctx)?.header("Cache-Control", "Private, max-age=60"))
+            })
+            .build();
+
+        // Requests with credentials are not answered from or stored in the cache
+        let authorized = || Request::get("http://localhost/").header("Authorization", "Bearer a");
+        assert_eq!(router.handle(&authorized()).body, b"Call 1");
+        assert_eq!(router.handle(&authorized()).body, b"Call 2");
+        assert_eq!(
+            router.handle(&Request::get("http://localhost/")).body,
+            b"Call 3"
+        );
+        assert_eq!(
+            router
+                .handle(&Request::get("http://localhost/").header("Cookie", "session=a"))
+                .body,
+            b"Call 4"
+        );
+
+        for path in ["/cookie", "/private"] {
+            let url = format!("http://localhost{path}");
+            let first = router.handle(&Request::get(&url)).body;
+            assert_ne!(router.handle(&Request::get(&url)).body, first);
+        }
+    }
+
+    #[test]
+    fn test_cache_vary() {
+        let router = RouterBuilder::with(context(10, Duration::from_secs(60)))
+            .pre_layer(cache_pre_layer)
+            .post_layer(cache_post_layer)
+            .get("/", |req, ctx| {
+                Ok(counter(req, ctx)?.header("Vary", "Accept-Language"))
+            })
+            .build();
+        let get = |language: &str| {
+            router
+                .handle(&Request::get("http://localhost/").header("Accept-Language", language))
+                .body
+        };
+        assert_eq!(get("en"), b"Call 1");
+        assert_eq!(get("nl"), b"Call 2");
+        assert_eq!(get("en"), b"Call 1");
+        assert_eq!(get("nl"), b"Call 2");
+    }
+
+    #[test]
+    fn test_cache_ttl_and_lru() {
+        let router = RouterBuilder::with(context(10, Duration::ZERO))
+            .pre_layer(cache_pre_layer)
+            .post_layer(cache_post_layer)
+            .get("/", counter)
+            .build();
+        assert_eq!(
+            router.handle(&Request::get("http://localhost/")).body,
+            b"Call 1"
+        );
+        assert_eq!(
+            router.handle(&Request::get("http://localhost/")).body,
+            b"Call 2"
+        );
+
+        let router = RouterBuilder::with(context(2, Duration::from_secs(60)))
+            .pre_layer(cache_pre_layer)
+            .post_layer(cache_post_layer)
+            .get("/:page", counter)
+            .build();
+        let get = |page: &str| {
+            router
+                .handle(&Request::get(format!("http://localhost/{page}")))
+                .body
+        };
+        assert_eq!(get("a"), b"Call 1");
+        assert_eq!(get("b"), b"Call 2");
+        assert_eq!(get("a"), b"Call 1");
+        assert_eq!(get("c"), b"Call 3");
+        assert_eq!(get("a"), b"Call 1");
+        assert_eq!(get("b"), b"Call 4");
+    }
+}
@@ -1,11 +1,12 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 #![doc = include_str!("../README.md")]
 
+use std::any::{Any, TypeId};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::error::Error;
@@ -14,6 +15,18 @@ use std::sync::Arc;
 use anyhow::Result;
 use small_http::{Method, Request, Response, Status};
 
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "session")]
+pub mod session;
+#[cfg(feature = "static-dir")]
+mod static_dir;
+
+#[cfg(feature = "static-dir")]
+pub use crate::static_dir::StaticSource;
+#[cfg(feature = "static-dir")]
+use crate::static_dir::{serve_static_file, StaticDir};
+
 // MARK: Handler
 
 // Parsed path parameters
@@ -21,9 +34,19 @@ type HandlerFn<T> = fn(&Request, &T) -> Result<Response>;
 type PreLayerFn<T> = fn(&Request, &mut T) -> Option<Result<Response>>;
 type PostLayerFn<T> = fn(&Request, &T, Response) -> Result<Response>;
 type ErrorHandlerFn<T> = fn(&Request, &T, &dyn Error) -> Response;
+#[cfg(feature = "websocket")]
+type WebSocketHandlerFn<T> = fn(small_websocket::WebSocket, &Request, &T);
+#[cfg(feature = "websocket")]
+type WebSocketUpgradeFn<T> = fn(&Request, &T, WebSocketHandlerFn<T>) -> Response;
+
+enum HandlerKind<T> {
+    Http(HandlerFn<T>),
+    #[cfg(feature = "websocket")]
+    WebSocket(WebSocketHandlerFn<T>, WebSocketUpgradeFn<T>),
+}
 
 struct Handler<T> {
-    handler: HandlerFn<T>,
+    handler: HandlerKind<T>,
     pre_layers: Vec<PreLayerFn<T>>,
     post_layers: Vec<PostLayerFn<T>>,
 }
@@ -35,28 +58,52 @@ impl<T> Handler<T> {
         post_layers: Vec<PostLayerFn<T>>,
     ) -> Self {
         Self {
-            handler,
+            handler: HandlerKind::Http(handler),
             pre_layers,
             post_layers,
         }
     }
 
     fn call(&self, req: &Request, ctx: &mut T) -> Result<Response> {
-        for pre_layer in &self.pre_layers {
-            if let Some(res) = pre_layer(req, ctx) {
-                let mut res = res?;
-                for post_layer in &self.post_layers {
-                    res = post_layer(req, ctx, res)?;
-                }
-                return Ok(res);
+        call_with_layers(&self.pre_layers, &self.post_layers, req, ctx, |req, ctx| {
+            Ok(match self.handler {
+                HandlerKind::Http(handler) => handler(req, ctx)?,
+                #[cfg(feature = "websocket")]
+                HandlerKind::WebSocket(handler, upgrade) => upgrade(req, ctx, handler),
+            })
+        })
+    }
+}
+
+fn call_with_layers<T>(
+    pre_layers: &[PreLayerFn<T>],
+    post_layers: &[PostLayerFn<T>],
+    req: &Request,
+    ctx: &mut T,
+    handler: impl FnOnce(&Request, &mut T) -> Result<Response>,
+) -> Result<Response> {
+    for pre_layer in pre_layers {
+        if let Some(res) = pre_layer(req, ctx) {
+            let mut res = res?;
+            for post_layer in post_layers {
+                res = post_layer(req, ctx, res)?;
             }
+            return Ok(res);
         }
-        let mut res = (self.handler)(req, ctx)?;
-        for post_layer in &self.post_layers {
-            res = post_layer(req, ctx, res)?;
-        }
-        Ok(res)
     }
+    let mut res = handler(req, ctx)?;
+    for post_layer in post_layers {
+        res = post_layer(req, ctx, res)?;
+    }
+    Ok(res)
+}
+
+// MARK: StaticRoute
+#[cfg(feature = "static-dir")]
+struct StaticRoute<T> {
+    dir: StaticDir,
+    pre_layers: Vec<PreLayerFn<T>>,
+    post_layers: Vec<PostLayerFn<T>>,
 }
 
 // MARK: Route
@@ -97,13 +144,13 @@ impl<T> Route<T> {
             .collect()
     }
 
-    fn is_match(&self, path: &str) -> bool {
-        let mut path_parts = path.split('/').filter(|part| !part.is_empty());
+    fn is_match(&self, path: &[String]) -> bool {
+        let mut path_parts = path.iter();
         for part in &self.parts {
             match part {
                 RoutePart::Static(expected) => {
                     if let Some(actual) = path_parts.next() {
-                        if actual != *expected {
+                        if actual != expected {
                             return false;
                         }
                     } else {
@@ -120,8 +167,8 @@ impl<T> Route<T> {
         path_parts.next().is_none()
     }
 
-    fn match_path(&self, path: &str) -> HashMap<String, String> {
-        let mut path_parts = path.split('/').filter(|part| !part.is_empty());
+    fn match_path(&self, path: &[String]) -> HashMap<String, String> {
+        let mut path_parts = path.iter();
         let mut params = HashMap::new();
         for part in &self.parts {
             match part {
@@ -130,7 +177,7 @@ impl<T> Route<T> {
                 }
                 RoutePart::Param(name) => {
                     if let Some(value) = path_parts.next() {
-                        params.insert(name.clone(), value.to_string());
+                        params.insert(name.clone(), value.clone());
                     }
                 }
             }
@@ -143,12 +190,16 @@ impl<T> Route<T> {
 /// Router builder
 pub struct RouterBuilder<T: Clone> {
     ctx: T,
+    states: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
     pre_layers: Vec<PreLayerFn<T>>,
     post_layers: Vec<PostLayerFn<T>>,
     routes: Vec<Route<T>>,
+    #[cfg(feature = "static-dir")]
+    static_routes: Vec<StaticRoute<T>>,
     not_allowed_method_handler: Option<Handler<T>>,
     fallback_handler: Option<Handler<T>>,
     error_handler: Option<ErrorHandlerFn<T>>,
+    auto_head: bool,
 }
 
 impl Default for RouterBuilder<()> {
@@ -169,15 +220,26 @@ impl<T: Clone> RouterBuilder<T> {
     pub fn with(ctx: T) -> Self {
         Self {
             ctx,
+            states: HashMap::new(),
             pre_layers: Vec::new(),
             post_layers: Vec::new(),
             routes: Vec::new(),
+            #[cfg(feature = "static-dir")]
+            static_routes: Vec::new(),
             not_allowed_method_handler: None,
             fallback_handler: None,
             error_handler: None,
+            auto_head: true,
         }
     }
 
+    /// Add shared state object, handlers can get it with `req.state::<S>()`, adding a state
+    /// of the same type again replaces it
+    pub fn state<S: Any + Send + Sync>(mut self, state: S) -> Self {
+        self.states.insert(TypeId::of::<S>(), Arc::new(state));
+        self
+    }
+
     /// Add pre layer
     pub fn pre_layer(mut self, layer: PreLayerFn<T>) -> Self {
         self.pre_layers.push(layer);
@@ -263,6 +325,39 @@ impl<T: Clone> RouterBuilder<T> {
         self.route(&[Method::Patch], route.into(), handler)
     }
 
+    /// Add WebSocket route for GET method, the connection is upgraded and the handler is run
+    /// on its own thread, requests that don't ask for an upgrade get a 426 Upgrade Required
+    #[cfg(feature = "websocket")]
+    pub fn websocket(mut self, route: impl Into<String>, handler: WebSocketHandlerFn<T>) -> Self
+    where
+        T: Send + 'static,
+    {
+        self.routes.push(Route::new(
+            vec![Method::Get],
+            route.into(),
+            Handler {
+                handler: HandlerKind::WebSocket(handler, websocket_upgrade::<T>),
+                pre_layers: self.pre_layers.clone(),
+                post_layers: self.post_layers.clone(),
+            },
+        ));
+        self
+    }
+
+    /// Serve static files under a path prefix for GET and HEAD requests that don't match a
+    /// route, with MIME type, ETag and Cache-Control headers. Directories are served by their
+    /// `index.html` and paths without a file extension fall back to the root `index.html`, so
+    /// single page apps don't need a custom fallback handler.
+    #[cfg(feature = "static-dir")]
+    pub fn static_dir(mut self, prefix: impl AsRef<str>, source: StaticSource) -> Self {
+        self.static_routes.push(StaticRoute {
+            dir: StaticDir::new(prefix.as_ref(), source),
+            pre_layers: self.pre_layers.clone(),
+            post_layers: self.post_layers.clone(),
+        });
+        self
+    }
+
     /// Set not allowed method handler (called when a route matches but method doesn't)
     pub fn not_allowed_method(mut self, handler: HandlerFn<T>) -> Self {
         self.not_allowed_method_handler = Some(Handler::new(
@@ -289,6 +384,13 @@ impl<T: Clone> RouterBuilder<T> {
         self
     }
 
+    /// Set if HEAD requests are answered by the GET route when a route has no HEAD handler,
+    /// enabled by default. The body is removed from the response, but its Content-Length is kept
+    pub const fn auto_head(mut self, auto_head: bool) -> Self {
+        self.auto_head = auto_head;
+        self
+    }
+
     /// Build router
     pub fn build(self) -> Router<T> {
         // Sort routes: longest first, then prefer static parts over params at each position
@@ -308,9 +410,19 @@ impl<T: Clone> RouterBuilder<T> {
             Ordering::Equal
         });
 
+        // Sort static routes: longest prefix first
+        #[cfg(feature = "static-dir")]
+        let mut static_routes = self.static_routes;
+        #[cfg(feature = "static-dir")]
+        static_routes.sort_by_key(|route| std::cmp::Reverse(route.dir.prefix_len()));
+
         Router(Arc::new(InnerRouter {
             ctx: self.ctx,
+            states: self.states,
             routes,
+            auto_head: self.auto_head,
+            #[cfg(feature = "static-dir")]
+            static_routes,
             not_allowed_method_handler: self.not_allowed_method_handler.unwrap_or_else(|| {
                 Handler::new(
                     |_, _| {
@@ -342,10 +454,36 @@ impl<T: Clone> RouterBuilder<T> {
     }
 }
 
+#[cfg(feature = "websocket")]
+fn websocket_upgrade<T: Clone + Send + 'static>(
+    req: &Request,
+    ctx: &T,
+    handler: WebSocketHandlerFn<T>,
+) -> Response {
+    let is_upgrade = req
+        .headers
+        .get("Upgrade")
+        .map_or(false, |upgrade| upgrade.eq_ignore_ascii_case("websocket"));
+    if !is_upgrade {
+        return Response::with_status(Status::UpgradeRequired)
+            .header("Upgrade", "websocket")
+            .header("Connection", "Upgrade")
+            .body("426 Upgrade Required");
+    }
+
+    let req_clone = req.clone();
+    let ctx = ctx.clone();
+    small_websocket::upgrade(req, move |ws| handler(ws, &req_clone, &ctx))
+}
+
 // MARK: InnerRouter
 struct InnerRouter<T: Clone> {
     ctx: T,
+    states: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
     routes: Vec<Route<T>>,
+    auto_head: bool,
+    #[cfg(feature = "static-dir")]
+    static_routes: Vec<StaticRoute<T>>,
     not_allowed_method_handler: Handler<T>,
     fallback_handler: Handler<T>,
     error_handler: ErrorHandlerFn<T>,
@@ -354,29 +492,68 @@ struct InnerRouter<T: Clone> {
 impl<T: Clone> InnerRouter<T> {
     fn handle(&self, req: &Request) -> Response {
         let mut ctx = self.ctx.clone();
-        match self.handle_inner(req, &mut ctx) {
+        let mut req = req.clone();
+        req.states.extend(
+            self.states
+                .iter()
+                .map(|(type_id, state)| (*type_id, state.clone())),
+        );
+        match self.handle_inner(&mut req, &mut ctx) {
             Ok(res) => res,
-            Err(err) => (self.error_handler)(req, &mut ctx, &*err),
+            Err(err) => (self.error_handler)(&req, &mut ctx, &*err),
         }
     }
 
-    fn handle_inner(&self, req: &Request, ctx: &mut T) -> Result<Response> {
-        // Match routes
-        let path = req.url.path();
+    fn handle_inner(&self, req: &mut Request, ctx: &mut T) -> Result<Response> {
+        // Match routes against the percent-decoded path
+        let Some(path) = req.path_segments() else {
+            return Ok(Response::with_status(Status::BadRequest).body("400 Bad Request"));
+        };
         for route in self.routes.iter() {
-            if route.is_match(path) {
-                let mut req = req.clone();
-                req.params = route.match_path(path);
+            if route.is_match(&path) {
+                req.params = route.match_path(&path);
 
                 // Find matching route by method
-                for route in self.routes.iter().filter(|r| r.route == route.route) {
+                let routes = self.routes.iter().filter(|r| r.route == route.route);
+                for route in routes.clone() {
                     if route.methods.contains(&req.method) {
-                        return route.handler.call(&req, ctx);
+                        return route.handler.call(req, ctx);
+                    }
+                }
+
+                // Or answer HEAD request with GET route without body
+                if self.auto_head && req.method == Method::Head {
+                    for route in routes {
+                        if route.methods.contains(&Method::Get) {
+                            let mut res = route.handler.call(req, ctx)?;
+                            if !res.body.is_empty() {
+                                res.headers.insert(
+                                    "Content-Length".to_string(),
+                                    res.body.len().to_string(),
+                                );
+                                res.body.clear();
+                            }
+                            return Ok(res);
+                        }
                     }
                 }
 
                 // Or run not allowed method handler
-                return self.not_allowed_method_handler.call(&req, ctx);
+                return self.not_allowed_method_handler.call(req, ctx);
+            }
+        }
+
+        // Or serve static file
+        #[cfg(feature = "static-dir")]
+        for route in self.static_routes.iter() {
+            if let Some(file) = route.dir.resolve(req) {
+                return call_with_layers(
+                    &route.pre_layers,
+                    &route.post_layers,
+                    req,
+                    ctx,
+                    |req, _| Ok(serve_static_file(req, file)),
+                );
             }
         }
 
@@ -447,6 +624,25 @@ mod test {
         ));
         assert_eq!(res.status, Status::Ok);
 
+        // Test route with percent-encoded param
+        let res = router.handle(&Request::get("http://localhost/hello/J%C3%BCrgen"));
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.body, "Hello, J\u{fc}rgen!".as_bytes());
+
+        // Test path traversal after decoding
+        let res = router.handle(&Request::get("http://localhost/hello/%2E%2E"));
+        assert_eq!(res.status, Status::BadRequest);
+
+        // Test path that isn't valid percent-encoded UTF-8
+        let res = router.handle(&Request::get("http://localhost/hello/%C3%28"));
+        assert_eq!(res.status, Status::BadRequest);
+
+        // Test HEAD request on GET route
+        let res = router.handle(&Request::head("http://localhost/hello/Bassie"));
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.headers.get("Content-Length"), Some("14"));
+        assert!(res.body.is_empty());
+
         // Test wrong method
         let res = router.handle(&Request::options("http://localhost/"));
         assert_eq!(res.status, Status::MethodNotAllowed);
@@ -457,4 +653,101 @@ mod test {
         assert_eq!(res.status, Status::InternalServerError);
         assert_eq!(res.body, b"500 Internal Server Error");
     }
+
+    fn head(_req: &Request, _ctx: &()) -> Result<Response> {
+        Ok(Response::with_status(Status::NoContent))
+    }
+
+    #[test]
+    fn test_auto_head() {
+        let router = RouterBuilder::new()
+            .get("/", home)
+            .head("/", head)
+            .get("/hello/:name", hello)
+            .build();
+        let res = router.handle(&Request::head("http://localhost/"));
+        assert_eq!(res.status, Status::NoContent);
+
+        let router = RouterBuilder::new()
+            .get("/hello/:name", hello)
+            .auto_head(false)
+            .build();
+        let res = router.handle(&Request::head("http://localhost/hello/Bassie"));
+        assert_eq!(res.status, Status::MethodNotAllowed);
+    }
+
+    struct Database(&'static str);
+    struct Config {
+        greeting: &'static str,
+    }
+
+    fn greet(req: &Request, _ctx: &()) -> Result<Response> {
+        let database = req.state::<Database>().unwrap();
+        let config = req.state::<Config>().unwrap();
+        Ok(Response::with_body(format!(
+            "{} from {}",
+            config.greeting, database.0
+        )))
+    }
+
+    fn missing_state(req: &Request, _ctx: &()) -> Result<Response> {
+        Ok(Response::with_body(if req.state::<u32>().is_some() {
+            "Found"
+        } else {
+            "Missing"
+        }))
+    }
+
+    #[test]
+    fn test_states() {
+        let router = RouterBuilder::new()
+            .state(Database("sqlite"))
+            .state(Config { greeting: "Hello" })
+            .get("/", greet)
+            .fallback(missing_state)
+            .build();
+
+        let res = router.handle(&Request::get("http://localhost/"));
+        assert_eq!(res.body, b"Hello from sqlite");
+
+        let res = router.handle(&Request::get("http://localhost/unknown"));
+        assert_eq!(res.body, b"Missing");
+    }
+
+    #[cfg(feature = "websocket")]
+    #[test]
+    fn test_websocket() {
+        use std::net::{Ipv4Addr, TcpListener};
+
+        use small_websocket::{Message, WebSocket};
+
+        fn echo(mut ws: WebSocket, req: &Request, ctx: &String) {
+            let name = req.params.get("name").unwrap();
+            if let Ok(Message::Text(text)) = ws.recv() {
+                ws.send(Message::Text(format!("{ctx} {name}: {text}")))
+                    .unwrap();
+            }
+        }
+
+        let router = RouterBuilder::with("Echo".to_string())
+            .websocket("/ws/:name", echo)
+            .build();
+
+        let res = router.handle(&Request::get("http://localhost/ws/bassie"));
+        assert_eq!(res.status, Status::UpgradeRequired);
+        assert_eq!(res.headers.get("Upgrade"), Some("websocket"));
+        let res = router.handle(&Request::post("http://localhost/ws/bassie"));
+        assert_eq!(res.status, Status::MethodNotAllowed);
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || small_http::serve(listener, move |req| router.handle(req)));
+
+        let mut ws = WebSocket::connect(format!("ws://{addr}/ws/bassie")).unwrap();
+        ws.send(Message::Text("Hello".to_string())).unwrap();
+        match ws.recv().unwrap() {
+            Message::Text(text) => assert_eq!(text, "Echo bassie: Hello"),
+            _ => panic!("Expected text message"),
+        }
+    }
 }
@@ -0,0 +1,333 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! HMAC signed session cookies
+//!
+//! Add a [`Session`] to the router context, implement [`SessionContext`] and register the
+//! [`session_pre_layer`] and [`session_post_layer`] layers:
+//!
+//! ```rs
+//! #[derive(Clone)]
+//! struct Context {
+//!     session: Session,
+//! }
+//!
+//! impl SessionContext for Context {
+//!     fn session(&self) -> &Session {
+//!         &self.session
+//!     }
+//! }
+//!
+//! let router = RouterBuilder::with(Context {
+//!     session: Session::new(b"secret key"),
+//! })
+//! .pre_layer(session_pre_layer)
+//! .post_layer(session_post_layer)
+//! .build();
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use sha2::Sha256;
+use small_http::{Request, Response, SameSite, SetCookie};
+
+/// Maximum age of a session cookie without max age, the browser keeps those until it is closed
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+// MARK: Signing
+/// Sign a cookie value with the current time, returns `value.issued_at.signature`
+pub fn sign(key: &[u8], value: &str) -> String {
+    let issued_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let signed = format!("{value}.{issued_at}");
+    let signature = signature(key, &signed);
+    format!("{signed}.{signature}")
+}
+
+/// Verify a signed cookie value, returns the original value when the signature is valid and
+/// the value was signed at most `max_age` ago
+pub fn verify<'a>(key: &[u8], signed: &'a str, max_age: Duration) -> Option<&'a str> {
+    let (signed_value, signature_hex) = signed.rsplit_once('.')?;
+    // Constant time compare to not leak the signature
+    if !sha1::constant_time_eq(signature(key, signed_value), signature_hex) {
+        return None;
+    }
+
+    let (value, issued_at) = signed_value.rsplit_once('.')?;
+    let issued_at = UNIX_EPOCH + Duration::from_secs(issued_at.parse().ok()?);
+    let age = SystemTime::now().duration_since(issued_at).ok()?;
+    if age <= max_age {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+fn signature(key: &[u8], value: &str) -> String {
+    hmac::hmac::<Sha256>(key, value.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+// MARK: Session
+#[derive(Default, Clone)]
+struct SessionState {
+    data: HashMap<String, String>,
+    modified: bool,
+}
+
+/// Session data stored in a signed cookie
+pub struct Session {
+    key: Arc<[u8]>,
+    cookie_name: String,
+    max_age: Option<Duration>,
+    secure: bool,
+    state: Mutex<SessionState>,
+}
+
+impl Clone for Session {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            cookie_name: self.cookie_name.clone(),
+            max_age: self.max_age,
+            secure: self.secure,
+            state: Mutex::new(self.state.lock().expect("Can't lock session").clone()),
+        }
+    }
+}
+
+impl Session {
+    /// Create new session with secret signing key
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: Arc::from(key.as_ref()),
+            cookie_name: "session".to_string(),
+            max_age: None,
+            secure: false,
+            state: Mutex::new(SessionState::default()),
+        }
+    }
+
+    /// Set cookie name
+    pub fn cookie_name(mut self, cookie_name: impl Into<String>) -> Self {
+        self.cookie_name = cookie_name.into();
+        self
+    }
+
+    /// Set cookie max age, by default the cookie expires when the browser is closed and is
+    /// accepted for at most a day
+    pub const fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Set cookie Secure attribute
+    pub const fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Get session value
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.state
+            .lock()
+            .expect("Can't lock session")
+            .data
+            .get(name)
+            .cloned()
+    }
+
+    /// Set session value
+    pub fn insert(&self, name: impl Into<String>, value: impl Into<String>) {
+        let mut state = self.state.lock().expect("Can't lock session");
+        state.data.insert(name.into(), value.into());
+        state.modified = true;
+    }
+
+    /// Remove session value
+    pub fn remove(&self, name: &str) {
+        let mut state = self.state.lock().expect("Can't lock session");
+        if state.data.remove(name).is_some() {
+            state.modified = true;
+        }
+    }
+
+    /// Remove all session values, the session cookie is removed from the client
+    pub fn clear(&self) {
+        let mut state = self.state.lock().expect("Can't lock session");
+        state.data.clear();
+        state.modified = true;
+    }
+
+    fn load(&self, req: &Request) {
+        let cookies = req.cookies();
+        let data = cookies
+            .get(&self.cookie_name)
+            .and_then(|signed| verify(&self.key, signed, self.max_age.unwrap_or(DEFAULT_MAX_AGE)))
+            .map(|value| {
+                form_urlencoded::parse(value.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        *self.state.lock().expect("Can't lock session") = SessionState {
+            data,
+            modified: false,
+        };
+    }
+
+    fn apply(&self, res: Response) -> Response {
+        let state = self.state.lock().expect("Can't lock session");
+        if !state.modified {
+            return res;
+        }
+        let cookie = if state.data.is_empty() {
+            SetCookie::removal(&self.cookie_name)
+        } else {
+            let value = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&state.data)
+                .finish();
+            let mut cookie = SetCookie::new(&self.cookie_name, sign(&self.key, &value));
+            if let Some(max_age) = self.max_age {
+                cookie = cookie.max_age(max_age);
+            }
+            cookie
+        };
+        res.set_cookie(
+            cookie
+                .path("/")
+                .same_site(SameSite::Lax)
+                .secure(self.secure)
+                .http_only(true),
+        )
+    }
+}
+
+// MARK: Layers
+/// Router context that contains a session
+pub trait SessionContext {
+    /// Get the session
+    fn session(&self) -> &Session;
+}
+
+/// Pre layer that loads the session from the signed session cookie
+pub fn session_pre_layer<T: SessionContext>(
+    req: &Request,
+    ctx: &mut T,
+) -> Option<Result<Response>> {
+    ctx.session().load(req);
+    None
+}
+
+/// Post layer that writes the session cookie when the session is modified
+pub fn session_post_layer<T: SessionContext>(
+    _req: &Request,
+    ctx: &T,
+    res: Response,
+) -> Result<Response> {
+    Ok(ctx.session().apply(res))
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use small_http::Status;
+
+    use super::*;
+    use crate::RouterBuilder;
+
+    #[derive(Clone)]
+    struct Context {
+        session: Session,
+    }
+
+    impl SessionContext for Context {
+        fn session(&self) -> &Session {
+            &self.session
+        }
+    }
+
+    fn login(_req: &Request, ctx: &Context) -> Result<Response> {
+        ctx.session.insert("user", "bassie");
+        Ok(Response::with_status(Status::Ok))
+    }
+
+    fn me(_req: &Request, ctx: &Context) -> Result<Response> {
+        Ok(match ctx.session.get("user") {
+            Some(user) => Response::with_body(user),
+            None => Response::with_status(Status::Unauthorized),
+        })
+    }
+
+    fn logout(_req: &Request, ctx: &Context) -> Result<Response> {
+        ctx.session.clear();
+        Ok(Response::with_status(Status::Ok))
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let max_age = Duration::from_secs(60);
+        let signed = sign(b"key", "a.b");
+        assert_eq!(verify(b"key", &signed, max_age), Some("a.b"));
+        assert_eq!(verify(b"other", &signed, max_age), None);
+        assert_eq!(verify(b"key", "a.b", max_age), None);
+        assert_eq!(verify(b"key", "unsigned", max_age), None);
+
+        // Expired values are rejected
+        let signed = format!("a.b.0.{}", signature(b"key", "a.b.0"));
+        assert_eq!(verify(b"key", &signed, max_age), None);
+    }
+
+    #[test]
+    fn test_session_layers() {
+        let router = RouterBuilder::with(Context {
+            session: Session::new(b"secret"),
+        })
+        .pre_layer(session_pre_layer)
+        .post_layer(session_post_layer)
+        .post("/login", login)
+        .get("/me", me)
+        .post("/logout", logout)
+        .build();
+
+        // Without session
+        let res = router.handle(&Request::get("http://localhost/me"));
+        assert_eq!(res.status, Status::Unauthorized);
+        assert!(res.headers.get("Set-Cookie").is_none());
+
+        // Login sets signed cookie
+        let res = router.handle(&Request::post("http://localhost/login"));
+        let set_cookie = res.headers.get("Set-Cookie").unwrap();
+        assert!(set_cookie.starts_with("session=user=bassie."));
+        assert!(set_cookie.ends_with("; Path=/; SameSite=Lax; HttpOnly"));
+        let cookie = set_cookie.split(';').next().unwrap().to_string();
+
+        // Session is loaded from cookie
+        let res = router.handle(&Request::get("http://localhost/me").header("Cookie", &cookie));
+        assert_eq!(res.body, b"bassie");
+        assert!(res.headers.get("Set-Cookie").is_none());
+
+        // Tampered cookie is ignored
+        let tampered = cookie.replace("bassie", "admin");
+        let res = router.handle(&Request::get("http://localhost/me").header("Cookie", tampered));
+        assert_eq!(res.status, Status::Unauthorized);
+
+        // Logout removes cookie
+        let res = router.handle(&Request::post("http://localhost/logout").header("Cookie", cookie));
+        assert!(res
+            .headers
+            .get("Set-Cookie")
+            .unwrap()
+            .starts_with("session=; Expires=Thu, 01 Jan 1970 00:00:00 GMT; Max-Age=0"));
+    }
+}
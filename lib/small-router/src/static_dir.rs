@@ -0,0 +1,265 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+#[cfg(feature = "embed")]
+use std::borrow::Cow;
+#[cfg(feature = "embed")]
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "embed")]
+use std::hash::{Hash, Hasher};
+use std::path::{Component, Path, PathBuf};
+
+#[cfg(feature = "embed")]
+use small_http::Status;
+use small_http::{Method, Request, Response};
+
+const INDEX_FILE: &str = "index.html";
+const CACHE_CONTROL: &str = "no-cache";
+
+// MARK: StaticSource
+/// Source of the files served by [`RouterBuilder::static_dir`](crate::RouterBuilder::static_dir)
+pub enum StaticSource {
+    /// Files embedded in the binary, create with [`StaticSource::embed`]
+    #[cfg(feature = "embed")]
+    Embed(fn(&str) -> Option<rust_embed::EmbeddedFile>),
+    /// Files in a directory on the filesystem
+    Dir(PathBuf),
+}
+
+impl StaticSource {
+    /// Create source for the files embedded with `#[derive(Embed)]`
+    #[cfg(feature = "embed")]
+    pub fn embed<E: rust_embed::RustEmbed>() -> Self {
+        Self::Embed(E::get)
+    }
+}
+
+// MARK: StaticDir
+pub(crate) enum StaticFile {
+    #[cfg(feature = "embed")]
+    Embed(String, Cow<'static, [u8]>),
+    Dir(PathBuf),
+}
+
+pub(crate) struct StaticDir {
+    prefix: String,
+    source: StaticSource,
+}
+
+impl StaticDir {
+    pub(crate) fn new(prefix: &str, source: StaticSource) -> Self {
+        Self {
+            prefix: prefix.trim_end_matches('/').to_string(),
+            source,
+        }
+    }
+
+    pub(crate) fn prefix_len(&self) -> usize {
+        self.prefix.len()
+    }
+
+    /// Find the file for a request, directories are served by their `index.html` and paths
+    /// without a file extension fall back to the root `index.html` for single page apps
+    pub(crate) fn resolve(&self, req: &Request) -> Option<StaticFile> {
+        if req.method != Method::Get && req.method != Method::Head {
+            return None;
+        }
+        let path = req.url.path().strip_prefix(&self.prefix)?;
+        if !path.is_empty() && !path.starts_with('/') {
+            return None;
+        }
+        let path = Self::normalize_path(path)?;
+
+        let mut candidates = if path.is_empty() {
+            Vec::new()
+        } else {
+            vec![path.clone(), format!("{path}/{INDEX_FILE}")]
+        };
+        let is_file_path = path
+            .rsplit('/')
+            .next()
+            .map_or(false, |name| name.contains('.'));
+        if !is_file_path {
+            candidates.push(INDEX_FILE.to_string());
+        }
+        candidates.into_iter().find_map(|path| self.get(path))
+    }
+
+    // Decode and validate the url path to a relative path with '/' separators
+    fn normalize_path(path: &str) -> Option<String> {
+        let path = small_http::fs::resolve_path(Path::new(""), path)?;
+        let mut normalized = String::new();
+        for component in path.components() {
+            match component {
+                Component::Normal(name) => {
+                    if !normalized.is_empty() {
+                        normalized.push('/');
+                    }
+                    normalized.push_str(name.to_str()?);
+                }
+                _ => return None,
+            }
+        }
+        Some(normalized)
+    }
+
+    fn get(&self, path: String) -> Option<StaticFile> {
+        match &self.source {
+            #[cfg(feature = "embed")]
+            StaticSource::Embed(get) => get(&path).map(|file| StaticFile::Embed(path, file.data)),
+            StaticSource::Dir(root) => {
+                let file_path = root.join(&path);
+                // Also reject symlinks that point outside the directory
+                match (root.canonicalize(), file_path.canonicalize()) {
+                    (Ok(root), Ok(file_path))
+                        if file_path.starts_with(&root) && file_path.is_file() =>
+                    {
+                        Some(StaticFile::Dir(file_path))
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+// MARK: Serve
+pub(crate) fn serve_static_file(req: &Request, file: StaticFile) -> Response {
+    match file {
+        #[cfg(feature = "embed")]
+        StaticFile::Embed(path, data) => serve_embedded_file(req, &path, data),
+        StaticFile::Dir(path) => {
+            small_http::fs::serve_file(req, path).header("Cache-Control", CACHE_CONTROL)
+        }
+    }
+}
+
+#[cfg(feature = "embed")]
+fn serve_embedded_file(req: &Request, path: &str, data: Cow<'static, [u8]>) -> Response {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    let etag = format!("\"{:x}-{:x}\"", hasher.finish(), data.len());
+    let res = Response::with_header(
+        "Content-Type",
+        mime_guess::from_path(path)
+            .first_or_octet_stream()
+            .to_string(),
+    )
+    .header("ETag", &etag)
+    .header("Cache-Control", CACHE_CONTROL);
+
+    let not_modified = req
+        .headers
+        .get("If-None-Match")
+        .map_or(false, |if_none_match| {
+            if_none_match
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches("W/"))
+                .any(|tag| tag == "*" || tag == etag)
+        });
+    if not_modified {
+        res.status(Status::NotModified)
+    } else {
+        res.body(data.into_owned())
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::net::{Ipv4Addr, TcpListener};
+
+    use small_http::Status;
+
+    use super::*;
+    use crate::RouterBuilder;
+
+    #[test]
+    fn test_static_dir() {
+        let dir = std::env::temp_dir().join(format!("small-router-static-{}", std::process::id()));
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(dir.join("index.html"), "<h1>Home</h1>").unwrap();
+        fs::write(dir.join("style.css"), "body {}").unwrap();
+        fs::write(dir.join("docs/index.html"), "<h1>Docs</h1>").unwrap();
+
+        let router = RouterBuilder::new()
+            .get("/api/hello", |_, _| Ok(Response::with_body("Hello")))
+            .static_dir("/", StaticSource::Dir(dir.clone()))
+            .build();
+
+        // Files are streamed, so fetch them from a server to read the bodies
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || small_http::serve(listener, move |req| router.handle(req)));
+        let get = |path: &str| {
+            Request::get(format!("http://{}:{}{}", addr.ip(), addr.port(), path))
+                .fetch()
+                .unwrap()
+        };
+
+        let res = get("/style.css");
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(res.headers.get("Content-Type"), Some("text/css"));
+        assert_eq!(res.headers.get("Cache-Control"), Some("no-cache"));
+        assert!(res.headers.get("ETag").is_some());
+        assert_eq!(res.body, b"body {}");
+
+        assert_eq!(get("/").body, b"<h1>Home</h1>");
+        assert_eq!(get("/docs/").body, b"<h1>Docs</h1>");
+        assert_eq!(get("/settings/profile").body, b"<h1>Home</h1>");
+        assert_eq!(get("/api/hello").body, b"Hello");
+
+        assert_eq!(get("/missing.js").status, Status::NotFound);
+        assert_eq!(get("/%2e%2e/etc/passwd").status, Status::BadRequest);
+        let res = Request::post(format!("http://{}:{}/style.css", addr.ip(), addr.port()))
+            .fetch()
+            .unwrap();
+        assert_eq!(res.status, Status::NotFound);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "embed")]
+    #[test]
+    fn test_static_dir_embed() {
+        let router = RouterBuilder::new()
+            .static_dir(
+                "/assets",
+                StaticSource::Embed(|path| {
+                    let data: &'static [u8] = match path {
+                        "app.js" => b"console.log(1);",
+                        "index.html" => b"<h1>App</h1>",
+                        _ => return None,
+                    };
+                    Some(rust_embed::EmbeddedFile {
+                        data: Cow::Borrowed(data),
+                    })
+                }),
+            )
+            .build();
+
+        let res = router.handle(&Request::get("http://localhost/assets/app.js"));
+        assert_eq!(res.status, Status::Ok);
+        assert_eq!(
+            res.headers.get("Content-Type"),
+            Some("application/javascript")
+        );
+        assert_eq!(res.body, b"console.log(1);");
+
+        let etag = res.headers.get("ETag").unwrap();
+        let res = router
+            .handle(&Request::get("http://localhost/assets/app.js").header("If-None-Match", etag));
+        assert_eq!(res.status, Status::NotModified);
+
+        let res = router.handle(&Request::get("http://localhost/assets"));
+        assert_eq!(res.body, b"<h1>App</h1>");
+        let res = router.handle(&Request::get("http://localhost/assetsfoo"));
+        assert_eq!(res.status, Status::NotFound);
+        let res = router.handle(&Request::get("http://localhost/assets/missing.js"));
+        assert_eq!(res.status, Status::NotFound);
+    }
+}
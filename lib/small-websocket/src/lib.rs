@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2025 Bastiaan van der Plaat
+ * Copyright (c) 2025-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -8,17 +8,33 @@
 
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use std::io::{Read, Write};
-use std::net::TcpStream;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use sha1::Sha1;
+#[cfg(feature = "client")]
+use small_http::HeaderMap;
+#[cfg(feature = "client")]
+use small_http::{Client, MaybeHttpsStream};
 use small_http::{Request, Response, Status};
 
-// Maximum allowed WebSocket frame payload in bytes (64 KiB)
-const MAX_FRAME_PAYLOAD: usize = 64 * 1024;
+#[cfg(feature = "client")]
+pub use crate::ws_client::{WsClient, WsClientBuilder};
+
+#[cfg(feature = "client")]
+mod ws_client;
+
+/// Default maximum accepted message payload size in bytes (64 KiB)
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024;
+
+// Close codes (RFC 6455 Section 7.4.1)
+const CLOSE_INVALID_DATA: u16 = 1007;
+const CLOSE_MESSAGE_TOO_BIG: u16 = 1009;
 
 /// WebSocket message
 #[derive(Debug, Clone)]
@@ -35,48 +51,205 @@ pub enum Message {
     Close(Option<u16>, Option<String>),
 }
 
-/// WebSocket connection
+// MARK: ConnectOptions
+/// WebSocket client connect options
+#[cfg(feature = "client")]
+#[derive(Default, Clone)]
+pub struct ConnectOptions {
+    protocols: Vec<String>,
+    headers: HeaderMap,
+    max_message_size: Option<usize>,
+}
+
+#[cfg(feature = "client")]
+impl ConnectOptions {
+    /// Create new connect options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set maximum accepted message payload size, defaults to [`DEFAULT_MAX_MESSAGE_SIZE`]
+    pub const fn max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = Some(max_message_size);
+        self
+    }
+
+    /// Add subprotocol that is requested with the `Sec-WebSocket-Protocol` header, in order
+    /// of preference
+    pub fn protocol(mut self, protocol: impl Into<String>) -> Self {
+        self.protocols.push(protocol.into());
+        self
+    }
+
+    /// Add extra handshake header, like an `Authorization` token or a `Cookie`
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.append(name.into(), value.into());
+        self
+    }
+}
+
+// MARK: Connection
+// Reads and writes have their own lock, so one thread can block on a read while others send
+struct Connection {
+    stream: TcpStream,
+    read_lock: Mutex<()>,
+    write_lock: Mutex<()>,
+    max_message_size: AtomicUsize,
+}
+
+enum FrameError {
+    Io(io::Error),
+    Invalid,
+    TooBig,
+    InvalidUtf8,
+}
+
+impl From<io::Error> for FrameError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl Connection {
+    fn recv(&self) -> io::Result<Message> {
+        let _read_lock = self.read_lock.lock().expect("Can't get lock");
+        let mut first_byte = [0; 1];
+        match (&self.stream).read(&mut first_byte) {
+            Ok(0) => Ok(Message::Close(None, Some("Connection closed".to_string()))),
+            Ok(_) => self.recv_frame(first_byte[0]),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn recv_non_blocking(&self) -> io::Result<Option<Message>> {
+        let _read_lock = self.read_lock.lock().expect("Can't get lock");
+        let mut first_byte = [0; 1];
+        let result = {
+            // Non-blocking mode is shared with writes, so hold both locks while it's enabled
+            let _write_lock = self.write_lock.lock().expect("Can't get lock");
+            self.stream.set_nonblocking(true)?;
+            let result = (&self.stream).read(&mut first_byte);
+            self.stream.set_nonblocking(false)?;
+            result
+        };
+        match result {
+            Ok(0) => Ok(Some(Message::Close(
+                None,
+                Some("Connection closed".to_string()),
+            ))),
+            // The rest of a started frame is read blocking
+            Ok(_) => self.recv_frame(first_byte[0]).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn recv_frame(&self, first_byte: u8) -> io::Result<Message> {
+        let max_message_size = self.max_message_size.load(Ordering::Relaxed);
+        let first_byte = [first_byte];
+        let mut reader = first_byte.chain(&self.stream);
+        match WebSocket::read_message(&mut reader, max_message_size) {
+            Ok(message) => Ok(message),
+            Err(FrameError::Io(err)) => Err(err),
+            Err(FrameError::Invalid) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid WebSocket frame",
+            )),
+            // Close the connection with the matching close code, the rest of the frame is unread
+            Err(FrameError::TooBig) => {
+                _ = self.send(Message::Close(
+                    Some(CLOSE_MESSAGE_TOO_BIG),
+                    Some("Message too big".to_string()),
+                ));
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "WebSocket message too big",
+                ))
+            }
+            Err(FrameError::InvalidUtf8) => {
+                _ = self.send(Message::Close(
+                    Some(CLOSE_INVALID_DATA),
+                    Some("Invalid UTF-8".to_string()),
+                ));
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "WebSocket message is not valid UTF-8",
+                ))
+            }
+        }
+    }
+
+    fn send(&self, message: Message) -> io::Result<()> {
+        let frame = WebSocket::encode_message(message);
+        let _write_lock = self.write_lock.lock().expect("Can't get lock");
+        (&self.stream).write_all(&frame)
+    }
+}
+
+// MARK: WebSocket
+/// WebSocket connection, clones share the same connection
 #[derive(Clone)]
 pub struct WebSocket {
-    stream: Arc<Mutex<TcpStream>>,
+    connection: Arc<Connection>,
+    protocol: Option<String>,
 }
 
 impl PartialEq for WebSocket {
     fn eq(&self, other: &Self) -> bool {
-        Arc::ptr_eq(&self.stream, &other.stream)
+        Arc::ptr_eq(&self.connection, &other.connection)
     }
 }
 impl Eq for WebSocket {}
 
 impl WebSocket {
-    fn new(stream: TcpStream) -> Self {
+    fn new(stream: TcpStream, protocol: Option<String>) -> Self {
         WebSocket {
-            stream: Arc::new(Mutex::new(stream)),
+            connection: Arc::new(Connection {
+                stream,
+                read_lock: Mutex::new(()),
+                write_lock: Mutex::new(()),
+                max_message_size: AtomicUsize::new(DEFAULT_MAX_MESSAGE_SIZE),
+            }),
+            protocol,
         }
     }
 
     /// Connect to a WebSocket server
     #[cfg(feature = "client")]
     pub fn connect(url: impl AsRef<str>) -> Result<Self, ConnectError> {
+        Self::connect_with_options(url, ConnectOptions::new())
+    }
+
+    /// Connect to a WebSocket server with subprotocols and extra handshake headers
+    #[cfg(feature = "client")]
+    pub fn connect_with_options(
+        url: impl AsRef<str>,
+        options: ConnectOptions,
+    ) -> Result<Self, ConnectError> {
         let parsed_url = url::Url::parse(url.as_ref()).map_err(|_| ConnectError)?;
-        let mut stream = TcpStream::connect(format!(
-            "{}:{}",
-            parsed_url.host().expect("URL should have a host"),
-            parsed_url.port().unwrap_or(80)
-        ))
-        .map_err(|_| ConnectError)?;
 
         let mut random_key = [0u8; 16];
         getrandom::fill(&mut random_key).expect("Can't generate random key");
         let random_key = BASE64_STANDARD.encode(random_key);
-        let req = Request::get(url.as_ref())
+        let mut req = Request::new().url(parsed_url);
+        for (name, value) in &options.headers {
+            req = req.header(name, value);
+        }
+        req = req
             .header("Upgrade", "websocket")
             .header("Connection", "Upgrade")
             .header("Sec-WebSocket-Version", "13")
             .header("Sec-WebSocket-Key", &random_key);
-        req.write_to_stream(&mut stream, false);
-
-        let res = Response::read_from_stream(&mut stream).map_err(|_| ConnectError)?;
+        if !options.protocols.is_empty() {
+            req = req.header("Sec-WebSocket-Protocol", options.protocols.join(", "));
+        }
+        let (res, stream) = Client::new().upgrade(req).map_err(|_| ConnectError)?;
+        // The connection is shared by the reader and writer halves, which TLS streams don't support
+        let stream = match stream {
+            MaybeHttpsStream::Plain(stream) => stream,
+            #[allow(unreachable_patterns)]
+            _ => return Err(ConnectError),
+        };
         if res.status != Status::SwitchingProtocols {
             return Err(ConnectError);
         }
@@ -88,123 +261,127 @@ impl WebSocket {
         sha1.update(random_key.as_bytes());
         sha1.update(b"258EAFA5-E914-47DA-95CA-C5AB0DC85B11");
         let expected_accept = BASE64_STANDARD.encode(sha1.finalize());
-        if *websocket_accept != expected_accept {
+        if !sha1::constant_time_eq(websocket_accept, expected_accept) {
+            return Err(ConnectError);
+        }
+
+        // The server may only select one of the requested subprotocols
+        let protocol = res.headers.get("Sec-WebSocket-Protocol");
+        if let Some(protocol) = protocol
+            && !options.protocols.iter().any(|p| p == protocol)
+        {
             return Err(ConnectError);
         }
 
-        Ok(WebSocket::new(stream))
+        let ws = WebSocket::new(stream, protocol.map(str::to_string));
+        if let Some(max_message_size) = options.max_message_size {
+            ws.set_max_message_size(max_message_size);
+        }
+        Ok(ws)
+    }
+
+    /// Get the negotiated subprotocol
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
     }
 
     /// Get the underlying TCP stream peer address
-    pub fn peer_addr(&self) -> std::io::Result<std::net::SocketAddr> {
-        self.stream.lock().expect("Can't get lock").peer_addr()
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.connection.stream.peer_addr()
+    }
+
+    /// Set read timeout, a `recv` that times out returns a `WouldBlock` or `TimedOut` error
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.connection.stream.set_read_timeout(timeout)
+    }
+
+    /// Set write timeout
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.connection.stream.set_write_timeout(timeout)
+    }
+
+    /// Set maximum accepted message payload size, defaults to [`DEFAULT_MAX_MESSAGE_SIZE`].
+    /// A larger message closes the connection with close code 1009 and fails the `recv`.
+    pub fn set_max_message_size(&self, max_message_size: usize) {
+        self.connection
+            .max_message_size
+            .store(max_message_size, Ordering::Relaxed);
+    }
+
+    /// Split into independent reader and writer halves, so one thread can block on receiving
+    /// while other threads send
+    pub fn split(self) -> (WebSocketReader, WebSocketWriter) {
+        (
+            WebSocketReader {
+                connection: self.connection.clone(),
+            },
+            WebSocketWriter {
+                connection: self.connection,
+            },
+        )
     }
 
     /// Receive WebSocket message
-    pub fn recv(&mut self) -> std::io::Result<Message> {
-        let mut stream = self.stream.lock().expect("Can't get lock");
-        let mut buf = [0; 1024];
-        match stream.read(&mut buf) {
-            Ok(0) => Ok(Message::Close(None, Some("Connection closed".to_string()))),
-            Ok(_) => Self::parse_message(&buf).ok_or_else(|| {
-                std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid WebSocket frame")
-            }),
-            Err(e) => Err(e),
-        }
+    pub fn recv(&mut self) -> io::Result<Message> {
+        self.connection.recv()
     }
 
     /// Receive WebSocket message without blocking
-    pub fn recv_non_blocking(&mut self) -> std::io::Result<Option<Message>> {
-        let mut stream = self.stream.lock().expect("Can't get lock");
-        stream.set_nonblocking(true)?;
-        let mut buf = [0; 1024];
-        match stream.read(&mut buf) {
-            Ok(0) => Ok(Some(Message::Close(
-                None,
-                Some("Connection closed".to_string()),
-            ))),
-            Ok(_) => Self::parse_message(&buf).map(Some).ok_or_else(|| {
-                std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid WebSocket frame")
-            }),
-            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
-            Err(e) => Err(e),
-        }
+    pub fn recv_non_blocking(&mut self) -> io::Result<Option<Message>> {
+        self.connection.recv_non_blocking()
     }
 
-    fn parse_message(buf: &[u8]) -> Option<Message> {
-        // Need at least 2 bytes for the frame header
-        if buf.len() < 2 {
-            return None;
-        }
-
-        // Parse WebSocket frame
-        let opcode = buf[0] & 0x0F;
-        let masked = (buf[1] & 0x80) != 0;
-        let payload_len = (buf[1] & 0x7F) as usize;
+    fn read_message(
+        reader: &mut impl Read,
+        max_message_size: usize,
+    ) -> Result<Message, FrameError> {
+        // Parse WebSocket frame header
+        let mut header = [0; 2];
+        reader.read_exact(&mut header)?;
+        let opcode = header[0] & 0x0F;
+        let masked = (header[1] & 0x80) != 0;
 
         // Handle payload length
-        let (payload_offset, payload_len) = match payload_len {
+        let payload_len = match header[1] & 0x7F {
             126 => {
-                if buf.len() < 4 {
-                    return None;
-                }
-                let len = u16::from_be_bytes([buf[2], buf[3]]) as usize;
-                (4, len)
+                let mut len = [0; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_be_bytes(len) as u64
             }
             127 => {
-                if buf.len() < 10 {
-                    return None;
-                }
-                let len = u64::from_be_bytes([
-                    buf[2], buf[3], buf[4], buf[5], buf[6], buf[7], buf[8], buf[9],
-                ]) as usize;
-                (10, len)
+                let mut len = [0; 8];
+                reader.read_exact(&mut len)?;
+                u64::from_be_bytes(len)
             }
-            len => (2, len),
+            len => len as u64,
         };
 
-        // Reject frames larger than the maximum allowed payload
-        if payload_len > MAX_FRAME_PAYLOAD {
-            return None;
+        // Reject frames larger than the maximum allowed payload before allocating
+        if payload_len > max_message_size as u64 {
+            return Err(FrameError::TooBig);
         }
 
         // Get masking key if present
-        let (mask_offset, mask) = if masked {
-            if buf.len() < payload_offset + 4 {
-                return None;
-            }
-            (
-                payload_offset + 4,
-                [
-                    buf[payload_offset],
-                    buf[payload_offset + 1],
-                    buf[payload_offset + 2],
-                    buf[payload_offset + 3],
-                ],
-            )
-        } else {
-            (payload_offset, [0; 4])
-        };
-
-        // Bounds check before slicing the buffer
-        if mask_offset + payload_len > buf.len() {
-            return None;
+        let mut mask = [0; 4];
+        if masked {
+            reader.read_exact(&mut mask)?;
         }
 
-        // Unmask and collect payload
-        let mut payload = buf[mask_offset..mask_offset + payload_len].to_vec();
+        // Read and unmask payload
+        let mut payload = vec![0; payload_len as usize];
+        reader.read_exact(&mut payload)?;
         if masked {
             for (byte, &key) in payload.iter_mut().zip(mask.iter().cycle()) {
                 *byte ^= key;
             }
         }
 
-        // Return appropriate message type
+        // Return appropriate message type, text must be valid UTF-8
         match opcode {
-            0x1 => Some(Message::Text(
-                String::from_utf8_lossy(&payload).into_owned(),
-            )),
-            0x2 => Some(Message::Binary(payload)),
+            0x1 => String::from_utf8(payload)
+                .map(Message::Text)
+                .map_err(|_| FrameError::InvalidUtf8),
+            0x2 => Ok(Message::Binary(payload)),
             0x8 => {
                 let code = if payload.len() >= 2 {
                     Some(u16::from_be_bytes([payload[0], payload[1]]))
@@ -212,38 +389,45 @@ impl WebSocket {
                     None
                 };
                 let reason = if payload.len() > 2 {
-                    Some(String::from_utf8_lossy(&payload[2..]).into_owned())
+                    Some(
+                        String::from_utf8(payload[2..].to_vec())
+                            .map_err(|_| FrameError::InvalidUtf8)?,
+                    )
                 } else {
                     None
                 };
-                Some(Message::Close(code, reason))
+                Ok(Message::Close(code, reason))
             }
-            0x9 => Some(Message::Ping(payload)),
-            0xA => Some(Message::Pong(payload)),
-            _ => None,
+            0x9 => Ok(Message::Ping(payload)),
+            0xA => Ok(Message::Pong(payload)),
+            _ => Err(FrameError::Invalid),
         }
     }
 
     /// Write a WebSocket message
-    pub fn send(&mut self, message: Message) -> std::io::Result<()> {
+    pub fn send(&mut self, message: Message) -> io::Result<()> {
+        self.connection.send(message)
+    }
+
+    fn encode_message(message: Message) -> Vec<u8> {
         let mut frame = Vec::new();
         match message {
             Message::Text(text) => {
                 frame.push(0x81); // Text frame
                 let payload = text.into_bytes();
-                self.write_frame(&mut frame, &payload)?;
+                Self::write_frame(&mut frame, &payload);
             }
             Message::Binary(data) => {
                 frame.push(0x82); // Binary frame
-                self.write_frame(&mut frame, &data)?;
+                Self::write_frame(&mut frame, &data);
             }
             Message::Ping(data) => {
                 frame.push(0x89); // Ping frame
-                self.write_frame(&mut frame, &data)?;
+                Self::write_frame(&mut frame, &data);
             }
             Message::Pong(data) => {
                 frame.push(0x8A); // Pong frame
-                self.write_frame(&mut frame, &data)?;
+                Self::write_frame(&mut frame, &data);
             }
             Message::Close(code, reason) => {
                 frame.push(0x88); // Close frame
@@ -254,16 +438,13 @@ impl WebSocket {
                 if let Some(r) = reason {
                     payload.extend_from_slice(r.as_bytes());
                 }
-                self.write_frame(&mut frame, &payload)?;
+                Self::write_frame(&mut frame, &payload);
             }
         }
-        self.stream
-            .lock()
-            .expect("Can't get lock")
-            .write_all(&frame)
+        frame
     }
 
-    fn write_frame(&self, frame: &mut Vec<u8>, payload: &[u8]) -> std::io::Result<()> {
+    fn write_frame(frame: &mut Vec<u8>, payload: &[u8]) {
         let payload_len = payload.len();
         if payload_len <= 125 {
             frame.push(payload_len as u8);
@@ -275,7 +456,68 @@ impl WebSocket {
             frame.extend_from_slice(&(payload_len as u64).to_be_bytes());
         }
         frame.extend_from_slice(payload);
-        Ok(())
+    }
+}
+
+// MARK: WebSocketReader
+/// Reading half of a [`WebSocket`]
+pub struct WebSocketReader {
+    connection: Arc<Connection>,
+}
+
+impl WebSocketReader {
+    /// Set read timeout, a `recv` that times out returns a `WouldBlock` or `TimedOut` error
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.connection.stream.set_read_timeout(timeout)
+    }
+
+    /// Set maximum accepted message payload size, see [`WebSocket::set_max_message_size`]
+    pub fn set_max_message_size(&self, max_message_size: usize) {
+        self.connection
+            .max_message_size
+            .store(max_message_size, Ordering::Relaxed);
+    }
+
+    /// Receive WebSocket message
+    pub fn recv(&mut self) -> io::Result<Message> {
+        self.connection.recv()
+    }
+
+    /// Receive WebSocket message without blocking
+    pub fn recv_non_blocking(&mut self) -> io::Result<Option<Message>> {
+        self.connection.recv_non_blocking()
+    }
+}
+
+// MARK: WebSocketWriter
+/// Writing half of a [`WebSocket`], clones share the same connection
+#[derive(Clone)]
+pub struct WebSocketWriter {
+    connection: Arc<Connection>,
+}
+
+impl PartialEq for WebSocketWriter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.connection, &other.connection)
+    }
+}
+impl Eq for WebSocketWriter {}
+
+impl WebSocketWriter {
+    /// Set write timeout
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.connection.stream.set_write_timeout(timeout)
+    }
+
+    /// Write a WebSocket message
+    pub fn send(&mut self, message: Message) -> io::Result<()> {
+        self.connection.send(message)
+    }
+
+    // Shut down the connection, which also unblocks a reader that waits on a message
+    #[cfg(feature = "client")]
+    pub(crate) fn shutdown(&self) {
+        _ = self.connection.stream.shutdown(std::net::Shutdown::Both);
     }
 }
 
@@ -291,9 +533,21 @@ impl Display for ConnectError {
 
 impl Error for ConnectError {}
 
+// MARK: Upgrade
 /// Upgrade HTTP request to WebSocket connection.
 /// Returns a 400 Bad Request response if the request does not conform to RFC 6455.
 pub fn upgrade(request: &Request, handler: impl FnOnce(WebSocket) + Send + 'static) -> Response {
+    upgrade_with_protocols(request, &[], handler)
+}
+
+/// Upgrade HTTP request to WebSocket connection with a list of supported subprotocols, the
+/// first subprotocol requested by the client that is supported is selected and available via
+/// [`WebSocket::protocol`]. When none match the connection is upgraded without a subprotocol.
+pub fn upgrade_with_protocols(
+    request: &Request,
+    protocols: &[&str],
+    handler: impl FnOnce(WebSocket) + Send + 'static,
+) -> Response {
     // Validate required WebSocket upgrade headers (RFC 6455 Section 4.2.1)
     let upgrade_ok = request
         .headers
@@ -323,7 +577,20 @@ pub fn upgrade(request: &Request, handler: impl FnOnce(WebSocket) + Send + 'stat
         "Sec-WebSocket-Accept",
         BASE64_STANDARD.encode(hasher.finalize()),
     );
-    res = res.takeover(|stream| handler(WebSocket::new(stream)));
+    let protocol = request
+        .headers
+        .get("Sec-WebSocket-Protocol")
+        .and_then(|requested| {
+            requested
+                .split(',')
+                .map(str::trim)
+                .find(|requested| protocols.contains(requested))
+        })
+        .map(str::to_string);
+    if let Some(protocol) = &protocol {
+        res = res.header("Sec-WebSocket-Protocol", protocol);
+    }
+    res = res.takeover(|stream| handler(WebSocket::new(stream, protocol)));
     res
 }
 
@@ -359,6 +626,103 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_websocket_protocol_and_headers() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            small_http::serve(listener, |req| {
+                if req.headers.get("Authorization") != Some("Bearer token") {
+                    return Response::with_status(Status::Unauthorized);
+                }
+                upgrade_with_protocols(req, &["mqtt", "graphql-ws"], |mut ws| {
+                    if let Ok(Message::Text(_)) = ws.recv() {
+                        let protocol = ws.protocol().unwrap_or_default().to_string();
+                        ws.send(Message::Text(protocol)).unwrap();
+                    }
+                })
+            });
+        });
+        let url = format!("ws://{}:{}/", addr.ip(), addr.port());
+
+        let mut ws = WebSocket::connect_with_options(
+            &url,
+            ConnectOptions::new()
+                .protocol("graphql-transport-ws")
+                .protocol("graphql-ws")
+                .header("Authorization", "Bearer token"),
+        )
+        .unwrap();
+        assert_eq!(ws.protocol(), Some("graphql-ws"));
+        ws.send(Message::Text("Protocol?".to_string())).unwrap();
+        assert!(matches!(ws.recv().unwrap(), Message::Text(t) if t == "graphql-ws"));
+
+        assert!(WebSocket::connect(&url).is_err());
+    }
+
+    #[test]
+    fn test_upgrade_protocol_selection() {
+        let req = Request::new()
+            .header("Upgrade", "websocket")
+            .header("Connection", "Upgrade")
+            .header("Sec-WebSocket-Version", "13")
+            .header("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ==")
+            .header("Sec-WebSocket-Protocol", "v2.chat, chat");
+        let res = upgrade_with_protocols(&req, &["chat"], |_ws| {});
+        assert_eq!(res.headers.get("Sec-WebSocket-Protocol"), Some("chat"));
+
+        let res = upgrade_with_protocols(&req, &["mqtt"], |_ws| {});
+        assert_eq!(res.status, Status::SwitchingProtocols);
+        assert!(res.headers.get("Sec-WebSocket-Protocol").is_none());
+    }
+
+    #[test]
+    fn test_websocket_split() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            small_http::serve(listener, |req| {
+                upgrade(req, |mut ws| {
+                    while let Ok(Message::Text(text)) = ws.recv() {
+                        ws.send(Message::Text(text)).unwrap();
+                    }
+                })
+            });
+        });
+
+        // Reader blocks on recv in another thread while the writer sends
+        let ws = WebSocket::connect(format!("ws://{}:{}/", addr.ip(), addr.port())).unwrap();
+        let (mut reader, mut writer) = ws.split();
+        let receiver = std::thread::spawn(move || reader.recv().unwrap());
+        std::thread::sleep(Duration::from_millis(50));
+        writer.send(Message::Text("Hello".to_string())).unwrap();
+        assert!(matches!(receiver.join().unwrap(), Message::Text(t) if t == "Hello"));
+    }
+
+    #[test]
+    fn test_websocket_read_timeout() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            small_http::serve(listener, |req| {
+                upgrade(req, |mut ws| while ws.recv().is_ok() {})
+            });
+        });
+
+        let mut ws = WebSocket::connect(format!("ws://{}:{}/", addr.ip(), addr.port())).unwrap();
+        ws.set_read_timeout(Some(Duration::from_millis(50)))
+            .unwrap();
+        let err = ws.recv().unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        ));
+    }
+
+    fn parse_message(frame: &[u8]) -> Option<Message> {
+        WebSocket::read_message(&mut &frame[..], DEFAULT_MAX_MESSAGE_SIZE).ok()
+    }
+
     // Build a minimal unmasked WebSocket frame: FIN + opcode, then length, then payload
     fn make_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
         let mut frame = vec![0x80 | opcode];
@@ -376,28 +740,24 @@ mod test {
     #[test]
     fn test_parse_text_frame() {
         let frame = make_frame(0x1, b"Hello");
-        let msg = WebSocket::parse_message(&frame).unwrap();
+        let msg = parse_message(&frame).unwrap();
         assert!(matches!(msg, Message::Text(t) if t == "Hello"));
     }
 
     #[test]
     fn test_parse_binary_frame() {
         let frame = make_frame(0x2, &[0xDE, 0xAD, 0xBE, 0xEF]);
-        let msg = WebSocket::parse_message(&frame).unwrap();
+        let msg = parse_message(&frame).unwrap();
         assert!(matches!(msg, Message::Binary(b) if b == [0xDE, 0xAD, 0xBE, 0xEF]));
     }
 
     #[test]
     fn test_parse_ping_pong_frames() {
         let ping = make_frame(0x9, b"ping-data");
-        assert!(
-            matches!(WebSocket::parse_message(&ping).unwrap(), Message::Ping(b) if b == b"ping-data")
-        );
+        assert!(matches!(parse_message(&ping).unwrap(), Message::Ping(b) if b == b"ping-data"));
 
         let pong = make_frame(0xA, b"pong-data");
-        assert!(
-            matches!(WebSocket::parse_message(&pong).unwrap(), Message::Pong(b) if b == b"pong-data")
-        );
+        assert!(matches!(parse_message(&pong).unwrap(), Message::Pong(b) if b == b"pong-data"));
     }
 
     #[test]
@@ -406,7 +766,7 @@ mod test {
         let mut payload = vec![0x03u8, 0xE8]; // 1000
         payload.extend_from_slice(b"bye");
         let frame = make_frame(0x8, &payload);
-        match WebSocket::parse_message(&frame).unwrap() {
+        match parse_message(&frame).unwrap() {
             Message::Close(code, reason) => {
                 assert_eq!(code, Some(1000));
                 assert_eq!(reason.as_deref(), Some("bye"));
@@ -418,7 +778,7 @@ mod test {
     #[test]
     fn test_parse_close_frame_no_payload() {
         let frame = make_frame(0x8, &[]);
-        match WebSocket::parse_message(&frame).unwrap() {
+        match parse_message(&frame).unwrap() {
             Message::Close(code, reason) => {
                 assert_eq!(code, None);
                 assert_eq!(reason, None);
@@ -442,7 +802,7 @@ mod test {
         frame.extend_from_slice(&mask);
         frame.extend_from_slice(&masked);
 
-        let msg = WebSocket::parse_message(&frame).unwrap();
+        let msg = parse_message(&frame).unwrap();
         assert!(matches!(msg, Message::Text(t) if t == "Hello"));
     }
 
@@ -453,21 +813,93 @@ mod test {
         let frame = make_frame(0x2, &payload);
         assert_eq!(frame[1], 126); // extended length marker
         assert_eq!(u16::from_be_bytes([frame[2], frame[3]]), 200);
-        let msg = WebSocket::parse_message(&frame).unwrap();
+        let msg = parse_message(&frame).unwrap();
         assert!(matches!(msg, Message::Binary(b) if b.len() == 200));
     }
 
     #[test]
     fn test_parse_unknown_opcode_returns_none() {
         let frame = make_frame(0x3, b"data"); // 0x3 is reserved/unknown
-        assert!(WebSocket::parse_message(&frame).is_none());
+        assert!(parse_message(&frame).is_none());
     }
 
     #[test]
     fn test_parse_truncated_frame_returns_none() {
         // Frame header says 10 bytes but buffer has only 3
         let frame = vec![0x82u8, 10u8, 0x01]; // binary, 10 bytes, only 1 provided
-        assert!(WebSocket::parse_message(&frame).is_none());
+        assert!(parse_message(&frame).is_none());
+    }
+
+    #[test]
+    fn test_parse_too_big_frame() {
+        let frame = make_frame(0x2, &[0u8; 200]);
+        assert!(matches!(
+            WebSocket::read_message(&mut &frame[..], 100),
+            Err(FrameError::TooBig)
+        ));
+
+        // 64-bit length is rejected before reading the payload
+        let mut frame = vec![0x82u8, 127];
+        frame.extend_from_slice(&u64::MAX.to_be_bytes());
+        assert!(matches!(
+            WebSocket::read_message(&mut &frame[..], DEFAULT_MAX_MESSAGE_SIZE),
+            Err(FrameError::TooBig)
+        ));
+    }
+
+    #[test]
+    fn test_parse_invalid_utf8_text_frame() {
+        let frame = make_frame(0x1, &[0x48, 0xFF, 0xFE]);
+        assert!(matches!(
+            WebSocket::read_message(&mut &frame[..], DEFAULT_MAX_MESSAGE_SIZE),
+            Err(FrameError::InvalidUtf8)
+        ));
+
+        let frame = make_frame(0x8, &[0x03, 0xE8, 0xC3]);
+        assert!(matches!(
+            WebSocket::read_message(&mut &frame[..], DEFAULT_MAX_MESSAGE_SIZE),
+            Err(FrameError::InvalidUtf8)
+        ));
+    }
+
+    #[test]
+    fn test_websocket_max_message_size() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            small_http::serve(listener, |req| {
+                upgrade(req, |mut ws| {
+                    ws.set_max_message_size(16);
+                    while let Ok(Message::Text(text)) = ws.recv() {
+                        ws.send(Message::Text(text)).unwrap();
+                    }
+                })
+            });
+        });
+
+        let mut ws = WebSocket::connect(format!("ws://{}:{}/", addr.ip(), addr.port())).unwrap();
+        ws.send(Message::Text("Small".to_string())).unwrap();
+        assert!(matches!(ws.recv().unwrap(), Message::Text(t) if t == "Small"));
+        ws.send(Message::Text("This message is too big".to_string()))
+            .unwrap();
+        assert!(matches!(ws.recv().unwrap(), Message::Close(Some(1009), _)));
+    }
+
+    #[test]
+    fn test_websocket_invalid_utf8_closes() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            small_http::serve(listener, |req| {
+                upgrade(req, |mut ws| while ws.recv().is_ok() {})
+            });
+        });
+
+        let mut ws = WebSocket::connect(format!("ws://{}:{}/", addr.ip(), addr.port())).unwrap();
+        (&ws.connection.stream)
+            .write_all(&make_frame(0x1, &[0xC3, 0x28]))
+            .unwrap();
+        assert!(matches!(ws.recv().unwrap(), Message::Close(Some(1007), _)));
     }
 
     #[test]
@@ -0,0 +1,356 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use crate::{ConnectOptions, Message, WebSocket, WebSocketWriter};
+
+type OnConnect = Box<dyn FnMut(&mut WebSocketWriter) -> io::Result<()> + Send + 'static>;
+type OnMessage = Box<dyn FnMut(Message) + Send + 'static>;
+
+// MARK: WsClientBuilder
+/// [`WsClient`] builder
+pub struct WsClientBuilder {
+    url: String,
+    options: ConnectOptions,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    max_queued_messages: usize,
+    on_connect: Option<OnConnect>,
+    on_message: Option<OnMessage>,
+}
+
+impl WsClientBuilder {
+    /// Set connect options used for every (re)connect
+    pub fn options(mut self, options: ConnectOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the delay before the first reconnect attempt, defaults to 500 ms. Every failed
+    /// attempt doubles the delay until the maximum backoff
+    pub const fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Set the maximum delay between reconnect attempts, defaults to 30 seconds
+    pub const fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Set the maximum amount of messages queued while disconnected, defaults to 1024. When the
+    /// queue is full the oldest message is dropped
+    pub const fn max_queued_messages(mut self, max_queued_messages: usize) -> Self {
+        self.max_queued_messages = max_queued_messages;
+        self
+    }
+
+    /// Set hook that is called after every (re)connect before the queued messages are sent,
+    /// use it to authenticate or resubscribe. An error drops the connection and reconnects
+    pub fn on_connect(
+        mut self,
+        on_connect: impl FnMut(&mut WebSocketWriter) -> io::Result<()> + Send + 'static,
+    ) -> Self {
+        self.on_connect = Some(Box::new(on_connect));
+        self
+    }
+
+    /// Set handler for received text and binary messages, called from the background thread
+    pub fn on_message(mut self, on_message: impl FnMut(Message) + Send + 'static) -> Self {
+        self.on_message = Some(Box::new(on_message));
+        self
+    }
+
+    /// Build client and start connecting in a background thread
+    pub fn build(self) -> WsClient {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                writer: None,
+                queue: VecDeque::new(),
+                closed: false,
+            }),
+            closed_condvar: Condvar::new(),
+            max_queued_messages: self.max_queued_messages,
+        });
+        std::thread::spawn({
+            let shared = shared.clone();
+            move || shared.run(self)
+        });
+        WsClient { shared }
+    }
+}
+
+// MARK: WsClient
+/// WebSocket client that keeps a connection to a server, it reconnects with exponential backoff
+/// and queues outgoing messages while disconnected
+///
+/// ```no_run
+/// use small_websocket::{Message, WsClient};
+///
+/// let client = WsClient::builder("ws://localhost:8080/events")
+///     .on_connect(|ws| ws.send(Message::Text("subscribe".to_string())))
+///     .on_message(|message| println!("{message:?}"))
+///     .build();
+/// client.send(Message::Text("Hello".to_string()));
+/// ```
+pub struct WsClient {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    closed_condvar: Condvar,
+    max_queued_messages: usize,
+}
+
+struct State {
+    writer: Option<WebSocketWriter>,
+    queue: VecDeque<Message>,
+    closed: bool,
+}
+
+impl WsClient {
+    /// Create new client builder
+    pub fn builder(url: impl Into<String>) -> WsClientBuilder {
+        WsClientBuilder {
+            url: url.into(),
+            options: ConnectOptions::new(),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_queued_messages: 1024,
+            on_connect: None,
+            on_message: None,
+        }
+    }
+
+    /// Check if the client is currently connected
+    pub fn is_connected(&self) -> bool {
+        self.shared
+            .state
+            .lock()
+            .expect("Can't get lock")
+            .writer
+            .is_some()
+    }
+
+    /// Send message, when disconnected the message is queued until the client is reconnected
+    pub fn send(&self, message: Message) {
+        let mut state = self.shared.state.lock().expect("Can't get lock");
+        if state.closed {
+            return;
+        }
+        if let Some(writer) = &mut state.writer {
+            match writer.send(message.clone()) {
+                Ok(()) => return,
+                // Shut the connection down so the background thread reconnects
+                Err(_) => {
+                    writer.shutdown();
+                    state.writer = None;
+                }
+            }
+        }
+        self.shared.enqueue(&mut state, message);
+    }
+
+    /// Close the connection and stop reconnecting, queued messages are dropped
+    pub fn close(&self) {
+        let mut state = self.shared.state.lock().expect("Can't get lock");
+        state.closed = true;
+        state.queue.clear();
+        if let Some(mut writer) = state.writer.take() {
+            _ = writer.send(Message::Close(Some(1000), None));
+            writer.shutdown();
+        }
+        self.shared.closed_condvar.notify_all();
+    }
+}
+
+impl Drop for WsClient {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Shared {
+    fn enqueue(&self, state: &mut State, message: Message) {
+        if self.max_queued_messages == 0 {
+            return;
+        }
+        if state.queue.len() >= self.max_queued_messages {
+            state.queue.pop_front();
+        }
+        state.queue.push_back(message);
+    }
+
+    fn run(&self, mut builder: WsClientBuilder) {
+        let mut backoff = builder.initial_backoff;
+        loop {
+            if let Ok(ws) = WebSocket::connect_with_options(&builder.url, builder.options.clone()) {
+                let (mut reader, mut writer) = ws.split();
+                if self.on_connected(&mut builder, &mut writer) {
+                    backoff = builder.initial_backoff;
+                    loop {
+                        match reader.recv() {
+                            Ok(Message::Ping(data)) => _ = writer.send(Message::Pong(data)),
+                            Ok(Message::Pong(_)) => {}
+                            Ok(Message::Close(_, _)) | Err(_) => break,
+                            Ok(message) => {
+                                if let Some(on_message) = &mut builder.on_message {
+                                    on_message(message);
+                                }
+                            }
+                        }
+                    }
+                }
+                writer.shutdown();
+                let mut state = self.state.lock().expect("Can't get lock");
+                if state.writer.as_ref() == Some(&writer) {
+                    state.writer = None;
+                }
+            }
+
+            // Wait before reconnecting, closing the client stops the wait
+            let state = self.state.lock().expect("Can't get lock");
+            let (state, _) = self
+                .closed_condvar
+                .wait_timeout_while(state, backoff, |state| !state.closed)
+                .expect("Can't get lock");
+            if state.closed {
+                return;
+            }
+            backoff = (backoff * 2).min(builder.max_backoff);
+        }
+    }
+
+    // Run the on connect hook and flush the queue, returns if the connection can be used
+    fn on_connected(&self, builder: &mut WsClientBuilder, writer: &mut WebSocketWriter) -> bool {
+        if let Some(on_connect) = &mut builder.on_connect
+            && on_connect(writer).is_err()
+        {
+            return false;
+        }
+
+        // The queue is flushed while holding the lock, so new messages stay in order
+        let mut state = self.state.lock().expect("Can't get lock");
+        if state.closed {
+            return false;
+        }
+        while let Some(message) = state.queue.pop_front() {
+            if writer.send(message.clone()).is_err() {
+                state.queue.push_front(message);
+                return false;
+            }
+        }
+        state.writer = Some(writer.clone());
+        true
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use std::net::{Ipv4Addr, TcpListener};
+    use std::sync::mpsc;
+
+    use super::*;
+    use crate::upgrade;
+
+    #[test]
+    fn test_ws_client_queue_and_on_connect() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            small_http::serve(listener, |req| {
+                upgrade(req, |mut ws| {
+                    while let Ok(Message::Text(text)) = ws.recv() {
+                        ws.send(Message::Text(text)).unwrap();
+                    }
+                })
+            });
+        });
+
+        // Messages sent before the connection is made are queued after the on connect hook
+        let (tx, rx) = mpsc::channel();
+        let client = WsClient::builder(format!("ws://{}:{}/", addr.ip(), addr.port()))
+            .on_connect(|ws| ws.send(Message::Text("subscribe".to_string())))
+            .on_message(move |message| {
+                if let Message::Text(text) = message {
+                    tx.send(text).unwrap();
+                }
+            })
+            .build();
+        client.send(Message::Text("Hello".to_string()));
+
+        let timeout = Duration::from_secs(5);
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), "subscribe");
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), "Hello");
+        assert!(client.is_connected());
+        client.send(Message::Text("World".to_string()));
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), "World");
+    }
+
+    #[test]
+    fn test_ws_client_reconnect() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            small_http::serve(listener, |req| {
+                // Every connection greets and is then dropped by the server
+                upgrade(req, |mut ws| {
+                    ws.send(Message::Text("Welcome".to_string())).unwrap();
+                })
+            });
+        });
+
+        let (tx, rx) = mpsc::channel();
+        let _client = WsClient::builder(format!("ws://{}:{}/", addr.ip(), addr.port()))
+            .initial_backoff(Duration::from_millis(10))
+            .on_message(move |message| {
+                if let Message::Text(text) = message {
+                    _ = tx.send(text);
+                }
+            })
+            .build();
+
+        let timeout = Duration::from_secs(5);
+        for _ in 0..3 {
+            assert_eq!(rx.recv_timeout(timeout).unwrap(), "Welcome");
+        }
+    }
+
+    #[test]
+    fn test_ws_client_max_queued_messages() {
+        // Nothing listens on this port, so the client stays disconnected
+        let addr = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let client = WsClient::builder(format!("ws://{}:{}/", addr.ip(), addr.port()))
+            .max_queued_messages(2)
+            .build();
+        for i in 0..3 {
+            client.send(Message::Text(i.to_string()));
+        }
+        let state = client.shared.state.lock().unwrap();
+        assert!(state.writer.is_none());
+        assert_eq!(
+            state
+                .queue
+                .iter()
+                .map(|message| match message {
+                    Message::Text(text) => text.as_str(),
+                    _ => "",
+                })
+                .collect::<Vec<_>>(),
+            ["1", "2"]
+        );
+    }
+}
@@ -10,10 +10,21 @@
 
 use std::mem::MaybeUninit;
 
+pub use crate::resize::resize_events;
+pub use crate::terminal::{
+    RawMode, Stream, clear_line, clear_screen, enable_ansi_support, hide_cursor, is_tty,
+    move_cursor, move_cursor_to_line_start, move_cursor_up, show_cursor, supports_ansi,
+};
+
+mod resize;
+mod terminal;
+
 /// Represents the width of a terminal in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Width(pub u16);
 
 /// Represents the height of a terminal in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Height(pub u16);
 
 /// Returns the terminal size as a tuple.
@@ -0,0 +1,212 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+#![allow(unsafe_code)]
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Mutex, Once};
+
+use crate::{Height, Width, terminal_size};
+
+static SUBSCRIBERS: Mutex<Vec<Sender<(Width, Height)>>> = Mutex::new(Vec::new());
+static WATCHER: Once = Once::new();
+
+/// Returns a receiver that gets the new terminal size every time the terminal is resized.
+///
+/// The terminal is watched by a single background thread that is started on the first call,
+/// subscriptions end when their receiver is dropped.
+pub fn resize_events() -> Receiver<(Width, Height)> {
+    let (tx, rx) = mpsc::channel();
+    SUBSCRIBERS
+        .lock()
+        .expect("Subscribers mutex poisoned")
+        .push(tx);
+    WATCHER.call_once(start_watcher);
+    rx
+}
+
+fn notify_resize(size: (Width, Height)) {
+    SUBSCRIBERS
+        .lock()
+        .expect("Subscribers mutex poisoned")
+        .retain(|tx| tx.send(size).is_ok());
+}
+
+fn start_watcher() {
+    cfg_select! {
+        unix => {
+            use std::sync::OnceLock;
+            use std::sync::atomic::{AtomicI32, Ordering};
+
+            static PIPE_WRITE_FD: AtomicI32 = AtomicI32::new(-1);
+            static OLD_ACTION: OnceLock<libc::sigaction> = OnceLock::new();
+
+            type SigInfoHandler =
+                extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void);
+
+            fn errno_location() -> *mut libc::c_int {
+                // SAFETY: These functions only return the address of the errno of this thread.
+                unsafe {
+                    cfg_select! {
+                        any(target_os = "linux", target_os = "emscripten") => {
+                            libc::__errno_location()
+                        }
+                        any(target_os = "android", target_os = "openbsd", target_os = "netbsd") => {
+                            libc::__errno()
+                        }
+                        _ => {
+                            libc::__error()
+                        }
+                    }
+                }
+            }
+
+            extern "C" fn on_sigwinch(
+                signal: libc::c_int,
+                info: *mut libc::siginfo_t,
+                context: *mut libc::c_void,
+            ) {
+                // Only async-signal-safe calls are allowed here, so wake the watcher thread
+                // through the self-pipe. The write can change errno, so it is restored for the
+                // interrupted code
+                // SAFETY: errno_location returns a valid pointer to the errno of this thread.
+                let saved_errno = unsafe { *errno_location() };
+                let fd = PIPE_WRITE_FD.load(Ordering::Relaxed);
+                // SAFETY: fd is the non-blocking write end of the self-pipe and the buffer is a
+                // valid one byte buffer.
+                _ = unsafe { libc::write(fd, [0u8].as_ptr().cast(), 1) };
+
+                // Chain to the handler that was installed before ours
+                if let Some(old_action) = OLD_ACTION.get() {
+                    let handler = old_action.sa_sigaction;
+                    if handler != libc::SIG_DFL && handler != libc::SIG_IGN {
+                        if old_action.sa_flags & libc::SA_SIGINFO != 0 {
+                            // SAFETY: With SA_SIGINFO the handler has the three argument signature.
+                            let handler: SigInfoHandler = unsafe { std::mem::transmute(handler) };
+                            handler(signal, info, context);
+                        } else {
+                            // SAFETY: Without SA_SIGINFO the handler has the one argument signature.
+                            let handler: extern "C" fn(libc::c_int) =
+                                unsafe { std::mem::transmute(handler) };
+                            handler(signal);
+                        }
+                    }
+                }
+                // SAFETY: errno_location returns a valid pointer to the errno of this thread.
+                unsafe { *errno_location() = saved_errno };
+            }
+
+            let mut fds = [0; 2];
+            // SAFETY: fds is a valid array of two file descriptors for pipe to fill.
+            if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+                return;
+            }
+            let [read_fd, write_fd] = fds;
+            // SAFETY: write_fd is the valid write end of the pipe created above.
+            unsafe { libc::fcntl(write_fd, libc::F_SETFL, libc::O_NONBLOCK) };
+            PIPE_WRITE_FD.store(write_fd, Ordering::Relaxed);
+
+            // Store the current handler before installing ours, so the first signal is chained
+            // SAFETY: sigaction is a plain C struct for which all zeroes is a valid value.
+            let mut old_action: libc::sigaction = unsafe { std::mem::zeroed() };
+            // SAFETY: A null action only queries the current action into old_action.
+            if unsafe { libc::sigaction(libc::SIGWINCH, std::ptr::null(), &mut old_action) } != 0 {
+                return;
+            }
+            _ = OLD_ACTION.set(old_action);
+
+            // SAFETY: sigaction is a plain C struct for which all zeroes is a valid value.
+            let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+            action.sa_sigaction = on_sigwinch as SigInfoHandler as libc::sighandler_t;
+            action.sa_flags = libc::SA_RESTART | libc::SA_SIGINFO;
+            // SAFETY: action.sa_mask is a valid sigset_t to initialize.
+            unsafe { libc::sigemptyset(&mut action.sa_mask) };
+            // SAFETY: action is fully initialized and on_sigwinch only performs async-signal-safe calls.
+            if unsafe { libc::sigaction(libc::SIGWINCH, &action, std::ptr::null_mut()) } != 0 {
+                return;
+            }
+
+            std::thread::spawn(move || {
+                let mut buffer = [0u8; 64];
+                loop {
+                    // SAFETY: read_fd is the valid read end of the self-pipe and buffer is a
+                    // valid writable buffer of the given length.
+                    let read =
+                        unsafe { libc::read(read_fd, buffer.as_mut_ptr().cast(), buffer.len()) };
+                    if read < 0
+                        && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted
+                    {
+                        continue;
+                    }
+                    if read <= 0 {
+                        break;
+                    }
+                    if let Some(size) = terminal_size() {
+                        notify_resize(size);
+                    }
+                }
+            });
+        }
+        windows => {
+            use std::time::Duration;
+
+            // Console resize events are delivered in the console input queue, reading them would
+            // swallow the key presses of the application, so poll the buffer size instead
+            const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+            std::thread::spawn(|| {
+                let mut last_size = terminal_size();
+                loop {
+                    std::thread::sleep(POLL_INTERVAL);
+                    let size = terminal_size();
+                    if size != last_size {
+                        if let Some(size) = size {
+                            notify_resize(size);
+                        }
+                        last_size = size;
+                    }
+                }
+            });
+        }
+        _ => {
+            compile_error!("Unsupported platform")
+        }
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_resize_events() {
+        let rx = resize_events();
+        let dropped_rx = resize_events();
+        drop(dropped_rx);
+
+        // Other tests can trigger real resize events, so skip those
+        notify_resize((Width(80), Height(24)));
+        assert!(
+            std::iter::from_fn(|| rx.recv_timeout(Duration::from_secs(1)).ok())
+                .any(|size| size == (Width(80), Height(24)))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sigwinch_is_handled() {
+        let rx = resize_events();
+        // SAFETY: SIGWINCH is handled by the watcher, so raising it does not terminate the process.
+        unsafe { libc::raise(libc::SIGWINCH) };
+        // Without a terminal there is no size to report
+        if let Some(size) = terminal_size() {
+            assert_eq!(rx.recv_timeout(Duration::from_secs(1)).unwrap(), size);
+        }
+    }
+}
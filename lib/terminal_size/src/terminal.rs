@@ -0,0 +1,294 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+#![allow(unsafe_code)]
+
+use std::io::{Error, Write};
+
+// MARK: Stream
+/// A standard stream of the process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    /// Standard input
+    Stdin,
+    /// Standard output
+    Stdout,
+    /// Standard error
+    Stderr,
+}
+
+#[cfg(windows)]
+mod windows {
+    use super::Stream;
+
+    pub(crate) type HANDLE = *mut std::ffi::c_void;
+    pub(crate) const INVALID_HANDLE_VALUE: HANDLE = (-1isize) as HANDLE;
+    pub(crate) const ENABLE_PROCESSED_INPUT: u32 = 0x0001;
+    pub(crate) const ENABLE_LINE_INPUT: u32 = 0x0002;
+    pub(crate) const ENABLE_ECHO_INPUT: u32 = 0x0004;
+    pub(crate) const ENABLE_VIRTUAL_TERMINAL_INPUT: u32 = 0x0200;
+    pub(crate) const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        pub(crate) fn GetStdHandle(nStdHandle: i32) -> HANDLE;
+        pub(crate) fn GetConsoleMode(hConsoleHandle: HANDLE, lpMode: *mut u32) -> i32;
+        pub(crate) fn SetConsoleMode(hConsoleHandle: HANDLE, dwMode: u32) -> i32;
+    }
+
+    /// Returns the console handle and mode of a standard stream
+    pub(crate) fn console_mode(stream: Stream) -> Result<(HANDLE, u32), std::io::Error> {
+        const STD_INPUT_HANDLE: i32 = -10;
+        const STD_OUTPUT_HANDLE: i32 = -11;
+        const STD_ERROR_HANDLE: i32 = -12;
+        let std_handle = match stream {
+            Stream::Stdin => STD_INPUT_HANDLE,
+            Stream::Stdout => STD_OUTPUT_HANDLE,
+            Stream::Stderr => STD_ERROR_HANDLE,
+        };
+
+        // SAFETY: std_handle is a valid standard handle constant for GetStdHandle.
+        let handle = unsafe { GetStdHandle(std_handle) };
+        if handle == INVALID_HANDLE_VALUE || handle.is_null() {
+            return Err(std::io::Error::last_os_error());
+        }
+        let mut mode = 0;
+        // SAFETY: handle is a valid handle (checked above); mode is a valid out-pointer.
+        if unsafe { GetConsoleMode(handle, &mut mode) } == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok((handle, mode))
+    }
+
+    /// Sets the console mode of a console handle
+    pub(crate) fn set_console_mode(handle: HANDLE, mode: u32) -> Result<(), std::io::Error> {
+        // SAFETY: handle is a valid console handle returned by console_mode.
+        if unsafe { SetConsoleMode(handle, mode) } == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether the standard stream is connected to a terminal.
+pub fn is_tty(stream: Stream) -> bool {
+    cfg_select! {
+        unix => {
+            let fd = match stream {
+                Stream::Stdin => libc::STDIN_FILENO,
+                Stream::Stdout => libc::STDOUT_FILENO,
+                Stream::Stderr => libc::STDERR_FILENO,
+            };
+            // SAFETY: isatty accepts any file descriptor and only reports whether it is a terminal.
+            unsafe { libc::isatty(fd) == 1 }
+        }
+        windows => {
+            windows::console_mode(stream).is_ok()
+        }
+        _ => {
+            compile_error!("Unsupported platform")
+        }
+    }
+}
+
+// MARK: ANSI
+/// Enables ANSI escape code support for the standard output and standard error consoles, this
+/// is only needed on Windows.
+// Const-compatible on Unix, but calls non-const Win32 APIs on Windows.
+#[allow(clippy::missing_const_for_fn)]
+pub fn enable_ansi_support() -> Result<(), Error> {
+    cfg_select! {
+        unix => {
+            Ok(())
+        }
+        windows => {
+            for stream in [Stream::Stdout, Stream::Stderr] {
+                if let Ok((handle, mode)) = windows::console_mode(stream) {
+                    windows::set_console_mode(
+                        handle,
+                        mode | windows::ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+                    )?;
+                }
+            }
+            Ok(())
+        }
+        _ => {
+            compile_error!("Unsupported platform")
+        }
+    }
+}
+
+/// Returns whether ANSI escape codes written to the standard stream are interpreted by a
+/// terminal, enabling ANSI support on Windows when needed.
+pub fn supports_ansi(stream: Stream) -> bool {
+    if !is_tty(stream) || std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return false;
+    }
+    cfg_select! {
+        unix => {
+            true
+        }
+        windows => {
+            windows::console_mode(stream).is_ok_and(|(handle, mode)| {
+                mode & windows::ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+                    || windows::set_console_mode(
+                        handle,
+                        mode | windows::ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+                    )
+                    .is_ok()
+            })
+        }
+        _ => {
+            compile_error!("Unsupported platform")
+        }
+    }
+}
+
+// MARK: Cursor
+/// Moves the cursor to the zero-based column and row.
+pub fn move_cursor(w: &mut impl Write, column: u16, row: u16) -> Result<(), Error> {
+    write!(w, "\x1b[{};{}H", row as u32 + 1, column as u32 + 1)
+}
+
+/// Moves the cursor to the start of the current line.
+pub fn move_cursor_to_line_start(w: &mut impl Write) -> Result<(), Error> {
+    w.write_all(b"\r")
+}
+
+/// Moves the cursor up by the given number of lines.
+pub fn move_cursor_up(w: &mut impl Write, lines: u16) -> Result<(), Error> {
+    if lines == 0 {
+        return Ok(());
+    }
+    write!(w, "\x1b[{lines}A")
+}
+
+/// Hides the cursor.
+pub fn hide_cursor(w: &mut impl Write) -> Result<(), Error> {
+    w.write_all(b"\x1b[?25l")
+}
+
+/// Shows the cursor.
+pub fn show_cursor(w: &mut impl Write) -> Result<(), Error> {
+    w.write_all(b"\x1b[?25h")
+}
+
+/// Clears the current line and moves the cursor to the start of it.
+pub fn clear_line(w: &mut impl Write) -> Result<(), Error> {
+    w.write_all(b"\r\x1b[2K")
+}
+
+/// Clears the screen and moves the cursor to the top left corner.
+pub fn clear_screen(w: &mut impl Write) -> Result<(), Error> {
+    w.write_all(b"\x1b[2J\x1b[H")
+}
+
+// MARK: Raw mode
+/// Guard that keeps the terminal in raw mode, the original mode is restored when dropped.
+pub struct RawMode {
+    #[cfg(unix)]
+    original: libc::termios,
+    #[cfg(windows)]
+    original: (windows::HANDLE, u32),
+}
+
+impl RawMode {
+    /// Puts the terminal connected to standard input in raw mode, input is then read byte by
+    /// byte without echo or line editing.
+    pub fn enter() -> Result<Self, Error> {
+        cfg_select! {
+            unix => {
+                let mut original = std::mem::MaybeUninit::<libc::termios>::uninit();
+                // SAFETY: original is a valid out-pointer that tcgetattr fills on success.
+                if unsafe { libc::tcgetattr(libc::STDIN_FILENO, original.as_mut_ptr()) } != 0 {
+                    return Err(Error::last_os_error());
+                }
+                // SAFETY: tcgetattr returned success, so original was fully initialized.
+                let original = unsafe { original.assume_init() };
+                let mut raw = original;
+                // SAFETY: raw is a valid termios struct obtained from tcgetattr.
+                unsafe { libc::cfmakeraw(&mut raw) };
+                // Keep output post-processing so '\n' still starts a new line
+                raw.c_oflag |= libc::OPOST;
+                // SAFETY: raw is a valid termios struct derived from the current terminal mode.
+                if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &raw) } != 0 {
+                    return Err(Error::last_os_error());
+                }
+                Ok(Self { original })
+            }
+            windows => {
+                let (handle, mode) = windows::console_mode(Stream::Stdin)?;
+                windows::set_console_mode(
+                    handle,
+                    (mode
+                        & !(windows::ENABLE_PROCESSED_INPUT
+                            | windows::ENABLE_LINE_INPUT
+                            | windows::ENABLE_ECHO_INPUT))
+                        | windows::ENABLE_VIRTUAL_TERMINAL_INPUT,
+                )?;
+                Ok(Self {
+                    original: (handle, mode),
+                })
+            }
+            _ => {
+                compile_error!("Unsupported platform")
+            }
+        }
+    }
+
+    /// Restores the original terminal mode.
+    pub fn exit(self) {}
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        cfg_select! {
+            unix => {
+                // SAFETY: original is the valid termios struct read by tcgetattr in enter.
+                unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &self.original) };
+            }
+            windows => {
+                let (handle, mode) = self.original;
+                _ = windows::set_console_mode(handle, mode);
+            }
+            _ => {
+                compile_error!("Unsupported platform")
+            }
+        }
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cursor_escape_codes() {
+        let mut out = Vec::new();
+        move_cursor(&mut out, 0, 0).unwrap();
+        move_cursor(&mut out, 9, 4).unwrap();
+        move_cursor_up(&mut out, 0).unwrap();
+        move_cursor_up(&mut out, 3).unwrap();
+        clear_line(&mut out).unwrap();
+        clear_screen(&mut out).unwrap();
+        hide_cursor(&mut out).unwrap();
+        show_cursor(&mut out).unwrap();
+        assert_eq!(
+            out,
+            b"\x1b[1;1H\x1b[5;10H\x1b[3A\r\x1b[2K\x1b[2J\x1b[H\x1b[?25l\x1b[?25h"
+        );
+    }
+
+    #[test]
+    fn test_supports_ansi_requires_tty() {
+        for stream in [Stream::Stdin, Stream::Stdout, Stream::Stderr] {
+            if !is_tty(stream) {
+                assert!(!supports_ansi(stream));
+            }
+        }
+    }
+}
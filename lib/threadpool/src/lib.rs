@@ -1,54 +1,148 @@
 /*
- * Copyright (c) 2023-2025 Bastiaan van der Plaat
+ * Copyright (c) 2023-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
 
 //! A minimal replacement for the [threadpool](https://crates.io/crates/threadpool
 
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::mpsc::{Sender, channel};
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
 
-// MARK: ThreadPool
-/// Thread pool for executing tasks on multiple worker threads
-pub struct ThreadPool {
-    workers: Vec<JoinHandle<()>>,
-    sender: Sender<Box<dyn FnOnce() + Send + 'static>>,
+type Hook = Arc<dyn Fn() + Send + Sync + 'static>;
+
+// Number of chunks per worker thread for map, so workers that finish early can pick up more work
+const CHUNKS_PER_WORKER: usize = 4;
+
+// MARK: ThreadPoolBuilder
+/// Builder for a [`ThreadPool`] with named worker threads and thread start and stop hooks
+pub struct ThreadPoolBuilder {
+    num_workers: usize,
+    name_prefix: Option<String>,
+    stack_size: Option<usize>,
+    on_thread_start: Option<Hook>,
+    on_thread_stop: Option<Hook>,
 }
 
-// Define the implementation of ThreadPool
-impl ThreadPool {
-    /// Creates a new ThreadPool with the specified number of worker threads.
-    /// Panics if num_workers is 0.
-    pub fn new(num_workers: usize) -> ThreadPool {
-        assert!(num_workers > 0, "Number of workers must be greater than 0");
+impl Default for ThreadPoolBuilder {
+    fn default() -> Self {
+        Self {
+            num_workers: thread::available_parallelism().map_or(1, |n| n.get()),
+            name_prefix: None,
+            stack_size: None,
+            on_thread_start: None,
+            on_thread_stop: None,
+        }
+    }
+}
+
+impl ThreadPoolBuilder {
+    /// Creates a new ThreadPoolBuilder with one worker thread per available CPU
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of worker threads
+    pub const fn num_workers(mut self, num_workers: usize) -> Self {
+        self.num_workers = num_workers;
+        self
+    }
+
+    /// Sets the worker thread name prefix, workers are named `{prefix}-{index}`
+    pub fn name_prefix(mut self, name_prefix: impl Into<String>) -> Self {
+        self.name_prefix = Some(name_prefix.into());
+        self
+    }
+
+    /// Sets the worker thread stack size in bytes
+    pub const fn stack_size(mut self, stack_size: usize) -> Self {
+        self.stack_size = Some(stack_size);
+        self
+    }
+
+    /// Sets a hook that runs on each worker thread when it starts, before any task
+    pub fn on_thread_start(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_thread_start = Some(Arc::new(f));
+        self
+    }
+
+    /// Sets a hook that runs on each worker thread when it stops, after its last task
+    pub fn on_thread_stop(mut self, f: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_thread_stop = Some(Arc::new(f));
+        self
+    }
+
+    /// Builds the ThreadPool and spawns its worker threads.
+    /// Panics if the number of workers is 0.
+    pub fn build(self) -> ThreadPool {
+        assert!(
+            self.num_workers > 0,
+            "Number of workers must be greater than 0"
+        );
 
         let (sender, receiver) = channel::<Box<dyn FnOnce() + Send + 'static>>();
         let receiver = Arc::new(Mutex::new(receiver));
-        let mut workers = Vec::with_capacity(num_workers);
+        let mut workers = Vec::with_capacity(self.num_workers);
 
         // Spawn worker threads
-        for _ in 0..num_workers {
+        for index in 0..self.num_workers {
+            let mut builder = thread::Builder::new();
+            if let Some(name_prefix) = &self.name_prefix {
+                builder = builder.name(format!("{name_prefix}-{index}"));
+            }
+            if let Some(stack_size) = self.stack_size {
+                builder = builder.stack_size(stack_size);
+            }
+
             let receiver = Arc::clone(&receiver);
-            let handle = thread::spawn(move || {
-                // Worker loop: receive and execute tasks
-                loop {
-                    let task = {
-                        let receiver = receiver.lock().expect("Mutex lock failed");
-                        match receiver.recv() {
-                            Ok(task) => task,
-                            Err(_) => return, // Channel closed, exit loop
-                        }
-                    };
-                    task();
-                }
-            });
+            let on_thread_start = self.on_thread_start.clone();
+            let on_thread_stop = self.on_thread_stop.clone();
+            let handle = builder
+                .spawn(move || {
+                    if let Some(on_thread_start) = on_thread_start {
+                        on_thread_start();
+                    }
+
+                    // Worker loop: receive and execute tasks
+                    loop {
+                        let task = {
+                            let receiver = receiver.lock().expect("Mutex lock failed");
+                            match receiver.recv() {
+                                Ok(task) => task,
+                                Err(_) => break, // Channel closed, exit loop
+                            }
+                        };
+                        task();
+                    }
+
+                    if let Some(on_thread_stop) = on_thread_stop {
+                        on_thread_stop();
+                    }
+                })
+                .expect("Can't spawn worker thread");
             workers.push(handle);
         }
 
         ThreadPool { workers, sender }
     }
+}
+
+// MARK: ThreadPool
+/// Thread pool for executing tasks on multiple worker threads
+pub struct ThreadPool {
+    workers: Vec<JoinHandle<()>>,
+    sender: Sender<Box<dyn FnOnce() + Send + 'static>>,
+}
+
+// Define the implementation of ThreadPool
+impl ThreadPool {
+    /// Creates a new ThreadPool with the specified number of worker threads.
+    /// Panics if num_workers is 0.
+    pub fn new(num_workers: usize) -> ThreadPool {
+        ThreadPoolBuilder::new().num_workers(num_workers).build()
+    }
 
     /// Executes a closure on an available worker thread.
     pub fn execute(&self, f: impl FnOnce() + Send + 'static) {
@@ -57,6 +151,72 @@ impl ThreadPool {
             .expect("A worker thread has died");
     }
 
+    /// Applies a function to all items in parallel and returns the results in input order.
+    /// The items are split into chunks which are executed as tasks, this blocks until all
+    /// chunks are done. A panic in the function is resumed on the calling thread.
+    pub fn map<T, R>(
+        &self,
+        iter: impl IntoIterator<Item = T>,
+        f: impl Fn(T) -> R + Send + Sync + 'static,
+    ) -> Vec<R>
+    where
+        T: Send + 'static,
+        R: Send + 'static,
+    {
+        let items = iter.into_iter().collect::<Vec<_>>();
+        let len = items.len();
+        let chunk_size = len.div_ceil(self.workers.len() * CHUNKS_PER_WORKER).max(1);
+
+        // Execute the chunks
+        let f = Arc::new(f);
+        let (sender, receiver) = channel();
+        let mut items = items.into_iter();
+        let mut chunk_count = 0;
+        loop {
+            let chunk = items.by_ref().take(chunk_size).collect::<Vec<_>>();
+            if chunk.is_empty() {
+                break;
+            }
+            let index = chunk_count;
+            let f = Arc::clone(&f);
+            let sender = sender.clone();
+            self.execute(move || {
+                let results = panic::catch_unwind(AssertUnwindSafe(|| {
+                    chunk.into_iter().map(|item| f(item)).collect::<Vec<_>>()
+                }));
+                _ = sender.send((index, results));
+            });
+            chunk_count += 1;
+        }
+        drop(sender);
+
+        // Collect the chunk results in input order
+        let mut chunks = (0..chunk_count).map(|_| None).collect::<Vec<_>>();
+        for (index, results) in receiver {
+            match results {
+                Ok(results) => chunks[index] = Some(results),
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        }
+        let mut results = Vec::with_capacity(len);
+        for chunk in chunks {
+            results.extend(chunk.expect("A worker thread has died"));
+        }
+        results
+    }
+
+    /// Calls a function on all items in parallel, this blocks until all items are done.
+    /// A panic in the function is resumed on the calling thread.
+    pub fn for_each_parallel<T>(
+        &self,
+        iter: impl IntoIterator<Item = T>,
+        f: impl Fn(T) + Send + Sync + 'static,
+    ) where
+        T: Send + 'static,
+    {
+        self.map(iter, f);
+    }
+
     /// Waits for all worker threads to finish their tasks.
     /// This consumes the ThreadPool since it joins all threads.
     pub fn join(self) {
@@ -116,6 +276,86 @@ mod test {
         assert_eq!(counter.load(Ordering::SeqCst), task_count); // All tasks should run
     }
 
+    #[test]
+    fn test_builder_names_and_hooks() {
+        let started = Arc::new(AtomicUsize::new(0));
+        let stopped = Arc::new(AtomicUsize::new(0));
+        let names = Arc::new(Mutex::new(Vec::new()));
+
+        let started_clone = Arc::clone(&started);
+        let stopped_clone = Arc::clone(&stopped);
+        let pool = ThreadPoolBuilder::new()
+            .num_workers(2)
+            .name_prefix("worker")
+            .stack_size(256 * 1024)
+            .on_thread_start(move || {
+                started_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .on_thread_stop(move || {
+                stopped_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .build();
+
+        for _ in 0..4 {
+            let names = Arc::clone(&names);
+            pool.execute(move || {
+                let name = thread::current().name().map(str::to_string);
+                names.lock().unwrap().push(name);
+            });
+        }
+        pool.join();
+
+        assert_eq!(started.load(Ordering::SeqCst), 2);
+        assert_eq!(stopped.load(Ordering::SeqCst), 2);
+        let names = names.lock().unwrap();
+        assert_eq!(names.len(), 4);
+        assert!(
+            names
+                .iter()
+                .all(|name| matches!(name.as_deref(), Some("worker-0" | "worker-1")))
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let pool = ThreadPool::new(3);
+        let results = pool.map(0..100, |n| {
+            // Later items finish first, results must still be in input order
+            thread::sleep(Duration::from_micros(100 - n));
+            n * 2
+        });
+        assert_eq!(results, (0..100).map(|n| n * 2).collect::<Vec<_>>());
+
+        assert!(pool.map(Vec::<u64>::new(), |n| n).is_empty());
+        assert_eq!(pool.map(["a", "bb"], str::len), [1, 2]);
+    }
+
+    #[test]
+    fn test_map_panic() {
+        let pool = ThreadPool::new(2);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            pool.map(0..10, |n| {
+                assert!(n != 5, "Item 5 failed");
+                n
+            })
+        }));
+        assert!(result.is_err());
+
+        // The workers survive the panic
+        assert_eq!(pool.map(0..4, |n| n + 1), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_for_each_parallel() {
+        let pool = ThreadPool::new(4);
+        let sum = Arc::new(AtomicUsize::new(0));
+        let sum_clone = Arc::clone(&sum);
+        pool.for_each_parallel(1..=100, move |n| {
+            sum_clone.fetch_add(n, Ordering::SeqCst);
+        });
+        assert_eq!(sum.load(Ordering::SeqCst), 5050);
+    }
+
     #[test]
     fn test_parallel_execution() {
         let pool = ThreadPool::new(2); // Two workers
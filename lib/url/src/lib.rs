@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -75,6 +75,133 @@ impl Url {
     pub fn fragment(&self) -> Option<&str> {
         self.fragment.as_deref()
     }
+
+    /// Append a key value pair to the URL query, both are form urlencoded
+    pub fn append_query_pair(&mut self, key: &str, value: &str) {
+        let mut query = self.query.take().unwrap_or_default();
+        if !query.is_empty() {
+            query.push('&');
+        }
+        form_urlencode(&mut query, key);
+        query.push('=');
+        form_urlencode(&mut query, value);
+        self.query = Some(query);
+    }
+
+    /// Append the fields of a struct to the URL query, the struct is form urlencoded
+    #[cfg(feature = "serde")]
+    pub fn with_query_struct<T: serde::Serialize + ?Sized>(
+        mut self,
+        value: &T,
+    ) -> Result<Self, QueryError> {
+        let pairs =
+            serde_urlencoded::to_string(value).map_err(|err| QueryError(err.to_string()))?;
+        if !pairs.is_empty() {
+            let mut query = self.query.take().unwrap_or_default();
+            if !query.is_empty() {
+                query.push('&');
+            }
+            query.push_str(&pairs);
+            self.query = Some(query);
+        }
+        Ok(self)
+    }
+
+    /// Parse the URL query into a struct
+    #[cfg(feature = "serde")]
+    pub fn query_as<T: serde::de::DeserializeOwned>(&self) -> Result<T, QueryError> {
+        serde_urlencoded::from_str(self.query.as_deref().unwrap_or_default())
+            .map_err(|err| QueryError(err.to_string()))
+    }
+
+    /// Normalize the URL so equivalent URLs compare equal: lowercase the scheme and host,
+    /// remove the default port, resolve dot segments in the path and drop an empty query
+    pub fn normalize(&mut self) {
+        self.scheme.make_ascii_lowercase();
+        if let Some(authority) = &mut self.authority {
+            authority.host.make_ascii_lowercase();
+            if authority.port.is_some() && authority.port == default_port(&self.scheme) {
+                authority.port = None;
+            }
+            self.path = remove_dot_segments(&self.path);
+            if self.path.is_empty() {
+                self.path = "/".to_string();
+            }
+        }
+        if self.query.as_deref() == Some("") {
+            self.query = None;
+        }
+    }
+
+    /// Sort the URL query pairs by key, keeping the order of pairs with the same key and
+    /// removing empty pairs
+    pub fn sort_query_pairs(&mut self) {
+        if let Some(query) = &self.query {
+            let mut pairs = query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .collect::<Vec<_>>();
+            pairs.sort_by_key(|pair| pair.split_once('=').map_or(*pair, |(key, _)| key));
+            self.query = if pairs.is_empty() {
+                None
+            } else {
+                Some(pairs.join("&"))
+            };
+        }
+    }
+}
+
+fn default_port(scheme: &str) -> Option<u16> {
+    match scheme {
+        "http" | "ws" => Some(80),
+        "https" | "wss" => Some(443),
+        "ftp" => Some(21),
+        _ => None,
+    }
+}
+
+fn remove_dot_segments(path: &str) -> String {
+    let is_absolute = path.starts_with('/');
+    let parts = path
+        .split('/')
+        .skip(is_absolute as usize)
+        .collect::<Vec<_>>();
+    let mut segments = Vec::with_capacity(parts.len());
+    for (index, part) in parts.iter().enumerate() {
+        let is_last = index == parts.len() - 1;
+        match *part {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            part => {
+                segments.push(part);
+                continue;
+            }
+        }
+        // Keep the trailing slash when the path ends with a dot segment
+        if is_last {
+            segments.push("");
+        }
+    }
+    let path = segments.join("/");
+    if is_absolute {
+        format!("/{path}")
+    } else {
+        path
+    }
+}
+
+fn form_urlencode(output: &mut String, input: &str) {
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'*' => {
+                output.push(byte as char)
+            }
+            b' ' => output.push('+'),
+            _ => output.push_str(&format!("%{byte:02X}")),
+        }
+    }
 }
 
 impl FromStr for Url {
@@ -176,6 +303,21 @@ impl Display for Url {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Url {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Url {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 // MARK: ParseError
 /// Url parser error
 #[derive(Debug)]
@@ -189,6 +331,22 @@ impl Display for ParseError {
 
 impl Error for ParseError {}
 
+// MARK: QueryError
+/// Url query struct encode or decode error
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct QueryError(String);
+
+#[cfg(feature = "serde")]
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "URL query error: {}", self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Error for QueryError {}
+
 // MARK: Tests
 #[cfg(test)]
 mod test {
@@ -344,4 +502,115 @@ mod test {
             assert_eq!(url.to_string(), *expected);
         }
     }
+
+    #[test]
+    fn test_normalize() {
+        let urls = [
+            ("HTTP://Example.COM:80/", "http://example.com/"),
+            ("https://example.com:443/path", "https://example.com/path"),
+            (
+                "https://example.com:8443/path",
+                "https://example.com:8443/path",
+            ),
+            ("http://example.com/a/./b/../c", "http://example.com/a/c"),
+            ("http://example.com/a/b/..", "http://example.com/a/"),
+            ("http://example.com/../a/.", "http://example.com/a/"),
+            ("http://example.com/a/?", "http://example.com/a/"),
+            (
+                "http://example.com/Path?Query",
+                "http://example.com/Path?Query",
+            ),
+        ];
+        for (input, expected) in &urls {
+            let mut url = Url::from_str(input).unwrap();
+            url.normalize();
+            assert_eq!(url.to_string(), *expected);
+        }
+
+        let mut a = Url::parse("HTTP://EXAMPLE.com:80/a/../b").unwrap();
+        let mut b = Url::parse("http://example.com/b").unwrap();
+        a.normalize();
+        b.normalize();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_sort_query_pairs() {
+        let mut url = Url::from_str("http://example.com/?b=2&a=1&&b=1&c").unwrap();
+        url.sort_query_pairs();
+        assert_eq!(url.query(), Some("a=1&b=2&b=1&c"));
+
+        let mut url = Url::from_str("http://example.com/?&").unwrap();
+        url.sort_query_pairs();
+        assert_eq!(url.query(), None);
+    }
+
+    #[test]
+    fn test_append_query_pair() {
+        let mut url = Url::from_str("http://example.com/search").unwrap();
+        url.append_query_pair("q", "hello world");
+        url.append_query_pair("filter", "a&b=c/ü");
+        assert_eq!(
+            url.to_string(),
+            "http://example.com/search?q=hello+world&filter=a%26b%3Dc%2F%C3%BC"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_query_struct() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Search {
+            q: String,
+            page: u32,
+            filter: Option<String>,
+        }
+
+        let search = Search {
+            q: "hello world & more".to_string(),
+            page: 2,
+            filter: None,
+        };
+        let url = Url::parse("http://example.com/search?lang=en")
+            .unwrap()
+            .with_query_struct(&search)
+            .unwrap();
+        assert_eq!(
+            url.to_string(),
+            "http://example.com/search?lang=en&q=hello+world+%26+more&page=2"
+        );
+
+        let url = Url::parse("http://example.com/search?q=hello+world+%26+more&page=2").unwrap();
+        assert_eq!(url.query_as::<Search>().unwrap(), search);
+        assert!(
+            Url::parse("http://example.com/search?page=two")
+                .unwrap()
+                .query_as::<Search>()
+                .is_err()
+        );
+        assert!(
+            Url::parse("http://example.com/")
+                .unwrap()
+                .with_query_struct(&"string")
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Link {
+            url: Url,
+        }
+
+        let link: Link =
+            serde_urlencoded::from_str("url=http%3A%2F%2Fexample.com%2Fa%3Fb%3Dc").unwrap();
+        assert_eq!(link.url.to_string(), "http://example.com/a?b=c");
+        assert_eq!(
+            serde_urlencoded::to_string(&link).unwrap(),
+            "url=http%3A%2F%2Fexample.com%2Fa%3Fb%3Dc"
+        );
+        assert!(serde_urlencoded::from_str::<Link>("url=invalid").is_err());
+    }
 }
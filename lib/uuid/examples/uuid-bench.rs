@@ -0,0 +1,45 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! A benchmark comparing bulk UUID generation with the buffered random generator against
+//! calling the OS random generator for every UUID, run with `--release`
+
+use std::time::Instant;
+
+use uuid::Uuid;
+
+const COUNT: u32 = 1_000_000;
+
+fn main() {
+    let start = Instant::now();
+    for _ in 0..COUNT {
+        let mut bytes = [0; 16];
+        getrandom::fill(&mut bytes).expect("Failed to generate random bytes");
+        bytes[6] = bytes[6] & 0x0f | 0x40;
+        bytes[8] = bytes[8] & 0x3f | 0x80;
+        std::hint::black_box(Uuid::from_bytes(bytes));
+    }
+    let unbuffered = start.elapsed();
+    println!("{COUNT} v4 UUIDs with a getrandom call per UUID: {unbuffered:?}");
+
+    let start = Instant::now();
+    for _ in 0..COUNT {
+        std::hint::black_box(Uuid::new_v4());
+    }
+    let buffered = start.elapsed();
+    println!("{COUNT} v4 UUIDs with Uuid::new_v4: {buffered:?}");
+
+    let start = Instant::now();
+    for _ in 0..COUNT {
+        std::hint::black_box(Uuid::now_v7());
+    }
+    println!("{COUNT} v7 UUIDs with Uuid::now_v7: {:?}", start.elapsed());
+
+    println!(
+        "Buffered v4 generation is {:.1}x faster",
+        unbuffered.as_secs_f64() / buffered.as_secs_f64()
+    );
+}
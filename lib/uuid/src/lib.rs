@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2023-2025 Bastiaan van der Plaat
+ * Copyright (c) 2023-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -101,12 +101,152 @@ impl Debug for Uuid {
     }
 }
 
+// MARK: Short representations
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+impl Uuid {
+    /// Encode UUID as Base58 string with the Bitcoin alphabet, at most 22 characters
+    pub fn to_base58(&self) -> String {
+        let zeros = self.0.iter().take_while(|byte| **byte == 0).count();
+        let mut n = u128::from_be_bytes(self.0);
+        let mut chars = Vec::with_capacity(22);
+        while n > 0 {
+            chars.push(BASE58_ALPHABET[(n % 58) as usize] as char);
+            n /= 58;
+        }
+        chars.extend(std::iter::repeat_n('1', zeros));
+        chars.iter().rev().collect()
+    }
+
+    /// Decode UUID from Base58 string
+    pub fn from_base58(s: &str) -> Result<Uuid, InvalidError> {
+        let ones = s.bytes().take_while(|c| *c == b'1').count();
+        let mut n: u128 = 0;
+        for c in s.bytes().skip(ones) {
+            let digit = BASE58_ALPHABET
+                .iter()
+                .position(|a| *a == c)
+                .ok_or(InvalidError)?;
+            n = n
+                .checked_mul(58)
+                .and_then(|n| n.checked_add(digit as u128))
+                .ok_or(InvalidError)?;
+        }
+        let bytes = n.to_be_bytes();
+        let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+        if ones != zeros {
+            return Err(InvalidError);
+        }
+        Ok(Uuid(bytes))
+    }
+}
+
+#[cfg(feature = "base64")]
+impl Uuid {
+    /// Encode UUID as URL-safe Base64 string without padding, always 22 characters
+    pub fn to_base64(&self) -> String {
+        use base64::Engine;
+        base64::BASE64_URL_SAFE_NO_PAD.encode(self.0)
+    }
+
+    /// Decode UUID from URL-safe Base64 string without padding
+    pub fn from_base64(s: &str) -> Result<Uuid, InvalidError> {
+        use base64::Engine;
+        if s.len() != 22 {
+            return Err(InvalidError);
+        }
+        let bytes = base64::BASE64_URL_SAFE_NO_PAD
+            .decode(s)
+            .map_err(|_| InvalidError)?;
+        Uuid::from_slice(&bytes)
+    }
+}
+
+// MARK: Random
+/// Fill buffer with crypto random bytes from a thread-local buffer that is refilled from the OS
+/// random generator, so generating UUIDs in bulk doesn't do a random syscall per UUID. The buffer
+/// remembers the process id it was filled in, so a forked child never reuses its parent's bytes
+#[cfg(any(feature = "v1", feature = "v4", feature = "v7"))]
+fn fill_random(buf: &mut [u8]) {
+    use std::cell::RefCell;
+
+    const BUFFER_SIZE: usize = 4096;
+    struct RandomBuffer {
+        bytes: [u8; BUFFER_SIZE],
+        position: usize,
+        pid: u32,
+    }
+    thread_local! {
+        static BUFFER: RefCell<RandomBuffer> = const {
+            RefCell::new(RandomBuffer {
+                bytes: [0; BUFFER_SIZE],
+                position: BUFFER_SIZE,
+                pid: 0,
+            })
+        };
+    }
+
+    BUFFER.with_borrow_mut(|buffer| {
+        // After a fork the child has a copy of the buffer, so discard it
+        let pid = std::process::id();
+        if buffer.pid != pid {
+            buffer.bytes.fill(0);
+            buffer.position = BUFFER_SIZE;
+            buffer.pid = pid;
+        }
+
+        let mut offset = 0;
+        while offset < buf.len() {
+            if buffer.position == BUFFER_SIZE {
+                getrandom::fill(&mut buffer.bytes).expect("Failed to generate random bytes");
+                buffer.position = 0;
+            }
+            let len = (buf.len() - offset).min(BUFFER_SIZE - buffer.position);
+            let random = &mut buffer.bytes[buffer.position..buffer.position + len];
+            buf[offset..offset + len].copy_from_slice(random);
+            // Clear used bytes so they don't linger in memory
+            random.fill(0);
+            buffer.position += len;
+            offset += len;
+        }
+    });
+}
+
+#[cfg(feature = "v1")]
+impl Uuid {
+    /// Create UUID v1 with time and node id (most often a MAC address)
+    pub fn new_v1(time: std::time::SystemTime, node: [u8; 6]) -> Uuid {
+        // Count of 100 ns intervals since the Gregorian epoch (1582-10-15)
+        const GREGORIAN_OFFSET: u64 = 0x01b2_1dd2_1381_4000;
+        let duration = time
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards");
+        let timestamp = GREGORIAN_OFFSET
+            + duration.as_secs() * 10_000_000
+            + (duration.subsec_nanos() / 100) as u64;
+
+        let mut bytes = [0; 16];
+        bytes[0] = (timestamp >> 24) as u8;
+        bytes[1] = (timestamp >> 16) as u8;
+        bytes[2] = (timestamp >> 8) as u8;
+        bytes[3] = timestamp as u8;
+        bytes[4] = (timestamp >> 40) as u8;
+        bytes[5] = (timestamp >> 32) as u8;
+        bytes[6] = (timestamp >> 56) as u8 & 0x0f | 0x10;
+        bytes[7] = (timestamp >> 48) as u8;
+        fill_random(&mut bytes[8..10]);
+        bytes[8] = bytes[8] & 0x3f | 0x80;
+        bytes[10..].copy_from_slice(&node);
+        Uuid(bytes)
+    }
+}
+
 #[cfg(feature = "v4")]
 impl Uuid {
     /// Create UUID v4
     pub fn new_v4() -> Uuid {
         let mut bytes = [0; 16];
-        getrandom::fill(&mut bytes).expect("Failed to generate random bytes");
+        fill_random(&mut bytes);
         bytes[6] = bytes[6] & 0x0f | 0x40;
         bytes[8] = bytes[8] & 0x3f | 0x80;
         Uuid(bytes)
@@ -128,7 +268,7 @@ impl Uuid {
         bytes[3] = (timestamp >> 16) as u8;
         bytes[4] = (timestamp >> 8) as u8;
         bytes[5] = timestamp as u8;
-        getrandom::fill(&mut bytes[6..]).expect("Failed to generate random bytes");
+        fill_random(&mut bytes[6..]);
         bytes[6] = bytes[6] & 0x0f | 0x70;
         bytes[8] = bytes[8] & 0x3f | 0x80;
         Uuid(bytes)
@@ -138,6 +278,58 @@ impl Uuid {
     pub fn now_v7() -> Uuid {
         Self::new_v7(std::time::SystemTime::now())
     }
+
+    /// Create UUID v7 with current time and a 42-bit counter, UUIDs created by this
+    /// function are monotonic even when they are generated within the same millisecond
+    /// (RFC 9562 section 6.2 method 1)
+    pub fn new_v7_with_counter() -> Uuid {
+        use std::sync::Mutex;
+
+        const COUNTER_BITS: u32 = 42;
+        const COUNTER_MAX: u64 = (1 << COUNTER_BITS) - 1;
+        static STATE: Mutex<(u64, u64)> = Mutex::new((0, 0));
+
+        let mut random = [0; 10];
+        fill_random(&mut random);
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as u64;
+        let (timestamp, counter) = {
+            let mut state = STATE.lock().expect("Can't lock mutex");
+            let (last_timestamp, last_counter) = *state;
+            *state = if now > last_timestamp {
+                // Seed counter randomly but keep the highest bit clear so it has room to grow
+                let seed = u64::from_be_bytes([
+                    0, 0, random[0], random[1], random[2], random[3], random[4], random[5],
+                ]);
+                (now, seed & (COUNTER_MAX >> 1))
+            } else if last_counter < COUNTER_MAX {
+                (last_timestamp, last_counter + 1)
+            } else {
+                // Counter overflow, borrow the next millisecond
+                (last_timestamp + 1, 0)
+            };
+            *state
+        };
+
+        let mut bytes = [0; 16];
+        bytes[0] = (timestamp >> 40) as u8;
+        bytes[1] = (timestamp >> 32) as u8;
+        bytes[2] = (timestamp >> 24) as u8;
+        bytes[3] = (timestamp >> 16) as u8;
+        bytes[4] = (timestamp >> 8) as u8;
+        bytes[5] = timestamp as u8;
+        bytes[6] = (counter >> 38) as u8 & 0x0f | 0x70;
+        bytes[7] = (counter >> 30) as u8;
+        bytes[8] = (counter >> 24) as u8 & 0x3f | 0x80;
+        bytes[9] = (counter >> 16) as u8;
+        bytes[10] = (counter >> 8) as u8;
+        bytes[11] = counter as u8;
+        bytes[12..].copy_from_slice(&random[6..]);
+        Uuid(bytes)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -224,6 +416,48 @@ mod test {
         assert!(matches!(uuid, InvalidError));
     }
 
+    #[test]
+    fn test_base58() {
+        let uuid = "a0b1c2d3-e4f5-6789-9a0b-cdef01234567"
+            .parse::<Uuid>()
+            .unwrap();
+        let base58 = uuid.to_base58();
+        assert!(base58.len() <= 22);
+        assert_eq!(Uuid::from_base58(&base58).unwrap(), uuid);
+
+        assert_eq!(Uuid::nil().to_base58(), "1111111111111111");
+        assert_eq!(Uuid::from_base58("1111111111111111").unwrap(), Uuid::nil());
+        let uuid = Uuid::from_bytes([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(uuid.to_base58(), "1111111111111112");
+        assert_eq!(Uuid::from_base58("1111111111111112").unwrap(), uuid);
+
+        assert!(Uuid::from_base58("0OIl").is_err());
+        assert!(Uuid::from_base58("112").is_err());
+        assert!(Uuid::from_base58("zzzzzzzzzzzzzzzzzzzzzzzzz").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "base64")]
+    fn test_base64() {
+        let uuid = "a0b1c2d3-e4f5-6789-9a0b-cdef01234567"
+            .parse::<Uuid>()
+            .unwrap();
+        assert_eq!(uuid.to_base64(), "oLHC0-T1Z4maC83vASNFZw");
+        assert_eq!(Uuid::from_base64("oLHC0-T1Z4maC83vASNFZw").unwrap(), uuid);
+        assert!(Uuid::from_base64("oLHC0-T1Z4maC83vASNF").is_err());
+        assert!(Uuid::from_base64("oLHC0+T1Z4maC83vASNFZw").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "v1")]
+    fn test_generate_v1() {
+        let uuid = Uuid::new_v1(std::time::UNIX_EPOCH, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        let string = uuid.to_string();
+        assert!(string.starts_with("13814000-1dd2-11b2-"));
+        assert!(string.ends_with("-010203040506"));
+        assert!(matches!(uuid.into_bytes()[8] >> 6, 2 | 3));
+    }
+
     #[test]
     #[cfg(feature = "v4")]
     fn test_generate_v4() {
@@ -234,6 +468,16 @@ mod test {
         assert!(matches!(bytes[8] >> 6, 2 | 3));
     }
 
+    #[test]
+    #[cfg(feature = "v4")]
+    fn test_generate_v4_bulk_unique() {
+        // Generate enough UUIDs to refill the random buffer multiple times
+        let uuids = (0..1000)
+            .map(|_| Uuid::new_v4().into_bytes())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(uuids.len(), 1000);
+    }
+
     #[test]
     #[cfg(feature = "v7")]
     fn test_generate_v7() {
@@ -244,6 +488,20 @@ mod test {
         assert!(matches!(bytes[8] >> 6, 2 | 3));
     }
 
+    #[test]
+    #[cfg(feature = "v7")]
+    fn test_generate_v7_with_counter_monotonic() {
+        let mut last = Uuid::new_v7_with_counter();
+        for _ in 0..1000 {
+            let uuid = Uuid::new_v7_with_counter();
+            let bytes = uuid.into_bytes();
+            assert_eq!(bytes[6] >> 4, 7);
+            assert!(matches!(bytes[8] >> 6, 2 | 3));
+            assert!(bytes > last.into_bytes());
+            last = uuid;
+        }
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_serde_serialization() {
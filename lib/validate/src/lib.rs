@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2024-2025 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -13,6 +13,12 @@ use std::fmt::{self, Display, Formatter};
 #[cfg(feature = "derive")]
 pub use validate_derive::Validate;
 
+pub mod rules;
+
+// Allow the derive macro to be used in the tests of this crate
+#[cfg(all(test, feature = "derive"))]
+extern crate self as validate;
+
 // MARK: Error
 /// Validate result
 pub type Result = std::result::Result<(), Error>;
@@ -43,10 +49,28 @@ impl Display for Error {
 impl error::Error for Error {}
 
 // MARK: Report
-/// Validation report
+/// Validation message severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// Error, fails validation
+    Error,
+    /// Warning, doesn't fail validation
+    Warning,
+}
+
+/// Validation report with error and warning messages per field
 #[derive(Default)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct Report(pub HashMap<String, Vec<String>>);
+pub struct Report {
+    /// Error messages per field
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub errors: HashMap<String, Vec<String>>,
+    /// Warning messages per field
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub warnings: HashMap<String, Vec<String>>,
+}
 
 impl Report {
     /// Create new report
@@ -56,17 +80,54 @@ impl Report {
 
     /// Is report empty
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+
+    /// Has report errors
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Has report warnings
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
     }
 
     /// Get errors for field
     pub fn get_errors(&self, field: impl AsRef<str>) -> Option<&Vec<String>> {
-        self.0.get(field.as_ref())
+        self.errors.get(field.as_ref())
+    }
+
+    /// Get warnings for field
+    pub fn get_warnings(&self, field: impl AsRef<str>) -> Option<&Vec<String>> {
+        self.warnings.get(field.as_ref())
+    }
+
+    /// Insert message with severity for field
+    pub fn insert(
+        &mut self,
+        field: impl Into<String>,
+        severity: Severity,
+        message: impl Into<String>,
+    ) {
+        let messages = match severity {
+            Severity::Error => &mut self.errors,
+            Severity::Warning => &mut self.warnings,
+        };
+        messages
+            .entry(field.into())
+            .or_default()
+            .push(message.into());
     }
 
     /// Insert error for field
     pub fn insert_error(&mut self, field: impl Into<String>, message: impl Into<String>) {
-        self.0.entry(field.into()).or_default().push(message.into());
+        self.insert(field, Severity::Error, message);
+    }
+
+    /// Insert warning for field
+    pub fn insert_warning(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.insert(field, Severity::Warning, message);
     }
 }
 
@@ -76,7 +137,7 @@ pub trait Validate {
     /// Validate context
     type Context;
 
-    /// Validate self
+    /// Validate self, fails with the report when it has errors
     fn validate(&self) -> std::result::Result<(), Report>
     where
         Self::Context: Default,
@@ -85,8 +146,27 @@ pub trait Validate {
         self.validate_with(&ctx)
     }
 
-    /// Validate self with context
-    fn validate_with(&self, context: &Self::Context) -> std::result::Result<(), Report>;
+    /// Validate self with context, fails with the report when it has errors
+    fn validate_with(&self, context: &Self::Context) -> std::result::Result<(), Report> {
+        let report = self.report_with(context);
+        if report.has_errors() {
+            Err(report)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validate self and get the report with all errors and warnings
+    fn report(&self) -> Report
+    where
+        Self::Context: Default,
+    {
+        let ctx = Self::Context::default();
+        self.report_with(&ctx)
+    }
+
+    /// Validate self with context and get the report with all errors and warnings
+    fn report_with(&self, context: &Self::Context) -> Report;
 }
 
 /// Validate email
@@ -105,9 +185,58 @@ pub fn is_valid_url(url: &str) -> bool {
 // MARK: Tests
 #[cfg(test)]
 mod test {
-    #[cfg(any(feature = "email", feature = "url"))]
     use super::*;
 
+    #[test]
+    fn test_report_severity() {
+        let mut report = Report::new();
+        assert!(report.is_empty());
+
+        report.insert_warning("name", "Should be shorter");
+        assert!(!report.is_empty());
+        assert!(!report.has_errors());
+        assert!(report.has_warnings());
+
+        report.insert("name", Severity::Error, "Must not be empty");
+        assert!(report.has_errors());
+        assert_eq!(
+            report.get_errors("name"),
+            Some(&vec!["Must not be empty".to_string()])
+        );
+        assert_eq!(
+            report.get_warnings("name"),
+            Some(&vec!["Should be shorter".to_string()])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn test_derive_severity() {
+        #[derive(Validate)]
+        struct Body {
+            #[validate(length(min = 1), length(max = 4, severity = "warn"))]
+            name: String,
+        }
+
+        let body = Body {
+            name: "Bastiaan".to_string(),
+        };
+        assert!(body.validate().is_ok());
+        let report = body.report();
+        assert!(!report.has_errors());
+        assert_eq!(
+            report.get_warnings("name"),
+            Some(&vec!["Must be at most 4 characters long".to_string()])
+        );
+
+        let body = Body {
+            name: String::new(),
+        };
+        let report = body.validate().unwrap_err();
+        assert!(report.has_errors());
+        assert!(!report.has_warnings());
+    }
+
     #[test]
     #[cfg(feature = "email")]
     fn test_valid_email() {
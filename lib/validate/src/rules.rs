@@ -0,0 +1,370 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Validation rules that can be used on any value, these are also used by the derive macro
+//!
+//! ```
+//! use validate::rules::{self, Rule};
+//!
+//! let rule = rules::length(3..=20).and(rules::ascii());
+//! assert!(rule.validate("bastiaan").is_ok());
+//! assert_eq!(
+//!     rule.validate("ba").unwrap_err().message(),
+//!     "Must be at least 3 characters long"
+//! );
+//! ```
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Display;
+use std::ops::{Bound, RangeBounds};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::{Error, Result};
+
+// MARK: Rule
+/// Validation rule for values of type `T`
+pub trait Rule<T: ?Sized> {
+    /// Validate value
+    fn validate(&self, value: &T) -> Result;
+}
+
+// Rules are combined with an inherent method, so the value type is only inferred at validate
+macro_rules! impl_and {
+    ($type:ident $(<$($generic:ident),*>)?) => {
+        impl $(<$($generic),*>)? $type $(<$($generic),*>)? {
+            /// Combine with another rule, the value must pass both rules
+            pub const fn and<R>(self, other: R) -> And<Self, R> {
+                And(self, other)
+            }
+        }
+    };
+}
+
+/// Functions can be used as custom rules
+impl<T: ?Sized, F: Fn(&T) -> Result> Rule<T> for F {
+    fn validate(&self, value: &T) -> Result {
+        self(value)
+    }
+}
+
+/// Rule that passes when both rules pass, created with `and`
+pub struct And<A, B>(A, B);
+
+impl_and!(And<A, B>);
+
+impl<T: ?Sized, A: Rule<T>, B: Rule<T>> Rule<T> for And<A, B> {
+    fn validate(&self, value: &T) -> Result {
+        self.0.validate(value)?;
+        self.1.validate(value)
+    }
+}
+
+// MARK: Ascii
+/// Rule that the value only contains ASCII characters
+pub struct Ascii;
+
+impl_and!(Ascii);
+
+/// Value must only contain ASCII characters
+pub const fn ascii() -> Ascii {
+    Ascii
+}
+
+impl<T: AsRef<str> + ?Sized> Rule<T> for Ascii {
+    fn validate(&self, value: &T) -> Result {
+        if value.as_ref().is_ascii() {
+            Ok(())
+        } else {
+            Err(Error::new("Must only contain ASCII characters"))
+        }
+    }
+}
+
+// MARK: Length
+/// Values with a length
+pub trait HasLength {
+    /// Get length
+    fn length(&self) -> usize;
+}
+
+macro_rules! impl_has_length {
+    ($($type:ty $(, $generic:ident)*);* $(;)?) => {
+        $(
+            impl<$($generic),*> HasLength for $type {
+                fn length(&self) -> usize {
+                    self.len()
+                }
+            }
+        )*
+    };
+}
+
+impl_has_length!(
+    str;
+    String;
+    [T], T;
+    Vec<T>, T;
+    VecDeque<T>, T;
+    HashMap<K, V, S>, K, V, S;
+    HashSet<T, S>, T, S;
+    BTreeMap<K, V>, K, V;
+    BTreeSet<T>, T;
+);
+
+impl<T, const N: usize> HasLength for [T; N] {
+    fn length(&self) -> usize {
+        N
+    }
+}
+
+impl<T: HasLength + ?Sized> HasLength for Box<T> {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+impl<T: HasLength + ?Sized> HasLength for Rc<T> {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+impl<T: HasLength + ?Sized> HasLength for Arc<T> {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+impl<T: HasLength + ToOwned + ?Sized> HasLength for Cow<'_, T> {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+impl<T: HasLength + ?Sized> HasLength for &T {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+/// Rule that the length of the value is in a range
+pub struct Length {
+    min: Option<usize>,
+    max: Option<usize>,
+}
+
+impl_and!(Length);
+
+/// Value length must be in range, like `3..=20` or `1..`
+pub fn length(range: impl RangeBounds<usize>) -> Length {
+    Length {
+        min: match range.start_bound() {
+            Bound::Included(min) => Some(*min),
+            Bound::Excluded(min) => Some(min + 1),
+            Bound::Unbounded => None,
+        },
+        max: match range.end_bound() {
+            Bound::Included(max) => Some(*max),
+            Bound::Excluded(max) => Some(max.saturating_sub(1)),
+            Bound::Unbounded => None,
+        },
+    }
+}
+
+impl<T: HasLength + ?Sized> Rule<T> for Length {
+    fn validate(&self, value: &T) -> Result {
+        let length = value.length();
+        if let Some(min) = self.min
+            && length < min
+        {
+            return Err(Error::new(format!(
+                "Must be at least {min} characters long"
+            )));
+        }
+        if let Some(max) = self.max
+            && length > max
+        {
+            return Err(Error::new(format!("Must be at most {max} characters long")));
+        }
+        Ok(())
+    }
+}
+
+// MARK: Range
+/// Rule that the value is in a range
+pub struct Range<T> {
+    min: Bound<T>,
+    max: Bound<T>,
+}
+
+impl_and!(Range<T>);
+
+/// Value must be in range, like `1..=50` or `8..`
+pub fn range<T: Clone>(range: impl RangeBounds<T>) -> Range<T> {
+    Range {
+        min: range.start_bound().cloned(),
+        max: range.end_bound().cloned(),
+    }
+}
+
+impl<T: PartialOrd + Display> Rule<T> for Range<T> {
+    fn validate(&self, value: &T) -> Result {
+        match &self.min {
+            Bound::Included(min) if value < min => {
+                return Err(Error::new(format!("Must be at least {min}")));
+            }
+            Bound::Excluded(min) if value <= min => {
+                return Err(Error::new(format!("Must be greater than {min}")));
+            }
+            _ => {}
+        }
+        match &self.max {
+            Bound::Included(max) if value > max => {
+                Err(Error::new(format!("Must be at most {max}")))
+            }
+            Bound::Excluded(max) if value >= max => {
+                Err(Error::new(format!("Must be less than {max}")))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// MARK: Email
+/// Rule that the value is a valid email address
+#[cfg(feature = "email")]
+pub struct Email;
+
+#[cfg(feature = "email")]
+impl_and!(Email);
+
+/// Value must be a valid email address
+#[cfg(feature = "email")]
+pub const fn email() -> Email {
+    Email
+}
+
+#[cfg(feature = "email")]
+impl<T: AsRef<str> + ?Sized> Rule<T> for Email {
+    fn validate(&self, value: &T) -> Result {
+        if crate::is_valid_email(value.as_ref()) {
+            Ok(())
+        } else {
+            Err(Error::new("Must be a valid email address"))
+        }
+    }
+}
+
+// MARK: Url
+/// Rule that the value is a valid url
+#[cfg(feature = "url")]
+pub struct Url;
+
+#[cfg(feature = "url")]
+impl_and!(Url);
+
+/// Value must be a valid url
+#[cfg(feature = "url")]
+pub const fn url() -> Url {
+    Url
+}
+
+#[cfg(feature = "url")]
+impl<T: AsRef<str> + ?Sized> Rule<T> for Url {
+    fn validate(&self, value: &T) -> Result {
+        if crate::is_valid_url(value.as_ref()) {
+            Ok(())
+        } else {
+            Err(Error::new("Must be a valid url"))
+        }
+    }
+}
+
+// MARK: Tests
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_length() {
+        let rule = length(3..=5);
+        assert!(rule.validate("abc").is_ok());
+        assert!(rule.validate(&"abcde".to_string()).is_ok());
+        assert_eq!(
+            rule.validate("ab").unwrap_err().message(),
+            "Must be at least 3 characters long"
+        );
+        assert_eq!(
+            rule.validate("abcdef").unwrap_err().message(),
+            "Must be at most 5 characters long"
+        );
+        assert!(length(1..).validate(&vec![1, 2, 3]).is_ok());
+        assert!(length(..3).validate(&[1, 2, 3][..]).is_err());
+        assert!(length(..3).validate(&[1, 2, 3]).is_err());
+        assert!(length(1..).validate(&HashSet::from([1])).is_ok());
+        assert!(length(1..).validate(&BTreeMap::<i32, i32>::new()).is_err());
+        assert!(length(3..).validate(&VecDeque::from([1, 2, 3])).is_ok());
+        assert!(rule.validate(&Box::<str>::from("abc")).is_ok());
+        assert!(rule.validate(&Cow::Borrowed("ab")).is_err());
+    }
+
+    #[test]
+    fn test_range() {
+        let rule = range(1..=50);
+        assert!(rule.validate(&1).is_ok());
+        assert!(rule.validate(&50).is_ok());
+        assert_eq!(
+            rule.validate(&0).unwrap_err().message(),
+            "Must be at least 1"
+        );
+        assert_eq!(
+            rule.validate(&51).unwrap_err().message(),
+            "Must be at most 50"
+        );
+        assert_eq!(
+            range(0.0..1.0).validate(&1.0).unwrap_err().message(),
+            "Must be less than 1"
+        );
+    }
+
+    #[test]
+    fn test_and_custom() {
+        fn no_spaces(value: &str) -> Result {
+            if value.contains(' ') {
+                Err(Error::new("Must not contain spaces"))
+            } else {
+                Ok(())
+            }
+        }
+
+        let rule = length(3..=20).and(ascii()).and(no_spaces);
+        assert!(rule.validate("bastiaan").is_ok());
+        assert_eq!(
+            rule.validate("b").unwrap_err().message(),
+            "Must be at least 3 characters long"
+        );
+        assert_eq!(
+            rule.validate("bastiaän").unwrap_err().message(),
+            "Must only contain ASCII characters"
+        );
+        assert_eq!(
+            rule.validate("bas tiaan").unwrap_err().message(),
+            "Must not contain spaces"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "email")]
+    fn test_email() {
+        assert!(email().validate("test@example.com").is_ok());
+        assert_eq!(
+            email().validate("example.com").unwrap_err().message(),
+            "Must be a valid email address"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 /*
- * Copyright (c) 2024 Bastiaan van der Plaat
+ * Copyright (c) 2024-2026 Bastiaan van der Plaat
  *
  * SPDX-License-Identifier: MIT
  */
@@ -13,6 +13,7 @@ use syn::{DeriveInput, Expr, Meta, parse_macro_input};
 struct Rule {
     r#type: RuleType,
     is_option: bool,
+    is_warning: bool,
 }
 
 enum RuleType {
@@ -28,6 +29,28 @@ enum RuleType {
     Custom(Expr),
 }
 
+fn parse_is_warning(list: &syn::punctuated::Punctuated<Meta, syn::token::Comma>) -> bool {
+    for item in list {
+        if let Meta::NameValue(name_value) = item
+            && name_value.path.is_ident("severity")
+        {
+            if let Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(severity),
+                ..
+            }) = &name_value.value
+            {
+                return match severity.value().as_str() {
+                    "error" => false,
+                    "warn" | "warning" => true,
+                    _ => panic!("Invalid severity"),
+                };
+            }
+            panic!("Invalid severity");
+        }
+    }
+    false
+}
+
 /// [Validate] derive
 #[proc_macro_derive(Validate, attributes(validate))]
 pub fn validate_derive(input: TokenStream) -> TokenStream {
@@ -80,6 +103,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                     rules.push(Rule {
                                         r#type: RuleType::Ascii,
                                         is_option,
+                                        is_warning: false,
                                     });
                                 }
                                 #[cfg(feature = "email")]
@@ -87,6 +111,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                     rules.push(Rule {
                                         r#type: RuleType::Email,
                                         is_option,
+                                        is_warning: false,
                                     });
                                 }
                                 #[cfg(feature = "url")]
@@ -94,6 +119,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                     rules.push(Rule {
                                         r#type: RuleType::Url,
                                         is_option,
+                                        is_warning: false,
                                     });
                                 }
                             }
@@ -103,6 +129,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                         syn::punctuated::Punctuated::<_, syn::token::Comma>::parse_terminated,
                                     )
                                     .expect("Invalid attribute");
+                                let is_warning = parse_is_warning(&list);
                                 if meta_list.path.is_ident("length") {
                                     for item in &list {
                                         if let Meta::NameValue(name_value) = item {
@@ -112,6 +139,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                                         name_value.value.clone(),
                                                     ),
                                                     is_option,
+                                                    is_warning,
                                                 });
                                             }
                                             if name_value.path.is_ident("max") {
@@ -120,6 +148,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                                         name_value.value.clone(),
                                                     ),
                                                     is_option,
+                                                    is_warning,
                                                 });
                                             }
                                         }
@@ -134,6 +163,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                                         name_value.value.clone(),
                                                     ),
                                                     is_option,
+                                                    is_warning,
                                                 });
                                             }
                                             if name_value.path.is_ident("max") {
@@ -142,6 +172,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                                         name_value.value.clone(),
                                                     ),
                                                     is_option,
+                                                    is_warning,
                                                 });
                                             }
                                         }
@@ -156,6 +187,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                                                         .expect("Invalid attribute"),
                                                 ),
                                                 is_option,
+                                                is_warning,
                                             });
                                         }
                                     }
@@ -182,64 +214,52 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
     let validate_fields = fields.iter().map(|(field, rules)| {
         let field_name = field.ident.as_ref().expect("Invalid field");
         let validate_rules = rules.iter().map(|rule| {
-            let test_condition = |condition, error| {
+            let insert = if rule.is_warning {
+                quote! { insert_warning }
+            } else {
+                quote! { insert_error }
+            };
+            let test_rule = |rule_expr| {
                 let field_name_string = field_name.to_string().replace("r#", "");
                 if rule.is_option {
                     quote! {
                         if let Some(value) = &self.#field_name {
-                            if #condition {
-                                report.insert_error(#field_name_string, #error);
+                            if let Err(err) = validate::rules::Rule::validate(&#rule_expr, value) {
+                                report.#insert(#field_name_string, err.message());
                             }
                         }
                     }
                 } else {
                     quote! {
                         let value = &self.#field_name;
-                        if #condition {
-                            report.insert_error(#field_name_string, #error);
+                        if let Err(err) = validate::rules::Rule::validate(&#rule_expr, value) {
+                            report.#insert(#field_name_string, err.message());
                         }
                     }
                 }
             };
 
             match &rule.r#type {
-                RuleType::Ascii => test_condition(
-                    quote! { !value.is_ascii() },
-                    quote! { "Must only contain ASCII characters".to_string() },
-                ),
+                RuleType::Ascii => test_rule(quote! { validate::rules::ascii() }),
                 #[cfg(feature = "email")]
-                RuleType::Email => test_condition(
-                    quote! { !validate::is_valid_email(value) },
-                    quote! { "Must be a valid email address".to_string() },
-                ),
+                RuleType::Email => test_rule(quote! { validate::rules::email() }),
                 #[cfg(feature = "url")]
-                RuleType::Url => test_condition(
-                    quote! { !validate::is_valid_url(value) },
-                    quote! { "Must be a valid url".to_string() },
-                ),
-                RuleType::LengthMin(min) => test_condition(
-                    quote! { value.len() < #min as usize },
-                    quote! { format!("Must be at least {} characters long", #min) },
-                ),
-                RuleType::LengthMax(max) => test_condition(
-                    quote! { value.len() > #max as usize },
-                    quote! { format!("Must be at most {} characters long", #max) },
-                ),
-                RuleType::RangeMin(min) => test_condition(
-                    quote! { *value < #min },
-                    quote! { format!("Must be at least {}", #min) },
-                ),
-                RuleType::RangeMax(max) => test_condition(
-                    quote! { *value > #max },
-                    quote! { format!("Must be at most {}", #max) },
-                ),
+                RuleType::Url => test_rule(quote! { validate::rules::url() }),
+                RuleType::LengthMin(min) => {
+                    test_rule(quote! { validate::rules::length((#min as usize)..) })
+                }
+                RuleType::LengthMax(max) => {
+                    test_rule(quote! { validate::rules::length(..=(#max as usize)) })
+                }
+                RuleType::RangeMin(min) => test_rule(quote! { validate::rules::range((#min)..) }),
+                RuleType::RangeMax(max) => test_rule(quote! { validate::rules::range(..=(#max)) }),
                 RuleType::Custom(custom) => {
                     if context.is_some() {
                         if rule.is_option {
                             quote! {
                                 if let Some(value) = &self.#field_name {
                                     if let Err(err) = #custom(value, context) {
-                                        report.insert_error(stringify!(#field_name), err.message());
+                                        report.#insert(stringify!(#field_name), err.message());
                                     }
                                 }
                             }
@@ -247,7 +267,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                             quote! {
                                 let value = &self.#field_name;
                                 if let Err(err) = #custom(value, context) {
-                                    report.insert_error(stringify!(#field_name), err.message());
+                                    report.#insert(stringify!(#field_name), err.message());
                                 }
                             }
                         }
@@ -255,7 +275,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                         quote! {
                             if let Some(value) = &self.#field_name {
                                 if let Err(err) = #custom(value) {
-                                    report.insert_error(stringify!(#field_name), err.message());
+                                    report.#insert(stringify!(#field_name), err.message());
                                 }
                             }
                         }
@@ -263,7 +283,7 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
                         quote! {
                             let value = &self.#field_name;
                             if let Err(err) = #custom(value) {
-                                report.insert_error(stringify!(#field_name), err.message());
+                                report.#insert(stringify!(#field_name), err.message());
                             }
                         }
                     }
@@ -278,14 +298,10 @@ pub fn validate_derive(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl validate::Validate for #name {
             type Context = #context_type;
-            fn validate_with(&self, context: &Self::Context) -> std::result::Result<(), validate::Report> {
+            fn report_with(&self, context: &Self::Context) -> validate::Report {
                 let mut report = validate::Report::new();
                 #(#validate_fields;)*
-                if report.is_empty() {
-                    Ok(())
-                } else {
-                    Err(report)
-                }
+                report
             }
         }
     })
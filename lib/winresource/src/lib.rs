@@ -6,11 +6,10 @@
 
 //! A minimal replacement for the [winresource](https://crates.io/crates/winresource) crate
 
-use core::panic;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::{env, fs};
+use std::{env, fs, io};
 
 use crate::version::MicrosoftVersion;
 
@@ -20,35 +19,79 @@ mod version;
 ///
 /// Supports msvc rc.exe, mingw windres and zig rc.
 pub struct WindowsResource {
-    icon_path: Option<PathBuf>,
+    icons: Vec<(String, PathBuf)>,
     manifest: Option<String>,
-    version_fields: HashMap<String, String>,
+    file_version: MicrosoftVersion,
+    product_version: MicrosoftVersion,
+    version_fields: BTreeMap<String, String>,
+    translations: Vec<Translation>,
+    resources: Vec<Resource>,
+    strings: BTreeMap<u16, String>,
+    append_rc_content: String,
+}
+
+/// VERSIONINFO fixed version field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionInfo {
+    /// Binary file version
+    FileVersion,
+    /// Binary product version
+    ProductVersion,
+}
+
+struct Translation {
+    language: u16,
+    codepage: u16,
+    version_fields: BTreeMap<String, String>,
+}
+
+impl Translation {
+    const fn new(language: u16, codepage: u16) -> Self {
+        Self {
+            language,
+            codepage,
+            version_fields: BTreeMap::new(),
+        }
+    }
+}
+
+struct Resource {
+    id: String,
+    r#type: String,
+    data: Vec<u8>,
 }
 
 impl Default for WindowsResource {
     fn default() -> Self {
-        let mut version_fields = HashMap::new();
-        version_fields.insert(
-            "FileVersion".to_string(),
-            env::var("CARGO_PKG_VERSION").expect("CARGO_PKG_VERSION not set"),
-        );
-        version_fields.insert(
-            "ProductVersion".to_string(),
-            env::var("CARGO_PKG_VERSION").expect("CARGO_PKG_VERSION not set"),
-        );
-        version_fields.insert(
-            "FileDescription".to_string(),
-            env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME not set"),
-        );
-        version_fields.insert(
-            "ProductName".to_string(),
-            env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME not set"),
-        );
+        let pkg_version = env::var("CARGO_PKG_VERSION").expect("CARGO_PKG_VERSION not set");
+        let pkg_name = env::var("CARGO_PKG_NAME").expect("CARGO_PKG_NAME not set");
+
+        // Strip pre-release and build metadata, they can't be represented in VERSIONINFO
+        let version = semver::Version::parse(
+            pkg_version
+                .split(['-', '+'])
+                .next()
+                .expect("Should have first part"),
+        )
+        .expect("Can't parse version semver");
+        let version = MicrosoftVersion::new(version.major, version.minor, version.patch, 0);
+
+        let mut version_fields = BTreeMap::new();
+        version_fields.insert("FileVersion".to_string(), pkg_version.clone());
+        version_fields.insert("ProductVersion".to_string(), pkg_version);
+        version_fields.insert("FileDescription".to_string(), pkg_name.clone());
+        version_fields.insert("ProductName".to_string(), pkg_name);
 
         Self {
-            icon_path: None,
+            icons: Vec::new(),
             manifest: None,
+            file_version: version.clone(),
+            product_version: version,
             version_fields,
+            translations: vec![Translation::new(0x0409, 0x04B0)],
+            resources: Vec::new(),
+            strings: BTreeMap::new(),
+            append_rc_content: String::new(),
         }
     }
 }
@@ -66,9 +109,112 @@ impl WindowsResource {
         self
     }
 
-    /// Set the icon file
+    /// Set a version field for one translation only, overriding the shared value
+    ///
+    /// The translation is added with the Unicode codepage when it doesn't exist yet.
+    pub fn set_for_language(
+        &mut self,
+        language: u16,
+        key: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> &mut Self {
+        if !self.translations.iter().any(|t| t.language == language) {
+            self.add_translation(language, 0x04B0);
+        }
+        self.translations
+            .iter_mut()
+            .find(|t| t.language == language)
+            .expect("Should exist")
+            .version_fields
+            .insert(key.as_ref().to_string(), value.as_ref().to_string());
+        self
+    }
+
+    /// Set a fixed VERSIONINFO version, packed as `0xMMMM_mmmm_pppp_bbbb` (major, minor, patch, build)
+    ///
+    /// By default both versions are derived from `CARGO_PKG_VERSION`.
+    pub fn set_version_info(&mut self, field: VersionInfo, value: u64) -> &mut Self {
+        let version = MicrosoftVersion::from(value);
+        match field {
+            VersionInfo::FileVersion => self.file_version = version,
+            VersionInfo::ProductVersion => self.product_version = version,
+        }
+        self
+    }
+
+    /// Set the language of the main translation, defaults to `0x0409` (English US)
+    pub fn set_language(&mut self, language: u16) -> &mut Self {
+        self.translations[0].language = language;
+        self
+    }
+
+    /// Set the codepage of the main translation, defaults to `0x04B0` (Unicode)
+    pub fn set_codepage(&mut self, codepage: u16) -> &mut Self {
+        self.translations[0].codepage = codepage;
+        self
+    }
+
+    /// Add an extra translation block with a language and codepage
+    pub fn add_translation(&mut self, language: u16, codepage: u16) -> &mut Self {
+        if !self
+            .translations
+            .iter()
+            .any(|t| t.language == language && t.codepage == codepage)
+        {
+            self.translations.push(Translation::new(language, codepage));
+        }
+        self
+    }
+
+    /// Set the main icon file, this is the icon Windows Explorer shows for the executable
     pub fn set_icon(&mut self, path: impl AsRef<Path>) -> &mut Self {
-        self.icon_path = Some(path.as_ref().to_path_buf());
+        self.set_icon_with_id(path, "1")
+    }
+
+    /// Set an icon file with an explicit ordinal or name ID
+    pub fn set_icon_with_id(&mut self, path: impl AsRef<Path>, id: impl AsRef<str>) -> &mut Self {
+        let id = id.as_ref().to_string();
+        let path = path.as_ref().to_path_buf();
+        if let Some(icon) = self.icons.iter_mut().find(|(icon_id, _)| *icon_id == id) {
+            icon.1 = path;
+        } else {
+            self.icons.push((id, path));
+        }
+        self
+    }
+
+    /// Set a raw user-defined resource with an ordinal or name ID and a resource type
+    pub fn set_resource(
+        &mut self,
+        id: impl AsRef<str>,
+        r#type: impl AsRef<str>,
+        data: impl Into<Vec<u8>>,
+    ) -> &mut Self {
+        let id = id.as_ref().to_string();
+        let r#type = r#type.as_ref().to_string();
+        let data = data.into();
+        if let Some(resource) = self
+            .resources
+            .iter_mut()
+            .find(|resource| resource.id == id && resource.r#type == r#type)
+        {
+            resource.data = data;
+        } else {
+            self.resources.push(Resource { id, r#type, data });
+        }
+        self
+    }
+
+    /// Set a string table entry
+    pub fn set_string(&mut self, id: u16, value: impl AsRef<str>) -> &mut Self {
+        self.strings.insert(id, value.as_ref().to_string());
+        self
+    }
+
+    /// Append raw content to the generated resource.rc file
+    pub fn append_rc_content(&mut self, content: impl AsRef<str>) -> &mut Self {
+        self.append_rc_content.push_str(content.as_ref());
+        self.append_rc_content.push_str("\r\n");
         self
     }
 
@@ -78,87 +224,33 @@ impl WindowsResource {
         self
     }
 
-    /// Compile the resources
-    pub fn compile(&self) -> Result<(), String> {
-        let out_dir = env::var("OUT_DIR").expect("OUT_DIR environment variable not set");
+    /// Write the resource.rc file and the files it references to the directory of the given path
+    pub fn write_resource_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let dir = path.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(dir)?;
 
         // Write manifest file
         if let Some(manifest) = &self.manifest {
-            let manifest_path = Path::new(&out_dir).join("manifest.xml");
-            if let Some(parent) = manifest_path.parent() {
-                fs::create_dir_all(parent).unwrap_or_else(|_| {
-                    panic!("failed to create output directory {}", parent.display())
-                });
-            }
-            fs::write(&manifest_path, manifest.as_bytes()).unwrap_or_else(|_| {
-                panic!("failed to write manifest to {}", manifest_path.display())
-            });
+            fs::write(dir.join("manifest.xml"), manifest.as_bytes())?;
         }
 
-        // Write resource.rc file
-        let mut rc_content = "#pragma code_page(65001)\r\n\r\n".to_string();
-
-        if let Some(icon_path) = &self.icon_path {
-            rc_content.push_str(&format!(
-                "1 ICON \"{}\"\r\n\r\n",
-                escape_string(&icon_path.display().to_string())
-            ));
+        // Write user-defined resource files
+        for (index, resource) in self.resources.iter().enumerate() {
+            fs::write(dir.join(format!("resource{index}.bin")), &resource.data)?;
         }
 
-        if self.manifest.is_some() {
-            rc_content.push_str(&format!(
-                "1 24 \"{}\"\r\n\r\n",
-                escape_string(
-                    &Path::new(&out_dir)
-                        .join("manifest.xml")
-                        .display()
-                        .to_string()
-                )
-            ));
-        }
+        fs::write(path, self.rc_content(dir))
+    }
 
-        let version = semver::Version::parse(
-            &env::var("CARGO_PKG_VERSION").expect("CARGO_PKG_VERSION not set"),
-        )
-        .expect("Can't parse version semver");
-        rc_content.push_str(&format!(
-            "1 VERSIONINFO\r\n\
-        FILEVERSION {maj},{min},{pat},0\r\n\
-        PRODUCTVERSION {maj},{min},{pat},0\r\n\
-        FILEOS 0x00040004\r\n\
-        FILETYPE 1\r\n\
-        FILESUBTYPE 0\r\n\
-        FILEFLAGSMASK 0x3F\r\n\
-        FILEFLAGS 0\r\n\
-        BEGIN\r\n\
-          BLOCK \"StringFileInfo\"\r\n\
-          BEGIN\r\n\
-            BLOCK \"040904B0\"\r\n\
-            BEGIN\r\n",
-            maj = version.major,
-            min = version.minor,
-            pat = version.patch
-        ));
-        for (k, v) in &self.version_fields {
-            rc_content.push_str(&format!(
-                "VALUE \"{k}\", \"{val}\"\n",
-                k = escape_string(k),
-                val = escape_string(v)
-            ));
-        }
-        rc_content.push_str(
-            "END\r\n\
-          END\r\n\
-          BLOCK \"VarFileInfo\"\r\n\
-          BEGIN\r\n\
-            VALUE \"Translation\", 0x0409, 0x04B0\r\n\
-          END\r\n\
-        END\n",
-        );
+    /// Compile the resources
+    pub fn compile(&self) -> Result<(), String> {
+        let out_dir = env::var("OUT_DIR").expect("OUT_DIR environment variable not set");
 
+        // Write resource.rc file
         let rc_path = Path::new(&out_dir).join("resource.rc");
-        fs::write(&rc_path, rc_content)
-            .unwrap_or_else(|_| panic!("failed to write resource.rc to {}", rc_path.display()));
+        self.write_resource_file(&rc_path)
+            .map_err(|e| format!("failed to write {}: {e}", rc_path.display()))?;
 
         // Compile resource.rc
         if env::var("RUSTC_LINKER").unwrap_or_default().contains("zig") {
@@ -243,6 +335,99 @@ impl WindowsResource {
             other => Err(format!("unsupported target environment: {other}")),
         }
     }
+
+    fn rc_content(&self, out_dir: &Path) -> String {
+        let mut rc_content = "#pragma code_page(65001)\r\n\r\n".to_string();
+
+        for (id, icon_path) in &self.icons {
+            rc_content.push_str(&format!(
+                "{id} ICON \"{}\"\r\n",
+                escape_string(&icon_path.display().to_string())
+            ));
+        }
+        if !self.icons.is_empty() {
+            rc_content.push_str("\r\n");
+        }
+
+        if self.manifest.is_some() {
+            rc_content.push_str(&format!(
+                "1 24 \"{}\"\r\n\r\n",
+                escape_string(&out_dir.join("manifest.xml").display().to_string())
+            ));
+        }
+
+        for (index, resource) in self.resources.iter().enumerate() {
+            rc_content.push_str(&format!(
+                "{} {} \"{}\"\r\n",
+                resource.id,
+                resource.r#type,
+                escape_string(
+                    &out_dir
+                        .join(format!("resource{index}.bin"))
+                        .display()
+                        .to_string()
+                )
+            ));
+        }
+        if !self.resources.is_empty() {
+            rc_content.push_str("\r\n");
+        }
+
+        if !self.strings.is_empty() {
+            rc_content.push_str("STRINGTABLE\r\nBEGIN\r\n");
+            for (id, value) in &self.strings {
+                rc_content.push_str(&format!("  {id}, \"{}\"\r\n", escape_string(value)));
+            }
+            rc_content.push_str("END\r\n\r\n");
+        }
+
+        rc_content.push_str(&format!(
+            "1 VERSIONINFO\r\n\
+            FILEVERSION {file_version}\r\n\
+            PRODUCTVERSION {product_version}\r\n\
+            FILEOS 0x00040004\r\n\
+            FILETYPE 1\r\n\
+            FILESUBTYPE 0\r\n\
+            FILEFLAGSMASK 0x3F\r\n\
+            FILEFLAGS 0\r\n\
+            BEGIN\r\n\
+            BLOCK \"StringFileInfo\"\r\n\
+            BEGIN\r\n",
+            file_version = rc_version(&self.file_version),
+            product_version = rc_version(&self.product_version),
+        ));
+        for translation in &self.translations {
+            rc_content.push_str(&format!(
+                "BLOCK \"{:04X}{:04X}\"\r\nBEGIN\r\n",
+                translation.language, translation.codepage
+            ));
+            let mut version_fields = self.version_fields.clone();
+            version_fields.extend(translation.version_fields.clone());
+            for (k, v) in &version_fields {
+                rc_content.push_str(&format!(
+                    "VALUE \"{k}\", \"{val}\"\r\n",
+                    k = escape_string(k),
+                    val = escape_string(v)
+                ));
+            }
+            rc_content.push_str("END\r\n");
+        }
+        rc_content.push_str(&format!(
+            "END\r\n\
+            BLOCK \"VarFileInfo\"\r\n\
+            BEGIN\r\n\
+            VALUE \"Translation\", {}\r\n\
+            END\r\n\
+            END\r\n",
+            self.translations
+                .iter()
+                .map(|t| format!("0x{:04X}, 0x{:04X}", t.language, t.codepage))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        rc_content.push_str(&self.append_rc_content);
+        rc_content
+    }
 }
 
 fn find_rc_exe() -> Option<PathBuf> {
@@ -285,6 +470,13 @@ fn find_rc_exe() -> Option<PathBuf> {
     best_path
 }
 
+fn rc_version(version: &MicrosoftVersion) -> String {
+    format!(
+        "{},{},{},{}",
+        version.major, version.minor, version.build, version.revision
+    )
+}
+
 fn escape_string(string: &str) -> String {
     let mut escaped = String::new();
     for chr in string.chars() {
@@ -316,4 +508,57 @@ mod test {
             r"C:\\Program Files\\Foobar"
         );
     }
+
+    #[test]
+    fn rc_icons_resources_and_strings() {
+        let mut res = WindowsResource::new();
+        res.set_icon("icon.ico")
+            .set_icon_with_id("small.ico", "2")
+            .set_icon_with_id("main.ico", "1")
+            .set_resource("DATA", "RCDATA", b"hello".to_vec())
+            .set_string(1, "Hello")
+            .set_string(2, "World \"quoted\"")
+            .append_rc_content("#define CUSTOM 1");
+        let rc = res.rc_content(Path::new("out"));
+
+        assert!(rc.contains("1 ICON \"main.ico\"\r\n2 ICON \"small.ico\"\r\n"));
+        assert!(!rc.contains("icon.ico"));
+        assert!(rc.contains(&format!(
+            "DATA RCDATA \"{}\"\r\n",
+            escape_string(&Path::new("out").join("resource0.bin").display().to_string())
+        )));
+        assert!(rc.contains(
+            "STRINGTABLE\r\nBEGIN\r\n  1, \"Hello\"\r\n  2, \"World \"\"quoted\"\"\"\r\nEND\r\n"
+        ));
+        assert!(rc.ends_with("#define CUSTOM 1\r\n"));
+    }
+
+    #[test]
+    fn rc_version_info() {
+        let mut res = WindowsResource::new();
+        res.set("CompanyName", "Bassie")
+            .set_version_info(VersionInfo::FileVersion, 0x0001_0002_0003_0004)
+            .set_version_info(VersionInfo::ProductVersion, 0x0002_0000_0000_0000)
+            .set_language(0x0413)
+            .add_translation(0x0409, 0x04E4)
+            .set_for_language(0x0409, "FileDescription", "English description");
+        let rc = res.rc_content(Path::new("out"));
+
+        assert!(rc.contains("FILEVERSION 1,2,3,4\r\n"));
+        assert!(rc.contains("PRODUCTVERSION 2,0,0,0\r\n"));
+        assert!(rc.contains("BLOCK \"041304B0\"\r\n"));
+        assert!(rc.contains("BLOCK \"040904E4\"\r\n"));
+        assert!(rc.contains("VALUE \"Translation\", 0x0413, 0x04B0, 0x0409, 0x04E4\r\n"));
+        assert_eq!(rc.matches("VALUE \"CompanyName\", \"Bassie\"").count(), 2);
+        assert_eq!(
+            rc.matches("VALUE \"FileDescription\", \"winresource\"")
+                .count(),
+            1
+        );
+        assert_eq!(
+            rc.matches("VALUE \"FileDescription\", \"English description\"")
+                .count(),
+            1
+        );
+    }
 }
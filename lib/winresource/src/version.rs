@@ -54,6 +54,17 @@ impl MicrosoftVersion {
     }
 }
 
+impl From<u64> for MicrosoftVersion {
+    fn from(value: u64) -> Self {
+        Self::new(
+            (value >> 48) as u16,
+            (value >> 32) as u16,
+            (value >> 16) as u16,
+            value as u16,
+        )
+    }
+}
+
 impl Display for MicrosoftVersion {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
@@ -120,6 +131,12 @@ mod test {
         assert!(MicrosoftVersion::parse("1..3.4").is_err());
     }
 
+    #[test]
+    fn microsoft_version_from_u64() {
+        let v = MicrosoftVersion::from(0x0001_0002_0003_0004);
+        assert_eq!(v, MicrosoftVersion::new(1, 2, 3, 4));
+    }
+
     #[test]
     fn microsoft_display_format() {
         let v = MicrosoftVersion::new(10, 0, 2, 5);
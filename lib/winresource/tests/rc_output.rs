@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) 2026 Bastiaan van der Plaat
+ *
+ * SPDX-License-Identifier: MIT
+ */
+
+//! Generated .rc output tests
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use winresource::{VersionInfo, WindowsResource};
+
+fn write_rc(res: &WindowsResource) -> (PathBuf, String) {
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "{}_rc_output_{}_{}",
+        env!("CARGO_PKG_NAME"),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let rc_path = dir.join("resource.rc");
+    res.write_resource_file(&rc_path)
+        .expect("Can't write resource.rc");
+    let rc = fs::read_to_string(&rc_path).expect("Can't read resource.rc");
+    (dir, rc)
+}
+
+#[test]
+fn test_rc_defaults() {
+    let mut res = WindowsResource::new();
+    res.set_version_info(VersionInfo::FileVersion, 0x0001_0002_0003_0000)
+        .set_version_info(VersionInfo::ProductVersion, 0x0001_0002_0003_0000);
+    let (dir, rc) = write_rc(&res);
+
+    assert!(rc.starts_with("#pragma code_page(65001)\r\n\r\n1 VERSIONINFO\r\n"));
+    assert!(rc.contains("FILEVERSION 1,2,3,0\r\n"));
+    assert!(rc.contains("PRODUCTVERSION 1,2,3,0\r\n"));
+    assert!(rc.contains("BLOCK \"040904B0\"\r\n"));
+    assert!(rc.contains("VALUE \"Translation\", 0x0409, 0x04B0\r\n"));
+    assert!(rc.contains(&format!(
+        "VALUE \"ProductName\", \"{}\"\r\n",
+        env!("CARGO_PKG_NAME")
+    )));
+    assert!(!rc.contains("ICON"));
+    assert!(!rc.contains("STRINGTABLE"));
+    assert_eq!(rc.matches("BEGIN").count(), rc.matches("END").count());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_rc_icons_and_manifest() {
+    let mut res = WindowsResource::new();
+    res.set_icon(r"C:\icons\app.ico")
+        .set_icon_with_id("small.ico", "2")
+        .set_manifest("<assembly/>");
+    let (dir, rc) = write_rc(&res);
+
+    assert!(rc.contains("1 ICON \"C:\\\\icons\\\\app.ico\"\r\n2 ICON \"small.ico\"\r\n"));
+    assert_eq!(
+        fs::read_to_string(dir.join("manifest.xml")).unwrap(),
+        "<assembly/>"
+    );
+    assert!(rc.contains("1 24 \""));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_rc_resources_and_strings() {
+    let mut res = WindowsResource::new();
+    res.set_resource("101", "RCDATA", b"\x00\x01binary".to_vec())
+        .set_string(2, "Second")
+        .set_string(1, "First\tline")
+        .append_rc_content("#define EXTRA 1");
+    let (dir, rc) = write_rc(&res);
+
+    assert_eq!(
+        fs::read(dir.join("resource0.bin")).unwrap(),
+        b"\x00\x01binary"
+    );
+    assert!(rc.contains("101 RCDATA \""));
+    assert!(
+        rc.contains("STRINGTABLE\r\nBEGIN\r\n  1, \"First\\tline\"\r\n  2, \"Second\"\r\nEND\r\n")
+    );
+    assert!(rc.ends_with("#define EXTRA 1\r\n"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_rc_translations() {
+    let mut res = WindowsResource::new();
+    res.set("FileDescription", "App")
+        .set_language(0x0413)
+        .set_for_language(0x0407, "FileDescription", "Anwendung");
+    let (dir, rc) = write_rc(&res);
+
+    assert!(rc.contains("BLOCK \"041304B0\"\r\nBEGIN\r\n"));
+    assert!(rc.contains("BLOCK \"040704B0\"\r\nBEGIN\r\n"));
+    assert!(rc.contains("VALUE \"FileDescription\", \"App\"\r\n"));
+    assert!(rc.contains("VALUE \"FileDescription\", \"Anwendung\"\r\n"));
+    assert!(rc.contains("VALUE \"Translation\", 0x0413, 0x04B0, 0x0407, 0x04B0\r\n"));
+    fs::remove_dir_all(dir).unwrap();
+}